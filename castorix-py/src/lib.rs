@@ -0,0 +1,194 @@
+//! Python bindings for the castorix hub client
+//!
+//! Exposes read queries (profiles, casts, follows) and cast signing with
+//! signers stored by `castorix signers`. Only the library is linked: the crate
+//! is built with `default-features = false`, so no CLI, API server or MCP code
+//! ends up in the extension module.
+//!
+//! Hub calls run on a shared Tokio runtime with the GIL released, so they can
+//! be driven from notebook threads.
+
+use std::future::Future;
+use std::sync::OnceLock;
+
+use castorix::core::client::cast::build_cast_message;
+use castorix::core::client::offline::message_to_json;
+use castorix::core::client::profile::current_profile;
+use castorix::core::client::FarcasterClient;
+use castorix::core::client::Signing;
+use castorix::core::crypto::encrypted_storage::EncryptedEd25519KeyManager;
+use ed25519_dalek::SigningKey;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use tokio::runtime::Runtime;
+
+create_exception!(castorix, CastorixError, PyException);
+
+/// Default number of items returned by list queries
+const DEFAULT_LIMIT: u32 = 100;
+
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| Runtime::new().expect("Failed to start Tokio runtime"))
+}
+
+/// Run a hub call to completion with the GIL released
+fn block_on<F, T>(py: Python<'_>, future: F) -> PyResult<T>
+where
+    F: Future<Output = anyhow::Result<T>> + Send,
+    T: Send,
+{
+    py.allow_threads(|| runtime().block_on(future))
+        .map_err(|e| CastorixError::new_err(format!("{e:#}")))
+}
+
+/// Convert a JSON value into the equivalent Python object
+fn to_python(py: Python<'_>, value: &serde_json::Value) -> PyResult<PyObject> {
+    let json = py.import("json")?;
+    Ok(json.call_method1("loads", (value.to_string(),))?.unbind())
+}
+
+/// Read-only and signing access to a Farcaster hub
+#[pyclass(module = "castorix")]
+struct Client {
+    inner: FarcasterClient<Signing>,
+}
+
+#[pymethods]
+impl Client {
+    /// Create a client for `hub_url`, or the configured hub (FARCASTER_HUB_URL)
+    #[new]
+    #[pyo3(signature = (hub_url=None))]
+    fn new(hub_url: Option<String>) -> Self {
+        let hub_url = hub_url.unwrap_or_else(|| {
+            castorix::consts::get_config()
+                .farcaster_hub_url()
+                .to_string()
+        });
+        Self {
+            inner: FarcasterClient::new(hub_url.trim_end_matches('/').to_string(), None),
+        }
+    }
+
+    /// The hub URL this client talks to
+    #[getter]
+    fn hub_url(&self) -> &str {
+        self.inner.hub_url()
+    }
+
+    /// Current profile fields of a FID, e.g. `{"username": "dwr", "bio": ...}`
+    fn profile(&self, py: Python<'_>, fid: u64) -> PyResult<PyObject> {
+        let messages = block_on(py, self.inner.get_user_profile(fid))?;
+        let profile = current_profile(&messages);
+        to_python(py, &serde_json::to_value(profile).unwrap_or_default())
+    }
+
+    /// Recent casts of a FID as hub JSON messages
+    #[pyo3(signature = (fid, limit=DEFAULT_LIMIT))]
+    fn casts(&self, py: Python<'_>, fid: u64, limit: u32) -> PyResult<PyObject> {
+        let casts = block_on(py, self.inner.get_casts_by_fid(fid, limit))?;
+        to_python(py, &serde_json::Value::Array(casts))
+    }
+
+    /// Follow links pointing at a FID; `limit=0` fetches all of them
+    #[pyo3(signature = (fid, limit=DEFAULT_LIMIT))]
+    fn followers(&self, py: Python<'_>, fid: u64, limit: u32) -> PyResult<PyObject> {
+        let followers = block_on(py, self.inner.get_followers(fid, limit))?;
+        to_python(py, &serde_json::Value::Array(followers))
+    }
+
+    /// Follow links created by a FID; `limit=0` fetches all of them
+    #[pyo3(signature = (fid, limit=DEFAULT_LIMIT))]
+    fn following(&self, py: Python<'_>, fid: u64, limit: u32) -> PyResult<PyObject> {
+        let following = block_on(py, self.inner.get_following(fid, limit))?;
+        to_python(py, &serde_json::Value::Array(following))
+    }
+
+    /// Custody address of a FID
+    fn custody_address(&self, py: Python<'_>, fid: u64) -> PyResult<String> {
+        block_on(py, self.inner.get_custody_address(fid))
+    }
+
+    /// Sign and publish a text cast, returning its hash
+    fn cast(&self, py: Python<'_>, signer: &Signer, text: &str) -> PyResult<String> {
+        let message = build_cast_message(signer.fid, text, &signer.key)
+            .map_err(|e| CastorixError::new_err(e.to_string()))?;
+        let response = block_on(py, self.inner.submit_message(&message))?;
+        if !response.success {
+            return Err(CastorixError::new_err(format!(
+                "Hub rejected the cast: {}",
+                response.message.unwrap_or_default()
+            )));
+        }
+        Ok(format!("0x{}", hex::encode(message.get_hash())))
+    }
+}
+
+/// An Ed25519 signer of a FID
+#[pyclass(module = "castorix")]
+struct Signer {
+    #[pyo3(get)]
+    fid: u64,
+    key: SigningKey,
+}
+
+#[pymethods]
+impl Signer {
+    /// Decrypt the signer stored for `fid` by `castorix signers`
+    #[staticmethod]
+    #[pyo3(signature = (fid, password, keys_file=None))]
+    fn load(fid: u64, password: &str, keys_file: Option<String>) -> PyResult<Self> {
+        let keys_file = match keys_file {
+            Some(path) => path,
+            None => EncryptedEd25519KeyManager::default_keys_file()
+                .map_err(|e| CastorixError::new_err(e.to_string()))?,
+        };
+        let key = EncryptedEd25519KeyManager::load_from_file(&keys_file)
+            .and_then(|manager| manager.get_signing_key(fid, password))
+            .map_err(|e| CastorixError::new_err(e.to_string()))?;
+        Ok(Self { fid, key })
+    }
+
+    /// Use a hex encoded 32-byte Ed25519 private key directly
+    #[staticmethod]
+    fn from_hex(fid: u64, private_key: &str) -> PyResult<Self> {
+        let bytes: [u8; 32] = hex::decode(private_key.trim_start_matches("0x"))
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                CastorixError::new_err("Ed25519 private key must be 32 hex encoded bytes")
+            })?;
+        Ok(Self {
+            fid,
+            key: SigningKey::from_bytes(&bytes),
+        })
+    }
+
+    /// Hex encoded public key, as registered in the KeyRegistry
+    #[getter]
+    fn public_key(&self) -> String {
+        format!("0x{}", hex::encode(self.key.verifying_key().to_bytes()))
+    }
+
+    /// Sign a text cast without submitting it, returning the hub JSON message
+    fn sign_cast(&self, py: Python<'_>, text: &str) -> PyResult<PyObject> {
+        let message = build_cast_message(self.fid, text, &self.key)
+            .and_then(|message| message_to_json(&message))
+            .map_err(|e| CastorixError::new_err(e.to_string()))?;
+        to_python(py, &message)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Signer(fid={}, public_key={})", self.fid, self.public_key())
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "castorix")]
+fn castorix_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Client>()?;
+    m.add_class::<Signer>()?;
+    m.add("CastorixError", m.py().get_type::<CastorixError>())?;
+    Ok(())
+}
@@ -0,0 +1,21 @@
+//! Build and verify an ENS username proof
+
+use anyhow::Result;
+use castorix::core::crypto::key_manager::KeyManager;
+use castorix::ens_proof::EnsProof;
+
+/// Create a proof for a name the wallet owns, check it and print it as JSON
+pub async fn run(domain: &str, fid: u64, private_key: &str, rpc_url: &str) -> Result<()> {
+    let key_manager = KeyManager::from_private_key(private_key)?;
+    println!("👛 Wallet: {:?}", key_manager.address());
+
+    let ens_proof = EnsProof::new(key_manager, rpc_url.to_string());
+    let proof = ens_proof.create_ens_proof(domain, fid).await?;
+    if !ens_proof.verify_proof(&proof).await? {
+        anyhow::bail!("Freshly built proof failed verification");
+    }
+
+    println!("✅ Proof for {domain} -> FID {fid} verified");
+    println!("{}", ens_proof.serialize_proof(&proof)?);
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! Castorix cookbook
+//!
+//! Scripted end-to-end scenarios built only on the public `castorix` library
+//! API. Each subcommand is a small, readable recipe; the offline parts are
+//! also run as tests (`cargo test --example castorix-cookbook`), so the
+//! recipes double as integration tests of the library surface.
+//!
+//! ```text
+//! # Register a FID on a local Anvil fork of Optimism (see `make start-nodes`)
+//! cargo run --example castorix-cookbook -- register-fid
+//!
+//! # Sign a cast with an Ed25519 signer key and submit it to a hub
+//! cargo run --example castorix-cookbook -- publish-cast --fid 12345 \
+//!     --signer-key <hex> --text "gm"
+//!
+//! # Build and verify a username proof for an ENS name
+//! cargo run --example castorix-cookbook -- build-proof --domain alice.eth \
+//!     --fid 12345 --private-key <hex>
+//! ```
+
+mod build_proof;
+mod publish_cast;
+mod register_fid;
+
+use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
+
+/// First pre-funded account of a default Anvil node
+const ANVIL_PRIVATE_KEY: &str =
+    "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Castorix cookbook - end-to-end library scenarios
+#[derive(Parser)]
+#[command(name = "castorix-cookbook")]
+struct Cookbook {
+    #[command(subcommand)]
+    scenario: Scenario,
+}
+
+#[derive(Subcommand)]
+enum Scenario {
+    /// 🆔 Register a FID on a local Anvil node
+    ///
+    /// Uses the Optimism mainnet contract addresses, so the node must be a
+    /// fork of Optimism. Prints the existing FID if the wallet already has one.
+    RegisterFid {
+        /// RPC URL of the Anvil node
+        #[arg(long, default_value = "http://127.0.0.1:8545")]
+        rpc_url: String,
+        /// Private key of the registering wallet
+        #[arg(long, default_value = ANVIL_PRIVATE_KEY)]
+        private_key: String,
+        /// Recovery address (defaults to the wallet address)
+        #[arg(long)]
+        recovery: Option<String>,
+    },
+    /// 📝 Sign a cast with an Ed25519 signer and submit it to a hub
+    PublishCast {
+        /// FID the cast is published for
+        #[arg(long)]
+        fid: u64,
+        /// Hex encoded Ed25519 private key registered as a signer of the FID
+        #[arg(long)]
+        signer_key: String,
+        /// Cast text
+        #[arg(long)]
+        text: String,
+        /// Hub HTTP API URL
+        #[arg(long, default_value = "http://127.0.0.1:3381")]
+        hub_url: String,
+        /// Print the signed message instead of submitting it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 🌐 Build and verify an ENS username proof
+    BuildProof {
+        /// ENS name resolving to the signing wallet
+        #[arg(long)]
+        domain: String,
+        /// FID the proof links the name to
+        #[arg(long)]
+        fid: u64,
+        /// Private key of the wallet the name resolves to
+        #[arg(long)]
+        private_key: String,
+        /// Ethereum mainnet RPC URL for ENS resolution (defaults to ETH_RPC_URL)
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    match Cookbook::parse().scenario {
+        Scenario::RegisterFid {
+            rpc_url,
+            private_key,
+            recovery,
+        } => register_fid::run(&rpc_url, &private_key, recovery.as_deref()).await,
+        Scenario::PublishCast {
+            fid,
+            signer_key,
+            text,
+            hub_url,
+            dry_run,
+        } => publish_cast::run(&hub_url, fid, &signer_key, &text, dry_run).await,
+        Scenario::BuildProof {
+            domain,
+            fid,
+            private_key,
+            rpc_url,
+        } => {
+            let rpc_url = rpc_url
+                .or_else(|| std::env::var("ETH_RPC_URL").ok())
+                .ok_or_else(|| anyhow::anyhow!("Pass --rpc-url or set ETH_RPC_URL"))?;
+            build_proof::run(&domain, fid, &private_key, &rpc_url).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use castorix::core::crypto::key_manager::KeyManager;
+    use castorix::core::protocol::MessageData;
+    use castorix::core::protocol::UserNameProof;
+    use castorix::ens_proof::EnsProof;
+    use ed25519_dalek::Signature;
+    use ed25519_dalek::Verifier;
+    use protobuf::Message as ProtobufMessage;
+
+    use super::*;
+
+    #[test]
+    fn test_publish_cast_builds_verifiable_message() {
+        let signing_key = publish_cast::parse_signing_key(&"11".repeat(32)).unwrap();
+        let message = publish_cast::build_cast(42, "gm", 1000, &signing_key).unwrap();
+
+        let signature = Signature::from_slice(message.get_signature()).unwrap();
+        assert!(signing_key
+            .verifying_key()
+            .verify(message.get_hash(), &signature)
+            .is_ok());
+
+        let data = MessageData::parse_from_bytes(message.get_data_bytes()).unwrap();
+        assert_eq!(data.get_fid(), 42);
+        assert_eq!(data.get_cast_add_body().get_text(), "gm");
+        assert_eq!(
+            message.get_hash(),
+            &blake3::hash(message.get_data_bytes()).as_bytes()[..20]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_proof_signature_round_trip() {
+        let key_manager = KeyManager::from_private_key(ANVIL_PRIVATE_KEY).unwrap();
+        let owner = key_manager.address();
+        let ens_proof = EnsProof::new(key_manager, "http://127.0.0.1:8545".to_string());
+
+        let mut proof = UserNameProof::new();
+        proof.set_name(b"alice.eth".to_vec());
+        proof.set_owner(owner.as_bytes().to_vec());
+        proof.set_fid(42);
+        proof.set_timestamp(1_700_000_000);
+        let message = ens_proof.create_proof_message(&proof).unwrap();
+        let signature = ens_proof
+            .key_manager()
+            .sign_message(&message)
+            .await
+            .unwrap();
+        proof.set_signature(signature.to_vec());
+        assert!(ens_proof.verify_proof(&proof).await.unwrap());
+
+        // A tampered proof no longer recovers to the owner; verify_proof would
+        // go on to the ERC-1271 fallback, which needs a node, so check offline
+        proof.set_fid(43);
+        let tampered = ens_proof.create_proof_message(&proof).unwrap();
+        let recovered = ethers::types::Signature::try_from(proof.get_signature())
+            .unwrap()
+            .recover(tampered.as_str())
+            .unwrap();
+        assert_ne!(recovered, owner);
+    }
+}
@@ -0,0 +1,93 @@
+//! Sign a CastAdd message with an Ed25519 signer and submit it
+
+use anyhow::Result;
+use castorix::core::client::offline::message_to_json;
+use castorix::core::client::FarcasterClient;
+use castorix::core::protocol::message::CastAddBody;
+use castorix::core::protocol::message::FarcasterNetwork;
+use castorix::core::protocol::message::HashScheme;
+use castorix::core::protocol::message::SignatureScheme;
+use castorix::core::protocol::Message;
+use castorix::core::protocol::MessageData;
+use castorix::core::protocol::MessageType;
+use ed25519_dalek::Signer;
+use ed25519_dalek::SigningKey;
+use protobuf::Message as ProtobufMessage;
+
+/// Farcaster epoch (January 1, 2021 UTC) in unix seconds
+const FARCASTER_EPOCH: u64 = 1609459200;
+
+/// Build a signed cast and submit it, or print it with `dry_run`
+pub async fn run(
+    hub_url: &str,
+    fid: u64,
+    signer_key: &str,
+    text: &str,
+    dry_run: bool,
+) -> Result<()> {
+    let signing_key = parse_signing_key(signer_key)?;
+    let timestamp = (chrono::Utc::now().timestamp() as u64 - FARCASTER_EPOCH) as u32;
+    let message = build_cast(fid, text, timestamp, &signing_key)?;
+
+    println!("📝 Cast 0x{}", hex::encode(message.get_hash()));
+    if dry_run {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&message_to_json(&message)?)?
+        );
+        return Ok(());
+    }
+
+    let client = FarcasterClient::new(hub_url.to_string(), None);
+    let response = client.submit_message(&message).await?;
+    if response.success {
+        println!("✅ Submitted to {hub_url}");
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "Hub rejected the cast: {}",
+            response.message.unwrap_or_default()
+        )
+    }
+}
+
+/// Parse a hex encoded 32-byte Ed25519 private key
+pub fn parse_signing_key(hex_key: &str) -> Result<SigningKey> {
+    let bytes: [u8; 32] = hex::decode(hex_key.trim_start_matches("0x"))?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Ed25519 private key must be 32 bytes"))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+/// Build a CastAdd message signed by an Ed25519 signer
+///
+/// The hash is the first 20 bytes of the BLAKE3 digest of the encoded data and
+/// the signature covers that hash, as the hubs expect.
+pub fn build_cast(
+    fid: u64,
+    text: &str,
+    timestamp: u32,
+    signing_key: &SigningKey,
+) -> Result<Message> {
+    let mut body = CastAddBody::new();
+    body.set_text(text.to_string());
+
+    let mut data = MessageData::new();
+    data.set_field_type(MessageType::MESSAGE_TYPE_CAST_ADD);
+    data.set_fid(fid);
+    data.set_timestamp(timestamp);
+    data.set_network(FarcasterNetwork::FARCASTER_NETWORK_MAINNET);
+    data.set_cast_add_body(body);
+
+    let data_bytes = data.write_to_bytes()?;
+    let hash = blake3::hash(&data_bytes).as_bytes()[..20].to_vec();
+
+    let mut message = Message::new();
+    message.set_hash_scheme(HashScheme::HASH_SCHEME_BLAKE3);
+    message.set_signature_scheme(SignatureScheme::SIGNATURE_SCHEME_ED25519);
+    message.set_signature(signing_key.sign(&hash).to_bytes().to_vec());
+    message.set_signer(signing_key.verifying_key().to_bytes().to_vec());
+    message.set_hash(hash);
+    message.set_data_bytes(data_bytes);
+    Ok(message)
+}
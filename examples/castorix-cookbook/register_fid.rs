@@ -0,0 +1,55 @@
+//! Register a FID through the IdGateway
+
+use std::str::FromStr;
+
+use anyhow::Result;
+use castorix::farcaster::contracts::types::ContractAddresses;
+use castorix::farcaster::contracts::types::ContractResult;
+use castorix::farcaster::contracts::FarcasterContractClient;
+use ethers::signers::LocalWallet;
+use ethers::signers::Signer;
+use ethers::types::Address;
+
+/// Register a FID for a wallet, or report the one it already owns
+pub async fn run(rpc_url: &str, private_key: &str, recovery: Option<&str>) -> Result<()> {
+    let wallet = LocalWallet::from_str(private_key)?;
+    let address = wallet.address();
+    let recovery = match recovery {
+        Some(recovery) => Address::from_str(recovery)?,
+        None => address,
+    };
+
+    let client = FarcasterContractClient::new_with_wallet(
+        rpc_url.to_string(),
+        ContractAddresses::default(),
+        wallet,
+    )?;
+
+    println!("👛 Wallet: {address:?}");
+    if let Some(fid) = client.address_has_fid(address).await? {
+        println!("✅ Wallet already owns FID {fid}");
+        return print_fid_info(&client, fid).await;
+    }
+
+    let price = client.get_registration_price().await?;
+    println!(
+        "💰 Registration price: {} ETH",
+        ethers::utils::format_ether(price)
+    );
+
+    match client.register_fid(recovery).await? {
+        ContractResult::Success((fid, _)) => {
+            println!("🎉 Registered FID {fid}");
+            print_fid_info(&client, fid).await
+        }
+        ContractResult::Error(e) => anyhow::bail!("Registration failed: {e}"),
+    }
+}
+
+async fn print_fid_info(client: &FarcasterContractClient, fid: u64) -> Result<()> {
+    let info = client.get_fid_info(fid).await?;
+    println!("   Custody:  {:?}", info.custody);
+    println!("   Recovery: {:?}", info.recovery);
+    println!("   Active keys: {}", info.active_keys);
+    Ok(())
+}
@@ -0,0 +1,321 @@
+//! Load testing for a running REST API server
+//!
+//! [`run`] drives a fixed number of concurrent workers against an
+//! [`ApiServer`](crate::api::ApiServer) and records the latency and outcome of
+//! every request per route, so that caching and connection pooling can be
+//! checked under load. Routes are requested in a weighted round-robin mix,
+//! with `{fid}` in a route replaced by the FIDs given in turn.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Routes requested when none are given
+pub const DEFAULT_ROUTES: &[&str] = &[
+    "/v1/hub/users/{fid}",
+    "/v1/hub/users/{fid}/profile",
+    "/v1/hub/users/{fid}/stats",
+    "/v1/hub/users/{fid}/casts",
+];
+
+/// A route in the request mix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchRoute {
+    /// Path template, e.g. `/api/hub/users/{fid}`
+    pub path: String,
+    /// Share of requests relative to the other routes
+    pub weight: u32,
+}
+
+impl BenchRoute {
+    /// Parse `PATH[:WEIGHT]`, e.g. `/api/hub/users/{fid}:3`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (path, weight) = match spec.rsplit_once(':') {
+            Some((path, weight)) if weight.chars().all(|c| c.is_ascii_digit()) => {
+                let weight = weight
+                    .parse()
+                    .with_context(|| format!("Invalid route weight in '{spec}'"))?;
+                (path, weight)
+            }
+            _ => (spec, 1),
+        };
+        if !path.starts_with('/') {
+            anyhow::bail!("Route '{spec}' must start with '/'");
+        }
+        if weight == 0 {
+            anyhow::bail!("Route '{spec}' has a weight of 0");
+        }
+        Ok(Self {
+            path: path.to_string(),
+            weight,
+        })
+    }
+}
+
+/// How workers are started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadPattern {
+    /// All workers start at once
+    Steady,
+    /// Workers start one after another, spread evenly over the ramp-up time
+    Ramp,
+}
+
+/// Load test settings
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    /// Base URL of the API server, e.g. `http://127.0.0.1:3000`
+    pub base_url: String,
+    pub routes: Vec<BenchRoute>,
+    /// FIDs substituted for `{fid}`, in turn
+    pub fids: Vec<u64>,
+    pub concurrency: usize,
+    /// Total requests to send; the run stops at whichever of this and `duration` comes first
+    pub requests: Option<u64>,
+    pub duration: Option<Duration>,
+    /// Overall request rate cap in requests per second
+    pub rate: Option<f64>,
+    pub pattern: LoadPattern,
+    pub ramp_up: Duration,
+    /// Per-request timeout; timed out requests count as errors
+    pub timeout: Duration,
+}
+
+/// Latency and error figures of one route
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteReport {
+    pub route: String,
+    pub requests: u64,
+    pub errors: u64,
+    /// Errors as a percentage of requests
+    pub error_rate: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    /// Responses per HTTP status; transport errors are counted under `error`
+    pub statuses: HashMap<String, u64>,
+}
+
+/// Result of a load test run
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub elapsed_secs: f64,
+    pub requests: u64,
+    pub errors: u64,
+    pub requests_per_sec: f64,
+    pub routes: Vec<RouteReport>,
+}
+
+/// Samples collected by one worker for one route
+#[derive(Debug, Default)]
+struct RouteSamples {
+    latencies: Vec<Duration>,
+    errors: u64,
+    statuses: HashMap<String, u64>,
+}
+
+impl RouteSamples {
+    fn merge(&mut self, other: RouteSamples) {
+        self.latencies.extend(other.latencies);
+        self.errors += other.errors;
+        for (status, count) in other.statuses {
+            *self.statuses.entry(status).or_default() += count;
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted latencies
+///
+/// # Arguments
+/// * `sorted` - Latencies in ascending order
+/// * `p` - Percentile between 0 and 100
+pub fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Expand weighted routes into the order requests cycle through
+fn route_schedule(routes: &[BenchRoute]) -> Vec<usize> {
+    routes
+        .iter()
+        .enumerate()
+        .flat_map(|(i, route)| std::iter::repeat_n(i, route.weight as usize))
+        .collect()
+}
+
+/// Run a load test against an API server
+///
+/// # Arguments
+/// * `config` - Routes, concurrency and stop conditions
+///
+/// # Returns
+/// * `Result<BenchReport>` - Per-route latencies and error rates, or an error if the
+///   server cannot be reached at all
+pub async fn run(config: BenchConfig) -> Result<BenchReport> {
+    if config.routes.is_empty() {
+        anyhow::bail!("No routes to request");
+    }
+    if config.fids.is_empty() {
+        anyhow::bail!("At least one FID is required");
+    }
+    if config.requests.is_none() && config.duration.is_none() {
+        anyhow::bail!("Either a request count or a duration is required");
+    }
+
+    let base_url = config.base_url.trim_end_matches('/').to_string();
+    let client = crate::core::utils::http::builder()?
+        .timeout(config.timeout)
+        .pool_max_idle_per_host(config.concurrency)
+        .build()?;
+    client
+        .get(format!("{base_url}/health"))
+        .send()
+        .await
+        .with_context(|| format!("API server not reachable at {base_url}"))?;
+
+    let config = Arc::new(config);
+    let schedule = Arc::new(route_schedule(&config.routes));
+    let issued = Arc::new(AtomicU64::new(0));
+    let concurrency = config.concurrency.max(1);
+    let started = Instant::now();
+    let deadline = config.duration.map(|d| started + d);
+
+    let mut workers = Vec::with_capacity(concurrency);
+    for worker in 0..concurrency {
+        let client = client.clone();
+        let base_url = base_url.clone();
+        let config = config.clone();
+        let schedule = schedule.clone();
+        let issued = issued.clone();
+        let start_delay = match config.pattern {
+            LoadPattern::Steady => Duration::ZERO,
+            LoadPattern::Ramp => config.ramp_up.mul_f64(worker as f64 / concurrency as f64),
+        };
+
+        workers.push(tokio::spawn(async move {
+            let mut samples: HashMap<usize, RouteSamples> = HashMap::new();
+            tokio::time::sleep(start_delay).await;
+            loop {
+                if deadline.is_some_and(|d| Instant::now() >= d) {
+                    break;
+                }
+                let n = issued.fetch_add(1, Ordering::Relaxed);
+                if config.requests.is_some_and(|limit| n >= limit) {
+                    break;
+                }
+                if let Some(rate) = config.rate {
+                    let due = started + Duration::from_secs_f64(n as f64 / rate);
+                    tokio::time::sleep_until(due.into()).await;
+                }
+
+                let route = schedule[n as usize % schedule.len()];
+                let fid = config.fids[(n / schedule.len() as u64) as usize % config.fids.len()];
+                let path = config.routes[route].path.replace("{fid}", &fid.to_string());
+
+                let sent = Instant::now();
+                let outcome = client.get(format!("{base_url}{path}")).send().await;
+                let latency = sent.elapsed();
+
+                let entry = samples.entry(route).or_default();
+                entry.latencies.push(latency);
+                let status = match outcome {
+                    Ok(response) => {
+                        let status = response.status();
+                        // Read the body so the latency covers the full response
+                        let _ = response.bytes().await;
+                        if !status.is_success() {
+                            entry.errors += 1;
+                        }
+                        status.as_u16().to_string()
+                    }
+                    Err(_) => {
+                        entry.errors += 1;
+                        "error".to_string()
+                    }
+                };
+                *entry.statuses.entry(status).or_default() += 1;
+            }
+            samples
+        }));
+    }
+
+    let mut merged: HashMap<usize, RouteSamples> = HashMap::new();
+    for worker in workers {
+        for (route, samples) in worker.await? {
+            merged.entry(route).or_default().merge(samples);
+        }
+    }
+    let elapsed = started.elapsed();
+
+    let mut routes = Vec::new();
+    for (i, route) in config.routes.iter().enumerate() {
+        let Some(mut samples) = merged.remove(&i) else {
+            continue;
+        };
+        samples.latencies.sort();
+        let requests = samples.latencies.len() as u64;
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        routes.push(RouteReport {
+            route: route.path.clone(),
+            requests,
+            errors: samples.errors,
+            error_rate: samples.errors as f64 * 100.0 / requests.max(1) as f64,
+            p50_ms: ms(percentile(&samples.latencies, 50.0)),
+            p95_ms: ms(percentile(&samples.latencies, 95.0)),
+            p99_ms: ms(percentile(&samples.latencies, 99.0)),
+            max_ms: ms(samples.latencies.last().copied().unwrap_or_default()),
+            statuses: samples.statuses,
+        });
+    }
+
+    let requests = routes.iter().map(|r| r.requests).sum();
+    Ok(BenchReport {
+        elapsed_secs: elapsed.as_secs_f64(),
+        requests,
+        errors: routes.iter().map(|r| r.errors).sum(),
+        requests_per_sec: requests as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        routes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let latencies: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&latencies, 50.0), Duration::from_millis(50));
+        assert_eq!(percentile(&latencies, 95.0), Duration::from_millis(95));
+        assert_eq!(percentile(&latencies, 100.0), Duration::from_millis(100));
+        assert_eq!(percentile(&latencies[..1], 95.0), Duration::from_millis(1));
+        assert_eq!(percentile(&[], 50.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_parse_route() {
+        let route = BenchRoute::parse("/api/hub/users/{fid}:3").unwrap();
+        assert_eq!(route.path, "/api/hub/users/{fid}");
+        assert_eq!(route.weight, 3);
+        assert_eq!(BenchRoute::parse("/health").unwrap().weight, 1);
+        assert!(BenchRoute::parse("health").is_err());
+        assert!(BenchRoute::parse("/health:0").is_err());
+
+        let schedule = route_schedule(&[
+            BenchRoute::parse("/a:2").unwrap(),
+            BenchRoute::parse("/b").unwrap(),
+        ]);
+        assert_eq!(schedule, vec![0, 0, 1]);
+    }
+}
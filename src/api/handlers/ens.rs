@@ -1,18 +1,31 @@
 //! ENS API handlers
 
+use std::sync::Arc;
+
 use axum::{
     extract::{Path, State},
     Json,
 };
-use serde::Serialize;
+use ethers::types::Address;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::api::types::{ApiError, ApiResponse};
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::crypto::key_manager::KeyManager;
+use crate::ens_proof::normalize::normalize;
+use crate::ens_proof::EnsProof;
+
+/// Placeholder key for `EnsProof`, which is only used for lookups here
+const QUERY_ONLY_KEY: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
 
 /// Shared state for ENS handlers
 #[derive(Clone)]
 pub struct EnsState {
     pub eth_rpc_url: String,
     pub base_rpc_url: Option<String>,
+    /// Read-only hub client, used to check the proof owner belongs to the FID
+    pub hub_client: Arc<FarcasterClient>,
 }
 
 #[derive(Debug, Serialize)]
@@ -46,3 +59,124 @@ pub async fn verify_ownership(
     Err(ApiError::InternalError("ENS verification endpoint not implemented yet".to_string()))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BuildProofRequest {
+    pub domain: String,
+    pub fid: u64,
+    /// Address expected to sign, checked against what the domain resolves to
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BuildProofResponse {
+    pub domain: String,
+    pub fid: u64,
+    /// Address the domain resolves to, which must sign the proof
+    pub owner: String,
+    /// How the owner is tied to the FID: "custody" or "verified_address"
+    pub owner_relation: String,
+    /// The proof with an empty signature, in the same JSON form the CLI stores
+    pub proof: Value,
+    /// Signing method the proof is verified with
+    pub signing_method: String,
+    /// Message to sign with `personal_sign`
+    pub message: String,
+    /// The message as hex, exactly the bytes passed to `personal_sign`
+    pub message_hex: String,
+    /// EIP-191 digest of the message, for signers that take a hash
+    pub message_hash: String,
+}
+
+/// Build an unsigned username proof for a domain and FID
+///
+/// Checks that the domain resolves (rejecting expired wrapped subnames), that
+/// it resolves to `address` if one was given, and that the resolved address is
+/// the FID's custody address or one of its verified addresses. The caller signs
+/// `message` with the owner's wallet and sets the signature on `proof`; no key
+/// ever reaches the server.
+pub async fn build_proof(
+    State(state): State<EnsState>,
+    Json(request): Json<BuildProofRequest>,
+) -> Result<Json<ApiResponse<BuildProofResponse>>, ApiError> {
+    let domain = normalize(&request.domain).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    if !domain.contains('.') {
+        return Err(ApiError::BadRequest(format!(
+            "'{domain}' is not an ENS name; fnames are issued by the fname registry"
+        )));
+    }
+    let expected = request
+        .address
+        .as_deref()
+        .map(|address| {
+            address
+                .parse::<Address>()
+                .map_err(|_| ApiError::BadRequest(format!("Invalid address: {address}")))
+        })
+        .transpose()?;
+
+    let key_manager = KeyManager::from_private_key(QUERY_ONLY_KEY)?;
+    let ens_proof = EnsProof::new(key_manager, state.eth_rpc_url.clone());
+    ens_proof
+        .check_subdomain_control(&domain)
+        .await
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let owner = ens_proof
+        .resolve_proof_address(&domain)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("{domain} does not resolve to an address")))?;
+    if let Some(expected) = expected {
+        if expected != owner {
+            return Err(ApiError::BadRequest(format!(
+                "{domain} resolves to {owner:?}, not {expected:?}"
+            )));
+        }
+    }
+
+    let owner_relation = fid_owner_relation(&state.hub_client, request.fid, owner)
+        .await?
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "{owner:?} is neither the custody address nor a verified address of FID {}",
+                request.fid
+            ))
+        })?;
+
+    let proof = EnsProof::build_unsigned_proof(&domain, request.fid, owner);
+    let message = ens_proof.create_proof_message(&proof)?;
+    let proof_json = serde_json::from_str(&ens_proof.serialize_proof(&proof)?)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success(BuildProofResponse {
+        domain,
+        fid: request.fid,
+        owner: format!("{owner:?}"),
+        owner_relation: owner_relation.to_string(),
+        proof: proof_json,
+        signing_method: "personal_sign".to_string(),
+        message_hex: format!("0x{}", hex::encode(message.as_bytes())),
+        message_hash: format!("{:?}", ethers::utils::hash_message(&message)),
+        message,
+    })))
+}
+
+/// Whether an address is the FID's custody address or one of its verified addresses
+async fn fid_owner_relation(
+    hub_client: &FarcasterClient,
+    fid: u64,
+    owner: Address,
+) -> Result<Option<&'static str>, ApiError> {
+    let matches = |address: &str| address.parse::<Address>().ok() == Some(owner);
+    let custody = hub_client
+        .get_custody_address(fid)
+        .await
+        .map_err(|e| ApiError::NotFound(e.to_string()))?;
+    if matches(&custody) {
+        return Ok(Some("custody"));
+    }
+    let verified = hub_client.get_eth_addresses(fid).await?;
+    Ok(verified
+        .iter()
+        .any(|address| matches(address))
+        .then_some("verified_address"))
+}
@@ -1,8 +1,10 @@
 //! Health check endpoints
 
-use axum::Json;
+use axum::{extract::State, http::StatusCode, Json};
 use serde_json::{json, Value};
 
+use super::hub::HubState;
+
 /// Health check endpoint
 pub async fn health_check() -> Json<Value> {
     Json(json!({
@@ -12,3 +14,26 @@ pub async fn health_check() -> Json<Value> {
     }))
 }
 
+/// Upstream hub health
+///
+/// `ok` when every hub is healthy, `degraded` when some are, and `down`
+/// (with status 503) when none is.
+pub async fn upstream_health(State(state): State<HubState>) -> (StatusCode, Json<Value>) {
+    let upstreams = state.client.http_client().health();
+    let healthy = upstreams.iter().filter(|upstream| upstream.healthy).count();
+    let (status, code) = if healthy == upstreams.len() {
+        ("ok", StatusCode::OK)
+    } else if healthy > 0 {
+        ("degraded", StatusCode::OK)
+    } else {
+        ("down", StatusCode::SERVICE_UNAVAILABLE)
+    };
+    (
+        code,
+        Json(json!({
+            "status": status,
+            "healthy": healthy,
+            "upstreams": upstreams
+        })),
+    )
+}
@@ -1,4 +1,10 @@
 //! Hub API handlers
+//!
+//! Queries go through the shared services in `core::services`, so every
+//! endpoint returns what the equivalent CLI command and MCP tool compute.
+//!
+//! The profile and spam routes changed shape with `/v1`; the deprecated
+//! `/api` routes keep their original bodies through the `*_legacy` handlers.
 
 use axum::{
     extract::{Path, Query, State},
@@ -10,11 +16,30 @@ use std::sync::Arc;
 
 use crate::api::types::{ApiError, ApiResponse};
 use crate::core::client::FarcasterClient;
+use crate::core::client::ReadOnly;
+use crate::core::protocol::spam_checker::SpamCheckResult;
+use crate::core::services::profile::DEFAULT_SAMPLE_PAGES;
+use crate::core::services::{
+    GraphService, LinkDirection, Profile, ProfileService, SpamService, UserStats,
+};
 
 /// Shared state for Hub handlers
 #[derive(Clone)]
 pub struct HubState {
-    pub client: Arc<FarcasterClient>,
+    /// Read-only hub client (cannot sign or submit messages)
+    pub client: Arc<FarcasterClient<ReadOnly>>,
+    /// Spam labels, if they could be loaded
+    pub spam: Option<SpamService>,
+}
+
+impl HubState {
+    fn profiles(&self) -> ProfileService<'_> {
+        ProfileService::new(&self.client)
+    }
+
+    fn graph(&self) -> GraphService<'_> {
+        GraphService::new(&self.client)
+    }
 }
 
 /// Query parameters for listing endpoints
@@ -33,7 +58,7 @@ pub async fn get_user(
     State(state): State<HubState>,
     Path(fid): Path<u64>,
 ) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    let user_data = state.client.get_user(fid).await?;
+    let user_data = state.profiles().user(fid).await?;
     Ok(Json(ApiResponse::success(user_data)))
 }
 
@@ -41,97 +66,137 @@ pub async fn get_user(
 pub async fn get_profile(
     State(state): State<HubState>,
     Path(fid): Path<u64>,
-) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    // For now, return the same as get_user (FarcasterClient doesn't have separate profile method)
-    let profile = state.client.get_user(fid).await?;
+) -> Result<Json<ApiResponse<Profile>>, ApiError> {
+    let profile = state.profiles().profile(fid).await?;
     Ok(Json(ApiResponse::success(profile)))
 }
 
+/// Get user profile by FID as the hub's raw user data (legacy `/api` shape)
+pub async fn get_profile_legacy(
+    State(state): State<HubState>,
+    Path(fid): Path<u64>,
+) -> Result<Json<ApiResponse<Value>>, ApiError> {
+    let user_data = state.profiles().user(fid).await?;
+    Ok(Json(ApiResponse::success(user_data)))
+}
+
 /// Get user statistics
 pub async fn get_stats(
     State(state): State<HubState>,
     Path(fid): Path<u64>,
+) -> Result<Json<ApiResponse<UserStats>>, ApiError> {
+    let stats = state.profiles().stats(fid, DEFAULT_SAMPLE_PAGES).await?;
+    Ok(Json(ApiResponse::success(stats)))
+}
+
+async fn get_links(
+    state: HubState,
+    direction: LinkDirection,
+    fid: u64,
+    limit: usize,
 ) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    // Return basic user stats (simplified version)
-    let user = state.client.get_user(fid).await?;
-    Ok(Json(ApiResponse::success(user)))
+    let limit = u32::try_from(limit).unwrap_or(u32::MAX);
+    let links = state.graph().links(direction, fid, limit).await?;
+    Ok(Json(ApiResponse::success(direction.to_json(fid, &links))))
 }
 
 /// Get followers for a FID
 pub async fn get_followers(
-    State(_state): State<HubState>,
-    Path(_fid): Path<u64>,
-    Query(_params): Query<ListParams>,
+    State(state): State<HubState>,
+    Path(fid): Path<u64>,
+    Query(params): Query<ListParams>,
 ) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    // Placeholder - FarcasterClient doesn't have this method yet
-    Err(ApiError::InternalError("Followers endpoint not implemented yet".to_string()))
+    get_links(state, LinkDirection::Followers, fid, params.limit).await
 }
 
 /// Get following for a FID
 pub async fn get_following(
-    State(_state): State<HubState>,
-    Path(_fid): Path<u64>,
-    Query(_params): Query<ListParams>,
+    State(state): State<HubState>,
+    Path(fid): Path<u64>,
+    Query(params): Query<ListParams>,
 ) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    // Placeholder - FarcasterClient doesn't have this method yet
-    Err(ApiError::InternalError("Following endpoint not implemented yet".to_string()))
+    get_links(state, LinkDirection::Following, fid, params.limit).await
 }
 
 /// Get verified Ethereum addresses
 pub async fn get_eth_addresses(
-    State(_state): State<HubState>,
-    Path(_fid): Path<u64>,
+    State(state): State<HubState>,
+    Path(fid): Path<u64>,
 ) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    // Placeholder - FarcasterClient doesn't have this method yet
-    Err(ApiError::InternalError("ETH addresses endpoint not implemented yet".to_string()))
+    let addresses = state.profiles().eth_addresses(fid).await?;
+    Ok(Json(ApiResponse::success(serde_json::json!({
+        "fid": fid,
+        "addresses": addresses,
+        "count": addresses.len(),
+    }))))
 }
 
 /// Get ENS domains
 pub async fn get_ens_domains(
-    State(_state): State<HubState>,
-    Path(_fid): Path<u64>,
+    State(state): State<HubState>,
+    Path(fid): Path<u64>,
 ) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    // Placeholder - FarcasterClient doesn't have this method yet
-    Err(ApiError::InternalError("ENS domains endpoint not implemented yet".to_string()))
+    let domains = state.profiles().ens_domains(fid).await?;
+    Ok(Json(ApiResponse::success(serde_json::json!({
+        "fid": fid,
+        "domains": domains,
+        "count": domains.len(),
+    }))))
 }
 
 /// Get custody address
 pub async fn get_custody_address(
-    State(_state): State<HubState>,
-    Path(_fid): Path<u64>,
+    State(state): State<HubState>,
+    Path(fid): Path<u64>,
 ) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    // Placeholder - FarcasterClient doesn't have this method yet
-    Err(ApiError::InternalError("Custody address endpoint not implemented yet".to_string()))
+    let custody_address = state.profiles().custody_address(fid).await?;
+    Ok(Json(ApiResponse::success(serde_json::json!({
+        "fid": fid,
+        "custody_address": custody_address,
+    }))))
 }
 
 /// Get casts by FID
 pub async fn get_casts(
-    State(_state): State<HubState>,
-    Path(_fid): Path<u64>,
-    Query(_params): Query<ListParams>,
+    State(state): State<HubState>,
+    Path(fid): Path<u64>,
+    Query(params): Query<ListParams>,
 ) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    // Placeholder - FarcasterClient doesn't have this method yet
-    Err(ApiError::InternalError("Casts endpoint not implemented yet".to_string()))
+    let casts = state
+        .client
+        .get_casts_by_fid(fid, params.limit as u32)
+        .await?;
+    Ok(Json(ApiResponse::success(Value::Array(casts))))
 }
 
-/// Check spam status
+/// Spam status in the legacy `/api` shape
 #[derive(Debug, Serialize)]
 pub struct SpamCheckResponse {
     pub fid: u64,
     pub is_spam: bool,
 }
 
+/// Check spam status
 pub async fn check_spam(
+    State(state): State<HubState>,
+    Path(fid): Path<u64>,
+) -> Result<Json<ApiResponse<SpamCheckResult>>, ApiError> {
+    let spam = state
+        .spam
+        .as_ref()
+        .ok_or_else(|| ApiError::InternalError("Spam labels are not loaded".to_string()))?;
+    Ok(Json(ApiResponse::success(spam.check(fid))))
+}
+
+/// Check spam status (legacy `/api` shape)
+///
+/// As before `/v1`, unlabelled FIDs and every FID when the labels could not
+/// be loaded are reported as not spam.
+pub async fn check_spam_legacy(
+    State(state): State<HubState>,
     Path(fid): Path<u64>,
 ) -> Result<Json<ApiResponse<SpamCheckResponse>>, ApiError> {
-    // Use the spam checker
-    use crate::mcp::utils::SpamChecker;
-    
-    let is_spam = match SpamChecker::load() {
-        Ok(checker) => checker.is_spam(fid),
-        Err(_) => false, // If spam checker fails to load, assume not spam
-    };
-    
+    let is_spam = state.spam.as_ref().is_some_and(|spam| spam.is_spam(fid));
     Ok(Json(ApiResponse::success(SpamCheckResponse {
         fid,
         is_spam,
@@ -140,9 +205,8 @@ pub async fn check_spam(
 
 /// Get hub info
 pub async fn get_hub_info(
-    State(_state): State<HubState>,
+    State(state): State<HubState>,
 ) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    // Placeholder - FarcasterClient doesn't have this method yet
-    Err(ApiError::InternalError("Hub info endpoint not implemented yet".to_string()))
+    let hub_info = state.client.get_hub_info().await?;
+    Ok(Json(ApiResponse::success(hub_info)))
 }
-
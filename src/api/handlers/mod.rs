@@ -4,4 +4,6 @@ pub mod hub;
 pub mod health;
 pub mod ens;
 pub mod contract;
+pub mod spam;
+pub mod share;
 
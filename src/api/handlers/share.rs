@@ -0,0 +1,212 @@
+//! Static profile share pages
+//!
+//! Renders a single self-contained HTML page (avatar, bio, recent casts) for a
+//! FID. The data comes from the same hub handlers that back the REST API, so
+//! the page shows exactly what `/api/hub/users/:fid` and `/casts` return.
+
+use axum::extract::Path;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::response::Html;
+use axum::response::Redirect;
+use axum::routing::get;
+use axum::Json;
+use axum::Router;
+use serde_json::Value;
+
+use crate::api::handlers::hub;
+use crate::api::handlers::hub::HubState;
+use crate::api::handlers::hub::ListParams;
+use crate::api::types::ApiError;
+use crate::api::types::ApiResponse;
+use crate::core::client::hub_client::FARCASTER_EPOCH;
+use crate::core::services::Profile;
+
+/// Number of recent casts shown on a share page
+pub const DEFAULT_SHARE_CASTS: usize = 10;
+
+/// Fetch a profile and its recent casts through the REST API hub handlers
+///
+/// # Arguments
+/// * `state` - Hub handler state
+/// * `fid` - The Farcaster ID
+/// * `limit` - Maximum number of casts
+///
+/// # Returns
+/// * `Result<(Value, Vec<Value>), ApiError>` - User data and casts, or an error
+pub async fn fetch_share_data(
+    state: HubState,
+    fid: u64,
+    limit: usize,
+) -> Result<(Value, Vec<Value>), ApiError> {
+    let Json(user_data) = hub::get_user(State(state.clone()), Path(fid)).await?;
+    let Json(casts) = hub::get_casts(State(state), Path(fid), Query(ListParams { limit })).await?;
+
+    let casts = match response_data(casts)? {
+        Value::Array(casts) => casts,
+        _ => Vec::new(),
+    };
+    Ok((response_data(user_data)?, casts))
+}
+
+fn response_data(response: ApiResponse<Value>) -> Result<Value, ApiError> {
+    match response.data {
+        Some(data) => Ok(data),
+        None => Err(ApiError::InternalError(
+            response
+                .error
+                .unwrap_or_else(|| "Empty response".to_string()),
+        )),
+    }
+}
+
+/// Render the share page for a FID
+pub async fn share_page(
+    State(state): State<HubState>,
+    Path(fid): Path<u64>,
+) -> Result<Html<String>, ApiError> {
+    let (user_data, casts) = fetch_share_data(state, fid, DEFAULT_SHARE_CASTS).await?;
+    Ok(Html(render_share_page(fid, &user_data, &casts)))
+}
+
+/// Router serving the share page of one FID at `/` and any FID at `/share/:fid`
+pub fn share_router(state: HubState, fid: u64) -> Router {
+    let target = format!("/share/{fid}");
+    Router::new()
+        .route(
+            "/",
+            get(move || async move { Redirect::temporary(&target) }),
+        )
+        .route("/share/:fid", get(share_page))
+        .with_state(state)
+}
+
+/// Render a self-contained HTML page for a profile and its casts
+///
+/// # Arguments
+/// * `fid` - The Farcaster ID
+/// * `user_data` - Hub `userDataByFid` response
+/// * `casts` - Cast messages, newest first
+///
+/// # Returns
+/// * `String` - The HTML document
+pub fn render_share_page(fid: u64, user_data: &Value, casts: &[Value]) -> String {
+    let profile = Profile::from_user_data(fid, user_data);
+    let title = profile
+        .display_name
+        .clone()
+        .or_else(|| profile.username.clone())
+        .unwrap_or_else(|| format!("FID {fid}"));
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape_html(&title)));
+    html.push_str(STYLE);
+    html.push_str("</head>\n<body>\n<main>\n<header>\n");
+
+    if let Some(pfp) = profile.pfp_url.as_deref().filter(|u| is_http_url(u)) {
+        html.push_str(&format!(
+            "<img class=\"avatar\" src=\"{}\" alt=\"avatar\">\n",
+            escape_html(pfp)
+        ));
+    }
+    html.push_str(&format!("<h1>{}</h1>\n", escape_html(&title)));
+    let handle = match &profile.username {
+        Some(username) => format!("@{} · FID {fid}", escape_html(username)),
+        None => format!("FID {fid}"),
+    };
+    html.push_str(&format!("<p class=\"handle\">{handle}</p>\n"));
+    if let Some(bio) = &profile.bio {
+        html.push_str(&format!("<p class=\"bio\">{}</p>\n", escape_html(bio)));
+    }
+    html.push_str("</header>\n<section>\n<h2>Recent casts</h2>\n");
+
+    let rendered: Vec<String> = casts.iter().filter_map(render_cast).collect();
+    if rendered.is_empty() {
+        html.push_str("<p class=\"empty\">No casts yet.</p>\n");
+    }
+    for cast in rendered {
+        html.push_str(&cast);
+    }
+
+    html.push_str("</section>\n</main>\n</body>\n</html>\n");
+    html
+}
+
+/// Render one cast, skipping messages that are not cast adds
+fn render_cast(cast: &Value) -> Option<String> {
+    let data = cast.get("data")?;
+    let text = data.get("castAddBody")?.get("text")?.as_str()?;
+    let time = data
+        .get("timestamp")
+        .and_then(|t| t.as_i64())
+        .and_then(|t| chrono::DateTime::from_timestamp(t + FARCASTER_EPOCH as i64, 0))
+        .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default();
+    Some(format!(
+        "<article><p>{}</p><time>{}</time></article>\n",
+        escape_html(text).replace('\n', "<br>"),
+        time
+    ))
+}
+
+/// Only link images over http(s) so user data cannot inject `javascript:` URLs
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+/// Escape text for use in HTML content and attribute values
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const STYLE: &str = "<style>
+body { font-family: system-ui, sans-serif; background: #f5f3fa; color: #222; margin: 0; }
+main { max-width: 640px; margin: 0 auto; padding: 24px; }
+header { text-align: center; margin-bottom: 24px; }
+.avatar { width: 96px; height: 96px; border-radius: 50%; object-fit: cover; }
+h1 { margin: 12px 0 4px; }
+.handle { color: #7c65c1; margin: 0; }
+.bio { white-space: pre-wrap; }
+article { background: #fff; border-radius: 8px; padding: 12px 16px; margin-bottom: 12px; }
+article p { margin: 0 0 8px; word-wrap: break-word; }
+time, .empty { color: #888; font-size: 0.85em; }
+</style>
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_escapes_user_content() {
+        let user_data = serde_json::json!({ "messages": [
+            { "data": { "userDataBody": { "type": "USER_DATA_TYPE_USERNAME", "value": "alice" } } },
+            { "data": { "userDataBody": { "type": "USER_DATA_TYPE_BIO", "value": "<script>x</script>" } } },
+            { "data": { "userDataBody": { "type": "USER_DATA_TYPE_PFP", "value": "javascript:alert(1)" } } },
+        ]});
+        let casts = vec![serde_json::json!({
+            "data": { "timestamp": 0, "castAddBody": { "text": "hi & bye" } }
+        })];
+
+        let html = render_share_page(42, &user_data, &casts);
+        assert!(html.contains("@alice · FID 42"));
+        assert!(html.contains("&lt;script&gt;x&lt;/script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("hi &amp; bye"));
+        assert!(html.contains("2021-01-01 00:00 UTC"));
+    }
+}
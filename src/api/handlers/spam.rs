@@ -0,0 +1,77 @@
+//! Batch spam check endpoint
+
+use std::convert::Infallible;
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::Json;
+use futures::stream;
+use serde::Deserialize;
+
+use crate::api::types::ApiError;
+use crate::core::services::SpamService;
+
+/// Maximum number of FIDs accepted in one request
+pub const MAX_BATCH_FIDS: usize = 10_000;
+
+/// Number of results serialized per streamed chunk
+const STREAM_CHUNK_SIZE: usize = 500;
+
+/// Shared state for spam handlers
+#[derive(Clone)]
+pub struct SpamState {
+    /// Spam labels indexed by FID, loaded once at startup
+    pub spam: SpamService,
+}
+
+/// Request body for `POST /api/spam/check`
+#[derive(Debug, Deserialize)]
+pub struct SpamCheckRequest {
+    pub fids: Vec<u64>,
+}
+
+/// Check many FIDs at once
+///
+/// Results are streamed as newline-delimited JSON in request order, one object
+/// per FID with its status, label value and label timestamp.
+pub async fn check_spam_batch(
+    State(state): State<SpamState>,
+    Json(request): Json<SpamCheckRequest>,
+) -> Result<Response, ApiError> {
+    if request.fids.is_empty() {
+        return Err(ApiError::BadRequest("fids must not be empty".to_string()));
+    }
+    if request.fids.len() > MAX_BATCH_FIDS {
+        return Err(ApiError::BadRequest(format!(
+            "At most {} FIDs can be checked per request, got {}",
+            MAX_BATCH_FIDS,
+            request.fids.len()
+        )));
+    }
+
+    let chunks: Vec<Vec<u64>> = request
+        .fids
+        .chunks(STREAM_CHUNK_SIZE)
+        .map(|chunk| chunk.to_vec())
+        .collect();
+    let spam = state.spam;
+    let body = stream::iter(chunks.into_iter().map(move |chunk| {
+        let mut lines = String::new();
+        for result in spam.check_many(&chunk) {
+            if let Ok(line) = serde_json::to_string(&result) {
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+        }
+        Ok::<_, Infallible>(lines)
+    }));
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(body),
+    )
+        .into_response())
+}
@@ -14,12 +14,17 @@
 //! - Does NOT submit any transactions
 //! - Only queries public data from Farcaster Hub and contracts
 //!
+//! The hub client is held as `FarcasterClient<ReadOnly>`, which has no submit
+//! methods, so the no-signing guarantee is enforced by the type system.
+//!
 //! For sensitive operations (signing, key management, transactions), use the CLI tool.
 
+pub mod bench;
 pub mod handlers;
 pub mod routes;
 pub mod server;
 pub mod types;
+pub mod versioning;
 
 pub use server::ApiServer;
 pub use types::{ApiError, ApiResponse};
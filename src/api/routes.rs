@@ -1,58 +1,237 @@
 //! API route definitions
 
 use axum::{
-    routing::get,
+    routing::{get, post},
     Router,
 };
 
-use super::handlers::{contract, ens, health, hub};
+use super::handlers::{contract, ens, health, hub, spam};
+use super::versioning::{self, LEGACY_PREFIX, V1_PREFIX};
 
 /// Build the main API router
+///
+/// API routes are served under `/v1` and under the deprecated `/api` prefix,
+/// see [`versioning`]. The profile and spam routes keep their original bodies
+/// under `/api`.
 pub fn build_router(
     hub_state: hub::HubState,
     ens_state: Option<ens::EnsState>,
     contract_state: Option<contract::ContractState>,
+    spam_state: Option<spam::SpamState>,
 ) -> Router {
     // Create base router with Hub routes
-    let mut app = Router::new()
-        // Health check
-        .route("/health", get(health::health_check))
-        
+    let mut api = Router::new()
         // Hub routes
-        .route("/api/hub/info", get(hub::get_hub_info))
-        .route("/api/hub/users/:fid", get(hub::get_user))
-        .route("/api/hub/users/:fid/profile", get(hub::get_profile))
-        .route("/api/hub/users/:fid/stats", get(hub::get_stats))
-        .route("/api/hub/users/:fid/followers", get(hub::get_followers))
-        .route("/api/hub/users/:fid/following", get(hub::get_following))
-        .route("/api/hub/users/:fid/addresses", get(hub::get_eth_addresses))
-        .route("/api/hub/users/:fid/ens", get(hub::get_ens_domains))
-        .route("/api/hub/users/:fid/custody", get(hub::get_custody_address))
-        .route("/api/hub/users/:fid/casts", get(hub::get_casts))
-        .route("/api/hub/spam/:fid", get(hub::check_spam))
-        .with_state(hub_state);
+        .route("/hub/info", get(hub::get_hub_info))
+        .route("/hub/users/:fid", get(hub::get_user))
+        .route("/hub/users/:fid/stats", get(hub::get_stats))
+        .route("/hub/users/:fid/followers", get(hub::get_followers))
+        .route("/hub/users/:fid/following", get(hub::get_following))
+        .route("/hub/users/:fid/addresses", get(hub::get_eth_addresses))
+        .route("/hub/users/:fid/ens", get(hub::get_ens_domains))
+        .route("/hub/users/:fid/custody", get(hub::get_custody_address))
+        .route("/hub/users/:fid/casts", get(hub::get_casts))
+        .with_state(hub_state.clone());
+
+    // Routes whose body changed with /v1
+    let v1_hub = Router::new()
+        .route("/hub/users/:fid/profile", get(hub::get_profile))
+        .route("/hub/spam/:fid", get(hub::check_spam))
+        .with_state(hub_state.clone());
+    let legacy_hub = Router::new()
+        .route("/hub/users/:fid/profile", get(hub::get_profile_legacy))
+        .route("/hub/spam/:fid", get(hub::check_spam_legacy))
+        .with_state(hub_state.clone());
 
     // Merge ENS routes if available
     if let Some(ens_state) = ens_state {
         let ens_router = Router::new()
-            .route("/api/ens/resolve/:domain", get(ens::resolve_domain))
-            .route("/api/ens/verify/:domain/:address", get(ens::verify_ownership))
+            .route("/ens/resolve/:domain", get(ens::resolve_domain))
+            .route("/ens/verify/:domain/:address", get(ens::verify_ownership))
+            .route("/proofs/build", post(ens::build_proof))
             .with_state(ens_state);
         
-        app = app.merge(ens_router);
+        api = api.merge(ens_router);
     }
 
     // Merge contract routes if available
     if let Some(contract_state) = contract_state {
         let contract_router = Router::new()
-            .route("/api/contract/fid/price", get(contract::get_fid_price))
-            .route("/api/contract/storage/price/:units", get(contract::get_storage_price))
-            .route("/api/contract/address/:address/fid", get(contract::check_address_fid))
+            .route("/contract/fid/price", get(contract::get_fid_price))
+            .route("/contract/storage/price/:units", get(contract::get_storage_price))
+            .route("/contract/address/:address/fid", get(contract::check_address_fid))
             .with_state(contract_state);
         
-        app = app.merge(contract_router);
+        api = api.merge(contract_router);
+    }
+
+    // Merge batch spam routes if the labels were loaded
+    if let Some(spam_state) = spam_state {
+        let spam_router = Router::new()
+            .route("/spam/check", post(spam::check_spam_batch))
+            .with_state(spam_state);
+
+        api = api.merge(spam_router);
     }
 
-    app
+    Router::new()
+        // Health check
+        .route("/health", get(health::health_check))
+        .route("/healthz", get(health::upstream_health))
+        .with_state(hub_state)
+        .nest(V1_PREFIX, api.clone().merge(v1_hub))
+        .nest(LEGACY_PREFIX, api.merge(legacy_hub))
+        .layer(axum::middleware::from_fn(versioning::versioning))
 }
 
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+
+    use axum::{
+        body::{to_bytes, Body},
+        extract::Request,
+        http::{HeaderMap, StatusCode},
+    };
+    use serde_json::{json, Value};
+    use tower::Service;
+
+    use super::*;
+    use crate::core::client::mock_hub::Fixture;
+    use crate::core::client::FarcasterClient;
+    use crate::core::client::MockHub;
+    use crate::core::protocol::spam_checker::{SpamIndex, SpamIndexEntry};
+    use crate::core::services::SpamService;
+
+    /// userDataByFid response of FID 3 served by the mock hub
+    pub(crate) fn user_data() -> Value {
+        json!({
+            "messages": [{
+                "data": {
+                    "fid": 3,
+                    "userDataBody": {"type": "USER_DATA_TYPE_USERNAME", "value": "alice"}
+                }
+            }]
+        })
+    }
+
+    /// The real router over a mock hub that knows FID 3
+    ///
+    /// With `spam_labels`, FID 3 is labelled as spam and FID 4 as not spam.
+    pub(crate) async fn mock_router(spam_labels: bool) -> Router {
+        let addr = MockHub::from_fixtures([Fixture {
+            request: "GET /v1/userDataByFid?fid=3".to_string(),
+            status: 200,
+            body: user_data(),
+        }])
+        .serve(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .unwrap();
+        let spam = spam_labels.then(|| {
+            SpamService::new(SpamIndex::from_iter([
+                SpamIndexEntry {
+                    fid: 3,
+                    label_value: 0,
+                    timestamp: 1_700_000_000,
+                },
+                SpamIndexEntry {
+                    fid: 4,
+                    label_value: 2,
+                    timestamp: 1_700_000_000,
+                },
+            ]))
+        });
+        let hub_state = hub::HubState {
+            client: Arc::new(FarcasterClient::read_only(format!("http://{addr}"))),
+            spam: spam.clone(),
+        };
+        build_router(
+            hub_state,
+            None,
+            None,
+            spam.map(|spam| spam::SpamState { spam }),
+        )
+    }
+
+    /// GET a route, returning the status, headers and JSON body
+    pub(crate) async fn get(router: &Router, uri: &str) -> (StatusCode, HeaderMap, Value) {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = router.clone().call(request).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let body = to_bytes(body, usize::MAX).await.unwrap();
+        (
+            parts.status,
+            parts.headers,
+            serde_json::from_slice(&body).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_legacy_profile_and_spam_keep_their_shape() {
+        let router = mock_router(true).await;
+
+        let (status, _, body) = get(&router, "/api/hub/users/3/profile").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({"success": true, "data": user_data()}));
+
+        let (_, _, body) = get(&router, "/api/hub/spam/3").await;
+        assert_eq!(
+            body,
+            json!({"success": true, "data": {"fid": 3, "is_spam": true}})
+        );
+        let (_, _, body) = get(&router, "/api/hub/spam/4").await;
+        assert_eq!(
+            body,
+            json!({"success": true, "data": {"fid": 4, "is_spam": false}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_legacy_spam_check_without_labels() {
+        let router = mock_router(false).await;
+        let (status, _, body) = get(&router, "/api/hub/spam/3").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            json!({"success": true, "data": {"fid": 3, "is_spam": false}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_v1_profile_and_spam_shapes() {
+        let router = mock_router(true).await;
+
+        let (status, _, body) = get(&router, "/v1/hub/users/3/profile").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["data"],
+            json!({
+                "fid": 3,
+                "username": "alice",
+                "display_name": null,
+                "bio": null,
+                "pfp_url": null,
+                "banner_url": null,
+                "location": null,
+                "url": null,
+                "twitter": null,
+                "github": null,
+                "primary_eth_address": null,
+                "primary_sol_address": null,
+                "fields": 1
+            })
+        );
+
+        let (_, _, body) = get(&router, "/v1/hub/spam/3").await;
+        assert_eq!(
+            body["data"],
+            json!({
+                "fid": 3,
+                "status": "spam",
+                "label_value": 0,
+                "labeled_at": 1_700_000_000
+            })
+        );
+    }
+}
@@ -3,21 +3,29 @@
 use anyhow::{Context, Result};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing::info;
 
-use crate::api::handlers::{contract, ens, hub};
+use crate::api::handlers::{contract, ens, hub, spam};
 use crate::api::routes;
+use crate::core::client::hub_pool;
 use crate::core::client::FarcasterClient;
-use crate::farcaster::contracts::FarcasterContractClient;
+use crate::core::services::SpamService;
 use crate::farcaster::contracts::ContractAddresses;
+use crate::farcaster::contracts::FarcasterContractClient;
+
+/// How often upstream hubs are probed
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(15);
 
 /// API server configuration
 pub struct ApiServer {
     pub host: String,
     pub port: u16,
     pub hub_url: String,
+    /// More hubs to spread reads over, round-robin with `hub_url`
+    pub hub_upstreams: Vec<String>,
     pub eth_rpc_url: Option<String>,
     pub eth_base_rpc_url: Option<String>,
     pub eth_op_rpc_url: Option<String>,
@@ -30,6 +38,7 @@ impl Default for ApiServer {
             port: 3000,
             hub_url: std::env::var("FARCASTER_HUB_URL")
                 .unwrap_or_else(|_| "https://hub-api.neynar.com".to_string()),
+            hub_upstreams: Vec::new(),
             eth_rpc_url: std::env::var("ETH_RPC_URL").ok(),
             eth_base_rpc_url: std::env::var("ETH_BASE_RPC_URL").ok(),
             eth_op_rpc_url: std::env::var("ETH_OP_RPC_URL").ok(),
@@ -65,13 +74,45 @@ impl ApiServer {
         info!("   Host: {}", self.host);
         info!("   Port: {}", self.port);
         info!("   Hub URL: {}", self.hub_url);
+        for upstream in &self.hub_upstreams {
+            info!("   Read replica: {}", upstream);
+        }
         info!("🔒 Security: READ-ONLY mode (no private key access)");
 
         // SECURITY: Create Hub client WITHOUT key manager (read-only mode)
         // This ensures the API server can NEVER sign messages or access private keys
-        let hub_client = Arc::new(FarcasterClient::new(self.hub_url.clone(), None));
+        let hub_client = if self.hub_upstreams.is_empty() {
+            FarcasterClient::read_only(self.hub_url.clone())
+        } else {
+            let hubs = std::iter::once(&self.hub_url)
+                .chain(&self.hub_upstreams)
+                .map(|url| hub_pool::configured_endpoint(url))
+                .collect();
+            FarcasterClient::with_read_replicas(hubs)?
+        };
+        let hub_client = Arc::new(hub_client);
+
+        // Keep upstream health current for routing and /healthz
+        let pool = hub_client.http_client().clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                pool.check_health().await;
+            }
+        });
+
+        // Index spam labels once so spam checks never rescan the dataset
+        let spam = match SpamService::load() {
+            Ok(spam) => Some(spam),
+            Err(e) => {
+                info!("⚠️  Spam endpoints disabled (failed to load labels: {})", e);
+                None
+            }
+        };
         let hub_state = hub::HubState {
-            client: hub_client,
+            client: hub_client.clone(),
+            spam: spam.clone(),
         };
 
         // Create ENS state if RPC URL is available
@@ -80,6 +121,7 @@ impl ApiServer {
             Some(ens::EnsState {
                 eth_rpc_url: self.eth_rpc_url.clone().unwrap(),
                 base_rpc_url: self.eth_base_rpc_url.clone(),
+                hub_client: hub_client.clone(),
             })
         } else {
             info!("⚠️  ENS endpoints disabled (no ETH_RPC_URL)");
@@ -101,12 +143,21 @@ impl ApiServer {
             None
         };
 
+        let spam_state = spam.map(|spam| {
+            info!(
+                "✅ Spam endpoints enabled ({} labels)",
+                spam.summary().total
+            );
+            spam::SpamState { spam }
+        });
+
         // Log available endpoints based on enabled features
         let has_ens = ens_state.is_some();
         let has_contract = contract_state.is_some();
+        let has_spam = spam_state.is_some();
 
         // Build router
-        let app = routes::build_router(hub_state, ens_state, contract_state)
+        let app = routes::build_router(hub_state, ens_state, contract_state, spam_state)
             .layer(
                 CorsLayer::new()
                     .allow_origin(Any)
@@ -123,24 +174,34 @@ impl ApiServer {
         info!("🎯 API server listening on http://{}", addr);
         info!("📚 Available endpoints:");
         info!("   GET  /health - Health check");
-        info!("   GET  /api/hub/info - Hub information");
-        info!("   GET  /api/hub/users/:fid - User info");
-        info!("   GET  /api/hub/users/:fid/profile - User profile");
-        info!("   GET  /api/hub/users/:fid/stats - User stats");
-        info!("   GET  /api/hub/users/:fid/followers - Followers");
-        info!("   GET  /api/hub/users/:fid/following - Following");
-        info!("   GET  /api/hub/users/:fid/casts - User casts");
-        info!("   GET  /api/hub/spam/:fid - Spam check");
+        info!("   GET  /healthz - Upstream hub health");
+        info!("   (/api/... aliases of the /v1 routes are deprecated)");
+        info!("   GET  /v1/hub/info - Hub information");
+        info!("   GET  /v1/hub/users/:fid - User info");
+        info!("   GET  /v1/hub/users/:fid/profile - User profile");
+        info!("   GET  /v1/hub/users/:fid/stats - User stats");
+        info!("   GET  /v1/hub/users/:fid/followers - Followers");
+        info!("   GET  /v1/hub/users/:fid/following - Following");
+        info!("   GET  /v1/hub/users/:fid/addresses - Verified addresses");
+        info!("   GET  /v1/hub/users/:fid/ens - ENS domains");
+        info!("   GET  /v1/hub/users/:fid/custody - Custody address");
+        info!("   GET  /v1/hub/users/:fid/casts - User casts");
 
         if has_ens {
-            info!("   GET  /api/ens/resolve/:domain - Resolve ENS");
-            info!("   GET  /api/ens/verify/:domain/:address - Verify ownership");
+            info!("   GET  /v1/ens/resolve/:domain - Resolve ENS");
+            info!("   GET  /v1/ens/verify/:domain/:address - Verify ownership");
+            info!("   POST /v1/proofs/build - Build an unsigned username proof");
+        }
+
+        if has_spam {
+            info!("   GET  /v1/hub/spam/:fid - Spam check");
+            info!("   POST /v1/spam/check - Batch spam check (NDJSON)");
         }
 
         if has_contract {
-            info!("   GET  /api/contract/fid/price - FID price");
-            info!("   GET  /api/contract/storage/price/:units - Storage price");
-            info!("   GET  /api/contract/address/:address/fid - Check address FID");
+            info!("   GET  /v1/contract/fid/price - FID price");
+            info!("   GET  /v1/contract/storage/price/:units - Storage price");
+            info!("   GET  /v1/contract/address/:address/fid - Check address FID");
         }
 
         // Start server
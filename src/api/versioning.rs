@@ -0,0 +1,345 @@
+//! Versioned routes and response envelopes
+//!
+//! Every API route is served under `/v1` and, for existing consumers, under
+//! the legacy unversioned `/api` prefix:
+//! - `/v1` responses carry an [`API_VERSION_HEADER`] header and wrap the
+//!   handler's JSON in a [`V1Response`] envelope, whose shape is pinned by
+//!   the tests below. Changes to `api::types` that would alter it belong in
+//!   a new version.
+//! - `/api` responses keep their original body and gain `Deprecation` and
+//!   `Link: <...>; rel="successor-version"` headers pointing at the `/v1`
+//!   route.
+//!
+//! Non-JSON bodies, such as the NDJSON batch spam check, are passed through
+//! unwrapped.
+
+use axum::body::to_bytes;
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::header;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Current API version
+pub const API_VERSION: &str = "v1";
+
+/// Prefix of the current version's routes
+pub const V1_PREFIX: &str = "/v1";
+
+/// Prefix of the deprecated unversioned routes
+pub const LEGACY_PREFIX: &str = "/api";
+
+/// Response header naming the API version that produced a response
+pub const API_VERSION_HEADER: &str = "api-version";
+
+/// Largest response body that is re-wrapped in an envelope
+const MAX_ENVELOPE_BODY: usize = 16 * 1024 * 1024;
+
+/// Envelope of every JSON response under `/v1`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct V1Response {
+    pub api_version: String,
+    pub success: bool,
+    /// Response data; `null` on errors
+    pub data: Value,
+    /// Error message; `null` on success
+    pub error: Option<String>,
+}
+
+impl V1Response {
+    /// Wrap a handler's JSON body
+    ///
+    /// `ApiResponse` bodies are unpacked into the envelope's fields; any
+    /// other JSON becomes the envelope's data.
+    pub fn wrap(body: Value, success: bool) -> Self {
+        let is_api_response = body
+            .as_object()
+            .is_some_and(|object| object.get("success").is_some_and(Value::is_boolean));
+        let (success, data, error) = if is_api_response {
+            let mut object = body.as_object().cloned().unwrap_or_default();
+            (
+                object
+                    .get("success")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(success),
+                object.remove("data").unwrap_or(Value::Null),
+                object
+                    .remove("error")
+                    .and_then(|error| error.as_str().map(String::from)),
+            )
+        } else {
+            (success, body, None)
+        };
+        Self {
+            api_version: API_VERSION.to_string(),
+            success,
+            data,
+            error,
+        }
+    }
+}
+
+/// Apply versioning to every response: envelopes under `/v1`, deprecation
+/// headers under `/api`
+pub async fn versioning(request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    let query = request
+        .uri()
+        .query()
+        .map(|query| format!("?{query}"))
+        .unwrap_or_default();
+    let response = next.run(request).await;
+    if strip_route_prefix(&path, V1_PREFIX).is_some() {
+        return envelope(response).await;
+    }
+    match strip_route_prefix(&path, LEGACY_PREFIX) {
+        Some(route) => deprecate(response, &format!("{V1_PREFIX}{route}{query}")),
+        None => response,
+    }
+}
+
+/// The route below `prefix`, if `path` is under it
+fn strip_route_prefix<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    path.strip_prefix(prefix)
+        .filter(|route| route.is_empty() || route.starts_with('/'))
+}
+
+async fn envelope(response: Response) -> Response {
+    let (mut parts, body) = response.into_parts();
+    parts
+        .headers
+        .insert(API_VERSION_HEADER, HeaderValue::from_static(API_VERSION));
+    let is_json = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !is_json {
+        return Response::from_parts(parts, body);
+    }
+    let bytes = match to_bytes(body, MAX_ENVELOPE_BODY).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    let Ok(json) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let wrapped = V1Response::wrap(json, parts.status.is_success());
+    let Ok(wrapped) = serde_json::to_vec(&wrapped) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(wrapped))
+}
+
+fn deprecate(mut response: Response, successor: &str) -> Response {
+    let headers = response.headers_mut();
+    headers.insert("deprecation", HeaderValue::from_static("true"));
+    if let Ok(link) = HeaderValue::from_str(&format!("<{successor}>; rel=\"successor-version\"")) {
+        headers.insert(header::LINK, link);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::routing::post;
+    use axum::Json;
+    use axum::Router;
+    use serde_json::json;
+    use tower::Service;
+
+    use super::*;
+    use crate::api::routes::tests::get as get_route;
+    use crate::api::routes::tests::mock_router;
+    use crate::api::routes::tests::user_data;
+    use crate::api::types::ApiError;
+    use crate::api::types::ApiResponse;
+
+    fn router() -> Router {
+        let api = Router::new()
+            .route(
+                "/hub/users/:fid",
+                get(|| async { Json(ApiResponse::success(json!({"fid": 3}))) }),
+            )
+            .route(
+                "/hub/missing",
+                get(|| async {
+                    Err::<Json<ApiResponse<()>>, _>(ApiError::NotFound("No user".into()))
+                }),
+            )
+            .route("/spam/check", post(|| async { "{\"fid\":3}\n" }));
+        Router::new()
+            .nest(V1_PREFIX, api.clone())
+            .nest(LEGACY_PREFIX, api)
+            .layer(axum::middleware::from_fn(versioning))
+    }
+
+    async fn call(method: &str, uri: &str) -> (StatusCode, axum::http::HeaderMap, Vec<u8>) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let response = router().call(request).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let body = to_bytes(body, usize::MAX).await.unwrap().to_vec();
+        (parts.status, parts.headers, body)
+    }
+
+    fn body_json(body: &[u8]) -> Value {
+        serde_json::from_slice(body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_v1_success_shape() {
+        let (status, headers, body) = call("GET", "/v1/hub/users/3").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[API_VERSION_HEADER], "v1");
+        assert!(headers.get("deprecation").is_none());
+        assert_eq!(
+            body_json(&body),
+            json!({
+                "api_version": "v1",
+                "success": true,
+                "data": {"fid": 3},
+                "error": null
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_v1_error_shape() {
+        let (status, _, body) = call("GET", "/v1/hub/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_json(&body),
+            json!({
+                "api_version": "v1",
+                "success": false,
+                "data": null,
+                "error": "No user"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_v1_non_json_passes_through() {
+        let (_, headers, body) = call("POST", "/v1/spam/check").await;
+        assert_eq!(headers[API_VERSION_HEADER], "v1");
+        assert_eq!(body, b"{\"fid\":3}\n");
+    }
+
+    #[tokio::test]
+    async fn test_legacy_routes_are_deprecated() {
+        let (status, headers, body) = call("GET", "/api/hub/users/3?limit=5").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers["deprecation"], "true");
+        assert_eq!(
+            headers[header::LINK],
+            "</v1/hub/users/3?limit=5>; rel=\"successor-version\""
+        );
+        // Legacy bodies keep their original shape
+        assert_eq!(
+            body_json(&body),
+            json!({"success": true, "data": {"fid": 3}})
+        );
+
+        let (_, _, body) = call("GET", "/api/hub/missing").await;
+        assert_eq!(
+            body_json(&body),
+            json!({"success": false, "error": "No user"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_real_v1_routes_are_enveloped() {
+        let router = mock_router(true).await;
+
+        let (status, headers, body) = get_route(&router, "/v1/hub/users/3").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[API_VERSION_HEADER], "v1");
+        assert!(headers.get("deprecation").is_none());
+        assert_eq!(
+            body,
+            json!({
+                "api_version": "v1",
+                "success": true,
+                "data": user_data(),
+                "error": null
+            })
+        );
+
+        let (_, headers, body) = get_route(&router, "/v1/hub/users/3/profile").await;
+        assert_eq!(headers[API_VERSION_HEADER], "v1");
+        assert_eq!(body["api_version"], "v1");
+        assert_eq!(body["success"], true);
+        assert_eq!(body["data"]["username"], "alice");
+        assert_eq!(body["error"], Value::Null);
+
+        let (_, headers, body) = get_route(&router, "/v1/hub/spam/4").await;
+        assert_eq!(headers[API_VERSION_HEADER], "v1");
+        assert_eq!(
+            body,
+            json!({
+                "api_version": "v1",
+                "success": true,
+                "data": {
+                    "fid": 4,
+                    "status": "not_spam",
+                    "label_value": 2,
+                    "labeled_at": 1_700_000_000
+                },
+                "error": null
+            })
+        );
+
+        let (status, _, body) = get_route(&router, "/v1/hub/users/5").await;
+        assert!(!status.is_success());
+        assert_eq!(body["api_version"], "v1");
+        assert_eq!(body["success"], false);
+        assert_eq!(body["data"], Value::Null);
+        assert!(body["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_real_legacy_routes_are_deprecated() {
+        let router = mock_router(true).await;
+        for (route, data) in [
+            ("/hub/users/3", user_data()),
+            ("/hub/users/3/profile", user_data()),
+            ("/hub/spam/3", json!({"fid": 3, "is_spam": true})),
+        ] {
+            let (status, headers, body) = get_route(&router, &format!("/api{route}")).await;
+            assert_eq!(status, StatusCode::OK, "{route}");
+            assert_eq!(headers["deprecation"], "true", "{route}");
+            assert_eq!(
+                headers[header::LINK],
+                format!("</v1{route}>; rel=\"successor-version\""),
+                "{route}"
+            );
+            assert!(headers.get(API_VERSION_HEADER).is_none(), "{route}");
+            assert_eq!(body, json!({"success": true, "data": data}), "{route}");
+        }
+    }
+
+    #[test]
+    fn test_wrap_plain_json() {
+        let wrapped = V1Response::wrap(json!({"status": "ok"}), true);
+        assert_eq!(
+            serde_json::to_value(wrapped).unwrap(),
+            json!({
+                "api_version": "v1",
+                "success": true,
+                "data": {"status": "ok"},
+                "error": null
+            })
+        );
+    }
+}
@@ -0,0 +1,79 @@
+// Mock Farcaster hub serving recorded fixtures
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use castorix::core::client::MockHub;
+use clap::Parser;
+
+/// 🧪 Serve recorded hub responses so the CLI can run without a network
+///
+/// Point castorix at it with --hub-url. With --record, requests are forwarded
+/// to a real hub and its responses are saved as fixtures for later replay.
+///
+/// Example: mock-hub --fixtures tests/fixtures/hub
+/// Example: mock-hub --fixtures my-fixtures --record https://hub.example.com
+#[derive(Parser)]
+#[command(name = "mock-hub", version)]
+struct Args {
+    /// Directory holding one JSON fixture per request
+    #[arg(long, default_value = "tests/fixtures/hub")]
+    fixtures: PathBuf,
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    host: IpAddr,
+    /// Port to listen on (0 picks a free port)
+    #[arg(long, default_value = "2281")]
+    port: u16,
+    /// Forward requests to this hub and record its responses
+    #[arg(long, value_name = "HUB_URL")]
+    record: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let hub = match &args.record {
+        Some(hub_url) => MockHub::recording(&args.fixtures, hub_url)?,
+        None => MockHub::load(&args.fixtures)?,
+    };
+    let fixtures = hub.len();
+    let recording = hub.is_recording();
+    let addr = hub.serve(SocketAddr::new(args.host, args.port)).await?;
+
+    match &args.record {
+        Some(hub_url) => println!(
+            "🎙️  Recording {hub_url} into {} ({fixtures} existing fixtures)",
+            args.fixtures.display()
+        ),
+        None => println!(
+            "🧪 Replaying {fixtures} fixtures from {}",
+            args.fixtures.display()
+        ),
+    }
+    println!("🌐 Mock hub listening on http://{addr}");
+    if !recording && fixtures == 0 {
+        println!("⚠️  No fixtures found; every request will return 404");
+    }
+    println!("💡 Use it with: castorix --hub-url http://{addr} <command>");
+
+    tokio::signal::ctrl_c().await?;
+    Ok(())
+}
@@ -0,0 +1,513 @@
+//! Command aliases and did-you-mean suggestions
+//!
+//! Before clap sees the arguments, the first positional one is looked up in
+//! the user's aliases (stored in `<data dir>/config.json`) and then in the
+//! built-in ones, and replaced by its expansion: `castorix p 3` runs
+//! `castorix hub profile 3`. Real command names always win over aliases.
+//!
+//! Watchlist nicknames are resolved in the same pass: any argument of the form
+//! `@name` (or `--flag=@name`) naming a watched account is replaced by its FID,
+//! so `castorix hub casts @alice` works wherever a FID is expected.
+//!
+//! The active context (see [`crate::cli::context`]) is applied next, filling in
+//! its FID, wallet and hub where the command line leaves them out.
+//!
+//! Any other name given where a FID is expected (`hub profile dwr`,
+//! `hub profile vitalik.eth`) is resolved through the hub once clap rejects it
+//! as a number; see [`crate::core::client::NameResolver`].
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::error::ContextKind;
+use clap::error::ContextValue;
+use clap::error::ErrorKind;
+use clap::CommandFactory;
+use clap::FromArgMatches;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::cli::commands::Cli;
+use crate::cli::context;
+use crate::core::client::name_resolver::looks_like_name;
+use crate::core::client::name_resolver::NameResolver;
+use crate::core::utils::data_dir;
+
+/// Aliases available without any configuration
+pub const BUILTIN_ALIASES: &[(&str, &str)] = &[
+    ("p", "hub profile"),
+    ("f", "hub followers"),
+    ("fg", "hub following"),
+    ("u", "hub user"),
+    ("c", "hub casts"),
+    ("st", "hub stats"),
+];
+
+/// Maximum edit distance for a did-you-mean suggestion
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Usernames resolved in one command line at most
+const MAX_NAME_RESOLUTIONS: usize = 8;
+
+/// Settings persisted in `<data dir>/config.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CliConfig {
+    /// User defined aliases, name -> space separated expansion
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    /// Watched accounts, nickname -> FID
+    #[serde(default)]
+    pub watchlist: BTreeMap<String, u64>,
+    /// Named contexts, see [`crate::cli::context`]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: BTreeMap<String, context::Context>,
+    /// Context commands default to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_context: Option<String>,
+    /// Keys written by other versions are kept as they are
+    #[serde(flatten)]
+    other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl CliConfig {
+    /// Location of the config file
+    pub fn path() -> PathBuf {
+        data_dir::data_dir().join("config.json")
+    }
+
+    /// Load the config file, starting empty if it does not exist
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Invalid config file {}", path.display())),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Write the config file
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Names (and clap aliases) of the top-level commands
+pub fn command_names() -> Vec<String> {
+    Cli::command()
+        .get_subcommands()
+        .flat_map(|c| std::iter::once(c.get_name()).chain(c.get_all_aliases()))
+        .map(|n| n.to_string())
+        .collect()
+}
+
+/// Parse the command line with alias expansion and extended suggestions
+///
+/// Exits with clap's usual message on a parse error.
+pub async fn parse_with_aliases() -> Cli {
+    let args: Vec<String> = std::env::args().collect();
+
+    // Aliases live in the data dir, which depends on --path/--profile
+    let (path, profile) = scan_data_dir_flags(&args);
+    let config = data_dir::init(path.as_deref(), profile.as_deref())
+        .ok()
+        .and_then(|_| CliConfig::load().ok())
+        .unwrap_or_default();
+    let managing_contexts = command_position(&args).is_some_and(|i| args[i] == "context");
+    let context = match context::active(&config, scan_flag(&args, "--context")) {
+        Ok(context) => context,
+        // A missing context must stay fixable with the context commands
+        Err(_) if managing_contexts => None,
+        Err(e) => {
+            eprintln!("❌ {e:#}");
+            std::process::exit(2)
+        }
+    };
+    // Say so once whenever the context supplies something, so defaults never act silently
+    let mut announced = false;
+    let mut announce = |name: &str| {
+        if !std::mem::replace(&mut announced, true) {
+            eprintln!("🪪 Using context '{name}'");
+        }
+    };
+    let user_aliases = config.aliases;
+
+    let mut args = expand_args(args, &user_aliases, &command_names());
+    if let Some((name, context)) = &context {
+        let applied = context::apply_defaults(args.clone(), context);
+        if applied != args {
+            announce(name);
+            args = applied;
+        }
+    }
+    let mut args = resolve_watch_names(args, &config.watchlist);
+    let mut command = Cli::command();
+    let mut resolver = None;
+    for _ in 0..=MAX_NAME_RESOLUTIONS {
+        let result = command
+            .try_get_matches_from_mut(&args)
+            .and_then(|mut matches| Cli::from_arg_matches_mut(&mut matches));
+
+        let mut error = match result {
+            Ok(cli) => {
+                if let Some(Err(e)) = resolver.as_ref().map(NameResolver::save) {
+                    tracing::debug!("Failed to save name cache: {e}");
+                }
+                return cli;
+            }
+            Err(error) => error,
+        };
+        if error.kind() == ErrorKind::InvalidSubcommand {
+            add_alias_suggestions(&mut error, &user_aliases);
+        }
+        if let Some((name, context::Context { fid: Some(fid), .. })) = &context {
+            if let Some(filled) = context::fill_missing_fid(&args, &error, *fid) {
+                announce(name);
+                args = filled;
+                continue;
+            }
+        }
+        let Some(name) = rejected_fid_name(&error) else {
+            error.format(&mut command).exit()
+        };
+        let resolver = resolver.get_or_insert_with(|| {
+            NameResolver::with_cache_file(
+                scan_flag(&args, "--hub-url")
+                    .unwrap_or_else(|| crate::consts::get_config().farcaster_hub_url().to_string()),
+                scan_flag(&args, "--eth-rpc-url")
+                    .unwrap_or_else(|| crate::consts::get_config().eth_rpc_url().to_string()),
+                NameResolver::default_cache_path(),
+            )
+        });
+        match resolver.resolve(&name).await {
+            Ok(fid) => args = replace_arg_value(args, &name, &fid.to_string()),
+            Err(e) => {
+                eprintln!("❌ Could not resolve '{name}' to a FID: {e}");
+                error.format(&mut command).exit()
+            }
+        }
+    }
+    eprintln!("❌ Too many usernames to resolve in one command");
+    std::process::exit(2)
+}
+
+/// The value clap rejected, if it is a username given for a FID argument
+fn rejected_fid_name(error: &clap::Error) -> Option<String> {
+    if error.kind() != ErrorKind::ValueValidation {
+        return None;
+    }
+    let Some(ContextValue::String(arg)) = error.get(ContextKind::InvalidArg) else {
+        return None;
+    };
+    let Some(ContextValue::String(value)) = error.get(ContextKind::InvalidValue) else {
+        return None;
+    };
+    let is_fid_arg = arg.to_lowercase().contains("fid");
+    (is_fid_arg && looks_like_name(value)).then(|| value.clone())
+}
+
+/// Replace the first argument (or `--flag=` value) equal to `value`
+fn replace_arg_value(mut args: Vec<String>, value: &str, replacement: &str) -> Vec<String> {
+    for arg in args.iter_mut().skip(1) {
+        if arg == value {
+            *arg = replacement.to_string();
+            break;
+        }
+        if let Some((flag, flag_value)) = arg.split_once('=') {
+            if flag.starts_with("--") && flag_value == value {
+                *arg = format!("{flag}={replacement}");
+                break;
+            }
+        }
+    }
+    args
+}
+
+/// Add aliases close to an unknown subcommand to clap's suggestions
+fn add_alias_suggestions(error: &mut clap::Error, user_aliases: &BTreeMap<String, String>) {
+    let Some(ContextValue::String(input)) = error.get(ContextKind::InvalidSubcommand) else {
+        return;
+    };
+    let candidates: Vec<String> = all_aliases(user_aliases)
+        .into_keys()
+        .chain(command_names())
+        .collect();
+    let mut suggested = match error.get(ContextKind::SuggestedSubcommand) {
+        Some(ContextValue::Strings(existing)) => existing.clone(),
+        Some(ContextValue::String(existing)) => vec![existing.clone()],
+        _ => Vec::new(),
+    };
+    for candidate in suggest(input, &candidates) {
+        if !suggested.contains(&candidate) {
+            suggested.push(candidate);
+        }
+    }
+    if !suggested.is_empty() {
+        error.insert(
+            ContextKind::SuggestedSubcommand,
+            ContextValue::Strings(suggested),
+        );
+    }
+}
+
+/// Built-in aliases overlaid with the user's own
+pub fn all_aliases(user_aliases: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    let mut aliases: BTreeMap<String, String> = BUILTIN_ALIASES
+        .iter()
+        .map(|(name, expansion)| (name.to_string(), expansion.to_string()))
+        .collect();
+    aliases.extend(user_aliases.clone());
+    aliases
+}
+
+/// Replace an alias in the command position with its expansion
+///
+/// # Arguments
+/// * `args` - Full argument list including the program name
+/// * `user_aliases` - User defined aliases, taking precedence over built-ins
+/// * `commands` - Real command names, which are never treated as aliases
+///
+/// # Returns
+/// * `Vec<String>` - The arguments with at most one alias expanded
+pub fn expand_args(
+    args: Vec<String>,
+    user_aliases: &BTreeMap<String, String>,
+    commands: &[String],
+) -> Vec<String> {
+    let Some(index) = command_position(&args) else {
+        return args;
+    };
+    let name = &args[index];
+    if commands.contains(name) {
+        return args;
+    }
+    let Some(expansion) = all_aliases(user_aliases).remove(name) else {
+        return args;
+    };
+
+    let mut expanded = args[..index].to_vec();
+    expanded.extend(expansion.split_whitespace().map(|s| s.to_string()));
+    expanded.extend(args[index + 1..].iter().cloned());
+    expanded
+}
+
+/// Replace `@name` arguments naming watched accounts with their FIDs
+///
+/// Unknown names are left alone, so text such as a cast mentioning `@someone`
+/// passes through unchanged.
+///
+/// # Arguments
+/// * `args` - Full argument list including the program name
+/// * `watchlist` - Watched accounts, nickname -> FID
+///
+/// # Returns
+/// * `Vec<String>` - The arguments with nicknames resolved
+pub fn resolve_watch_names(args: Vec<String>, watchlist: &BTreeMap<String, u64>) -> Vec<String> {
+    if watchlist.is_empty() {
+        return args;
+    }
+    let resolve = |value: &str| {
+        value
+            .strip_prefix('@')
+            .and_then(|name| watchlist.get(name))
+            .map(|fid| fid.to_string())
+    };
+    args.into_iter()
+        .map(|arg| {
+            if let Some(fid) = resolve(&arg) {
+                return fid;
+            }
+            if let Some((flag, value)) = arg.split_once('=') {
+                if flag.starts_with("--") {
+                    if let Some(fid) = resolve(value) {
+                        return format!("{flag}={fid}");
+                    }
+                }
+            }
+            arg
+        })
+        .collect()
+}
+
+/// Index of the first positional argument, skipping global options and their values
+pub(crate) fn command_position(args: &[String]) -> Option<usize> {
+    let valued_flags: Vec<String> = Cli::command()
+        .get_arguments()
+        .filter(|a| a.get_action().takes_values())
+        .filter_map(|a| a.get_long().map(|l| format!("--{l}")))
+        .collect();
+
+    let mut i = 1;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--" {
+            return None;
+        }
+        if !arg.starts_with('-') {
+            return Some(i);
+        }
+        if valued_flags.contains(arg) {
+            i += 1;
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Value of a global flag, read before full parsing
+fn scan_flag(args: &[String], flag: &str) -> Option<String> {
+    let prefix = format!("{flag}=");
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        if let Some(value) = arg.strip_prefix(&prefix) {
+            return Some(value.to_string());
+        }
+        if arg == flag {
+            return iter.next().cloned();
+        }
+    }
+    None
+}
+
+/// Values of --path and --profile, read before full parsing
+fn scan_data_dir_flags(args: &[String]) -> (Option<String>, Option<String>) {
+    let mut path = None;
+    let mut profile = None;
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        if let Some(value) = arg.strip_prefix("--path=") {
+            path = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--profile=") {
+            profile = Some(value.to_string());
+        } else if arg == "--path" {
+            path = iter.next().cloned();
+        } else if arg == "--profile" {
+            profile = iter.next().cloned();
+        }
+    }
+    (path, profile)
+}
+
+/// Candidates within a small edit distance of `input`, closest first
+pub fn suggest(input: &str, candidates: &[String]) -> Vec<String> {
+    let mut scored: Vec<(usize, &String)> = candidates
+        .iter()
+        .map(|c| (edit_distance(input, c), c))
+        .filter(|(d, c)| *d <= MAX_SUGGESTION_DISTANCE && *d < c.len().max(input.len()))
+        .collect();
+    scored.sort();
+    scored.dedup_by(|a, b| a.1 == b.1);
+    scored.into_iter().map(|(_, c)| c.clone()).collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_builtin_alias_expands_after_global_flags() {
+        let expanded = expand_args(
+            args(&["castorix", "--hub-url", "http://hub", "p", "3"]),
+            &BTreeMap::new(),
+            &command_names(),
+        );
+        assert_eq!(
+            expanded,
+            args(&["castorix", "--hub-url", "http://hub", "hub", "profile", "3"])
+        );
+    }
+
+    #[test]
+    fn test_user_alias_overrides_builtin_but_not_commands() {
+        let mut user = BTreeMap::new();
+        user.insert("p".to_string(), "hub user".to_string());
+        user.insert("hub".to_string(), "key list".to_string());
+
+        let expanded = expand_args(args(&["castorix", "p", "3"]), &user, &command_names());
+        assert_eq!(expanded, args(&["castorix", "hub", "user", "3"]));
+
+        let untouched = expand_args(args(&["castorix", "hub", "info"]), &user, &command_names());
+        assert_eq!(untouched, args(&["castorix", "hub", "info"]));
+    }
+
+    #[test]
+    fn test_watch_names_resolve_to_fids() {
+        let mut watchlist = BTreeMap::new();
+        watchlist.insert("alice".to_string(), 3);
+
+        let resolved = resolve_watch_names(
+            args(&["castorix", "hub", "casts", "@alice", "--fid=@alice", "@bob"]),
+            &watchlist,
+        );
+        assert_eq!(
+            resolved,
+            args(&["castorix", "hub", "casts", "3", "--fid=3", "@bob"])
+        );
+    }
+
+    #[test]
+    fn test_rejected_fid_names() {
+        let parse = |list: &[&str]| Cli::command().try_get_matches_from(args(list)).unwrap_err();
+        assert_eq!(
+            rejected_fid_name(&parse(&["castorix", "hub", "profile", "dwr"])).as_deref(),
+            Some("dwr")
+        );
+        assert_eq!(
+            rejected_fid_name(&parse(&["castorix", "hub", "profile", "vitalik.eth"])).as_deref(),
+            Some("vitalik.eth")
+        );
+        assert_eq!(
+            rejected_fid_name(&parse(&[
+                "castorix",
+                "hub",
+                "followers",
+                "1",
+                "--limit",
+                "x"
+            ])),
+            None
+        );
+        assert_eq!(
+            replace_arg_value(args(&["castorix", "hub", "profile", "dwr"]), "dwr", "3"),
+            args(&["castorix", "hub", "profile", "3"])
+        );
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let candidates = command_names();
+        assert_eq!(
+            suggest("hbu", &candidates).first().map(String::as_str),
+            Some("hub")
+        );
+        assert!(suggest("completely-unknown", &candidates).is_empty());
+    }
+}
@@ -1,15 +1,30 @@
 use clap::Parser;
 use clap::Subcommand;
 
+use crate::cli::types::AliasCommands;
 use crate::cli::types::ApiCommands;
+use crate::cli::types::AuditCommands;
+use crate::cli::types::BenchCommands;
+use crate::cli::types::ChannelCommands;
+use crate::cli::types::ContextCommands;
 use crate::cli::types::CustodyCommands;
+#[cfg(feature = "dev")]
+use crate::cli::types::DevCommands;
 use crate::cli::types::EnsCommands;
 use crate::cli::types::FidCommands;
 use crate::cli::types::HubCommands;
+use crate::cli::types::IndexerCommands;
 use crate::cli::types::KeyCommands;
 use crate::cli::types::McpCommands;
+use crate::cli::types::MonitorCommands;
+use crate::cli::types::PluginCommands;
+use crate::cli::types::SelfCommands;
 use crate::cli::types::SignersCommands;
 use crate::cli::types::StorageCommands;
+use crate::cli::types::TierCommands;
+use crate::cli::types::TxCommands;
+use crate::cli::types::UtilCommands;
+use crate::cli::types::WatchlistCommands;
 
 /// Castorix - Farcaster ENS Domain Proof Tool
 /// A comprehensive tool for managing private keys, creating ENS domain proofs, and interacting with Farcaster Hub
@@ -51,15 +66,102 @@ Examples:
   
   # Use custom storage path
   castorix --path /custom/path key generate-encrypted my-wallet "My Wallet"
+
+  # Keep work keys separate from personal ones
+  castorix --profile work custody list
+
+  # Query a different hub for one command
+  castorix --hub-url http://localhost:2281 hub user 12345
+
+  # Read casts from a downloaded snapshot without network access
+  castorix --offline ./snapshot hub casts 12345
+
+  # Emoji-free output for logs and screen readers
+  castorix --plain hub casts 12345
   
 For more information, visit: https://github.com/your-repo/castorix
 "#)]
 pub struct Cli {
     /// Custom path for storing encrypted keys and configuration files
-    /// If not specified, uses the default system directory (~/.castorix/)
+    /// If not specified, uses CASTORIX_DATA_DIR or the default system directory (~/.castorix/)
     #[arg(long, global = true, value_name = "PATH")]
     pub path: Option<String>,
 
+    /// Profile whose keys and data to use, stored under <PATH>/profiles/<NAME>
+    /// (overrides CASTORIX_PROFILE; "default" uses the base directory itself)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Farcaster Hub URL for this command (overrides FARCASTER_HUB_URL)
+    #[arg(long, global = true, value_name = "URL")]
+    pub hub_url: Option<String>,
+
+    /// Ethereum RPC URL for this command (overrides ETH_RPC_URL, or ETH_OP_RPC_URL
+    /// for commands that call the Farcaster contracts on Optimism)
+    #[arg(long, global = true, value_name = "URL")]
+    pub eth_rpc_url: Option<String>,
+
+    /// Answer hub queries from a local snapshot export instead of a live hub
+    /// (a .json/.jsonl/.pb file or a directory of them)
+    #[arg(
+        long,
+        global = true,
+        value_name = "SNAPSHOT",
+        conflicts_with = "hub_url"
+    )]
+    pub offline: Option<String>,
+
+    /// Plain output without emoji or colors, for logs and screen readers
+    #[arg(long, global = true)]
+    pub plain: bool,
+
+    /// Keep only these fields of JSON output: comma-separated paths such as
+    /// data.fid,data.timestamp, with [N] or [*] for array elements
+    #[arg(long, global = true, value_name = "PATHS")]
+    pub fields: Option<String>,
+
+    /// Print JSON output on a single line instead of pretty-printed
+    #[arg(long, global = true)]
+    pub compact: bool,
+
+    /// Wait for a transaction to have N confirmations, or to be covered by the
+    /// "safe" or "finalized" block, before reporting success
+    /// (overrides CASTORIX_WAIT_FOR)
+    #[arg(long, global = true, value_name = "N|safe|finalized")]
+    pub wait_for: Option<String>,
+
+    /// Submit at most this many hub messages per minute across casts,
+    /// reactions, links and proofs; 0 for no limit
+    /// (overrides CASTORIX_WRITE_RATE, default 60)
+    #[arg(long, global = true, value_name = "N")]
+    pub write_rate: Option<u32>,
+
+    /// Context to use for this command instead of the active one
+    /// (overrides CASTORIX_CONTEXT)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub context: Option<String>,
+
+    /// Time RPC calls, hub requests, signing and disk access, and print a
+    /// summary of where the time went on stderr when the command finishes
+    #[arg(long, global = true)]
+    pub timing: bool,
+
+    /// Report which hub served each request (and any failover) on stderr;
+    /// fallback hubs and API keys are read from <PATH>/hubs.json
+    #[arg(long, global = true)]
+    pub verbose: bool,
+
+    /// Fail instead of prompting for passwords, confirmations or other input,
+    /// printing what was required as JSON on stderr (exit code 3); also
+    /// enabled by CASTORIX_NON_INTERACTIVE=1
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
+
+    /// Language of CLI output: a code looked up in <PATH>/locales/<LANG>.json,
+    /// or a path to a message catalog (overrides CASTORIX_LANG)
+    #[arg(long, global = true, value_name = "LANG")]
+    pub lang: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -124,6 +226,35 @@ pub enum Commands {
         #[command(subcommand)]
         action: StorageCommands,
     },
+    /// 💎 Farcaster Pro subscriptions
+    ///
+    /// Check subscription tiers of Farcaster IDs, price them, and buy or extend
+    /// them through the TierRegistry on Base.
+    Tier {
+        #[command(subcommand)]
+        action: TierCommands,
+    },
+    /// 🧊 Air-gapped transactions
+    ///
+    /// Build a transaction on an online machine, sign it on an offline one
+    /// holding the encrypted key, and broadcast it from an online one again.
+    /// Covers FID registration, storage rent and adding or removing signers.
+    ///
+    /// Example: castorix tx build rent 12345 --units 1 --from 0x... -o rent.json
+    /// Example: castorix tx sign rent.json -o rent.signed.json
+    /// Example: castorix tx broadcast rent.signed.json
+    Tx {
+        #[command(subcommand)]
+        action: TxCommands,
+    },
+    /// 🗂️ Local index of Farcaster registry events
+    ///
+    /// Scan IdRegistry, KeyRegistry and StorageRegistry logs on Optimism into a
+    /// local store and query them without a hub's onchain-event endpoints.
+    Indexer {
+        #[command(subcommand)]
+        action: IndexerCommands,
+    },
     /// 🔌 MCP (Model Context Protocol) server
     ///
     /// Run MCP server to expose Farcaster query capabilities to AI assistants.
@@ -140,6 +271,247 @@ pub enum Commands {
         #[command(subcommand)]
         action: ApiCommands,
     },
+    /// 📈 Load testing
+    ///
+    /// Drive concurrent request patterns against a running REST API server and
+    /// report latency percentiles and error rates per route.
+    Bench {
+        #[command(subcommand)]
+        action: BenchCommands,
+    },
+    /// 🧪 Local development helpers
+    ///
+    /// Utilities for working against a local Anvil node, such as funding test wallets.
+    /// These commands refuse to run against non-local chains. Only built with
+    /// the `dev` feature.
+    #[cfg(feature = "dev")]
+    Dev {
+        #[command(subcommand)]
+        action: DevCommands,
+    },
+    /// 👀 Monitor a FID and deliver webhook notifications
+    ///
+    /// Poll the Farcaster Hub for new followers, mentions, recasts and signer changes
+    /// of a FID. Events are POSTed as JSON to the webhook (with retry), or printed
+    /// as JSON lines when no webhook is given. `monitor contracts` watches the
+    /// protocol contracts instead.
+    ///
+    /// Example: castorix monitor --fid 12345 --webhook https://example.com/hook
+    /// Example: castorix monitor contracts --interval 60 --webhook https://example.com/hook
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Monitor {
+        #[command(subcommand)]
+        target: Option<MonitorCommands>,
+
+        /// FID to monitor
+        #[arg(long, required = true)]
+        fid: Option<u64>,
+
+        /// Webhook URL receiving JSON notifications
+        #[arg(long)]
+        webhook: Option<String>,
+
+        /// Polling interval in seconds
+        #[arg(long, default_value = "30")]
+        interval: u64,
+
+        /// Maximum delivery retries per notification
+        #[arg(long, default_value = "5")]
+        max_retries: u32,
+    },
+    /// 📰 Publish casts from an RSS, Atom or JSON feed
+    ///
+    /// Poll a feed every --interval seconds, render each new item through the
+    /// template and publish it as a cast signed by the FID's Ed25519 signer.
+    /// Items already published are remembered in the data directory, so
+    /// restarts never post twice. On the first run the items already in the
+    /// feed are only recorded, unless --post-existing is given. Template
+    /// placeholders: {title} {link} {summary} {id} {published}.
+    ///
+    /// Example: castorix autocast --fid 12345 --feed https://blog.example/rss --template "New post: {title} {link}"
+    /// Example: castorix autocast --fid 12345 --feed https://blog.example/feed.json --template "{title}" --embed-link --once
+    Autocast {
+        /// FID publishing the casts
+        #[arg(long)]
+        fid: u64,
+
+        /// URL of the RSS, Atom or JSON feed
+        #[arg(long)]
+        feed: String,
+
+        /// Cast text with placeholders such as {title} and {link}
+        #[arg(long)]
+        template: String,
+
+        /// Polling interval in seconds
+        #[arg(long, default_value = "900")]
+        interval: u64,
+
+        /// Most casts published per poll; the rest wait for the next poll
+        #[arg(long, default_value = "1")]
+        max_per_poll: usize,
+
+        /// Embed each item's link so clients show a preview
+        #[arg(long)]
+        embed_link: bool,
+
+        /// On the first run, publish items already in the feed
+        #[arg(long)]
+        post_existing: bool,
+
+        /// Poll once and exit, e.g. when run from cron
+        #[arg(long)]
+        once: bool,
+
+        /// Show the casts that would be published without submitting or recording anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 🪪 Render a shareable profile page
+    ///
+    /// Build a static HTML summary of a FID (avatar, bio, recent casts) from the
+    /// REST API hub handlers. Write it to a file or stdout, or serve it on
+    /// localhost with --serve for quick demos.
+    ///
+    /// Example: castorix share 12345 --serve
+    /// Example: castorix share 12345 --output profile.html
+    Share {
+        /// FID to render
+        fid: u64,
+
+        /// Serve the page over HTTP instead of writing it out
+        #[arg(long)]
+        serve: bool,
+
+        /// Host to bind when serving
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Port to bind when serving
+        #[arg(long, default_value = "8080")]
+        port: u16,
+
+        /// Write the HTML to this file instead of stdout
+        #[arg(long, short = 'o', conflicts_with = "serve")]
+        output: Option<String>,
+
+        /// Number of recent casts to include
+        #[arg(long, default_value = "10")]
+        casts: usize,
+    },
+    /// 🔁 Copy the messages of a FID from one hub to another
+    ///
+    /// Read every cast, reaction, link, verification and user data message of
+    /// a FID from the source hub and submit the ones the target is missing,
+    /// oldest first. Use it to seed a self-hosted hub such as a new Snapchain
+    /// node. The target must already know the FID's signers, which hubs sync
+    /// from the chain. Nothing is signed: messages are submitted as they are.
+    ///
+    /// Example: castorix replay --from https://hub.example --to http://localhost:3381 --fid 12345
+    Replay {
+        /// Hub to read messages from
+        #[arg(long)]
+        from: String,
+
+        /// Hub to submit messages to
+        #[arg(long)]
+        to: String,
+
+        /// FID whose messages are copied
+        #[arg(long)]
+        fid: u64,
+
+        /// Show what would be submitted without submitting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 🏷️ Manage command aliases
+    ///
+    /// Short aliases such as `p` (hub profile) and `f` (hub followers) are built
+    /// in; define your own with `alias set`.
+    ///
+    /// Example: castorix p 12345
+    Alias {
+        #[command(subcommand)]
+        action: AliasCommands,
+    },
+    /// 🪪 Switch between identities
+    ///
+    /// A context names a FID, the wallet holding its custody key and a hub.
+    /// While one is active, commands default to it: a missing FID is filled
+    /// in, `@me` stands for its FID, and --wallet and --hub-url default to its
+    /// wallet and hub.
+    ///
+    /// Example: castorix context set main --fid 12345 --wallet my-wallet --use
+    /// Example: castorix hub casts
+    /// Example: castorix hub cast @me "gm"
+    Context {
+        #[command(subcommand)]
+        action: ContextCommands,
+    },
+    /// 📜 Review the audit log of signatures
+    ///
+    /// Every signature castorix makes (hub messages, wallet signatures and
+    /// transactions) is appended to a hash-chained log in the data directory.
+    ///
+    /// Example: castorix audit show
+    /// Example: castorix audit verify
+    Audit {
+        #[command(subcommand)]
+        action: AuditCommands,
+    },
+    /// 📺 Channel membership and moderation
+    ///
+    /// Channel members, moderators, bans and restrictions are kept by Warpcast,
+    /// not by hubs, and are read from its public API (CASTORIX_WARPCAST_API_URL
+    /// to use another).
+    ///
+    /// Example: castorix channel moderators rust
+    /// Example: castorix channel memberships 12345
+    Channel {
+        #[command(subcommand)]
+        action: ChannelCommands,
+    },
+    /// 👀 Watch accounts under local nicknames
+    ///
+    /// Watched accounts can be referred to as `@name` wherever a FID is expected.
+    ///
+    /// Example: castorix watchlist add 3 --name dwr
+    /// Example: castorix watchlist status
+    Watchlist {
+        #[command(subcommand)]
+        action: WatchlistCommands,
+    },
+    /// 🔏 Verify or update the castorix binary
+    ///
+    /// Check the running binary against a minisign-signed release manifest,
+    /// or download, verify and install a newer release.
+    #[command(name = "self")]
+    SelfCmd {
+        #[command(subcommand)]
+        action: SelfCommands,
+    },
+    /// 🧰 Standalone utilities
+    ///
+    /// Example: castorix util decode-message 0a4a0801...
+    Util {
+        #[command(subcommand)]
+        action: UtilCommands,
+    },
+    /// 🧩 Manage plugins
+    ///
+    /// Any other command runs the castorix-<command> plugin, passing the
+    /// remaining arguments through and a JSON description of the invocation
+    /// in CASTORIX_PLUGIN_CONTEXT.
+    ///
+    /// Example: castorix plugin list
+    Plugin {
+        #[command(subcommand)]
+        action: PluginCommands,
+    },
+    /// A command provided by a plugin
+    #[command(external_subcommand)]
+    External(Vec<String>),
 }
 
 impl Cli {
@@ -0,0 +1,374 @@
+//! Named contexts: the identity commands act as unless told otherwise
+//!
+//! A context bundles a FID, the encrypted wallet holding its custody key and
+//! the hub to talk to. Contexts are stored in `<data dir>/config.json` and
+//! one of them is active, chosen with `castorix context use <name>` (or for
+//! one command with `--context <name>` / `CASTORIX_CONTEXT`).
+//!
+//! Like aliases, the active context is applied to the arguments before clap
+//! parses them:
+//! - a required FID that is missing is filled in, so `castorix hub casts`
+//!   lists the context's casts;
+//! - `@me` given for a FID argument stands for the context's FID, for
+//!   commands where the FID comes before other positional arguments
+//!   (`castorix hub cast @me "gm"`); anywhere else, such as cast text, it is
+//!   left as written;
+//! - commands taking `--wallet` or `--wallet-name` get the context's wallet
+//!   unless one is given;
+//! - `--hub-url` defaults to the context's hub.
+//!
+//! Ed25519 signers are stored per FID, so the context's FID also selects the
+//! signer.
+
+use anyhow::Result;
+use clap::error::ContextKind;
+use clap::error::ContextValue;
+use clap::error::ErrorKind;
+use clap::CommandFactory;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::cli::aliases::command_position;
+use crate::cli::aliases::CliConfig;
+use crate::cli::commands::Cli;
+use crate::consts::env_vars;
+
+/// Argument standing for the active context's FID
+pub const ME: &str = "@me";
+
+/// Wallet flags filled in from the context, in order of preference
+const WALLET_FLAGS: [&str; 2] = ["wallet-name", "wallet"];
+
+/// An identity commands default to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Context {
+    /// FID used where a command needs one and none is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fid: Option<u64>,
+    /// Encrypted wallet (custody key) used by commands taking `--wallet`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<String>,
+    /// Hub URL used unless `--hub-url` is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hub_url: Option<String>,
+}
+
+impl Context {
+    /// Whether the context sets nothing
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The context in force: `--context`, else `CASTORIX_CONTEXT`, else the one
+/// chosen with `context use`
+///
+/// # Returns
+/// * `Result<Option<(String, Context)>>` - Its name and settings, or `None`
+///   when no context is active; an error when a named context does not exist
+pub fn active(config: &CliConfig, flag: Option<String>) -> Result<Option<(String, Context)>> {
+    let name = flag
+        .or_else(|| std::env::var(env_vars::CONTEXT).ok())
+        .filter(|name| !name.is_empty())
+        .or_else(|| config.active_context.clone());
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    match config.contexts.get(&name) {
+        Some(context) => Ok(Some((name, context.clone()))),
+        None => anyhow::bail!("No context named '{name}'; see `castorix context list`"),
+    }
+}
+
+/// Apply a context's defaults that need no parse error to show up: `@me`,
+/// the wallet and the hub URL
+///
+/// Context management commands themselves are left alone.
+pub fn apply_defaults(mut args: Vec<String>, context: &Context) -> Vec<String> {
+    let position = command_position(&args);
+    if position.is_some_and(|index| args[index] == "context") {
+        return args;
+    }
+    if let Some(fid) = context.fid {
+        replace_me(&mut args, fid);
+    }
+
+    if let Some(wallet) = &context.wallet {
+        let (leaf, _) = subcommand(&args);
+        let takes_wallet = |long: &str| {
+            leaf.get_arguments()
+                .any(|arg| arg.get_long() == Some(long) && arg.get_action().takes_values())
+        };
+        if let Some(flag) = WALLET_FLAGS.into_iter().find(|flag| takes_wallet(flag)) {
+            if !WALLET_FLAGS.iter().any(|flag| has_flag(&args, flag)) {
+                args.push(format!("--{flag}"));
+                args.push(wallet.clone());
+            }
+        }
+    }
+
+    if let Some(hub_url) = &context.hub_url {
+        if !has_flag(&args, "hub-url") && !has_flag(&args, "offline") && args.len() > 1 {
+            args.insert(1, "--hub-url".to_string());
+            args.insert(2, hub_url.clone());
+        }
+    }
+    args
+}
+
+/// Replace `@me` with `fid` where the subcommand expects a FID
+///
+/// Options are matched by name and positionals by their order, so `@me`
+/// given as free text (`castorix hub cast @me "@me"`) is left alone.
+fn replace_me(args: &mut [String], fid: u64) {
+    let (leaf, index) = subcommand(args);
+    let root = Cli::command();
+    let find = |flag: &str| {
+        leaf.get_arguments()
+            .chain(root.get_arguments())
+            .find(|arg| match flag.strip_prefix("--") {
+                Some(long) => arg.get_long() == Some(long),
+                None => flag.len() == 2 && arg.get_short() == flag.chars().nth(1),
+            })
+            .cloned()
+    };
+    let positionals: Vec<&clap::Arg> = leaf.get_positionals().collect();
+    let fid = fid.to_string();
+
+    let mut position = 0;
+    let mut options = true;
+    let mut i = index;
+    while i < args.len() {
+        let arg = args[i].clone();
+        if options && arg == "--" {
+            options = false;
+        } else if options && arg.starts_with('-') && arg.len() > 1 {
+            if let Some((flag, value)) = arg.split_once('=') {
+                if value == ME && find(flag).is_some_and(|arg| is_fid(&arg)) {
+                    args[i] = format!("{flag}={fid}");
+                }
+            } else if let Some(flag) = find(&arg).filter(|arg| arg.get_action().takes_values()) {
+                i += 1;
+                if args.get(i).is_some_and(|value| value == ME) && is_fid(&flag) {
+                    args[i] = fid.clone();
+                }
+            }
+        } else {
+            let target = positionals.get(position).or_else(|| {
+                positionals
+                    .last()
+                    .filter(|arg| matches!(arg.get_action(), clap::ArgAction::Append))
+            });
+            if arg == ME && target.is_some_and(|arg| is_fid(arg)) {
+                args[i] = fid.clone();
+            }
+            position += 1;
+        }
+        i += 1;
+    }
+}
+
+/// Whether an argument takes FIDs, judging by its name
+fn is_fid(arg: &clap::Arg) -> bool {
+    arg.get_id().as_str().to_lowercase().contains("fid")
+        || arg
+            .get_value_names()
+            .is_some_and(|names| names.iter().any(|name| name.to_lowercase().contains("fid")))
+}
+
+/// Fill in a missing required FID argument from the context
+///
+/// # Returns
+/// * `Option<Vec<String>>` - The arguments with the FID added, or `None` when
+///   the error is not (only) about missing FIDs
+pub fn fill_missing_fid(args: &[String], error: &clap::Error, fid: u64) -> Option<Vec<String>> {
+    if error.kind() != ErrorKind::MissingRequiredArgument {
+        return None;
+    }
+    let missing = match error.get(ContextKind::InvalidArg) {
+        Some(ContextValue::Strings(missing)) => missing.clone(),
+        Some(ContextValue::String(missing)) => vec![missing.clone()],
+        _ => return None,
+    };
+    if missing.is_empty() || !missing.iter().all(|arg| arg.to_lowercase().contains("fid")) {
+        return None;
+    }
+
+    let mut args = args.to_vec();
+    let fid = fid.to_string();
+    for arg in missing {
+        match arg.split_whitespace().next() {
+            // A flag such as `--fid <FID>`
+            Some(flag) if flag.starts_with("--") => {
+                args.push(flag.to_string());
+                args.push(fid.clone());
+            }
+            // A positional `<FID>`, the first one after the subcommand
+            _ => {
+                let (_, index) = subcommand(&args);
+                args.insert(index, fid.clone());
+            }
+        }
+    }
+    Some(args)
+}
+
+/// The subcommand the arguments select, and the index just after its name
+fn subcommand(args: &[String]) -> (clap::Command, usize) {
+    let mut command = Cli::command();
+    let Some(mut index) = command_position(args) else {
+        return (command, args.len());
+    };
+    while let Some(sub) = args
+        .get(index)
+        .and_then(|name| command.find_subcommand(name))
+        .cloned()
+    {
+        command = sub;
+        index += 1;
+    }
+    (command, index)
+}
+
+fn has_flag(args: &[String], long: &str) -> bool {
+    let flag = format!("--{long}");
+    let prefix = format!("{flag}=");
+    args.iter()
+        .skip(1)
+        .take_while(|arg| *arg != "--")
+        .any(|arg| *arg == flag || arg.starts_with(&prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn context() -> Context {
+        Context {
+            fid: Some(42),
+            wallet: Some("main".to_string()),
+            hub_url: Some("http://hub:2281".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_apply_defaults() {
+        let applied = apply_defaults(args(&["castorix", "hub", "cast", "@me", "gm"]), &context());
+        assert_eq!(
+            applied,
+            args(&[
+                "castorix",
+                "--hub-url",
+                "http://hub:2281",
+                "hub",
+                "cast",
+                "42",
+                "gm"
+            ])
+        );
+
+        let applied = apply_defaults(
+            args(&["castorix", "--hub-url", "http://other", "fid", "list"]),
+            &context(),
+        );
+        assert_eq!(
+            applied,
+            args(&[
+                "castorix",
+                "--hub-url",
+                "http://other",
+                "fid",
+                "list",
+                "--wallet",
+                "main"
+            ])
+        );
+
+        let given = args(&["castorix", "--offline", "snap", "fid", "list", "--wallet=x"]);
+        assert_eq!(apply_defaults(given.clone(), &context()), given);
+    }
+
+    #[test]
+    fn test_me_only_replaces_fids() {
+        let applied = apply_defaults(
+            args(&["castorix", "--offline", "snap", "hub", "cast", "@me", "@me"]),
+            &context(),
+        );
+        assert_eq!(
+            applied,
+            args(&["castorix", "--offline", "snap", "hub", "cast", "42", "@me"])
+        );
+
+        let applied = apply_defaults(
+            args(&[
+                "castorix",
+                "--offline",
+                "snap",
+                "hub",
+                "cast",
+                "@me",
+                "--parent-url",
+                "@me",
+                "--",
+                "@me",
+            ]),
+            &context(),
+        );
+        assert_eq!(
+            applied,
+            args(&[
+                "castorix",
+                "--offline",
+                "snap",
+                "hub",
+                "cast",
+                "42",
+                "--parent-url",
+                "@me",
+                "--",
+                "@me"
+            ])
+        );
+
+        let applied = apply_defaults(args(&["castorix", "monitor", "--fid=@me"]), &context());
+        assert_eq!(
+            applied,
+            args(&[
+                "castorix",
+                "--hub-url",
+                "http://hub:2281",
+                "monitor",
+                "--fid=42"
+            ])
+        );
+    }
+
+    #[test]
+    fn test_fill_missing_fid() {
+        let mut command = Cli::command();
+        let mut missing = |line: &[&str]| {
+            command
+                .try_get_matches_from_mut(args(line))
+                .map(|_| ())
+                .unwrap_err()
+        };
+
+        let line = ["castorix", "hub", "casts", "--limit", "5"];
+        let filled = fill_missing_fid(&args(&line), &missing(&line), 7).unwrap();
+        assert_eq!(
+            filled,
+            args(&["castorix", "hub", "casts", "7", "--limit", "5"])
+        );
+
+        let line = ["castorix", "monitor"];
+        let filled = fill_missing_fid(&args(&line), &missing(&line), 7).unwrap();
+        assert_eq!(filled, args(&["castorix", "monitor", "--fid", "7"]));
+
+        let line = ["castorix", "alias", "remove"];
+        assert!(fill_missing_fid(&args(&line), &missing(&line), 7).is_none());
+    }
+}
@@ -0,0 +1,200 @@
+//! Per-command endpoint overrides
+//!
+//! `--hub-url` and `--eth-rpc-url` replace `FARCASTER_HUB_URL` and the RPC
+//! endpoint for a single invocation: `ETH_RPC_URL` for mainnet (ENS) commands
+//! and `ETH_OP_RPC_URL` for commands that call the Farcaster contracts. Overrides are checked before any handler runs so a
+//! typo fails fast instead of surfacing as a confusing error halfway through a
+//! command.
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use reqwest::Url;
+
+use crate::core::client::OfflineSnapshot;
+
+/// Timeout for the reachability probe of an overridden endpoint
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Endpoint overrides given on the command line
+#[derive(Debug, Clone, Default)]
+pub struct EndpointOverrides {
+    /// Farcaster Hub URL (`--hub-url`)
+    pub hub_url: Option<String>,
+    /// Ethereum mainnet RPC URL (`--eth-rpc-url`)
+    pub eth_rpc_url: Option<String>,
+}
+
+impl EndpointOverrides {
+    /// Create overrides from optional command line values
+    pub fn new(hub_url: Option<String>, eth_rpc_url: Option<String>) -> Self {
+        Self {
+            hub_url: hub_url.map(|u| u.trim_end_matches('/').to_string()),
+            eth_rpc_url,
+        }
+    }
+
+    /// Hub URL to use: the override if given, otherwise the configured one
+    pub fn hub_url(&self) -> String {
+        self.hub_url
+            .clone()
+            .unwrap_or_else(|| crate::consts::get_config().farcaster_hub_url().to_string())
+    }
+
+    /// Ethereum RPC URL to use: the override if given, otherwise the configured one
+    pub fn eth_rpc_url(&self) -> String {
+        self.eth_rpc_url
+            .clone()
+            .unwrap_or_else(|| crate::consts::get_config().eth_rpc_url().to_string())
+    }
+
+    /// RPC URL for the Farcaster contracts on Optimism: the override if given,
+    /// otherwise `ETH_OP_RPC_URL`
+    pub fn op_rpc_url(&self) -> String {
+        self.eth_rpc_url
+            .clone()
+            .unwrap_or_else(|| crate::consts::get_config().eth_op_rpc_url().to_string())
+    }
+
+    /// Serve a snapshot export on a loopback port and use it as the hub
+    ///
+    /// # Arguments
+    /// * `snapshot_path` - Export file or directory accepted by [`OfflineSnapshot::load`]
+    pub async fn use_offline_snapshot(&mut self, snapshot_path: &str) -> Result<()> {
+        let snapshot = OfflineSnapshot::load(Path::new(snapshot_path))?;
+        eprintln!(
+            "📦 Offline mode: {} messages from {} FIDs in {}",
+            snapshot.message_count(),
+            snapshot.fid_count(),
+            snapshot_path
+        );
+        let addr = snapshot
+            .serve(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await?;
+        self.hub_url = Some(format!("http://{addr}"));
+        Ok(())
+    }
+
+    /// Check the scheme of every override and probe that it answers
+    ///
+    /// # Returns
+    /// * `Result<()>` - Ok if all overrides are usable, or an error naming the bad flag
+    pub async fn validate(&self) -> Result<()> {
+        if let Some(hub_url) = &self.hub_url {
+            check_scheme(hub_url, "--hub-url")?;
+            probe_hub(hub_url)
+                .await
+                .with_context(|| format!("--hub-url {hub_url} is not reachable"))?;
+        }
+        if let Some(rpc_url) = &self.eth_rpc_url {
+            check_scheme(rpc_url, "--eth-rpc-url")?;
+            probe_eth_rpc(rpc_url)
+                .await
+                .with_context(|| format!("--eth-rpc-url {rpc_url} is not reachable"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Require an absolute http(s) URL with a host
+fn check_scheme(url: &str, flag: &str) -> Result<Url> {
+    let parsed = Url::parse(url).with_context(|| format!("{flag} is not a valid URL: {url}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        anyhow::bail!(
+            "{flag} must use http or https, got '{}': {url}",
+            parsed.scheme()
+        );
+    }
+    if parsed.host_str().is_none() {
+        anyhow::bail!("{flag} has no host: {url}");
+    }
+    Ok(parsed)
+}
+
+/// A hub is reachable if `/v1/info` answers with a success status
+async fn probe_hub(hub_url: &str) -> Result<()> {
+    let (request, request_id) = crate::core::utils::http::tag_request(
+        crate::core::utils::http::client()
+            .get(format!("{hub_url}/v1/info"))
+            .timeout(PROBE_TIMEOUT),
+    );
+    let note = crate::core::utils::http::request_note(&request_id);
+    let response = request
+        .send()
+        .await
+        .with_context(|| format!("request failed{note}"))?;
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "hub answered /v1/info with HTTP {}{note}",
+            response.status()
+        );
+    }
+    Ok(())
+}
+
+/// An RPC endpoint is reachable if it answers `eth_chainId`
+async fn probe_eth_rpc(rpc_url: &str) -> Result<()> {
+    let response: serde_json::Value = crate::core::utils::http::client()
+        .post(rpc_url)
+        .timeout(PROBE_TIMEOUT)
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_chainId",
+            "params": []
+        }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .context("RPC endpoint did not return JSON")?;
+    if response.get("result").is_none() {
+        anyhow::bail!("eth_chainId returned no result: {response}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_scheme() {
+        assert!(check_scheme("https://hub.example.com:3381", "--hub-url").is_ok());
+        assert!(check_scheme("ws://hub.example.com", "--hub-url").is_err());
+        assert!(check_scheme("hub.example.com", "--hub-url").is_err());
+    }
+
+    #[test]
+    fn test_overrides_fall_back_to_config() {
+        let overrides = EndpointOverrides::new(Some("http://localhost:2281/".to_string()), None);
+        assert_eq!(overrides.hub_url(), "http://localhost:2281");
+        assert_eq!(
+            overrides.eth_rpc_url(),
+            crate::consts::get_config().eth_rpc_url()
+        );
+        assert_eq!(
+            overrides.op_rpc_url(),
+            crate::consts::get_config().eth_op_rpc_url()
+        );
+    }
+
+    #[test]
+    fn test_rpc_override_reaches_contract_client() {
+        use crate::farcaster::contracts::ContractAddresses;
+        use crate::farcaster::contracts::FarcasterContractClient;
+
+        let overrides = EndpointOverrides::new(None, Some("http://127.0.0.1:9545".to_string()));
+        assert_eq!(overrides.eth_rpc_url(), "http://127.0.0.1:9545");
+        assert_eq!(overrides.op_rpc_url(), "http://127.0.0.1:9545");
+
+        let client =
+            FarcasterContractClient::new(overrides.op_rpc_url(), ContractAddresses::default())
+                .unwrap();
+        assert_eq!(client.provider.url().as_str(), "http://127.0.0.1:9545/");
+    }
+}
@@ -0,0 +1,263 @@
+//! Field selection for JSON output (`--fields`)
+//!
+//! A small JSONPath-like syntax picks the parts of a JSON result to keep:
+//! dotted keys (`data.fid`), an array index (`messages[0]`) or every element
+//! (`messages[*]`), with an optional leading `$.`. Selected fields keep their
+//! place in the document, so
+//!
+//! ```text
+//! castorix hub casts 12345 --json --fields data.fid,data.timestamp --compact
+//! ```
+//!
+//! prints each cast as `{"data":{"fid":12345,"timestamp":...}}`. A key applied
+//! to an array applies to each element, so `data.fid` works on a list of
+//! messages as well as on one message. Keys that contain dots cannot be
+//! selected.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::Result;
+use serde_json::Map;
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+    All,
+}
+
+/// One `--fields` path, e.g. `data.castAddBody.text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPath(Vec<Segment>);
+
+impl FromStr for FieldPath {
+    type Err = anyhow::Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        let invalid = |reason: &str| anyhow::anyhow!("Invalid field path '{path}': {reason}");
+        let trimmed = path.trim();
+        let trimmed = trimmed
+            .strip_prefix("$.")
+            .or_else(|| trimmed.strip_prefix('$'))
+            .unwrap_or(trimmed);
+        if trimmed.is_empty() {
+            return Err(invalid("it is empty"));
+        }
+
+        let mut segments = Vec::new();
+        for part in trimmed.split('.') {
+            let (key, mut rest) = part.split_at(part.find('[').unwrap_or(part.len()));
+            if key.is_empty() && (rest.is_empty() || !segments.is_empty()) {
+                return Err(invalid("keys cannot be empty"));
+            }
+            if !key.is_empty() {
+                segments.push(Segment::Key(key.to_string()));
+            }
+            while !rest.is_empty() {
+                let Some(end) = rest.find(']').filter(|_| rest.starts_with('[')) else {
+                    return Err(invalid("expected [N] or [*]"));
+                };
+                segments.push(match &rest[1..end] {
+                    "*" | "" => Segment::All,
+                    index => Segment::Index(
+                        index
+                            .parse()
+                            .map_err(|_| invalid(&format!("'{index}' is not an array index")))?,
+                    ),
+                });
+                rest = &rest[end + 1..];
+            }
+        }
+        Ok(Self(segments))
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                Segment::Key(key) if i == 0 => write!(f, "{key}")?,
+                Segment::Key(key) => write!(f, ".{key}")?,
+                Segment::Index(index) => write!(f, "[{index}]")?,
+                Segment::All => write!(f, "[*]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse a comma-separated `--fields` list
+pub fn parse_fields(fields: &str) -> Result<Vec<FieldPath>> {
+    fields
+        .split(',')
+        .filter(|field| !field.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Keep only the selected fields of `value`
+///
+/// With no paths the value is returned unchanged. Paths that match nothing
+/// are left out, so the result is `{}` (or a list of them) if none match.
+pub fn select(value: &Value, paths: &[FieldPath]) -> Value {
+    if paths.is_empty() {
+        return value.clone();
+    }
+    paths
+        .iter()
+        .filter_map(|path| select_path(value, &path.0))
+        .fold(empty_like(value), merge)
+}
+
+fn select_path(value: &Value, path: &[Segment]) -> Option<Value> {
+    let Some((segment, rest)) = path.split_first() else {
+        return Some(value.clone());
+    };
+    match (segment, value) {
+        (Segment::Key(key), Value::Object(object)) => {
+            let selected = select_path(object.get(key)?, rest)?;
+            Some(Value::Object(Map::from_iter([(key.clone(), selected)])))
+        }
+        // Keep elements that do not match as placeholders, so the results
+        // of several paths line up when merged
+        (Segment::Key(_), Value::Array(items)) | (Segment::All, Value::Array(items)) => {
+            let path = if *segment == Segment::All { rest } else { path };
+            Some(Value::Array(
+                items
+                    .iter()
+                    .map(|item| select_path(item, path).unwrap_or_else(|| empty_like(item)))
+                    .collect(),
+            ))
+        }
+        (Segment::Index(index), Value::Array(items)) => {
+            let selected = select_path(items.get(*index)?, rest)?;
+            Some(Value::Array(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        if i == *index {
+                            selected.clone()
+                        } else {
+                            empty_like(item)
+                        }
+                    })
+                    .collect(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn empty_like(value: &Value) -> Value {
+    match value {
+        Value::Array(_) => Value::Array(Vec::new()),
+        Value::Object(_) => Value::Object(Map::new()),
+        _ => Value::Null,
+    }
+}
+
+fn merge(into: Value, from: Value) -> Value {
+    match (into, from) {
+        (Value::Object(mut into), Value::Object(from)) => {
+            for (key, value) in from {
+                let merged = match into.remove(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => value,
+                };
+                into.insert(key, merged);
+            }
+            Value::Object(into)
+        }
+        (Value::Array(into), Value::Array(from)) => {
+            let mut into = into.into_iter();
+            let mut from = from.into_iter();
+            let mut merged = Vec::new();
+            loop {
+                match (into.next(), from.next()) {
+                    (Some(a), Some(b)) => merged.push(merge(a, b)),
+                    (Some(a), None) => merged.push(a),
+                    (None, Some(b)) => merged.push(b),
+                    (None, None) => break,
+                }
+            }
+            Value::Array(merged)
+        }
+        (into, Value::Null) => into,
+        (_, from) => from,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_fields() {
+        let fields = parse_fields("data.fid, $.messages[0].hash,items[*]").unwrap();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0].to_string(), "data.fid");
+        assert_eq!(fields[1].to_string(), "messages[0].hash");
+        assert_eq!(fields[2].to_string(), "items[*]");
+        assert!(parse_fields("data..fid").is_err());
+        assert!(parse_fields("data[x]").is_err());
+        assert!(parse_fields("data[0").is_err());
+    }
+
+    #[test]
+    fn test_select() {
+        let value = json!({
+            "fid": 3,
+            "messages": [
+                {"data": {"fid": 3, "timestamp": 10, "text": "a"}, "hash": "0x1"},
+                {"data": {"fid": 3, "timestamp": 20}, "hash": "0x2"},
+            ],
+        });
+        let fields = parse_fields("messages.data.timestamp,messages[*].hash").unwrap();
+        assert_eq!(
+            select(&value, &fields),
+            json!({"messages": [
+                {"data": {"timestamp": 10}, "hash": "0x1"},
+                {"data": {"timestamp": 20}, "hash": "0x2"},
+            ]})
+        );
+        let fields = parse_fields("messages[1].hash,fid,missing").unwrap();
+        assert_eq!(
+            select(&value, &fields),
+            json!({"fid": 3, "messages": [{}, {"hash": "0x2"}]})
+        );
+        let messages = &value["messages"];
+        assert_eq!(
+            select(messages, &parse_fields("data.text").unwrap()),
+            json!([{"data": {"text": "a"}}, {}])
+        );
+        assert_eq!(select(&value, &[]), value);
+    }
+
+    #[test]
+    fn test_select_index_paths_stay_aligned() {
+        let value = json!({
+            "messages": [
+                {"data": {"timestamp": 10}, "hash": "0x1"},
+                {"data": {"timestamp": 20}, "hash": "0x2"},
+            ],
+        });
+        let fields = parse_fields("messages[0].hash,messages[1].hash").unwrap();
+        assert_eq!(
+            select(&value, &fields),
+            json!({"messages": [{"hash": "0x1"}, {"hash": "0x2"}]})
+        );
+        let fields = parse_fields("messages[1].hash,messages.data.timestamp").unwrap();
+        assert_eq!(
+            select(&value, &fields),
+            json!({"messages": [
+                {"data": {"timestamp": 10}},
+                {"data": {"timestamp": 20}, "hash": "0x2"},
+            ]})
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use anyhow::Result;
+
+use crate::cli::aliases::command_names;
+use crate::cli::aliases::CliConfig;
+use crate::cli::aliases::BUILTIN_ALIASES;
+use crate::cli::types::AliasCommands;
+
+/// Handle command alias management
+pub fn handle_alias_command(command: AliasCommands) -> Result<()> {
+    match command {
+        AliasCommands::List => {
+            let config = CliConfig::load()?;
+            println!("🏷️  Built-in aliases:");
+            for (name, expansion) in BUILTIN_ALIASES {
+                let overridden = if config.aliases.contains_key(*name) {
+                    " (overridden)"
+                } else {
+                    ""
+                };
+                println!("   {name:<6} → {expansion}{overridden}");
+            }
+            if config.aliases.is_empty() {
+                println!(
+                    "📭 No user aliases. Add one with: castorix alias set <name> <command...>"
+                );
+            } else {
+                println!("👤 User aliases ({}):", CliConfig::path().display());
+                for (name, expansion) in &config.aliases {
+                    println!("   {name:<6} → {expansion}");
+                }
+            }
+        }
+        AliasCommands::Set { name, expansion } => {
+            let commands = command_names();
+            if commands.contains(&name) {
+                anyhow::bail!("'{name}' is a castorix command and cannot be used as an alias");
+            }
+            if name.starts_with('-') || name.contains(char::is_whitespace) {
+                anyhow::bail!("Invalid alias name '{name}'");
+            }
+            if !commands.contains(&expansion[0]) {
+                anyhow::bail!(
+                    "Aliases must expand to a castorix command; '{}' is not one",
+                    expansion[0]
+                );
+            }
+
+            let mut config = CliConfig::load()?;
+            let expansion = expansion.join(" ");
+            config.aliases.insert(name.clone(), expansion.clone());
+            config.save()?;
+            println!("✅ Alias '{name}' → {expansion}");
+        }
+        AliasCommands::Remove { name } => {
+            let mut config = CliConfig::load()?;
+            if config.aliases.remove(&name).is_none() {
+                anyhow::bail!("No user alias named '{name}'");
+            }
+            config.save()?;
+            println!("🗑️  Removed alias '{name}'");
+        }
+    }
+    Ok(())
+}
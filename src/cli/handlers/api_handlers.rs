@@ -3,24 +3,35 @@
 use anyhow::Result;
 
 use crate::api::server::ApiServer;
+use crate::cli::endpoints::EndpointOverrides;
 
 /// Handle API server commands
-pub async fn handle_api_command(host: String, port: u16) -> Result<()> {
+pub async fn handle_api_command(
+    host: String,
+    port: u16,
+    hub_upstreams: Vec<String>,
+    endpoints: &EndpointOverrides,
+) -> Result<()> {
     // Load environment variables
     dotenv::dotenv().ok();
 
-    let hub_url = std::env::var("FARCASTER_HUB_URL")
-        .unwrap_or_else(|_| "https://hub-api.neynar.com".to_string());
+    let hub_url = endpoints.hub_url.clone().unwrap_or_else(|| {
+        std::env::var("FARCASTER_HUB_URL")
+            .unwrap_or_else(|_| "https://hub-api.neynar.com".to_string())
+    });
 
     let server = ApiServer {
         host,
         port,
         hub_url,
-        eth_rpc_url: std::env::var("ETH_RPC_URL").ok(),
+        hub_upstreams,
+        eth_rpc_url: endpoints
+            .eth_rpc_url
+            .clone()
+            .or_else(|| std::env::var("ETH_RPC_URL").ok()),
         eth_base_rpc_url: std::env::var("ETH_BASE_RPC_URL").ok(),
         eth_op_rpc_url: std::env::var("ETH_OP_RPC_URL").ok(),
     };
 
     server.serve().await
 }
-
@@ -0,0 +1,60 @@
+use anyhow::Result;
+
+use crate::cli::output;
+use crate::cli::types::AuditCommands;
+use crate::core::audit::AuditLog;
+use crate::core::audit::AuditVerification;
+
+/// Handle audit log commands
+pub fn handle_audit_command(command: AuditCommands) -> Result<()> {
+    let log = AuditLog::open();
+    match command {
+        AuditCommands::Show { limit, key, json } => {
+            let mut entries = log.entries()?;
+            if let Some(key) = &key {
+                entries.retain(|entry| entry.key.eq_ignore_ascii_case(key));
+            }
+            if limit > 0 && entries.len() > limit {
+                entries.drain(..entries.len() - limit);
+            }
+
+            if json {
+                for entry in &entries {
+                    println!("{}", output::json_line(entry)?);
+                }
+                return Ok(());
+            }
+            if entries.is_empty() {
+                println!("📭 No signatures recorded in {}", log.path().display());
+                return Ok(());
+            }
+            println!("📜 Signature audit log ({})", log.path().display());
+            println!("{}", "─".repeat(50));
+            for entry in &entries {
+                let time = chrono::DateTime::from_timestamp(entry.timestamp, 0)
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|| entry.timestamp.to_string());
+                let state = if entry.broadcast {
+                    "📡 broadcast"
+                } else {
+                    "✍️  signed"
+                };
+                println!("#{:<5} {time}  {state}  {}", entry.seq, entry.operation);
+                println!("       key {}", entry.key);
+                println!("       hash {}", entry.message_hash);
+            }
+        }
+        AuditCommands::Verify => match log.verify()? {
+            AuditVerification::Intact { entries } => {
+                println!("✅ Audit log intact: {entries} entries chained");
+            }
+            AuditVerification::Broken { line, reason } => {
+                anyhow::bail!(
+                    "Audit log {} is tampered or corrupt at line {line}: {reason}",
+                    log.path().display()
+                );
+            }
+        },
+    }
+    Ok(())
+}
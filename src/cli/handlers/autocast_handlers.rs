@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use ed25519_dalek::SigningKey;
+
+use crate::cli::handlers::hub_handlers::load_fid_signing_key;
+use crate::core::client::autocast;
+use crate::core::client::autocast::Autocast;
+use crate::core::client::autocast::SeenItems;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_client::Signing;
+use crate::core::policy;
+
+/// When and how much autocast publishes
+#[derive(Debug, Clone, Copy)]
+pub struct Schedule {
+    /// Seconds between polls
+    pub interval: u64,
+    /// Most casts published per poll
+    pub max_per_poll: usize,
+    /// Publish the items already in the feed on the first run
+    pub post_existing: bool,
+    /// Poll once and exit
+    pub once: bool,
+    /// Show casts without submitting or recording anything
+    pub dry_run: bool,
+}
+
+/// Handle the autocast command
+pub async fn handle_autocast_command(
+    fid: u64,
+    autocast: Autocast,
+    schedule: Schedule,
+    hub_client: &FarcasterClient,
+) -> Result<()> {
+    autocast::check_template(&autocast.template)?;
+    policy::current().check_fid(fid)?;
+    let mut seen = SeenItems::open(&autocast.feed_url)?;
+
+    println!("📰 Feed: {}", autocast.feed_url);
+    println!("📝 Template: {}", autocast.template);
+    println!("📡 Hub: {}", hub_client.hub_url());
+    if !schedule.once {
+        println!(
+            "⏱️  Poll interval: {}s, at most {} cast(s) per poll (press Ctrl+C to stop)",
+            schedule.interval, schedule.max_per_poll
+        );
+    }
+    println!("{}", "─".repeat(50));
+
+    let signing_key = if schedule.dry_run {
+        None
+    } else {
+        match load_fid_signing_key(fid)? {
+            Some(key) => Some(key),
+            None => return Ok(()),
+        }
+    };
+    let client = FarcasterClient::new(hub_client.hub_url().to_string(), None);
+    let mut ticker = tokio::time::interval(Duration::from_secs(schedule.interval.max(1)));
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                println!("\n👋 Autocast stopped");
+                return Ok(());
+            }
+            _ = ticker.tick() => {}
+        }
+
+        let result = poll(
+            fid,
+            &autocast,
+            schedule,
+            &mut seen,
+            &client,
+            signing_key.as_ref(),
+        )
+        .await;
+        if schedule.once {
+            return result;
+        }
+        if let Err(e) = result {
+            println!("⚠️  Poll failed, will retry next interval: {e:#}");
+        }
+    }
+}
+
+/// Fetch the feed once and publish what is new
+async fn poll(
+    fid: u64,
+    autocast: &Autocast,
+    schedule: Schedule,
+    seen: &mut SeenItems,
+    client: &FarcasterClient<Signing>,
+    signing_key: Option<&SigningKey>,
+) -> Result<()> {
+    let items = autocast.fetch().await?;
+
+    if seen.is_first_run() && !schedule.post_existing {
+        if schedule.dry_run {
+            println!(
+                "🔍 First run: {} existing item(s) would be recorded without posting",
+                items.len()
+            );
+            return Ok(());
+        }
+        for item in &items {
+            seen.insert(&item.id);
+        }
+        seen.save()?;
+        println!(
+            "📌 First run: recorded {} existing item(s) without posting; new items will be published",
+            items.len()
+        );
+        return Ok(());
+    }
+
+    let unseen = seen.unseen(&items);
+    if unseen.is_empty() {
+        println!("💤 No new items");
+        return Ok(());
+    }
+
+    for item in unseen.iter().take(schedule.max_per_poll) {
+        let draft = autocast.draft(item)?;
+        if let Err(e) = draft.validate(fid, false) {
+            println!("⚠️  Skipping \"{}\": {e:#}", item.title);
+            if !schedule.dry_run {
+                seen.insert(&item.id);
+                seen.save()?;
+            }
+            continue;
+        }
+        let Some(signing_key) = signing_key else {
+            println!("🔍 Would publish:\n{}", draft.text);
+            continue;
+        };
+        let published = client
+            .submit_cast_draft(fid, &draft, false, signing_key)
+            .await
+            .with_context(|| format!("Failed to publish \"{}\"", item.title))?;
+        seen.insert(&item.id);
+        seen.save()?;
+        for (message, _) in &published {
+            println!(
+                "✅ Published 0x{}: {}",
+                hex::encode(message.get_hash()),
+                item.title
+            );
+        }
+    }
+    if unseen.len() > schedule.max_per_poll {
+        println!(
+            "⏳ {} more item(s) wait for the next poll",
+            unseen.len() - schedule.max_per_poll
+        );
+    }
+    Ok(())
+}
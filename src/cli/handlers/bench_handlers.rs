@@ -0,0 +1,150 @@
+//! Load testing command handlers
+
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::api::bench;
+use crate::api::bench::BenchConfig;
+use crate::api::bench::BenchReport;
+use crate::api::bench::BenchRoute;
+use crate::api::bench::LoadPattern;
+use crate::cli::output;
+use crate::cli::types::BenchCommands;
+
+/// Requests sent when neither a count nor a duration is given
+const DEFAULT_REQUESTS: u64 = 1000;
+
+/// Handle load testing commands
+pub async fn handle_bench_command(command: BenchCommands) -> Result<()> {
+    match command {
+        BenchCommands::Api {
+            url,
+            routes,
+            fids,
+            concurrency,
+            requests,
+            duration,
+            rate,
+            pattern,
+            ramp_up,
+            timeout,
+            json,
+        } => {
+            let routes = if routes.is_empty() {
+                bench::DEFAULT_ROUTES
+                    .iter()
+                    .map(|route| BenchRoute::parse(route))
+                    .collect::<Result<Vec<_>>>()?
+            } else {
+                routes
+                    .iter()
+                    .map(|route| BenchRoute::parse(route))
+                    .collect::<Result<Vec<_>>>()?
+            };
+            if rate.is_some_and(|rate| rate <= 0.0) {
+                anyhow::bail!("❌ --rate must be greater than 0");
+            }
+            let config = BenchConfig {
+                base_url: url,
+                routes,
+                fids,
+                concurrency,
+                requests: match (requests, duration) {
+                    (None, None) => Some(DEFAULT_REQUESTS),
+                    (requests, _) => requests,
+                },
+                duration: duration.map(Duration::from_secs),
+                rate,
+                pattern: match pattern.as_str() {
+                    "ramp" => LoadPattern::Ramp,
+                    _ => LoadPattern::Steady,
+                },
+                ramp_up: Duration::from_secs(ramp_up),
+                timeout: Duration::from_secs(timeout),
+            };
+            handle_bench_api(config, json).await
+        }
+    }
+}
+
+async fn handle_bench_api(config: BenchConfig, json: bool) -> Result<()> {
+    if !json {
+        println!("📈 Load testing {}", config.base_url);
+        println!(
+            "   Routes: {}  Concurrency: {}  Pattern: {:?}",
+            config.routes.len(),
+            config.concurrency,
+            config.pattern
+        );
+        match (config.requests, config.duration) {
+            (Some(requests), Some(duration)) => println!(
+                "   Stop after: {requests} requests or {}s",
+                duration.as_secs()
+            ),
+            (Some(requests), None) => println!("   Stop after: {requests} requests"),
+            (None, Some(duration)) => println!("   Stop after: {}s", duration.as_secs()),
+            (None, None) => {}
+        }
+        if let Some(rate) = config.rate {
+            println!("   Rate cap: {rate} req/s");
+        }
+    }
+
+    let report = bench::run(config).await?;
+
+    if json {
+        println!("{}", output::json(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
+
+fn print_report(report: &BenchReport) {
+    let width = report
+        .routes
+        .iter()
+        .map(|r| r.route.len())
+        .max()
+        .unwrap_or(0)
+        .max("Route".len());
+
+    println!();
+    println!(
+        "{:<width$}  {:>8}  {:>7}  {:>9}  {:>9}  {:>9}  {:>9}",
+        "Route", "Requests", "Errors", "p50 ms", "p95 ms", "p99 ms", "max ms"
+    );
+    for route in &report.routes {
+        println!(
+            "{:<width$}  {:>8}  {:>6.1}%  {:>9.1}  {:>9.1}  {:>9.1}  {:>9.1}",
+            route.route,
+            route.requests,
+            route.error_rate,
+            route.p50_ms,
+            route.p95_ms,
+            route.p99_ms,
+            route.max_ms
+        );
+        if route.errors > 0 {
+            let mut statuses: Vec<_> = route.statuses.iter().collect();
+            statuses.sort();
+            let statuses: Vec<String> = statuses
+                .into_iter()
+                .map(|(status, count)| format!("{status}: {count}"))
+                .collect();
+            println!("{:<width$}  ↳ {}", "", statuses.join(", "));
+        }
+    }
+    println!();
+
+    let summary = format!(
+        "{} requests in {:.1}s ({:.1} req/s), {} errors",
+        report.requests, report.elapsed_secs, report.requests_per_sec, report.errors
+    );
+    if report.errors == 0 {
+        println!("✅ {summary}");
+    } else {
+        println!("⚠️  {summary}");
+    }
+}
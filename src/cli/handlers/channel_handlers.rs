@@ -0,0 +1,131 @@
+use anyhow::Result;
+
+use crate::cli::output;
+use crate::cli::types::ChannelCommands;
+use crate::core::client::channels::ChannelClient;
+use crate::core::client::channels::ChannelModerator;
+use crate::core::client::channels::ChannelRole;
+use crate::core::client::channels::ModerationAction;
+
+/// Handle channel membership and moderation queries
+pub async fn handle_channel_command(command: ChannelCommands) -> Result<()> {
+    let client = ChannelClient::default();
+    match command {
+        ChannelCommands::Info { channel, json } => {
+            let channel = client.get_channel(&channel).await?;
+            if json {
+                println!("{}", output::json(&channel)?);
+                return Ok(());
+            }
+            println!("📺 /{} — {}", channel.id, channel.name);
+            if !channel.description.is_empty() {
+                println!("   {}", channel.description);
+            }
+            println!("   URL: {}", channel.url);
+            if let Some(count) = channel.member_count {
+                println!("   Members: {count}");
+            }
+            if let Some(count) = channel.follower_count {
+                println!("   Followers: {count}");
+            }
+            if channel.created_at.is_some() {
+                println!("   Created: {}", date(channel.created_at));
+            }
+            print_moderators(&channel.moderators());
+        }
+        ChannelCommands::Members {
+            channel,
+            limit,
+            json,
+        } => {
+            let members = client.get_members(&channel, limit).await?;
+            if json {
+                println!("{}", output::json(&members)?);
+                return Ok(());
+            }
+            println!("👥 Members of /{channel} ({}):", members.len());
+            for member in &members {
+                println!("   FID {:<10} since {}", member.fid, date(member.member_at));
+            }
+        }
+        ChannelCommands::Moderators { channel, json } => {
+            let moderators = client.get_channel(&channel).await?.moderators();
+            if json {
+                println!("{}", output::json(&moderators)?);
+                return Ok(());
+            }
+            println!("🛡️  Staff of /{channel}:");
+            print_moderators(&moderators);
+        }
+        ChannelCommands::Memberships { fid, limit, json } => {
+            let memberships = client.get_memberships(fid, limit).await?;
+            if json {
+                println!("{}", output::json(&memberships)?);
+                return Ok(());
+            }
+            if memberships.is_empty() {
+                println!("🏠 FID {fid} is not a member of any channel");
+                return Ok(());
+            }
+            println!(
+                "🏠 Channels FID {fid} is a member of ({}):",
+                memberships.len()
+            );
+            for membership in &memberships {
+                println!(
+                    "   /{:<24} since {}",
+                    membership.channel_id,
+                    date(membership.member_at)
+                );
+            }
+        }
+        ChannelCommands::Bans {
+            channel,
+            restricted,
+            limit,
+            json,
+        } => {
+            let action = if restricted {
+                ModerationAction::Restrict
+            } else {
+                ModerationAction::Ban
+            };
+            let entries = client.get_moderation(&channel, action, limit).await?;
+            if json {
+                println!("{}", output::json(&entries)?);
+                return Ok(());
+            }
+            let what = if restricted { "restricted" } else { "banned" };
+            if entries.is_empty() {
+                println!("✅ No FIDs {what} in /{channel}");
+                return Ok(());
+            }
+            println!("🚫 FIDs {what} in /{channel} ({}):", entries.len());
+            for entry in &entries {
+                println!("   FID {:<10} since {}", entry.fid, date(entry.since));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_moderators(moderators: &[ChannelModerator]) {
+    if moderators.is_empty() {
+        println!("   No moderators listed");
+    }
+    for moderator in moderators {
+        let role = match moderator.role {
+            ChannelRole::Lead => "lead",
+            ChannelRole::Moderator => "moderator",
+        };
+        println!("   FID {:<10} {role}", moderator.fid);
+    }
+}
+
+/// Unix time as a UTC date, or `unknown`
+fn date(timestamp: Option<u64>) -> String {
+    timestamp
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts as i64, 0))
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
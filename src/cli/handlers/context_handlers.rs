@@ -0,0 +1,156 @@
+use anyhow::Context as _;
+use anyhow::Result;
+
+use crate::cli::aliases::CliConfig;
+use crate::cli::context;
+use crate::cli::context::Context;
+use crate::cli::types::ContextCommands;
+
+/// Handle named context management
+pub fn handle_context_command(command: ContextCommands) -> Result<()> {
+    match command {
+        ContextCommands::Set {
+            name,
+            fid,
+            wallet,
+            hub,
+            activate,
+        } => {
+            if name.starts_with('-') || name.contains(char::is_whitespace) {
+                anyhow::bail!("Invalid context name '{name}'");
+            }
+            if let Some(hub) = &hub {
+                let url = reqwest::Url::parse(hub)
+                    .with_context(|| format!("--hub is not a valid URL: {hub}"))?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    anyhow::bail!("--hub must use http or https: {hub}");
+                }
+            }
+
+            let mut config = CliConfig::load()?;
+            let context = config.contexts.entry(name.clone()).or_default();
+            context.fid = fid.or(context.fid);
+            context.wallet = wallet.or(context.wallet.take());
+            context.hub_url = hub.or(context.hub_url.take());
+            if context.is_empty() {
+                config.contexts.remove(&name);
+                anyhow::bail!("A context needs at least one of --fid, --wallet or --hub");
+            }
+            let context = context.clone();
+            if activate {
+                config.active_context = Some(name.clone());
+            }
+            config.save()?;
+            println!("✅ Context '{name}' saved");
+            print_context(&context);
+            if activate {
+                println!("🎯 '{name}' is now the active context");
+            }
+        }
+        ContextCommands::Use { name } => {
+            let mut config = CliConfig::load()?;
+            if !config.contexts.contains_key(&name) {
+                anyhow::bail!("No context named '{name}'; see `castorix context list`");
+            }
+            config.active_context = Some(name.clone());
+            config.save()?;
+            println!("🎯 '{name}' is now the active context");
+        }
+        ContextCommands::Clear => {
+            let mut config = CliConfig::load()?;
+            match config.active_context.take() {
+                Some(name) => {
+                    config.save()?;
+                    println!("⏏️  Context '{name}' deactivated");
+                }
+                None => println!("📭 No context is active"),
+            }
+        }
+        ContextCommands::List => {
+            let config = CliConfig::load()?;
+            if config.contexts.is_empty() {
+                println!(
+                    "📭 No contexts. Create one with: castorix context set <name> --fid <FID>"
+                );
+                return Ok(());
+            }
+            let active = context::active(&config, None)
+                .ok()
+                .flatten()
+                .map(|(name, _)| name);
+            println!("🪪 Contexts ({}):", CliConfig::path().display());
+            for (name, context) in &config.contexts {
+                let marker = if active.as_deref() == Some(name.as_str()) {
+                    "*"
+                } else {
+                    " "
+                };
+                println!("{marker}  {name:<12} {}", summary(context));
+            }
+        }
+        ContextCommands::Show { name } => {
+            let config = CliConfig::load()?;
+            let (name, context) = match name {
+                Some(name) => {
+                    let context = config
+                        .contexts
+                        .get(&name)
+                        .cloned()
+                        .with_context(|| format!("No context named '{name}'"))?;
+                    (name, context)
+                }
+                None => match context::active(&config, None)? {
+                    Some(active) => active,
+                    None => {
+                        println!("📭 No context is active");
+                        return Ok(());
+                    }
+                },
+            };
+            println!("🪪 Context '{name}'");
+            print_context(&context);
+        }
+        ContextCommands::Remove { name } => {
+            let mut config = CliConfig::load()?;
+            if config.contexts.remove(&name).is_none() {
+                anyhow::bail!("No context named '{name}'");
+            }
+            if config.active_context.as_deref() == Some(name.as_str()) {
+                config.active_context = None;
+            }
+            config.save()?;
+            println!("🗑️  Removed context '{name}'");
+        }
+    }
+    Ok(())
+}
+
+fn summary(context: &Context) -> String {
+    let mut parts = Vec::new();
+    if let Some(fid) = context.fid {
+        parts.push(format!("FID {fid}"));
+    }
+    if let Some(wallet) = &context.wallet {
+        parts.push(format!("wallet {wallet}"));
+    }
+    if let Some(hub_url) = &context.hub_url {
+        parts.push(format!("hub {hub_url}"));
+    }
+    parts.join(", ")
+}
+
+fn print_context(context: &Context) {
+    let unset = || "(not set)".to_string();
+    println!(
+        "   FID:    {}",
+        context.fid.map(|fid| fid.to_string()).unwrap_or_else(unset)
+    );
+    println!(
+        "   Wallet: {}",
+        context.wallet.clone().unwrap_or_else(unset)
+    );
+    println!(
+        "   Hub:    {}",
+        context.hub_url.clone().unwrap_or_else(unset)
+    );
+}
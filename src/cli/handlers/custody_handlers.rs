@@ -1,9 +1,21 @@
 use anyhow::Result;
 
+use crate::cli::endpoints::EndpointOverrides;
 use crate::cli::types::CustodyCommands;
+use crate::core::crypto::encrypted_storage::custody_derivation_path;
+use crate::core::crypto::vanity;
+use crate::core::crypto::vanity::VanityPattern;
+use crate::core::policy;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
 
 /// Handle custody commands
-pub async fn handle_custody_command(command: CustodyCommands) -> Result<()> {
+pub async fn handle_custody_command(
+    command: CustodyCommands,
+    storage_path: Option<&str>,
+    endpoints: &EndpointOverrides,
+) -> Result<()> {
+    let hub_url = endpoints.hub_url();
     match command {
         CustodyCommands::List => {
             handle_custody_list().await?;
@@ -12,10 +24,42 @@ pub async fn handle_custody_command(command: CustodyCommands) -> Result<()> {
             handle_custody_import(fid).await?;
         }
         CustodyCommands::FromMnemonic { fid } => {
-            handle_custody_from_mnemonic(fid).await?;
+            handle_custody_from_mnemonic(fid, &custody_derivation_path(0), &hub_url).await?;
         }
-        CustodyCommands::Delete { fid } => {
-            handle_custody_delete(fid).await?;
+        CustodyCommands::Derive { fid, index, path } => {
+            let path = path.unwrap_or_else(|| custody_derivation_path(index));
+            handle_custody_from_mnemonic(fid, &path, &hub_url).await?;
+        }
+        CustodyCommands::GenerateVanity {
+            fid,
+            prefix,
+            suffix,
+            checksum,
+            threads,
+            max_attempts,
+        } => {
+            let pattern = VanityPattern::new(prefix.as_deref(), suffix.as_deref(), checksum)?;
+            let threads = threads
+                .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+            handle_custody_generate_vanity(fid, &pattern, threads, max_attempts).await?;
+        }
+        CustodyCommands::Delete { fid, force } => {
+            handle_custody_delete(&endpoints.op_rpc_url(), fid, force).await?;
+        }
+        CustodyCommands::Balance { fid, chain } => {
+            handle_custody_balance(fid, &chain, endpoints).await?;
+        }
+        CustodyCommands::Fund {
+            fid,
+            from,
+            amount,
+            chain,
+            yes,
+        } => {
+            handle_custody_fund(fid, from, amount, &chain, yes, storage_path, endpoints).await?;
+        }
+        CustodyCommands::Report { address, no_costs } => {
+            handle_custody_report(&endpoints.op_rpc_url(), &address, !no_costs).await?;
         }
     }
     Ok(())
@@ -26,9 +70,7 @@ async fn handle_custody_list() -> Result<()> {
     println!("{}", "=".repeat(50));
 
     // List all custody key files in the custody directory
-    let home_dir =
-        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
-    let custody_dir = home_dir.join(".castorix").join("custody");
+    let custody_dir = crate::core::utils::data_dir::custody_dir();
 
     if !custody_dir.exists() {
         println!("❌ No custody keys found.");
@@ -53,7 +95,8 @@ async fn handle_custody_list() -> Result<()> {
                             let file_path = entry.path().to_string_lossy().to_string();
                             if let Ok(manager) = crate::core::crypto::encrypted_storage::EncryptedEthKeyManager::load_from_file(&file_path) {
                                 if let Ok(address) = manager.get_address(fid) {
-                                    custody_keys.push((fid, address, file_path));
+                                    let path = manager.get_derivation_path(fid);
+                                    custody_keys.push((fid, address, path, file_path));
                                 }
                             }
                         }
@@ -69,10 +112,14 @@ async fn handle_custody_list() -> Result<()> {
         println!("💡 Use 'castorix custody from-mnemonic <fid>' to generate from mnemonic!");
     } else {
         println!("🔒 Custody keys found:");
-        println!("\n{:<8} {:<42} {:<50}", "FID", "Address", "File");
-        println!("{}", "-".repeat(100));
-        for (fid, address, file_path) in custody_keys {
-            println!("{:<8} {:<42} {:<50}", fid, address, file_path);
+        println!(
+            "\n{:<8} {:<42} {:<20} {:<50}",
+            "FID", "Address", "Derivation Path", "File"
+        );
+        println!("{}", "-".repeat(120));
+        for (fid, address, path, file_path) in custody_keys {
+            let path = path.unwrap_or_else(|| "imported".to_string());
+            println!("{:<8} {:<42} {:<20} {:<50}", fid, address, path, file_path);
         }
 
         println!("\n💡 Use 'castorix custody delete <fid>' to remove a key");
@@ -130,8 +177,101 @@ async fn handle_custody_import(fid: u64) -> Result<()> {
     Ok(())
 }
 
-async fn handle_custody_from_mnemonic(fid: u64) -> Result<()> {
+async fn handle_custody_generate_vanity(
+    fid: u64,
+    pattern: &VanityPattern,
+    threads: usize,
+    max_attempts: Option<u64>,
+) -> Result<()> {
+    println!("✨ Generating a vanity custody key for FID: {fid}");
+    println!("🎯 Pattern: {pattern}");
+    println!(
+        "🎲 Difficulty: 1 in {:.0} (about that many keys on average)",
+        pattern.difficulty()
+    );
+
+    let custody_key_file =
+        crate::core::crypto::encrypted_storage::EncryptedEthKeyManager::custody_key_file(fid)?;
+    let mut encrypted_manager =
+        crate::core::crypto::encrypted_storage::EncryptedEthKeyManager::load_from_file(
+            &custody_key_file,
+        )?;
+    if encrypted_manager.has_key(fid) {
+        anyhow::bail!(
+            "A custody key for FID {fid} is already stored; remove it with 'castorix custody delete {fid}' first"
+        );
+    }
+
+    // Ask before grinding so a long search can run unattended
+    let password =
+        crate::encrypted_key_manager::prompt_password("Enter password to encrypt the key: ")?;
+    let password_confirm = crate::encrypted_key_manager::prompt_password("Confirm password: ")?;
+    if password != password_confirm {
+        return Err(anyhow::anyhow!(
+            "❌ Passwords do not match. Please try again."
+        ));
+    }
+
+    println!("⛏️  Searching on {threads} thread(s)... (Ctrl-C to stop)");
+    let found = vanity::grind(pattern, threads, max_attempts, |attempts, elapsed| {
+        let rate = attempts as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+        let eta = pattern
+            .half_life(rate)
+            .map(|eta| format!(", 50% chance within {}", format_duration(eta)))
+            .unwrap_or_default();
+        eprint!(
+            "\r   {attempts} keys tried, {rate:.0} keys/s, {} elapsed{eta}   ",
+            format_duration(elapsed)
+        );
+    });
+    eprintln!();
+
+    let Some(found) = found else {
+        anyhow::bail!(
+            "No matching address after {} attempts; raise --max-attempts or shorten the pattern",
+            max_attempts.unwrap_or_default()
+        );
+    };
+    println!(
+        "🎉 Found {:?} after {} keys in {}",
+        found.address,
+        found.attempts,
+        format_duration(found.elapsed)
+    );
+
+    encrypted_manager
+        .import_and_encrypt(fid, &found.private_key_hex(), &password)
+        .await?;
+    encrypted_manager.save_to_file(&custody_key_file)?;
+
+    println!("✅ ECDSA key generated and encrypted successfully!");
+    println!(
+        "🔑 Address: {}",
+        ethers::utils::to_checksum(&found.address, None)
+    );
+    println!("📁 FID: {fid}");
+    println!("💾 Key stored securely with password protection");
+    Ok(())
+}
+
+/// Whole hours, minutes or seconds, whichever fits
+fn format_duration(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=119 => format!("{secs}s"),
+        120..=7199 => format!("{}m", secs / 60),
+        7200..=172_799 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+async fn handle_custody_from_mnemonic(
+    fid: u64,
+    derivation_path: &str,
+    hub_url: &str,
+) -> Result<()> {
     println!("🔑 Generating ECDSA key from mnemonic for FID: {fid}");
+    println!("🌳 Derivation path: {derivation_path}");
 
     // Prompt for mnemonic
     let mnemonic = crate::encrypted_key_manager::prompt_password(
@@ -161,7 +301,7 @@ async fn handle_custody_from_mnemonic(fid: u64) -> Result<()> {
 
     // Generate from mnemonic and encrypt
     encrypted_manager
-        .generate_from_recovery_phrase(fid, &mnemonic, &password)
+        .generate_from_recovery_phrase_at_path(fid, &mnemonic, &password, derivation_path)
         .await?;
 
     // Get the address from the encrypted manager
@@ -171,11 +311,8 @@ async fn handle_custody_from_mnemonic(fid: u64) -> Result<()> {
     println!("🔍 Verifying address matches FID custody address...");
 
     // Create Farcaster client to get custody address from Hub API
-    let config = crate::consts::get_config();
-    let hub_client = crate::core::client::hub_client::FarcasterClient::new(
-        config.farcaster_hub_url.clone(),
-        None,
-    );
+    let hub_client =
+        crate::core::client::hub_client::FarcasterClient::read_only(hub_url.to_string());
 
     // Get custody address from Hub API
     let actual_custody_address = hub_client
@@ -194,6 +331,7 @@ async fn handle_custody_from_mnemonic(fid: u64) -> Result<()> {
             🔑 Generated address: {}\n\n\
             🔍 To manage this FID, you need the mnemonic for the correct custody wallet.\n\
             💡 If you have the correct mnemonic, try again with the right one.\n\
+            💡 If this FID uses another account of the mnemonic, try: castorix custody derive {} --index <N>\n\
             💡 If you have the private key instead, use: castorix custody import {}",
             address,
             fid,
@@ -201,6 +339,7 @@ async fn handle_custody_from_mnemonic(fid: u64) -> Result<()> {
             fid,
             actual_custody_address,
             address,
+            fid,
             fid
         ));
     }
@@ -212,12 +351,13 @@ async fn handle_custody_from_mnemonic(fid: u64) -> Result<()> {
     println!("🔑 Address: {} ✓", address);
     println!("📁 FID: {}", fid);
     println!("💾 Key stored securely with password protection");
+    println!("🌳 Derivation path: {}", derivation_path);
     println!("📂 Saved to: {}", custody_key_file);
 
     Ok(())
 }
 
-async fn handle_custody_delete(fid: u64) -> Result<()> {
+async fn handle_custody_delete(rpc_url: &str, fid: u64, force: bool) -> Result<()> {
     println!("🗑️  Deleting ECDSA key for FID: {fid}");
 
     // Check if FID-specific custody key file exists
@@ -247,6 +387,12 @@ async fn handle_custody_delete(fid: u64) -> Result<()> {
     println!("   Address: {}", address);
     println!("   File: {}", custody_key_file);
 
+    let check =
+        crate::cli::handlers::key_handlers::safety::check_custody_key(rpc_url, &address).await;
+    if !crate::cli::handlers::key_handlers::safety::allow_deletion(&check, force) {
+        return Ok(());
+    }
+
     // Confirm deletion
     let confirm = crate::core::crypto::encrypted_storage::prompt_password(
         "Are you sure you want to delete this key? Type 'DELETE' to confirm: ",
@@ -267,3 +413,341 @@ async fn handle_custody_delete(fid: u64) -> Result<()> {
 
     Ok(())
 }
+
+/// Resolve the RPC URL for a chain name accepted by the custody commands
+fn chain_rpc_url(chain: &str, endpoints: &EndpointOverrides) -> Result<String> {
+    let config = crate::consts::get_config();
+    match chain.to_lowercase().as_str() {
+        "optimism" | "op" => Ok(endpoints.op_rpc_url()),
+        "base" => Ok(config.eth_base_rpc_url().to_string()),
+        other => Err(anyhow::anyhow!(
+            "❌ Unsupported chain '{other}'. Use 'optimism' or 'base'."
+        )),
+    }
+}
+
+/// Find the custody address of a FID from the IdRegistry on Optimism
+///
+/// The chain is the source of truth: after a custody transfer or recovery
+/// the local key file is stale. The hub is asked only when the registry
+/// cannot be read, and a local key for another address is just reported.
+async fn resolve_custody_address(
+    fid: u64,
+    endpoints: &EndpointOverrides,
+) -> Result<ethers::types::Address> {
+    use crate::farcaster::contracts::types::ContractAddresses;
+    use crate::farcaster::contracts::FarcasterContractClient;
+
+    let contract_client =
+        FarcasterContractClient::new(endpoints.op_rpc_url(), ContractAddresses::default())?;
+    let custody = match contract_client.get_fid_info(fid).await {
+        Ok(info) => info.custody,
+        Err(e) => {
+            eprintln!("⚠️  Could not read FID {fid} from the IdRegistry ({e}); asking the hub");
+            let hub_client =
+                crate::core::client::hub_client::FarcasterClient::read_only(endpoints.hub_url());
+            hub_client
+                .get_custody_address(fid)
+                .await?
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid custody address from hub: {e}"))?
+        }
+    };
+    if custody.is_zero() {
+        return Err(anyhow::anyhow!("❌ FID {fid} is not registered"));
+    }
+
+    let custody_key_file =
+        crate::core::crypto::encrypted_storage::EncryptedEthKeyManager::custody_key_file(fid)?;
+    if std::path::Path::new(&custody_key_file).exists() {
+        let local = crate::core::crypto::encrypted_storage::EncryptedEthKeyManager::load_from_file(
+            &custody_key_file,
+        )
+        .and_then(|manager| manager.get_address(fid))
+        .ok()
+        .and_then(|address| address.parse::<ethers::types::Address>().ok());
+        if let Some(local) = local.filter(|local| *local != custody) {
+            eprintln!(
+                "⚠️  The local custody key for FID {fid} is for {local:?}, but the FID is now \
+                 owned by {custody:?}; the local key is out of date"
+            );
+        }
+    }
+    Ok(custody)
+}
+
+async fn handle_custody_balance(
+    fid: u64,
+    chain: &str,
+    endpoints: &EndpointOverrides,
+) -> Result<()> {
+    use crate::farcaster::contracts::types::ContractAddresses;
+    use crate::farcaster::contracts::FarcasterContractClient;
+
+    println!("💰 Checking custody balance for FID: {fid} on {chain}");
+
+    let rpc_url = chain_rpc_url(chain, endpoints)?;
+    let custody_address = resolve_custody_address(fid, endpoints).await?;
+    let contract_client = FarcasterContractClient::new(rpc_url, ContractAddresses::default())?;
+    let balance = contract_client.get_eth_balance(custody_address).await?;
+
+    println!("✅ Custody address: {custody_address:?}");
+    println!("   Balance: {} ETH", ethers::utils::format_ether(balance));
+    if balance.is_zero() {
+        println!("💡 Use 'castorix custody fund {fid} --from <wallet> --amount <eth>' to fund it");
+    }
+
+    Ok(())
+}
+
+async fn handle_custody_fund(
+    fid: u64,
+    from: String,
+    amount: String,
+    chain: &str,
+    yes: bool,
+    storage_path: Option<&str>,
+    endpoints: &EndpointOverrides,
+) -> Result<()> {
+    use ethers::signers::LocalWallet;
+    use ethers::signers::Signer;
+
+    use crate::encrypted_key_manager::prompt_password;
+    use crate::encrypted_key_manager::EncryptedKeyManager;
+    use crate::farcaster::contracts::types::ContractAddresses;
+    use crate::farcaster::contracts::FarcasterContractClient;
+
+    println!("💸 Funding custody address for FID: {fid} on {chain}");
+
+    let amount_wei = ethers::utils::parse_ether(amount.trim())
+        .map_err(|e| anyhow::anyhow!("❌ Invalid amount '{amount}': {e}"))?;
+    if amount_wei.is_zero() {
+        return Err(anyhow::anyhow!("❌ Amount must be greater than zero"));
+    }
+
+    let rpc_url = chain_rpc_url(chain, endpoints)?;
+    let custody_address = resolve_custody_address(fid, endpoints).await?;
+
+    let mut manager = if let Some(path) = storage_path {
+        EncryptedKeyManager::new(&format!("{}/keys", path))
+    } else {
+        EncryptedKeyManager::default_config()
+    };
+    if !manager.key_exists(&from) {
+        println!("❌ Wallet '{from}' not found!");
+        println!("💡 Use 'castorix key list' to see available wallets");
+        return Ok(());
+    }
+    let password = prompt_password(&format!("Enter password for wallet '{from}': "))?;
+    manager.load_and_decrypt(&password, &from).await?;
+    let private_key = manager
+        .key_manager()
+        .ok_or_else(|| anyhow::anyhow!("Wallet '{from}' could not be loaded"))?
+        .wallet()
+        .signer()
+        .to_bytes();
+    let from_wallet = LocalWallet::from_bytes(&private_key)?;
+
+    let contract_client = FarcasterContractClient::new(rpc_url, ContractAddresses::default())?;
+    let sender_balance = contract_client
+        .get_eth_balance(from_wallet.address())
+        .await?;
+    let fee = contract_client.estimate_transfer_fee().await?;
+
+    println!("\n📋 Transfer Details:");
+    println!("   From: {:?} ({from})", from_wallet.address());
+    println!("   To (custody): {custody_address:?}");
+    println!("   Amount: {} ETH", ethers::utils::format_ether(amount_wei));
+    println!("   Estimated Fee: {} ETH", ethers::utils::format_ether(fee));
+    println!(
+        "   Sender Balance: {} ETH",
+        ethers::utils::format_ether(sender_balance)
+    );
+
+    if sender_balance < amount_wei + fee {
+        return Err(anyhow::anyhow!(
+            "❌ Insufficient balance in wallet '{from}': the transfer and its fee need {} ETH",
+            ethers::utils::format_ether(amount_wei + fee)
+        ));
+    }
+
+    policy::current().check_confirmation(yes)?;
+    if !yes {
+        prompt::ask(
+            InputKind::Confirmation,
+            "\n❓ Do you want to proceed with the transfer? (yes/no): ",
+        )?;
+
+        let mut confirmation = String::new();
+        std::io::stdin().read_line(&mut confirmation)?;
+        let confirmation = confirmation.trim().to_lowercase();
+        if confirmation != "yes" && confirmation != "y" {
+            println!("❌ Operation cancelled by user");
+            return Ok(());
+        }
+    }
+
+    contract_client
+        .transfer_eth(&from_wallet, custody_address, amount_wei)
+        .await?;
+
+    Ok(())
+}
+
+/// Transactions whose costs are fetched at the same time
+const TX_COST_CONCURRENCY: usize = 8;
+
+async fn handle_custody_report(rpc_url: &str, address: &str, include_costs: bool) -> Result<()> {
+    use ethers::types::Address;
+    use ethers::types::H256;
+    use ethers::types::U256;
+    use ethers::utils::format_ether;
+    use futures::stream::StreamExt;
+    use futures::stream::{
+        self,
+    };
+
+    use crate::farcaster::contracts::types::ContractAddresses;
+    use crate::farcaster::contracts::FarcasterContractClient;
+    use crate::farcaster::indexer::activity::Acquisition;
+    use crate::farcaster::indexer::EventKind;
+    use crate::farcaster::indexer::EventStore;
+
+    let address: Address = address
+        .parse()
+        .map_err(|_| anyhow::anyhow!("❌ Invalid address '{address}'"))?;
+    println!("🕵️  Farcaster activity report for {address:?}");
+    println!("{}", "=".repeat(60));
+
+    let store = EventStore::open_default()?;
+    match store.last_block() {
+        Some(block) => println!("   History: local index (synced to block {block})"),
+        None => {
+            println!("   ⚠️  The local event index is empty; only current state is shown");
+            println!("   💡 Run 'castorix indexer sync' to include history");
+        }
+    }
+    let activity = store.wallet_activity(&format!("{address:?}"));
+
+    let contract_client =
+        FarcasterContractClient::new(rpc_url.to_string(), ContractAddresses::default())?;
+    let current_fid = contract_client.address_has_fid(address).await?;
+
+    let mut fids: Vec<u64> = activity.fids.iter().map(|o| o.fid).collect();
+    fids.extend(current_fid);
+    fids.sort_unstable();
+    fids.dedup();
+    let infos = if fids.is_empty() {
+        Vec::new()
+    } else {
+        contract_client.get_fid_info_batch(&fids).await?
+    };
+
+    println!("\n🆔 FIDs ({})", fids.len());
+    if fids.is_empty() {
+        println!("   None");
+    }
+    for info in &infos {
+        let owns_now = info.custody == address;
+        println!(
+            "   FID {}: {}",
+            info.fid,
+            if owns_now {
+                "owned now".to_string()
+            } else {
+                format!("now held by {:?}", info.custody)
+            }
+        );
+        for ownership in activity.fids.iter().filter(|o| o.fid == info.fid) {
+            let how = match ownership.acquired {
+                Acquisition::Registered => "registered",
+                Acquisition::TransferredIn => "transferred in",
+                Acquisition::Recovered => "recovered to it",
+            };
+            match ownership.until_block {
+                Some(until) => println!(
+                    "      {how} at block {}, left at block {until}",
+                    ownership.from_block
+                ),
+                None => println!("      {how} at block {}", ownership.from_block),
+            }
+        }
+        if owns_now && !activity.fids.iter().any(|o| o.fid == info.fid) {
+            println!("      acquired outside the indexed range");
+        }
+        println!(
+            "      Keys: {} active, {} inactive, {} pending",
+            info.active_keys, info.inactive_keys, info.pending_keys
+        );
+    }
+
+    println!("\n🔑 Signers added ({})", activity.signers_added.len());
+    for event in &activity.signers_added {
+        if let EventKind::KeyAdded { key, key_type, .. } = &event.kind {
+            println!(
+                "   Block {}: FID {} key {key} (type {key_type})",
+                event.block_number, event.fid
+            );
+        }
+    }
+
+    println!(
+        "\n🏠 Storage rented ({} unit(s) in {} rental(s))",
+        activity.rented_units(),
+        activity.rentals.len()
+    );
+    for event in &activity.rentals {
+        if let EventKind::Rent { units, .. } = &event.kind {
+            println!(
+                "   Block {}: {units} unit(s) for FID {}",
+                event.block_number, event.fid
+            );
+        }
+    }
+
+    if !include_costs {
+        return Ok(());
+    }
+    println!(
+        "\n💸 ETH spent ({} transaction(s) to check)",
+        activity.tx_hashes.len()
+    );
+    let costs: Vec<_> = stream::iter(activity.tx_hashes.iter())
+        .map(|tx_hash| {
+            let contract_client = &contract_client;
+            async move {
+                let hash: H256 = tx_hash.parse().map_err(|_| {
+                    anyhow::anyhow!("Invalid transaction hash in the index: {tx_hash}")
+                })?;
+                contract_client.get_transaction_cost(hash).await
+            }
+        })
+        .buffer_unordered(TX_COST_CONCURRENCY)
+        .collect()
+        .await;
+
+    let (mut payments, mut fees, mut sent, mut failed) = (U256::zero(), U256::zero(), 0, 0);
+    for cost in costs {
+        match cost {
+            // Only transactions the address sent itself cost it anything
+            Ok(Some(cost)) if cost.from == address => {
+                payments += cost.value;
+                fees += cost.fee;
+                sent += 1;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::debug!("Failed to fetch transaction cost: {e}");
+                failed += 1;
+            }
+        }
+    }
+    println!("   Sent by this address: {sent}");
+    println!("   Payments: {} ETH", format_ether(payments));
+    println!("   Gas fees: {} ETH", format_ether(fees));
+    println!("   Total: {} ETH", format_ether(payments + fees));
+    if failed > 0 {
+        println!("   ⚠️  {failed} transaction(s) could not be fetched and are not included");
+    }
+    Ok(())
+}
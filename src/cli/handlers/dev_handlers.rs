@@ -0,0 +1,64 @@
+use anyhow::Context;
+use anyhow::Result;
+use ethers::types::Address;
+
+use crate::cli::types::DevCommands;
+use crate::farcaster::contracts::types::ContractAddresses;
+use crate::farcaster::contracts::DevFaucet;
+use crate::farcaster::contracts::FarcasterContractClient;
+
+/// Handle local development commands
+pub async fn handle_dev_command(command: DevCommands) -> Result<()> {
+    match command {
+        DevCommands::Faucet {
+            address,
+            amount,
+            rpc_url,
+            funder_key,
+        } => {
+            handle_faucet(address, amount, rpc_url, funder_key).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn handle_faucet(
+    address: String,
+    amount: String,
+    rpc_url: String,
+    funder_key: Option<String>,
+) -> Result<()> {
+    let target: Address = address
+        .parse()
+        .with_context(|| format!("Invalid address: {address}"))?;
+    let amount_wei = ethers::utils::parse_ether(amount.trim())
+        .map_err(|e| anyhow::anyhow!("❌ Invalid amount '{amount}': {e}"))?;
+
+    let faucet = match funder_key {
+        Some(key) => DevFaucet::from_private_key(&key)?,
+        None => DevFaucet::anvil(),
+    };
+
+    println!("🚰 Dev faucet");
+    println!("   RPC: {rpc_url}");
+    println!("   Funder: {:?}", faucet.address());
+    println!("   Target: {target:?}");
+    println!("   Amount: {} ETH", ethers::utils::format_ether(amount_wei));
+
+    let client = FarcasterContractClient::new(rpc_url, ContractAddresses::default())?;
+    match faucet.fund(&client, target, amount_wei).await {
+        Ok(_) => {
+            let balance = client.get_eth_balance(target).await?;
+            println!(
+                "✅ New balance: {} ETH",
+                ethers::utils::format_ether(balance)
+            );
+        }
+        Err(e) => {
+            println!("❌ Faucet failed: {e}");
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
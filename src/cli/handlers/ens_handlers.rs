@@ -1,14 +1,29 @@
+use anyhow::Context;
 use anyhow::Result;
+use futures::stream;
+use futures::StreamExt;
 
+use crate::cli::output;
+use crate::cli::types::EnsBaseCommands;
 use crate::cli::types::EnsCommands;
+use crate::cli::types::ProofsCommands;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::policy;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
+use crate::ens_proof::normalize::normalize;
+use crate::ens_proof::precheck::FidAddresses;
+use crate::ens_proof::ProofStore;
 
 /// Handle ENS commands
 pub async fn handle_ens_command(
     command: EnsCommands,
     ens_proof: &crate::ens_proof::EnsProof,
+    hub_client: &FarcasterClient,
 ) -> Result<()> {
     match command {
         EnsCommands::Resolve { domain } => {
+            let domain = normalized_domain(&domain)?;
             println!("🔍 Resolving ENS domain: {domain}");
             match ens_proof.query_base_ens_contract(&domain).await {
                 Ok(Some(address)) => println!("✅ Resolved to: {address}"),
@@ -66,6 +81,7 @@ pub async fn handle_ens_command(
             }
         }
         EnsCommands::CheckBaseSubdomain { domain } => {
+            let domain = normalized_domain(&domain)?;
             println!("🔍 Checking Base subdomain: {domain}");
             match ens_proof.check_base_subdomain(&domain).await {
                 Ok(Some(owner)) => {
@@ -78,6 +94,7 @@ pub async fn handle_ens_command(
             }
         }
         EnsCommands::QueryBaseContract { domain } => {
+            let domain = normalized_domain(&domain)?;
             println!("🔗 Querying Base chain ENS contract for: {domain}");
             match ens_proof.query_base_ens_contract(&domain).await {
                 Ok(Some(owner)) => {
@@ -90,6 +107,7 @@ pub async fn handle_ens_command(
             }
         }
         EnsCommands::Verify { domain } => {
+            let domain = normalized_domain(&domain)?;
             println!("🔐 Verifying ownership of domain: {domain}");
             match ens_proof.verify_ens_ownership(&domain).await {
                 Ok(owned) => {
@@ -106,7 +124,9 @@ pub async fn handle_ens_command(
             domain,
             fid,
             wallet_name,
+            output,
         } => {
+            let domain = normalized_domain(&domain)?;
             if let Some(wallet_name) = &wallet_name {
                 println!("📝 Generating username proof for domain: {domain} (FID: {fid}) using wallet: {wallet_name}");
             } else {
@@ -123,11 +143,21 @@ pub async fn handle_ens_command(
                             println!("📄 Proof JSON:");
                             println!("{json}");
 
-                            // Save to file
-                            let filename =
-                                format!("proof_{}_{}.json", domain.replace(".", "_"), fid);
-                            std::fs::write(&filename, &json)?;
-                            println!("💾 Proof saved to: {filename}");
+                            let store = ProofStore::open_default()?;
+                            let stored = store.save(&domain, fid, &json)?;
+                            println!(
+                                "💾 Proof stored as {} ({})",
+                                stored.id,
+                                store.proof_path(&stored.id).display()
+                            );
+                            if let Some(output) = output {
+                                std::fs::write(&output, &json)?;
+                                println!("💾 Copy written to: {output}");
+                            }
+                            println!(
+                                "💡 Submit it with: castorix hub submit-proof {} {fid}",
+                                stored.id
+                            );
                         }
                         Err(e) => println!("❌ Failed to serialize proof: {e}"),
                     }
@@ -135,6 +165,14 @@ pub async fn handle_ens_command(
                 Err(e) => println!("❌ Failed to create proof: {e}"),
             }
         }
+        EnsCommands::Precheck {
+            file,
+            fid,
+            json,
+            concurrency,
+        } => precheck(ens_proof, hub_client, &file, fid, json, concurrency).await?,
+        EnsCommands::Proofs { action } => handle_proofs_command(action)?,
+        EnsCommands::Base { action } => handle_base_command(action).await?,
         EnsCommands::VerifyProof { proof_file } => {
             println!("🔍 Verifying proof from file: {proof_file}");
             let proof_content = std::fs::read_to_string(&proof_file)?;
@@ -168,3 +206,326 @@ pub async fn handle_ens_command(
     }
     Ok(())
 }
+
+/// Check every domain in a file for proof readiness and print a report
+async fn precheck(
+    ens_proof: &crate::ens_proof::EnsProof,
+    hub_client: &FarcasterClient,
+    file: &str,
+    fid: u64,
+    json: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let content =
+        std::fs::read_to_string(file).with_context(|| format!("Failed to read {file}"))?;
+    let domains: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    if domains.is_empty() {
+        anyhow::bail!("No domains in {file}");
+    }
+
+    let addresses = FidAddresses::fetch(hub_client, fid).await?;
+    if !json {
+        println!("🚦 Checking {} domain(s) for FID {fid}", domains.len());
+        println!("   Custody address: {:?}", addresses.custody);
+        println!("   Verified addresses: {}", addresses.verified.len());
+        println!();
+    }
+
+    let addresses = &addresses;
+    let reports: Vec<_> = stream::iter(domains)
+        .map(|domain| ens_proof.precheck(domain, addresses))
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+    let ready = reports.iter().filter(|report| report.is_ready()).count();
+
+    if json {
+        println!("{}", output::json(&reports)?);
+    } else {
+        for report in &reports {
+            let name = report.domain.as_deref().unwrap_or(&report.input);
+            let mark = if report.is_ready() { "✅" } else { "❌" };
+            println!("{mark} {name}");
+            if let Some(owner) = &report.owner {
+                let relation = report.owner_relation.unwrap_or("not linked to the FID");
+                println!("   Owner: {owner} ({relation})");
+            }
+            for warning in &report.warnings {
+                println!("   ⚠️  {warning}");
+            }
+            for problem in &report.problems {
+                println!("   ❌ {problem}");
+            }
+        }
+        println!();
+        println!("📊 {ready}/{} domain(s) ready", reports.len());
+    }
+
+    if ready < reports.len() {
+        anyhow::bail!("{} domain(s) are not ready", reports.len() - ready);
+    }
+    Ok(())
+}
+
+/// Normalize a domain argument, saying so when it changes
+fn normalized_domain(domain: &str) -> Result<String> {
+    let normalized = normalize(domain)?;
+    if normalized != domain {
+        println!("ℹ️  Using normalized name: {normalized}");
+    }
+    Ok(normalized)
+}
+
+/// Handle basename registrar commands
+async fn handle_base_command(command: EnsBaseCommands) -> Result<()> {
+    use crate::ens_proof::basename_registrar::basename_label;
+    use crate::ens_proof::basename_registrar::price_with_buffer;
+    use crate::ens_proof::BasenameRegistrar;
+
+    let registrar = BasenameRegistrar::new(crate::consts::get_config().eth_base_rpc_url())?;
+    match command {
+        EnsBaseCommands::Register {
+            name,
+            years,
+            fid,
+            wallet_name,
+            primary,
+            yes,
+        } => {
+            let label = basename_label(&name)?;
+            let duration = years_to_duration(years)?;
+            println!("📝 Registering {label}.base.eth for {years} year(s)");
+
+            if !registrar.available(&label).await? {
+                println!("❌ {label}.base.eth is not available");
+                return Ok(());
+            }
+            let price = registrar.register_price(&label, duration).await?;
+            let wallet = load_paying_wallet(fid, wallet_name.as_deref()).await?;
+            let owner = ethers::signers::Signer::address(&wallet);
+            let value = price_with_buffer(price);
+            let balance = registrar.balance(owner).await?;
+
+            println!("\n📋 Registration Details:");
+            println!("   Name: {label}.base.eth");
+            println!("   Owner: {owner:?}");
+            println!("   Price: {} ETH", ethers::utils::format_ether(price));
+            println!(
+                "   Sending: {} ETH (excess is refunded)",
+                ethers::utils::format_ether(value)
+            );
+            println!("   Balance: {} ETH", ethers::utils::format_ether(balance));
+            println!("   Primary name: {}", if primary { "yes" } else { "no" });
+            if balance < value {
+                return Err(anyhow::anyhow!("❌ Insufficient balance on Base"));
+            }
+            if !confirm_transaction("Do you want to register this basename?", yes)? {
+                println!("❌ Operation cancelled by user");
+                return Ok(());
+            }
+
+            let tx_hash = registrar
+                .register(&wallet, &label, owner, duration, primary, value)
+                .await?;
+            println!("✅ Registered {label}.base.eth");
+            println!("   Transaction Hash: {tx_hash:?}");
+            println!("💡 Create a username proof with: castorix ens proof {label}.base.eth <fid>");
+        }
+        EnsBaseCommands::Renew {
+            name,
+            years,
+            fid,
+            wallet_name,
+            yes,
+        } => {
+            let label = basename_label(&name)?;
+            let duration = years_to_duration(years)?;
+            println!("🔁 Renewing {label}.base.eth for {years} year(s)");
+
+            if registrar.available(&label).await? {
+                println!("❌ {label}.base.eth is not registered; use 'castorix ens base register'");
+                return Ok(());
+            }
+            let price = registrar.renew_price(&label, duration).await?;
+            let wallet = load_paying_wallet(fid, wallet_name.as_deref()).await?;
+            let payer = ethers::signers::Signer::address(&wallet);
+            let value = price_with_buffer(price);
+
+            println!("\n📋 Renewal Details:");
+            println!("   Name: {label}.base.eth");
+            println!("   Paid by: {payer:?}");
+            println!("   Price: {} ETH", ethers::utils::format_ether(price));
+            println!(
+                "   Sending: {} ETH (excess is refunded)",
+                ethers::utils::format_ether(value)
+            );
+            if !confirm_transaction("Do you want to renew this basename?", yes)? {
+                println!("❌ Operation cancelled by user");
+                return Ok(());
+            }
+
+            let tx_hash = registrar.renew(&wallet, &label, duration, value).await?;
+            println!("✅ Renewed {label}.base.eth");
+            println!("   Transaction Hash: {tx_hash:?}");
+        }
+    }
+    Ok(())
+}
+
+/// Registration period in seconds for a number of years
+fn years_to_duration(years: u64) -> Result<u64> {
+    if years == 0 {
+        return Err(anyhow::anyhow!("❌ --years must be at least 1"));
+    }
+    Ok(years * crate::ens_proof::basename_registrar::SECONDS_PER_YEAR)
+}
+
+/// Load the wallet paying for a basename: a FID's custody wallet or a named encrypted wallet
+async fn load_paying_wallet(
+    fid: Option<u64>,
+    wallet_name: Option<&str>,
+) -> Result<ethers::signers::LocalWallet> {
+    use crate::core::crypto::encrypted_storage::EncryptedEthKeyManager;
+    use crate::encrypted_key_manager::prompt_password;
+    use crate::encrypted_key_manager::EncryptedKeyManager;
+
+    match (fid, wallet_name) {
+        (Some(fid), _) => {
+            let custody_key_file = EncryptedEthKeyManager::custody_key_file(fid)?;
+            if !std::path::Path::new(&custody_key_file).exists() {
+                return Err(anyhow::anyhow!(
+                    "❌ No custody key found for FID {fid}. Import one with: castorix custody import {fid}"
+                ));
+            }
+            let manager = EncryptedEthKeyManager::load_from_file(&custody_key_file)?;
+            let password =
+                prompt_password(&format!("Enter password for custody wallet (FID {fid}): "))?;
+            Ok(manager.get_wallet(fid, &password)?)
+        }
+        (None, Some(wallet_name)) => {
+            let mut manager = EncryptedKeyManager::default_config();
+            if !manager.key_exists(wallet_name) {
+                return Err(anyhow::anyhow!(
+                    "❌ Wallet '{wallet_name}' not found. Use 'castorix key list' to see available wallets"
+                ));
+            }
+            let password =
+                prompt_password(&format!("Enter password for wallet '{wallet_name}': "))?;
+            manager.load_and_decrypt(&password, wallet_name).await?;
+            Ok(manager
+                .key_manager()
+                .ok_or_else(|| anyhow::anyhow!("Wallet '{wallet_name}' could not be loaded"))?
+                .wallet()
+                .clone())
+        }
+        (None, None) => Err(anyhow::anyhow!(
+            "❌ Choose the paying wallet with --fid <FID> (custody wallet) or --wallet-name <NAME>"
+        )),
+    }
+}
+
+/// Ask for a yes/no confirmation unless `yes` was given
+fn confirm_transaction(prompt: &str, yes: bool) -> Result<bool> {
+    policy::current().check_confirmation(yes)?;
+    if yes {
+        return Ok(true);
+    }
+    prompt::ask(
+        InputKind::Confirmation,
+        &format!("\n❓ {prompt} (yes/no): "),
+    )?;
+    let mut confirmation = String::new();
+    std::io::stdin().read_line(&mut confirmation)?;
+    let confirmation = confirmation.trim().to_lowercase();
+    Ok(confirmation == "yes" || confirmation == "y")
+}
+
+/// Handle stored username proof commands
+fn handle_proofs_command(command: ProofsCommands) -> Result<()> {
+    let store = ProofStore::open_default()?;
+    match command {
+        ProofsCommands::List => {
+            let proofs = store.list()?;
+            if proofs.is_empty() {
+                println!(
+                    "📭 No stored proofs. Generate one with: castorix ens proof <domain> <fid>"
+                );
+                return Ok(());
+            }
+            println!("🗄️  Stored proofs: {}", proofs.len());
+            for proof in proofs {
+                let status = match (proof.submitted(), proof.submissions.last()) {
+                    (Some(s), _) => {
+                        format!("✅ submitted {}", s.submitted_at.format("%Y-%m-%d %H:%M"))
+                    }
+                    (None, Some(_)) => {
+                        format!("❌ {} failed submission(s)", proof.submissions.len())
+                    }
+                    (None, None) => "📝 not submitted".to_string(),
+                };
+                println!(
+                    "   {}  {} → FID {}  created {}  {}",
+                    proof.id,
+                    proof.domain,
+                    proof.fid,
+                    proof.created_at.format("%Y-%m-%d %H:%M"),
+                    status
+                );
+            }
+        }
+        ProofsCommands::Show { id } => {
+            let (metadata, proof) = store.get(&id)?;
+            println!("📝 Proof {}", metadata.id);
+            println!("{}", "=".repeat(40));
+            println!("🌐 Domain: {}", metadata.domain);
+            println!("🆔 FID: {}", metadata.fid);
+            println!("🕒 Created: {}", metadata.created_at.to_rfc3339());
+            println!("📁 File: {}", store.proof_path(&id).display());
+            println!("📄 Proof JSON:");
+            println!("{proof}");
+            if metadata.submissions.is_empty() {
+                println!("📭 Never submitted");
+            } else {
+                println!("📤 Submissions:");
+                for submission in &metadata.submissions {
+                    println!(
+                        "   {} {} to {}",
+                        if submission.success { "✅" } else { "❌" },
+                        submission.submitted_at.to_rfc3339(),
+                        submission.hub_url
+                    );
+                    if let Some(tx_hash) = &submission.tx_hash {
+                        println!("      tx {tx_hash}");
+                    }
+                    println!("      {}", submission.response);
+                }
+            }
+        }
+        ProofsCommands::Delete { id, yes } => {
+            let (metadata, _) = store.get(&id)?;
+            if !yes {
+                prompt::ask(
+                    InputKind::Confirmation,
+                    &format!(
+                        "❓ Delete proof {} for {} (FID {})? (yes/no): ",
+                        metadata.id, metadata.domain, metadata.fid
+                    ),
+                )?;
+                let mut confirmation = String::new();
+                std::io::stdin().read_line(&mut confirmation)?;
+                let confirmation = confirmation.trim().to_lowercase();
+                if confirmation != "yes" && confirmation != "y" {
+                    println!("❌ Operation cancelled by user");
+                    return Ok(());
+                }
+            }
+            store.delete(&id)?;
+            println!("🗑️  Deleted proof {id}");
+        }
+    }
+    Ok(())
+}
@@ -1,45 +1,113 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+
 use anyhow::Context;
 use anyhow::Result;
 use ethers::middleware::Middleware;
-use ethers::providers::Http;
-use ethers::providers::Provider;
 use ethers::signers::LocalWallet;
 use ethers::signers::Signer;
 use ethers::types::Address;
 use ethers::utils::format_ether;
+use futures::stream;
+use futures::StreamExt;
 
+use crate::cli::endpoints::EndpointOverrides;
+use crate::cli::output;
 use crate::cli::types::FidCommands;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_propagation::ExpectedEvent;
+use crate::core::client::hub_propagation::DEFAULT_HUB_WAIT_TIMEOUT;
+use crate::core::policy;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
 use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+use crate::farcaster::contracts::multicall::ScanFilter;
+use crate::farcaster::contracts::multicall::MAX_FIDS_PER_SCAN_MULTICALL;
+use crate::farcaster::contracts::sponsored::SponsorConfig;
 use crate::farcaster::contracts::types::ContractAddresses;
 use crate::farcaster::contracts::types::ContractResult;
 
 /// Handle FID registration and management commands
-pub async fn handle_fid_command(command: FidCommands, storage_path: Option<&str>) -> Result<()> {
+pub async fn handle_fid_command(
+    command: FidCommands,
+    storage_path: Option<&str>,
+    endpoints: &EndpointOverrides,
+) -> Result<()> {
     match command {
         FidCommands::Register {
             wallet,
             extra_storage,
             recovery,
+            sponsored,
+            bundler_url,
+            paymaster_url,
             dry_run,
+            wait_hub,
             yes,
         } => {
-            handle_fid_register(wallet, extra_storage, recovery, dry_run, yes, storage_path)
-                .await?;
+            let sponsor = sponsored
+                .then(|| SponsorConfig::resolve(bundler_url, paymaster_url))
+                .transpose()?;
+            let hub_client = wait_hub.then(|| FarcasterClient::read_only(endpoints.hub_url()));
+            handle_fid_register(
+                endpoints.op_rpc_url(),
+                wallet,
+                extra_storage,
+                recovery,
+                sponsor,
+                hub_client.as_ref(),
+                dry_run,
+                yes,
+                storage_path,
+            )
+            .await?;
         }
         FidCommands::Price { extra_storage } => {
-            handle_fid_price(extra_storage).await?;
+            handle_fid_price(endpoints.op_rpc_url(), extra_storage).await?;
         }
         FidCommands::List { wallet } => {
-            handle_fid_list(wallet, storage_path).await?;
+            handle_fid_list(wallet, storage_path, &endpoints.op_rpc_url()).await?;
+        }
+        FidCommands::Lookup {
+            address,
+            fids,
+            json,
+        } => {
+            let hub_client = FarcasterClient::read_only(endpoints.hub_url());
+            handle_fid_lookup(&hub_client, &endpoints.op_rpc_url(), address, fids, json).await?;
+        }
+        FidCommands::Scan {
+            from,
+            to,
+            filter,
+            output,
+            concurrency,
+        } => {
+            let hub_client = FarcasterClient::read_only(endpoints.hub_url());
+            handle_fid_scan(
+                &hub_client,
+                &endpoints.op_rpc_url(),
+                from,
+                to,
+                filter.parse()?,
+                output,
+                concurrency,
+            )
+            .await?;
         }
     }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_fid_register(
+    rpc_url: String,
     wallet_name: Option<String>,
     extra_storage: u64,
     recovery: Option<String>,
+    sponsor: Option<SponsorConfig>,
+    wait_hub: Option<&FarcasterClient>,
     dry_run: bool,
     yes: bool,
     storage_path: Option<&str>,
@@ -47,10 +115,6 @@ async fn handle_fid_register(
     println!("🆕 Register New FID");
     println!("{}", "=".repeat(40));
 
-    // Get RPC URL from configuration (Farcaster contracts are on Optimism)
-    let config = crate::consts::get_config();
-    let rpc_url = config.eth_op_rpc_url().to_string();
-
     // Check if using placeholder values
     if rpc_url.contains("your_api_key_here") || rpc_url == "https://mainnet.optimism.io" {
         println!("⚠️  Configuration Warning:");
@@ -147,10 +211,28 @@ async fn handle_fid_register(
         println!("   Total Price: {} ETH", format_ether(total_price));
     }
 
-    // Check wallet balance
-    let provider = Provider::<Http>::try_from(&rpc_url)?;
-    let balance = provider.get_balance(wallet.address(), None).await?;
-    println!("   Wallet Balance: {} ETH", format_ether(balance));
+    let sponsored_sender = match &sponsor {
+        Some(sponsor) => {
+            // Gas is sponsored; the fee comes from the wallet's smart account
+            let sender = sponsor.sender(&rpc_url, wallet.clone())?;
+            println!("\n⛽ Sponsored mode (ERC-4337)");
+            println!("   Bundler: {}", sponsor.bundler_url);
+            println!("   Paymaster: {}", sponsor.paymaster_url);
+            println!("   Smart Account: {:?}", sender.account_address().await?);
+            println!(
+                "   Smart Account Balance: {} ETH (pays the fee)",
+                format_ether(sender.account_balance().await?)
+            );
+            Some(sender)
+        }
+        None => {
+            // Check wallet balance
+            let provider = crate::core::utils::http::provider(&rpc_url)?;
+            let balance = provider.get_balance(wallet.address(), None).await?;
+            println!("   Wallet Balance: {} ETH", format_ether(balance));
+            None
+        }
+    };
 
     if dry_run {
         println!("\n🔍 DRY RUN MODE - No transaction will be sent");
@@ -166,13 +248,15 @@ async fn handle_fid_register(
     println!("   • Make sure you have sufficient ETH for gas and registration");
 
     // Ask for user confirmation (skip if --yes is provided)
+    policy::current().check_confirmation(yes)?;
     if !yes {
-        print!("\n❓ Do you want to proceed with FID registration? (yes/no): ");
-        use std::io::Write;
+        prompt::ask(
+            InputKind::Confirmation,
+            "\n❓ Do you want to proceed with FID registration? (yes/no): ",
+        )?;
         use std::io::{
             self,
         };
-        io::stdout().flush()?;
 
         let mut confirmation = String::new();
         io::stdin().read_line(&mut confirmation)?;
@@ -188,6 +272,29 @@ async fn handle_fid_register(
 
     println!("✅ Proceeding with FID registration...");
 
+    if let Some(sender) = &sponsored_sender {
+        println!("🚀 Registering FID through the bundler...");
+        return match contract_client
+            .register_fid_sponsored(sender, recovery_address, extra_storage)
+            .await?
+        {
+            ContractResult::Success((fid, receipt)) => {
+                println!("✅ FID registration successful!");
+                println!("   FID: {}", fid);
+                println!("   UserOperation: {:?}", receipt.user_op_hash);
+                println!("   Transaction Hash: {:?}", receipt.transaction_hash);
+                if let Some(hub_client) = wait_hub {
+                    wait_for_hub_registration(hub_client, fid).await?;
+                }
+                Ok(())
+            }
+            ContractResult::Error(e) => {
+                println!("❌ FID registration failed: {}", e);
+                Err(anyhow::anyhow!("FID registration failed: {}", e))
+            }
+        };
+    }
+
     // Register FID
     let result = if extra_storage > 0 {
         println!("🚀 Registering FID with {extra_storage} extra storage units...");
@@ -206,6 +313,9 @@ async fn handle_fid_register(
             if !overpayment.is_zero() {
                 println!("   Overpayment: {} ETH", format_ether(overpayment));
             }
+            if let Some(hub_client) = wait_hub {
+                wait_for_hub_registration(hub_client, fid).await?;
+            }
         }
         ContractResult::Error(e) => {
             println!("❌ FID registration failed: {}", e);
@@ -216,14 +326,20 @@ async fn handle_fid_register(
     Ok(())
 }
 
-async fn handle_fid_price(extra_storage: u64) -> Result<()> {
+/// Poll the hub until it has indexed a new FID and report the latency
+async fn wait_for_hub_registration(hub_client: &FarcasterClient, fid: u64) -> Result<()> {
+    println!("⏳ Waiting for the hub to index FID {fid}...");
+    let propagation = hub_client
+        .wait_for_onchain_event(fid, &ExpectedEvent::IdRegister, DEFAULT_HUB_WAIT_TIMEOUT)
+        .await?;
+    println!("✅ Hub indexed FID {fid} {}", propagation.summary());
+    Ok(())
+}
+
+async fn handle_fid_price(rpc_url: String, extra_storage: u64) -> Result<()> {
     println!("💰 FID Registration Price");
     println!("{}", "=".repeat(40));
 
-    // Get RPC URL from configuration (Farcaster contracts are on Optimism)
-    let config = crate::consts::get_config();
-    let rpc_url = config.eth_op_rpc_url().to_string();
-
     // Check if using placeholder values
     if rpc_url.contains("your_api_key_here") || rpc_url == "https://mainnet.optimism.io" {
         println!("⚠️  Configuration Warning:");
@@ -275,13 +391,14 @@ async fn handle_fid_price(extra_storage: u64) -> Result<()> {
     Ok(())
 }
 
-async fn handle_fid_list(wallet_name: Option<String>, storage_path: Option<&str>) -> Result<()> {
+async fn handle_fid_list(
+    wallet_name: Option<String>,
+    storage_path: Option<&str>,
+    rpc_url: &str,
+) -> Result<()> {
     println!("📋 FIDs Owned by Wallet");
     println!("{}", "=".repeat(40));
 
-    // Get RPC URL from configuration
-    let rpc_url = crate::consts::get_config().eth_rpc_url().to_string();
-
     // Get wallet address
     let wallet_address = if let Some(name) = wallet_name {
         // Load from encrypted storage
@@ -322,7 +439,8 @@ async fn handle_fid_list(wallet_name: Option<String>, storage_path: Option<&str>
     println!("   Wallet Address: {wallet_address}");
 
     // Create contract client (read-only)
-    let contract_client = FarcasterContractClient::new(rpc_url, ContractAddresses::default())?;
+    let contract_client =
+        FarcasterContractClient::new(rpc_url.to_string(), ContractAddresses::default())?;
 
     // Query FID for this address
     println!("\n🔍 Querying FID for wallet address...");
@@ -351,3 +469,223 @@ async fn handle_fid_list(wallet_name: Option<String>, storage_path: Option<&str>
 
     Ok(())
 }
+
+async fn handle_fid_lookup(
+    hub_client: &FarcasterClient,
+    rpc_url: &str,
+    address: String,
+    fids_file: Option<String>,
+    json: bool,
+) -> Result<()> {
+    use crate::core::client::profile::current_profile;
+    use crate::core::client::verifications::normalize_address;
+    use crate::core::client::verifications::VerificationMapper;
+    use crate::core::client::verifications::DEFAULT_VERIFICATION_CONCURRENCY;
+
+    let address = normalize_address(&address)?;
+    if !json {
+        println!("🔎 Looking up {address}");
+    }
+
+    // Custody: only Ethereum addresses can own a FID
+    let custody_fid = if address.starts_with("0x") {
+        let contract_client =
+            FarcasterContractClient::new(rpc_url.to_string(), ContractAddresses::default())?;
+        let parsed: Address = address.parse()?;
+        match contract_client.address_has_fid(parsed).await {
+            Ok(fid) => fid,
+            Err(e) => {
+                eprintln!("⚠️  Custody check failed: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Verified: search cached verifications plus any FIDs given
+    let mapper = VerificationMapper::with_cache_file(
+        DEFAULT_VERIFICATION_CONCURRENCY,
+        VerificationMapper::default_cache_path(),
+    );
+    let mut candidates = mapper.cached_fids();
+    if let Some(file) = &fids_file {
+        candidates.extend(crate::cli::handlers::hub_handlers::read_fids_file(file)?);
+    }
+    if let Some(fid) = custody_fid {
+        // The custody FID may also have verified its own custody address
+        candidates.push(fid);
+    }
+    let map = mapper.fetch_all(hub_client, &candidates).await;
+    if let Err(e) = mapper.save() {
+        eprintln!("⚠️  Failed to save verification cache: {e}");
+    }
+    let verified_fids = map.reverse_index().remove(&address).unwrap_or_default();
+
+    let role = match (custody_fid, verified_fids.is_empty()) {
+        (Some(_), true) => "custody",
+        (Some(_), false) => "custody+verified",
+        (None, false) => "verified",
+        (None, true) => "unknown",
+    };
+
+    let mut fids: Vec<u64> = custody_fid.into_iter().collect();
+    fids.extend(
+        verified_fids
+            .iter()
+            .filter(|fid| Some(**fid) != custody_fid),
+    );
+    let mut profiles = Vec::new();
+    for &fid in &fids {
+        let profile = match hub_client.get_user_profile(fid).await {
+            Ok(messages) => current_profile(&messages)
+                .into_iter()
+                .map(|(field, value)| (field.to_string(), value))
+                .collect(),
+            Err(_) => std::collections::BTreeMap::new(),
+        };
+        profiles.push((fid, profile));
+    }
+
+    if json {
+        let profiles: Vec<serde_json::Value> = profiles
+            .iter()
+            .map(|(fid, profile)| serde_json::json!({ "fid": fid, "profile": profile }))
+            .collect();
+        println!(
+            "{}",
+            output::json(&serde_json::json!({
+                "address": address,
+                "role": role,
+                "custody_fid": custody_fid,
+                "verified_fids": verified_fids,
+                "searched_fids": map.fids.len(),
+                "profiles": profiles,
+            }))?
+        );
+        return Ok(());
+    }
+
+    match custody_fid {
+        Some(fid) => println!("🔑 Custody address of FID {fid}"),
+        None => println!("🔑 Not a custody address"),
+    }
+    if verified_fids.is_empty() {
+        println!("🔗 Not verified by any of {} searched FIDs", map.fids.len());
+    } else {
+        let list: Vec<String> = verified_fids.iter().map(|f| f.to_string()).collect();
+        println!("🔗 Verified by FID {}", list.join(", "));
+    }
+    for (fid, profile) in &profiles {
+        let username = profile.get("username").map_or("unknown", |u| u.as_str());
+        println!("\n👤 FID {fid}: @{username}");
+        if let Some(display) = profile.get("display") {
+            println!("   Display Name: {display}");
+        }
+        if let Some(bio) = profile.get("bio") {
+            println!("   Bio: {}", bio.lines().next().unwrap_or_default());
+        }
+    }
+    if role == "unknown" {
+        println!("\n❓ Unknown address");
+        println!("💡 Hubs cannot search verifications by address; pass candidate FIDs with --fids");
+    }
+    for (fid, error) in &map.failed {
+        eprintln!("⚠️  FID {fid}: {error}");
+    }
+
+    Ok(())
+}
+
+async fn handle_fid_scan(
+    hub_client: &FarcasterClient,
+    rpc_url: &str,
+    from: u64,
+    to: u64,
+    filter: ScanFilter,
+    output: Option<String>,
+    concurrency: usize,
+) -> Result<()> {
+    if from == 0 || to < from {
+        anyhow::bail!(
+            "Invalid FID range {from}..={to}: FIDs start at 1 and --to must be >= --from"
+        );
+    }
+    let contract_client =
+        FarcasterContractClient::new(rpc_url.to_string(), ContractAddresses::default())?;
+
+    // Progress goes to stderr so CSV on stdout stays clean
+    eprintln!(
+        "🔬 Scanning FIDs {from}..={to} ({} per batch, {} batch(es) at a time)",
+        MAX_FIDS_PER_SCAN_MULTICALL,
+        concurrency.max(1)
+    );
+    let mut out: Box<dyn Write> = match &output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).with_context(|| format!("Failed to create {path}"))?,
+        )),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    writeln!(out, "fid,custody,recovery")?;
+
+    let fids: Vec<u64> = (from..=to).collect();
+    let contract_client = &contract_client;
+    let mut batches = stream::iter(fids.chunks(MAX_FIDS_PER_SCAN_MULTICALL))
+        .map(|chunk| async move {
+            let ownership = contract_client.scan_fid_ownership(chunk).await;
+            (chunk, ownership)
+        })
+        .buffered(concurrency.max(1));
+
+    let mut scanned = 0;
+    let mut matched = 0;
+    let mut failed = Vec::new();
+    while let Some((chunk, ownership)) = batches.next().await {
+        let ownership = match ownership {
+            Ok(ownership) => ownership,
+            Err(e) => {
+                eprintln!(
+                    "⚠️  FIDs {}..={} failed: {e}",
+                    chunk[0],
+                    chunk[chunk.len() - 1]
+                );
+                failed.push((chunk[0], chunk[chunk.len() - 1]));
+                continue;
+            }
+        };
+        for entry in ownership.iter().filter(|entry| filter.matches(entry)) {
+            if filter.needs_verifications() {
+                let custody = format!("{:?}", entry.custody);
+                match hub_client.get_verifications(entry.fid).await {
+                    Ok(verifications) if verifications.eth_addresses.contains(&custody) => {}
+                    Ok(_) => continue,
+                    Err(e) => {
+                        eprintln!("⚠️  Verifications of FID {} failed: {e}", entry.fid);
+                        continue;
+                    }
+                }
+            }
+            writeln!(
+                out,
+                "{},{:?},{:?}",
+                entry.fid, entry.custody, entry.recovery
+            )?;
+            matched += 1;
+        }
+        out.flush()?;
+        scanned += chunk.len();
+    }
+
+    eprintln!("✅ Scanned {scanned} FID(s), {matched} matched");
+    if let Some(path) = &output {
+        eprintln!("💾 Results written to {path}");
+    }
+    if !failed.is_empty() {
+        let ranges: Vec<String> = failed
+            .iter()
+            .map(|(first, last)| format!("{first}..={last}"))
+            .collect();
+        anyhow::bail!("{} batch(es) failed: {}", failed.len(), ranges.join(", "));
+    }
+    Ok(())
+}
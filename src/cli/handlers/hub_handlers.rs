@@ -1,6 +1,25 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Context;
 use anyhow::Result;
 
+use crate::cli::output;
 use crate::cli::types::HubCommands;
+use crate::core::client::export::ExportKind;
+use crate::core::client::export::ResumeMode;
+use crate::core::client::follower_count::CountSource;
+use crate::core::client::hub_client::FARCASTER_EPOCH;
+use crate::core::client::time_window::TimeWindow;
+use crate::core::policy;
+use crate::core::services::profile::StorageUsage;
+use crate::core::services::GraphService;
+use crate::core::services::LinkDirection;
+use crate::core::services::MessageService;
+use crate::core::services::ProfileService;
+use crate::core::services::SpamService;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
 
 /// Handle Farcaster Hub commands
 pub async fn handle_hub_command(
@@ -13,7 +32,7 @@ pub async fn handle_hub_command(
             match hub_client.get_user(fid).await {
                 Ok(user_data) => {
                     println!("✅ User data retrieved:");
-                    println!("{}", serde_json::to_string_pretty(&user_data)?);
+                    println!("{}", output::json(&user_data)?);
                 }
                 Err(e) => println!("❌ Failed to get user data: {e}"),
             }
@@ -22,8 +41,9 @@ pub async fn handle_hub_command(
             proof_file,
             fid,
             wallet_name,
+            auth_wallet,
         } => {
-            handle_submit_proof(hub_client, proof_file, fid, wallet_name).await?;
+            handle_submit_proof(hub_client, proof_file, fid, wallet_name, auth_wallet).await?;
         }
         HubCommands::EthAddresses { fid } => {
             println!("🔍 Getting Ethereum addresses for FID: {fid}");
@@ -72,6 +92,9 @@ pub async fn handle_hub_command(
                 println!("❌ Failed to create key manager for ENS query");
             }
         }
+        HubCommands::Proofs { fid, json } => {
+            handle_username_proofs(hub_client, fid, json).await?;
+        }
         HubCommands::CustodyAddress { fid } => {
             println!("🏠 Getting custody address for FID: {fid}");
             match hub_client.get_custody_address(fid).await {
@@ -87,26 +110,255 @@ pub async fn handle_hub_command(
         HubCommands::Info => {
             handle_hub_info(hub_client).await?;
         }
-        HubCommands::Followers { fid, limit } => {
-            handle_followers(hub_client, fid, limit).await?;
+        HubCommands::Followers {
+            fid,
+            limit,
+            since,
+            until,
+        } => {
+            let window = TimeWindow::parse(since.as_deref(), until.as_deref())?;
+            handle_followers(hub_client, fid, limit, window).await?;
+        }
+        HubCommands::Following {
+            fid,
+            limit,
+            since,
+            until,
+        } => {
+            let window = TimeWindow::parse(since.as_deref(), until.as_deref())?;
+            handle_following(hub_client, fid, limit, window).await?;
         }
-        HubCommands::Following { fid, limit } => {
-            handle_following(hub_client, fid, limit).await?;
+        HubCommands::Export {
+            what,
+            fid,
+            output,
+            resume,
+            fresh,
+            since,
+            until,
+        } => {
+            let window = TimeWindow::parse(since.as_deref(), until.as_deref())?;
+            handle_export(
+                hub_client,
+                what.parse()?,
+                fid,
+                window,
+                output,
+                resume,
+                fresh,
+            )
+            .await?;
         }
         HubCommands::Profile { fid, all } => {
             handle_profile(hub_client, fid, all).await?;
         }
-        HubCommands::Stats { fid } => {
-            handle_stats(hub_client, fid).await?;
+        HubCommands::SetProfile {
+            fid,
+            username,
+            display,
+            bio,
+            pfp,
+            banner,
+            url,
+            location,
+            twitter,
+            github,
+            dry_run,
+        } => {
+            let requested = [
+                ("username", username),
+                ("display", display),
+                ("bio", bio),
+                ("pfp", pfp),
+                ("banner", banner),
+                ("url", url),
+                ("location", location),
+                ("twitter", twitter),
+                ("github", github),
+            ];
+            handle_set_profile(hub_client, fid, &requested, dry_run).await?;
         }
-        HubCommands::Spam { fids } => {
-            handle_spam_check(fids).await?;
+        HubCommands::Cast {
+            fid,
+            text,
+            embed_urls,
+            embed_casts,
+            reply_to,
+            parent_url,
+            thread,
+            long,
+            no_mentions,
+            dry_run,
+        } => {
+            use crate::core::client::cast::CastEmbed;
+            use crate::core::protocol::builder::parse_cast_ref;
+
+            let mut embeds: Vec<CastEmbed> = embed_urls.into_iter().map(CastEmbed::Url).collect();
+            for embed in &embed_casts {
+                let cast_id = parse_cast_ref(embed).context("Invalid --embed-cast")?;
+                embeds.push(CastEmbed::Cast(cast_id));
+            }
+            let draft = crate::core::client::cast::CastDraft {
+                text,
+                embeds,
+                parent_url,
+                ..Default::default()
+            };
+            handle_cast(
+                hub_client,
+                fid,
+                draft,
+                reply_to,
+                thread,
+                long,
+                !no_mentions,
+                dry_run,
+            )
+            .await?;
+        }
+        HubCommands::UnfollowInactive {
+            fid,
+            inactive_days,
+            keep_spam,
+            per_minute,
+            concurrency,
+            dry_run,
+            yes,
+        } => {
+            handle_unfollow_inactive(
+                hub_client,
+                fid,
+                inactive_days,
+                keep_spam,
+                per_minute,
+                concurrency,
+                dry_run,
+                yes,
+            )
+            .await?;
+        }
+        HubCommands::Key { action } => {
+            crate::cli::handlers::key_handlers::hub::handle_hub_key_command(
+                action,
+                hub_client,
+                &crate::cli::endpoints::EndpointOverrides::default(),
+            )
+            .await?;
+        }
+        HubCommands::Stats { fid, sample_pages } => {
+            handle_stats(hub_client, fid, sample_pages).await?;
+        }
+        HubCommands::MessageCounts { fid, max, json } => {
+            handle_message_counts(hub_client, fid, max, json).await?;
+        }
+        HubCommands::Spam { fids, file, json } => {
+            handle_spam_check(fids, file, json).await?;
         }
         HubCommands::SpamStat => {
             handle_spam_stat(hub_client).await?;
         }
-        HubCommands::Casts { fid, limit, json } => {
-            handle_casts(hub_client, fid, limit, json).await?;
+        HubCommands::Casts {
+            fid,
+            limit,
+            json,
+            no_embeds,
+            since,
+            until,
+        } => {
+            let window = TimeWindow::parse(since.as_deref(), until.as_deref())?;
+            handle_casts(hub_client, fid, limit, window, json, !no_embeds).await?;
+        }
+        HubCommands::Reactions {
+            fid,
+            reaction_type,
+            limit,
+            json,
+            since,
+            until,
+        } => {
+            let window = TimeWindow::parse(since.as_deref(), until.as_deref())?;
+            handle_reactions(
+                hub_client,
+                fid,
+                reaction_type.as_deref(),
+                limit,
+                window,
+                json,
+            )
+            .await?;
+        }
+        HubCommands::Thread {
+            fid,
+            hash,
+            depth,
+            max_parents,
+            max_casts,
+            output,
+            no_embeds,
+        } => {
+            let limits = crate::core::client::ThreadLimits {
+                max_parents,
+                max_depth: depth,
+                max_casts,
+            };
+            handle_thread(hub_client, fid, &hash, limits, output == "json", !no_embeds).await?;
+        }
+        HubCommands::CastStats {
+            fid,
+            hash,
+            refresh,
+            json,
+        } => {
+            handle_cast_stats(hub_client, fid, &hash, refresh, json).await?;
+        }
+        HubCommands::TopCasts {
+            fid,
+            days,
+            limit,
+            refresh,
+            json,
+        } => {
+            handle_top_casts(hub_client, fid, days, limit, refresh, json).await?;
+        }
+        HubCommands::Link { target, fid, qr } => {
+            handle_link(hub_client, &target, fid, qr).await?;
+        }
+        HubCommands::Mentions {
+            fid,
+            limit,
+            page,
+            casts,
+            json,
+        } => {
+            handle_mentions(hub_client, fid, limit, page, casts, json).await?;
+        }
+        HubCommands::Compare { hubs, fid } => {
+            handle_compare(hub_client, hubs, fid).await?;
+        }
+        HubCommands::SyncStatus {
+            max_block_delay,
+            json,
+        } => {
+            handle_sync_status(hub_client, max_block_delay, json).await?;
+        }
+        HubCommands::VerificationsMap {
+            fids,
+            format,
+            reverse,
+            output,
+            concurrency,
+            refresh,
+        } => {
+            handle_verifications_map(
+                hub_client,
+                fids,
+                format,
+                reverse,
+                output,
+                concurrency,
+                refresh,
+            )
+            .await?;
         }
     }
     Ok(())
@@ -117,10 +369,20 @@ async fn handle_submit_proof(
     proof_file: String,
     fid: u64,
     wallet_name: Option<String>,
+    auth_wallet: Option<String>,
 ) -> Result<()> {
     println!("📤 Submitting username proof from file: {proof_file} for FID: {fid}");
 
-    let proof_content = std::fs::read_to_string(&proof_file)?;
+    // Accept the id of a stored proof as well as a path
+    let store = crate::ens_proof::ProofStore::open_default()?;
+    let proof_content = if std::path::Path::new(&proof_file).exists() {
+        std::fs::read_to_string(&proof_file)?
+    } else {
+        store
+            .get(&proof_file)
+            .map(|(_, proof)| proof)
+            .with_context(|| format!("{proof_file} is neither a file nor a stored proof id"))?
+    };
     let proof_data: serde_json::Value = serde_json::from_str(&proof_content)?;
 
     // Create UserNameProof from JSON
@@ -137,6 +399,37 @@ async fn handle_submit_proof(
     proof.set_signature(hex::decode(proof_data["signature"].as_str().unwrap_or(""))?);
     proof.set_fid(proof_data["fid"].as_u64().unwrap_or(0));
 
+    if let Some(auth_wallet) = auth_wallet {
+        let mut encrypted_manager =
+            crate::encrypted_key_manager::EncryptedKeyManager::default_config();
+        let password = crate::encrypted_key_manager::prompt_password(&format!(
+            "Enter password for wallet '{auth_wallet}': "
+        ))?;
+        encrypted_manager
+            .load_and_decrypt(&password, &auth_wallet)
+            .await?;
+        let wallet = encrypted_manager
+            .key_manager()
+            .ok_or_else(|| {
+                anyhow::anyhow!("Failed to load key manager for wallet: {}", auth_wallet)
+            })?
+            .wallet()
+            .clone();
+
+        println!(
+            "🪪 Signing with auth address {}",
+            ethers::utils::to_checksum(&ethers::signers::Signer::address(&wallet), None)
+        );
+        let client = crate::core::client::hub_client::FarcasterClient::new(
+            hub_client.hub_url().to_string(),
+            None,
+        );
+        let result = client
+            .submit_username_proof_with_auth_address(&proof, &wallet)
+            .await;
+        return report_proof_submission(&store, hub_client.hub_url(), &proof, result);
+    }
+
     // Create a new FarcasterClient with the specified wallet if provided
     let client = if let Some(wallet_name) = wallet_name {
         // Load encrypted key manager and decrypt the key
@@ -166,23 +459,86 @@ async fn handle_submit_proof(
             Some(key_manager),
         )
     } else {
+        // The Ed25519 path signs with the FID's stored signer and needs no wallet
         crate::core::client::hub_client::FarcasterClient::new(
             hub_client.hub_url().to_string(),
-            hub_client.key_manager().cloned(),
+            None,
         )
     };
 
     // Submit using Ed25519 key for the specified FID
     let result = client.submit_username_proof_with_ed25519(&proof, fid).await;
+    report_proof_submission(&store, hub_client.hub_url(), &proof, result)
+}
 
-    match result {
+/// Print the outcome of a proof submission and record it against the stored proof
+fn report_proof_submission(
+    store: &crate::ens_proof::ProofStore,
+    hub_url: &str,
+    proof: &crate::core::protocol::username_proof::UserNameProof,
+    result: Result<crate::core::client::hub_client::HubResponse>,
+) -> Result<()> {
+    let (success, response) = match &result {
         Ok(response) => {
             println!("✅ Username proof submitted successfully!");
             println!("📋 Response: {response:?}");
+            (response.success, serde_json::to_value(response)?)
         }
-        Err(e) => println!("❌ Failed to submit username proof: {e}"),
+        Err(e) => {
+            println!("❌ Failed to submit username proof: {e}");
+            (false, serde_json::json!({ "error": e.to_string() }))
+        }
+    };
+
+    let submission = crate::ens_proof::store::ProofSubmission {
+        submitted_at: chrono::Utc::now(),
+        hub_url: hub_url.to_string(),
+        success,
+        response,
+        tx_hash: None,
+    };
+    match store.record_submission(&hex::encode(proof.get_signature()), submission) {
+        Ok(Some(id)) => println!("🗄️  Recorded in stored proof {id}"),
+        Ok(None) => {}
+        Err(e) => println!("⚠️  Could not record submission: {e}"),
     }
+    Ok(())
+}
+
+async fn handle_link(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    target: &str,
+    author_fid: Option<u64>,
+    qr: bool,
+) -> Result<()> {
+    use crate::core::utils::warpcast;
+
+    // Usernames make nicer links but are optional; fall back to FID-based URLs
+    let username_of = |fid: u64| async move {
+        hub_client
+            .get_user(fid)
+            .await
+            .ok()
+            .and_then(|data| warpcast::username_from_user_data(&data))
+    };
 
+    let url = if let Ok(fid) = target.parse::<u64>() {
+        let username = username_of(fid).await;
+        warpcast::profile_url(fid, username.as_deref())
+    } else if target.starts_with("0x") && hex::decode(&target[2..]).is_ok() {
+        let username = match author_fid {
+            Some(fid) => username_of(fid).await,
+            None => None,
+        };
+        warpcast::cast_url(target, username.as_deref())
+    } else {
+        anyhow::bail!("'{target}' is neither a FID nor a 0x-prefixed cast hash");
+    };
+
+    println!("🔗 {url}");
+    if qr {
+        println!("{}", warpcast::render_qr(&url)?);
+    }
     Ok(())
 }
 
@@ -195,7 +551,7 @@ async fn handle_hub_info(
     match hub_client.get_hub_info().await {
         Ok(hub_info) => {
             println!("✅ Hub information retrieved:");
-            println!("{}", serde_json::to_string_pretty(&hub_info)?);
+            println!("{}", output::json(&hub_info)?);
         }
         Err(e) => {
             println!("❌ Failed to get Hub information: {e}");
@@ -210,30 +566,56 @@ async fn handle_followers(
     hub_client: &crate::core::client::hub_client::FarcasterClient,
     fid: u64,
     limit: u32,
+    window: TimeWindow,
+) -> Result<()> {
+    handle_links(hub_client, LinkDirection::Followers, fid, limit, window).await
+}
+
+/// Print the followers or following of a FID with the time of each follow
+async fn handle_links(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    direction: LinkDirection,
+    fid: u64,
+    limit: u32,
+    window: TimeWindow,
 ) -> Result<()> {
     let limit_text = if limit == 0 {
         "all".to_string()
     } else {
         limit.to_string()
     };
-    println!("👥 Getting followers for FID: {fid} (limit: {limit_text})");
+    let icon = match direction {
+        LinkDirection::Followers => "👥",
+        LinkDirection::Following => "👤",
+    };
+    let what = direction.name();
+    println!(
+        "{icon} Getting {what} for FID: {fid} (limit: {limit_text}){}",
+        window_suffix(&window)
+    );
 
-    match hub_client.get_followers(fid, limit).await {
-        Ok(followers) => {
-            if followers.is_empty() {
-                println!("❌ No followers found for FID: {fid}");
+    let graph = GraphService::new(hub_client);
+    let links = if window.is_unbounded() {
+        graph.links(direction, fid, limit).await
+    } else {
+        graph.links_in_window(direction, fid, window, limit).await
+    };
+    match links {
+        Ok(links) => {
+            if links.is_empty() {
+                println!("❌ No {what} found for FID: {fid}");
             } else {
-                println!("✅ Found {} follower(s):", followers.len());
-                for (i, follower) in followers.iter().enumerate() {
-                    // Extract FID from the link message
-                    let follower_fid = follower
-                        .get("data")
-                        .and_then(|d| d.get("fid"))
-                        .and_then(|f| f.as_u64())
-                        .unwrap_or(0);
+                match direction {
+                    LinkDirection::Followers => {
+                        println!("✅ Found {} follower(s):", links.len())
+                    }
+                    LinkDirection::Following => println!("✅ Found {} following:", links.len()),
+                }
+                for (i, link) in links.iter().enumerate() {
+                    let linked_fid = direction.linked_fid(link).unwrap_or(0);
 
                     // Extract timestamp for when they followed
-                    let timestamp = follower
+                    let timestamp = link
                         .get("data")
                         .and_then(|d| d.get("timestamp"))
                         .and_then(|t| t.as_u64())
@@ -242,64 +624,456 @@ async fn handle_followers(
                     println!(
                         "   {}. FID: {} (followed at timestamp: {})",
                         i + 1,
-                        follower_fid,
+                        linked_fid,
                         timestamp
                     );
                 }
             }
         }
-        Err(e) => println!("❌ Failed to get followers: {e}"),
+        Err(e) => println!("❌ Failed to get {what}: {e}"),
     }
 
     Ok(())
 }
 
+async fn handle_export(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    kind: ExportKind,
+    fid: u64,
+    window: TimeWindow,
+    output: String,
+    resume: bool,
+    fresh: bool,
+) -> Result<()> {
+    let mode = match (resume, fresh) {
+        (true, _) => ResumeMode::Resume,
+        (_, true) => ResumeMode::Fresh,
+        _ => ResumeMode::Auto,
+    };
+    println!(
+        "📦 Exporting {} of FID {fid}{} to {output}",
+        kind.name(),
+        window_suffix(&window)
+    );
+
+    let mut first = true;
+    let summary = hub_client
+        .export_list(kind, fid, window, Path::new(&output), mode, |checkpoint| {
+            if std::mem::take(&mut first) {
+                if checkpoint.pages > 0 {
+                    println!(
+                        "↩️  Resuming after page {} ({} message(s) already exported)",
+                        checkpoint.pages, checkpoint.messages
+                    );
+                }
+                return;
+            }
+            println!(
+                "📄 Page {}: {} message(s) exported",
+                checkpoint.pages, checkpoint.messages
+            );
+        })
+        .await;
+    let summary = match summary {
+        Ok(summary) => summary,
+        // Once the output is open, a checkpoint is there to resume from
+        Err(e) if !first => {
+            return Err(e.context(
+                "Export interrupted; re-run the command to resume from the last checkpoint",
+            ))
+        }
+        Err(e) => return Err(e),
+    };
+
+    println!(
+        "✅ Exported {} message(s) in {} page(s) to {output}",
+        summary.messages, summary.pages
+    );
+    Ok(())
+}
+
 async fn handle_following(
     hub_client: &crate::core::client::hub_client::FarcasterClient,
     fid: u64,
     limit: u32,
+    window: TimeWindow,
 ) -> Result<()> {
-    let limit_text = if limit == 0 {
-        "all".to_string()
+    handle_links(hub_client, LinkDirection::Following, fid, limit, window).await
+}
+
+/// " (since ... until ...)" for a bounded window, empty otherwise
+fn window_suffix(window: &TimeWindow) -> String {
+    if window.is_unbounded() {
+        String::new()
     } else {
-        limit.to_string()
+        format!(" ({})", window.describe())
+    }
+}
+
+async fn handle_set_profile(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fid: u64,
+    requested: &[(&str, Option<String>)],
+    dry_run: bool,
+) -> Result<()> {
+    use crate::core::client::profile;
+
+    let updates: Vec<_> = requested
+        .iter()
+        .filter_map(|(name, value)| Some((profile::profile_field(name)?, value.clone()?)))
+        .collect();
+    if updates.is_empty() {
+        println!("❌ Nothing to update");
+        println!("💡 Pass at least one field, e.g. --bio \"...\" or --display \"...\"");
+        return Ok(());
+    }
+
+    println!("✏️  Comparing requested profile fields for FID: {fid}");
+    let messages = hub_client.get_user_profile(fid).await?;
+    let current = profile::current_profile(&messages);
+    let changes = profile::diff_profile(&current, &updates);
+
+    for (field, value) in &updates {
+        match changes
+            .iter()
+            .find(|change| change.field.name == field.name)
+        {
+            Some(change) => println!(
+                "   ~ {:<9} {:?} → {:?}",
+                field.name,
+                change.current.as_deref().unwrap_or(""),
+                change.new
+            ),
+            None => println!("   = {:<9} {:?} (unchanged)", field.name, value),
+        }
+    }
+    if changes.is_empty() {
+        println!("✅ Profile is already up to date, nothing to submit");
+        return Ok(());
+    }
+    println!(
+        "📝 {} field(s) to update, {} unchanged",
+        changes.len(),
+        updates.len() - changes.len()
+    );
+    if dry_run {
+        println!("🔍 Dry run: no messages submitted");
+        return Ok(());
+    }
+
+    let Some(signing_key) = load_fid_signing_key(fid)? else {
+        return Ok(());
     };
-    println!("👤 Getting following for FID: {fid} (limit: {limit_text})");
 
-    match hub_client.get_following(fid, limit).await {
-        Ok(following) => {
-            if following.is_empty() {
-                println!("❌ No following found for FID: {fid}");
-            } else {
-                println!("✅ Found {} following:", following.len());
-                for (i, user) in following.iter().enumerate() {
-                    // Extract target FID from the link message
-                    let target_fid = user
-                        .get("data")
-                        .and_then(|d| d.get("linkBody"))
-                        .and_then(|lb| lb.get("targetFid"))
-                        .and_then(|f| f.as_u64())
-                        .unwrap_or(0);
+    let client = crate::core::client::hub_client::FarcasterClient::new(
+        hub_client.hub_url().to_string(),
+        None,
+    );
+    let mut failed = 0;
+    for change in &changes {
+        match client
+            .submit_user_data(fid, change.field.data_type, &change.new, &signing_key)
+            .await
+        {
+            Ok(_) => println!("✅ Updated {}", change.field.name),
+            Err(e) => {
+                println!("❌ Failed to update {}: {e}", change.field.name);
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!(
+            "{failed} of {} profile field(s) failed to update",
+            changes.len()
+        );
+    }
+    Ok(())
+}
 
-                    // Extract timestamp for when they followed
-                    let timestamp = user
-                        .get("data")
-                        .and_then(|d| d.get("timestamp"))
-                        .and_then(|t| t.as_u64())
-                        .unwrap_or(0);
+#[allow(clippy::too_many_arguments)]
+async fn handle_cast(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fid: u64,
+    mut draft: crate::core::client::cast::CastDraft,
+    reply_to: Option<String>,
+    mut thread: bool,
+    long: bool,
+    resolve_mentions: bool,
+    dry_run: bool,
+) -> Result<()> {
+    use crate::core::client::cast::supported_cast_type;
+    use crate::core::client::name_resolver::NameResolver;
+    use crate::core::protocol::builder::max_cast_text_bytes;
+    use crate::core::protocol::builder::parse_cast_ref;
+    use crate::core::protocol::builder::MAX_EMBEDS;
+    use crate::core::protocol::message::CastType;
+
+    if let Some(reply_to) = reply_to {
+        draft.parent = Some(parse_cast_ref(&reply_to).context("Invalid --reply-to")?);
+    }
+    if draft.embeds.len() > MAX_EMBEDS {
+        println!(
+            "❌ Invalid cast: {} embeds given, the limit is {MAX_EMBEDS}",
+            draft.embeds.len()
+        );
+        return Ok(());
+    }
 
-                    println!(
-                        "   {}. FID: {} (followed at timestamp: {})",
-                        i + 1,
-                        target_fid,
-                        timestamp
-                    );
+    if resolve_mentions {
+        let mut resolver = NameResolver::with_cache_file(
+            hub_client.hub_url().to_string(),
+            crate::consts::get_config().eth_rpc_url().to_string(),
+            NameResolver::default_cache_path(),
+        );
+        let (fids, unresolved) = resolver.resolve_mentions(&draft.text).await;
+        if let Err(e) = resolver.save() {
+            tracing::debug!("Failed to save name cache: {e}");
+        }
+        let mut resolved: Vec<_> = fids.iter().collect();
+        resolved.sort();
+        for (name, fid) in resolved {
+            println!("👤 @{name} → FID {fid}");
+        }
+        for (name, reason) in &unresolved {
+            println!("⚠️  @{name} stays plain text: {reason}");
+        }
+        draft.mentions = fids;
+    }
+
+    let needed = draft.single_cast_type();
+    if long && needed != CastType::CAST {
+        let supported = match hub_client.get_hub_info().await {
+            Ok(info) => supported_cast_type(&info),
+            Err(e) => {
+                println!("⚠️  Could not check the hub for long cast support: {e}");
+                CastType::CAST
+            }
+        };
+        if needed as i32 <= supported as i32 {
+            println!("📜 Publishing as a {needed:?} (hub supports up to {supported:?})");
+            draft.cast_type = needed;
+            thread = false;
+        } else if thread {
+            println!("⚠️  The hub doesn't accept a {needed:?}; splitting into a thread instead");
+        } else {
+            println!("❌ The hub doesn't accept a {needed:?} (it supports up to {supported:?})");
+            println!("💡 Add --thread to fall back to a numbered thread");
+            return Ok(());
+        }
+    }
+
+    let parts = match draft.validate(fid, thread) {
+        Ok(parts) => parts,
+        Err(e) => {
+            println!("❌ Invalid cast: {e:#}");
+            if !thread && draft.text.trim().len() > max_cast_text_bytes(draft.cast_type) {
+                println!("💡 Add --thread to publish it as a numbered thread");
+                if !long {
+                    println!("💡 Or add --long to publish it as one long cast");
                 }
             }
+            return Ok(());
         }
-        Err(e) => println!("❌ Failed to get following: {e}"),
+    };
+    if parts.len() > 1 {
+        println!("🧵 Text split into a thread of {} casts:", parts.len());
+    } else {
+        println!("📝 Cast from FID {fid}:");
+    }
+    for text in &parts {
+        println!("{}", "─".repeat(50));
+        println!("{text}");
+        println!(
+            "   ({}/{} bytes)",
+            text.len(),
+            max_cast_text_bytes(draft.cast_type)
+        );
+    }
+    println!("{}", "─".repeat(50));
+    for embed in &draft.embeds {
+        println!("📎 Embeds {embed}");
+    }
+    if dry_run {
+        println!("🔍 Dry run: no messages submitted");
+        return Ok(());
     }
 
+    let Some(signing_key) = load_fid_signing_key(fid)? else {
+        return Ok(());
+    };
+    let client = crate::core::client::hub_client::FarcasterClient::new(
+        hub_client.hub_url().to_string(),
+        None,
+    );
+    let published = client
+        .submit_cast_draft(fid, &draft, thread, &signing_key)
+        .await?;
+    for (message, _) in &published {
+        println!("✅ Published 0x{}", hex::encode(message.get_hash()));
+    }
+    if let Some((first, _)) = published.first() {
+        println!(
+            "💡 View it with: castorix hub thread {fid} 0x{}",
+            hex::encode(first.get_hash())
+        );
+    }
+    Ok(())
+}
+
+/// Load the Ed25519 signer of a FID, prompting for its password
+pub(crate) fn load_fid_signing_key(fid: u64) -> Result<Option<ed25519_dalek::SigningKey>> {
+    let keys_file =
+        crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager::default_keys_file()?;
+    let ed25519_manager =
+        crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager::load_from_file(
+            &keys_file,
+        )?;
+    if !ed25519_manager.has_key(fid) {
+        println!("❌ No Ed25519 key found for FID: {fid}");
+        println!("💡 Generate or import one first: castorix hub key generate {fid}");
+        return Ok(None);
+    }
+    let password = crate::core::crypto::encrypted_storage::prompt_password(&format!(
+        "Enter password for FID {fid}: "
+    ))?;
+    Ok(Some(ed25519_manager.get_signing_key(fid, &password)?))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_unfollow_inactive(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fid: u64,
+    inactive_days: u64,
+    keep_spam: bool,
+    per_minute: u32,
+    concurrency: usize,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    use futures::stream::StreamExt;
+    use futures::stream::{
+        self,
+    };
+
+    use crate::core::client::links;
+    use crate::core::client::links::UnfollowReason;
+
+    println!("🧹 Checking accounts followed by FID: {fid}");
+    let following = links::followed_fids(&hub_client.get_following(fid, 0).await?);
+    if following.is_empty() {
+        println!("❌ FID {fid} doesn't follow anyone");
+        return Ok(());
+    }
+    println!("   Following {} account(s)", following.len());
+
+    let spam_checker = if keep_spam {
+        None
+    } else {
+        match SpamService::load() {
+            Ok(spam) => Some(spam),
+            Err(e) => {
+                println!("⚠️  Spam labels unavailable, checking activity only: {e}");
+                None
+            }
+        }
+    };
+
+    let latest: Vec<(u64, Result<Option<u64>>)> = stream::iter(following.iter().copied())
+        .map(|target| async move { (target, hub_client.get_latest_cast_timestamp(target).await) })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let now = crate::core::client::hub_client::farcaster_now() as u64;
+    let mut candidates = Vec::new();
+    let mut unchecked = 0;
+    for (target, last_cast) in latest {
+        let last_cast = match last_cast {
+            Ok(last_cast) => last_cast,
+            Err(e) => {
+                tracing::debug!("Failed to get casts for FID {target}: {e}");
+                unchecked += 1;
+                continue;
+            }
+        };
+        let is_spam = spam_checker
+            .as_ref()
+            .is_some_and(|spam| spam.is_spam(target));
+        if let Some(reason) = links::unfollow_reason(last_cast, is_spam, now, inactive_days) {
+            candidates.push((target, reason));
+        }
+    }
+
+    if unchecked > 0 {
+        println!("⚠️  {unchecked} account(s) could not be checked and are kept");
+    }
+    if candidates.is_empty() {
+        println!("✅ Every followed account cast within the last {inactive_days} day(s)");
+        return Ok(());
+    }
+    println!("📋 {} account(s) to unfollow:", candidates.len());
+    for (target, reason) in &candidates {
+        let reason = match reason {
+            UnfollowReason::NeverCast => "never cast".to_string(),
+            UnfollowReason::Inactive { last_cast } => {
+                format!(
+                    "last cast {} day(s) ago",
+                    (now - last_cast) / (24 * 60 * 60)
+                )
+            }
+            UnfollowReason::Spam => "labelled spam".to_string(),
+        };
+        println!("   FID {target}: {reason}");
+    }
+    if dry_run {
+        println!("🔍 Dry run: no messages submitted");
+        return Ok(());
+    }
+
+    policy::current().check_confirmation(yes)?;
+    if !yes {
+        prompt::ask(
+            InputKind::Confirmation,
+            &format!(
+                "\n❓ Unfollow {} account(s) from FID {fid}? (yes/no): ",
+                candidates.len()
+            ),
+        )?;
+        let mut confirmation = String::new();
+        std::io::stdin().read_line(&mut confirmation)?;
+        if !matches!(confirmation.trim().to_lowercase().as_str(), "yes" | "y") {
+            println!("❌ Cancelled");
+            return Ok(());
+        }
+    }
+    let Some(signing_key) = load_fid_signing_key(fid)? else {
+        return Ok(());
+    };
+
+    let client = crate::core::client::hub_client::FarcasterClient::new(
+        hub_client.hub_url().to_string(),
+        None,
+    );
+    let interval = std::time::Duration::from_secs_f64(60.0 / per_minute.max(1) as f64);
+    let mut removed = 0;
+    for (i, (target, _)) in candidates.iter().enumerate() {
+        if i > 0 {
+            tokio::time::sleep(interval).await;
+        }
+        match client.unfollow(fid, *target, &signing_key).await {
+            Ok(_) => {
+                removed += 1;
+                println!(
+                    "   ✅ Unfollowed FID {target} ({}/{})",
+                    i + 1,
+                    candidates.len()
+                );
+            }
+            Err(e) => println!("   ❌ Failed to unfollow FID {target}: {e}"),
+        }
+    }
+    println!("✅ Unfollowed {removed} of {} account(s)", candidates.len());
     Ok(())
 }
 
@@ -310,96 +1084,67 @@ async fn handle_profile(
 ) -> Result<()> {
     println!("👤 Getting profile for FID: {fid}");
 
-    match hub_client.get_user_profile(fid).await {
-        Ok(profile_data) => {
-            if profile_data.is_empty() {
+    match ProfileService::new(hub_client).profile(fid).await {
+        Ok(profile) => {
+            if profile.is_empty() {
                 println!("❌ No profile data found for FID: {fid}");
             } else {
                 println!("✅ Profile for FID: {fid}");
                 println!("{}", "─".repeat(50));
 
-                // Parse and display profile information
-                let mut username = "Unknown".to_string();
-                let mut display_name = "Unknown".to_string();
-                let mut bio = "No bio".to_string();
-                let mut pfp_url = "No profile picture".to_string();
-                let mut location = "No location".to_string();
-                let mut twitter = "No Twitter".to_string();
-                let mut github = "No GitHub".to_string();
-                let mut url = "No website".to_string();
-                let mut eth_address = "No Ethereum address".to_string();
-                let mut sol_address = "No Solana address".to_string();
-
-                for data in &profile_data {
-                    if let Some(user_data_body) =
-                        data.get("data").and_then(|d| d.get("userDataBody"))
-                    {
-                        if let Some(data_type) = user_data_body.get("type").and_then(|t| t.as_str())
-                        {
-                            if let Some(value) =
-                                user_data_body.get("value").and_then(|v| v.as_str())
-                            {
-                                match data_type {
-                                    "USER_DATA_TYPE_USERNAME" => username = value.to_string(),
-                                    "USER_DATA_TYPE_DISPLAY" => display_name = value.to_string(),
-                                    "USER_DATA_TYPE_BIO" => bio = value.to_string(),
-                                    "USER_DATA_TYPE_PFP" => pfp_url = value.to_string(),
-                                    "USER_DATA_TYPE_LOCATION" => location = value.to_string(),
-                                    "USER_DATA_TYPE_TWITTER" => twitter = format!("@{}", value),
-                                    "USER_DATA_TYPE_GITHUB" => github = format!("@{}", value),
-                                    "USER_DATA_TYPE_URL" => url = value.to_string(),
-                                    "USER_DATA_PRIMARY_ADDRESS_ETHEREUM" => {
-                                        eth_address = value.to_string()
-                                    }
-                                    "USER_DATA_PRIMARY_ADDRESS_SOLANA" => {
-                                        sol_address = value.to_string()
-                                    }
-                                    _ => {} // Ignore other types
-                                }
-                            }
-                        }
-                    }
-                }
+                let or = |value: &Option<String>, default: &str| {
+                    value.clone().unwrap_or_else(|| default.to_string())
+                };
+                let handle = |value: &Option<String>, default: &str| {
+                    value
+                        .as_ref()
+                        .map(|v| format!("@{v}"))
+                        .unwrap_or_else(|| default.to_string())
+                };
+                let username = or(&profile.username, "Unknown");
+                let display_name = or(&profile.display_name, "Unknown");
+                let bio = or(&profile.bio, "No bio");
 
                 if show_all {
                     // Display all profile information
                     println!("📝 Display Name: {}", display_name);
                     println!("👤 Username: @{}", username);
                     println!("📄 Bio: {}", bio);
-                    println!("📍 Location: {}", location);
-                    println!("🐦 Twitter: {}", twitter);
-                    println!("💻 GitHub: {}", github);
-                    println!("🌐 Website: {}", url);
-                    println!("🔗 Ethereum: {}", eth_address);
-                    println!("🔗 Solana: {}", sol_address);
-                    println!("🖼️  Profile Picture: {}", pfp_url);
-
-                    // Display profile picture if available
-                    if pfp_url != "No profile picture" && !pfp_url.is_empty() {
-                        if let Err(e) =
-                            crate::image_display::ImageDisplay::smart_display(&pfp_url).await
-                        {
-                            println!("❌ Failed to display profile picture: {}", e);
-                        }
-                    }
-
-                    println!("{}", "─".repeat(50));
-                    println!("📊 Total profile fields: {}", profile_data.len());
+                    println!("📍 Location: {}", or(&profile.location, "No location"));
+                    println!("🐦 Twitter: {}", handle(&profile.twitter, "No Twitter"));
+                    println!("💻 GitHub: {}", handle(&profile.github, "No GitHub"));
+                    println!("🌐 Website: {}", or(&profile.url, "No website"));
+                    println!(
+                        "🔗 Ethereum: {}",
+                        or(&profile.primary_eth_address, "No Ethereum address")
+                    );
+                    println!(
+                        "🔗 Solana: {}",
+                        or(&profile.primary_sol_address, "No Solana address")
+                    );
+                    println!(
+                        "🖼️  Profile Picture: {}",
+                        or(&profile.pfp_url, "No profile picture")
+                    );
                 } else {
                     // Display only basic information
                     println!("👤 @{}", username);
                     println!("📝 {}", display_name);
                     println!("📄 {}", bio);
+                }
 
-                    // Display profile picture if available
-                    if pfp_url != "No profile picture" && !pfp_url.is_empty() {
-                        if let Err(e) =
-                            crate::image_display::ImageDisplay::smart_display(&pfp_url).await
-                        {
-                            println!("❌ Failed to display profile picture: {}", e);
-                        }
+                // Display profile picture if available
+                if let Some(pfp_url) = &profile.pfp_url {
+                    if let Err(e) = crate::image_display::ImageDisplay::smart_display(pfp_url).await
+                    {
+                        println!("❌ Failed to display profile picture: {}", e);
                     }
                 }
+
+                if show_all {
+                    println!("{}", "─".repeat(50));
+                    println!("📊 Total profile fields: {}", profile.fields);
+                }
             }
         }
         Err(e) => println!("❌ Failed to get profile: {e}"),
@@ -411,138 +1156,68 @@ async fn handle_profile(
 async fn handle_stats(
     hub_client: &crate::core::client::hub_client::FarcasterClient,
     fid: u64,
+    sample_pages: u32,
 ) -> Result<()> {
     println!("📊 Getting statistics for FID: {fid}");
 
+    match hub_client.get_follower_count(fid, sample_pages).await {
+        Ok(followers) => match followers.source {
+            CountSource::HubTotal | CountSource::Exact => {
+                println!("👥 Followers: {}", followers.count)
+            }
+            CountSource::Estimate => println!(
+                "👥 Followers: ~{} (estimated from {} sampled links)",
+                followers.count, followers.sampled
+            ),
+            CountSource::AtLeast => println!(
+                "👥 Followers: at least {} (increase --sample-pages for more)",
+                followers.count
+            ),
+        },
+        Err(e) => println!("❌ Failed to count followers: {e}"),
+    }
+
     // Get storage limits which includes following count
     match hub_client.get_storage_limits(fid).await {
         Ok(storage_data) => {
             println!("✅ Storage limits retrieved:");
 
-            if let Some(limits) = storage_data.get("limits").and_then(|l| l.as_array()) {
-                for limit in limits {
-                    if let (Some(store_type), Some(name), Some(limit_val), Some(used)) = (
-                        limit.get("storeType").and_then(|s| s.as_str()),
-                        limit.get("name").and_then(|n| n.as_str()),
-                        limit.get("limit").and_then(|l| l.as_u64()),
-                        limit.get("used").and_then(|u| u.as_u64()),
-                    ) {
-                        match name {
-                            "LINKS" => {
-                                println!(
-                                    "   👥 Following: {}/{} ({}%)",
-                                    used,
-                                    limit_val,
-                                    if limit_val > 0 {
-                                        (used * 100) / limit_val
-                                    } else {
-                                        0
-                                    }
-                                );
-                            }
-                            "CASTS" => {
-                                println!(
-                                    "   📝 Casts: {}/{} ({}%)",
-                                    used,
-                                    limit_val,
-                                    if limit_val > 0 {
-                                        (used * 100) / limit_val
-                                    } else {
-                                        0
-                                    }
-                                );
-                            }
-                            "REACTIONS" => {
-                                println!(
-                                    "   ❤️  Reactions: {}/{} ({}%)",
-                                    used,
-                                    limit_val,
-                                    if limit_val > 0 {
-                                        (used * 100) / limit_val
-                                    } else {
-                                        0
-                                    }
-                                );
-                            }
-                            "USER_DATA" => {
-                                println!(
-                                    "   👤 Profile Data: {}/{} ({}%)",
-                                    used,
-                                    limit_val,
-                                    if limit_val > 0 {
-                                        (used * 100) / limit_val
-                                    } else {
-                                        0
-                                    }
-                                );
-                            }
-                            "VERIFICATIONS" => {
-                                println!(
-                                    "   ✅ Verifications: {}/{} ({}%)",
-                                    used,
-                                    limit_val,
-                                    if limit_val > 0 {
-                                        (used * 100) / limit_val
-                                    } else {
-                                        0
-                                    }
-                                );
-                            }
-                            "USERNAME_PROOFS" => {
-                                println!(
-                                    "   🏷️  Username Proofs: {}/{} ({}%)",
-                                    used,
-                                    limit_val,
-                                    if limit_val > 0 {
-                                        (used * 100) / limit_val
-                                    } else {
-                                        0
-                                    }
-                                );
-                            }
-                            _ => {
-                                println!(
-                                    "   {} {}: {}/{} ({}%)",
-                                    store_type,
-                                    name,
-                                    used,
-                                    limit_val,
-                                    if limit_val > 0 {
-                                        (used * 100) / limit_val
-                                    } else {
-                                        0
-                                    }
-                                );
-                            }
-                        }
-                    }
-                }
+            for usage in StorageUsage::from_limits(&storage_data) {
+                let label = match usage.name.as_str() {
+                    "LINKS" => "👥 Following".to_string(),
+                    "CASTS" => "📝 Casts".to_string(),
+                    "REACTIONS" => "❤️  Reactions".to_string(),
+                    "USER_DATA" => "👤 Profile Data".to_string(),
+                    "VERIFICATIONS" => "✅ Verifications".to_string(),
+                    "USERNAME_PROOFS" => "🏷️  Username Proofs".to_string(),
+                    _ => format!("{} {}", usage.store_type, usage.name),
+                };
+                println!(
+                    "   {}: {}/{} ({}%)",
+                    label,
+                    usage.used,
+                    usage.limit,
+                    usage.percent()
+                );
             }
 
             // Show tier information if available
-            if let Some(tier_subscriptions) = storage_data
-                .get("tier_subscriptions")
-                .and_then(|t| t.as_array())
-            {
-                if !tier_subscriptions.is_empty() {
-                    println!("\n💎 Tier Information:");
-                    for tier in tier_subscriptions {
-                        if let (Some(tier_type), Some(expires_at)) = (
-                            tier.get("tier_type").and_then(|t| t.as_str()),
-                            tier.get("expires_at").and_then(|e| e.as_u64()),
-                        ) {
-                            if expires_at > 0 {
-                                let expire_date =
-                                    chrono::DateTime::from_timestamp(expires_at as i64, 0)
-                                        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
-                                        .unwrap_or_else(|| "Unknown".to_string());
-                                println!("   {} (expires: {})", tier_type, expire_date);
-                            } else {
-                                println!("   {} (permanent)", tier_type);
-                            }
+            let tier_subscriptions = crate::core::client::tiers::tier_subscriptions(&storage_data);
+            if !tier_subscriptions.is_empty() {
+                println!("\n💎 Tier Information:");
+                for tier in &tier_subscriptions {
+                    match tier.expires_at {
+                        Some(expires_at) => {
+                            let expire_date =
+                                chrono::DateTime::from_timestamp(expires_at as i64, 0)
+                                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+                                    .unwrap_or_else(|| "Unknown".to_string());
+                            println!("   {} (expires: {})", tier.tier_type, expire_date);
                         }
+                        None => println!("   {} (permanent)", tier.tier_type),
                     }
                 }
+                println!("💡 Details: castorix tier status {fid}");
             }
         }
         Err(e) => println!("❌ Failed to get storage limits: {e}"),
@@ -551,14 +1226,84 @@ async fn handle_stats(
     Ok(())
 }
 
-async fn handle_spam_check(fids: Vec<u64>) -> Result<()> {
-    println!("🚫 Checking spam status for FIDs: {:?}", fids);
+async fn handle_message_counts(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fid: u64,
+    max: Option<u64>,
+    json: bool,
+) -> Result<()> {
+    if !json {
+        println!("🧮 Counting messages for FID: {fid}");
+        println!("⏳ Walking hub stores...");
+    }
+    let counts = MessageService::new(hub_client).counts(fid, max).await?;
+    if json {
+        println!("{}", output::json(&counts)?);
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "   {:<14} {:>10} {:>14}",
+        "STORE", "MESSAGES", "STORAGE USED"
+    );
+    for store in &counts.stores {
+        let count = if store.capped {
+            format!("{}+", store.count)
+        } else {
+            store.count.to_string()
+        };
+        let used = store
+            .storage_used
+            .map_or_else(|| "-".to_string(), |used| used.to_string());
+        println!("   {:<14} {:>10} {:>14}", store.store, count, used);
+    }
+    let total = if counts.capped() {
+        format!("{}+", counts.total())
+    } else {
+        counts.total().to_string()
+    };
+    println!("   {:<14} {:>10}", "total", total);
+    if counts.capped() {
+        println!("\n💡 Counts ending in + stopped at --max; drop it for exact totals");
+    }
+    Ok(())
+}
+
+/// Read FIDs from a file, whitespace or comma separated ("-" for stdin)
+pub(crate) fn read_fids_file(file: &str) -> Result<Vec<u64>> {
+    let content = if file == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(file)?
+    };
+    content
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid FID '{token}' in {file}"))
+        })
+        .collect()
+}
+
+async fn handle_spam_check(mut fids: Vec<u64>, file: Option<String>, json: bool) -> Result<()> {
+    if let Some(file) = file {
+        fids.extend(read_fids_file(&file)?);
+    }
+
+    if !json {
+        if fids.len() > 20 {
+            println!("🚫 Checking spam status for {} FIDs", fids.len());
+        } else {
+            println!("🚫 Checking spam status for FIDs: {:?}", fids);
+        }
+    }
 
-    // Load spam checker
-    let spam_checker = match crate::core::protocol::spam_checker::SpamChecker::load_from_file(
-        "labels/labels/spam.jsonl",
-    ) {
-        Ok(checker) => checker,
+    // Load spam labels
+    let spam = match SpamService::load() {
+        Ok(spam) => spam,
         Err(e) => {
             println!("❌ Failed to load spam labels: {e}");
             println!("💡 Make sure the labels submodule is properly initialized");
@@ -567,29 +1312,39 @@ async fn handle_spam_check(fids: Vec<u64>) -> Result<()> {
     };
 
     // Get statistics
-    let (total, spam_count, non_spam_count) = spam_checker.get_stats();
-    println!(
-        "📊 Spam labels loaded: {} total, {} spam, {} non-spam",
-        total, spam_count, non_spam_count
-    );
+    if !json {
+        let summary = spam.summary();
+        println!(
+            "📊 Spam labels loaded: {} total, {} spam, {} non-spam",
+            summary.total, summary.spam, summary.not_spam
+        );
+    }
 
-    // Check each FID
+    // Check each FID against the sorted index, writing results as they are produced
+    let mut out = std::io::stdout().lock();
     for fid in fids {
-        match spam_checker.get_label(fid) {
-            Some(label) => {
-                let status = match label.label_value {
-                    0 => "🚫 SPAM",
-                    2 => "✅ CLEAN",
+        let result = spam.check(fid);
+        if json {
+            writeln!(out, "{}", output::json_line(&result)?)?;
+            continue;
+        }
+        match (result.label_value, result.labeled_at) {
+            (Some(label_value), Some(labeled_at)) => {
+                let status = match result.status {
+                    "spam" => "🚫 SPAM",
+                    "not_spam" => "✅ CLEAN",
                     _ => "❓ UNKNOWN",
                 };
-                println!(
-                    "   FID {}: {} (label_value: {})",
-                    fid, status, label.label_value
-                );
-            }
-            None => {
-                println!("   FID {}: ❓ NOT FOUND (not in dataset)", fid);
+                let labeled = chrono::DateTime::from_timestamp(labeled_at as i64, 0)
+                    .map(|dt| dt.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| labeled_at.to_string());
+                writeln!(
+                    out,
+                    "   FID {}: {} (label_value: {}, labeled: {})",
+                    fid, status, label_value, labeled
+                )?;
             }
+            _ => writeln!(out, "   FID {}: ❓ NOT FOUND (not in dataset)", fid)?,
         }
     }
 
@@ -601,11 +1356,9 @@ async fn handle_spam_stat(
 ) -> Result<()> {
     println!("📊 Getting comprehensive spam statistics...");
 
-    // Load spam checker
-    let spam_checker = match crate::core::protocol::spam_checker::SpamChecker::load_from_file(
-        "labels/labels/spam.jsonl",
-    ) {
-        Ok(checker) => checker,
+    // Load spam labels
+    let spam = match SpamService::load() {
+        Ok(spam) => spam,
         Err(e) => {
             println!("❌ Failed to load spam labels: {e}");
             println!("💡 Make sure the labels submodule is properly initialized");
@@ -614,25 +1367,20 @@ async fn handle_spam_stat(
     };
 
     // Get spam statistics
-    let (total_labels, spam_count, non_spam_count) = spam_checker.get_stats();
-    let unknown_count = total_labels - spam_count - non_spam_count;
+    let summary = spam.summary();
+    let (total_labels, spam_count, non_spam_count, unknown_count) = (
+        summary.total,
+        summary.spam,
+        summary.not_spam,
+        summary.unknown,
+    );
 
     // Calculate percentages
-    let spam_percentage = if total_labels > 0 {
-        (spam_count * 100) / total_labels
-    } else {
-        0
-    };
-    let non_spam_percentage = if total_labels > 0 {
-        (non_spam_count * 100) / total_labels
-    } else {
-        0
-    };
-    let unknown_percentage = if total_labels > 0 {
-        (unknown_count * 100) / total_labels
-    } else {
-        0
-    };
+    let spam_percentage = (spam_count * 100).checked_div(total_labels).unwrap_or(0);
+    let non_spam_percentage = (non_spam_count * 100)
+        .checked_div(total_labels)
+        .unwrap_or(0);
+    let unknown_percentage = (unknown_count * 100).checked_div(total_labels).unwrap_or(0);
 
     println!("\n🚫 Spam Labels Statistics:");
     println!("{}", "─".repeat(50));
@@ -779,14 +1527,14 @@ async fn handle_spam_stat(
     }
 
     // Show data freshness info if available
-    if let Some(first_timestamp) = spam_checker.get_oldest_timestamp() {
+    if let Some(first_timestamp) = summary.oldest_label {
         let first_date = chrono::DateTime::from_timestamp(first_timestamp as i64, 0)
             .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
             .unwrap_or_else(|| "Unknown".to_string());
         println!("📅 Oldest Label: {}", first_date);
     }
 
-    if let Some(last_timestamp) = spam_checker.get_newest_timestamp() {
+    if let Some(last_timestamp) = summary.newest_label {
         let last_date = chrono::DateTime::from_timestamp(last_timestamp as i64, 0)
             .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
             .unwrap_or_else(|| "Unknown".to_string());
@@ -798,29 +1546,336 @@ async fn handle_spam_stat(
     Ok(())
 }
 
+/// Handle hub thread command
+async fn handle_thread(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fid: u64,
+    hash: &str,
+    limits: crate::core::client::ThreadLimits,
+    json: bool,
+    embeds: bool,
+) -> Result<()> {
+    let mut thread = hub_client
+        .get_thread(fid, hash, limits)
+        .await
+        .with_context(|| format!("Failed to load thread for cast {hash}"))?;
+    if embeds {
+        let previews = fetch_embed_previews(&thread.root.embed_urls()).await;
+        thread.root.attach_previews(&previews);
+    }
+    if json {
+        println!("{}", output::json(&thread)?);
+        return Ok(());
+    }
+
+    println!(
+        "🧵 Thread around cast {} ({} cast(s))",
+        thread.focus_hash, thread.cast_count
+    );
+    if let Some(url) = &thread.parent_url {
+        println!("   Posted under: {url}");
+    }
+    if thread.has_more_parents {
+        println!("   ⬆️  Earlier parents not shown (raise --max-parents)");
+    }
+    println!("{}", "─".repeat(80));
+    let mut lines = Vec::new();
+    render_thread_node(&thread.root, "", true, true, &mut lines);
+    for line in lines {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// Render a thread node and its replies as box-drawing tree lines
+fn render_thread_node(
+    node: &crate::core::client::ThreadNode,
+    prefix: &str,
+    is_last: bool,
+    is_root: bool,
+    lines: &mut Vec<String>,
+) {
+    let (branch, child_prefix) = match (is_root, is_last) {
+        (true, _) => (String::new(), String::new()),
+        (false, true) => (format!("{prefix}└─ "), format!("{prefix}   ")),
+        (false, false) => (format!("{prefix}├─ "), format!("{prefix}│  ")),
+    };
+    let body_prefix = if node.replies.is_empty() {
+        format!("{child_prefix}   ")
+    } else {
+        format!("{child_prefix}│  ")
+    };
+
+    let date_time = chrono::DateTime::from_timestamp((node.timestamp + FARCASTER_EPOCH) as i64, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    let marker = if node.focus { "👉 " } else { "" };
+    lines.push(format!(
+        "{branch}{marker}FID {} · {date_time} · {}",
+        node.fid, node.hash
+    ));
+    for text_line in node.text.lines() {
+        lines.push(format!("{body_prefix}{text_line}"));
+    }
+    for url in &node.embeds {
+        lines.push(format!("{body_prefix}🔗 {url}"));
+        if let Some(preview) = node.previews.iter().find(|p| &p.url == url) {
+            for line in embed_preview_lines(preview) {
+                lines.push(format!("{body_prefix}   {line}"));
+            }
+        }
+    }
+    if node.truncated {
+        lines.push(format!("{body_prefix}… more replies (raise --max-casts)"));
+    }
+
+    for (i, reply) in node.replies.iter().enumerate() {
+        render_thread_node(
+            reply,
+            &child_prefix,
+            i + 1 == node.replies.len(),
+            false,
+            lines,
+        );
+    }
+}
+
+async fn handle_cast_stats(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fid: u64,
+    hash: &str,
+    refresh: bool,
+    json: bool,
+) -> Result<()> {
+    let cache = cast_stats_cache(refresh);
+    let stats = hub_client
+        .get_cast_stats(fid, hash, &cache)
+        .await
+        .with_context(|| format!("Failed to get engagement of cast {hash}"))?;
+    if let Err(e) = cache.save() {
+        tracing::debug!("Failed to save cast stats cache: {e}");
+    }
+    if json {
+        println!("{}", output::json(&stats)?);
+        return Ok(());
+    }
+
+    println!("📈 Engagement of cast {} by FID {}", stats.hash, stats.fid);
+    println!("   {}", cast_preview(&stats.text));
+    println!("   ❤️  Likes:   {}", stats.likes);
+    println!("   🔁 Recasts: {}", stats.recasts);
+    println!("   💬 Replies: {}", stats.replies);
+    println!("   📝 Quotes:  {}", stats.quotes);
+    println!("   📊 Total:   {}", stats.engagement);
+    Ok(())
+}
+
+async fn handle_top_casts(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fid: u64,
+    days: u32,
+    limit: usize,
+    refresh: bool,
+    json: bool,
+) -> Result<()> {
+    if !json {
+        println!("🏆 Ranking casts of FID {fid} from the last {days} day(s)...");
+    }
+    let cache = cast_stats_cache(refresh);
+    let mut ranked = hub_client
+        .get_top_casts(fid, days, &cache)
+        .await
+        .with_context(|| format!("Failed to rank casts of FID {fid}"))?;
+    if let Err(e) = cache.save() {
+        tracing::debug!("Failed to save cast stats cache: {e}");
+    }
+    let total = ranked.len();
+    if limit > 0 {
+        ranked.truncate(limit);
+    }
+    if json {
+        println!("{}", output::json(&ranked)?);
+        return Ok(());
+    }
+    if ranked.is_empty() {
+        println!("❌ No casts by FID {fid} in the last {days} day(s)");
+        return Ok(());
+    }
+
+    println!("✅ Top {} of {total} cast(s):", ranked.len());
+    for (i, stats) in ranked.iter().enumerate() {
+        let date = chrono::DateTime::from_timestamp((stats.timestamp + FARCASTER_EPOCH) as i64, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+        println!(
+            "\n   {}. {} · {date} · {}",
+            i + 1,
+            stats.hash,
+            cast_preview(&stats.text)
+        );
+        println!(
+            "      📊 {} · ❤️  {} · 🔁 {} · 💬 {} · 📝 {}",
+            stats.engagement, stats.likes, stats.recasts, stats.replies, stats.quotes
+        );
+    }
+    Ok(())
+}
+
+/// First line of a cast's text, shortened for one-line listings
+fn cast_preview(text: &str) -> String {
+    const MAX_PREVIEW_CHARS: usize = 60;
+    let line = text.lines().next().unwrap_or_default();
+    let mut preview: String = line.chars().take(MAX_PREVIEW_CHARS).collect();
+    if line.chars().count() > MAX_PREVIEW_CHARS || text.lines().count() > 1 {
+        preview.push_str("...");
+    }
+    preview
+}
+
+/// Cast summary cache in the data directory, bypassed for reads with --refresh
+fn cast_stats_cache(refresh: bool) -> crate::core::client::CastStatsCache {
+    use crate::core::client::CastStatsCache;
+
+    let cache = CastStatsCache::with_cache_file(CastStatsCache::default_cache_path());
+    if refresh {
+        cache.refreshing()
+    } else {
+        cache
+    }
+}
+
+/// Fetch link previews for embed URLs through the cached fetcher
+async fn fetch_embed_previews(
+    urls: &[String],
+) -> std::collections::HashMap<String, crate::core::client::EmbedPreview> {
+    use crate::core::client::embeds::DEFAULT_EMBED_CONCURRENCY;
+    use crate::core::client::EmbedFetcher;
+
+    if urls.is_empty() {
+        return Default::default();
+    }
+    let fetcher = EmbedFetcher::with_cache_file(
+        DEFAULT_EMBED_CONCURRENCY,
+        EmbedFetcher::default_cache_path(),
+    );
+    let previews = fetcher.fetch_all(urls).await;
+    if let Err(e) = fetcher.save() {
+        tracing::debug!("Failed to save embed cache: {e}");
+    }
+    previews
+}
+
+/// Short lines describing a link preview
+fn embed_preview_lines(preview: &crate::core::client::EmbedPreview) -> Vec<String> {
+    const MAX_DESCRIPTION_CHARS: usize = 120;
+    let mut lines = Vec::new();
+    if let Some(title) = &preview.title {
+        lines.push(format!("📰 {title}"));
+    }
+    if let Some(description) = &preview.description {
+        let mut short: String = description.chars().take(MAX_DESCRIPTION_CHARS).collect();
+        if description.chars().count() > MAX_DESCRIPTION_CHARS {
+            short.push_str("...");
+        }
+        lines.push(short);
+    }
+    if let Some(image) = &preview.image {
+        lines.push(format!("🖼️  {image}"));
+    }
+    lines
+}
+
+/// Handle hub proofs command
+async fn handle_username_proofs(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fid: u64,
+    show_json: bool,
+) -> Result<()> {
+    println!("🪪 Getting username proofs for FID: {fid}");
+    let proofs = match hub_client.get_username_proofs(fid).await {
+        Ok(proofs) => proofs,
+        Err(e) => {
+            println!("❌ Failed to get username proofs: {e}");
+            return Ok(());
+        }
+    };
+
+    if proofs.is_empty() {
+        println!("❌ No username proofs found for FID: {fid}");
+        println!("   💡 Create one with 'castorix ens proof <domain> {fid}'");
+    } else if show_json {
+        println!("{}", output::json(&proofs)?);
+    } else {
+        println!("✅ Found {} username proof(s):", proofs.len());
+        for (i, proof) in proofs.iter().enumerate() {
+            println!("\n   {}. {}", i + 1, proof.name);
+            println!("      Type: {}", username_type_label(&proof.proof_type));
+            match chrono::DateTime::from_timestamp(proof.timestamp as i64, 0) {
+                Some(time) => println!("      Timestamp: {}", time.format("%Y-%m-%d %H:%M:%S UTC")),
+                None => println!("      Timestamp: {}", proof.timestamp),
+            }
+            println!("      Owner: {}", proof.owner);
+        }
+    }
+    Ok(())
+}
+
+/// Human-readable name of a hub `UserNameType`
+fn username_type_label(proof_type: &str) -> &str {
+    match proof_type {
+        "USERNAME_TYPE_FNAME" => "fname",
+        "USERNAME_TYPE_ENS_L1" => "ENS",
+        "USERNAME_TYPE_BASENAME" => "Basename",
+        other => other,
+    }
+}
+
 async fn handle_casts(
     hub_client: &crate::core::client::hub_client::FarcasterClient,
     fid: u64,
     limit: u32,
+    window: TimeWindow,
     show_json: bool,
+    embeds: bool,
 ) -> Result<()> {
     let limit_text = if limit == 0 {
         "all".to_string()
     } else {
         limit.to_string()
     };
-    println!("📝 Getting casts for FID: {fid} (limit: {limit_text})");
+    println!(
+        "📝 Getting casts for FID: {fid} (limit: {limit_text}){}",
+        window_suffix(&window)
+    );
 
-    match hub_client.get_casts_by_fid(fid, limit).await {
+    let casts = if window.is_unbounded() {
+        hub_client.get_casts_by_fid(fid, limit).await
+    } else {
+        hub_client.get_casts_in_window(fid, window, limit).await
+    };
+    match casts {
         Ok(casts) => {
             if casts.is_empty() {
                 println!("❌ No casts found for FID: {fid}");
             } else if show_json {
                 // Show full JSON structure
                 println!("✅ Found {} cast(s) - showing full JSON:", casts.len());
-                println!("{}", serde_json::to_string_pretty(&casts)?);
+                println!("{}", output::json(&casts)?);
             } else {
                 println!("✅ Found {} cast(s):", casts.len());
+                let previews = if embeds {
+                    let urls: Vec<String> = casts
+                        .iter()
+                        .filter_map(|c| c.pointer("/data/castAddBody/embeds"))
+                        .filter_map(|e| e.as_array())
+                        .flatten()
+                        .filter_map(|e| e.get("url").and_then(|u| u.as_str()))
+                        .map(|u| u.to_string())
+                        .collect();
+                    fetch_embed_previews(&urls).await
+                } else {
+                    Default::default()
+                };
                 println!("{}", "─".repeat(80));
 
                 for (i, cast) in casts.iter().enumerate() {
@@ -853,7 +1908,6 @@ async fn handle_casts(
 
                     // Format timestamp (convert Farcaster epoch to Unix timestamp)
                     // Farcaster epoch starts at 2021-01-01 00:00:00 UTC
-                    const FARCASTER_EPOCH: u64 = 1609459200; // January 1, 2021 UTC in seconds
                     let unix_timestamp = timestamp + FARCASTER_EPOCH;
                     let date_time = chrono::DateTime::from_timestamp(unix_timestamp as i64, 0)
                         .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
@@ -911,6 +1965,18 @@ async fn handle_casts(
 
                     if embed_count > 0 {
                         println!("   📎 Embeds: {}", embed_count);
+                        for url in embeds
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|e| e.get("url").and_then(|u| u.as_str()))
+                        {
+                            if let Some(preview) = previews.get(url) {
+                                println!("      🔗 {url}");
+                                for line in embed_preview_lines(preview) {
+                                    println!("         {line}");
+                                }
+                            }
+                        }
                     }
 
                     if mention_count > 0 {
@@ -927,3 +1993,431 @@ async fn handle_casts(
 
     Ok(())
 }
+
+/// Print the reactions a FID made, newest first
+async fn handle_reactions(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fid: u64,
+    reaction_type: Option<&str>,
+    limit: u32,
+    window: TimeWindow,
+    show_json: bool,
+) -> Result<()> {
+    let hub_type = reaction_type.map(|t| match t {
+        "recast" => "REACTION_TYPE_RECAST",
+        _ => "REACTION_TYPE_LIKE",
+    });
+    let limit_text = if limit == 0 {
+        "all".to_string()
+    } else {
+        limit.to_string()
+    };
+    println!(
+        "👍 Getting {} for FID: {fid} (limit: {limit_text}){}",
+        reaction_type.map_or("reactions", |t| if t == "recast" {
+            "recasts"
+        } else {
+            "likes"
+        }),
+        window_suffix(&window)
+    );
+
+    let reactions = hub_client
+        .get_reactions_in_window(fid, hub_type, window, limit)
+        .await?;
+    if show_json {
+        println!("{}", output::json(&reactions)?);
+        return Ok(());
+    }
+    if reactions.is_empty() {
+        println!("❌ No reactions found for FID: {fid}");
+        return Ok(());
+    }
+    println!("✅ Found {} reaction(s):", reactions.len());
+    for reaction in &reactions {
+        let body = reaction.pointer("/data/reactionBody");
+        let kind = match body.and_then(|b| b.get("type")).and_then(|t| t.as_str()) {
+            Some("REACTION_TYPE_RECAST") => "🔁 recast",
+            Some("REACTION_TYPE_LIKE") => "❤️ like",
+            _ => "❓ reaction",
+        };
+        let target = match body.and_then(|b| b.get("targetCastId")) {
+            Some(cast_id) => format!(
+                "cast {} by FID {}",
+                cast_id
+                    .get("hash")
+                    .and_then(|h| h.as_str())
+                    .unwrap_or("unknown"),
+                cast_id.get("fid").and_then(|f| f.as_u64()).unwrap_or(0)
+            ),
+            None => body
+                .and_then(|b| b.get("targetUrl"))
+                .and_then(|u| u.as_str())
+                .unwrap_or("unknown target")
+                .to_string(),
+        };
+        let date_time = reaction
+            .pointer("/data/timestamp")
+            .and_then(|t| t.as_u64())
+            .and_then(|t| chrono::DateTime::from_timestamp((t + FARCASTER_EPOCH) as i64, 0))
+            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+        println!("  {date_time}  {kind}  {target}");
+    }
+    Ok(())
+}
+
+async fn handle_mentions(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fid: u64,
+    limit: usize,
+    page: usize,
+    casts: u32,
+    show_json: bool,
+) -> Result<()> {
+    use crate::core::client::notifications::NotificationKind;
+
+    println!("🔔 Getting notifications for FID: {fid} (page {page}, {limit} per page)");
+
+    match hub_client.get_notifications(fid, page, limit, casts).await {
+        Ok(notifications) => {
+            if show_json {
+                println!("{}", output::json(&notifications)?);
+            } else if notifications.items.is_empty() {
+                println!("❌ No notifications found for FID: {fid}");
+            } else {
+                println!("{}", "─".repeat(80));
+                for notification in &notifications.items {
+                    let date_time = chrono::DateTime::from_timestamp(
+                        (notification.timestamp + FARCASTER_EPOCH) as i64,
+                        0,
+                    )
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|| "Unknown".to_string());
+
+                    match notification.kind {
+                        NotificationKind::Mention => {
+                            let text = notification.text.as_deref().unwrap_or("");
+                            let display_text = if text.chars().count() > 120 {
+                                format!("{}...", text.chars().take(120).collect::<String>())
+                            } else {
+                                text.to_string()
+                            };
+                            println!(
+                                "💬 {date_time}  FID {} mentioned you: {display_text}",
+                                notification.actor_fid
+                            );
+                        }
+                        NotificationKind::Like => println!(
+                            "❤️  {date_time}  FID {} liked your cast {}",
+                            notification.actor_fid,
+                            notification.target_hash.as_deref().unwrap_or("unknown")
+                        ),
+                        NotificationKind::Recast => println!(
+                            "🔁 {date_time}  FID {} recasted your cast {}",
+                            notification.actor_fid,
+                            notification.target_hash.as_deref().unwrap_or("unknown")
+                        ),
+                    }
+                }
+                println!("{}", "─".repeat(80));
+                if notifications.has_more {
+                    println!(
+                        "💡 More notifications available: castorix hub mentions {fid} --page {}",
+                        page + 1
+                    );
+                }
+            }
+        }
+        Err(e) => println!("❌ Failed to get notifications: {e}"),
+    }
+
+    Ok(())
+}
+
+async fn handle_compare(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    mut hubs: Vec<String>,
+    fid: u64,
+) -> Result<()> {
+    use crate::core::client::multi_hub::find_divergences;
+    use crate::core::client::multi_hub::MultiHubClient;
+
+    // A hub given twice would be queried twice and counted as two sources
+    let mut seen = std::collections::HashSet::new();
+    hubs.retain(|hub| seen.insert(hub.clone()));
+    let default_hub = hub_client.hub_url().to_string();
+    if hubs.len() < 2 && !hubs.contains(&default_hub) {
+        hubs.insert(0, default_hub);
+    }
+
+    println!("🔀 Comparing FID {fid} across {} hubs...", hubs.len());
+    let multi_hub = MultiHubClient::new(hubs);
+    let snapshots = multi_hub.store_snapshots(fid).await;
+
+    println!("{}", "─".repeat(80));
+    for snapshot in &snapshots {
+        match &snapshot.error {
+            Some(e) => println!("❌ {}: unreachable ({e})", snapshot.hub_url),
+            None => {
+                println!("📡 {}", snapshot.hub_url);
+                for (store, state) in &snapshot.stores {
+                    println!(
+                        "   {:<16} used: {:<8} latest: {}",
+                        store,
+                        state
+                            .used
+                            .map(|u| u.to_string())
+                            .unwrap_or_else(|| "-".to_string()),
+                        state.latest_hash.as_deref().unwrap_or("-")
+                    );
+                }
+            }
+        }
+    }
+    println!("{}", "─".repeat(80));
+
+    let divergences = find_divergences(&snapshots);
+    if divergences.is_empty() {
+        println!("✅ All reachable hubs agree on FID {fid}");
+    } else {
+        println!("⚠️  {} store(s) diverge:", divergences.len());
+        for divergence in &divergences {
+            println!("   🗂️  {}", divergence.store);
+            for (hub_url, state) in &divergence.states {
+                println!(
+                    "      {hub_url}: used={} latest={}",
+                    state
+                        .used
+                        .map(|u| u.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    state.latest_hash.as_deref().unwrap_or("-")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_sync_status(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    max_block_delay: u64,
+    show_json: bool,
+) -> Result<()> {
+    use crate::core::client::sync_status::format_lag;
+    use crate::core::client::sync_status::SyncStatus;
+    use crate::core::client::sync_status::DEFAULT_BLOCK_TIME;
+
+    let hub_info = hub_client
+        .get_hub_info()
+        .await
+        .map_err(|e| anyhow::anyhow!("Hub {} is unreachable: {e}", hub_client.hub_url()))?;
+    let status = SyncStatus::from_hub_info(&hub_info);
+    let issues = status.health_issues(max_block_delay);
+
+    if show_json {
+        println!(
+            "{}",
+            output::json(&serde_json::json!({
+                "hub_url": hub_client.hub_url(),
+                "healthy": issues.is_empty(),
+                "issues": issues,
+                "status": status,
+            }))?
+        );
+    } else {
+        println!("🩺 Sync status for {}", hub_client.hub_url());
+        println!("{}", "─".repeat(50));
+        if let Some(version) = &status.version {
+            println!("🏷️  Version: {version}");
+        }
+        if let Some(num_messages) = status.num_messages {
+            println!("📨 Messages: {num_messages}");
+        }
+        if let Some(peers) = status.peers {
+            println!("🤝 Peers: {peers}");
+        }
+        for shard in &status.shards {
+            println!(
+                "🧩 Shard {}: height {}, behind by {} blocks, {} messages",
+                shard.shard_id,
+                shard
+                    .max_height
+                    .map(|h| h.to_string())
+                    .unwrap_or_else(|| "?".to_string()),
+                shard.block_delay.unwrap_or(0),
+                shard
+                    .num_messages
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| "?".to_string())
+            );
+        }
+        if let Some(lag) = status.estimated_lag(DEFAULT_BLOCK_TIME) {
+            println!("⏱️  Estimated lag: {}", format_lag(lag));
+        }
+        println!("{}", "─".repeat(50));
+    }
+
+    if issues.is_empty() {
+        if !show_json {
+            println!("✅ Hub is healthy");
+        }
+        Ok(())
+    } else {
+        if !show_json {
+            for issue in &issues {
+                println!("❌ {issue}");
+            }
+        }
+        anyhow::bail!("Hub is unhealthy ({} issue(s))", issues.len())
+    }
+}
+
+async fn handle_verifications_map(
+    hub_client: &crate::core::client::hub_client::FarcasterClient,
+    fids_file: String,
+    format: String,
+    reverse: bool,
+    output: Option<String>,
+    concurrency: usize,
+    refresh: bool,
+) -> Result<()> {
+    use crate::core::client::verifications::VerificationMapper;
+
+    let fids = read_fids_file(&fids_file)?;
+    // Status lines would corrupt CSV/JSON written to stdout
+    let verbose = format == "table" || output.is_some();
+    if verbose {
+        println!(
+            "🗺️  Fetching verifications for {} FIDs ({} at a time)...",
+            fids.len(),
+            concurrency.max(1)
+        );
+    }
+
+    let cache_path = VerificationMapper::default_cache_path();
+    if refresh {
+        let _ = std::fs::remove_file(&cache_path);
+    }
+    let mapper = VerificationMapper::with_cache_file(concurrency, cache_path);
+    let map = mapper.fetch_all(hub_client, &fids).await;
+    if let Err(e) = mapper.save() {
+        eprintln!("⚠️  Failed to save verification cache: {e}");
+    }
+
+    let mut out = String::new();
+    if reverse {
+        let index = map.reverse_index();
+        match format.as_str() {
+            "csv" => {
+                out.push_str("address,protocol,fids\n");
+                for (address, fids) in &index {
+                    let fids: Vec<String> = fids.iter().map(|f| f.to_string()).collect();
+                    out.push_str(&format!(
+                        "{address},{},{}\n",
+                        address_protocol(address),
+                        fids.join(";")
+                    ));
+                }
+            }
+            "json" => {
+                out = output::json(&serde_json::json!({
+                    "addresses": index,
+                    "failed": map.failed,
+                }))? + "\n";
+            }
+            _ => {
+                out.push_str(&format!("{:<46} {:<9} FIDs\n", "Address", "Protocol"));
+                for (address, fids) in &index {
+                    let fids: Vec<String> = fids.iter().map(|f| f.to_string()).collect();
+                    out.push_str(&format!(
+                        "{address:<46} {:<9} {}\n",
+                        address_protocol(address),
+                        fids.join(", ")
+                    ));
+                }
+            }
+        }
+    } else {
+        match format.as_str() {
+            "csv" => {
+                out.push_str("fid,protocol,address\n");
+                for entry in &map.fids {
+                    if entry.is_empty() {
+                        out.push_str(&format!("{},,\n", entry.fid));
+                    }
+                    for address in &entry.eth_addresses {
+                        out.push_str(&format!("{},ethereum,{address}\n", entry.fid));
+                    }
+                    for address in &entry.sol_addresses {
+                        out.push_str(&format!("{},solana,{address}\n", entry.fid));
+                    }
+                }
+            }
+            "json" => {
+                out = output::json(&map)? + "\n";
+            }
+            _ => {
+                out.push_str(&format!("{:<10} {:<44} Solana\n", "FID", "Ethereum"));
+                for entry in &map.fids {
+                    let rows = entry
+                        .eth_addresses
+                        .len()
+                        .max(entry.sol_addresses.len())
+                        .max(1);
+                    for row in 0..rows {
+                        let fid = if row == 0 {
+                            entry.fid.to_string()
+                        } else {
+                            String::new()
+                        };
+                        out.push_str(&format!(
+                            "{fid:<10} {:<44} {}\n",
+                            entry.eth_addresses.get(row).map_or("-", |a| a.as_str()),
+                            entry.sol_addresses.get(row).map_or("-", |a| a.as_str())
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    match &output {
+        Some(path) => {
+            std::fs::write(path, &out).with_context(|| format!("Failed to write {path}"))?;
+            println!("💾 Wrote {path}");
+        }
+        // Data goes straight to stdout, bypassing --plain and translation
+        None => std::io::stdout().lock().write_all(out.as_bytes())?,
+    }
+
+    if verbose {
+        let verified = map.fids.iter().filter(|entry| !entry.is_empty()).count();
+        println!(
+            "✅ {} of {} FIDs have verified addresses ({} addresses)",
+            verified,
+            map.fids.len(),
+            map.reverse_index().len()
+        );
+    }
+    for (fid, error) in &map.failed {
+        eprintln!("❌ FID {fid}: {error}");
+    }
+    if !map.failed.is_empty() {
+        anyhow::bail!(
+            "Failed to fetch verifications for {} FIDs",
+            map.failed.len()
+        );
+    }
+    Ok(())
+}
+
+/// Protocol of an address in the verifications reverse index
+fn address_protocol(address: &str) -> &'static str {
+    if address.starts_with("0x") {
+        "ethereum"
+    } else {
+        "solana"
+    }
+}
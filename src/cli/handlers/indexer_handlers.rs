@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::cli::types::IndexerCommands;
+use crate::farcaster::indexer::EventIndexer;
+use crate::farcaster::indexer::EventKind;
+use crate::farcaster::indexer::EventStore;
+use crate::farcaster::indexer::IndexedEvent;
+use crate::farcaster::indexer::DEFAULT_BATCH_SIZE;
+
+/// Handle onchain registry event indexer commands
+pub async fn handle_indexer_command(command: IndexerCommands) -> Result<()> {
+    match command {
+        IndexerCommands::Sync {
+            from_block,
+            to_block,
+            batch_size,
+            rpc_url,
+        } => handle_sync(from_block, to_block, batch_size, rpc_url).await,
+        IndexerCommands::Status => handle_status(),
+        IndexerCommands::Signer { key, timestamps } => handle_signer(&key, timestamps).await,
+        IndexerCommands::RegisteredBy { address } => handle_registered_by(&address),
+        IndexerCommands::Fid { fid } => handle_fid(fid),
+    }
+}
+
+fn op_rpc_url(explicit: Option<String>) -> String {
+    explicit.unwrap_or_else(|| crate::consts::get_config().eth_op_rpc_url().to_string())
+}
+
+async fn handle_sync(
+    from_block: Option<u64>,
+    to_block: Option<u64>,
+    batch_size: u64,
+    rpc_url: Option<String>,
+) -> Result<()> {
+    let mut store = EventStore::open_default()?;
+    let indexer = EventIndexer::new(&op_rpc_url(rpc_url), batch_size)?;
+
+    println!("🔄 Indexing Farcaster registry events");
+    match store.last_block() {
+        Some(last) if from_block.is_none() => println!("   Resuming after block {last}"),
+        _ => {}
+    }
+
+    let summary = indexer
+        .sync(&mut store, from_block, to_block, |block, head, count| {
+            if count > 0 || block == head {
+                println!("   📦 block {block}/{head}: {count} events");
+            }
+        })
+        .await?;
+
+    println!(
+        "✅ Indexed blocks {}..={}: {} new events ({} total)",
+        summary.from_block,
+        summary.to_block,
+        summary.events,
+        store.events().len()
+    );
+    Ok(())
+}
+
+fn handle_status() -> Result<()> {
+    let store = EventStore::open_default()?;
+    println!("🗂️  Registry event index");
+    println!("{}", "=".repeat(40));
+    println!("📁 Location: {}", store.dir().display());
+    match store.last_block() {
+        Some(block) => println!("🧱 Indexed up to block: {block}"),
+        None => {
+            println!("🧱 Nothing indexed yet; run `castorix indexer sync`");
+            return Ok(());
+        }
+    }
+    println!("📊 Events: {}", store.events().len());
+
+    let mut by_kind: BTreeMap<&str, usize> = BTreeMap::new();
+    for event in store.events() {
+        *by_kind.entry(kind_name(&event.kind)).or_default() += 1;
+    }
+    for (kind, count) in by_kind {
+        println!("   {kind}: {count}");
+    }
+    Ok(())
+}
+
+async fn handle_signer(key: &str, timestamps: bool) -> Result<()> {
+    let store = EventStore::open_default()?;
+    let history = store.signer_history(key);
+    if history.is_empty() {
+        println!("❌ No KeyRegistry events for {key} in the local index");
+        print_coverage(&store);
+        return Ok(());
+    }
+
+    let indexer = if timestamps {
+        Some(EventIndexer::new(&op_rpc_url(None), DEFAULT_BATCH_SIZE)?)
+    } else {
+        None
+    };
+
+    println!("🔑 Signer {key}");
+    for event in history {
+        print_event(event);
+        if let Some(indexer) = &indexer {
+            let timestamp = indexer.block_timestamp(event.block_number).await?;
+            if let Some(time) = chrono::DateTime::from_timestamp(timestamp as i64, 0) {
+                println!("      🕒 {}", time.to_rfc3339());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn handle_registered_by(address: &str) -> Result<()> {
+    let store = EventStore::open_default()?;
+    let registrations = store.registered_by(address);
+    if registrations.is_empty() {
+        println!("❌ No FIDs registered to {address} in the local index");
+        print_coverage(&store);
+        return Ok(());
+    }
+
+    println!("🆔 FIDs registered to {address}: {}", registrations.len());
+    for event in registrations {
+        print_event(event);
+    }
+    Ok(())
+}
+
+fn handle_fid(fid: u64) -> Result<()> {
+    let store = EventStore::open_default()?;
+    let history = store.fid_history(fid);
+    if history.is_empty() {
+        println!("❌ No events for FID {fid} in the local index");
+        print_coverage(&store);
+        return Ok(());
+    }
+
+    println!("📜 Events for FID {fid}: {}", history.len());
+    for event in history {
+        print_event(event);
+    }
+    Ok(())
+}
+
+fn print_coverage(store: &EventStore) {
+    match store.last_block() {
+        Some(block) => println!("   Index covers blocks up to {block}"),
+        None => println!("   The index is empty; run `castorix indexer sync` first"),
+    }
+}
+
+fn print_event(event: &IndexedEvent) {
+    let details = match &event.kind {
+        EventKind::Register { to, recovery } => format!("to {to}, recovery {recovery}"),
+        EventKind::Transfer { from, to } | EventKind::Recover { from, to } => {
+            format!("{from} -> {to}")
+        }
+        EventKind::ChangeRecoveryAddress { recovery } => format!("recovery {recovery}"),
+        EventKind::KeyAdded {
+            key_type,
+            key,
+            metadata_type,
+        } => format!("key {key} (type {key_type}, metadata type {metadata_type})"),
+        EventKind::KeyRemoved { key } | EventKind::KeyAdminReset { key } => format!("key {key}"),
+        EventKind::Rent { payer, units } => format!("{units} units paid by {payer}"),
+        EventKind::SetPrice {
+            old_price,
+            new_price,
+        } => format!(
+            "unit price ${:.2} -> ${:.2}",
+            *old_price as f64 / 1e8,
+            *new_price as f64 / 1e8
+        ),
+    };
+    println!(
+        "   • block {} FID {} {}: {}",
+        event.block_number,
+        event.fid,
+        kind_name(&event.kind),
+        details
+    );
+    println!("      tx {}", event.tx_hash);
+}
+
+fn kind_name(kind: &EventKind) -> &'static str {
+    match kind {
+        EventKind::Register { .. } => "register",
+        EventKind::Transfer { .. } => "transfer",
+        EventKind::Recover { .. } => "recover",
+        EventKind::ChangeRecoveryAddress { .. } => "change_recovery_address",
+        EventKind::KeyAdded { .. } => "key_added",
+        EventKind::KeyRemoved { .. } => "key_removed",
+        EventKind::KeyAdminReset { .. } => "key_admin_reset",
+        EventKind::Rent { .. } => "rent",
+        EventKind::SetPrice { .. } => "set_price",
+    }
+}
@@ -0,0 +1,294 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::cli::output;
+use crate::core::crypto::key_audit::password_strength;
+use crate::core::crypto::key_audit::permission_issue;
+use crate::core::crypto::key_audit::AuditSeverity;
+use crate::core::crypto::key_audit::KeyAuditReport;
+use crate::core::crypto::key_audit::MIN_PASSWORD_SCORE;
+use crate::core::utils::data_dir;
+
+/// Handle `castorix key audit`
+pub async fn handle_key_audit(
+    storage_path: Option<&str>,
+    check_passwords: bool,
+    offline: bool,
+    json: bool,
+    hub_url: &str,
+    rpc_url: &str,
+) -> Result<()> {
+    use crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager;
+    use crate::core::crypto::encrypted_storage::EncryptedEthKeyManager;
+    use crate::encrypted_key_manager::EncryptedKeyManager;
+
+    let mut report = KeyAuditReport::default();
+
+    let wallet_dir = match storage_path {
+        Some(path) => PathBuf::from(path).join("keys"),
+        None => data_dir::keys_dir(),
+    };
+    let custody_dir = data_dir::custody_dir();
+
+    // Wallet keys (castorix key generate-encrypted / import)
+    let wallet_manager = EncryptedKeyManager::new(&wallet_dir.to_string_lossy());
+    let wallets = wallet_manager.list_keys_with_info().unwrap_or_default();
+    let mut addresses: Vec<(String, String)> = Vec::new();
+    for info in &wallets {
+        report.keys_checked += 1;
+        let label = format!("wallet {}", info.name);
+        check_permissions(
+            &mut report,
+            &label,
+            &wallet_dir.join(format!("{}.json", info.name)),
+        );
+        if check_passwords {
+            let password = crate::encrypted_key_manager::prompt_password(&format!(
+                "Enter password for wallet '{}' (leave empty to skip): ",
+                info.name
+            ))?;
+            if !password.is_empty() {
+                let mut manager = EncryptedKeyManager::new(&wallet_dir.to_string_lossy());
+                match manager.load_and_decrypt(&password, &info.name).await {
+                    Ok(()) => check_password(&mut report, &label, &password),
+                    Err(_) => println!("⚠️  Wrong password for {label}, skipping strength check"),
+                }
+            }
+        }
+        addresses.push((label, info.address.clone()));
+    }
+    check_permissions(&mut report, "wallet key directory", &wallet_dir);
+
+    // Custody keys (castorix hub key import / generate / from-mnemonic)
+    let mut custody_fids: Vec<u64> = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(&custody_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !file_name.ends_with("-custody.json") {
+                continue;
+            }
+            let Ok(manager) = EncryptedEthKeyManager::load_from_file(&path.to_string_lossy())
+            else {
+                report.add(
+                    AuditSeverity::Medium,
+                    file_name,
+                    "custody key file could not be parsed",
+                    "restore the file from a backup or re-import the key",
+                );
+                continue;
+            };
+            for (fid, address, _) in manager.list_keys() {
+                report.keys_checked += 1;
+                let label = format!("custody FID {fid}");
+                check_permissions(&mut report, &label, &path);
+                if check_passwords {
+                    let password = crate::encrypted_key_manager::prompt_password(&format!(
+                        "Enter password for custody key of FID {fid} (leave empty to skip): "
+                    ))?;
+                    if !password.is_empty() {
+                        match manager.decrypt_wallet(fid, &password) {
+                            Ok(_) => check_password(&mut report, &label, &password),
+                            Err(_) => {
+                                println!("⚠️  Wrong password for {label}, skipping strength check")
+                            }
+                        }
+                    }
+                }
+                addresses.push((label, address));
+                custody_fids.push(fid);
+            }
+        }
+    }
+    check_permissions(&mut report, "custody key directory", &custody_dir);
+
+    // Ed25519 signer keys
+    let mut signer_keys: Vec<(u64, String)> = Vec::new();
+    if let Ok(ed25519_file) = EncryptedEd25519KeyManager::default_keys_file() {
+        if Path::new(&ed25519_file).exists() {
+            check_permissions(&mut report, "Ed25519 key file", Path::new(&ed25519_file));
+            if let Ok(manager) = EncryptedEd25519KeyManager::load_from_file(&ed25519_file) {
+                for info in manager.list_keys() {
+                    report.keys_checked += 1;
+                    let label = match &info.label {
+                        Some(kept) => format!("Ed25519 key kept as '{kept}' for FID {}", info.fid),
+                        None => format!("Ed25519 signer for FID {}", info.fid),
+                    };
+                    if check_passwords {
+                        let password = crate::encrypted_key_manager::prompt_password(&format!(
+                            "Enter password for {label} (leave empty to skip): "
+                        ))?;
+                        if !password.is_empty() {
+                            let unlocked = match &info.label {
+                                Some(kept) => {
+                                    manager.get_labeled_signing_key(info.fid, kept, &password)
+                                }
+                                None => manager.get_signing_key(info.fid, &password),
+                            };
+                            match unlocked {
+                                Ok(_) => check_password(&mut report, &label, &password),
+                                Err(_) => println!(
+                                    "⚠️  Wrong password for {label}, skipping strength check"
+                                ),
+                            }
+                        }
+                    }
+                    signer_keys.push((info.fid, info.public_key));
+                }
+            }
+        }
+    }
+
+    if offline {
+        println!("📴 Offline mode: skipping hub and on-chain checks");
+    } else {
+        audit_online(
+            &mut report,
+            &addresses,
+            &custody_fids,
+            &signer_keys,
+            hub_url,
+            rpc_url,
+        )
+        .await;
+    }
+
+    report.sort();
+    if json {
+        println!("{}", output::json(&report)?);
+        return Ok(());
+    }
+
+    println!("🛡️  Key Audit Report");
+    println!("{}", "─".repeat(50));
+    println!("🔑 Keys checked: {}", report.keys_checked);
+    if report.findings.is_empty() {
+        println!("✅ No issues found");
+        return Ok(());
+    }
+    for finding in &report.findings {
+        let icon = match finding.severity {
+            AuditSeverity::High => "🔴",
+            AuditSeverity::Medium => "🟠",
+            AuditSeverity::Low => "🟡",
+        };
+        println!("\n{icon} [{}] {}", finding.severity, finding.key);
+        println!("   Issue: {}", finding.issue);
+        println!("   💡 {}", finding.recommendation);
+    }
+    println!("\n{}", "─".repeat(50));
+    println!(
+        "📊 {} high, {} medium, {} low",
+        report.count(AuditSeverity::High),
+        report.count(AuditSeverity::Medium),
+        report.count(AuditSeverity::Low)
+    );
+    if !check_passwords {
+        println!("💡 Run with --check-passwords to also score encryption passwords");
+    }
+
+    Ok(())
+}
+
+fn check_permissions(report: &mut KeyAuditReport, label: &str, path: &Path) {
+    if let Some(issue) = permission_issue(path) {
+        report.add(
+            AuditSeverity::High,
+            label,
+            issue,
+            format!("chmod go-rwx {}", path.display()),
+        );
+    }
+}
+
+fn check_password(report: &mut KeyAuditReport, label: &str, password: &str) {
+    let score = password_strength(password);
+    if score < MIN_PASSWORD_SCORE {
+        report.add(
+            if score <= 1 {
+                AuditSeverity::High
+            } else {
+                AuditSeverity::Medium
+            },
+            label,
+            format!("encryption password is weak (strength {score}/4)"),
+            "re-encrypt the key with a long passphrase of several unrelated words",
+        );
+    }
+}
+
+/// Hub and Optimism checks: unused addresses, unregistered signers, missing recovery
+async fn audit_online(
+    report: &mut KeyAuditReport,
+    addresses: &[(String, String)],
+    custody_fids: &[u64],
+    signer_keys: &[(u64, String)],
+    hub_url: &str,
+    rpc_url: &str,
+) {
+    use ethers::providers::Middleware;
+    use ethers::types::Address;
+
+    use crate::core::client::hub_client::FarcasterClient;
+    use crate::farcaster::contracts::types::ContractAddresses;
+    use crate::farcaster::contracts::FarcasterContractClient;
+
+    match FarcasterContractClient::new(rpc_url.to_string(), ContractAddresses::default()) {
+        Ok(client) => {
+            for (label, address) in addresses {
+                let Ok(address) = address.parse::<Address>() else {
+                    continue;
+                };
+                match client.provider().get_transaction_count(address, None).await {
+                    Ok(count) if count.is_zero() => report.add(
+                        AuditSeverity::Low,
+                        label.as_str(),
+                        format!("{address:?} has never sent a transaction on Optimism"),
+                        "delete the key if it is not needed, or verify it is the intended address",
+                    ),
+                    Ok(_) => {}
+                    Err(e) => println!("⚠️  Could not check activity of {label}: {e}"),
+                }
+            }
+
+            for fid in custody_fids {
+                match client.get_fid_info(*fid).await {
+                    Ok(info) if info.recovery == Address::zero() => report.add(
+                        AuditSeverity::Medium,
+                        format!("custody FID {fid}"),
+                        "FID has no recovery address set",
+                        "set a recovery address so the FID can be recovered if the custody key is lost",
+                    ),
+                    Ok(_) => {}
+                    Err(e) => println!("⚠️  Could not read FID {fid} from IdRegistry: {e}"),
+                }
+            }
+        }
+        Err(e) => println!("⚠️  Skipping on-chain checks: {e}"),
+    }
+
+    let hub = FarcasterClient::read_only(hub_url.to_string());
+    for (fid, public_key) in signer_keys {
+        match hub.get_signers(*fid).await {
+            Ok(signers) => {
+                let wanted = public_key.trim_start_matches("0x").to_lowercase();
+                let registered = signers
+                    .iter()
+                    .any(|s| s.key.trim_start_matches("0x").to_lowercase() == wanted);
+                if !registered {
+                    report.add(
+                        AuditSeverity::Low,
+                        format!("Ed25519 signer for FID {fid}"),
+                        format!("key 0x{wanted} is stored locally but not registered on-chain"),
+                        "register it with 'castorix signers register' or delete it",
+                    );
+                }
+            }
+            Err(e) => println!("⚠️  Could not fetch signers for FID {fid}: {e}"),
+        }
+    }
+}
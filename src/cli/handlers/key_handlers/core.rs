@@ -8,6 +8,7 @@ pub async fn handle_key_command(
     command: KeyCommands,
     key_manager: &crate::core::crypto::key_manager::KeyManager,
     storage_path: Option<&str>,
+    endpoints: &crate::cli::endpoints::EndpointOverrides,
 ) -> Result<()> {
     match command {
         KeyCommands::Info => {
@@ -17,7 +18,7 @@ pub async fn handle_key_command(
             println!("{}", "=".repeat(50));
 
             let manager = if let Some(path) = storage_path {
-                EncryptedKeyManager::new(path)
+                EncryptedKeyManager::new(&format!("{}/keys", path))
             } else {
                 EncryptedKeyManager::default_config()
             };
@@ -37,7 +38,7 @@ pub async fn handle_key_command(
                                     .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
                                     .unwrap_or_else(|| "Unknown".to_string());
                             println!("   Created: {created_date}");
-                            println!("   Storage: ~/.castorix/keys/{}.json", info.name);
+                            println!("   Storage: {}", manager.get_key_path(&info.name));
                         }
                     }
                 }
@@ -96,8 +97,14 @@ pub async fn handle_key_command(
         KeyCommands::List => {
             super::encrypted::handle_list_keys(storage_path).await?;
         }
-        KeyCommands::Delete { key_name } => {
-            super::encrypted::handle_delete_key(key_name, storage_path).await?;
+        KeyCommands::Delete { key_name, force } => {
+            super::encrypted::handle_delete_key(
+                key_name,
+                storage_path,
+                force,
+                &endpoints.op_rpc_url(),
+            )
+            .await?;
         }
         KeyCommands::Rename { old_name, new_name } => {
             super::encrypted::handle_rename_key(old_name, new_name, storage_path).await?;
@@ -111,6 +118,21 @@ pub async fn handle_key_command(
         KeyCommands::Import => {
             super::encrypted::handle_import_key(storage_path).await?;
         }
+        KeyCommands::Audit {
+            check_passwords,
+            offline,
+            json,
+        } => {
+            super::audit::handle_key_audit(
+                storage_path,
+                check_passwords,
+                offline,
+                json,
+                &endpoints.hub_url(),
+                &endpoints.op_rpc_url(),
+            )
+            .await?;
+        }
     }
     Ok(())
 }
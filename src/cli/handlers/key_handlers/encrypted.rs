@@ -1,7 +1,9 @@
 use anyhow::Result;
 
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
+
 pub async fn handle_generate_encrypted(storage_path: Option<&str>) -> Result<()> {
-    use std::io::Write;
     use std::io::{
         self,
     };
@@ -15,8 +17,7 @@ pub async fn handle_generate_encrypted(storage_path: Option<&str>) -> Result<()>
     println!("{}", "=".repeat(40));
 
     // Get key name
-    print!("Enter key name: ");
-    io::stdout().flush()?;
+    prompt::ask(InputKind::Text, "Enter key name: ")?;
     let mut key_name = String::new();
     io::stdin().read_line(&mut key_name)?;
     let key_name = key_name.trim().to_string();
@@ -44,8 +45,10 @@ pub async fn handle_generate_encrypted(storage_path: Option<&str>) -> Result<()>
     println!("   Address: {address}");
 
     // Confirm if user wants to continue
-    print!("\nDo you want to encrypt and save this key? (y/N): ");
-    io::stdout().flush()?;
+    prompt::ask(
+        InputKind::Confirmation,
+        "\nDo you want to encrypt and save this key? (y/N): ",
+    )?;
     let mut confirm = String::new();
     io::stdin().read_line(&mut confirm)?;
 
@@ -77,12 +80,7 @@ pub async fn handle_generate_encrypted(storage_path: Option<&str>) -> Result<()>
             println!("✅ Encrypted key saved successfully!");
             println!("   Key Name: {key_name}");
             println!("   Address: {address}");
-            let storage_path = if let Some(path) = storage_path {
-                format!("{}/keys/{key_name}.json", path)
-            } else {
-                format!("~/.castorix/keys/{key_name}.json")
-            };
-            println!("   Storage: {storage_path}");
+            println!("   Storage: {}", manager.get_key_path(&key_name));
         }
         Err(e) => println!("❌ Failed to save encrypted key: {e}"),
     }
@@ -157,7 +155,12 @@ pub async fn handle_list_keys(storage_path: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-pub async fn handle_delete_key(key_name: String, storage_path: Option<&str>) -> Result<()> {
+pub async fn handle_delete_key(
+    key_name: String,
+    storage_path: Option<&str>,
+    force: bool,
+    rpc_url: &str,
+) -> Result<()> {
     use std::fs;
 
     use crate::encrypted_key_manager::prompt_password;
@@ -177,6 +180,12 @@ pub async fn handle_delete_key(key_name: String, storage_path: Option<&str>) ->
         return Ok(());
     }
 
+    let address = manager.get_key_info(&key_name)?.address;
+    let check = super::safety::check_custody_key(rpc_url, &address).await;
+    if !super::safety::allow_deletion(&check, force) {
+        return Ok(());
+    }
+
     let password = prompt_password("Enter password to confirm deletion: ")?;
 
     // Verify password by trying to load the key
@@ -190,14 +199,9 @@ pub async fn handle_delete_key(key_name: String, storage_path: Option<&str>) ->
     match temp_manager.load_and_decrypt(&password, &key_name).await {
         Ok(_) => {
             // Password is correct, proceed with deletion
-            let key_path = if let Some(path) = storage_path {
-                format!("{}/{key_name}.json", path)
-            } else {
-                format!("~/.castorix/keys/{key_name}.json")
-            };
-            let expanded_path = shellexpand::tilde(&key_path).to_string();
+            let key_path = temp_manager.get_key_path(&key_name);
 
-            match fs::remove_file(&expanded_path) {
+            match fs::remove_file(&key_path) {
                 Ok(_) => println!("✅ Key '{key_name}' deleted successfully!"),
                 Err(e) => println!("❌ Failed to delete key: {e}"),
             }
@@ -281,7 +285,6 @@ pub async fn handle_update_alias(
 }
 
 pub async fn handle_import_key(storage_path: Option<&str>) -> Result<()> {
-    use std::io::Write;
     use std::io::{
         self,
     };
@@ -296,8 +299,7 @@ pub async fn handle_import_key(storage_path: Option<&str>) -> Result<()> {
     println!("{}", "=".repeat(40));
 
     // Get key name
-    print!("Enter key name: ");
-    io::stdout().flush()?;
+    prompt::ask(InputKind::Text, "Enter key name: ")?;
     let mut key_name = String::new();
     io::stdin().read_line(&mut key_name)?;
     let key_name = key_name.trim().to_string();
@@ -319,8 +321,10 @@ pub async fn handle_import_key(storage_path: Option<&str>) -> Result<()> {
             println!("   Address: {address}");
 
             // Confirm if user wants to continue
-            print!("\nDo you want to encrypt and save this key? (y/N): ");
-            io::stdout().flush()?;
+            prompt::ask(
+                InputKind::Confirmation,
+                "\nDo you want to encrypt and save this key? (y/N): ",
+            )?;
             let mut confirm = String::new();
             io::stdin().read_line(&mut confirm)?;
 
@@ -354,7 +358,7 @@ pub async fn handle_import_key(storage_path: Option<&str>) -> Result<()> {
                     println!("✅ Private key imported successfully!");
                     println!("   Key Name: {key_name}");
                     println!("   Address: {address}");
-                    println!("   Storage: ~/.castorix/keys/{key_name}.json");
+                    println!("   Storage: {}", manager.get_key_path(&key_name));
                 }
                 Err(e) => println!("❌ Failed to save encrypted key: {e}"),
             }
@@ -1,9 +1,21 @@
 use anyhow::Result;
 
+use crate::cli::endpoints::EndpointOverrides;
+use crate::cli::output;
 use crate::cli::types::HubKeyCommands;
+use crate::core::client::hub_client::farcaster_now;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_client::FARCASTER_EPOCH;
+use crate::core::services::SignerService;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
 
 /// Handle Hub key management commands
-pub async fn handle_hub_key_command(command: HubKeyCommands) -> Result<()> {
+pub async fn handle_hub_key_command(
+    command: HubKeyCommands,
+    hub_client: &FarcasterClient,
+    endpoints: &EndpointOverrides,
+) -> Result<()> {
     match command {
         HubKeyCommands::Import { fid } => {
             handle_hub_key_import(fid).await?;
@@ -11,12 +23,19 @@ pub async fn handle_hub_key_command(command: HubKeyCommands) -> Result<()> {
         HubKeyCommands::List => {
             handle_hub_key_list().await?;
         }
-        HubKeyCommands::Delete { fid } => {
-            handle_hub_key_delete(fid).await?;
+        HubKeyCommands::Delete { fid, force } => {
+            handle_hub_key_delete(&endpoints.op_rpc_url(), fid, force).await?;
         }
         HubKeyCommands::FromMnemonic { fid } => {
             handle_hub_key_from_mnemonic(fid).await?;
         }
+        HubKeyCommands::Stats {
+            fid,
+            stale_days,
+            json,
+        } => {
+            handle_hub_key_stats(hub_client, fid, stale_days, json).await?;
+        }
     }
     Ok(())
 }
@@ -38,12 +57,13 @@ async fn handle_hub_key_import(fid: u64) -> Result<()> {
     if eth_exists {
         println!("⚠️  ECDSA key already exists for FID: {fid}");
 
-        print!("\nDo you want to replace the existing key? (y/N): ");
-        use std::io::Write;
+        prompt::ask(
+            InputKind::Confirmation,
+            "\nDo you want to replace the existing key? (y/N): ",
+        )?;
         use std::io::{
             self,
         };
-        io::stdout().flush()?;
 
         let mut input = String::new();
         io::stdin().read_line(&mut input)?;
@@ -181,12 +201,13 @@ async fn handle_hub_key_from_mnemonic(fid: u64) -> Result<()> {
     if eth_exists {
         println!("⚠️  ECDSA key already exists for FID: {fid}");
 
-        print!("\nDo you want to replace the existing key? (y/N): ");
-        use std::io::Write;
+        prompt::ask(
+            InputKind::Confirmation,
+            "\nDo you want to replace the existing key? (y/N): ",
+        )?;
         use std::io::{
             self,
         };
-        io::stdout().flush()?;
 
         let mut input = String::new();
         io::stdin().read_line(&mut input)?;
@@ -248,7 +269,7 @@ async fn handle_hub_key_from_mnemonic(fid: u64) -> Result<()> {
     Ok(())
 }
 
-async fn handle_hub_key_delete(fid: u64) -> Result<()> {
+async fn handle_hub_key_delete(rpc_url: &str, fid: u64, force: bool) -> Result<()> {
     println!("🗑️ Deleting ECDSA key for FID: {fid}");
     println!("{}", "=".repeat(40));
 
@@ -271,13 +292,19 @@ async fn handle_hub_key_delete(fid: u64) -> Result<()> {
     println!("   Address: {address}");
     println!("   Type: Ethereum wallet (custody key)");
 
+    let check = super::safety::check_custody_key(rpc_url, &address).await;
+    if !super::safety::allow_deletion(&check, force) {
+        return Ok(());
+    }
+
     // Confirm deletion
-    print!("\n⚠️  Are you sure you want to delete this key? (y/N): ");
-    use std::io::Write;
+    prompt::ask(
+        InputKind::Confirmation,
+        "\n⚠️  Are you sure you want to delete this key? (y/N): ",
+    )?;
     use std::io::{
         self,
     };
-    io::stdout().flush()?;
 
     let mut input = String::new();
     io::stdin().read_line(&mut input)?;
@@ -309,3 +336,73 @@ async fn handle_hub_key_delete(fid: u64) -> Result<()> {
 
     Ok(())
 }
+
+async fn handle_hub_key_stats(
+    hub_client: &FarcasterClient,
+    fid: u64,
+    stale_days: u64,
+    json: bool,
+) -> Result<()> {
+    if !json {
+        println!("📊 Signer usage for FID: {fid}");
+        println!("{}", "=".repeat(40));
+        println!("⏳ Scanning hub messages...");
+    }
+
+    let report = SignerService::new(hub_client).usage(fid).await?;
+    let stale_before = (farcaster_now() as u64).saturating_sub(stale_days * 86400);
+
+    if json {
+        let stale: Vec<&str> = report
+            .stale(stale_before)
+            .map(|usage| usage.key.as_str())
+            .collect();
+        let mut output = serde_json::to_value(&report)?;
+        output["stale"] = serde_json::json!(stale);
+        println!("{}", output::json(&output)?);
+        return Ok(());
+    }
+
+    println!("✅ Scanned {} messages", report.scanned);
+    if report.signers.is_empty() {
+        println!("❌ No signers found for FID: {fid}");
+        return Ok(());
+    }
+
+    let format_time = |timestamp: Option<u64>| {
+        timestamp
+            .and_then(|t| chrono::DateTime::from_timestamp((t + FARCASTER_EPOCH) as i64, 0))
+            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "never".to_string())
+    };
+    for usage in &report.signers {
+        let status = if !usage.active {
+            "removed"
+        } else if usage.last_timestamp.is_none_or(|last| last < stale_before) {
+            "⚠️  stale"
+        } else {
+            "active"
+        };
+        println!("\n🔑 {}", usage.key);
+        println!("   Status: {status}");
+        println!("   Messages: {}", usage.messages);
+        if !usage.by_type.is_empty() {
+            let by_type: Vec<String> = usage
+                .by_type
+                .iter()
+                .map(|(message_type, count)| format!("{message_type} {count}"))
+                .collect();
+            println!("   By type: {}", by_type.join(", "));
+        }
+        println!("   First used: {}", format_time(usage.first_timestamp));
+        println!("   Last used: {}", format_time(usage.last_timestamp));
+    }
+
+    let stale = report.stale(stale_before).count();
+    if stale > 0 {
+        println!("\n⚠️  {stale} registered signer(s) unused in the last {stale_days} days");
+        println!("💡 Every registered signer can post for this FID; remove unused ones with:");
+        println!("   castorix signers unregister {fid}");
+    }
+    Ok(())
+}
@@ -1,5 +1,7 @@
+pub mod audit;
 pub mod core;
 pub mod encrypted;
 pub mod hub;
+pub mod safety;
 
 pub use core::handle_key_command;
@@ -0,0 +1,60 @@
+//! On-chain safety check shared by the key deletion commands
+
+use anyhow::Result;
+use ethers::types::Address;
+
+use crate::farcaster::contracts::key_safety::DeletionCheck;
+use crate::farcaster::contracts::types::ContractAddresses;
+use crate::farcaster::contracts::FarcasterContractClient;
+
+fn contract_client(rpc_url: &str) -> Result<FarcasterContractClient> {
+    FarcasterContractClient::new(rpc_url.to_string(), ContractAddresses::default())
+}
+
+/// Check whether an Ethereum key about to be deleted is a custody key
+pub async fn check_custody_key(rpc_url: &str, address: &str) -> DeletionCheck {
+    let address = match address.parse::<Address>() {
+        Ok(address) => address,
+        Err(e) => return DeletionCheck::unverified(format!("invalid address {address}: {e}")),
+    };
+    match contract_client(rpc_url) {
+        Ok(client) => client.check_custody_deletion(address).await,
+        Err(e) => DeletionCheck::unverified(format!("Optimism RPC unavailable: {e}")),
+    }
+}
+
+/// Check whether an Ed25519 key about to be deleted is a registered signer
+pub async fn check_signer_key(rpc_url: &str, fid: u64, public_key: &str) -> DeletionCheck {
+    let public_key = match hex::decode(public_key.trim_start_matches("0x")) {
+        Ok(bytes) => bytes,
+        Err(e) => return DeletionCheck::unverified(format!("invalid public key: {e}")),
+    };
+    match contract_client(rpc_url) {
+        Ok(client) => client.check_signer_deletion(fid, &public_key).await,
+        Err(e) => DeletionCheck::unverified(format!("Optimism RPC unavailable: {e}")),
+    }
+}
+
+/// Report the outcome of a check and decide whether deletion may go ahead
+///
+/// Deletion is refused when the key still holds an on-chain role, or when
+/// that could not be ruled out, unless `force` is set.
+pub fn allow_deletion(check: &DeletionCheck, force: bool) -> bool {
+    if check.is_safe() {
+        println!("✅ On-chain check: the key is not in use");
+        return true;
+    }
+    for role in &check.in_use {
+        println!("⚠️  This key is still the {role}");
+    }
+    for reason in &check.unverified {
+        println!("⚠️  Could not verify on-chain state: {reason}");
+    }
+    if force {
+        println!("⚠️  --force given, deleting anyway");
+        true
+    } else {
+        println!("❌ Refusing to delete. Re-run with --force to delete anyway.");
+        false
+    }
+}
@@ -6,6 +6,7 @@ use anyhow::Result;
 use tracing::info;
 use tracing::Level;
 
+use crate::cli::endpoints::EndpointOverrides;
 use crate::cli::types::McpCommands;
 use crate::mcp::create_contract_tools;
 use crate::mcp::create_custody_tools;
@@ -15,12 +16,15 @@ use crate::mcp::create_signer_tools;
 use crate::mcp::HubContext;
 use crate::mcp::McpServer;
 use crate::mcp::SignerContext;
+use crate::mcp::ToolPermissions;
 use crate::mcp::ToolRegistry;
 
 /// Handle MCP commands
-pub async fn handle_mcp_command(command: McpCommands, hub_url: String) -> Result<()> {
+pub async fn handle_mcp_command(command: McpCommands, endpoints: &EndpointOverrides) -> Result<()> {
     match command {
-        McpCommands::Serve => {
+        McpCommands::Serve { config } => {
+            let hub_url = endpoints.hub_url();
+
             // Initialize tracing
             let log_level = std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string());
             tracing_subscriber::fmt()
@@ -36,7 +40,8 @@ pub async fn handle_mcp_command(command: McpCommands, hub_url: String) -> Result
                 .with_thread_ids(false)
                 .with_file(false)
                 .with_line_number(false)
-                .init();
+                .try_init()
+                .map_err(|e| anyhow::anyhow!("Failed to initialize logging: {e}"))?;
 
             info!(
                 "Starting Castorix MCP Server v{}",
@@ -44,6 +49,12 @@ pub async fn handle_mcp_command(command: McpCommands, hub_url: String) -> Result
             );
             info!("Connecting to Farcaster Hub: {}", hub_url);
 
+            // Load the operator's tool permissions before doing any work
+            let permissions = match &config {
+                Some(path) => ToolPermissions::load(std::path::Path::new(path), true)?,
+                None => ToolPermissions::load(&ToolPermissions::default_path(), false)?,
+            };
+
             // Create contexts
             let hub_context = Arc::new(HubContext::new(hub_url.clone()));
             let signer_context = Arc::new(SignerContext::new(hub_url));
@@ -83,10 +94,14 @@ pub async fn handle_mcp_command(command: McpCommands, hub_url: String) -> Result
 
             // Register ENS tools
             info!("Registering ENS tools...");
-            if let (Ok(eth_rpc_url), Ok(base_rpc_url)) = (
-                std::env::var("ETH_RPC_URL"),
-                std::env::var("ETH_BASE_RPC_URL"),
-            ) {
+            let eth_rpc_url = endpoints
+                .eth_rpc_url
+                .clone()
+                .map(Ok)
+                .unwrap_or_else(|| std::env::var("ETH_RPC_URL"));
+            if let (Ok(eth_rpc_url), Ok(base_rpc_url)) =
+                (eth_rpc_url, std::env::var("ETH_BASE_RPC_URL"))
+            {
                 match create_ens_tools(eth_rpc_url, base_rpc_url) {
                     Ok(ens_tools) => {
                         registry.register_all(ens_tools);
@@ -106,6 +121,15 @@ pub async fn handle_mcp_command(command: McpCommands, hub_url: String) -> Result
 
             info!("Registered {} tools total", registry.count());
 
+            for name in permissions.unknown_tools(&registry.names()) {
+                eprintln!(
+                    "Warning: MCP configuration names tool '{}', which is not available",
+                    name
+                );
+            }
+            registry.set_permissions(permissions);
+            info!("Exposing {} tools", registry.enabled_count());
+
             // Create and start MCP server
             let server = McpServer::new(registry);
 
@@ -1,25 +1,58 @@
+pub mod alias_handlers;
 pub mod api_handlers;
+pub mod audit_handlers;
+pub mod autocast_handlers;
+pub mod bench_handlers;
+pub mod channel_handlers;
+pub mod context_handlers;
 pub mod custody_handlers;
+#[cfg(feature = "dev")]
+pub mod dev_handlers;
 pub mod ens_handlers;
 pub mod fid_handlers;
 pub mod hub_handlers;
+pub mod indexer_handlers;
 pub mod key_handlers;
 pub mod mcp_handlers;
+pub mod monitor_handlers;
+pub mod plugin_handlers;
+pub mod replay_handlers;
+pub mod self_handlers;
+pub mod share_handlers;
 pub mod signers_handlers;
 pub mod storage_handlers;
+pub mod tier_handlers;
+pub mod tx_handlers;
+pub mod util_handlers;
+pub mod watchlist_handlers;
 
 use anyhow::Result;
 
+use crate::cli::endpoints::EndpointOverrides;
+use crate::cli::types::AliasCommands;
 use crate::cli::types::ApiCommands;
+use crate::cli::types::AuditCommands;
+use crate::cli::types::BenchCommands;
+use crate::cli::types::ChannelCommands;
+use crate::cli::types::ContextCommands;
 use crate::cli::types::CustodyCommands;
+#[cfg(feature = "dev")]
+use crate::cli::types::DevCommands;
 use crate::cli::types::EnsCommands;
 use crate::cli::types::FidCommands;
 use crate::cli::types::HubCommands;
 use crate::cli::types::HubKeyCommands;
+use crate::cli::types::IndexerCommands;
 use crate::cli::types::KeyCommands;
 use crate::cli::types::McpCommands;
+use crate::cli::types::PluginCommands;
+use crate::cli::types::SelfCommands;
 use crate::cli::types::SignersCommands;
 use crate::cli::types::StorageCommands;
+use crate::cli::types::TierCommands;
+use crate::cli::types::TxCommands;
+use crate::cli::types::UtilCommands;
+use crate::cli::types::WatchlistCommands;
 
 /// CLI command handler
 pub struct CliHandler;
@@ -30,26 +63,36 @@ impl CliHandler {
         command: KeyCommands,
         key_manager: &crate::core::crypto::key_manager::KeyManager,
         storage_path: Option<&str>,
+        endpoints: &EndpointOverrides,
     ) -> Result<()> {
         crate::cli::handlers::key_handlers::core::handle_key_command(
             command,
             key_manager,
             storage_path,
+            endpoints,
         )
         .await
     }
 
     /// Handle Hub Ed25519 key management commands
-    pub async fn handle_hub_key_command(command: HubKeyCommands) -> Result<()> {
-        crate::cli::handlers::key_handlers::hub::handle_hub_key_command(command).await
+    pub async fn handle_hub_key_command(
+        command: HubKeyCommands,
+        hub_client: &crate::core::client::hub_client::FarcasterClient,
+        endpoints: &EndpointOverrides,
+    ) -> Result<()> {
+        crate::cli::handlers::key_handlers::hub::handle_hub_key_command(
+            command, hub_client, endpoints,
+        )
+        .await
     }
 
     /// Handle ENS commands
     pub async fn handle_ens_command(
         command: EnsCommands,
         ens_proof: &crate::ens_proof::EnsProof,
+        hub_client: &crate::core::client::hub_client::FarcasterClient,
     ) -> Result<()> {
-        ens_handlers::handle_ens_command(command, ens_proof).await
+        ens_handlers::handle_ens_command(command, ens_proof, hub_client).await
     }
 
     /// Handle Farcaster Hub commands
@@ -61,45 +104,209 @@ impl CliHandler {
     }
 
     /// Handle ECDSA custody key management commands
-    pub async fn handle_custody_command(command: CustodyCommands) -> Result<()> {
-        custody_handlers::handle_custody_command(command).await
+    pub async fn handle_custody_command(
+        command: CustodyCommands,
+        storage_path: Option<&str>,
+        endpoints: &EndpointOverrides,
+    ) -> Result<()> {
+        custody_handlers::handle_custody_command(command, storage_path, endpoints).await
     }
 
     /// Handle signer management commands
     pub async fn handle_signers_command(
         command: SignersCommands,
         hub_client: &crate::core::client::hub_client::FarcasterClient,
+        endpoints: &EndpointOverrides,
     ) -> Result<()> {
-        signers_handlers::handle_signers_command(command, hub_client).await
+        signers_handlers::handle_signers_command(command, hub_client, endpoints).await
     }
 
     /// Handle FID registration and management commands
     pub async fn handle_fid_command(
         command: FidCommands,
         storage_path: Option<&str>,
+        endpoints: &EndpointOverrides,
     ) -> Result<()> {
-        fid_handlers::handle_fid_command(command, storage_path).await
+        fid_handlers::handle_fid_command(command, storage_path, endpoints).await
     }
 
     /// Handle storage rental and management commands
     pub async fn handle_storage_command(
         command: StorageCommands,
         storage_path: Option<&str>,
+        endpoints: &EndpointOverrides,
+    ) -> Result<()> {
+        storage_handlers::handle_storage_command(command, storage_path, endpoints).await
+    }
+
+    /// Handle subscription tier commands
+    pub async fn handle_tier_command(
+        command: TierCommands,
+        storage_path: Option<&str>,
+        endpoints: &EndpointOverrides,
+    ) -> Result<()> {
+        tier_handlers::handle_tier_command(command, storage_path, endpoints).await
+    }
+
+    /// Handle air-gapped transaction commands
+    pub async fn handle_tx_command(
+        command: TxCommands,
+        storage_path: Option<&str>,
+        endpoints: &EndpointOverrides,
     ) -> Result<()> {
-        storage_handlers::handle_storage_command(command, storage_path).await
+        tx_handlers::handle_tx_command(command, storage_path, endpoints).await
+    }
+
+    /// Handle onchain registry event indexer commands
+    pub async fn handle_indexer_command(command: IndexerCommands) -> Result<()> {
+        indexer_handlers::handle_indexer_command(command).await
     }
 
     /// Handle MCP server commands
-    pub async fn handle_mcp_command(command: McpCommands, hub_url: String) -> Result<()> {
-        mcp_handlers::handle_mcp_command(command, hub_url).await
+    pub async fn handle_mcp_command(
+        command: McpCommands,
+        endpoints: &EndpointOverrides,
+    ) -> Result<()> {
+        mcp_handlers::handle_mcp_command(command, endpoints).await
+    }
+
+    /// Handle local development commands
+    #[cfg(feature = "dev")]
+    pub async fn handle_dev_command(command: DevCommands) -> Result<()> {
+        dev_handlers::handle_dev_command(command).await
+    }
+
+    /// Handle FID monitor command
+    pub async fn handle_monitor_command(
+        fid: u64,
+        webhook: Option<String>,
+        interval: u64,
+        max_retries: u32,
+        hub_client: &crate::core::client::hub_client::FarcasterClient,
+    ) -> Result<()> {
+        monitor_handlers::handle_monitor_command(fid, webhook, interval, max_retries, hub_client)
+            .await
+    }
+
+    /// Handle the protocol contract monitor command
+    pub async fn handle_monitor_contracts_command(
+        webhook: Option<String>,
+        interval: u64,
+        max_retries: u32,
+        endpoints: &EndpointOverrides,
+    ) -> Result<()> {
+        monitor_handlers::handle_monitor_contracts_command(
+            webhook,
+            interval,
+            max_retries,
+            endpoints,
+        )
+        .await
+    }
+
+    /// Handle feed-driven automated casting
+    pub async fn handle_autocast_command(
+        fid: u64,
+        autocast: crate::core::client::autocast::Autocast,
+        schedule: autocast_handlers::Schedule,
+        hub_client: &crate::core::client::hub_client::FarcasterClient,
+    ) -> Result<()> {
+        autocast_handlers::handle_autocast_command(fid, autocast, schedule, hub_client).await
+    }
+
+    /// Handle profile share page rendering and serving
+    pub async fn handle_share_command(
+        fid: u64,
+        serve: bool,
+        host: String,
+        port: u16,
+        output: Option<String>,
+        casts: usize,
+        hub_client: crate::core::client::hub_client::FarcasterClient,
+    ) -> Result<()> {
+        share_handlers::handle_share_command(fid, serve, host, port, output, casts, hub_client)
+            .await
+    }
+
+    /// Handle command alias management
+    pub fn handle_alias_command(command: AliasCommands) -> Result<()> {
+        alias_handlers::handle_alias_command(command)
+    }
+
+    /// Copy the messages of a FID from one hub to another
+    pub async fn handle_replay_command(
+        from: String,
+        to: String,
+        fid: u64,
+        dry_run: bool,
+    ) -> Result<()> {
+        replay_handlers::handle_replay_command(&from, &to, fid, dry_run).await
+    }
+
+    /// Handle named context management
+    pub fn handle_context_command(command: ContextCommands) -> Result<()> {
+        context_handlers::handle_context_command(command)
+    }
+
+    /// Handle audit log commands
+    pub fn handle_audit_command(command: AuditCommands) -> Result<()> {
+        audit_handlers::handle_audit_command(command)
+    }
+
+    /// Handle channel membership and moderation queries
+    pub async fn handle_channel_command(command: ChannelCommands) -> Result<()> {
+        channel_handlers::handle_channel_command(command).await
+    }
+
+    /// Handle watchlist commands
+    pub async fn handle_watchlist_command(
+        command: WatchlistCommands,
+        hub_client: &crate::core::client::hub_client::FarcasterClient,
+    ) -> Result<()> {
+        watchlist_handlers::handle_watchlist_command(command, hub_client).await
+    }
+
+    /// Handle release verification and self-update commands
+    pub async fn handle_self_command(command: SelfCommands) -> Result<()> {
+        self_handlers::handle_self_command(command).await
+    }
+
+    /// Handle standalone utilities
+    pub fn handle_util_command(command: UtilCommands) -> Result<()> {
+        util_handlers::handle_util_command(command)
+    }
+
+    /// Handle plugin commands
+    pub fn handle_plugin_command(command: PluginCommands) -> Result<()> {
+        plugin_handlers::handle_plugin_command(command)
+    }
+
+    /// Run the plugin providing an unknown command, returning its exit code
+    pub fn handle_external_command(
+        args: Vec<String>,
+        endpoints: &EndpointOverrides,
+        profile: Option<&str>,
+        context: Option<String>,
+    ) -> Result<i32> {
+        plugin_handlers::handle_external_command(args, endpoints, profile, context)
     }
 
     /// Handle API server commands
-    pub async fn handle_api_command(command: ApiCommands) -> Result<()> {
+    pub async fn handle_api_command(
+        command: ApiCommands,
+        endpoints: &EndpointOverrides,
+    ) -> Result<()> {
         match command {
-            ApiCommands::Serve { host, port } => {
-                api_handlers::handle_api_command(host, port).await
-            }
+            ApiCommands::Serve {
+                host,
+                port,
+                upstreams,
+            } => api_handlers::handle_api_command(host, port, upstreams, endpoints).await,
         }
     }
+
+    /// Handle load testing commands
+    pub async fn handle_bench_command(command: BenchCommands) -> Result<()> {
+        bench_handlers::handle_bench_command(command).await
+    }
 }
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::cli::endpoints::EndpointOverrides;
+use crate::cli::output;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::monitor::FidMonitor;
+use crate::core::client::webhook::WebhookNotifier;
+use crate::farcaster::contracts::status_monitor::ContractMonitor;
+use crate::farcaster::contracts::types::ContractAddresses;
+use crate::farcaster::contracts::FarcasterContractClient;
+
+/// Handle the FID monitor command
+pub async fn handle_monitor_command(
+    fid: u64,
+    webhook: Option<String>,
+    interval: u64,
+    max_retries: u32,
+    hub_client: &FarcasterClient,
+) -> Result<()> {
+    let notifier = match webhook {
+        Some(url) => Some(WebhookNotifier::new(url, max_retries)?),
+        None => None,
+    };
+
+    println!("👀 Monitoring FID: {fid}");
+    println!("📡 Hub: {}", hub_client.hub_url());
+    match &notifier {
+        Some(n) => println!("🔔 Webhook: {}", n.url()),
+        None => println!("🔔 No webhook configured, printing events to stdout"),
+    }
+    println!("⏱️  Poll interval: {interval}s (press Ctrl+C to stop)");
+    println!("{}", "─".repeat(50));
+
+    let mut monitor = FidMonitor::new(hub_client, fid);
+    let mut ticker = tokio::time::interval(Duration::from_secs(interval.max(1)));
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                println!("\n👋 Monitor stopped");
+                return Ok(());
+            }
+            _ = ticker.tick() => {}
+        }
+
+        let events = match monitor.poll().await {
+            Ok(events) => events,
+            Err(e) => {
+                println!("⚠️  Poll failed, will retry next interval: {e}");
+                continue;
+            }
+        };
+
+        for event in events {
+            match &notifier {
+                Some(n) => match n.deliver(&event).await {
+                    Ok(()) => println!("✅ Delivered {:?} event", event.kind),
+                    Err(e) => println!("❌ Failed to deliver {:?} event: {e}", event.kind),
+                },
+                None => println!("{}", output::json_line(&event)?),
+            }
+        }
+    }
+}
+
+/// Handle the protocol contract monitor command
+pub async fn handle_monitor_contracts_command(
+    webhook: Option<String>,
+    interval: u64,
+    max_retries: u32,
+    endpoints: &EndpointOverrides,
+) -> Result<()> {
+    let notifier = match webhook {
+        Some(url) => Some(WebhookNotifier::new(url, max_retries)?),
+        None => None,
+    };
+    let rpc_url = endpoints.op_rpc_url();
+    let client = FarcasterContractClient::new(rpc_url.clone(), ContractAddresses::default())?;
+
+    println!("🚦 Monitoring Farcaster contracts");
+    println!("📡 RPC: {rpc_url}");
+    match &notifier {
+        Some(n) => println!("🔔 Webhook: {}", n.url()),
+        None => println!("🔔 No webhook configured, printing alerts to stdout"),
+    }
+    println!("⏱️  Poll interval: {interval}s (press Ctrl+C to stop)");
+    println!("{}", "─".repeat(50));
+
+    let mut monitor = ContractMonitor::new(&client);
+    let mut ticker = tokio::time::interval(Duration::from_secs(interval.max(1)));
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                println!("\n👋 Monitor stopped");
+                return Ok(());
+            }
+            _ = ticker.tick() => {}
+        }
+
+        let (status, alerts) = monitor.poll().await;
+        if let Some(status) = &status {
+            let state = if status.any_paused() {
+                "⏸️  paused"
+            } else {
+                "✅ open"
+            };
+            println!(
+                "{state} · block {} · registration {} ETH · {} ms",
+                status.block_number,
+                ethers::utils::format_ether(status.registration_price),
+                status.rpc_latency_ms
+            );
+        }
+
+        for alert in alerts {
+            match &notifier {
+                Some(n) => match n.deliver(&alert).await {
+                    Ok(()) => println!("✅ Delivered alert: {}", alert.message),
+                    Err(e) => println!("❌ Failed to deliver alert '{}': {e}", alert.message),
+                },
+                None => println!("{}", output::json_line(&alert)?),
+            }
+        }
+    }
+}
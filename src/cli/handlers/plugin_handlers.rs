@@ -0,0 +1,107 @@
+use anyhow::Result;
+
+use crate::cli::aliases;
+use crate::cli::aliases::CliConfig;
+use crate::cli::context;
+use crate::cli::endpoints::EndpointOverrides;
+use crate::cli::output;
+use crate::cli::plugins;
+use crate::cli::plugins::PluginConfig;
+use crate::cli::plugins::PluginContext;
+use crate::cli::plugins::PluginKind;
+use crate::cli::plugins::CONTEXT_SCHEMA_VERSION;
+use crate::cli::types::PluginCommands;
+use crate::core::utils::data_dir;
+use crate::core::utils::prompt;
+
+/// Handle plugin commands
+pub fn handle_plugin_command(command: PluginCommands) -> Result<()> {
+    match command {
+        PluginCommands::List { json } => {
+            let plugins = plugins::discover();
+            if json {
+                println!("{}", output::json(&plugins)?);
+                return Ok(());
+            }
+            if plugins.is_empty() {
+                println!("🧩 No plugins installed");
+                println!(
+                    "💡 Put castorix-<name> executables on PATH or in {}",
+                    plugins::plugins_dir().display()
+                );
+                return Ok(());
+            }
+            println!("🧩 Plugins ({}):", plugins.len());
+            for plugin in &plugins {
+                let kind = match plugin.kind {
+                    PluginKind::Executable => "",
+                    PluginKind::Wasm => " (wasm)",
+                };
+                println!("   {:<20} {}{kind}", plugin.name, plugin.path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run the plugin providing an unknown command
+///
+/// # Arguments
+/// * `args` - The command name followed by its arguments
+/// * `endpoints` - Endpoints in effect, passed on to the plugin
+/// * `profile` - The `--profile` flag, if given
+/// * `context_flag` - The `--context` flag, if given
+///
+/// # Returns
+/// * `Result<i32>` - The plugin's exit code; exits with status 2 when no
+///   plugin provides the command
+pub fn handle_external_command(
+    args: Vec<String>,
+    endpoints: &EndpointOverrides,
+    profile: Option<&str>,
+    context_flag: Option<String>,
+) -> Result<i32> {
+    let (name, args) = args
+        .split_first()
+        .map(|(name, args)| (name.clone(), args.to_vec()))
+        .ok_or_else(|| anyhow::anyhow!("No command given"))?;
+    let cli_config = CliConfig::load()?;
+    let Some(plugin) = plugins::find(&name) else {
+        eprintln!("error: unrecognized subcommand '{name}'");
+        let candidates: Vec<String> = aliases::command_names()
+            .into_iter()
+            .chain(aliases::all_aliases(&cli_config.aliases).into_keys())
+            .chain(plugins::discover().into_iter().map(|plugin| plugin.name))
+            .collect();
+        if let Some(similar) = aliases::suggest(&name, &candidates).first() {
+            eprintln!();
+            eprintln!("  tip: a similar subcommand exists: '{similar}'");
+        }
+        eprintln!();
+        eprintln!("For more information, try '--help'.");
+        std::process::exit(2);
+    };
+
+    let config = crate::consts::get_config();
+    let active = context::active(&cli_config, context_flag)?;
+    let (context_name, context) = active.unzip();
+    let plugin_context = PluginContext {
+        schema_version: CONTEXT_SCHEMA_VERSION,
+        castorix_version: env!("CARGO_PKG_VERSION").to_string(),
+        plugin: name,
+        args,
+        data_dir: data_dir::data_dir(),
+        profile: data_dir::profile_name(profile),
+        context_name,
+        context,
+        config: PluginConfig {
+            hub_url: endpoints.hub_url(),
+            eth_rpc_url: endpoints.eth_rpc_url(),
+            eth_base_rpc_url: config.eth_base_rpc_url().to_string(),
+            eth_op_rpc_url: endpoints.op_rpc_url(),
+        },
+        plain: output::is_plain(),
+        non_interactive: prompt::is_non_interactive(),
+    };
+    plugin.run(&plugin_context)
+}
@@ -0,0 +1,82 @@
+use anyhow::Result;
+
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_pool::configured_endpoint;
+use crate::core::client::replay::message_from_json;
+use crate::core::client::replay::message_hash;
+use crate::core::client::replay::plan_replay;
+use crate::core::policy;
+
+/// Copy the messages of a FID from one hub to another
+///
+/// Each hub is used on its own, without failing over to the configured hubs,
+/// so that messages are read from and written to exactly the hubs given.
+pub async fn handle_replay_command(from: &str, to: &str, fid: u64, dry_run: bool) -> Result<()> {
+    policy::current().check_fid(fid)?;
+    let source = FarcasterClient::with_hubs(vec![configured_endpoint(from)])?;
+    let target = FarcasterClient::with_hubs(vec![configured_endpoint(to)])?.into_signing(None);
+
+    println!("📥 Reading messages of FID {fid} from {}", source.hub_url());
+    let messages = source
+        .get_all_messages(fid, |store, count| println!("   {store}: {count}"))
+        .await?;
+    println!("🔎 Checking what {} already has", target.hub_url());
+    let existing = target.get_all_messages(fid, |_, _| {}).await?;
+    let plan = plan_replay(messages, &existing);
+    println!(
+        "📋 {} messages read, {} already present, {} to submit",
+        plan.total,
+        plan.present,
+        plan.pending.len()
+    );
+    if plan.pending.is_empty() {
+        println!("✅ {} is up to date for FID {fid}", target.hub_url());
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    let mut messages = Vec::new();
+    for json in &plan.pending {
+        let hash = message_hash(json).unwrap_or_else(|| "?".to_string());
+        match message_from_json(json) {
+            Ok(message) => messages.push((hash, message)),
+            Err(e) => failed.push((hash, e)),
+        }
+    }
+    if dry_run {
+        println!(
+            "🔍 Dry run: {} messages would be submitted, {} can't be replayed",
+            messages.len(),
+            failed.len()
+        );
+        print_failures(&failed);
+        return Ok(());
+    }
+
+    let (mut submitted, mut duplicates) = (0, 0);
+    for (i, (hash, message)) in messages.iter().enumerate() {
+        match target.replay_message(message).await {
+            Ok(true) => submitted += 1,
+            Ok(false) => duplicates += 1,
+            Err(e) => failed.push((hash.clone(), e)),
+        }
+        if (i + 1) % 100 == 0 {
+            println!("   {}/{} submitted", i + 1, messages.len());
+        }
+    }
+    println!(
+        "✅ Replayed {submitted} messages to {} ({duplicates} were already there)",
+        target.hub_url()
+    );
+    print_failures(&failed);
+    if !failed.is_empty() {
+        anyhow::bail!("{} messages could not be replayed", failed.len());
+    }
+    Ok(())
+}
+
+fn print_failures(failed: &[(String, anyhow::Error)]) {
+    for (hash, error) in failed {
+        println!("   ❌ 0x{hash}: {error:#}");
+    }
+}
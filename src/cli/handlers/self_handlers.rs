@@ -0,0 +1,120 @@
+use anyhow::Result;
+
+use crate::cli::types::SelfCommands;
+use crate::core::release;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
+
+/// Handle release verification and self-update commands
+pub async fn handle_self_command(command: SelfCommands) -> Result<()> {
+    match command {
+        SelfCommands::Verify {
+            manifest,
+            public_key,
+        } => handle_self_verify(manifest, public_key).await,
+        SelfCommands::Update {
+            manifest,
+            public_key,
+            yes,
+            force,
+        } => handle_self_update(manifest, public_key, yes, force).await,
+    }
+}
+
+async fn handle_self_verify(manifest: Option<String>, public_key: Option<String>) -> Result<()> {
+    let url = manifest
+        .or_else(|| std::env::var(release::RELEASE_MANIFEST_ENV).ok())
+        .unwrap_or_else(|| release::version_manifest_url(release::CURRENT_VERSION));
+    let public_key = release::release_public_key(public_key)?;
+
+    println!("🔏 Verifying castorix {}", release::CURRENT_VERSION);
+    println!("📄 Manifest: {url}");
+    let manifest = release::fetch_manifest(&url, &public_key).await?;
+    println!(
+        "✅ Manifest signature is valid (release {})",
+        manifest.version
+    );
+    manifest.ensure_version(release::CURRENT_VERSION)?;
+
+    let target = release::current_target();
+    let artifact = manifest
+        .artifact_for(&target)
+        .ok_or_else(|| anyhow::anyhow!("Release {} has no build for {target}", manifest.version))?;
+
+    let (exe, digest) = release::current_exe_digest()?;
+    println!("📦 Binary: {}", exe.display());
+    println!("   SHA-256:  {digest}");
+    println!("   Expected: {}", artifact.sha256);
+
+    if digest.eq_ignore_ascii_case(&artifact.sha256) {
+        println!("✅ Running binary matches the signed {target} release");
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "Running binary does not match the signed {target} release {}",
+            manifest.version
+        )
+    }
+}
+
+async fn handle_self_update(
+    manifest: Option<String>,
+    public_key: Option<String>,
+    yes: bool,
+    force: bool,
+) -> Result<()> {
+    let url = release::manifest_url(manifest);
+    let public_key = release::release_public_key(public_key)?;
+
+    println!(
+        "⬆️  Checking for updates (current: {})",
+        release::CURRENT_VERSION
+    );
+    let manifest = release::fetch_manifest(&url, &public_key).await?;
+    println!(
+        "✅ Manifest signature is valid (release {})",
+        manifest.version
+    );
+
+    if !manifest.is_newer_than_current() && !force {
+        println!("✅ Already up to date, use --force to reinstall");
+        return Ok(());
+    }
+
+    let target = release::current_target();
+    let artifact = manifest
+        .artifact_for(&target)
+        .ok_or_else(|| anyhow::anyhow!("Release {} has no build for {target}", manifest.version))?;
+
+    if !yes {
+        prompt::ask(
+            InputKind::Confirmation,
+            &format!(
+                "\n❓ Replace castorix {} with {}? (yes/no): ",
+                release::CURRENT_VERSION,
+                manifest.version
+            ),
+        )?;
+
+        let mut confirmation = String::new();
+        std::io::stdin().read_line(&mut confirmation)?;
+        let confirmation = confirmation.trim().to_lowercase();
+        if confirmation != "yes" && confirmation != "y" {
+            println!("❌ Update cancelled by user");
+            return Ok(());
+        }
+    }
+
+    println!("📥 Downloading {}", artifact.url);
+    let bytes = release::download_artifact(artifact).await?;
+    println!("✅ Download matches the signed SHA-256");
+
+    let exe = std::env::current_exe()?;
+    release::replace_binary(&exe, &bytes)?;
+    println!(
+        "🎉 Updated {} to castorix {}",
+        exe.display(),
+        manifest.version
+    );
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+
+use crate::api::handlers::hub::HubState;
+use crate::api::handlers::share;
+use crate::core::client::hub_client::FarcasterClient;
+
+/// Handle the profile share page command
+pub async fn handle_share_command(
+    fid: u64,
+    serve: bool,
+    host: String,
+    port: u16,
+    output: Option<String>,
+    casts: usize,
+    hub_client: FarcasterClient,
+) -> Result<()> {
+    let state = HubState {
+        client: Arc::new(hub_client),
+        spam: None,
+    };
+
+    if serve {
+        return serve_share_page(state, fid, &host, port).await;
+    }
+
+    let (user_data, cast_messages) = share::fetch_share_data(state, fid, casts)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to load profile for FID {fid}: {e}"))?;
+    let html = share::render_share_page(fid, &user_data, &cast_messages);
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, html).with_context(|| format!("Failed to write {path}"))?;
+            println!("✅ Share page for FID {fid} written to {path}");
+        }
+        None => print!("{html}"),
+    }
+    Ok(())
+}
+
+/// Serve the share page on a local address until Ctrl+C
+async fn serve_share_page(state: HubState, fid: u64, host: &str, port: u16) -> Result<()> {
+    let addr: SocketAddr = format!("{host}:{port}")
+        .parse()
+        .context("Invalid host:port combination")?;
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind to {addr}"))?;
+
+    println!("🪪 Share page for FID {fid}");
+    println!("🌐 http://{addr}/share/{fid}");
+    println!("📡 Hub: {}", state.client.hub_url());
+    println!("⏹️  Press Ctrl+C to stop");
+
+    axum::serve(listener, share::share_router(state, fid))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .context("Server error")?;
+
+    println!("\n👋 Share server stopped");
+    Ok(())
+}
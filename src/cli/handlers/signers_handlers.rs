@@ -3,6 +3,7 @@ use aes_gcm::aead::KeyInit;
 use aes_gcm::Aes256Gcm;
 use aes_gcm::Key;
 use aes_gcm::Nonce;
+use anyhow::Context;
 use anyhow::Result;
 use argon2::password_hash::SaltString;
 use argon2::Argon2;
@@ -11,11 +12,28 @@ use base64::engine::general_purpose;
 use base64::Engine as _;
 use ethers::prelude::Middleware;
 use ethers::signers::Signer;
+use ethers::utils::hash_message;
 
+use crate::cli::endpoints::EndpointOverrides;
+use crate::cli::handlers::tx_handlers::load_custody_wallet;
 use crate::cli::types::SignersCommands;
+use crate::core::audit;
 use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_propagation::ExpectedEvent;
+use crate::core::client::hub_propagation::DEFAULT_HUB_WAIT_TIMEOUT;
+use crate::core::crypto::encrypted_storage::ConflictResolution;
+use crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager;
+use crate::core::crypto::encrypted_storage::ImportConflict;
+use crate::core::policy;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
+use crate::farcaster::contracts::adapters::Eip712Domain;
+use crate::farcaster::contracts::contract_client::AppIdentity;
 use crate::farcaster::contracts::types::ContractResult;
 
+/// `--on-conflict` strategy and `--label` of a signer import
+type ImportConflictArgs<'a> = (Option<ImportConflict>, Option<&'a str>);
+
 #[derive(Debug, Clone)]
 struct LocalEd25519Key {
     name: String,
@@ -26,10 +44,12 @@ struct LocalEd25519Key {
 pub async fn handle_signers_command(
     command: SignersCommands,
     hub_client: &FarcasterClient,
+    endpoints: &EndpointOverrides,
 ) -> Result<()> {
+    let rpc_url = &endpoints.op_rpc_url();
     match command {
         SignersCommands::List => {
-            handle_signers_list().await?;
+            handle_signers_list(hub_client).await?;
         }
         SignersCommands::Info { fid } => {
             handle_signers_info(hub_client, fid).await?;
@@ -37,16 +57,21 @@ pub async fn handle_signers_command(
         SignersCommands::Register {
             fid,
             wallet,
+            app_fid,
             payment_wallet,
             dry_run,
+            wait_hub,
             yes,
         } => {
             handle_add_signer(
                 hub_client,
+                rpc_url,
                 fid,
                 wallet.as_deref(),
+                app_fid,
                 payment_wallet.as_deref(),
                 dry_run,
+                wait_hub,
                 yes,
             )
             .await?;
@@ -56,24 +81,207 @@ pub async fn handle_signers_command(
             wallet,
             payment_wallet,
             dry_run,
+            wait_hub,
+            yes,
         } => {
             handle_del_signer(
                 hub_client,
+                rpc_url,
                 fid,
                 wallet.as_deref(),
                 payment_wallet.as_deref(),
                 dry_run,
+                wait_hub,
+                yes,
+            )
+            .await?;
+        }
+        SignersCommands::Rotate {
+            fid,
+            old_key,
+            canary,
+            dry_run,
+            yes,
+        } => {
+            handle_rotate_signer(
+                hub_client,
+                rpc_url,
+                fid,
+                old_key.as_deref(),
+                canary,
+                dry_run,
+                yes,
             )
             .await?;
         }
-        SignersCommands::Import { fid } => {
-            handle_signers_import(fid).await?;
+        SignersCommands::Import {
+            fid,
+            format,
+            file,
+            path,
+            on_conflict,
+            label,
+        } => {
+            check_conflict_label(on_conflict, label.as_deref())?;
+            let conflict = (on_conflict, label.as_deref());
+            if format == "hex" && file.is_none() {
+                handle_signers_import(fid, conflict).await?;
+            } else {
+                handle_signers_import_external(
+                    hub_client,
+                    fid,
+                    &format,
+                    file.as_deref(),
+                    &path,
+                    conflict,
+                )
+                .await?;
+            }
         }
         SignersCommands::Export { identifier } => {
             handle_signers_export(&identifier).await?;
         }
-        SignersCommands::Delete { identifier } => {
-            handle_signers_delete(&identifier).await?;
+        SignersCommands::Delete { identifier, force } => {
+            handle_signers_delete(rpc_url, &identifier, force).await?;
+        }
+        SignersCommands::AddAuthAddress {
+            fid,
+            address,
+            dry_run,
+            yes,
+        } => {
+            handle_add_auth_address(rpc_url, fid, &address, dry_run, yes).await?;
+        }
+        SignersCommands::RemoveAuthAddress {
+            fid,
+            address,
+            payment_wallet,
+            dry_run,
+            yes,
+        } => {
+            handle_remove_auth_address(
+                rpc_url,
+                fid,
+                &address,
+                payment_wallet.as_deref(),
+                dry_run,
+                yes,
+            )
+            .await?;
+        }
+        SignersCommands::Onchain {
+            fid,
+            include_removed,
+            page_size,
+            from_block,
+        } => {
+            handle_signers_onchain(
+                hub_client,
+                rpc_url,
+                fid,
+                include_removed,
+                page_size,
+                from_block,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Handle signers onchain command
+async fn handle_signers_onchain(
+    hub_client: &FarcasterClient,
+    rpc_url: &str,
+    fid: u64,
+    include_removed: bool,
+    page_size: u64,
+    from_block: Option<u64>,
+) -> Result<()> {
+    use crate::core::utils::warpcast;
+    use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+    use crate::farcaster::contracts::key_listing::KEY_STATE_ADDED;
+    use crate::farcaster::contracts::key_listing::KEY_STATE_REMOVED;
+    use crate::farcaster::contracts::types::ContractAddresses;
+    use crate::farcaster::indexer::DEFAULT_START_BLOCK;
+
+    println!("⛓️  Listing KeyRegistry keys for FID: {fid}");
+    let contract_client =
+        FarcasterContractClient::new(rpc_url.to_string(), ContractAddresses::default())?;
+
+    let mut states = vec![(KEY_STATE_ADDED, "Added")];
+    if include_removed {
+        states.push((KEY_STATE_REMOVED, "Removed"));
+    }
+    let mut keys = Vec::new();
+    for (state, label) in states {
+        for key in contract_client.list_keys(fid, state, page_size).await? {
+            keys.push((key, label));
+        }
+    }
+    if keys.is_empty() {
+        println!("ℹ️  No keys found in the KeyRegistry for FID: {fid}");
+        return Ok(());
+    }
+
+    let start = from_block.unwrap_or(DEFAULT_START_BLOCK);
+    let metadata = match contract_client.key_add_metadata(fid, start).await {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            println!("⚠️  Could not read key metadata: {e}");
+            println!("   💡 If your RPC limits log ranges, pass a later --from-block");
+            Default::default()
+        }
+    };
+
+    // Several keys are usually requested by the same app; look each one up once
+    let mut app_names: std::collections::HashMap<u64, Option<String>> = Default::default();
+    println!("✅ Found {} key(s) for FID {}:", keys.len(), fid);
+    for (i, (key, state)) in keys.iter().enumerate() {
+        println!("\n🔑 Key #{}:", i + 1);
+        println!("   Public Key: 0x{}", hex::encode(key));
+        println!("   State: {state}");
+
+        let Some(added) = metadata.get(key) else {
+            println!("   Metadata: not found in Add events since block {start}");
+            continue;
+        };
+        println!("   Key Type: {} (1 = Ed25519)", added.key_type);
+        println!("   Added At Block: {}", added.block_number);
+        let Some(request) = added.signed_key_request() else {
+            println!(
+                "   Metadata Type: {} (not a signed key request)",
+                added.metadata_type
+            );
+            continue;
+        };
+
+        let app_fid = request.request_fid;
+        let app_name = match app_names.get(&app_fid) {
+            Some(name) => name.clone(),
+            None => {
+                let name = hub_client
+                    .get_user(app_fid)
+                    .await
+                    .ok()
+                    .and_then(|data| warpcast::username_from_user_data(&data));
+                app_names.insert(app_fid, name.clone());
+                name
+            }
+        };
+        match app_name {
+            Some(name) => println!("   📱 Requested By: @{name} (FID {app_fid})"),
+            None => println!("   📱 Requested By: FID {app_fid}"),
+        }
+        println!("   Request Signer: {:?}", request.request_signer);
+        match chrono::DateTime::from_timestamp(request.deadline as i64, 0) {
+            Some(deadline) if request.deadline < i64::MAX as u64 => {
+                println!(
+                    "   Request Deadline: {}",
+                    deadline.format("%Y-%m-%d %H:%M:%S UTC")
+                )
+            }
+            _ => println!("   Request Deadline: {}", request.deadline),
         }
     }
     Ok(())
@@ -134,14 +342,19 @@ async fn handle_signers_info(hub_client: &FarcasterClient, fid: u64) -> Result<(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_add_signer(
-    _hub_client: &FarcasterClient,
+    hub_client: &FarcasterClient,
+    rpc_url: &str,
     fid: u64,
     wallet_name: Option<&str>,
+    app_fid: Option<u64>,
     payment_wallet_name: Option<&str>,
     dry_run: bool,
+    wait_hub: bool,
     yes: bool,
 ) -> Result<()> {
+    policy::current().check_fid(fid)?;
     println!("➕ Adding signer for FID: {fid}");
 
     // Determine the custody wallet name
@@ -201,7 +414,18 @@ async fn handle_add_signer(
         .map_err(|e| anyhow::anyhow!("Failed to load wallet for FID {}: {}", fid, e))?;
 
     // Create FarcasterContractClient with the custody wallet for authorization
-    let contract_client = create_contract_client_with_local_wallet(wallet).await?;
+    let mut contract_client = create_contract_client_with_local_wallet(rpc_url, wallet).await?;
+
+    // Request the key as the app FID, signed with the app's own custody key
+    if let Some(app_fid) = app_fid.filter(|&app_fid| app_fid != fid) {
+        let app_wallet = load_custody_wallet(app_fid)?;
+        contract_client = contract_client.with_app_identity(AppIdentity {
+            fid: app_fid,
+            wallet: std::sync::Arc::new(app_wallet),
+        });
+        contract_client.verify_app_identity().await?;
+        println!("📱 Requesting the key as app FID {app_fid}");
+    }
 
     // If using third-party payment, create a separate client for the payment wallet
     let payment_contract_client = if payment_wallet_name != wallet_name {
@@ -226,7 +450,7 @@ async fn handle_add_signer(
             })?
             .clone();
 
-        Some(create_contract_client_with_wallet(payment_key_manager).await?)
+        Some(create_contract_client_with_wallet(rpc_url, payment_key_manager).await?)
     } else {
         None
     };
@@ -290,6 +514,13 @@ async fn handle_add_signer(
         } else {
             println!("   • Using custody wallet for both authorization and gas payment");
         }
+        if let Some(app) = contract_client.app_identity() {
+            println!(
+                "   • Requested by app FID {} ({:?})",
+                app.fid,
+                app.wallet.address()
+            );
+        }
 
         // Create EIP-712 signature for signer registration (simulation)
         let deadline = std::time::SystemTime::now()
@@ -386,13 +617,15 @@ async fn handle_add_signer(
     }
 
     // Ask for user confirmation (skip if --yes is provided)
+    policy::current().check_confirmation(yes)?;
     if !yes {
-        print!("\n❓ Do you want to proceed with the on-chain registration? (yes/no): ");
-        use std::io::Write;
+        prompt::ask(
+            InputKind::Confirmation,
+            "\n❓ Do you want to proceed with the on-chain registration? (yes/no): ",
+        )?;
         use std::io::{
             self,
         };
-        io::stdout().flush()?;
 
         let mut confirmation = String::new();
         io::stdin().read_line(&mut confirmation)?;
@@ -471,6 +704,7 @@ async fn handle_add_signer(
             .add_for_raw(
                 &payment_client.provider,
                 payment_wallet,
+                fid,
                 fid_info.custody,
                 1, // Ed25519 key type
                 public_key.clone(),
@@ -528,6 +762,7 @@ async fn handle_add_signer(
         let result = contract_client
             .key_gateway
             .add_for(
+                fid,
                 fid_info.custody,
                 1, // Ed25519 key type
                 public_key.clone(),
@@ -658,18 +893,27 @@ async fn handle_add_signer(
 
     println!("✅ Ed25519 private key stored encrypted for FID: {fid}");
     println!("🔑 Public key: {}", hex::encode(&public_key));
+
+    if wait_hub {
+        wait_for_hub(hub_client, fid, ExpectedEvent::SignerAdd(public_key)).await?;
+    }
     println!("📝 You can now use this signer for Farcaster operations with FID {fid}");
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_del_signer(
     hub_client: &FarcasterClient,
+    rpc_url: &str,
     fid: u64,
     wallet_name: Option<&str>,
     payment_wallet_name: Option<&str>,
     dry_run: bool,
+    wait_hub: bool,
+    yes: bool,
 ) -> Result<()> {
+    policy::current().check_fid(fid)?;
     println!("➖ Removing signer for FID: {fid}");
 
     // Determine the custody wallet name
@@ -729,7 +973,7 @@ async fn handle_del_signer(
         .map_err(|e| anyhow::anyhow!("Failed to load wallet for FID {}: {}", fid, e))?;
 
     // Create FarcasterContractClient with the custody wallet for authorization
-    let contract_client = create_contract_client_with_local_wallet(wallet).await?;
+    let contract_client = create_contract_client_with_local_wallet(rpc_url, wallet).await?;
 
     // If using third-party payment, create a separate client for the payment wallet
     let payment_contract_client = if payment_wallet_name != wallet_name {
@@ -754,7 +998,7 @@ async fn handle_del_signer(
             })?
             .clone();
 
-        Some(create_contract_client_with_wallet(payment_key_manager).await?)
+        Some(create_contract_client_with_wallet(rpc_url, payment_key_manager).await?)
     } else {
         None
     };
@@ -879,19 +1123,8 @@ async fn handle_del_signer(
         println!("   • Using custody wallet for both authorization and gas payment");
     }
 
-    // Ask for user confirmation
-    print!("\n❓ Do you want to proceed with the on-chain removal? (yes/no): ");
-    use std::io::Write;
-    use std::io::{
-        self,
-    };
-    io::stdout().flush()?;
-
-    let mut confirmation = String::new();
-    io::stdin().read_line(&mut confirmation)?;
-    let confirmation = confirmation.trim().to_lowercase();
-
-    if confirmation != "yes" && confirmation != "y" {
+    // Ask for user confirmation (skip if --yes is provided)
+    if !confirm_onchain("Do you want to proceed with the on-chain removal?", yes)? {
         println!("❌ Operation cancelled by user");
         return Ok(());
     }
@@ -923,6 +1156,7 @@ async fn handle_del_signer(
         payment_client
             .key_registry
             .remove_for(
+                fid,
                 fid_info.custody,
                 public_key_bytes.clone(),
                 deadline,
@@ -934,6 +1168,7 @@ async fn handle_del_signer(
         contract_client
             .key_registry
             .remove_for(
+                fid,
                 fid_info.custody,
                 public_key_bytes.clone(),
                 deadline,
@@ -975,11 +1210,469 @@ async fn handle_del_signer(
         }
     }
 
+    if wait_hub {
+        wait_for_hub(
+            hub_client,
+            fid,
+            ExpectedEvent::SignerRemove(public_key_bytes),
+        )
+        .await?;
+    }
+
     Ok(())
 }
 
+/// Text of the canary message validated with the new key during rotation
+const ROTATION_CANARY_TEXT: &str = "castorix signer rotation canary";
+
+/// Replace the signer `old_key` of a FID with a freshly generated key
+///
+/// The new key is added and verified before anything irreversible happens;
+/// failures up to that point remove it again, see [`rollback_rotation`].
+async fn handle_rotate_signer(
+    hub_client: &FarcasterClient,
+    rpc_url: &str,
+    fid: u64,
+    old_key: Option<&str>,
+    canary: bool,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    use crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager;
+
+    policy::current().check_fid(fid)?;
+    println!("🔄 Rotating signer for FID: {fid}");
+
+    let keys_file = EncryptedEd25519KeyManager::default_keys_file()?;
+    let mut ed25519_manager = if std::path::Path::new(&keys_file).exists() {
+        EncryptedEd25519KeyManager::load_from_file(&keys_file)?
+    } else {
+        EncryptedEd25519KeyManager::new()
+    };
+    let old_key = match old_key {
+        Some(key) => key.trim_start_matches("0x").to_lowercase(),
+        None if ed25519_manager.has_key(fid) => ed25519_manager.get_public_key(fid)?,
+        None => anyhow::bail!(
+            "❌ No local signer for FID {fid}; give the key to replace with --old-key"
+        ),
+    };
+    let old_key_bytes =
+        hex::decode(&old_key).map_err(|_| anyhow::anyhow!("Invalid public key hex: {old_key}"))?;
+    if old_key_bytes.len() != 32 {
+        anyhow::bail!(
+            "❌ An Ed25519 public key is 32 bytes, got {}",
+            old_key_bytes.len()
+        );
+    }
+
+    let active = hub_client.get_signers(fid).await?;
+    if !active.iter().any(|signer| {
+        signer
+            .key
+            .trim_start_matches("0x")
+            .eq_ignore_ascii_case(&old_key)
+    }) {
+        anyhow::bail!(
+            "❌ 0x{old_key} is not an active signer of FID {fid}; see 'castorix signers info {fid}'"
+        );
+    }
+
+    println!("🔑 Old signer: 0x{old_key}");
+    println!("📋 Rotation plan:");
+    println!("   1. Generate a new Ed25519 key");
+    println!("   2. Register it on-chain through the KeyGateway");
+    println!("   3. Wait for the hub to index it");
+    if canary {
+        println!("   4. Have the hub validate a canary message signed with it");
+    }
+    println!("   {}. Store it locally for FID {fid}", 4 + canary as u8);
+    println!(
+        "   {}. Remove the old signer from the KeyRegistry",
+        5 + canary as u8
+    );
+    if dry_run {
+        println!("\n🧪 DRY-RUN MODE: No transactions were sent");
+        return Ok(());
+    }
+
+    println!("\n⚠️  ON-CHAIN OPERATION WARNING:");
+    println!("   • This sends two transactions and consumes gas fees");
+    println!("   • Messages signed with the old key are pruned by hubs once it is removed");
+    if !confirm_onchain("Do you want to rotate the signer?", yes)? {
+        println!("❌ Operation cancelled by user");
+        return Ok(());
+    }
+
+    // Ask for everything up front so that no prompt can fail between transactions
+    let contract_client = load_custody_contract_client(rpc_url, fid).await?;
+    let custody = contract_client
+        .wallet_address()
+        .ok_or_else(|| anyhow::anyhow!("No wallet address available"))?;
+    let password = crate::core::crypto::encrypted_storage::prompt_password(&format!(
+        "Enter password to encrypt the new Ed25519 key for FID {fid}: "
+    ))?;
+    let password_confirm = crate::core::crypto::encrypted_storage::prompt_password(&format!(
+        "Confirm password for the new Ed25519 key for FID {fid}: "
+    ))?;
+    if password != password_confirm {
+        anyhow::bail!("Passwords do not match. Please try again.");
+    }
+
+    let signing_key = crate::farcaster::contracts::key_utils::generate_ed25519_keypair();
+    let new_key = signing_key.verifying_key().to_bytes().to_vec();
+    println!("🔑 New signer: 0x{}", hex::encode(&new_key));
+
+    println!("⛓️  Registering the new signer on-chain...");
+    add_signer_key(&contract_client, fid, custody, &new_key).await?;
+    println!("✅ New signer registered");
+
+    if let Err(e) = wait_for_hub(hub_client, fid, ExpectedEvent::SignerAdd(new_key.clone())).await {
+        return Err(rollback_rotation(&contract_client, fid, custody, &new_key, e).await);
+    }
+
+    if canary {
+        println!("🐤 Validating a canary message signed with the new key...");
+        let validated =
+            crate::core::client::cast::build_cast_message(fid, ROTATION_CANARY_TEXT, &signing_key);
+        let validated = match validated {
+            Ok(message) => hub_client.validate_message(&message).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = validated {
+            return Err(rollback_rotation(&contract_client, fid, custody, &new_key, e).await);
+        }
+        println!("✅ Hub accepts messages signed with the new key");
+    }
+
+    println!("🔐 Storing the new Ed25519 key encrypted...");
+    if ed25519_manager.has_key(fid) {
+        ed25519_manager.remove_key(fid)?;
+    }
+    let stored = ed25519_manager
+        .import_signing_key(fid, &signing_key, &password)
+        .and_then(|()| ed25519_manager.save_to_file(&keys_file));
+    if let Err(e) = stored {
+        return Err(rollback_rotation(&contract_client, fid, custody, &new_key, e.into()).await);
+    }
+    println!("✅ New key stored for FID {fid}");
+
+    println!("⛓️  Removing the old signer on-chain...");
+    if let Err(e) = remove_signer_key(&contract_client, fid, custody, &old_key_bytes).await {
+        println!("❌ Failed to remove the old signer: {e:#}");
+        println!("⚠️  Both signers are active; the new key is stored locally");
+        println!("💡 Remove the old key later with 'castorix tx build signer-remove {fid} --key 0x{old_key}'");
+        return Err(e);
+    }
+    println!("✅ Old signer removed");
+    wait_for_hub(hub_client, fid, ExpectedEvent::SignerRemove(old_key_bytes)).await?;
+
+    println!("🎉 Rotated FID {fid} to signer 0x{}", hex::encode(&new_key));
+    Ok(())
+}
+
+/// Undo a rotation by removing the new key again
+///
+/// # Returns
+/// * `anyhow::Error` - `error` with the outcome of the rollback attached
+async fn rollback_rotation(
+    contract_client: &crate::farcaster::contracts::contract_client::FarcasterContractClient,
+    fid: u64,
+    custody: ethers::types::Address,
+    new_key: &[u8],
+    error: anyhow::Error,
+) -> anyhow::Error {
+    println!("❌ Rotation failed: {error:#}");
+    println!("↩️  Rolling back: removing the new signer...");
+    match remove_signer_key(contract_client, fid, custody, new_key).await {
+        Ok(()) => {
+            println!("✅ Rolled back; the old signer is unchanged");
+            error.context("Signer rotation failed and was rolled back")
+        }
+        Err(rollback_error) => {
+            println!("❌ Rollback failed: {rollback_error:#}");
+            println!(
+                "⚠️  The new signer 0x{} is still registered and not stored locally",
+                hex::encode(new_key)
+            );
+            error.context(format!(
+                "Signer rotation failed and could not be rolled back: {rollback_error:#}"
+            ))
+        }
+    }
+}
+
+/// Register an Ed25519 key through KeyGateway.addFor, paid by the custody wallet
+async fn add_signer_key(
+    contract_client: &crate::farcaster::contracts::contract_client::FarcasterContractClient,
+    fid: u64,
+    custody: ethers::types::Address,
+    public_key: &[u8],
+) -> Result<()> {
+    let deadline = std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)?
+        .as_secs()
+        + 3600;
+    let signed_key_request_signature = contract_client
+        .create_signed_key_request_signature(fid, custody, public_key, deadline)
+        .await?;
+    let metadata = contract_client
+        .create_signed_key_request_metadata(
+            fid,
+            custody,
+            public_key,
+            deadline,
+            signed_key_request_signature,
+        )
+        .await?;
+    let add_for_signature = contract_client
+        .create_add_for_signature(custody, 1, public_key, 1, &metadata, deadline)
+        .await?;
+    match contract_client
+        .key_gateway
+        .add_for(
+            fid,
+            custody,
+            1,
+            public_key.to_vec(),
+            1,
+            metadata,
+            deadline.into(),
+            add_for_signature,
+        )
+        .await?
+    {
+        ContractResult::Success(receipt) => {
+            println!("🔗 Transaction Hash: {:?}", receipt.transaction_hash);
+            Ok(())
+        }
+        ContractResult::Error(e) => Err(anyhow::anyhow!("Failed to register signer: {e}")),
+    }
+}
+
+/// Remove a key through KeyRegistry.removeFor, paid by the custody wallet
+async fn remove_signer_key(
+    contract_client: &crate::farcaster::contracts::contract_client::FarcasterContractClient,
+    fid: u64,
+    custody: ethers::types::Address,
+    public_key: &[u8],
+) -> Result<()> {
+    let deadline = std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)?
+        .as_secs()
+        + 3600;
+    let signature =
+        create_signer_remove_signature(contract_client, custody, public_key, deadline).await?;
+    match contract_client
+        .key_registry
+        .remove_for(fid, custody, public_key.to_vec(), deadline, signature)
+        .await?
+    {
+        ContractResult::Success(receipt) => {
+            println!("🔗 Transaction Hash: {:?}", receipt.transaction_hash);
+            Ok(())
+        }
+        ContractResult::Error(e) => Err(anyhow::anyhow!("Failed to remove signer: {e}")),
+    }
+}
+
+/// Poll the hub until it has indexed an onchain signer change and report the latency
+async fn wait_for_hub(
+    hub_client: &FarcasterClient,
+    fid: u64,
+    expected: ExpectedEvent,
+) -> Result<()> {
+    println!("⏳ Waiting for the hub to index the signer change...");
+    let propagation = hub_client
+        .wait_for_onchain_event(fid, &expected, DEFAULT_HUB_WAIT_TIMEOUT)
+        .await?;
+    println!("✅ Hub indexed the signer change {}", propagation.summary());
+    Ok(())
+}
+
+/// Load the custody wallet of a FID and check it still owns the FID
+async fn load_custody_contract_client(
+    rpc_url: &str,
+    fid: u64,
+) -> Result<crate::farcaster::contracts::contract_client::FarcasterContractClient> {
+    let custody_key_file =
+        crate::core::crypto::encrypted_storage::EncryptedEthKeyManager::custody_key_file(fid)?;
+    if !std::path::Path::new(&custody_key_file).exists() {
+        return Err(anyhow::anyhow!(
+            "❌ No custody key found for FID {fid}. Please create one first using:\n   castorix custody import {fid}\n   or\n   castorix custody from-mnemonic {fid}"
+        ));
+    }
+    let encrypted_manager =
+        crate::core::crypto::encrypted_storage::EncryptedEthKeyManager::load_from_file(
+            &custody_key_file,
+        )?;
+    let password = crate::core::crypto::encrypted_storage::prompt_password(&format!(
+        "Enter password for custody wallet (FID {fid}): "
+    ))?;
+    let wallet = encrypted_manager
+        .get_wallet(fid, &password)
+        .map_err(|e| anyhow::anyhow!("Failed to load wallet for FID {}: {}", fid, e))?;
+    let contract_client = create_contract_client_with_local_wallet(rpc_url, wallet).await?;
+
+    let fid_info = contract_client.get_fid_info(fid).await?;
+    if contract_client.wallet_address() != Some(fid_info.custody) {
+        return Err(anyhow::anyhow!(
+            "❌ Stored custody wallet is not the custody address {} of FID {}",
+            fid_info.custody,
+            fid
+        ));
+    }
+    Ok(contract_client)
+}
+
+/// Ask for a yes/no confirmation unless `yes` was given
+fn confirm_onchain(prompt: &str, yes: bool) -> Result<bool> {
+    policy::current().check_confirmation(yes)?;
+    if yes {
+        return Ok(true);
+    }
+    prompt::ask(
+        InputKind::Confirmation,
+        &format!("\n❓ {prompt} (yes/no): "),
+    )?;
+    let mut confirmation = String::new();
+    std::io::stdin().read_line(&mut confirmation)?;
+    let confirmation = confirmation.trim().to_lowercase();
+    Ok(confirmation == "yes" || confirmation == "y")
+}
+
+async fn handle_add_auth_address(
+    rpc_url: &str,
+    fid: u64,
+    address: &str,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    let auth_address: ethers::types::Address = address
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid Ethereum address: {address}"))?;
+    policy::current().check_fid(fid)?;
+    println!("🪪 Adding auth address {auth_address:?} to FID {fid}");
+
+    let contract_client = load_custody_contract_client(rpc_url, fid).await?;
+    println!("✅ Custody wallet authorized to manage FID {fid}");
+
+    if !contract_client.auth_address_supported().await? {
+        return Err(anyhow::anyhow!(
+            "❌ The KeyRegistry has no validator for auth address keys (key type {})",
+            crate::farcaster::contracts::auth_address::AUTH_ADDRESS_KEY_TYPE
+        ));
+    }
+    if contract_client.is_auth_address(fid, auth_address).await? {
+        println!("ℹ️  {auth_address:?} is already an auth address of FID {fid}");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("\n🧪 DRY-RUN MODE: KeyRegistry accepts auth addresses and FID {fid} is managed by this wallet");
+        println!("   • Would call KeyGateway.addFor with key type 2 and key {auth_address:?}");
+        return Ok(());
+    }
+
+    println!("\n⚠️  ON-CHAIN OPERATION WARNING:");
+    println!("   • {auth_address:?} will be able to sign messages for FID {fid}");
+    println!("   • The operation will consume gas fees");
+    if !confirm_onchain("Do you want to add this auth address?", yes)? {
+        println!("❌ Operation cancelled by user");
+        return Ok(());
+    }
+
+    match contract_client.add_auth_address(fid, auth_address).await? {
+        ContractResult::Success(()) => {
+            println!("✅ Auth address {auth_address:?} added to FID {fid}");
+            Ok(())
+        }
+        ContractResult::Error(e) => Err(anyhow::anyhow!("❌ Failed to add auth address: {e}")),
+    }
+}
+
+async fn handle_remove_auth_address(
+    rpc_url: &str,
+    fid: u64,
+    address: &str,
+    payment_wallet_name: Option<&str>,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    let auth_address: ethers::types::Address = address
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid Ethereum address: {address}"))?;
+    policy::current().check_fid(fid)?;
+    println!("🚫 Removing auth address {auth_address:?} from FID {fid}");
+
+    let contract_client = load_custody_contract_client(rpc_url, fid).await?;
+    let custody = contract_client
+        .wallet_address()
+        .ok_or_else(|| anyhow::anyhow!("No wallet address available"))?;
+
+    if !contract_client.is_auth_address(fid, auth_address).await? {
+        return Err(anyhow::anyhow!(
+            "❌ {auth_address:?} is not an active auth address of FID {fid}"
+        ));
+    }
+
+    if dry_run {
+        let deadline = std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)?
+            .as_secs()
+            + 3600;
+        let signature = contract_client
+            .create_remove_for_signature(custody, auth_address.as_bytes(), deadline)
+            .await?;
+        println!("\n🧪 DRY-RUN MODE: removeFor authorization signed");
+        println!("   • Owner: {custody:?}");
+        println!("   • Deadline: {deadline}");
+        println!("   • Signature: 0x{}", hex::encode(signature));
+        return Ok(());
+    }
+
+    let payment_client = match payment_wallet_name {
+        Some(name) => {
+            let mut manager = crate::encrypted_key_manager::EncryptedKeyManager::default_config();
+            let password = crate::encrypted_key_manager::prompt_password(&format!(
+                "Enter password for payment wallet '{name}': "
+            ))?;
+            manager.load_and_decrypt(&password, name).await?;
+            let key_manager = manager
+                .key_manager()
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Failed to load key manager for payment wallet: {}", name)
+                })?
+                .clone();
+            Some(create_contract_client_with_wallet(rpc_url, key_manager).await?)
+        }
+        None => None,
+    };
+
+    println!("\n⚠️  ON-CHAIN OPERATION WARNING:");
+    println!("   • {auth_address:?} will no longer be able to sign for FID {fid}");
+    println!("   • Messages it signed will be revoked by hubs");
+    println!("   • The operation will consume gas fees");
+    if !confirm_onchain("Do you want to remove this auth address?", yes)? {
+        println!("❌ Operation cancelled by user");
+        return Ok(());
+    }
+
+    match contract_client
+        .remove_auth_address(fid, custody, auth_address, payment_client.as_ref())
+        .await?
+    {
+        ContractResult::Success(receipt) => {
+            println!("✅ Auth address removed from FID {fid}");
+            println!("🔗 Transaction Hash: {:?}", receipt.transaction_hash);
+            Ok(())
+        }
+        ContractResult::Error(e) => Err(anyhow::anyhow!("❌ Failed to remove auth address: {e}")),
+    }
+}
+
 /// Create a FarcasterContractClient with the specified wallet
 async fn create_contract_client_with_wallet(
+    rpc_url: &str,
     key_manager: crate::core::crypto::key_manager::KeyManager,
 ) -> Result<crate::farcaster::contracts::contract_client::FarcasterContractClient> {
     // Get the wallet from the key manager
@@ -988,12 +1681,8 @@ async fn create_contract_client_with_wallet(
     // Use contract addresses from the default implementation (Optimism mainnet)
     let addresses = crate::farcaster::contracts::types::ContractAddresses::default();
 
-    // Use RPC URL from configuration (supports environment variables)
-    let config = crate::consts::get_config();
-    let rpc_url = config.eth_op_rpc_url().to_string();
-
     crate::farcaster::contracts::contract_client::FarcasterContractClient::new_with_wallet(
-        rpc_url,
+        rpc_url.to_string(),
         addresses,
         wallet.clone(),
     )
@@ -1002,17 +1691,14 @@ async fn create_contract_client_with_wallet(
 
 /// Create a FarcasterContractClient with a LocalWallet directly
 async fn create_contract_client_with_local_wallet(
+    rpc_url: &str,
     wallet: ethers::signers::LocalWallet,
 ) -> Result<crate::farcaster::contracts::contract_client::FarcasterContractClient> {
     // Use contract addresses from the default implementation (Optimism mainnet)
     let addresses = crate::farcaster::contracts::types::ContractAddresses::default();
 
-    // Use RPC URL from configuration (supports environment variables)
-    let config = crate::consts::get_config();
-    let rpc_url = config.eth_op_rpc_url().to_string();
-
     crate::farcaster::contracts::contract_client::FarcasterContractClient::new_with_wallet(
-        rpc_url,
+        rpc_url.to_string(),
         addresses,
         wallet.clone(),
     )
@@ -1045,7 +1731,13 @@ async fn create_signer_add_signature(
     let key_gateway_address = contract_client.addresses.key_gateway;
 
     // Create the EIP-712 typed data structure
+    let domain = contract_client
+        .adapters()
+        .await?
+        .key_gateway
+        .eip712_domain();
     let typed_data = create_add_typed_data(
+        domain,
         fid_owner,
         1u32, // Ed25519 key type
         public_key,
@@ -1059,6 +1751,7 @@ async fn create_signer_add_signature(
 
     // Sign the typed data using EIP-712
     let signature = wallet.sign_typed_data(&typed_data).await?;
+    audit::record_typed_data(wallet.address(), &typed_data);
 
     // Return the signature as bytes
     Ok(signature.to_vec())
@@ -1067,6 +1760,7 @@ async fn create_signer_add_signature(
 /// Create EIP-712 typed data for Add operation
 #[allow(clippy::too_many_arguments)]
 fn create_add_typed_data(
+    key_gateway_domain: Eip712Domain,
     fid_owner: ethers::types::Address,
     key_type: u32,
     key: &[u8],
@@ -1083,10 +1777,10 @@ fn create_add_typed_data(
     use ethers::types::transaction::eip712::Eip712DomainType;
     use ethers::types::transaction::eip712::TypedData;
 
-    // Domain separator for Farcaster KeyGateway
+    // Domain separator of the deployed KeyGateway version
     let domain = EIP712Domain {
-        name: Some("Farcaster KeyGateway".to_string()),
-        version: Some("1".to_string()),
+        name: Some(key_gateway_domain.name.to_string()),
+        version: Some(key_gateway_domain.version.to_string()),
         chain_id: Some(ethers::types::U256::from(chain_id)),
         verifying_contract: Some(key_gateway_address),
         salt: None,
@@ -1212,6 +1906,11 @@ async fn create_signer_remove_signature(
 
     // Sign the message
     let signature = wallet.sign_message(message.as_bytes()).await?;
+    audit::record_eth_signature(
+        wallet.address(),
+        "personal_sign",
+        hash_message(message.as_bytes()),
+    );
 
     // Return the signature as bytes
     Ok(signature.to_vec())
@@ -1345,15 +2044,12 @@ fn decrypt_legacy_key(
     Ok(plaintext)
 }
 
-async fn handle_signers_import(fid: u64) -> Result<()> {
+async fn handle_signers_import(fid: u64, conflict: ImportConflictArgs<'_>) -> Result<()> {
     println!("📥 Importing Ed25519 signer key for FID: {fid}");
 
     // Check for legacy Ed25519 key first
-    let legacy_key_path = dirs::home_dir()
-        .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
-        .join(".castorix")
-        .join("keys")
-        .join(format!("fid-{}-ed25519.json", fid));
+    let legacy_key_path =
+        crate::core::utils::data_dir::keys_dir().join(format!("fid-{}-ed25519.json", fid));
 
     let private_key = if legacy_key_path.exists() {
         println!("🔍 Found legacy Ed25519 key for FID: {fid}");
@@ -1410,29 +2106,9 @@ async fn handle_signers_import(fid: u64) -> Result<()> {
             )?;
     }
 
-    // Check if key already exists for this FID
-    if encrypted_manager.has_key(fid) {
-        println!("⚠️  Ed25519 key already exists for FID: {fid}");
-
-        print!("\nDo you want to replace the existing key? (y/N): ");
-        use std::io::Write;
-        use std::io::{
-            self,
-        };
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        let response = input.trim().to_lowercase();
-
-        if response != "y" && response != "yes" {
-            println!("❌ Operation cancelled. Existing key will not be replaced.");
-            return Ok(());
-        }
-
-        // Remove existing key
-        encrypted_manager.remove_key(fid)?;
-        println!("🗑️  Removed existing Ed25519 key");
+    // Make room if a key already exists for this FID
+    if !resolve_signer_conflict(&mut encrypted_manager, fid, conflict, &ed25519_keys_file)? {
+        return Ok(());
     }
 
     // Import and encrypt the key
@@ -1462,7 +2138,154 @@ async fn handle_signers_import(fid: u64) -> Result<()> {
     Ok(())
 }
 
-async fn handle_signers_list() -> Result<()> {
+/// Import a signer from a recovery phrase or another client's key export
+async fn handle_signers_import_external(
+    hub_client: &FarcasterClient,
+    fid: u64,
+    format: &str,
+    file: Option<&str>,
+    path: &str,
+    conflict: ImportConflictArgs<'_>,
+) -> Result<()> {
+    use crate::core::crypto::key_import;
+
+    println!("📥 Importing Ed25519 signer key for FID: {fid} ({format})");
+    let signing_key = match (format, file) {
+        ("mnemonic", None) => {
+            let phrase = crate::encrypted_key_manager::prompt_password("Enter recovery phrase: ")?;
+            let passphrase = crate::encrypted_key_manager::prompt_password(
+                "Enter BIP39 passphrase (leave empty if none): ",
+            )?;
+            println!("🌱 Deriving signer at {path}");
+            key_import::signing_key_from_mnemonic(&phrase, &passphrase, path)?
+        }
+        ("mnemonic", Some(file)) => {
+            let phrase =
+                std::fs::read_to_string(file).with_context(|| format!("Failed to read {file}"))?;
+            println!("🌱 Deriving signer at {path}");
+            key_import::signing_key_from_mnemonic(&phrase, "", path)?
+        }
+        ("protobuf", Some(file)) => key_import::signing_key_from_protobuf(
+            &std::fs::read(file).with_context(|| format!("Failed to read {file}"))?,
+        )?,
+        ("json", Some(file)) => key_import::signing_key_from_json(
+            &std::fs::read_to_string(file).with_context(|| format!("Failed to read {file}"))?,
+        )?,
+        ("hex", Some(file)) => key_import::signing_key_from_text(
+            &std::fs::read_to_string(file).with_context(|| format!("Failed to read {file}"))?,
+        )?,
+        (_, None) => {
+            let export = crate::encrypted_key_manager::prompt_password(&format!(
+                "Paste the {format} key export (hex or base64): "
+            ))?;
+            if format == "json" {
+                key_import::signing_key_from_json(&export)?
+            } else {
+                key_import::signing_key_from_protobuf(export.as_bytes())?
+            }
+        }
+        (format, Some(_)) => anyhow::bail!("Unsupported key format: {format}"),
+    };
+    let public_key = hex::encode(signing_key.verifying_key().to_bytes());
+    println!("🔑 Public Key: 0x{public_key}");
+
+    match hub_client.get_signers(fid).await {
+        Ok(signers) => {
+            let registered = signers.iter().any(|s| {
+                s.key
+                    .trim_start_matches("0x")
+                    .eq_ignore_ascii_case(&public_key)
+            });
+            if registered {
+                println!(
+                    "✅ Key is an active signer of FID {fid}; no on-chain registration needed"
+                );
+            } else {
+                println!("⚠️  Key is not an active signer of FID {fid} on the hub");
+                println!("   Check the format and derivation path, or register it with 'castorix signers register'");
+            }
+        }
+        Err(e) => println!("⚠️  Could not check the FID's signers on the hub: {e}"),
+    }
+
+    let ed25519_keys_file =
+        crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager::default_keys_file()?;
+    let mut encrypted_manager =
+        crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager::load_from_file(
+            &ed25519_keys_file,
+        )?;
+    if !resolve_signer_conflict(&mut encrypted_manager, fid, conflict, &ed25519_keys_file)? {
+        return Ok(());
+    }
+
+    let password = crate::core::crypto::encrypted_storage::prompt_password(
+        "Enter password to encrypt the key: ",
+    )?;
+    encrypted_manager.import_signing_key(fid, &signing_key, &password)?;
+    encrypted_manager.save_to_file(&ed25519_keys_file)?;
+    println!("✅ Ed25519 signer key imported and encrypted successfully!");
+    println!("📝 You can now use this signer for Farcaster operations with FID {fid}");
+    Ok(())
+}
+
+/// Apply `--on-conflict` when the FID already has a local key, asking if it was not given
+///
+/// # Returns
+/// * `Result<bool>` - Whether the import can go ahead
+fn resolve_signer_conflict(
+    manager: &mut EncryptedEd25519KeyManager,
+    fid: u64,
+    (on_conflict, label): ImportConflictArgs<'_>,
+    keys_file: &str,
+) -> Result<bool> {
+    if !manager.has_key(fid) {
+        return Ok(true);
+    }
+    println!("⚠️  Ed25519 key already exists for FID: {fid}");
+    let conflict = match on_conflict {
+        Some(conflict) => conflict,
+        None => {
+            prompt::ask(
+                InputKind::Text,
+                "\n❓ [a]bort, [o]verwrite after a backup, or [k]eep both? (a/o/k): ",
+            )?;
+            let mut input = String::new();
+            std::io::stdin().read_line(&mut input)?;
+            match input.trim().to_lowercase().as_str() {
+                "o" | "overwrite" => ImportConflict::Overwrite,
+                "k" | "keep-both" => ImportConflict::KeepBoth,
+                _ => {
+                    println!("❌ Operation cancelled. Existing key will not be replaced.");
+                    return Ok(false);
+                }
+            }
+        }
+    };
+    check_conflict_label(Some(conflict), label)?;
+    match manager.resolve_import_conflict(fid, conflict, label, keys_file)? {
+        Some(ConflictResolution::BackedUp(backup)) => {
+            println!("💾 Backed up the keys file to {}", backup.display());
+            println!("🗑️  Replacing the existing Ed25519 key");
+        }
+        Some(ConflictResolution::Labeled(label)) => {
+            println!("🏷️  Keeping the existing key as '{label}'");
+        }
+        None => println!("🗑️  Replacing the existing Ed25519 key"),
+    }
+    Ok(true)
+}
+
+/// Refuse `--label` with a conflict strategy that doesn't keep the current key
+fn check_conflict_label(conflict: Option<ImportConflict>, label: Option<&str>) -> Result<()> {
+    match (conflict, label) {
+        (Some(conflict), Some(_)) if conflict != ImportConflict::KeepBoth => {
+            anyhow::bail!("--label only applies with --on-conflict keep-both")
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn handle_signers_list(hub_client: &FarcasterClient) -> Result<()> {
     println!("📋 All Local Ed25519 Signer Keys");
     println!("{}", "=".repeat(50));
 
@@ -1475,10 +2298,7 @@ async fn handle_signers_list() -> Result<()> {
         )?;
 
     // Check for legacy Ed25519 keys in the old format and migrate them
-    let legacy_keys_dir = dirs::home_dir()
-        .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
-        .join(".castorix")
-        .join("keys");
+    let legacy_keys_dir = crate::core::utils::data_dir::keys_dir();
 
     if legacy_keys_dir.exists() {
         println!("🔍 Checking for legacy Ed25519 keys...");
@@ -1553,9 +2373,6 @@ async fn handle_signers_list() -> Result<()> {
                 let mut index = 1;
                 for (fid, keys) in fid_groups {
                     // Check if this FID has registered signers on-chain
-                    // Try local hub first, fallback to Neynar if not available
-                    let hub_url = crate::consts::get_config().farcaster_hub_url().to_string();
-                    let hub_client = FarcasterClient::new(hub_url, None);
                     let registered_status = match hub_client.get_signers(fid).await {
                         Ok(signers) => {
                             if signers.is_empty() {
@@ -1599,9 +2416,18 @@ async fn handle_signers_list() -> Result<()> {
                             chrono::DateTime::from_timestamp(info.created_at as i64, 0)
                                 .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
                                 .unwrap_or_else(|| "Unknown".to_string());
+                        let label = info
+                            .label
+                            .map(|label| format!(" (kept as '{label}')"))
+                            .unwrap_or_default();
                         println!(
-                            "{:<4} {:<8} {:<66} {:<20} {}",
-                            index, info.fid, info.public_key, created_date, registered_status
+                            "{:<4} {:<8} {:<66} {:<20} {}{}",
+                            index,
+                            info.fid,
+                            info.public_key,
+                            created_date,
+                            registered_status,
+                            label
                         );
                         index += 1;
                     }
@@ -1659,7 +2485,7 @@ async fn handle_signers_export(identifier: &str) -> Result<()> {
     }
 
     // Try to parse as index number first
-    let (fid, public_key) = if let Ok(index) = identifier.parse::<usize>() {
+    let (fid, public_key, label) = if let Ok(index) = identifier.parse::<usize>() {
         if index < 1 || index > all_keys.len() {
             println!(
                 "❌ Invalid index number: {}. Available range: 1-{}",
@@ -1671,7 +2497,11 @@ async fn handle_signers_export(identifier: &str) -> Result<()> {
         }
 
         let key_info = &all_keys[index - 1];
-        (key_info.fid, key_info.public_key.clone())
+        (
+            key_info.fid,
+            key_info.public_key.clone(),
+            key_info.label.clone(),
+        )
     } else {
         // Try to parse as public key
         let clean_pubkey = identifier.trim_start_matches("0x");
@@ -1689,7 +2519,11 @@ async fn handle_signers_export(identifier: &str) -> Result<()> {
         });
 
         match matching_key {
-            Some(key_info) => (key_info.fid, key_info.public_key.clone()),
+            Some(key_info) => (
+                key_info.fid,
+                key_info.public_key.clone(),
+                key_info.label.clone(),
+            ),
             None => {
                 println!(
                     "❌ No local Ed25519 key found with public key: {}",
@@ -1710,7 +2544,11 @@ async fn handle_signers_export(identifier: &str) -> Result<()> {
     ))?;
 
     // Get the private key
-    match ed25519_manager.get_signing_key(fid, &password) {
+    let signing_key = match &label {
+        Some(label) => ed25519_manager.get_labeled_signing_key(fid, label, &password),
+        None => ed25519_manager.get_signing_key(fid, &password),
+    };
+    match signing_key {
         Ok(signing_key) => {
             let private_key_hex = hex::encode(signing_key.to_bytes());
 
@@ -1739,7 +2577,7 @@ async fn handle_signers_export(identifier: &str) -> Result<()> {
     Ok(())
 }
 
-async fn handle_signers_delete(identifier: &str) -> Result<()> {
+async fn handle_signers_delete(rpc_url: &str, identifier: &str, force: bool) -> Result<()> {
     println!("🗑️  Deleting local Ed25519 signer key...");
     println!("🔍 Identifier: {}", identifier);
 
@@ -1755,7 +2593,7 @@ async fn handle_signers_delete(identifier: &str) -> Result<()> {
     let all_keys = ed25519_manager.list_keys_with_info("")?;
 
     // Check if identifier is a number (index) or a public key
-    let (fid, public_key) = if let Ok(index) = identifier.parse::<usize>() {
+    let (fid, public_key, label) = if let Ok(index) = identifier.parse::<usize>() {
         // Index-based deletion
         if index < 1 || index > all_keys.len() {
             println!(
@@ -1772,7 +2610,11 @@ async fn handle_signers_delete(identifier: &str) -> Result<()> {
             "🔑 Selected key by index {}: {}",
             index, key_info.public_key
         );
-        (key_info.fid, key_info.public_key.clone())
+        (
+            key_info.fid,
+            key_info.public_key.clone(),
+            key_info.label.clone(),
+        )
     } else {
         // Public key-based deletion
         let clean_pubkey = identifier.trim_start_matches("0x");
@@ -1790,7 +2632,11 @@ async fn handle_signers_delete(identifier: &str) -> Result<()> {
         match matching_key {
             Some(key_info) => {
                 println!("🔑 Selected key by public key: {}", key_info.public_key);
-                (key_info.fid, key_info.public_key.clone())
+                (
+                    key_info.fid,
+                    key_info.public_key.clone(),
+                    key_info.label.clone(),
+                )
             }
             None => {
                 println!(
@@ -1805,6 +2651,17 @@ async fn handle_signers_delete(identifier: &str) -> Result<()> {
 
     println!("✅ Found matching key for FID: {}", fid);
 
+    let check =
+        crate::cli::handlers::key_handlers::safety::check_signer_key(rpc_url, fid, &public_key)
+            .await;
+    if !crate::cli::handlers::key_handlers::safety::allow_deletion(&check, force) {
+        println!(
+            "💡 Use 'castorix signers unregister {}' to remove it from chain first",
+            fid
+        );
+        return Ok(());
+    }
+
     // ⚠️  IMPORTANT: Confirmation with backup warning
     println!("\n⚠️  PERMANENT DELETION WARNING:");
     println!("   • This will permanently delete the Ed25519 private key from local storage");
@@ -1815,12 +2672,13 @@ async fn handle_signers_delete(identifier: &str) -> Result<()> {
     println!("   • FID: {}", fid);
 
     // Ask for confirmation with backup verification
-    print!("\n❓ Have you backed up this private key? (yes/no): ");
-    use std::io::Write;
+    prompt::ask(
+        InputKind::Confirmation,
+        "\n❓ Have you backed up this private key? (yes/no): ",
+    )?;
     use std::io::{
         self,
     };
-    io::stdout().flush()?;
 
     let mut backup_confirmation = String::new();
     io::stdin().read_line(&mut backup_confirmation)?;
@@ -1836,8 +2694,10 @@ async fn handle_signers_delete(identifier: &str) -> Result<()> {
     }
 
     // Final confirmation
-    print!("\n❓ Are you absolutely sure you want to permanently delete this key? (yes/no): ");
-    io::stdout().flush()?;
+    prompt::ask(
+        InputKind::Confirmation,
+        "\n❓ Are you absolutely sure you want to permanently delete this key? (yes/no): ",
+    )?;
 
     let mut final_confirmation = String::new();
     io::stdin().read_line(&mut final_confirmation)?;
@@ -1851,7 +2711,11 @@ async fn handle_signers_delete(identifier: &str) -> Result<()> {
     // Delete the key
     println!("🗑️  Deleting Ed25519 key for FID {}...", fid);
 
-    match ed25519_manager.remove_key(fid) {
+    let removed = match &label {
+        Some(label) => ed25519_manager.remove_labeled_key(fid, label),
+        None => ed25519_manager.remove_key(fid),
+    };
+    match removed {
         Ok(_) => {
             // Save the changes to file
             if let Err(e) = ed25519_manager.save_to_file(&ed25519_keys_file) {
@@ -2,23 +2,36 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use ethers::middleware::Middleware;
-use ethers::providers::Http;
-use ethers::providers::Provider;
 use ethers::signers::LocalWallet;
 use ethers::signers::Signer;
+use ethers::types::U256;
 use ethers::utils::format_ether;
+use ethers::utils::format_units;
 
+use crate::cli::endpoints::EndpointOverrides;
+use crate::cli::output;
 use crate::cli::types::StorageCommands;
+use crate::core::policy;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
 use crate::encrypted_key_manager::prompt_password;
 use crate::encrypted_key_manager::EncryptedKeyManager;
 use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+use crate::farcaster::contracts::sponsored::SponsorConfig;
 use crate::farcaster::contracts::types::ContractAddresses;
 use crate::farcaster::contracts::types::ContractResult;
+use crate::farcaster::indexer::EventKind;
+use crate::farcaster::indexer::EventStore;
+use crate::farcaster::indexer::DEFAULT_START_BLOCK;
+
+/// Decimals of the StorageRegistry's USD prices (Chainlink feed precision)
+const USD_PRICE_DECIMALS: u32 = 8;
 
 /// Handle storage rental and management commands
 pub async fn handle_storage_command(
     command: StorageCommands,
     storage_path: Option<&str>,
+    endpoints: &EndpointOverrides,
 ) -> Result<()> {
     match command {
         StorageCommands::Rent {
@@ -26,14 +39,22 @@ pub async fn handle_storage_command(
             units,
             wallet,
             payment_wallet,
+            sponsored,
+            bundler_url,
+            paymaster_url,
             dry_run,
             yes,
         } => {
+            let sponsor = sponsored
+                .then(|| SponsorConfig::resolve(bundler_url, paymaster_url))
+                .transpose()?;
             handle_storage_rent(
+                endpoints.op_rpc_url(),
                 fid,
                 units,
                 wallet,
                 payment_wallet,
+                sponsor,
                 dry_run,
                 yes,
                 storage_path,
@@ -41,20 +62,29 @@ pub async fn handle_storage_command(
             .await?;
         }
         StorageCommands::Price { fid, units } => {
-            handle_storage_price(fid, units).await?;
+            handle_storage_price(endpoints.op_rpc_url(), fid, units).await?;
+        }
+        StorageCommands::Info { fid, from_block } => {
+            handle_storage_info(endpoints.op_rpc_url(), fid, from_block).await?;
+        }
+        StorageCommands::PriceHistory { from_block, json } => {
+            handle_storage_price_history(endpoints.op_rpc_url(), from_block, json).await?;
         }
         StorageCommands::Usage { fid } => {
-            handle_storage_usage(fid).await?;
+            handle_storage_usage(endpoints.op_rpc_url(), fid, &endpoints.hub_url()).await?;
         }
     }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_storage_rent(
+    rpc_url: String,
     fid: u64,
     units: u32,
     wallet_name: Option<String>,
     payment_wallet_name: Option<String>,
+    sponsor: Option<SponsorConfig>,
     dry_run: bool,
     yes: bool,
     storage_path: Option<&str>,
@@ -62,10 +92,6 @@ async fn handle_storage_rent(
     println!("🏠 Rent Storage Units for FID {fid}");
     println!("{}", "=".repeat(40));
 
-    // Get RPC URL from configuration (Farcaster contracts are on Optimism)
-    let config = crate::consts::get_config();
-    let rpc_url = config.eth_op_rpc_url().to_string();
-
     // Check if using placeholder values
     if rpc_url.contains("your_api_key_here") || rpc_url == "https://mainnet.optimism.io" {
         println!("⚠️  Configuration Warning:");
@@ -204,10 +230,27 @@ async fn handle_storage_rent(
     let price = contract_client.get_storage_price(units as u64).await?;
     println!("   Storage Rental Price: {} ETH", format_ether(price));
 
-    // Check payment wallet balance
-    let provider = Provider::<Http>::try_from(&rpc_url)?;
-    let balance = provider.get_balance(payment_wallet.address(), None).await?;
-    println!("   Payment Wallet Balance: {} ETH", format_ether(balance));
+    let sponsored_sender = match &sponsor {
+        Some(sponsor) => {
+            let sender = sponsor.sender(&rpc_url, custody_wallet.clone())?;
+            println!("\n⛽ Sponsored mode (ERC-4337)");
+            println!("   Bundler: {}", sponsor.bundler_url);
+            println!("   Paymaster: {}", sponsor.paymaster_url);
+            println!("   Smart Account: {:?}", sender.account_address().await?);
+            println!(
+                "   Smart Account Balance: {} ETH (pays the rent)",
+                format_ether(sender.account_balance().await?)
+            );
+            Some(sender)
+        }
+        None => {
+            // Check payment wallet balance
+            let provider = crate::core::utils::http::provider(&rpc_url)?;
+            let balance = provider.get_balance(payment_wallet.address(), None).await?;
+            println!("   Payment Wallet Balance: {} ETH", format_ether(balance));
+            None
+        }
+    };
 
     if dry_run {
         println!("\n🔍 DRY RUN MODE - No transaction will be sent");
@@ -238,13 +281,15 @@ async fn handle_storage_rent(
     println!("   • Make sure you have sufficient ETH for gas and storage rental");
 
     // Ask for user confirmation (skip if --yes is provided)
+    policy::current().check_confirmation(yes)?;
     if !yes {
-        print!("\n❓ Do you want to proceed with storage rental? (yes/no): ");
-        use std::io::Write;
+        prompt::ask(
+            InputKind::Confirmation,
+            "\n❓ Do you want to proceed with storage rental? (yes/no): ",
+        )?;
         use std::io::{
             self,
         };
-        io::stdout().flush()?;
 
         let mut confirmation = String::new();
         io::stdin().read_line(&mut confirmation)?;
@@ -260,6 +305,25 @@ async fn handle_storage_rent(
 
     println!("✅ Proceeding with storage rental...");
 
+    if let Some(sender) = &sponsored_sender {
+        println!("💳 Renting through the bundler with sponsored gas");
+        return match contract_client
+            .rent_storage_sponsored(sender, fid, units as u64)
+            .await?
+        {
+            ContractResult::Success(receipt) => {
+                println!("✅ Storage rental successful!");
+                println!("   UserOperation: {:?}", receipt.user_op_hash);
+                println!("   Transaction Hash: {:?}", receipt.transaction_hash);
+                Ok(())
+            }
+            ContractResult::Error(e) => {
+                println!("❌ Storage rental failed: {}", e);
+                Err(anyhow::anyhow!("Storage rental failed: {}", e))
+            }
+        };
+    }
+
     // Rent storage
     let result = if payment_wallet.address() != custody_wallet.address() {
         println!(
@@ -291,14 +355,10 @@ async fn handle_storage_rent(
     Ok(())
 }
 
-async fn handle_storage_price(fid: u64, units: u32) -> Result<()> {
+async fn handle_storage_price(rpc_url: String, fid: u64, units: u32) -> Result<()> {
     println!("💰 Storage Rental Price for FID {fid}");
     println!("{}", "=".repeat(40));
 
-    // Get RPC URL from configuration (Farcaster contracts are on Optimism)
-    let config = crate::consts::get_config();
-    let rpc_url = config.eth_op_rpc_url().to_string();
-
     // Check if using placeholder values
     if rpc_url.contains("your_api_key_here") || rpc_url == "https://mainnet.optimism.io" {
         println!("⚠️  Configuration Warning:");
@@ -327,13 +387,256 @@ async fn handle_storage_price(fid: u64, units: u32) -> Result<()> {
     Ok(())
 }
 
-async fn handle_storage_usage(fid: u64) -> Result<()> {
-    println!("📊 Storage Usage for FID {fid}");
+async fn handle_storage_info(
+    rpc_url: String,
+    fid: Option<u64>,
+    from_block: Option<u64>,
+) -> Result<()> {
+    println!("📈 Farcaster Storage Economics");
     println!("{}", "=".repeat(40));
 
-    // Get RPC URL from configuration (Farcaster contracts are on Optimism)
-    let config = crate::consts::get_config();
-    let rpc_url = config.eth_op_rpc_url().to_string();
+    let contract_client = FarcasterContractClient::new(rpc_url, ContractAddresses::default())?;
+
+    println!("🔍 Querying StorageRegistry...");
+    let info = contract_client.get_storage_info().await?;
+
+    println!("\n📊 Storage Registry:");
+    println!("   Unit Price: {} ETH", format_ether(info.unit_price));
+    println!(
+        "   Unit Price (USD): ${}",
+        format_units(info.usd_unit_price, USD_PRICE_DECIMALS)?
+    );
+    println!(
+        "   ETH/USD Price: ${}",
+        format_units(info.eth_usd_price, USD_PRICE_DECIMALS)?
+    );
+    let utilization = if info.max_units > 0 {
+        info.rented_units as f64 / info.max_units as f64 * 100.0
+    } else {
+        0.0
+    };
+    println!(
+        "   Rented Units: {} / {} ({utilization:.1}%)",
+        info.rented_units, info.max_units
+    );
+    match chrono::DateTime::from_timestamp(info.deprecation_timestamp as i64, 0) {
+        Some(time)
+            if info.deprecation_timestamp > 0 && info.deprecation_timestamp < i64::MAX as u64 =>
+        {
+            let status = if time < chrono::Utc::now() {
+                "rentals closed"
+            } else {
+                "rentals open until then"
+            };
+            println!(
+                "   Deprecation: {} ({status})",
+                time.format("%Y-%m-%d %H:%M UTC")
+            );
+        }
+        _ => println!("   Deprecation: not scheduled"),
+    }
+    println!(
+        "   Status: {}",
+        if info.paused {
+            "⏸️  Paused"
+        } else {
+            "✅ Active"
+        }
+    );
+
+    let Some(fid) = fid else {
+        return Ok(());
+    };
+
+    println!("\n🧾 Storage Rentals for FID {fid}:");
+    let store = EventStore::open_default()?;
+    let rentals: Vec<(u64, String, u64)> = if store.last_block().is_some() && from_block.is_none() {
+        println!(
+            "   Source: local index (synced to block {})",
+            store.last_block().unwrap_or_default()
+        );
+        store
+            .fid_history(fid)
+            .into_iter()
+            .filter_map(|event| match &event.kind {
+                EventKind::Rent { payer, units } => {
+                    Some((event.block_number, payer.clone(), *units))
+                }
+                _ => None,
+            })
+            .collect()
+    } else {
+        let start = from_block.unwrap_or(DEFAULT_START_BLOCK);
+        println!("   Source: Rent events from block {start}");
+        match contract_client.get_rent_history(fid, start).await {
+            Ok(records) => records
+                .into_iter()
+                .map(|r| (r.block_number, format!("{:?}", r.payer), r.units))
+                .collect(),
+            Err(e) => {
+                println!("   ❌ {e}");
+                println!("   💡 If your RPC limits log ranges, run 'castorix indexer sync' first");
+                return Ok(());
+            }
+        }
+    };
+
+    if rentals.is_empty() {
+        println!("   No rentals found");
+        return Ok(());
+    }
+    for (block, payer, units) in &rentals {
+        println!("   Block {block}: {units} unit(s) paid by {payer}");
+    }
+    let total: u64 = rentals.iter().map(|(_, _, units)| units).sum();
+    println!(
+        "   Total rented: {total} unit(s) in {} rental(s)",
+        rentals.len()
+    );
+
+    Ok(())
+}
+
+/// Width of the longest bar in the price history chart
+const PRICE_BAR_WIDTH: usize = 30;
+
+async fn handle_storage_price_history(
+    rpc_url: String,
+    from_block: Option<u64>,
+    json: bool,
+) -> Result<()> {
+    let contract_client = FarcasterContractClient::new(rpc_url, ContractAddresses::default())?;
+
+    // (block, old price, new price), USD with 8 decimals
+    let store = EventStore::open_default()?;
+    let indexed: Vec<(u64, U256, U256)> = store
+        .price_changes()
+        .into_iter()
+        .filter_map(|event| match &event.kind {
+            EventKind::SetPrice {
+                old_price,
+                new_price,
+            } => Some((
+                event.block_number,
+                U256::from(*old_price),
+                U256::from(*new_price),
+            )),
+            _ => None,
+        })
+        .collect();
+    // Stores synced before SetPrice was indexed have none, so read the chain then
+    let (source, changes) = if from_block.is_none() && !indexed.is_empty() {
+        (
+            format!(
+                "local index (synced to block {})",
+                store.last_block().unwrap_or_default()
+            ),
+            indexed,
+        )
+    } else {
+        let start = from_block.unwrap_or(DEFAULT_START_BLOCK);
+        let changes = contract_client
+            .get_price_history(start)
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "{e}\n💡 If your RPC limits log ranges, run 'castorix indexer sync' first"
+                )
+            })?
+            .into_iter()
+            .map(|change| (change.block_number, change.old_price, change.new_price))
+            .collect();
+        (format!("SetPrice events from block {start}"), changes)
+    };
+
+    let mut history = Vec::with_capacity(changes.len());
+    for (block, old_price, new_price) in changes {
+        let timestamp = contract_client
+            .provider()
+            .get_block(block)
+            .await?
+            .map(|b| b.timestamp.as_u64());
+        history.push((block, timestamp, old_price, new_price));
+    }
+    let info = contract_client.get_storage_info().await?;
+
+    if json {
+        let changes: Vec<serde_json::Value> = history
+            .iter()
+            .map(|(block, timestamp, old_price, new_price)| {
+                Ok(serde_json::json!({
+                    "block_number": block,
+                    "timestamp": timestamp,
+                    "old_usd_price": format_units(*old_price, USD_PRICE_DECIMALS)?,
+                    "new_usd_price": format_units(*new_price, USD_PRICE_DECIMALS)?,
+                }))
+            })
+            .collect::<Result<_>>()?;
+        println!(
+            "{}",
+            output::json(&serde_json::json!({
+                "source": source,
+                "changes": changes,
+                "current": {
+                    "usd_unit_price": format_units(info.usd_unit_price, USD_PRICE_DECIMALS)?,
+                    "eth_unit_price": format_ether(info.unit_price),
+                    "eth_usd_price": format_units(info.eth_usd_price, USD_PRICE_DECIMALS)?,
+                },
+            }))?
+        );
+        return Ok(());
+    }
+
+    println!("📉 Storage Unit Price History");
+    println!("{}", "=".repeat(40));
+    println!("   Source: {source}");
+    if history.is_empty() {
+        println!("   No price changes found");
+    } else {
+        let max = history
+            .iter()
+            .flat_map(|(_, _, old, new)| [*old, *new])
+            .max()
+            .unwrap_or_default()
+            .max(U256::one());
+        let bar = |price: U256| {
+            let width = (price * PRICE_BAR_WIDTH / max).as_usize();
+            "█".repeat(width.max(1))
+        };
+        let (_, _, first_price, _) = history[0];
+        println!(
+            "\n   {:<16} {:>10}  {} ${}",
+            "before",
+            "",
+            bar(first_price),
+            format_units(first_price, USD_PRICE_DECIMALS)?
+        );
+        for (block, timestamp, _, new_price) in &history {
+            let date = timestamp
+                .and_then(|t| chrono::DateTime::from_timestamp(t as i64, 0))
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "unknown date".to_string());
+            println!(
+                "   {date:<16} {block:>10}  {} ${}",
+                bar(*new_price),
+                format_units(*new_price, USD_PRICE_DECIMALS)?
+            );
+        }
+    }
+
+    println!(
+        "\n💰 Current unit price: ${} ({} ETH at ETH/USD ${})",
+        format_units(info.usd_unit_price, USD_PRICE_DECIMALS)?,
+        format_ether(info.unit_price),
+        format_units(info.eth_usd_price, USD_PRICE_DECIMALS)?
+    );
+    println!("💡 The USD price is fixed between changes; the ETH price follows ETH/USD");
+    Ok(())
+}
+
+async fn handle_storage_usage(rpc_url: String, fid: u64, hub_url: &str) -> Result<()> {
+    println!("📊 Storage Usage for FID {fid}");
+    println!("{}", "=".repeat(40));
 
     // Check if using placeholder values
     if rpc_url.contains("your_api_key_here") || rpc_url == "https://mainnet.optimism.io" {
@@ -361,9 +664,8 @@ async fn handle_storage_usage(fid: u64) -> Result<()> {
     // Note: registration_time is not available in FidInfo struct
 
     // Try to get basic FID information from hub
-    let hub_url = crate::consts::get_config().farcaster_hub_url().to_string();
-
-    let hub_client = crate::core::client::hub_client::FarcasterClient::read_only(hub_url);
+    let hub_client =
+        crate::core::client::hub_client::FarcasterClient::read_only(hub_url.to_string());
 
     match hub_client.get_user(fid).await {
         Ok(_user) => {
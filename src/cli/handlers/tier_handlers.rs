@@ -0,0 +1,270 @@
+use anyhow::Result;
+use ethers::middleware::Middleware;
+use ethers::signers::LocalWallet;
+use ethers::signers::Signer;
+use ethers::utils::format_ether;
+
+use crate::cli::endpoints::EndpointOverrides;
+use crate::cli::output;
+use crate::cli::types::TierCommands;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::policy;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
+use crate::encrypted_key_manager::prompt_password;
+use crate::encrypted_key_manager::EncryptedKeyManager;
+use crate::farcaster::contracts::tier_registry::TierRegistry;
+
+/// Handle subscription tier commands
+pub async fn handle_tier_command(
+    command: TierCommands,
+    storage_path: Option<&str>,
+    endpoints: &EndpointOverrides,
+) -> Result<()> {
+    match command {
+        TierCommands::Status { fid, json } => {
+            let hub_client = FarcasterClient::read_only(endpoints.hub_url());
+            handle_tier_status(&hub_client, fid, json).await?;
+        }
+        TierCommands::Price { days, tier } => {
+            handle_tier_price(tier, days).await?;
+        }
+        TierCommands::Purchase {
+            fid,
+            days,
+            tier,
+            wallet,
+            dry_run,
+            yes,
+        } => {
+            handle_tier_purchase(fid, tier, days, wallet, dry_run, yes, storage_path).await?;
+        }
+    }
+    Ok(())
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+async fn handle_tier_status(hub_client: &FarcasterClient, fid: u64, json: bool) -> Result<()> {
+    let subscriptions = hub_client.get_tier_subscriptions(fid).await?;
+    let purchases = hub_client.get_tier_purchases(fid).await.unwrap_or_default();
+
+    if json {
+        println!(
+            "{}",
+            output::json(&serde_json::json!({
+                "fid": fid,
+                "subscriptions": subscriptions,
+                "purchases": purchases,
+            }))?
+        );
+        return Ok(());
+    }
+
+    println!("💎 Subscription tiers for FID {fid}");
+    println!("{}", "─".repeat(50));
+    let now = chrono::Utc::now().timestamp() as u64;
+    if subscriptions.is_empty() {
+        println!("ℹ️  No subscription tiers");
+        println!("💡 Subscribe with: castorix tier purchase {fid} --wallet <wallet-name>");
+    }
+    for subscription in &subscriptions {
+        let state = if subscription.is_active(now) {
+            "✅ active"
+        } else {
+            "⌛ expired"
+        };
+        match subscription.expires_at {
+            Some(expires_at) => {
+                let days_left = expires_at.saturating_sub(now) / 86_400;
+                println!(
+                    "   {} — {state}, expires {} ({days_left} days left)",
+                    subscription.tier_type,
+                    format_timestamp(expires_at)
+                );
+            }
+            None => println!("   {} — {state}, permanent", subscription.tier_type),
+        }
+    }
+
+    if !purchases.is_empty() {
+        println!("\n🧾 Purchases:");
+        for purchase in &purchases {
+            println!(
+                "   {} {} days on {} by {}",
+                purchase.tier_type,
+                purchase.for_days,
+                purchase
+                    .block_timestamp
+                    .map(format_timestamp)
+                    .unwrap_or_else(|| "unknown date".to_string()),
+                purchase.payer.as_deref().unwrap_or("unknown payer")
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn handle_tier_price(tier: u64, days: u64) -> Result<()> {
+    let rpc_url = crate::consts::get_config().eth_base_rpc_url().to_string();
+    let registry = TierRegistry::new(&rpc_url)?;
+
+    println!("💰 Tier {tier} price");
+    println!("{}", "─".repeat(50));
+    let info = registry.tier_info(tier).await?;
+    let token = registry.payment_token(info.payment_token).await?;
+    println!(
+        "   Status: {}",
+        if info.is_active { "active" } else { "inactive" }
+    );
+    println!("   Payment token: {} ({:?})", token.symbol, token.address);
+    println!("   Per day: {}", token.format(info.token_price_per_day));
+    println!(
+        "   Subscription length: {} to {} days",
+        info.min_days, info.max_days
+    );
+
+    if days < info.min_days || days > info.max_days {
+        println!(
+            "⚠️  {days} days is outside the allowed range of {} to {} days",
+            info.min_days, info.max_days
+        );
+        return Ok(());
+    }
+    let price = registry.price(tier, days).await?;
+    println!("✅ {days} days: {}", token.format(price));
+    Ok(())
+}
+
+async fn load_wallet(wallet_name: &str, storage_path: Option<&str>) -> Result<Option<LocalWallet>> {
+    let mut manager = if let Some(path) = storage_path {
+        let keys_path = std::path::Path::new(path).join("keys");
+        EncryptedKeyManager::new(&keys_path.to_string_lossy())
+    } else {
+        EncryptedKeyManager::default_config()
+    };
+    if !manager.key_exists(wallet_name) {
+        println!("❌ Wallet '{wallet_name}' not found!");
+        println!("💡 Use 'castorix key list' to see available wallets");
+        return Ok(None);
+    }
+
+    let password = prompt_password(&format!("Enter password for wallet '{wallet_name}': "))?;
+    match manager.load_and_decrypt(&password, wallet_name).await {
+        Ok(_) => {
+            let wallet = manager
+                .key_manager()
+                .ok_or_else(|| anyhow::anyhow!("Wallet '{wallet_name}' failed to load"))?
+                .wallet()
+                .clone();
+            println!("✅ Wallet loaded: {:?}", wallet.address());
+            Ok(Some(wallet))
+        }
+        Err(e) => {
+            println!("❌ Failed to load wallet: {e}");
+            Ok(None)
+        }
+    }
+}
+
+async fn handle_tier_purchase(
+    fid: u64,
+    tier: u64,
+    days: u64,
+    wallet_name: String,
+    dry_run: bool,
+    yes: bool,
+    storage_path: Option<&str>,
+) -> Result<()> {
+    println!("🛒 Subscribe FID {fid} to tier {tier} for {days} days");
+    println!("{}", "=".repeat(40));
+
+    let rpc_url = crate::consts::get_config().eth_base_rpc_url().to_string();
+    let registry = TierRegistry::new(&rpc_url)?;
+    let info = registry.tier_info(tier).await?;
+    if !info.is_active {
+        println!("❌ Tier {tier} is not active");
+        return Ok(());
+    }
+    if days < info.min_days || days > info.max_days {
+        println!(
+            "❌ {days} days is outside the allowed range of {} to {} days",
+            info.min_days, info.max_days
+        );
+        return Ok(());
+    }
+    let token = registry.payment_token(info.payment_token).await?;
+    let price = registry.price(tier, days).await?;
+
+    let Some(wallet) = load_wallet(&wallet_name, storage_path).await? else {
+        return Ok(());
+    };
+    let provider = crate::core::utils::http::provider(rpc_url.as_str())?;
+    let token_balance = registry
+        .token_balance(token.address, wallet.address())
+        .await?;
+    let eth_balance = provider.get_balance(wallet.address(), None).await?;
+
+    println!("\n📋 Purchase Summary:");
+    println!("   FID: {fid}");
+    println!("   Tier: {tier}");
+    println!("   Days: {days}");
+    println!("   Price: {}", token.format(price));
+    println!("   Payer: {:?}", wallet.address());
+    println!(
+        "   {} balance: {}",
+        token.symbol,
+        token.format(token_balance)
+    );
+    println!("   ETH balance (gas): {} ETH", format_ether(eth_balance));
+
+    if token_balance < price {
+        println!(
+            "❌ Insufficient {}: need {}",
+            token.symbol,
+            token.format(price)
+        );
+        return Ok(());
+    }
+    if eth_balance.is_zero() {
+        println!("⚠️  The wallet has no ETH on Base to pay for gas");
+    }
+
+    if dry_run {
+        println!("\n🔍 Dry run: no transactions sent");
+        return Ok(());
+    }
+
+    policy::current().check_confirmation(yes)?;
+    if !yes {
+        prompt::ask(
+            InputKind::Confirmation,
+            "\n❓ Do you want to proceed with the purchase? (yes/no): ",
+        )?;
+        let mut confirmation = String::new();
+        std::io::stdin().read_line(&mut confirmation)?;
+        let confirmation = confirmation.trim().to_lowercase();
+        if confirmation != "yes" && confirmation != "y" {
+            println!("❌ Operation cancelled by user");
+            return Ok(());
+        }
+    } else {
+        println!("\n✅ Auto-confirmed with --yes flag");
+    }
+
+    println!("⏳ Sending purchase...");
+    let purchase = registry.purchase(&wallet, fid, tier, days).await?;
+    if let Some(approve_tx) = purchase.approve_tx {
+        println!("   Approval: {approve_tx:?}");
+    }
+    println!("✅ Subscription purchased!");
+    println!("   Transaction Hash: {:?}", purchase.purchase_tx);
+    println!(
+        "💡 The hub shows the new expiry once it indexes the event: castorix tier status {fid}"
+    );
+    Ok(())
+}
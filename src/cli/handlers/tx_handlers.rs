@@ -0,0 +1,324 @@
+use std::io::Write;
+
+use anyhow::Result;
+use ethers::abi::Token;
+use ethers::signers::LocalWallet;
+use ethers::types::Address;
+use ethers::types::Bytes;
+use ethers::utils::format_ether;
+use ethers::utils::format_units;
+
+use crate::cli::endpoints::EndpointOverrides;
+use crate::cli::types::TxBuildCommands;
+use crate::cli::types::TxCommands;
+use crate::core::crypto::encrypted_storage::EncryptedEthKeyManager;
+use crate::core::policy;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
+use crate::encrypted_key_manager::prompt_password;
+use crate::encrypted_key_manager::EncryptedKeyManager;
+use crate::farcaster::contracts::offline_tx::SignedTx;
+use crate::farcaster::contracts::offline_tx::TxOperation;
+use crate::farcaster::contracts::offline_tx::UnsignedTx;
+use crate::farcaster::contracts::types::ContractAddresses;
+use crate::farcaster::contracts::FarcasterContractClient;
+
+/// Handle air-gapped transaction commands
+///
+/// Progress goes to stderr so that a transaction written to stdout can be
+/// piped or redirected as is.
+pub async fn handle_tx_command(
+    command: TxCommands,
+    storage_path: Option<&str>,
+    endpoints: &EndpointOverrides,
+) -> Result<()> {
+    let rpc_url = &endpoints.op_rpc_url();
+    match command {
+        TxCommands::Build { operation } => handle_tx_build(rpc_url, operation).await?,
+        TxCommands::Sign {
+            file,
+            wallet,
+            fid,
+            output,
+            yes,
+        } => handle_tx_sign(&file, wallet, fid, output, yes, storage_path).await?,
+        TxCommands::Broadcast { file, yes } => handle_tx_broadcast(rpc_url, &file, yes).await?,
+    }
+    Ok(())
+}
+
+fn contract_client(rpc_url: &str) -> Result<FarcasterContractClient> {
+    FarcasterContractClient::new(rpc_url.to_string(), ContractAddresses::default())
+}
+
+fn parse_address(address: &str, what: &str) -> Result<Address> {
+    address
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid {what} address: {address}"))
+}
+
+fn parse_key(key: &str) -> Result<Bytes> {
+    let key = hex::decode(key.trim_start_matches("0x"))
+        .map_err(|_| anyhow::anyhow!("Invalid public key hex: {key}"))?;
+    if key.len() != 32 {
+        anyhow::bail!("An Ed25519 public key is 32 bytes, got {} bytes", key.len());
+    }
+    Ok(key.into())
+}
+
+/// Write a transaction file, or print it when no file is given
+fn write_tx_file(json: &str, output: Option<&str>) -> Result<()> {
+    match output {
+        Some(path) => {
+            std::fs::write(path, format!("{json}\n"))
+                .map_err(|e| anyhow::anyhow!("Failed to write {path}: {e}"))?;
+            eprintln!("💾 Written to {path}");
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+fn read_tx_file(path: &str) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read {path}: {e}"))
+}
+
+fn confirm(question: &str, yes: bool) -> Result<bool> {
+    policy::current().check_confirmation(yes)?;
+    if yes {
+        eprintln!("✅ Auto-confirmed with --yes flag");
+        return Ok(true);
+    }
+    let question = format!("\n❓ {question} (yes/no): ");
+    prompt::ensure_interactive(InputKind::Confirmation, &question)?;
+    eprint!("{question}");
+    std::io::stderr().flush()?;
+    let mut confirmation = String::new();
+    std::io::stdin().read_line(&mut confirmation)?;
+    let confirmation = confirmation.trim().to_lowercase();
+    if confirmation != "yes" && confirmation != "y" {
+        eprintln!("❌ Operation cancelled by user");
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+fn print_unsigned(tx: &UnsignedTx) {
+    eprintln!("📝 {}", tx.operation);
+    eprintln!("   Chain ID: {}", tx.chain_id);
+    eprintln!("   From: {:?}", tx.from);
+    eprintln!("   To: {:?}", tx.to);
+    match tx.decode_call() {
+        Ok(call) => {
+            eprintln!("   Call: {}", call.function.signature());
+            for (input, arg) in call.function.inputs.iter().zip(&call.args) {
+                eprintln!("      {}: {}", input.name, format_token(arg));
+            }
+            if matches!(tx.operation, TxOperation::SignerAdd { .. }) {
+                eprintln!("      (metadata is the key request, signed with the transaction)");
+            }
+        }
+        Err(e) => eprintln!("   Call: ⚠️  {e}"),
+    }
+    eprintln!("   Value: {} ETH", format_ether(tx.value));
+    eprintln!("   Nonce: {}", tx.nonce);
+    eprintln!("   Gas limit: {}", tx.gas);
+    eprintln!(
+        "   Max fee: {} gwei (priority {} gwei)",
+        format_units(tx.max_fee_per_gas, "gwei").unwrap_or_default(),
+        format_units(tx.max_priority_fee_per_gas, "gwei").unwrap_or_default()
+    );
+    match tx.max_cost() {
+        Ok(cost) => eprintln!("   Max cost: {} ETH", format_ether(cost)),
+        Err(e) => eprintln!("   Max cost: ⚠️  {e}"),
+    }
+}
+
+fn format_token(token: &Token) -> String {
+    match token {
+        Token::Address(address) => format!("{address:?}"),
+        Token::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Uint(value) => value.to_string(),
+        other => other.to_string(),
+    }
+}
+
+async fn handle_tx_build(rpc_url: &str, operation: TxBuildCommands) -> Result<()> {
+    let client = contract_client(rpc_url)?;
+    let (from, operation, gas_limit, output) = match operation {
+        TxBuildCommands::Register {
+            from,
+            recovery,
+            extra_storage,
+            gas_limit,
+            output,
+        } => {
+            let from = parse_address(&from, "sender")?;
+            let recovery = match recovery {
+                Some(recovery) => parse_address(&recovery, "recovery")?,
+                None => from,
+            };
+            let operation = TxOperation::Register {
+                recovery,
+                extra_storage,
+            };
+            (from, operation, gas_limit, output)
+        }
+        TxBuildCommands::Rent {
+            fid,
+            units,
+            from,
+            gas_limit,
+            output,
+        } => {
+            let from = parse_address(&from, "sender")?;
+            (from, TxOperation::Rent { fid, units }, gas_limit, output)
+        }
+        TxBuildCommands::SignerAdd {
+            fid,
+            key,
+            valid_hours,
+            gas_limit,
+            output,
+        } => {
+            let from = fid_custody(&client, fid).await?;
+            let deadline = valid_hours
+                .checked_mul(3600)
+                .and_then(|secs| secs.checked_add(chrono::Utc::now().timestamp() as u64))
+                .ok_or_else(|| anyhow::anyhow!("--valid-hours {valid_hours} is too far ahead"))?;
+            let operation = client
+                .signer_add_operation(fid, parse_key(&key)?, deadline)
+                .await?;
+            (from, operation, gas_limit, output)
+        }
+        TxBuildCommands::SignerRemove {
+            fid,
+            key,
+            gas_limit,
+            output,
+        } => {
+            let from = fid_custody(&client, fid).await?;
+            let operation = TxOperation::SignerRemove {
+                fid,
+                key: parse_key(&key)?,
+            };
+            (from, operation, gas_limit, output)
+        }
+    };
+
+    eprintln!("🧱 Building transaction...");
+    let tx = client.build_offline_tx(from, operation, gas_limit).await?;
+    print_unsigned(&tx);
+    if let TxOperation::SignerAdd { deadline, .. } = &tx.operation {
+        let expires = chrono::DateTime::from_timestamp(*deadline as i64, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_default();
+        eprintln!("   ⏰ Sign and broadcast before {expires}");
+    }
+    write_tx_file(&serde_json::to_string_pretty(&tx)?, output.as_deref())?;
+    eprintln!("💡 Sign it offline with 'castorix tx sign <file>'");
+    Ok(())
+}
+
+async fn fid_custody(client: &FarcasterContractClient, fid: u64) -> Result<Address> {
+    Ok(client.get_fid_info(fid).await?.custody)
+}
+
+async fn handle_tx_sign(
+    file: &str,
+    wallet_name: Option<String>,
+    fid: Option<u64>,
+    output: Option<String>,
+    yes: bool,
+    storage_path: Option<&str>,
+) -> Result<()> {
+    let tx = UnsignedTx::from_json(&read_tx_file(file)?)?;
+    print_unsigned(&tx);
+    tx.check()
+        .map_err(|e| anyhow::anyhow!("Refusing to sign {file}: {e}"))?;
+    if !confirm("Sign this transaction?", yes)? {
+        return Ok(());
+    }
+
+    let wallet = match (wallet_name, fid.or(tx.operation.fid())) {
+        (Some(name), _) => load_named_wallet(&name, storage_path).await?,
+        (None, Some(fid)) => load_custody_wallet(fid)?,
+        (None, None) => {
+            anyhow::bail!(
+                "Give --wallet or --fid to choose the key that signs {:?}",
+                tx.from
+            )
+        }
+    };
+    let signed = tx.sign(&wallet).await?;
+    eprintln!("✍️  Signed transaction {:?}", signed.hash);
+    write_tx_file(&serde_json::to_string_pretty(&signed)?, output.as_deref())?;
+    eprintln!("💡 Broadcast it online with 'castorix tx broadcast <file>'");
+    Ok(())
+}
+
+async fn load_named_wallet(name: &str, storage_path: Option<&str>) -> Result<LocalWallet> {
+    let mut manager = match storage_path {
+        Some(path) => {
+            let keys_path = std::path::Path::new(path).join("keys");
+            EncryptedKeyManager::new(&keys_path.to_string_lossy())
+        }
+        None => EncryptedKeyManager::default_config(),
+    };
+    if !manager.key_exists(name) {
+        anyhow::bail!(
+            "Wallet '{name}' not found; use 'castorix key list' to see available wallets"
+        );
+    }
+    let password = prompt_password(&format!("Enter password for wallet '{name}': "))?;
+    manager.load_and_decrypt(&password, name).await?;
+    Ok(manager
+        .key_manager()
+        .ok_or_else(|| anyhow::anyhow!("Wallet '{name}' failed to load"))?
+        .wallet()
+        .clone())
+}
+
+pub(crate) fn load_custody_wallet(fid: u64) -> Result<LocalWallet> {
+    let custody_key_file = EncryptedEthKeyManager::custody_key_file(fid)?;
+    if !std::path::Path::new(&custody_key_file).exists() {
+        anyhow::bail!(
+            "No custody key found for FID {fid}; import one with 'castorix custody import {fid}'"
+        );
+    }
+    let manager = EncryptedEthKeyManager::load_from_file(&custody_key_file)?;
+    let password = crate::core::crypto::encrypted_storage::prompt_password(&format!(
+        "Enter password for custody wallet (FID {fid}): "
+    ))?;
+    manager
+        .get_wallet(fid, &password)
+        .map_err(|e| anyhow::anyhow!("Failed to load wallet for FID {fid}: {e}"))
+}
+
+async fn handle_tx_broadcast(rpc_url: &str, file: &str, yes: bool) -> Result<()> {
+    let signed = SignedTx::from_json(&read_tx_file(file)?)?;
+    signed.verify()?;
+    println!("📡 {}", signed.operation);
+    println!("   Chain ID: {}", signed.chain_id);
+    println!("   From: {:?}", signed.from);
+    println!("   Transaction: {:?}", signed.hash);
+    if !confirm("Broadcast this transaction?", yes)? {
+        return Ok(());
+    }
+
+    let client = contract_client(rpc_url)?;
+    let receipt = client.broadcast_signed_tx(&signed).await?;
+    println!(
+        "✅ Included in block {}",
+        receipt.block_number.unwrap_or_default()
+    );
+    if let Some(gas_used) = receipt.gas_used {
+        println!("   Gas used: {gas_used}");
+    }
+    if let TxOperation::Register { .. } = signed.operation {
+        if let Some(fid) = client.address_has_fid(signed.from).await? {
+            println!("🆔 Registered FID {fid}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use std::path::Path;
+
+use anyhow::Context;
+use anyhow::Result;
+use base64::engine::general_purpose;
+use base64::Engine as _;
+use ethers::types::transaction::eip712::TypedData;
+use ethers::types::Address;
+use ethers::types::H256;
+use protobuf::Message as _;
+
+use crate::cli::output;
+use crate::cli::types::UtilCommands;
+use crate::core::client::hub_client::message_data;
+use crate::core::client::offline::message_to_json;
+use crate::core::protocol::message::Message;
+use crate::core::protocol::verify;
+use crate::core::protocol::verify::MessageVerification;
+use crate::ens_proof::names;
+use crate::ens_proof::normalize::normalize;
+
+/// Handle standalone utilities
+pub fn handle_util_command(command: UtilCommands) -> Result<()> {
+    match command {
+        UtilCommands::DecodeMessage {
+            input,
+            verify_signature,
+            json,
+            raw,
+        } => decode_message(&input, verify_signature, json, raw),
+        UtilCommands::VerifySig {
+            scheme,
+            pubkey,
+            payload,
+            signature,
+        } => verify_sig(&scheme, &pubkey, &payload, &signature),
+        UtilCommands::Namehash {
+            name,
+            no_normalize,
+            json,
+        } => namehash(&name, no_normalize, json),
+    }
+}
+
+fn namehash(name: &str, no_normalize: bool, json: bool) -> Result<()> {
+    let name = if no_normalize {
+        name.to_string()
+    } else {
+        normalize(name)?
+    };
+    let labels: Vec<(&str, H256)> = name
+        .split('.')
+        .map(|label| (label, names::labelhash(label)))
+        .collect();
+    let node = names::namehash(&name);
+    let encoded = names::dns_encode(&name)?;
+
+    if json {
+        let output = serde_json::json!({
+            "name": name,
+            "namehash": format!("{node:?}"),
+            "labels": labels
+                .iter()
+                .map(|(label, hash)| serde_json::json!({
+                    "label": label,
+                    "labelhash": format!("{hash:?}"),
+                }))
+                .collect::<Vec<_>>(),
+            "dns_encoded": format!("0x{}", hex::encode(&encoded)),
+        });
+        println!("{}", output::json(&output)?);
+        return Ok(());
+    }
+
+    println!("🏷️  Name:        {name}");
+    println!("#️⃣  Namehash:    {node:?}");
+    for (label, hash) in &labels {
+        println!("   labelhash({label}): {hash:?}");
+    }
+    println!("📦 DNS encoded: 0x{}", hex::encode(&encoded));
+    Ok(())
+}
+
+fn verify_sig(scheme: &str, pubkey: &str, payload: &str, signature: &str) -> Result<()> {
+    let signature = decode_text(signature).context("--signature must be hex or base64")?;
+    match scheme {
+        "ed25519" => {
+            let public_key = decode_hex(pubkey).context("--pubkey must be hex")?;
+            let payload = match decode_hex(payload) {
+                Some(bytes) => bytes,
+                None => std::fs::read(payload)
+                    .with_context(|| format!("--payload is neither hex nor a file: {payload}"))?,
+            };
+            verify::verify_ed25519(&public_key, &payload, &signature)?;
+        }
+        "eip712" => {
+            let address: Address = pubkey
+                .parse()
+                .with_context(|| format!("--pubkey must be an Ethereum address: {pubkey}"))?;
+            match decode_hex(payload) {
+                Some(message_hash) => {
+                    verify::verify_message_hash_eip712(address, &message_hash, &signature)?
+                }
+                None => {
+                    let json = if Path::new(payload).is_file() {
+                        std::fs::read_to_string(payload)
+                            .with_context(|| format!("Failed to read {payload}"))?
+                    } else {
+                        payload.to_string()
+                    };
+                    let typed_data: TypedData = serde_json::from_str(&json)
+                        .context("--payload is neither a hex message hash nor typed data JSON")?;
+                    verify::verify_typed_data(address, &typed_data, &signature)?;
+                }
+            }
+        }
+        other => anyhow::bail!("Unsupported signature scheme: {other}"),
+    }
+    println!("✅ Signature valid");
+    Ok(())
+}
+
+fn decode_message(input: &str, verify_signature: bool, json: bool, raw: bool) -> Result<()> {
+    let bytes = read_input(input)?;
+    let message = Message::parse_from_bytes(&bytes)
+        .context("Input is not a protobuf-encoded Farcaster message")?;
+    let verification = verify::verify_message(&message, verify_signature)?;
+
+    if json {
+        let output = serde_json::json!({
+            "message": message_to_json(&message)?,
+            "verification": verification,
+        });
+        println!("{}", output::json(&output)?);
+    } else {
+        if raw {
+            println!("{message:#?}");
+            if !message.has_data() {
+                println!("data_bytes decoded:");
+                println!("{:#?}", message_data(&message)?);
+            }
+        } else {
+            println!("{}", output::json(&message_to_json(&message)?)?);
+        }
+        println!("{}", "─".repeat(50));
+        print_verification(&message, &verification);
+    }
+
+    if !verification.is_valid() {
+        anyhow::bail!("Message failed verification");
+    }
+    Ok(())
+}
+
+fn print_verification(message: &Message, verification: &MessageVerification) {
+    if verification.hash_ok {
+        println!("✅ Hash matches: {}", verification.computed_hash);
+    } else {
+        println!(
+            "❌ Hash mismatch: message has 0x{} ({:?}), data hashes to {}",
+            hex::encode(message.get_hash()),
+            message.get_hash_scheme(),
+            verification.computed_hash
+        );
+    }
+    match &verification.validation_error {
+        None => println!("✅ Message data is well-formed"),
+        Some(e) => println!("❌ Invalid message data: {e}"),
+    }
+    match (verification.signature_ok, &verification.signature_error) {
+        (Some(true), _) => println!(
+            "✅ Signature valid for signer 0x{}",
+            hex::encode(message.get_signer())
+        ),
+        (Some(false), error) => println!(
+            "❌ Signature invalid: {}",
+            error.as_deref().unwrap_or("unknown error")
+        ),
+        (None, _) => println!("⚠️  Signature not checked (use --verify-signature)"),
+    }
+}
+
+/// Message bytes from hex or base64 text, or from a file holding either or
+/// raw bytes
+fn read_input(input: &str) -> Result<Vec<u8>> {
+    let path = Path::new(input);
+    let (text, file_bytes) = if path.is_file() {
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        (String::from_utf8_lossy(&bytes).into_owned(), Some(bytes))
+    } else {
+        (input.to_string(), None)
+    };
+
+    decode_text(&text)
+        .or(file_bytes)
+        .context("Input is neither hex nor base64, and no such file exists")
+}
+
+/// Bytes from hex (with or without 0x) or base64 text; whitespace is ignored
+fn decode_text(text: &str) -> Option<Vec<u8>> {
+    let text: String = text.split_whitespace().collect();
+    decode_hex(&text).or_else(|| general_purpose::STANDARD.decode(&text).ok())
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    hex::decode(text.strip_prefix("0x").unwrap_or(text)).ok()
+}
@@ -0,0 +1,159 @@
+use anyhow::Result;
+use futures::stream::StreamExt;
+use futures::stream::{
+    self,
+};
+
+use crate::cli::aliases::CliConfig;
+use crate::cli::types::WatchlistCommands;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_client::FARCASTER_EPOCH;
+use crate::core::client::profile;
+
+/// Longest cast excerpt shown in the status table
+const EXCERPT_CHARS: usize = 40;
+
+/// Latest activity of a watched account
+struct WatchStatus {
+    name: String,
+    fid: u64,
+    username: Option<String>,
+    /// Farcaster timestamp and text of the latest cast
+    latest_cast: Option<(u64, String)>,
+    error: Option<String>,
+}
+
+/// Handle watchlist commands
+pub async fn handle_watchlist_command(
+    command: WatchlistCommands,
+    hub_client: &FarcasterClient,
+) -> Result<()> {
+    match command {
+        WatchlistCommands::Add { fid, name } => {
+            let name = name.trim_start_matches('@').to_string();
+            if name.is_empty()
+                || name.parse::<u64>().is_ok()
+                || name.contains(|c: char| c.is_whitespace() || c == '=')
+            {
+                anyhow::bail!("Invalid nickname '{name}'");
+            }
+            let mut config = CliConfig::load()?;
+            let replaced = config.watchlist.insert(name.clone(), fid);
+            config.save()?;
+            match replaced {
+                Some(previous) if previous != fid => {
+                    println!("✅ @{name} now refers to FID {fid} (was {previous})")
+                }
+                _ => println!("✅ Watching FID {fid} as @{name}"),
+            }
+        }
+        WatchlistCommands::Remove { name } => {
+            let name = name.trim_start_matches('@');
+            let mut config = CliConfig::load()?;
+            if config.watchlist.remove(name).is_none() {
+                anyhow::bail!("No watched account named '@{name}'");
+            }
+            config.save()?;
+            println!("🗑️  Stopped watching @{name}");
+        }
+        WatchlistCommands::List => {
+            let config = CliConfig::load()?;
+            if config.watchlist.is_empty() {
+                println!("📭 Watchlist is empty. Add an account with: castorix watchlist add <fid> --name <name>");
+            } else {
+                println!("👀 Watched accounts ({}):", config.watchlist.len());
+                for (name, fid) in &config.watchlist {
+                    println!("   @{name:<16} FID {fid}");
+                }
+            }
+        }
+        WatchlistCommands::Status { concurrency } => {
+            handle_watchlist_status(hub_client, concurrency).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn handle_watchlist_status(hub_client: &FarcasterClient, concurrency: usize) -> Result<()> {
+    let config = CliConfig::load()?;
+    if config.watchlist.is_empty() {
+        println!("📭 Watchlist is empty. Add an account with: castorix watchlist add <fid> --name <name>");
+        return Ok(());
+    }
+
+    let statuses: Vec<WatchStatus> = stream::iter(config.watchlist)
+        .map(|(name, fid)| async move {
+            let profile = hub_client.get_user_profile(fid).await;
+            let casts = hub_client.get_casts_by_fid(fid, 1).await;
+            let error = match (&profile, &casts) {
+                (Err(e), _) | (_, Err(e)) => Some(e.to_string()),
+                _ => None,
+            };
+            WatchStatus {
+                name,
+                fid,
+                username: profile
+                    .ok()
+                    .and_then(|messages| profile::current_profile(&messages).remove("username")),
+                latest_cast: casts.ok().and_then(|casts| {
+                    let data = casts.first()?.get("data")?;
+                    let timestamp = data.get("timestamp")?.as_u64()?;
+                    let text = data
+                        .pointer("/castAddBody/text")
+                        .and_then(|t| t.as_str())
+                        .unwrap_or_default();
+                    Some((timestamp, text.to_string()))
+                }),
+                error,
+            }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+
+    println!(
+        "{:<16} {:>8}  {:<16} {:<12} LATEST CAST",
+        "NAME", "FID", "USERNAME", "LAST SEEN"
+    );
+    let now = chrono::Utc::now().timestamp() as u64;
+    for status in statuses {
+        let (last_seen, excerpt) = match (&status.latest_cast, &status.error) {
+            (Some((timestamp, text)), _) => (
+                format_age(now.saturating_sub(timestamp + FARCASTER_EPOCH)),
+                excerpt(text),
+            ),
+            (None, Some(error)) => ("error".to_string(), excerpt(error)),
+            (None, None) => ("never".to_string(), String::new()),
+        };
+        println!(
+            "{:<16} {:>8}  {:<16} {:<12} {}",
+            format!("@{}", status.name),
+            status.fid,
+            status.username.as_deref().unwrap_or("-"),
+            last_seen,
+            excerpt
+        );
+    }
+    Ok(())
+}
+
+/// Compact age such as `5m ago` or `3d ago`
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86399 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+/// First line of a text, shortened to fit the table
+fn excerpt(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    if line.chars().count() > EXCERPT_CHARS {
+        let short: String = line.chars().take(EXCERPT_CHARS - 1).collect();
+        format!("{short}…")
+    } else {
+        line.to_string()
+    }
+}
@@ -0,0 +1,140 @@
+//! Message catalogs for localized CLI output
+//!
+//! A catalog is a JSON object mapping English phrases, as the CLI prints them,
+//! to their translation:
+//!
+//! ```json
+//! { "Getting casts for FID": "Casts abrufen für FID", "No casts found": "Keine Casts gefunden" }
+//! ```
+//!
+//! Every output line has its catalog phrases replaced, longest phrase first, so
+//! a phrase also matches inside lines that carry dynamic values. Catalogs are
+//! selected with `--lang <LANG>` (or `CASTORIX_LANG`) and read from
+//! `<data dir>/locales/<LANG>.json`; distributions can ship catalogs anywhere
+//! and pass a file path instead of a language code.
+
+use std::borrow::Cow;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use anyhow::Result;
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Phrase translations applied to CLI output
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    /// (English phrase, translation), longest phrase first
+    entries: Vec<(String, String)>,
+}
+
+impl Catalog {
+    /// Parse a catalog from its JSON representation
+    pub fn from_json(json: &str) -> Result<Self> {
+        let map: std::collections::HashMap<String, String> = serde_json::from_str(json)
+            .context("Message catalog must be a JSON object of strings")?;
+        let mut entries: Vec<(String, String)> = map
+            .into_iter()
+            .filter(|(from, _)| !from.is_empty())
+            .collect();
+        entries.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        Ok(Self { entries })
+    }
+
+    /// Load a catalog file
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read message catalog {}", path.display()))?;
+        Self::from_json(&json)
+            .with_context(|| format!("Invalid message catalog {}", path.display()))
+    }
+
+    /// Number of phrases in the catalog
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the catalog has no phrases
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Translate every catalog phrase found in `text`
+    pub fn translate<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut translated = Cow::Borrowed(text);
+        for (from, to) in &self.entries {
+            if translated.contains(from.as_str()) {
+                translated = Cow::Owned(translated.replace(from.as_str(), to));
+            }
+        }
+        translated
+    }
+}
+
+/// Path of the catalog for a language code or file path
+///
+/// # Arguments
+/// * `lang` - A language code such as `de`, or a path to a catalog file
+///
+/// # Returns
+/// * `PathBuf` - The catalog file to load
+pub fn catalog_path(lang: &str) -> PathBuf {
+    let path = Path::new(lang);
+    if path.extension().is_some_and(|ext| ext == "json") || path.components().count() > 1 {
+        return path.to_path_buf();
+    }
+    crate::core::utils::data_dir::data_dir()
+        .join("locales")
+        .join(format!("{lang}.json"))
+}
+
+/// Load the catalog for a language and use it for all further output
+///
+/// English (`en`, `en_US`, `C`, ...) needs no catalog and is accepted without one.
+pub fn init(lang: &str) -> Result<()> {
+    let english = matches!(lang, "" | "en" | "C" | "POSIX")
+        || lang.starts_with("en_")
+        || lang.starts_with("en-");
+    if english {
+        return Ok(());
+    }
+    let catalog = Catalog::load(&catalog_path(lang))?;
+    let _ = CATALOG.set(catalog);
+    Ok(())
+}
+
+/// The active catalog, if one was loaded
+pub fn catalog() -> Option<&'static Catalog> {
+    CATALOG.get().filter(|catalog| !catalog.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_prefers_longest_phrase() {
+        let catalog = Catalog::from_json(
+            r#"{ "casts": "Casts", "No casts found": "Keine Casts gefunden", "FID": "FID" }"#,
+        )
+        .unwrap();
+        assert_eq!(catalog.len(), 3);
+        assert_eq!(
+            catalog.translate("❌ No casts found for FID: 42"),
+            "❌ Keine Casts gefunden for FID: 42"
+        );
+        assert!(matches!(
+            catalog.translate("nothing here"),
+            Cow::Borrowed(_)
+        ));
+        assert!(Catalog::from_json("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn test_catalog_path() {
+        assert_eq!(catalog_path("./de.json"), PathBuf::from("./de.json"));
+        assert!(catalog_path("de").ends_with("locales/de.json"));
+    }
+}
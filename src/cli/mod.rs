@@ -1,15 +1,49 @@
+//! Command line interface
+
+#[cfg(feature = "cli")]
+pub mod aliases;
+#[cfg(feature = "cli")]
 pub mod commands;
+#[cfg(feature = "cli")]
+pub mod context;
+#[cfg(feature = "cli")]
+pub mod endpoints;
+#[cfg(feature = "cli")]
+pub mod fields;
+#[cfg(feature = "cli")]
 pub mod handlers;
+#[cfg(feature = "cli")]
+pub mod i18n;
+#[cfg(feature = "cli")]
+pub mod output;
+#[cfg(feature = "cli")]
+pub mod plugins;
+#[cfg(feature = "cli")]
 pub mod types;
 
+#[cfg(feature = "cli")]
 pub use commands::Cli;
+#[cfg(feature = "cli")]
 pub use commands::Commands;
+#[cfg(feature = "cli")]
+pub use endpoints::EndpointOverrides;
+#[cfg(feature = "cli")]
 pub use handlers::CliHandler;
+#[cfg(feature = "cli")]
 pub use types::CustodyCommands;
+#[cfg(all(feature = "cli", feature = "dev"))]
+pub use types::DevCommands;
+#[cfg(feature = "cli")]
 pub use types::EnsCommands;
+#[cfg(feature = "cli")]
 pub use types::FidCommands;
+#[cfg(feature = "cli")]
 pub use types::HubCommands;
+#[cfg(feature = "cli")]
 pub use types::KeyCommands;
+#[cfg(feature = "cli")]
 pub use types::McpCommands;
+#[cfg(feature = "cli")]
 pub use types::SignersCommands;
+#[cfg(feature = "cli")]
 pub use types::StorageCommands;
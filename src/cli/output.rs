@@ -0,0 +1,170 @@
+//! Terminal output filtering
+//!
+//! Every `print!`/`println!`/`eprint!`/`eprintln!` in the crate goes through
+//! [`write`] (see `src/macros.rs`). Output is passed through untouched unless
+//! plain mode is on or a message catalog is loaded: plain mode strips emoji and
+//! ANSI colors for logs and screen readers, and the catalog translates English
+//! phrases (see [`crate::cli::i18n`]).
+//!
+//! Commands that print JSON format it with [`json`], which applies `--fields`
+//! (see [`crate::cli::fields`]) and `--compact`.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cli::fields::FieldPath;
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static COMPACT: AtomicBool = AtomicBool::new(false);
+static FIELDS: OnceLock<Vec<FieldPath>> = OnceLock::new();
+
+/// Turn plain (emoji- and color-free) output on or off
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Whether plain output is on
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Set how [`json`] formats results: the `--fields` to keep and whether to
+/// print them on one line
+///
+/// Only the first call sets the fields.
+pub fn set_json_format(fields: Option<&str>, compact: bool) -> Result<()> {
+    if let Some(fields) = fields {
+        let _ = FIELDS.set(crate::cli::fields::parse_fields(fields)?);
+    }
+    COMPACT.store(compact, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Format a command's JSON result per `--fields` and `--compact`
+pub fn json<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    let value = select_fields(value)?;
+    Ok(if COMPACT.load(Ordering::Relaxed) {
+        serde_json::to_string(&value)?
+    } else {
+        serde_json::to_string_pretty(&value)?
+    })
+}
+
+/// Format one record of a JSON Lines stream per `--fields`, always on one line
+pub fn json_line<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    Ok(serde_json::to_string(&select_fields(value)?)?)
+}
+
+fn select_fields<T: Serialize + ?Sized>(value: &T) -> Result<serde_json::Value> {
+    let value = serde_json::to_value(value)?;
+    Ok(match FIELDS.get() {
+        Some(fields) => crate::cli::fields::select(&value, fields),
+        None => value,
+    })
+}
+
+/// Write formatted output to stdout or stderr, localized and decorated per the current mode
+pub fn write(to_stderr: bool, newline: bool, args: fmt::Arguments) {
+    let catalog = crate::cli::i18n::catalog();
+    if !is_plain() && catalog.is_none() {
+        match (to_stderr, newline) {
+            (false, false) => std::print!("{args}"),
+            (false, true) => std::println!("{args}"),
+            (true, false) => std::eprint!("{args}"),
+            (true, true) => std::eprintln!("{args}"),
+        }
+        return;
+    }
+
+    let text = args.to_string();
+    let text = match catalog {
+        Some(catalog) => catalog.translate(&text),
+        None => Cow::Borrowed(text.as_str()),
+    };
+    let text = if is_plain() {
+        Cow::Owned(strip_decorations(&text))
+    } else {
+        text
+    };
+    let newline = if newline { "\n" } else { "" };
+    // Unlike the std macros, don't panic on write errors such as a closed pipe
+    if to_stderr {
+        let _ = write!(std::io::stderr(), "{text}{newline}");
+    } else {
+        let _ = write!(std::io::stdout(), "{text}{newline}");
+    }
+}
+
+/// Remove emoji and ANSI escape sequences, along with the spacing emoji leave behind
+///
+/// Leading indentation is kept, so `"   ✅ Done"` becomes `"   Done"`.
+pub fn strip_decorations(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequence: ESC [ params final-byte
+            if chars.peek() == Some(&'[') {
+                chars.next();
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        if is_emoji(c) {
+            while chars.peek().is_some_and(|&c| is_emoji(c)) {
+                chars.next();
+            }
+            // Drop the gap after an emoji that started a line or a word
+            if out.is_empty() || out.ends_with(char::is_whitespace) {
+                while chars.peek().is_some_and(|&c| c == ' ') {
+                    chars.next();
+                }
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Emoji, pictographs and the joiners/selectors that combine them
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF   // pictographs, emoticons, transport, flags
+        | 0x2600..=0x27BF   // misc symbols and dingbats (✅ ❌ ⚠ ✓)
+        | 0x2B00..=0x2BFF   // arrows and stars (⬆ ⭐)
+        | 0x2300..=0x23FF   // technical (⏰ ⌛ ⏳)
+        | 0x2139            // ℹ
+        | 0x200D            // zero width joiner
+        | 0x20E3            // combining keycap
+        | 0xFE0F            // emoji presentation selector
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_decorations() {
+        assert_eq!(strip_decorations("✅ Found 3 cast(s):"), "Found 3 cast(s):");
+        assert_eq!(strip_decorations("   ⚠️  Warning"), "   Warning");
+        assert_eq!(strip_decorations("\x1b[38;5;208m#\x1b[0m plain"), "# plain");
+        assert_eq!(strip_decorations("Signer #1: 0xabc"), "Signer #1: 0xabc");
+        assert_eq!(
+            strip_decorations("Requested by 👉 @alice"),
+            "Requested by @alice"
+        );
+    }
+}
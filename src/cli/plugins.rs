@@ -0,0 +1,242 @@
+//! External subcommands: `castorix <name>` runs a `castorix-<name>` plugin
+//!
+//! A command castorix does not know is looked up as a plugin, first in
+//! `<data dir>/plugins/` and then on `PATH`:
+//! - an executable named `castorix-<name>` is run directly;
+//! - a `castorix-<name>.wasm` module in the plugins directory is run with the
+//!   `wasmtime` runtime, which must be on `PATH`.
+//!
+//! The remaining arguments are passed through unchanged, stdin, stdout and
+//! stderr are inherited, and castorix exits with the plugin's exit code. The
+//! plugin gets a [`PluginContext`] as JSON in the `CASTORIX_PLUGIN_CONTEXT`
+//! environment variable. Its schema only ever gains fields within one
+//! [`CONTEXT_SCHEMA_VERSION`].
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::cli::context::Context;
+use crate::core::utils::data_dir;
+
+/// File name prefix of plugins
+pub const PLUGIN_PREFIX: &str = "castorix-";
+
+/// Environment variable holding the [`PluginContext`] JSON
+pub const CONTEXT_ENV: &str = "CASTORIX_PLUGIN_CONTEXT";
+
+/// Version of the [`PluginContext`] schema; bumped on incompatible changes
+pub const CONTEXT_SCHEMA_VERSION: u32 = 1;
+
+/// Runtime used for WASM plugins
+const WASM_RUNTIME: &str = "wasmtime";
+
+/// How a plugin is run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginKind {
+    Executable,
+    Wasm,
+}
+
+/// A discovered plugin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Plugin {
+    /// Command name, without the `castorix-` prefix
+    pub name: String,
+    pub kind: PluginKind,
+    pub path: PathBuf,
+}
+
+/// Endpoints in effect for the command
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginConfig {
+    pub hub_url: String,
+    pub eth_rpc_url: String,
+    pub eth_base_rpc_url: String,
+    pub eth_op_rpc_url: String,
+}
+
+/// Everything a plugin is told about the invocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginContext {
+    pub schema_version: u32,
+    pub castorix_version: String,
+    /// Plugin command name
+    pub plugin: String,
+    /// Arguments after the command name
+    pub args: Vec<String>,
+    /// Data directory of the active profile
+    pub data_dir: PathBuf,
+    /// Active profile name
+    pub profile: String,
+    /// Name of the active context, see [`crate::cli::context`]
+    pub context_name: Option<String>,
+    /// Active context, if any
+    pub context: Option<Context>,
+    pub config: PluginConfig,
+    /// Output should be plain text without emoji or colors (`--plain`)
+    pub plain: bool,
+    /// The plugin must not prompt (`--non-interactive`)
+    pub non_interactive: bool,
+}
+
+/// Directory searched for plugins before `PATH`
+pub fn plugins_dir() -> PathBuf {
+    data_dir::data_dir().join("plugins")
+}
+
+/// All plugins, the first found for each name winning
+///
+/// # Returns
+/// * `Vec<Plugin>` - Plugins sorted by name
+pub fn discover() -> Vec<Plugin> {
+    let mut dirs = vec![plugins_dir()];
+    if let Some(path) = std::env::var_os("PATH") {
+        dirs.extend(std::env::split_paths(&path));
+    }
+    let mut found = BTreeMap::new();
+    for (i, dir) in dirs.iter().enumerate() {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        // WASM modules are only picked up from the plugins directory
+        let mut plugins: Vec<Plugin> = entries
+            .flatten()
+            .filter_map(|entry| plugin_at(&entry.path()))
+            .filter(|plugin| i == 0 || plugin.kind == PluginKind::Executable)
+            .collect();
+        plugins.sort_by(|a, b| a.path.cmp(&b.path));
+        for plugin in plugins {
+            found.entry(plugin.name.clone()).or_insert(plugin);
+        }
+    }
+    found.into_values().collect()
+}
+
+/// The plugin providing command `name`, if any
+pub fn find(name: &str) -> Option<Plugin> {
+    discover().into_iter().find(|plugin| plugin.name == name)
+}
+
+/// Recognize a plugin file by its name and permissions
+fn plugin_at(path: &Path) -> Option<Plugin> {
+    let file_name = path.file_name()?.to_str()?;
+    let stem = file_name.strip_prefix(PLUGIN_PREFIX)?;
+    let (name, kind) = match stem.strip_suffix(".wasm") {
+        Some(name) => (name, PluginKind::Wasm),
+        None => (
+            stem.strip_suffix(std::env::consts::EXE_SUFFIX)
+                .filter(|_| !std::env::consts::EXE_SUFFIX.is_empty())
+                .unwrap_or(stem),
+            PluginKind::Executable,
+        ),
+    };
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid_name || !path.is_file() {
+        return None;
+    }
+    if kind == PluginKind::Executable && !is_executable(path) {
+        return None;
+    }
+    Some(Plugin {
+        name: name.to_string(),
+        kind,
+        path: path.to_path_buf(),
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.metadata()
+        .is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+impl Plugin {
+    /// Run the plugin and wait for it
+    ///
+    /// # Arguments
+    /// * `context` - Passed to the plugin in [`CONTEXT_ENV`]
+    ///
+    /// # Returns
+    /// * `Result<i32>` - The plugin's exit code (1 if it was killed by a signal)
+    pub fn run(&self, context: &PluginContext) -> Result<i32> {
+        let context_json = serde_json::to_string(context)?;
+        let mut command = match self.kind {
+            PluginKind::Executable => {
+                let mut command = std::process::Command::new(&self.path);
+                command.env(CONTEXT_ENV, &context_json);
+                command
+            }
+            PluginKind::Wasm => {
+                let mut command = std::process::Command::new(WASM_RUNTIME);
+                command
+                    .arg("run")
+                    .arg("--env")
+                    .arg(format!("{CONTEXT_ENV}={context_json}"))
+                    .arg(&self.path)
+                    .arg("--");
+                command
+            }
+        };
+        let status = command
+            .args(&context.args)
+            .status()
+            .with_context(|| match self.kind {
+                PluginKind::Executable => format!("Failed to run plugin {}", self.path.display()),
+                PluginKind::Wasm => format!(
+                    "Failed to run WASM plugin {}; is {WASM_RUNTIME} installed and on PATH?",
+                    self.path.display()
+                ),
+            })?;
+        Ok(status.code().unwrap_or(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_at() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, b"#!/bin/sh\n").unwrap();
+            path
+        };
+
+        let wasm = plugin_at(&write("castorix-stats.wasm")).unwrap();
+        assert_eq!(wasm.name, "stats");
+        assert_eq!(wasm.kind, PluginKind::Wasm);
+        assert!(plugin_at(&write("other-tool")).is_none());
+        assert!(plugin_at(&write("castorix-")).is_none());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let script = write("castorix-hello");
+            assert!(plugin_at(&script).is_none());
+            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+            let plugin = plugin_at(&script).unwrap();
+            assert_eq!(plugin.name, "hello");
+            assert_eq!(plugin.kind, PluginKind::Executable);
+        }
+    }
+}
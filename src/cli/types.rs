@@ -8,7 +8,12 @@ pub enum ApiCommands {
     /// Run the HTTP REST API server to expose Farcaster query capabilities via HTTP endpoints.
     /// The server provides a traditional RESTful API with /api/* routes.
     ///
+    /// With --upstream, reads are spread round-robin over the hub and every
+    /// upstream, skipping hubs that fail health checks; /healthz reports the
+    /// state of each upstream.
+    ///
     /// Example: castorix api serve --port 3000
+    /// Example: castorix api serve --upstream https://hub-b.example --upstream https://hub-c.example
     Serve {
         /// Host to bind to (default: 0.0.0.0)
         #[arg(long, default_value = "0.0.0.0")]
@@ -17,6 +22,95 @@ pub enum ApiCommands {
         /// Port to bind to (default: 3000)
         #[arg(long, default_value = "3000")]
         port: u16,
+
+        /// Additional hub to serve reads from; repeat for more
+        #[arg(long = "upstream")]
+        upstreams: Vec<String>,
+    },
+}
+
+/// Load testing commands
+#[derive(Subcommand)]
+pub enum BenchCommands {
+    /// 📈 Load test a running REST API server
+    ///
+    /// Send concurrent requests to an API server started with `castorix api serve`
+    /// and report p50/p95/p99 latency and error rates per route. Routes take an
+    /// optional weight (PATH:WEIGHT) and `{fid}` is replaced by the given FIDs in turn.
+    ///
+    /// Example: castorix bench api --concurrency 50 --requests 5000
+    /// Example: castorix bench api --route '/v1/hub/users/{fid}:3' --route /health --fids 3,2,194 --duration 60
+    /// Example: castorix bench api --pattern ramp --ramp-up 30 --duration 120 --rate 200
+    Api {
+        /// Base URL of the API server
+        #[arg(long, default_value = "http://127.0.0.1:3000")]
+        url: String,
+
+        /// Route to request as PATH[:WEIGHT]; repeat for a mix (default: hub user routes)
+        #[arg(long = "route")]
+        routes: Vec<String>,
+
+        /// Comma separated FIDs substituted for {fid}
+        #[arg(long, value_delimiter = ',', default_value = "3")]
+        fids: Vec<u64>,
+
+        /// Number of concurrent workers
+        #[arg(long, short = 'c', default_value = "10")]
+        concurrency: usize,
+
+        /// Total number of requests (default: 1000 unless --duration is given)
+        #[arg(long, short = 'n')]
+        requests: Option<u64>,
+
+        /// Run for this many seconds
+        #[arg(long, short = 'd')]
+        duration: Option<u64>,
+
+        /// Cap the overall request rate (requests per second)
+        #[arg(long)]
+        rate: Option<f64>,
+
+        /// How workers are started: steady (all at once) or ramp (spread over --ramp-up)
+        #[arg(long, default_value = "steady", value_parser = ["steady", "ramp"])]
+        pattern: String,
+
+        /// Seconds over which workers are started with --pattern ramp
+        #[arg(long, default_value = "10")]
+        ramp_up: u64,
+
+        /// Per-request timeout in seconds
+        #[arg(long, default_value = "30")]
+        timeout: u64,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Local development commands
+#[cfg(feature = "dev")]
+#[derive(Subcommand)]
+pub enum DevCommands {
+    /// 🚰 Fund an address from the local dev faucet
+    ///
+    /// Send ETH from Anvil's default pre-funded account (or a custom funder key)
+    /// to an address. Only works when the RPC endpoint is a local chain (chain id 31337 or 1337).
+    ///
+    /// Example: castorix dev faucet 0x70997970C51812dc3A010C7d01b50e0d17dc79C8
+    /// Example: castorix dev faucet 0x7099... --amount 1 --rpc-url http://127.0.0.1:8545
+    Faucet {
+        /// Address to fund
+        address: String,
+        /// Amount of ETH to send (default: 10)
+        #[arg(long, default_value = "10")]
+        amount: String,
+        /// Local node RPC URL
+        #[arg(long, default_value = "http://127.0.0.1:8545")]
+        rpc_url: String,
+        /// Funder private key (defaults to Anvil account 0)
+        #[arg(long)]
+        funder_key: Option<String>,
     },
 }
 
@@ -28,8 +122,16 @@ pub enum McpCommands {
     /// Run the MCP server to expose Farcaster query tools to AI assistants.
     /// The server communicates via JSON-RPC 2.0 over stdin/stdout.
     ///
+    /// Tools can be disabled and their arguments limited in a configuration
+    /// file, by default mcp.json in the castorix data directory.
+    ///
     /// Example: castorix mcp serve
-    Serve,
+    /// Example: castorix mcp serve --config ./mcp-readonly.json
+    Serve {
+        /// Tool permissions file (defaults to mcp.json in the data directory)
+        #[arg(long)]
+        config: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -118,10 +220,18 @@ pub enum KeyCommands {
     /// You'll be prompted for the password to confirm deletion.
     /// ⚠️  WARNING: This action cannot be undone!
     ///
+    /// Deletion is refused while the key's address is still the custody address
+    /// of a FID, since the FID could then only be recovered; pass --force to
+    /// delete anyway.
+    ///
     /// Example: castorix key delete old-wallet
+    /// Example: castorix key delete old-wallet --force
     Delete {
         /// Name of the encrypted key to delete
         key_name: String,
+        /// Delete even if the key is still in use on-chain or that cannot be checked
+        #[arg(long)]
+        force: bool,
     },
 
     /// 🔄 Rename an encrypted key
@@ -149,6 +259,28 @@ pub enum KeyCommands {
         /// New alias
         new_alias: String,
     },
+
+    /// 🛡️ Audit stored keys for security problems
+    ///
+    /// Inspect wallet, custody and Ed25519 key files and report weak spots:
+    /// loose file permissions, weak encryption passwords (with --check-passwords),
+    /// addresses never used on-chain, signer keys that are not registered and
+    /// custody FIDs without a recovery address.
+    ///
+    /// Example: castorix key audit
+    /// Example: castorix key audit --check-passwords
+    /// Example: castorix key audit --offline --json
+    Audit {
+        /// Prompt for each key's password and score its strength
+        #[arg(long)]
+        check_passwords: bool,
+        /// Skip hub and on-chain checks
+        #[arg(long)]
+        offline: bool,
+        /// Output the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -179,10 +311,16 @@ pub enum HubKeyCommands {
     /// This will remove the encrypted key file but will not affect the on-chain state.
     /// ⚠️  WARNING: This action cannot be undone!
     ///
+    /// Deletion is refused while the key is still the FID's custody key unless
+    /// --force is given.
+    ///
     /// Example: castorix hub key delete 12345
     Delete {
         /// FID (Farcaster ID) of the key to delete
         fid: u64,
+        /// Delete even if the key is still in use on-chain or that cannot be checked
+        #[arg(long)]
+        force: bool,
     },
 
     /// 🌱 Generate ECDSA key from recovery phrase
@@ -196,6 +334,26 @@ pub enum HubKeyCommands {
         /// FID (Farcaster ID) for this key
         fid: u64,
     },
+
+    /// 📊 Count hub messages signed by each Ed25519 signer of a FID
+    ///
+    /// Scans the FID's casts, reactions, links, user data and verifications and
+    /// groups them by signer, showing each signer's message count and last use.
+    /// Signers that have not signed anything recently are candidates for removal
+    /// from KeyRegistry: every registered signer can post on the FID's behalf.
+    ///
+    /// Example: castorix hub key stats 12345
+    /// Example: castorix hub key stats 12345 --stale-days 90 --json
+    Stats {
+        /// FID (Farcaster ID) whose signers to inspect
+        fid: u64,
+        /// Flag signers with no messages in this many days as stale
+        #[arg(long, default_value = "180")]
+        stale_days: u64,
+        /// Output the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -234,21 +392,37 @@ pub enum SignersCommands {
     ///
     /// The generated private key will be encrypted and stored securely.
     ///
+    /// With --wait-hub the command only returns once the hub has indexed the
+    /// new key, so messages can be signed with it straight away.
+    ///
+    /// With --app-fid the key is requested by an app FID instead of the user's
+    /// own: the app's custody key (imported with 'castorix custody import
+    /// <APP_FID>') signs the SignedKeyRequest, and clients show the signer as
+    /// belonging to that app.
+    ///
     /// Example: castorix signers register 12345
     /// Example: castorix signers register 12345 --wallet my-wallet
     /// Example: castorix signers register 12345 --payment-wallet gas-payer --dry-run
+    /// Example: castorix signers register 12345 --wait-hub
+    /// Example: castorix signers register 12345 --app-fid 9152
     Register {
         /// FID (Farcaster ID) to register signer to
         fid: u64,
         /// ECDSA wallet name for custody key (optional, auto-detected if not provided)
         #[arg(long)]
         wallet: Option<String>,
+        /// App FID that requests the key (defaults to the user's FID)
+        #[arg(long)]
+        app_fid: Option<u64>,
         /// ECDSA wallet name for gas payment (optional, defaults to custody wallet)
         #[arg(long)]
         payment_wallet: Option<String>,
         /// Simulate the transaction without sending it to the chain
         #[arg(long)]
         dry_run: bool,
+        /// Wait until the hub has indexed the new signer before exiting
+        #[arg(long)]
+        wait_hub: bool,
         /// Automatically confirm the operation without prompting
         #[arg(long)]
         yes: bool,
@@ -269,6 +443,7 @@ pub enum SignersCommands {
     /// Example: castorix signers unregister 12345
     /// Example: castorix signers unregister 12345 --wallet my-wallet
     /// Example: castorix signers unregister 12345 --payment-wallet gas-payer --dry-run
+    /// Example: castorix signers unregister 12345 --wait-hub
     Unregister {
         /// FID (Farcaster ID) to unregister signer from
         fid: u64,
@@ -281,6 +456,45 @@ pub enum SignersCommands {
         /// Simulate the transaction without sending it to the chain
         #[arg(long)]
         dry_run: bool,
+        /// Wait until the hub has indexed the removal before exiting
+        #[arg(long)]
+        wait_hub: bool,
+        /// Automatically confirm the operation without prompting
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// 🔄 Rotate a FID's signer to a new key
+    ///
+    /// Replace a signer in one workflow: generate a new Ed25519 key, register
+    /// it through the KeyGateway, wait until the hub has indexed it, optionally
+    /// have the hub validate a canary message signed with it, store it locally
+    /// and finally remove the old key from the KeyRegistry.
+    ///
+    /// The old key defaults to the FID's locally stored signer. If any step
+    /// before the old key's removal fails, the new key is removed again so the
+    /// FID is left as it was.
+    ///
+    /// ⚠️  WARNING: This sends two on-chain transactions and consumes gas fees.
+    ///
+    /// Example: castorix signers rotate 12345
+    /// Example: castorix signers rotate 12345 --canary
+    /// Example: castorix signers rotate 12345 --old-key 0x48400d66... --dry-run
+    Rotate {
+        /// FID (Farcaster ID) whose signer to rotate
+        fid: u64,
+        /// Public key of the signer to replace (default: the locally stored signer)
+        #[arg(long)]
+        old_key: Option<String>,
+        /// Have the hub validate a message signed with the new key before removing the old one
+        #[arg(long)]
+        canary: bool,
+        /// Show the rotation plan without sending transactions
+        #[arg(long)]
+        dry_run: bool,
+        /// Automatically confirm the operation without prompting
+        #[arg(long)]
+        yes: bool,
     },
 
     /// 📥 Import an Ed25519 signer key
@@ -289,11 +503,38 @@ pub enum SignersCommands {
     /// The private key will be encrypted and stored locally.
     /// This allows you to use an existing Ed25519 key as a signer.
     ///
+    /// Keys can also be migrated from other clients without registering a new
+    /// signer on-chain: derive one from a recovery phrase (SLIP-0010, see --path)
+    /// or read a protobuf or JSON key export. The imported key is checked against
+    /// the FID's active signers on the hub.
+    ///
     /// Example: castorix signers import 12345
-    #[clap(hide = true)]
+    /// Example: castorix signers import 12345 --format mnemonic
+    /// Example: castorix signers import 12345 --format protobuf --file signer.bin
+    /// Example: castorix signers import 12345 --format json --file signer.json
+    /// Example: castorix signers import 12345 --on-conflict keep-both --label laptop
+    ///
+    /// If the FID already has a local key, --on-conflict decides what happens:
+    /// abort, overwrite (after backing up the keys file) or keep-both (the
+    /// current key is kept under a label). Without it you are asked.
     Import {
         /// FID (Farcaster ID) for this signer key
         fid: u64,
+        /// Format of the key: hex, mnemonic, protobuf or json
+        #[arg(long, default_value = "hex", value_parser = ["hex", "mnemonic", "protobuf", "json"])]
+        format: String,
+        /// Read the key from a file instead of prompting for it
+        #[arg(long)]
+        file: Option<String>,
+        /// SLIP-0010 derivation path for --format mnemonic
+        #[arg(long, default_value = crate::core::crypto::key_import::DEFAULT_SIGNER_DERIVATION_PATH)]
+        path: String,
+        /// What to do if the FID already has a local key
+        #[arg(long, value_enum)]
+        on_conflict: Option<crate::core::crypto::encrypted_storage::ImportConflict>,
+        /// Label for the current key with --on-conflict keep-both
+        #[arg(long)]
+        label: Option<String>,
     },
 
     /// 📤 Export a local Ed25519 signer key
@@ -328,11 +569,91 @@ pub enum SignersCommands {
     /// ⚠️  WARNING: This action cannot be undone. The private key will be permanently deleted.
     /// Make sure to export/backup the key first using 'castorix signers export <pubkey>'.
     ///
+    /// Deletion is refused while the key is still registered in the KeyRegistry:
+    /// unregister it first with 'castorix signers unregister', or pass --force.
+    ///
     /// Example: castorix signers delete 48400d66960f2c4450e8847ad87b40274fd16d2796ece2a938219a8a737803cc
     /// Example: castorix signers delete 1
     Delete {
         /// Public key or index number of the Ed25519 signer to delete
         identifier: String,
+        /// Delete even if the key is still in use on-chain or that cannot be checked
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// 🪪 Add an auth address to a FID
+    ///
+    /// Register an Ethereum address as an auth address key (KeyRegistry key type 2)
+    /// through the KeyGateway. Messages can then be signed by that address with
+    /// EIP-712 instead of an Ed25519 signer.
+    ///
+    /// The FID's custody wallet is loaded from local custody storage.
+    ///
+    /// ⚠️  WARNING: This triggers on-chain operations and consumes gas fees.
+    ///
+    /// Example: castorix signers add-auth-address 12345 0x70997970C51812dc3A010C7d01b50e0d17dc79C8
+    /// Example: castorix signers add-auth-address 12345 0x7099... --dry-run
+    AddAuthAddress {
+        /// FID (Farcaster ID) to add the auth address to
+        fid: u64,
+        /// Ethereum address to authorize
+        address: String,
+        /// Check the FID and KeyRegistry support without sending a transaction
+        #[arg(long)]
+        dry_run: bool,
+        /// Automatically confirm the operation without prompting
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// 🚫 Remove an auth address from a FID
+    ///
+    /// Remove an auth address key from the KeyRegistry with a removeFor
+    /// authorization signed by the FID's custody wallet.
+    ///
+    /// ⚠️  WARNING: This triggers on-chain operations and consumes gas fees.
+    ///
+    /// Example: castorix signers remove-auth-address 12345 0x70997970C51812dc3A010C7d01b50e0d17dc79C8
+    /// Example: castorix signers remove-auth-address 12345 0x7099... --payment-wallet gas-payer
+    RemoveAuthAddress {
+        /// FID (Farcaster ID) to remove the auth address from
+        fid: u64,
+        /// Auth address to remove
+        address: String,
+        /// ECDSA wallet name for gas payment (optional, defaults to custody wallet)
+        #[arg(long)]
+        payment_wallet: Option<String>,
+        /// Sign the authorization without sending the transaction
+        #[arg(long)]
+        dry_run: bool,
+        /// Automatically confirm the operation without prompting
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// ⛓️ List a FID's keys straight from the KeyRegistry
+    ///
+    /// Page through keysOf(fid) on Optimism without going through a hub, and
+    /// decode the SignedKeyRequestMetadata each key was added with to show which
+    /// app (requesting FID) asked for the signer, who signed the request and its
+    /// deadline. Metadata comes from the KeyRegistry Add events.
+    ///
+    /// Example: castorix signers onchain 12345
+    /// Example: castorix signers onchain 12345 --include-removed
+    /// Example: castorix signers onchain 12345 --from-block 120000000
+    Onchain {
+        /// FID (Farcaster ID) to list keys for
+        fid: u64,
+        /// Also list keys that were removed
+        #[arg(long)]
+        include_removed: bool,
+        /// Keys requested per keysOf call
+        #[arg(long, default_value_t = 100)]
+        page_size: u64,
+        /// First block to search for Add events (defaults to the KeyRegistry deployment)
+        #[arg(long)]
+        from_block: Option<u64>,
     },
 }
 
@@ -368,15 +689,123 @@ pub enum CustodyCommands {
         fid: u64,
     },
 
+    /// 🌳 Derive an ECDSA key from a mnemonic account index
+    ///
+    /// Derive the custody key for a FID from a specific BIP44 account of a mnemonic,
+    /// so one mnemonic can manage several FIDs. Index N uses m/44'/60'/0'/0/N.
+    /// The derived address must match the FID's custody address.
+    ///
+    /// Example: castorix custody derive 12345 --index 2
+    /// Example: castorix custody derive 12345 --path "m/44'/60'/1'/0/0"
+    Derive {
+        /// FID (Farcaster ID) for this key
+        fid: u64,
+        /// Account index in the standard Ethereum derivation path
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+        /// Full BIP32 derivation path (overrides --index)
+        #[arg(long, conflicts_with = "index")]
+        path: Option<String>,
+    },
+
+    /// ✨ Generate an ECDSA key with a vanity address
+    ///
+    /// Try random keys on all CPU cores until the address starts and/or ends with
+    /// the given hex characters, then store the key encrypted for the FID like an
+    /// imported key. Every extra character makes the search 16 times longer.
+    ///
+    /// Example: castorix custody generate-vanity 12345 --prefix 0xCAFE
+    /// Example: castorix custody generate-vanity 12345 --suffix beef --threads 4
+    GenerateVanity {
+        /// FID (Farcaster ID) for this key
+        fid: u64,
+        /// Hex characters the address must start with
+        #[arg(long)]
+        prefix: Option<String>,
+        /// Hex characters the address must end with
+        #[arg(long)]
+        suffix: Option<String>,
+        /// Match letter case against the EIP-55 checksum address
+        #[arg(long)]
+        checksum: bool,
+        /// Worker threads (defaults to the number of CPU cores)
+        #[arg(long)]
+        threads: Option<usize>,
+        /// Give up after this many attempts
+        #[arg(long)]
+        max_attempts: Option<u64>,
+    },
+
     /// 🗑️ Delete an ECDSA key
     ///
     /// Remove an ECDSA key for a specific FID from local storage.
     /// This will permanently delete the encrypted key file.
     ///
+    /// Deletion is refused while the key is still the FID's custody key unless
+    /// --force is given.
+    ///
     /// Example: castorix custody delete 12345
     Delete {
         /// FID (Farcaster ID) to delete key for
         fid: u64,
+        /// Delete even if the key is still in use on-chain or that cannot be checked
+        #[arg(long)]
+        force: bool,
+    },
+    /// 💰 Check custody address ETH balance
+    ///
+    /// Show the ETH balance of a FID's custody address on Optimism or Base.
+    /// The address is read from the IdRegistry on Optimism (or the hub if that fails).
+    ///
+    /// Example: castorix custody balance 12345
+    /// Example: castorix custody balance 12345 --chain base
+    Balance {
+        /// FID (Farcaster ID) whose custody address to check
+        fid: u64,
+        /// Chain to query: optimism or base
+        #[arg(long, default_value = "optimism")]
+        chain: String,
+    },
+
+    /// 💸 Fund a custody address from a managed wallet
+    ///
+    /// Transfer ETH from one of your encrypted wallets to a FID's custody address.
+    ///
+    /// ⚠️  WARNING: This triggers an on-chain transfer.
+    ///
+    /// Example: castorix custody fund 12345 --from my-wallet --amount 0.01
+    Fund {
+        /// FID (Farcaster ID) whose custody address to fund
+        fid: u64,
+        /// Name of the encrypted wallet to send ETH from
+        #[arg(long)]
+        from: String,
+        /// Amount of ETH to send (e.g. 0.01)
+        #[arg(long)]
+        amount: String,
+        /// Chain to send on: optimism or base
+        #[arg(long, default_value = "optimism")]
+        chain: String,
+        /// Automatically confirm the operation without prompting
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// 🕵️ Report an address's activity on the Farcaster contracts
+    ///
+    /// Summarize the FIDs an address owns now or owned before, the signers added
+    /// to them, the storage it rented and the ETH it spent doing so. History comes
+    /// from the local event index (run 'castorix indexer sync' first); current
+    /// ownership and key counts are read from chain in one Multicall3 batch.
+    ///
+    /// Example: castorix custody report 0x8773442740C17C9d0F0B87022c722F9a136206eD
+    /// Example: castorix custody report 0x8773... --no-costs
+    Report {
+        /// Address to report on
+        address: String,
+        /// Skip fetching transactions to total the ETH spent
+        #[arg(long)]
+        no_costs: bool,
     },
 }
 
@@ -463,7 +892,10 @@ pub enum EnsCommands {
     /// Generate a signed proof linking your ENS domain to your Farcaster ID.
     /// This proof can be submitted to Farcaster to verify domain ownership.
     ///
+    /// The proof is kept in the managed proofs directory; see `castorix ens proofs`.
+    ///
     /// Example: castorix ens proof mydomain.eth 12345 --wallet-name my-wallet
+    /// Example: castorix ens proof mydomain.eth 12345 --output proof.json
     Proof {
         /// ENS domain name
         domain: String,
@@ -472,6 +904,52 @@ pub enum EnsCommands {
         /// Wallet name for encrypted key (required)
         #[arg(long)]
         wallet_name: Option<String>,
+        /// Also write a copy of the proof to this file
+        #[arg(long)]
+        output: Option<String>,
+    },
+
+    /// 🚦 Check many domains are ready for username proofs
+    ///
+    /// For each domain in the file, check that it is a valid ENS name, that it
+    /// resolves to the FID's custody address or one of its verified addresses,
+    /// and that a subname cannot be reclaimed or has expired. Nothing is signed.
+    /// Exits with an error if any domain is not ready.
+    ///
+    /// Example: castorix ens precheck --file domains.txt --fid 12345
+    Precheck {
+        /// File with one domain per line; blank lines and `#` comments are skipped
+        #[arg(long)]
+        file: String,
+        /// Farcaster ID the proofs would be for
+        #[arg(long)]
+        fid: u64,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+        /// Domains checked at the same time
+        #[arg(long, default_value = "4")]
+        concurrency: usize,
+    },
+
+    /// 🗄️ Manage stored username proofs
+    ///
+    /// Every generated proof is stored under the data directory together with
+    /// its creation time and the result of each submission to a hub.
+    ///
+    /// Example: castorix ens proofs list
+    Proofs {
+        #[command(subcommand)]
+        action: ProofsCommands,
+    },
+
+    /// 🔵 Register and renew basenames on Base
+    ///
+    /// Example: castorix ens base register alice --fid 12345
+    /// Example: castorix ens base renew alice.base.eth --wallet-name my-wallet --years 2
+    Base {
+        #[command(subcommand)]
+        action: EnsBaseCommands,
     },
 
     /// 🔍 Verify a username proof
@@ -486,6 +964,96 @@ pub enum EnsCommands {
     },
 }
 
+/// Stored username proof commands
+#[derive(Subcommand)]
+pub enum ProofsCommands {
+    /// 📋 List stored proofs
+    ///
+    /// Example: castorix ens proofs list
+    List,
+
+    /// 🔍 Show a stored proof and its submission history
+    ///
+    /// Example: castorix ens proofs show mydomain_eth_12345_20250101T120000
+    Show {
+        /// Proof id as shown by `ens proofs list`
+        id: String,
+    },
+
+    /// 🗑️ Delete a stored proof
+    ///
+    /// Example: castorix ens proofs delete mydomain_eth_12345_20250101T120000
+    Delete {
+        /// Proof id as shown by `ens proofs list`
+        id: String,
+        /// Delete without prompting
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+/// Basename registrar commands
+///
+/// Transactions are paid by the custody wallet of `--fid` or by the encrypted
+/// wallet named with `--wallet-name`.
+#[derive(Subcommand)]
+pub enum EnsBaseCommands {
+    /// 📝 Register a basename
+    ///
+    /// Check the name is available, show its price and register it through the
+    /// Basenames registrar controller. The name's address record is set to the
+    /// paying wallet, so it can be used for a username proof right away.
+    ///
+    /// ⚠️  WARNING: This triggers an on-chain transaction on Base.
+    ///
+    /// Example: castorix ens base register alice --fid 12345
+    /// Example: castorix ens base register alice.base.eth --wallet-name my-wallet --years 2 --primary
+    Register {
+        /// Basename to register (alice or alice.base.eth)
+        name: String,
+        /// Registration period in years
+        #[arg(long, default_value = "1")]
+        years: u64,
+        /// Pay with the custody wallet of this FID
+        #[arg(long, conflicts_with = "wallet_name")]
+        fid: Option<u64>,
+        /// Pay with this encrypted wallet
+        #[arg(long)]
+        wallet_name: Option<String>,
+        /// Also set the name as the wallet's primary name
+        #[arg(long)]
+        primary: bool,
+        /// Automatically confirm the transaction without prompting
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// 🔁 Renew a basename
+    ///
+    /// Extend a basename's registration. Anyone can pay for a renewal; the
+    /// owner does not change.
+    ///
+    /// ⚠️  WARNING: This triggers an on-chain transaction on Base.
+    ///
+    /// Example: castorix ens base renew alice --fid 12345 --years 2
+    Renew {
+        /// Basename to renew (alice or alice.base.eth)
+        name: String,
+        /// Renewal period in years
+        #[arg(long, default_value = "1")]
+        years: u64,
+        /// Pay with the custody wallet of this FID
+        #[arg(long, conflicts_with = "wallet_name")]
+        fid: Option<u64>,
+        /// Pay with this encrypted wallet
+        #[arg(long)]
+        wallet_name: Option<String>,
+        /// Automatically confirm the transaction without prompting
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum HubCommands {
     /// 👤 Get user information
@@ -507,16 +1075,24 @@ pub enum HubCommands {
     /// The system will automatically use the Ed25519 key bound to the specified FID.
     /// If no Ed25519 key exists for the FID, an error will be displayed.
     ///
+    /// The proof may be a file path or the id of a stored proof; submissions of
+    /// stored proofs are recorded in their history.
+    ///
     /// Example: castorix hub submit-proof proof.json 12345
+    /// Example: castorix hub submit-proof mydomain_eth_12345_20250101T120000 12345
     /// Example: castorix hub submit-proof proof.json 12345 --wallet-name my-wallet
+    /// Example: castorix hub submit-proof proof.json 12345 --auth-wallet my-auth-address
     SubmitProof {
-        /// Path to proof JSON file
+        /// Path to proof JSON file, or a stored proof id
         proof_file: String,
         /// FID (Farcaster ID) for Ed25519 key signing
         fid: u64,
         /// Wallet name for encrypted key (required)
         #[arg(long)]
         wallet_name: Option<String>,
+        /// Sign with this encrypted wallet as an auth address instead of an Ed25519 signer
+        #[arg(long, conflicts_with = "wallet_name")]
+        auth_wallet: Option<String>,
     },
 
     /// 🔍 Get Ethereum addresses for a FID
@@ -541,6 +1117,23 @@ pub enum HubCommands {
         fid: u64,
     },
 
+    /// 🪪 List username proofs for a FID
+    ///
+    /// Show every fname, ENS and Basename proof the hub has associated with a
+    /// Farcaster ID, with its type, timestamp and owner address. Check this before
+    /// submitting a new proof to see which names are already linked.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix hub proofs 12345
+    /// Example: castorix hub proofs 12345 --json
+    Proofs {
+        /// Farcaster ID (FID)
+        fid: u64,
+        /// Show the proofs as JSON instead of formatted output
+        #[arg(long)]
+        json: bool,
+    },
+
     /// 🏠 Get custody address for a FID
     ///
     /// Retrieve the custody address (Ethereum address) associated with a specific Farcaster ID.
@@ -569,12 +1162,20 @@ pub enum HubCommands {
     ///
     /// Example: castorix hub followers 12345
     /// Example: castorix hub followers 12345 --limit 0  # Get all followers
+    /// Example: castorix hub followers 12345 --since 7d  # Followed in the last week
     Followers {
         /// Farcaster ID (FID) to get followers for
         fid: u64,
         /// Maximum number of followers to retrieve (0 for all, default: 1000)
         #[arg(long, default_value = "1000")]
         limit: u32,
+        /// Only follows from this time on: a date (2024-06-01), an RFC 3339 time
+        /// or an age such as 7d
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Only follows before this time; a date includes that whole day
+        #[arg(long, value_name = "TIME")]
+        until: Option<String>,
     },
 
     /// 👤 Get following for a FID
@@ -590,6 +1191,50 @@ pub enum HubCommands {
         /// Maximum number of following to retrieve (0 for all, default: 1000)
         #[arg(long, default_value = "1000")]
         limit: u32,
+        /// Only follows from this time on: a date (2024-06-01), an RFC 3339 time
+        /// or an age such as 7d
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Only follows before this time; a date includes that whole day
+        #[arg(long, value_name = "TIME")]
+        until: Option<String>,
+    },
+
+    /// 📦 Export the followers, following, casts or reactions of a FID to JSONL
+    ///
+    /// Write every message as one hub JSON message per line, the format
+    /// `--offline` snapshots read. A checkpoint (page token and counts) is saved
+    /// next to the output after each page, so re-running an interrupted export
+    /// resumes where it stopped. The checkpoint is removed once the export completes.
+    /// With --since/--until only messages from that window are written; casts
+    /// and reactions stop paging once past its end.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix hub export followers 3 --output followers-3.jsonl
+    /// Example: castorix hub export casts 3 --output casts-3.jsonl --fresh
+    /// Example: castorix hub export reactions 3 --output june.jsonl --since 2024-06-01 --until 2024-06-30
+    Export {
+        /// What to export
+        #[arg(value_parser = ["followers", "following", "casts", "reactions"])]
+        what: String,
+        /// Farcaster ID (FID) to export
+        fid: u64,
+        /// JSONL file to write
+        #[arg(long)]
+        output: String,
+        /// Require a checkpoint to resume from instead of starting over when there is none
+        #[arg(long, conflicts_with = "fresh")]
+        resume: bool,
+        /// Ignore any checkpoint and start the export over
+        #[arg(long)]
+        fresh: bool,
+        /// Only messages from this time on: a date (2024-06-01), an RFC 3339 time
+        /// or an age such as 7d
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Only messages before this time; a date includes that whole day
+        #[arg(long, value_name = "TIME")]
+        until: Option<String>,
     },
 
     /// 👤 Get user profile for a FID
@@ -609,29 +1254,185 @@ pub enum HubCommands {
         all: bool,
     },
 
-    /// 📊 Get user statistics for a FID
+    /// ✏️ Update profile fields for a FID
     ///
-    /// Retrieve statistics and storage limits for the specified Farcaster ID.
-    /// This shows follower count, following count, and storage usage.
-    /// This is a read-only operation that doesn't require authentication.
+    /// Fetch the current profile, show a field-by-field diff and submit one
+    /// USER_DATA_ADD message per changed field, signed with the FID's stored
+    /// Ed25519 signer. Fields that already have the requested value are skipped,
+    /// so no storage is spent on them. Pass an empty value to clear a field.
     ///
-    /// Example: castorix hub stats 12345
-    Stats {
-        /// Farcaster ID (FID) to get statistics for
+    /// Example: castorix hub set-profile 12345 --bio "Building on Farcaster" --dry-run
+    /// Example: castorix hub set-profile 12345 --display "Alice" --url https://example.com
+    SetProfile {
+        /// Farcaster ID (FID) whose profile to update
+        fid: u64,
+        /// Username (fname or ENS name with a proof)
+        #[arg(long)]
+        username: Option<String>,
+        /// Display name
+        #[arg(long)]
+        display: Option<String>,
+        /// Bio
+        #[arg(long)]
+        bio: Option<String>,
+        /// Profile picture URL
+        #[arg(long)]
+        pfp: Option<String>,
+        /// Banner image URL
+        #[arg(long)]
+        banner: Option<String>,
+        /// Website URL
+        #[arg(long)]
+        url: Option<String>,
+        /// Location
+        #[arg(long)]
+        location: Option<String>,
+        /// Twitter/X username
+        #[arg(long)]
+        twitter: Option<String>,
+        /// GitHub username
+        #[arg(long)]
+        github: Option<String>,
+        /// Show the diff without submitting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// 📝 Publish a cast
+    ///
+    /// Validate the text (320 bytes), embeds (at most 2, URLs before quoted
+    /// casts) and parent against the hub's rules, then sign the cast with the FID's stored Ed25519 signer and
+    /// submit it. With --thread, text over the limit is split at paragraphs,
+    /// sentences or words into a numbered reply chain instead of being rejected.
+    /// With --long, such text is published as a single long cast (up to 1024
+    /// bytes) or 10k cast when the hub supports it; otherwise the command fails,
+    /// or falls back to a thread when --thread is also given.
+    /// @username tokens (fnames or ENS names) are resolved to FIDs and encoded as
+    /// mentions; names that don't resolve stay as plain text.
+    ///
+    /// Example: castorix hub cast 12345 "gm farcaster"
+    /// Example: castorix hub cast 12345 "Great talk @dwr and @vitalik.eth"
+    /// Example: castorix hub cast 12345 "Release notes..." --embed-url https://example.com --dry-run
+    /// Example: castorix hub cast 12345 "This 👇" --embed-cast 678:0xa1b2c3d4e5f60718293a4b5c6d7e8f9012345678
+    /// Example: castorix hub cast 12345 "$(cat post.txt)" --thread
+    /// Example: castorix hub cast 12345 "$(cat essay.txt)" --long --thread
+    /// Example: castorix hub cast 12345 "Agreed!" --reply-to 678:0xa1b2c3d4e5f60718293a4b5c6d7e8f9012345678
+    Cast {
+        /// Farcaster ID (FID) of the author
         fid: u64,
+        /// Cast text
+        text: String,
+        /// URL to embed, such as a page or an image (repeatable)
+        #[arg(long = "embed-url", visible_alias = "embed", value_name = "URL")]
+        embed_urls: Vec<String>,
+        /// Cast to quote, as FID:HASH (repeatable)
+        #[arg(long = "embed-cast", value_name = "FID:HASH")]
+        embed_casts: Vec<String>,
+        /// Cast to reply to, as FID:HASH
+        #[arg(long, conflicts_with = "parent_url")]
+        reply_to: Option<String>,
+        /// Parent URL to post under, such as a channel URL
+        #[arg(long)]
+        parent_url: Option<String>,
+        /// Split text over 320 bytes into a numbered thread
+        #[arg(long)]
+        thread: bool,
+        /// Publish text over 320 bytes as one long cast if the hub supports it
+        #[arg(long)]
+        long: bool,
+        /// Keep @username tokens as plain text instead of resolving them to mentions
+        #[arg(long)]
+        no_mentions: bool,
+        /// Validate and show the cast(s) without submitting anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
-    /// 🚫 Check spam status for FIDs
+    /// 🧹 Unfollow inactive and spam accounts
     ///
-    /// Check if one or more FIDs are marked as spam in Warpcast's spam labels dataset.
-    /// This uses the public spam labels from merkle-team/labels repository.
-    /// This is a read-only operation that doesn't require authentication.
+    /// Check every account a FID follows for its latest cast and spam label, list
+    /// those that haven't cast within --inactive-days (or never cast, or are
+    /// labelled spam), and after confirmation unfollow them with LINK_REMOVE
+    /// messages signed by the FID's Ed25519 signer, at most --per-minute a minute.
     ///
-    /// Example: castorix hub spam 12345
-    /// Example: castorix hub spam 12345 67890 11111
-    Spam {
+    /// Example: castorix hub unfollow-inactive 12345 --inactive-days 180 --dry-run
+    /// Example: castorix hub unfollow-inactive 12345 --per-minute 20
+    UnfollowInactive {
+        /// Farcaster ID (FID) whose following list to clean up
+        fid: u64,
+        /// Days without casts after which an account counts as inactive
+        #[arg(long, default_value = "180")]
+        inactive_days: u64,
+        /// Keep following spam-labelled accounts that are still active
+        #[arg(long)]
+        keep_spam: bool,
+        /// Maximum unfollows submitted per minute
+        #[arg(long, default_value = "30")]
+        per_minute: u32,
+        /// Accounts checked for their latest cast at the same time
+        #[arg(long, default_value = "8")]
+        concurrency: usize,
+        /// List the accounts that would be unfollowed without submitting anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// 📊 Get user statistics for a FID
+    ///
+    /// Retrieve statistics and storage limits for the specified Farcaster ID.
+    /// This shows follower count, following count, and storage usage.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix hub stats 12345
+    /// Example: castorix hub stats 12345 --sample-pages 20
+    Stats {
+        /// Farcaster ID (FID) to get statistics for
+        fid: u64,
+        /// Pages of 1000 follow links to count before estimating the follower total
+        #[arg(long, default_value = "5")]
+        sample_pages: u32,
+    },
+
+    /// 🧮 Count a FID's messages in each hub store
+    ///
+    /// Walks the FID's casts, reactions, links, verifications and user data and
+    /// counts the messages in each, next to the `used` figures of its storage
+    /// limits. Use --max to stop counting a busy store early.
+    ///
+    /// Example: castorix hub message-counts 12345
+    /// Example: castorix hub message-counts 12345 --max 1000 --json
+    MessageCounts {
+        /// Farcaster ID (FID) whose messages to count
+        fid: u64,
+        /// Stop counting a store after this many messages
+        #[arg(long)]
+        max: Option<u64>,
+        /// Output the counts as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// 🚫 Check spam status for FIDs
+    ///
+    /// Check if one or more FIDs are marked as spam in Warpcast's spam labels dataset.
+    /// This uses the public spam labels from merkle-team/labels repository.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix hub spam 12345
+    /// Example: castorix hub spam 12345 67890 11111
+    /// Example: castorix hub spam --file fids.txt --json
+    Spam {
         /// Farcaster ID(s) (FID) to check for spam status
         fids: Vec<u64>,
+        /// Read more FIDs from a file (whitespace or comma separated, "-" for stdin)
+        #[arg(long)]
+        file: Option<String>,
+        /// Stream one JSON object per FID (with label timestamp) instead of text
+        #[arg(long)]
+        json: bool,
     },
 
     /// 📊 Get spam statistics
@@ -654,6 +1455,8 @@ pub enum HubCommands {
     /// Example: castorix hub casts 12345 --limit 10
     /// Example: castorix hub casts 12345 --limit 0  # Get all casts
     /// Example: castorix hub casts 12345 --json     # Show full JSON data
+    /// Example: castorix hub casts 12345 --no-embeds  # Skip link previews
+    /// Example: castorix hub casts 12345 --since 2024-06-01 --until 2024-06-30 --limit 0
     Casts {
         /// Farcaster ID (FID) to get casts for
         fid: u64,
@@ -663,6 +1466,233 @@ pub enum HubCommands {
         /// Show full JSON data structure instead of formatted output
         #[arg(long)]
         json: bool,
+        /// Don't fetch link previews (OpenGraph title/description) for embedded URLs
+        #[arg(long)]
+        no_embeds: bool,
+        /// Only casts from this time on: a date (2024-06-01), an RFC 3339 time
+        /// or an age such as 7d
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Only casts before this time; a date includes that whole day
+        #[arg(long, value_name = "TIME")]
+        until: Option<String>,
+    },
+    /// ❤️ Get reactions (likes and recasts) made by a FID
+    ///
+    /// List the casts a Farcaster ID liked or recast, newest first.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix hub reactions 12345
+    /// Example: castorix hub reactions 12345 --type recast --since 30d --limit 0
+    /// Example: castorix hub reactions 12345 --json
+    Reactions {
+        /// Farcaster ID (FID) to get reactions for
+        fid: u64,
+        /// Only reactions of this type
+        #[arg(long = "type", value_parser = ["like", "recast"])]
+        reaction_type: Option<String>,
+        /// Maximum number of reactions to retrieve (0 for all, default: 50)
+        #[arg(long, default_value = "50")]
+        limit: u32,
+        /// Show full JSON data structure instead of formatted output
+        #[arg(long)]
+        json: bool,
+        /// Only reactions from this time on: a date (2024-06-01), an RFC 3339 time
+        /// or an age such as 7d
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Only reactions before this time; a date includes that whole day
+        #[arg(long, value_name = "TIME")]
+        until: Option<String>,
+    },
+    /// 🧵 Show the conversation around a cast
+    ///
+    /// Resolve a cast's parents up to the thread root and its replies below it
+    /// into a threaded tree. Depth and size are bounded so busy threads stay quick.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix hub thread 12345 0xa1b2c3d4e5f60718293a4b5c6d7e8f9012345678
+    /// Example: castorix hub thread 12345 0xa1b2... --depth 5 --max-casts 500
+    /// Example: castorix hub thread 12345 0xa1b2... --output json
+    Thread {
+        /// FID of the cast author
+        fid: u64,
+        /// Cast hash (0x-prefixed hex)
+        hash: String,
+        /// Maximum reply depth expanded below the cast
+        #[arg(long, default_value = "3")]
+        depth: usize,
+        /// Maximum number of parent casts resolved above the cast
+        #[arg(long, default_value = "10")]
+        max_parents: usize,
+        /// Maximum number of casts in the thread
+        #[arg(long, default_value = "200")]
+        max_casts: usize,
+        /// Output format: text (tree) or json (nested structure)
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        output: String,
+        /// Don't fetch link previews (OpenGraph title/description) for embedded URLs
+        #[arg(long)]
+        no_embeds: bool,
+    },
+    /// 📈 Engagement summary of a cast
+    ///
+    /// Count the likes, recasts, replies and quotes of a cast. Quotes are found
+    /// among the replies and the author's later casts, since hubs don't index
+    /// embeds. Results are cached for 10 minutes.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix hub cast-stats 12345 0xa1b2c3d4e5f60718293a4b5c6d7e8f9012345678
+    /// Example: castorix hub cast-stats 12345 0xa1b2... --json
+    CastStats {
+        /// FID of the cast author
+        fid: u64,
+        /// Cast hash (0x-prefixed hex)
+        hash: String,
+        /// Fetch fresh counts instead of using cached ones
+        #[arg(long)]
+        refresh: bool,
+        /// Show JSON output instead of formatted text
+        #[arg(long)]
+        json: bool,
+    },
+    /// 🏆 Rank a FID's recent casts by engagement
+    ///
+    /// Summarize every cast from the last --days days (likes, recasts, replies
+    /// and quotes) and list the most engaging first. Results are cached for
+    /// 10 minutes, so re-running is fast.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix hub top-casts 12345
+    /// Example: castorix hub top-casts 12345 --days 7 --limit 5
+    /// Example: castorix hub top-casts 12345 --json
+    TopCasts {
+        /// Farcaster ID (FID) whose casts to rank
+        fid: u64,
+        /// Only casts from this many days back
+        #[arg(long, default_value = "30")]
+        days: u32,
+        /// Number of casts to show (0 for all)
+        #[arg(long, default_value = "10")]
+        limit: usize,
+        /// Fetch fresh counts instead of using cached ones
+        #[arg(long)]
+        refresh: bool,
+        /// Show JSON output instead of formatted text
+        #[arg(long)]
+        json: bool,
+    },
+    /// 🔗 Warpcast link for a profile or cast
+    ///
+    /// Print the warpcast.com URL of a FID's profile or of a cast hash, and
+    /// optionally render it as a QR code to open on a phone.
+    ///
+    /// Example: castorix hub link 12345 --qr
+    /// Example: castorix hub link 0xa1b2c3d4e5f60718293a4b5c6d7e8f9012345678 --fid 12345
+    Link {
+        /// FID of a profile, or a 0x-prefixed cast hash
+        target: String,
+        /// Author FID of the cast, for Warpcast's short username link
+        #[arg(long)]
+        fid: Option<u64>,
+        /// Render the link as a QR code in the terminal
+        #[arg(long)]
+        qr: bool,
+    },
+    /// 🔔 Get mentions and reactions for a FID
+    ///
+    /// Build a notification-style list of casts mentioning the FID and likes/recasts
+    /// on its recent casts, newest first.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix hub mentions 12345
+    /// Example: castorix hub mentions 12345 --page 2 --limit 50
+    Mentions {
+        /// Farcaster ID (FID) to get notifications for
+        fid: u64,
+        /// Number of entries per page (default: 20)
+        #[arg(long, default_value = "20")]
+        limit: usize,
+        /// Page number, starting at 1
+        #[arg(long, default_value = "1")]
+        page: usize,
+        /// Number of recent casts to collect reactions for (default: 10)
+        #[arg(long, default_value = "10")]
+        casts: u32,
+        /// Show full JSON data structure instead of formatted output
+        #[arg(long)]
+        json: bool,
+    },
+    /// 🔀 Compare a FID's data across multiple hubs
+    ///
+    /// Fetch message counts and latest message hashes per store from several hubs
+    /// concurrently and report stores where the hubs disagree.
+    /// If only one hub is given, it is compared against the configured hub.
+    ///
+    /// Example: castorix hub compare --hubs https://hub-a.example,https://hub-b.example --fid 12345
+    Compare {
+        /// Comma-separated list of hub URLs
+        #[arg(long, value_delimiter = ',', required = true)]
+        hubs: Vec<String>,
+        /// Farcaster ID (FID) to compare
+        #[arg(long)]
+        fid: u64,
+    },
+    /// 🩺 Report hub sync health
+    ///
+    /// Interpret the shard sync fields from the hub info endpoint: blocks behind,
+    /// message counts and peers, with an estimated time lag.
+    /// Exits with a non-zero status when the hub is unhealthy, for use in monitoring scripts.
+    ///
+    /// Example: castorix hub sync-status
+    /// Example: castorix hub sync-status --max-block-delay 50
+    SyncStatus {
+        /// Maximum per-shard block delay before the hub is reported unhealthy
+        #[arg(long, default_value = "100")]
+        max_block_delay: u64,
+        /// Show full JSON data structure instead of formatted output
+        #[arg(long)]
+        json: bool,
+    },
+    /// 🗺️ Map FIDs to their verified addresses
+    ///
+    /// Look up the verified Ethereum and Solana addresses of every FID in a file,
+    /// several FIDs at a time, and print a FID → addresses table. With --reverse
+    /// the output is an address → FIDs index instead. Results are cached for an
+    /// hour so re-running over a growing allowlist only fetches new FIDs.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix hub verifications-map --fids fids.txt
+    /// Example: castorix hub verifications-map --fids fids.txt --format csv --output map.csv
+    /// Example: castorix hub verifications-map --fids fids.txt --reverse --format csv
+    VerificationsMap {
+        /// File of FIDs (whitespace or comma separated, "-" for stdin)
+        #[arg(long)]
+        fids: String,
+        /// Output format: table, csv or json
+        #[arg(long, default_value = "table", value_parser = ["table", "csv", "json"])]
+        format: String,
+        /// Print the address → FIDs index instead of FID → addresses
+        #[arg(long)]
+        reverse: bool,
+        /// Write the output to this file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+        /// Number of FIDs fetched at the same time
+        #[arg(long, default_value = "8")]
+        concurrency: usize,
+        /// Ignore cached verifications and fetch every FID again
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// 🔑 Manage keys of a FID
+    ///
+    /// Example: castorix hub key list
+    /// Example: castorix hub key stats 12345
+    Key {
+        #[command(subcommand)]
+        action: HubKeyCommands,
     },
 }
 
@@ -675,12 +1705,18 @@ pub enum FidCommands {
     /// This requires a wallet with sufficient ETH for gas fees and registration cost.
     /// You can optionally specify extra storage units to rent during registration.
     ///
+    /// With --sponsored the wallet needs no ETH: it signs a registerFor message
+    /// that its ERC-4337 smart account submits, with gas paid by the paymaster.
+    /// The registration fee is taken from the smart account's balance.
+    ///
     /// ⚠️  WARNING: This triggers on-chain operations and consumes gas fees.
     /// You will be prompted for confirmation before proceeding.
     ///
     /// Example: castorix fid register
     /// Example: castorix fid register --wallet my-wallet
     /// Example: castorix fid register --extra-storage 5 --dry-run
+    /// Example: castorix fid register --wallet my-wallet --sponsored --bundler-url https://... --paymaster-url https://...
+    /// Example: castorix fid register --wallet my-wallet --yes --wait-hub
     Register {
         /// Wallet name for registration (required)
         #[arg(long)]
@@ -694,6 +1730,18 @@ pub enum FidCommands {
         /// Simulate the transaction without sending it to the chain
         #[arg(long)]
         dry_run: bool,
+        /// Send through an ERC-4337 bundler with gas paid by a paymaster
+        #[arg(long)]
+        sponsored: bool,
+        /// Bundler RPC URL for --sponsored (defaults to CASTORIX_BUNDLER_URL)
+        #[arg(long, requires = "sponsored")]
+        bundler_url: Option<String>,
+        /// ERC-7677 paymaster URL for --sponsored (defaults to CASTORIX_PAYMASTER_URL)
+        #[arg(long, requires = "sponsored")]
+        paymaster_url: Option<String>,
+        /// Wait until the hub has indexed the registration before exiting
+        #[arg(long)]
+        wait_hub: bool,
         /// Automatically confirm the operation without prompting
         #[arg(long)]
         yes: bool,
@@ -724,6 +1772,121 @@ pub enum FidCommands {
         #[arg(long)]
         wallet: Option<String>,
     },
+    /// 🔎 Find the FID behind an address
+    ///
+    /// Report whether an address is the custody address of a FID (IdRegistry.idOf),
+    /// a verified address of one or more FIDs, or unknown, with a profile summary
+    /// of each FID found. Hubs cannot search verifications by address, so verified
+    /// addresses are matched against FIDs cached by `hub verifications-map` and
+    /// any FIDs given with --fids.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix fid lookup 0x8fc5d6afe572fefc4ec153587b63ce543f6fa2ea
+    /// Example: castorix fid lookup 0x8fc5... --fids allowlist.txt --json
+    Lookup {
+        /// Ethereum (0x...) or Solana (base58) address
+        address: String,
+        /// File of FIDs to search for verified addresses ("-" for stdin)
+        #[arg(long)]
+        fids: Option<String>,
+        /// Print the result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// 🔬 Scan a range of FIDs for custody and recovery patterns
+    ///
+    /// Read custodyOf/recoveryOf for every FID in the range with batched
+    /// Multicall3 reads, several batches at a time, and write the FIDs matching
+    /// the filter as CSV (fid,custody,recovery) while the scan runs.
+    /// custody-verified also asks the hub for each registered FID's verifications.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix fid scan --from 1 --to 100000 --filter no-recovery
+    /// Example: castorix fid scan --from 1 --to 5000 --filter custody-verified --output verified.csv
+    Scan {
+        /// First FID to scan
+        #[arg(long, default_value_t = 1)]
+        from: u64,
+        /// Last FID to scan (inclusive)
+        #[arg(long)]
+        to: u64,
+        /// Which FIDs to report
+        #[arg(long, default_value = "all", value_parser = ["all", "no-recovery", "recovery-is-custody", "custody-verified", "unregistered"])]
+        filter: String,
+        /// Write the CSV to this file instead of stdout
+        #[arg(long)]
+        output: Option<String>,
+        /// Multicall batches in flight at once
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+}
+
+/// Farcaster Pro and other subscription tier commands
+#[derive(Subcommand)]
+pub enum TierCommands {
+    /// 💎 Show the subscription tiers of a FID
+    ///
+    /// List the tiers a FID holds with their expiry, and the purchases made for it,
+    /// as indexed by the Farcaster Hub.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix tier status 12345
+    /// Example: castorix tier status 12345 --json
+    Status {
+        /// FID (Farcaster ID) to check
+        fid: u64,
+        /// Show full JSON data structure instead of formatted output
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// 💰 Get the price of a subscription
+    ///
+    /// Read a tier's terms and the price for a number of days from the TierRegistry on Base.
+    /// This is a read-only operation that queries the blockchain.
+    ///
+    /// Example: castorix tier price
+    /// Example: castorix tier price --days 30
+    Price {
+        /// Days of subscription to price
+        #[arg(long, default_value = "365")]
+        days: u64,
+        /// Tier ID (1 is Farcaster Pro)
+        #[arg(long, default_value = "1")]
+        tier: u64,
+    },
+
+    /// 🛒 Buy or extend a subscription
+    ///
+    /// Pay for a FID's subscription from a managed wallet on Base. The wallet pays
+    /// in the tier's token (USDC for Farcaster Pro) plus ETH for gas, and may pay
+    /// for any FID. The token allowance is approved first when needed.
+    ///
+    /// ⚠️  WARNING: This triggers on-chain operations, spends tokens and consumes gas fees.
+    /// You will be prompted for confirmation before proceeding.
+    ///
+    /// Example: castorix tier purchase 12345 --wallet my-wallet --dry-run
+    /// Example: castorix tier purchase 12345 --days 30 --wallet my-wallet
+    Purchase {
+        /// FID (Farcaster ID) to subscribe
+        fid: u64,
+        /// Days of subscription to buy
+        #[arg(long, default_value = "365")]
+        days: u64,
+        /// Tier ID (1 is Farcaster Pro)
+        #[arg(long, default_value = "1")]
+        tier: u64,
+        /// Wallet name paying for the subscription
+        #[arg(long)]
+        wallet: String,
+        /// Show the price and balances without sending transactions
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
 }
 
 /// Storage rental and management commands
@@ -735,12 +1898,16 @@ pub enum StorageCommands {
     /// This allows the FID to store more messages, casts, and other data.
     /// Requires the custody wallet for the FID to authorize the transaction.
     ///
+    /// With --sponsored the rent is paid from the wallet's ERC-4337 smart
+    /// account and gas by the paymaster.
+    ///
     /// ⚠️  WARNING: This triggers on-chain operations and consumes gas fees.
     /// You will be prompted for confirmation before proceeding.
     ///
     /// Example: castorix storage rent 12345 --units 5
     /// Example: castorix storage rent 12345 --units 10 --wallet my-wallet --dry-run
     /// Example: castorix storage rent 12345 --units 5 --wallet custody-wallet --payment-wallet gas-payer
+    /// Example: castorix storage rent 12345 --units 1 --wallet my-wallet --sponsored
     Rent {
         /// FID (Farcaster ID) to rent storage for
         fid: u64,
@@ -756,6 +1923,15 @@ pub enum StorageCommands {
         /// Simulate the transaction without sending it to the chain
         #[arg(long)]
         dry_run: bool,
+        /// Send through an ERC-4337 bundler with gas paid by a paymaster
+        #[arg(long, conflicts_with = "payment_wallet")]
+        sponsored: bool,
+        /// Bundler RPC URL for --sponsored (defaults to CASTORIX_BUNDLER_URL)
+        #[arg(long, requires = "sponsored")]
+        bundler_url: Option<String>,
+        /// ERC-7677 paymaster URL for --sponsored (defaults to CASTORIX_PAYMASTER_URL)
+        #[arg(long, requires = "sponsored")]
+        paymaster_url: Option<String>,
         /// Automatically confirm the operation without prompting
         #[arg(long)]
         yes: bool,
@@ -775,6 +1951,43 @@ pub enum StorageCommands {
         units: u32,
     },
 
+    /// 📈 Show global storage economics
+    ///
+    /// Query the StorageRegistry for the unit price (ETH and USD), the ETH/USD
+    /// rate, rented and maximum units and the deprecation time. With --fid the
+    /// FID's rentals are listed from `Rent` events, read from the local indexer
+    /// store when it has been synced and from the chain otherwise.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix storage info
+    /// Example: castorix storage info --fid 12345
+    Info {
+        /// FID whose storage rentals to list
+        #[arg(long)]
+        fid: Option<u64>,
+        /// First block searched for rentals when reading from the chain
+        #[arg(long)]
+        from_block: Option<u64>,
+    },
+
+    /// 📉 Show how the storage unit price changed over time
+    ///
+    /// Lists the StorageRegistry `SetPrice` events with a bar per price, read
+    /// from the local indexer store when it has been synced and from the chain
+    /// otherwise, followed by the current unit price in USD and ETH.
+    /// This is a read-only operation that doesn't require authentication.
+    ///
+    /// Example: castorix storage price-history
+    /// Example: castorix storage price-history --from-block 120000000 --json
+    PriceHistory {
+        /// First block searched for price changes when reading from the chain
+        #[arg(long)]
+        from_block: Option<u64>,
+        /// Output JSON instead of a chart
+        #[arg(long)]
+        json: bool,
+    },
+
     /// 📊 Check storage usage and limits
     ///
     /// Check the current storage usage and limits for a specific FID.
@@ -787,3 +2000,586 @@ pub enum StorageCommands {
         fid: u64,
     },
 }
+
+/// Air-gapped transaction commands
+#[derive(Subcommand)]
+pub enum TxCommands {
+    /// 🧱 Build an unsigned transaction
+    ///
+    /// Read the price, nonce, fees and gas for an operation from Optimism and
+    /// write them to a JSON file to be signed on an offline machine.
+    /// Requires ETH_OP_RPC_URL but no key.
+    ///
+    /// Example: castorix tx build rent 12345 --units 2 --from 0x... --output rent.json
+    /// Example: castorix tx build signer-add 12345 --key 0x... --output add.json
+    Build {
+        #[command(subcommand)]
+        operation: TxBuildCommands,
+    },
+
+    /// ✍️  Sign a transaction built with `tx build`
+    ///
+    /// Needs no network access: run it on the offline machine holding the
+    /// encrypted key. The transaction is shown for review before signing.
+    ///
+    /// Example: castorix tx sign rent.json --fid 12345 --output rent.signed.json
+    /// Example: castorix tx sign register.json --wallet cold-wallet
+    Sign {
+        /// Unsigned transaction file
+        file: String,
+        /// Sign with this managed wallet
+        #[arg(long, conflicts_with = "fid")]
+        wallet: Option<String>,
+        /// Sign with the custody key of this FID (defaults to the FID the
+        /// transaction acts on)
+        #[arg(long)]
+        fid: Option<u64>,
+        /// File to write the signed transaction to (defaults to stdout)
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+        /// Automatically confirm the operation without prompting
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// 📡 Broadcast a transaction signed with `tx sign`
+    ///
+    /// ⚠️  WARNING: This sends the transaction to Optimism.
+    ///
+    /// Example: castorix tx broadcast rent.signed.json
+    Broadcast {
+        /// Signed transaction file
+        file: String,
+        /// Automatically confirm the operation without prompting
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+/// Operations `tx build` can prepare
+#[derive(Subcommand)]
+pub enum TxBuildCommands {
+    /// 🆔 Register a FID to the sender
+    Register {
+        /// Address that will sign, pay and own the FID
+        #[arg(long)]
+        from: String,
+        /// Recovery address (defaults to the sender)
+        #[arg(long)]
+        recovery: Option<String>,
+        /// Extra storage units to rent with the FID
+        #[arg(long, default_value = "0")]
+        extra_storage: u64,
+        /// Gas limit to use instead of the estimate
+        #[arg(long)]
+        gas_limit: Option<u64>,
+        /// File to write the unsigned transaction to (defaults to stdout)
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+    },
+    /// 🏠 Rent storage units for a FID
+    Rent {
+        /// FID (Farcaster ID) to rent storage for
+        fid: u64,
+        /// Number of storage units to rent
+        #[arg(long)]
+        units: u64,
+        /// Address that will sign and pay (any address may pay)
+        #[arg(long)]
+        from: String,
+        /// Gas limit to use instead of the estimate
+        #[arg(long)]
+        gas_limit: Option<u64>,
+        /// File to write the unsigned transaction to (defaults to stdout)
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+    },
+    /// ➕ Add an Ed25519 signer to a FID
+    ///
+    /// Signed by the FID's custody address, which also signs the key request.
+    SignerAdd {
+        /// FID (Farcaster ID) to add the signer to
+        fid: u64,
+        /// Ed25519 public key (hex)
+        #[arg(long)]
+        key: String,
+        /// Hours the key request stays valid, which bounds how long signing
+        /// and broadcasting may take
+        #[arg(long, default_value = "24")]
+        valid_hours: u64,
+        /// Gas limit to use instead of the estimate
+        #[arg(long)]
+        gas_limit: Option<u64>,
+        /// File to write the unsigned transaction to (defaults to stdout)
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+    },
+    /// ➖ Remove a key from a FID
+    ///
+    /// Signed by the FID's custody address.
+    SignerRemove {
+        /// FID (Farcaster ID) to remove the key from
+        fid: u64,
+        /// Public key to remove (hex)
+        #[arg(long)]
+        key: String,
+        /// Gas limit to use instead of the estimate
+        #[arg(long)]
+        gas_limit: Option<u64>,
+        /// File to write the unsigned transaction to (defaults to stdout)
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+    },
+}
+
+/// Monitor targets other than a FID
+#[derive(Subcommand)]
+pub enum MonitorCommands {
+    /// 🚦 Monitor the protocol contracts
+    ///
+    /// Poll the pause state of IdGateway, KeyGateway and StorageRegistry, the
+    /// registration price and the health of the Optimism RPC endpoint. Alerts
+    /// (paused, unpaused, price_changed, rpc_down, rpc_recovered, rpc_stalled)
+    /// are POSTed as JSON to the webhook (with retry), or printed as JSON lines
+    /// when no webhook is given. Contracts already paused at startup are
+    /// alerted on the first poll.
+    ///
+    /// Example: castorix monitor contracts --interval 60 --webhook https://example.com/hook
+    Contracts {
+        /// Polling interval in seconds
+        #[arg(long, default_value = "60")]
+        interval: u64,
+        /// Webhook URL receiving JSON alerts
+        #[arg(long)]
+        webhook: Option<String>,
+        /// Maximum delivery retries per alert
+        #[arg(long, default_value = "5")]
+        max_retries: u32,
+    },
+}
+
+/// Release verification and self-update commands
+#[derive(Subcommand)]
+pub enum SelfCommands {
+    /// 🔏 Verify the running binary against a signed release manifest
+    ///
+    /// Download the release manifest and its minisign signature, check the
+    /// signature against the release public key built into castorix, and
+    /// compare the SHA-256 of the running binary with the digest published for
+    /// this version and platform.
+    ///
+    /// Example: castorix self verify
+    /// Example: castorix self verify --manifest https://example.com/v0.1.0/manifest.json
+    Verify {
+        /// Manifest URL (defaults to CASTORIX_RELEASE_MANIFEST or the release of the running version)
+        #[arg(long)]
+        manifest: Option<String>,
+        /// Base64 minisign public key (defaults to the key built into castorix)
+        #[arg(long)]
+        public_key: Option<String>,
+    },
+
+    /// ⬆️  Download, verify and install the latest release
+    ///
+    /// The manifest signature and the downloaded binary's hash are both checked
+    /// before the running binary is replaced.
+    ///
+    /// Example: castorix self update
+    /// Example: castorix self update --yes --force
+    Update {
+        /// Manifest URL (defaults to CASTORIX_RELEASE_MANIFEST or the latest GitHub release)
+        #[arg(long)]
+        manifest: Option<String>,
+        /// Base64 minisign public key (defaults to the key built into castorix)
+        #[arg(long)]
+        public_key: Option<String>,
+        /// Automatically confirm the update without prompting
+        #[arg(long)]
+        yes: bool,
+        /// Install even if the release is not newer than the running version
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Onchain registry event indexer commands
+#[derive(Subcommand)]
+pub enum IndexerCommands {
+    /// 🔄 Scan registry logs into the local index
+    ///
+    /// Fetch IdRegistry, KeyRegistry and StorageRegistry events in block batches
+    /// and store them under the data directory. Without --from-block the scan
+    /// resumes after the last indexed block; without --to-block it runs to the
+    /// current head.
+    ///
+    /// Example: castorix indexer sync
+    /// Example: castorix indexer sync --from-block 111888232 --to-block 112000000 --batch-size 500
+    Sync {
+        /// First block to scan
+        #[arg(long)]
+        from_block: Option<u64>,
+        /// Last block to scan (defaults to the chain head)
+        #[arg(long)]
+        to_block: Option<u64>,
+        /// Blocks per eth_getLogs request
+        #[arg(long, default_value_t = crate::farcaster::indexer::DEFAULT_BATCH_SIZE)]
+        batch_size: u64,
+        /// Optimism RPC URL (defaults to ETH_OP_RPC_URL)
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+
+    /// 📊 Show what the local index covers
+    ///
+    /// Example: castorix indexer status
+    Status,
+
+    /// 🔑 Show when a signer key was added or removed
+    ///
+    /// Example: castorix indexer signer 0x1234...abcd
+    Signer {
+        /// Signer public key (hex)
+        key: String,
+        /// Look up block timestamps over RPC
+        #[arg(long)]
+        timestamps: bool,
+    },
+
+    /// 🆔 List FIDs registered to an address
+    ///
+    /// Example: castorix indexer registered-by 0x8ba1f109551bD432803012645Ac136ddd64DBA72
+    RegisteredBy {
+        /// Owner address the FIDs were registered to
+        address: String,
+    },
+
+    /// 📜 Show every indexed event for a FID
+    ///
+    /// Example: castorix indexer fid 12345
+    Fid {
+        /// Farcaster ID
+        fid: u64,
+    },
+}
+
+/// Command alias management
+#[derive(Subcommand)]
+pub enum AliasCommands {
+    /// 📋 List built-in and user defined aliases
+    ///
+    /// Example: castorix alias list
+    List,
+
+    /// ➕ Define or replace an alias
+    ///
+    /// The alias is saved in the config file of the data directory and expands
+    /// to the given command words when used in place of a command.
+    ///
+    /// Example: castorix alias set me hub profile 12345
+    /// Example: castorix alias set sp hub spam --json
+    Set {
+        /// Alias name
+        name: String,
+        /// Command the alias expands to
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true, allow_hyphen_values = true)]
+        expansion: Vec<String>,
+    },
+
+    /// 🗑️ Remove a user defined alias
+    ///
+    /// Example: castorix alias remove me
+    Remove {
+        /// Alias name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ContextCommands {
+    /// ➕ Create or update a context
+    ///
+    /// Settings given replace the context's current ones; others are kept.
+    ///
+    /// Example: castorix context set main --fid 12345 --wallet my-wallet
+    /// Example: castorix context set local --fid 12345 --hub http://localhost:2281 --use
+    Set {
+        /// Context name
+        name: String,
+        /// FID commands default to
+        #[arg(long)]
+        fid: Option<u64>,
+        /// Encrypted wallet holding the FID's custody key
+        #[arg(long)]
+        wallet: Option<String>,
+        /// Hub URL commands default to
+        #[arg(long)]
+        hub: Option<String>,
+        /// Make it the active context
+        #[arg(long = "use")]
+        activate: bool,
+    },
+
+    /// 🎯 Make a context the active one
+    ///
+    /// Example: castorix context use main
+    Use {
+        /// Context name
+        name: String,
+    },
+
+    /// ⏏️ Deactivate the active context
+    ///
+    /// Example: castorix context clear
+    Clear,
+
+    /// 📋 List contexts, marking the active one
+    ///
+    /// Example: castorix context list
+    List,
+
+    /// 🔍 Show a context, the active one by default
+    ///
+    /// Example: castorix context show
+    Show {
+        /// Context name
+        name: Option<String>,
+    },
+
+    /// 🗑️ Remove a context
+    ///
+    /// Example: castorix context remove local
+    Remove {
+        /// Context name
+        name: String,
+    },
+}
+
+/// Signature audit log
+#[derive(Subcommand)]
+pub enum AuditCommands {
+    /// 📜 Show recorded signatures, newest last
+    ///
+    /// Example: castorix audit show
+    /// Example: castorix audit show --limit 50 --key 0x1234...
+    Show {
+        /// Number of most recent entries to show (0 for all)
+        #[arg(long, default_value = "20")]
+        limit: usize,
+        /// Only show signatures by this key (address or Ed25519 public key)
+        #[arg(long)]
+        key: Option<String>,
+        /// Output raw entries as JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// 🔗 Check the log's hash chain for edited or removed entries
+    ///
+    /// Example: castorix audit verify
+    Verify,
+}
+
+/// Watched accounts with local nicknames
+#[derive(Subcommand)]
+pub enum WatchlistCommands {
+    /// ➕ Watch a FID under a nickname
+    ///
+    /// The nickname is saved in the config file of the data directory. Once
+    /// added, `@name` can be used anywhere a FID is expected.
+    ///
+    /// Example: castorix watchlist add 3 --name dwr
+    /// Example: castorix hub casts @dwr
+    Add {
+        /// FID (Farcaster ID) to watch
+        fid: u64,
+        /// Nickname for the account
+        #[arg(long)]
+        name: String,
+    },
+
+    /// 🗑️ Stop watching an account
+    ///
+    /// Example: castorix watchlist remove dwr
+    Remove {
+        /// Nickname of the account
+        name: String,
+    },
+
+    /// 📋 List watched accounts
+    ///
+    /// Example: castorix watchlist list
+    List,
+
+    /// 📊 Show each watched account's latest activity
+    ///
+    /// Prints a compact table with each account's username and latest cast.
+    ///
+    /// Example: castorix watchlist status
+    Status {
+        /// Accounts fetched at the same time
+        #[arg(long, default_value = "8")]
+        concurrency: usize,
+    },
+}
+
+/// Standalone utilities
+#[derive(Subcommand)]
+pub enum UtilCommands {
+    /// 🔬 Decode and check a raw hub message
+    ///
+    /// Parses protobuf `Message` bytes (as returned by hubs or found in logs),
+    /// prints every field including the nested body, recomputes the hash and
+    /// optionally verifies the signature. The input is hex (with or without
+    /// 0x) or base64 text, or a file holding hex, base64 or raw bytes.
+    ///
+    /// Example: castorix util decode-message 0a4a0801...
+    /// Example: castorix util decode-message message.bin --verify-signature
+    DecodeMessage {
+        /// Hex or base64 encoded message, or a file containing one
+        input: String,
+        /// Also verify the signature against the signer key in the message
+        #[arg(long)]
+        verify_signature: bool,
+        /// Print the message and checks as JSON
+        #[arg(long)]
+        json: bool,
+        /// Print the protobuf fields as decoded instead of the hub JSON shape
+        #[arg(long, conflicts_with = "json")]
+        raw: bool,
+    },
+
+    /// ✍️ Verify a signature out of band
+    ///
+    /// Ed25519: the public key is 32 bytes of hex and the payload is hex (for
+    /// a hub message, its hash) or a file of raw bytes.
+    ///
+    /// EIP-712: the public key is the signer's Ethereum address and the
+    /// payload is typed data JSON (inline or a file), or the hex hash of a
+    /// message signed by an auth address.
+    ///
+    /// Signatures are hex or base64, as shown by hubs. Exits with an error
+    /// when the signature does not verify.
+    ///
+    /// Example: castorix util verify-sig --scheme ed25519 --pubkey 0xea4a... --payload 0xdca4... --signature Czw/...
+    /// Example: castorix util verify-sig --scheme eip712 --pubkey 0x1234... --payload request.json --signature 0xabcd...
+    VerifySig {
+        /// Signature scheme
+        #[arg(long, value_parser = ["ed25519", "eip712"])]
+        scheme: String,
+        /// Ed25519 public key, or Ethereum address for EIP-712
+        #[arg(long)]
+        pubkey: String,
+        /// Signed payload (see above for formats)
+        #[arg(long)]
+        payload: String,
+        /// Signature, hex or base64
+        #[arg(long)]
+        signature: String,
+    },
+
+    /// #️⃣ Compute the namehash, labelhashes and DNS encoding of an ENS name
+    ///
+    /// The name is normalized first (ENSIP-15), as resolvers expect; the
+    /// values are the ones to pass to registry and resolver calls.
+    ///
+    /// Example: castorix util namehash vitalik.eth
+    Namehash {
+        /// ENS name, e.g. vitalik.eth
+        name: String,
+        /// Hash the name exactly as given instead of normalizing it
+        #[arg(long)]
+        no_normalize: bool,
+        /// Print the values as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Plugin commands
+#[derive(Subcommand)]
+pub enum PluginCommands {
+    /// 📋 List installed plugins
+    ///
+    /// Plugins are executables named castorix-<name> in <PATH>/plugins or on
+    /// PATH, and castorix-<name>.wasm modules in <PATH>/plugins. The first one
+    /// found for each name is used.
+    ///
+    /// Example: castorix plugin list
+    List {
+        /// Print the plugins as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Channel membership and moderation queries
+#[derive(Subcommand)]
+pub enum ChannelCommands {
+    /// ℹ️ Show a channel's details and staff
+    ///
+    /// Example: castorix channel info rust
+    Info {
+        /// Channel id, such as `farcaster`
+        channel: String,
+        /// Print the channel as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// 👥 List a channel's members
+    ///
+    /// Example: castorix channel members rust --limit 500
+    Members {
+        /// Channel id
+        channel: String,
+        /// Most members to list
+        #[arg(long, default_value = "100")]
+        limit: usize,
+        /// Print the members as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// 🛡️ List a channel's lead and moderators
+    ///
+    /// Example: castorix channel moderators rust
+    Moderators {
+        /// Channel id
+        channel: String,
+        /// Print the moderators as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// 🏠 List the channels a FID is a member of
+    ///
+    /// Example: castorix channel memberships 12345
+    Memberships {
+        /// Farcaster ID (FID)
+        fid: u64,
+        /// Most memberships to list
+        #[arg(long, default_value = "100")]
+        limit: usize,
+        /// Print the memberships as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// 🚫 List the FIDs a channel has banned, or restricted
+    ///
+    /// Example: castorix channel bans rust
+    /// Example: castorix channel bans rust --restricted
+    Bans {
+        /// Channel id
+        channel: String,
+        /// List restricted FIDs, whose casts need approval, instead of banned ones
+        #[arg(long)]
+        restricted: bool,
+        /// Most entries to list
+        #[arg(long, default_value = "100")]
+        limit: usize,
+        /// Print the entries as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
@@ -1,5 +1,7 @@
 use std::env;
 
+use crate::core::crypto::kdf::KdfParams;
+
 /// Environment variable configuration manager
 /// Loads configuration from .env file and provides typed access to environment variables
 pub struct Config {
@@ -7,6 +9,8 @@ pub struct Config {
     pub eth_base_rpc_url: String,
     pub eth_op_rpc_url: String,
     pub farcaster_hub_url: String,
+    /// Argon2 parameters used when encrypting keys
+    pub kdf_params: KdfParams,
 }
 
 impl Config {
@@ -29,6 +33,7 @@ impl Config {
                 .unwrap_or_else(|_| "https://mainnet.optimism.io".to_string()),
             farcaster_hub_url: env::var("FARCASTER_HUB_URL")
                 .unwrap_or_else(|_| "http://192.168.1.192:3381".to_string()),
+            kdf_params: kdf_params_from_env(),
         })
     }
 
@@ -46,6 +51,7 @@ impl Config {
                 .unwrap_or_else(|_| "https://mainnet.optimism.io".to_string()),
             farcaster_hub_url: env::var("FARCASTER_HUB_URL")
                 .unwrap_or_else(|_| "http://192.168.1.192:3381".to_string()),
+            kdf_params: kdf_params_from_env(),
         })
     }
 
@@ -99,6 +105,11 @@ impl Config {
         &self.farcaster_hub_url
     }
 
+    /// Get the Argon2 parameters used for newly encrypted keys
+    pub fn kdf_params(&self) -> KdfParams {
+        self.kdf_params
+    }
+
     /// Print current configuration (masking sensitive values)
     pub fn print_config(&self) {
         println!("=== Configuration ===");
@@ -106,10 +117,38 @@ impl Config {
         println!("ETH_BASE_RPC_URL: {}", self.eth_base_rpc_url);
         println!("ETH_OP_RPC_URL: {}", self.eth_op_rpc_url);
         println!("FARCASTER_HUB_URL: {}", self.farcaster_hub_url);
+        println!(
+            "KDF: argon2id m={}KiB t={} p={}",
+            self.kdf_params.memory_kib, self.kdf_params.iterations, self.kdf_params.parallelism
+        );
         println!("===================");
     }
 }
 
+/// Read Argon2 parameters from `CASTORIX_KDF_*`, falling back to the recommended values
+fn kdf_params_from_env() -> KdfParams {
+    let recommended = KdfParams::recommended();
+    let read = |name: &str, default: u32| {
+        env::var(name)
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(default)
+    };
+    let params = KdfParams {
+        memory_kib: read(env_vars::KDF_MEMORY_KIB, recommended.memory_kib),
+        iterations: read(env_vars::KDF_ITERATIONS, recommended.iterations),
+        parallelism: read(env_vars::KDF_PARALLELISM, recommended.parallelism),
+    };
+
+    match params.validate() {
+        Ok(()) => params,
+        Err(e) => {
+            eprintln!("Warning: ignoring CASTORIX_KDF_* settings: {e}");
+            recommended
+        }
+    }
+}
+
 /// Mask sensitive parts of URLs (like API keys)
 fn mask_url(url: &str) -> String {
     if url.contains("your_api_key_here") {
@@ -165,6 +204,26 @@ pub mod env_vars {
     pub const ETH_BASE_RPC_URL: &str = "ETH_BASE_RPC_URL";
     pub const ETH_OP_RPC_URL: &str = "ETH_OP_RPC_URL";
     pub const FARCASTER_HUB_URL: &str = "FARCASTER_HUB_URL";
+    pub const KDF_MEMORY_KIB: &str = "CASTORIX_KDF_MEMORY_KIB";
+    pub const KDF_ITERATIONS: &str = "CASTORIX_KDF_ITERATIONS";
+    pub const KDF_PARALLELISM: &str = "CASTORIX_KDF_PARALLELISM";
+    pub const PROFILE: &str = "CASTORIX_PROFILE";
+    pub const DATA_DIR: &str = "CASTORIX_DATA_DIR";
+    pub const BUNDLER_URL: &str = "CASTORIX_BUNDLER_URL";
+    pub const PAYMASTER_URL: &str = "CASTORIX_PAYMASTER_URL";
+    pub const PROXY: &str = "CASTORIX_PROXY";
+    pub const HTTP_PROXY: &str = "CASTORIX_HTTP_PROXY";
+    pub const HTTPS_PROXY: &str = "CASTORIX_HTTPS_PROXY";
+    pub const NO_PROXY: &str = "CASTORIX_NO_PROXY";
+    pub const CA_CERT: &str = "CASTORIX_CA_CERT";
+    pub const USER_AGENT: &str = "CASTORIX_USER_AGENT";
+    pub const REQUEST_ID: &str = "CASTORIX_REQUEST_ID";
+    pub const REQUEST_ID_HEADER: &str = "CASTORIX_REQUEST_ID_HEADER";
+    pub const CONTEXT: &str = "CASTORIX_CONTEXT";
+    pub const WAIT_FOR: &str = "CASTORIX_WAIT_FOR";
+    pub const WAIT_TIMEOUT: &str = "CASTORIX_WAIT_TIMEOUT";
+    pub const WARPCAST_API_URL: &str = "CASTORIX_WARPCAST_API_URL";
+    pub const WRITE_RATE: &str = "CASTORIX_WRITE_RATE";
 }
 
 /// Default values for environment variables
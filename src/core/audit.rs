@@ -0,0 +1,329 @@
+//! Append-only audit log of signatures
+//!
+//! Every signature castorix produces is recorded as one JSON line in
+//! `audit.jsonl` in the data directory: when it was made, by which key, for
+//! what operation, over which hash, and whether it was broadcast. Each entry
+//! carries the hash of the previous one, so editing or dropping a line breaks
+//! the chain and shows up in [`AuditLog::verify`].
+//!
+//! Hub messages are recorded when signed and again, with `broadcast` set, once
+//! a hub accepts them. On-chain transactions are recorded when sent.
+//!
+//! Recording is off until [`enable`] is called (the CLI does this at start-up),
+//! so library users and tests never write to the real data directory.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use anyhow::Result;
+use ethers::types::transaction::eip712::Eip712;
+use ethers::types::transaction::eip712::TypedData;
+use ethers::types::Address;
+use ethers::types::H256;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::core::client::hub_client::message_data;
+use crate::core::protocol::message::Message;
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
+
+/// `prev_hash` of the first entry
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// Serializes appends within the process so the chain never forks
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+/// One recorded signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 0
+    pub seq: u64,
+    /// Unix timestamp (seconds)
+    pub timestamp: i64,
+    /// Signing key: an Ethereum address or a 0x-prefixed Ed25519 public key
+    pub key: String,
+    /// What was signed, e.g. `cast_add`, `personal_sign` or `tx:register`
+    pub operation: String,
+    /// Hash that was signed (message hash, EIP-712 digest or transaction hash)
+    pub message_hash: String,
+    /// Whether the signature was sent to a hub or chain
+    pub broadcast: bool,
+    /// `hash` of the previous entry
+    pub prev_hash: String,
+    /// SHA-256 over the fields above
+    pub hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        for field in [
+            self.seq.to_string(),
+            self.timestamp.to_string(),
+            self.key.clone(),
+            self.operation.clone(),
+            self.message_hash.clone(),
+            self.broadcast.to_string(),
+            self.prev_hash.clone(),
+        ] {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field.as_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// Outcome of checking the hash chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditVerification {
+    /// Every entry links to the previous one
+    Intact { entries: usize },
+    /// The chain breaks at a line (1-based)
+    Broken { line: usize, reason: String },
+}
+
+/// Location of the audit log in the data directory
+pub fn audit_log_path() -> PathBuf {
+    crate::core::utils::data_dir::data_dir().join("audit.jsonl")
+}
+
+/// Start recording signatures to the audit log in the data directory
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Record a signature if auditing is enabled
+///
+/// Failures are reported on stderr but never fail the signing operation.
+pub fn record(key: &str, operation: &str, message_hash: &str, broadcast: bool) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Err(e) = AuditLog::open().append(key, operation, message_hash, broadcast) {
+        crate::eprintln!("⚠️  Could not write the audit log: {e}");
+    }
+}
+
+/// Record a signed hub message
+pub fn record_message(message: &Message, broadcast: bool) {
+    let operation = message_data(message)
+        .map(|data| message_operation(&format!("{:?}", data.get_field_type())))
+        .unwrap_or_else(|_| "message".to_string());
+    record(
+        &format!("0x{}", hex::encode(message.get_signer())),
+        &operation,
+        &format!("0x{}", hex::encode(message.get_hash())),
+        broadcast,
+    );
+}
+
+/// Record an Ethereum signature that is not itself sent anywhere
+pub fn record_eth_signature(signer: Address, operation: &str, digest: H256) {
+    record(
+        &format!("{signer:?}"),
+        operation,
+        &format!("{digest:?}"),
+        false,
+    );
+}
+
+/// Record an EIP-712 signature over typed data
+pub fn record_typed_data(signer: Address, typed_data: &TypedData) {
+    if let Ok(digest) = typed_data.encode_eip712() {
+        record_eth_signature(
+            signer,
+            &format!("eip712:{}", typed_data.primary_type),
+            H256::from(digest),
+        );
+    }
+}
+
+/// Record a transaction sent to the chain
+pub fn record_transaction(sender: Option<Address>, method: &str, tx_hash: H256) {
+    let key = sender
+        .map(|sender| format!("{sender:?}"))
+        .unwrap_or_else(|| "unknown".to_string());
+    record(&key, &format!("tx:{method}"), &format!("{tx_hash:?}"), true);
+}
+
+/// `MESSAGE_TYPE_CAST_ADD` → `cast_add`
+fn message_operation(message_type: &str) -> String {
+    message_type
+        .trim_start_matches("MESSAGE_TYPE_")
+        .to_lowercase()
+}
+
+/// The audit log file
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// The log in the data directory
+    pub fn open() -> Self {
+        Self::at(audit_log_path())
+    }
+
+    /// A log at a specific path
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// All entries in order, empty when the log does not exist yet
+    pub fn entries(&self) -> Result<Vec<AuditEntry>> {
+        let Ok(content) = std::fs::read_to_string(&self.path) else {
+            return Ok(Vec::new());
+        };
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).with_context(|| {
+                    format!(
+                        "Invalid audit entry on line {} of {}",
+                        i + 1,
+                        self.path.display()
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Append an entry chained to the current last one
+    ///
+    /// # Arguments
+    /// * `key` - Signing key identity
+    /// * `operation` - What was signed
+    /// * `message_hash` - Hash that was signed
+    /// * `broadcast` - Whether the signature was sent to a hub or chain
+    ///
+    /// # Returns
+    /// * `Result<AuditEntry>` - The entry as written
+    pub fn append(
+        &self,
+        key: &str,
+        operation: &str,
+        message_hash: &str,
+        broadcast: bool,
+    ) -> Result<AuditEntry> {
+        let _guard = APPEND_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let _timer = metrics::start(Phase::DiskIo, "audit log");
+        let last = self.entries()?.pop();
+        let mut entry = AuditEntry {
+            seq: last.as_ref().map_or(0, |last| last.seq + 1),
+            timestamp: chrono::Utc::now().timestamp(),
+            key: key.to_string(),
+            operation: operation.to_string(),
+            message_hash: message_hash.to_string(),
+            broadcast,
+            prev_hash: last.map_or_else(|| GENESIS_HASH.to_string(), |last| last.hash),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        Ok(entry)
+    }
+
+    /// Check that every entry is unmodified and linked to the previous one
+    pub fn verify(&self) -> Result<AuditVerification> {
+        let entries = match self.entries() {
+            Ok(entries) => entries,
+            Err(e) => {
+                return Ok(AuditVerification::Broken {
+                    line: 0,
+                    reason: format!("{e:#}"),
+                })
+            }
+        };
+        let mut prev_hash = GENESIS_HASH.to_string();
+        for (i, entry) in entries.iter().enumerate() {
+            let broken = |reason: &str| AuditVerification::Broken {
+                line: i + 1,
+                reason: reason.to_string(),
+            };
+            if entry.seq != i as u64 {
+                return Ok(broken(&format!("expected entry {i}, found {}", entry.seq)));
+            }
+            if entry.prev_hash != prev_hash {
+                return Ok(broken("does not link to the previous entry"));
+            }
+            if entry.hash != entry.compute_hash() {
+                return Ok(broken("contents do not match the entry hash"));
+            }
+            prev_hash = entry.hash.clone();
+        }
+        Ok(AuditVerification::Intact {
+            entries: entries.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_detects_tampering() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = AuditLog::at(dir.path().join("audit.jsonl"));
+        let first = log.append("0xabc", "cast_add", "0x01", false).unwrap();
+        let second = log.append("0xabc", "cast_add", "0x01", true).unwrap();
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.prev_hash, first.hash);
+        assert_eq!(
+            log.verify().unwrap(),
+            AuditVerification::Intact { entries: 2 }
+        );
+
+        let content = std::fs::read_to_string(log.path()).unwrap();
+        std::fs::write(
+            log.path(),
+            content.replace("\"broadcast\":false", "\"broadcast\":true"),
+        )
+        .unwrap();
+        assert!(matches!(
+            log.verify().unwrap(),
+            AuditVerification::Broken { line: 1, .. }
+        ));
+
+        let last_line = content.lines().last().unwrap();
+        std::fs::write(log.path(), format!("{last_line}\n")).unwrap();
+        assert!(matches!(
+            log.verify().unwrap(),
+            AuditVerification::Broken { line: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_message_operation() {
+        assert_eq!(message_operation("MESSAGE_TYPE_CAST_ADD"), "cast_add");
+        assert_eq!(
+            message_operation("MESSAGE_TYPE_USERNAME_PROOF"),
+            "username_proof"
+        );
+    }
+}
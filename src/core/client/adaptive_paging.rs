@@ -0,0 +1,233 @@
+//! Page size and request pacing that adapt to hub rate limits
+//!
+//! Public hubs answer bulk pagination with 429 (rate limited) or 503
+//! (overloaded) long before a walk over a large FID completes. Instead of
+//! failing at a fixed page size, [`AdaptivePaging`] halves the page size and
+//! backs off whenever the hub pushes back, retries the same page token, and
+//! ramps back up after a run of successful pages. Hub page tokens mark a
+//! position rather than a page number, so the page size can change mid-walk.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+
+use crate::core::client::hub_pool::HubPool;
+
+/// Largest page size requested from hubs
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Smallest page size the pager shrinks to
+pub const MIN_PAGE_SIZE: u32 = 10;
+
+/// Delay after the first throttled response
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Longest delay between requests
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Successful pages in a row before the page size grows again
+const RAMP_UP_AFTER: u32 = 3;
+
+/// Throttled responses in a row before a page is given up on
+const MAX_THROTTLED_ATTEMPTS: u32 = 10;
+
+#[derive(Debug)]
+struct PagingState {
+    max_page_size: u32,
+    page_size: u32,
+    delay: Duration,
+    successes: u32,
+}
+
+/// Page size and delay shared by the requests of one pagination walk
+///
+/// Cheap to clone; clones share state, so pipelined requests slow down together.
+#[derive(Debug, Clone)]
+pub struct AdaptivePaging {
+    state: Arc<Mutex<PagingState>>,
+}
+
+impl Default for AdaptivePaging {
+    fn default() -> Self {
+        Self::new(MAX_PAGE_SIZE)
+    }
+}
+
+impl AdaptivePaging {
+    /// Pager starting at, and never exceeding, `max_page_size`
+    pub fn new(max_page_size: u32) -> Self {
+        let max_page_size = max_page_size.max(1);
+        Self {
+            state: Arc::new(Mutex::new(PagingState {
+                max_page_size,
+                page_size: max_page_size,
+                delay: Duration::ZERO,
+                successes: 0,
+            })),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, PagingState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Page size for the next request
+    pub fn page_size(&self) -> u32 {
+        self.state().page_size
+    }
+
+    /// Delay before the next request
+    pub fn delay(&self) -> Duration {
+        self.state().delay
+    }
+
+    /// Shrink the page size and back off after a 429/503
+    ///
+    /// # Arguments
+    /// * `retry_after` - Delay the hub asked for, if it sent `Retry-After`
+    pub fn on_throttled(&self, retry_after: Option<Duration>) {
+        let mut state = self.state();
+        state.successes = 0;
+        state.page_size = (state.page_size / 2).max(MIN_PAGE_SIZE.min(state.max_page_size));
+        let backoff = (state.delay * 2).max(INITIAL_BACKOFF);
+        state.delay = retry_after.unwrap_or(backoff).min(MAX_BACKOFF);
+    }
+
+    /// Record a successful page, growing the page size after a run of them
+    pub fn on_success(&self) {
+        let mut state = self.state();
+        state.successes += 1;
+        if state.successes < RAMP_UP_AFTER {
+            return;
+        }
+        state.successes = 0;
+        state.page_size = (state.page_size * 2).min(state.max_page_size);
+        state.delay /= 2;
+        if state.delay < INITIAL_BACKOFF / 4 {
+            state.delay = Duration::ZERO;
+        }
+    }
+}
+
+/// Whether a hub status means "slow down" rather than a failed request
+fn is_throttled(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    )
+}
+
+/// Fetch one page of a hub list endpoint, adapting to rate limits
+///
+/// # Arguments
+/// * `client` - Hub connection
+/// * `base_url` - Full endpoint URL with its query, without paging parameters
+/// * `page_token` - Token of the page to fetch, `None` for the first page
+/// * `paging` - Page size and pacing shared by the walk
+/// * `what` - Short description used in error messages
+///
+/// # Returns
+/// * `Result<serde_json::Value>` - The parsed page, or an error once the hub
+///   keeps throttling or fails otherwise
+pub(crate) async fn fetch_page_adaptive(
+    client: HubPool,
+    base_url: String,
+    page_token: Option<String>,
+    paging: AdaptivePaging,
+    what: &str,
+) -> Result<serde_json::Value> {
+    for _ in 0..MAX_THROTTLED_ATTEMPTS {
+        let delay = paging.delay();
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+
+        let mut url = format!("{base_url}&pageSize={}", paging.page_size());
+        if let Some(token) = &page_token {
+            url.push_str(&format!("&pageToken={token}"));
+        }
+        let response = client
+            .get(&url)
+            .await
+            .with_context(|| format!("Failed to get {what} from Farcaster Hub"))?;
+
+        let status = response.status();
+        if is_throttled(status) {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+                .map(Duration::from_secs);
+            paging.on_throttled(retry_after);
+            if crate::core::client::hub_pool::is_verbose() {
+                crate::eprintln!(
+                    "⏳ Hub returned {status} for {what}; page size now {}, waiting {:?}",
+                    paging.page_size(),
+                    paging.delay()
+                );
+            }
+            continue;
+        }
+
+        let response_text = response.text().await?;
+        if !status.is_success() {
+            return Err(anyhow::anyhow!(
+                "Farcaster Hub returned error {}: {}",
+                status,
+                response_text
+            ));
+        }
+        paging.on_success();
+        return serde_json::from_str(&response_text)
+            .with_context(|| format!("Failed to parse {what} response"));
+    }
+    Err(anyhow::anyhow!(
+        "Farcaster Hub kept rate limiting {} requests ({} attempts at page size {})",
+        what,
+        MAX_THROTTLED_ATTEMPTS,
+        paging.page_size()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrinks_and_ramps_back_up() {
+        let paging = AdaptivePaging::default();
+        paging.on_throttled(None);
+        assert_eq!(paging.page_size(), 50);
+        assert_eq!(paging.delay(), INITIAL_BACKOFF);
+        paging.on_throttled(Some(Duration::from_secs(120)));
+        assert_eq!(paging.page_size(), 25);
+        assert_eq!(paging.delay(), MAX_BACKOFF);
+        for _ in 0..3 {
+            paging.on_throttled(None);
+        }
+        assert_eq!(paging.page_size(), MIN_PAGE_SIZE);
+
+        for _ in 0..RAMP_UP_AFTER * 10 {
+            paging.on_success();
+        }
+        assert_eq!(paging.page_size(), MAX_PAGE_SIZE);
+        assert_eq!(paging.delay(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_small_limits_stay_small() {
+        let paging = AdaptivePaging::new(5);
+        paging.on_throttled(None);
+        assert_eq!(paging.page_size(), 5);
+        for _ in 0..RAMP_UP_AFTER {
+            paging.on_success();
+        }
+        assert_eq!(paging.page_size(), 5);
+    }
+}
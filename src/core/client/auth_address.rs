@@ -0,0 +1,113 @@
+use anyhow::Result;
+use ethers::signers::LocalWallet;
+use protobuf::Message as ProtobufMessage;
+
+use crate::core::client::hub_client::farcaster_now;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_client::HubResponse;
+use crate::core::client::hub_client::Signing;
+use crate::core::crypto::auth_address;
+use crate::core::protocol::message::FarcasterNetwork;
+use crate::core::protocol::message::HashScheme;
+use crate::core::protocol::message::Message;
+use crate::core::protocol::message::MessageData;
+use crate::core::protocol::message::MessageType;
+use crate::core::protocol::username_proof::UserNameProof;
+use crate::core::protocol::username_proof::UserNameType;
+
+/// Hash and sign message data with an auth address
+///
+/// The hash is the first 20 bytes of the BLAKE3 digest of the encoded data, as
+/// for Ed25519 messages; only the signature scheme and signer differ.
+///
+/// # Arguments
+/// * `data` - The message data to sign
+/// * `wallet` - Wallet of an auth address registered for `data.fid`
+///
+/// # Returns
+/// * `Result<Message>` - The signed message, with `data_bytes` set
+pub fn build_auth_address_message(data: MessageData, wallet: &LocalWallet) -> Result<Message> {
+    let data_bytes = data.write_to_bytes()?;
+    let hash = blake3::hash(&data_bytes).as_bytes()[..20].to_vec();
+
+    let mut message = Message::new();
+    message.set_hash(hash);
+    message.set_hash_scheme(HashScheme::HASH_SCHEME_BLAKE3);
+    auth_address::sign_message(&mut message, wallet)?;
+    message.set_data_bytes(data_bytes);
+    Ok(message)
+}
+
+impl FarcasterClient<Signing> {
+    /// Sign message data with an auth address and submit it
+    ///
+    /// The hub checks that the address is an active key of type 2 for the FID
+    /// and that the message type may be signed by an auth address.
+    ///
+    /// # Arguments
+    /// * `data` - The message data to submit
+    /// * `wallet` - Wallet of the auth address
+    ///
+    /// # Returns
+    /// * `Result<HubResponse>` - The hub response or an error
+    pub async fn submit_with_auth_address(
+        &self,
+        data: MessageData,
+        wallet: &LocalWallet,
+    ) -> Result<HubResponse> {
+        let message = build_auth_address_message(data, wallet)?;
+        self.submit_message(&message).await
+    }
+
+    /// Submit a username proof signed by an auth address
+    ///
+    /// # Arguments
+    /// * `proof` - The username proof to submit
+    /// * `wallet` - Wallet of an auth address registered for the proof's FID
+    ///
+    /// # Returns
+    /// * `Result<HubResponse>` - The hub response or an error
+    pub async fn submit_username_proof_with_auth_address(
+        &self,
+        proof: &UserNameProof,
+        wallet: &LocalWallet,
+    ) -> Result<HubResponse> {
+        let farcaster_timestamp = farcaster_now();
+
+        let mut username_proof = proof.clone();
+        username_proof.set_timestamp(farcaster_timestamp as u64);
+        username_proof.set_field_type(UserNameType::USERNAME_TYPE_BASENAME);
+
+        let mut data = MessageData::new();
+        data.set_field_type(MessageType::MESSAGE_TYPE_USERNAME_PROOF);
+        data.set_fid(proof.get_fid());
+        data.set_timestamp(farcaster_timestamp);
+        data.set_network(FarcasterNetwork::FARCASTER_NETWORK_MAINNET);
+        data.set_username_proof_body(username_proof);
+
+        self.submit_with_auth_address(data, wallet).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::signers::Signer;
+
+    use super::*;
+
+    #[test]
+    fn test_message_is_signed_by_auth_address() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let mut data = MessageData::new();
+        data.set_fid(42);
+        data.set_field_type(MessageType::MESSAGE_TYPE_CAST_ADD);
+
+        let message = build_auth_address_message(data.clone(), &wallet).unwrap();
+
+        assert_eq!(message.get_hash().len(), 20);
+        assert_eq!(message.get_data_bytes(), data.write_to_bytes().unwrap());
+        let signer =
+            auth_address::recover_signer(message.get_hash(), message.get_signature()).unwrap();
+        assert_eq!(signer, wallet.address());
+    }
+}
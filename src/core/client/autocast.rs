@@ -0,0 +1,399 @@
+//! Casts rendered from RSS, Atom and JSON feed items
+//!
+//! A feed is fetched, each item it lists is rendered through a template such
+//! as `"New post: {title} {link}"`, and items already handled are remembered
+//! in `<data dir>/autocast/` so a restart never publishes the same item twice.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::client::cast::CastDraft;
+use crate::core::client::cast::CastEmbed;
+use crate::core::utils::data_dir;
+
+/// Item ids remembered per feed; feeds list far fewer items than this
+const SEEN_CAPACITY: usize = 2000;
+
+/// Placeholders a template may use
+pub const PLACEHOLDERS: [&str; 5] = ["title", "link", "summary", "id", "published"];
+
+/// One entry of a feed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FeedItem {
+    /// Stable identity: the guid or id, else the link, else the title
+    pub id: String,
+    pub title: String,
+    pub link: String,
+    /// Description or summary as plain text
+    pub summary: String,
+    /// Publication date as the feed gives it
+    pub published: String,
+}
+
+impl FeedItem {
+    fn with_id(mut self, id: Option<String>) -> Option<Self> {
+        self.id = id
+            .filter(|id| !id.is_empty())
+            .or_else(|| Some(self.link.clone()).filter(|link| !link.is_empty()))
+            .or_else(|| Some(self.title.clone()).filter(|title| !title.is_empty()))?;
+        Some(self)
+    }
+
+    fn field(&self, name: &str) -> Option<&str> {
+        match name {
+            "title" => Some(&self.title),
+            "link" => Some(&self.link),
+            "summary" => Some(&self.summary),
+            "id" => Some(&self.id),
+            "published" => Some(&self.published),
+            _ => None,
+        }
+    }
+}
+
+/// Parse an RSS 2.0, Atom or JSON feed, in the order the feed lists items
+///
+/// JSON may be a [JSON Feed](https://jsonfeed.org) or a plain array of
+/// objects with `title`, `url` or `link`, and optionally `id` and `summary`.
+pub fn parse_feed(body: &str) -> Result<Vec<FeedItem>> {
+    let body = body.trim_start_matches('\u{feff}').trim_start();
+    if body.starts_with('{') || body.starts_with('[') {
+        let json: Value = serde_json::from_str(body).context("Invalid JSON feed")?;
+        let items = json.get("items").unwrap_or(&json);
+        let items = items.as_array().context("JSON feed has no items array")?;
+        return Ok(items.iter().filter_map(json_item).collect());
+    }
+
+    let document = roxmltree::Document::parse(body).context("Invalid RSS or Atom feed")?;
+    let items = document
+        .descendants()
+        .filter_map(|node| match node.tag_name().name() {
+            "item" => rss_item(node),
+            "entry" => atom_item(node),
+            _ => None,
+        })
+        .collect();
+    Ok(items)
+}
+
+fn json_item(item: &Value) -> Option<FeedItem> {
+    let text = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| item.get(key).and_then(|v| v.as_str()))
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+    let id = item.get("id").map(|id| match id {
+        Value::String(id) => id.clone(),
+        other => other.to_string(),
+    });
+    FeedItem {
+        id: String::new(),
+        title: text(&["title"]),
+        link: text(&["url", "link", "external_url"]),
+        summary: plain_text(&text(&["summary", "content_text", "content_html"])),
+        published: text(&["date_published", "published"]),
+    }
+    .with_id(id)
+}
+
+fn child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    node.children()
+        .find(|child| child.tag_name().name() == name)
+        .map(|child| {
+            child
+                .descendants()
+                .filter(|n| n.is_text())
+                .filter_map(|n| n.text())
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+}
+
+fn rss_item(node: roxmltree::Node) -> Option<FeedItem> {
+    let text = |name: &str| child_text(node, name).unwrap_or_default();
+    FeedItem {
+        id: String::new(),
+        title: text("title"),
+        link: text("link"),
+        summary: plain_text(&text("description")),
+        published: text("pubDate"),
+    }
+    .with_id(child_text(node, "guid"))
+}
+
+fn atom_item(node: roxmltree::Node) -> Option<FeedItem> {
+    let text = |name: &str| child_text(node, name).unwrap_or_default();
+    // The alternate link is the item's page; a bare <link href> counts as one
+    let link = node
+        .children()
+        .filter(|child| child.tag_name().name() == "link")
+        .find(|child| child.attribute("rel").is_none_or(|rel| rel == "alternate"))
+        .and_then(|child| child.attribute("href"))
+        .unwrap_or_default()
+        .to_string();
+    let summary = child_text(node, "summary").or_else(|| child_text(node, "content"));
+    let published = child_text(node, "published").or_else(|| child_text(node, "updated"));
+    FeedItem {
+        id: String::new(),
+        title: text("title"),
+        link,
+        summary: plain_text(&summary.unwrap_or_default()),
+        published: published.unwrap_or_default(),
+    }
+    .with_id(child_text(node, "id"))
+}
+
+/// Strip HTML tags and collapse whitespace
+fn plain_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Check that a template only uses known placeholders
+pub fn check_template(template: &str) -> Result<()> {
+    render(template, &FeedItem::default()).map(|_| ())
+}
+
+/// Fill `{title}`, `{link}`, `{summary}`, `{id}` and `{published}` in a
+/// template; `{{` and `}}` stand for literal braces
+pub fn render(template: &str, item: &FeedItem) -> Result<String> {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        text.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            text.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let end = match tail.find('}') {
+            Some(end) if tail.starts_with('{') => end,
+            _ => anyhow::bail!("Unbalanced brace in template at `{tail}`; use {{{{ or }}}}"),
+        };
+        let name = &tail[1..end];
+        let value = item.field(name).with_context(|| {
+            format!(
+                "Unknown placeholder {{{name}}}; available: {}",
+                PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(" ")
+            )
+        })?;
+        text.push_str(value);
+        rest = &tail[end + 1..];
+    }
+    text.push_str(rest);
+    Ok(text.trim().to_string())
+}
+
+/// What to publish for a feed
+#[derive(Debug, Clone)]
+pub struct Autocast {
+    pub feed_url: String,
+    pub template: String,
+    /// Also embed each item's link, so clients show a preview card
+    pub embed_link: bool,
+}
+
+impl Autocast {
+    /// Fetch the feed and parse its items
+    pub async fn fetch(&self) -> Result<Vec<FeedItem>> {
+        let response = crate::core::utils::http::client()
+            .get(&self.feed_url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch feed {}", self.feed_url))?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Feed {} returned HTTP {status}", self.feed_url);
+        }
+        parse_feed(&response.text().await?)
+    }
+
+    /// Cast draft of one feed item
+    pub fn draft(&self, item: &FeedItem) -> Result<CastDraft> {
+        let embeds = if self.embed_link && !item.link.is_empty() {
+            vec![CastEmbed::Url(item.link.clone())]
+        } else {
+            Vec::new()
+        };
+        Ok(CastDraft {
+            text: render(&self.template, item)?,
+            embeds,
+            ..CastDraft::default()
+        })
+    }
+}
+
+/// Ids of the items of one feed that were already published or skipped
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SeenItems {
+    feed_url: String,
+    ids: VecDeque<String>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    stored: bool,
+}
+
+impl SeenItems {
+    /// Load the record of a feed, starting empty if there is none yet
+    pub fn open(feed_url: &str) -> Result<Self> {
+        let name = hex::encode(&blake3::hash(feed_url.as_bytes()).as_bytes()[..8]);
+        let path = data_dir::data_dir()
+            .join("autocast")
+            .join(format!("{name}.json"));
+        let mut seen = match std::fs::read_to_string(&path) {
+            Ok(content) => Self {
+                stored: true,
+                ..serde_json::from_str(&content)
+                    .with_context(|| format!("Corrupt autocast record {}", path.display()))?
+            },
+            Err(_) => Self {
+                feed_url: feed_url.to_string(),
+                ..Self::default()
+            },
+        };
+        seen.path = Some(path);
+        Ok(seen)
+    }
+
+    /// Whether this feed was never polled before
+    pub fn is_first_run(&self) -> bool {
+        !self.stored
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.iter().any(|seen| seen == id)
+    }
+
+    /// Items not handled yet, oldest first
+    ///
+    /// Feeds list the newest item first, so the order is reversed.
+    pub fn unseen(&self, items: &[FeedItem]) -> Vec<FeedItem> {
+        items
+            .iter()
+            .rev()
+            .filter(|item| !self.contains(&item.id))
+            .cloned()
+            .collect()
+    }
+
+    /// Remember an item; call [`SeenItems::save`] to persist it
+    pub fn insert(&mut self, id: &str) {
+        if self.contains(id) {
+            return;
+        }
+        self.ids.push_back(id.to_string());
+        while self.ids.len() > SEEN_CAPACITY {
+            self.ids.pop_front();
+        }
+    }
+
+    /// Write the record to the data directory
+    pub fn save(&mut self) -> Result<()> {
+        if let Some(path) = &self.path {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+            self.stored = true;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_feeds() {
+        let rss = r#"<?xml version="1.0"?>
+            <rss version="2.0"><channel><title>Blog</title>
+              <item><title>Second</title><link>https://b.example/2</link>
+                <guid>post-2</guid><description><![CDATA[<p>Hello &amp; <b>bye</b></p>]]></description></item>
+              <item><title>First</title><link>https://b.example/1</link></item>
+            </channel></rss>"#;
+        let items = parse_feed(rss).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, "post-2");
+        assert_eq!(items[0].summary, "Hello & bye");
+        assert_eq!(items[1].id, "https://b.example/1");
+
+        let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+              <entry><id>tag:a,1</id><title>Atom</title>
+                <link rel="self" href="https://a.example/self"/>
+                <link href="https://a.example/post"/></entry>
+            </feed>"#;
+        let items = parse_feed(atom).unwrap();
+        assert_eq!(items[0].link, "https://a.example/post");
+
+        let json = r#"{"version": "https://jsonfeed.org/version/1.1",
+            "items": [{"id": 7, "title": "JSON", "url": "https://j.example/7"}]}"#;
+        let items = parse_feed(json).unwrap();
+        assert_eq!(
+            (items[0].id.as_str(), items[0].title.as_str()),
+            ("7", "JSON")
+        );
+        assert!(parse_feed("not a feed").is_err());
+    }
+
+    #[test]
+    fn test_render_template() {
+        let item = FeedItem {
+            id: "1".to_string(),
+            title: "Release".to_string(),
+            link: "https://example.com".to_string(),
+            ..FeedItem::default()
+        };
+        assert_eq!(
+            render("New post: {title} {link} {{x}}", &item).unwrap(),
+            "New post: Release https://example.com {x}"
+        );
+        assert!(check_template("{titel}").is_err());
+        assert!(check_template("{title").is_err());
+        assert!(check_template("} {title}").is_err());
+    }
+
+    #[test]
+    fn test_unseen_items_oldest_first() {
+        let item = |id: &str| FeedItem {
+            id: id.to_string(),
+            ..FeedItem::default()
+        };
+        let mut seen = SeenItems::default();
+        assert!(seen.is_first_run());
+        seen.insert("b");
+        let unseen = seen.unseen(&[item("c"), item("b"), item("a")]);
+        let ids: Vec<&str> = unseen.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["a", "c"]);
+    }
+}
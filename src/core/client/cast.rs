@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use ed25519_dalek::SigningKey;
+use serde_json::Value;
+
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_client::HubResponse;
+use crate::core::client::hub_client::Signing;
+use crate::core::protocol::builder::CastBuilder;
+use crate::core::protocol::builder::MessageBuilder;
+use crate::core::protocol::builder::MAX_CAST_TEXT_BYTES;
+use crate::core::protocol::builder::MAX_LONG_CAST_TEXT_BYTES;
+use crate::core::protocol::cast_text::encode_mentions;
+use crate::core::protocol::cast_text::split_thread;
+use crate::core::protocol::message::CastId;
+use crate::core::protocol::message::CastType;
+use crate::core::protocol::message::Message;
+
+/// Build a signed CastAdd message with plain text
+///
+/// # Arguments
+/// * `fid` - The author's FID
+/// * `text` - The cast text
+/// * `signing_key` - An active signer of `fid`
+///
+/// # Returns
+/// * `Result<Message>` - The signed message, with `data_bytes` set
+pub fn build_cast_message(fid: u64, text: &str, signing_key: &SigningKey) -> Result<Message> {
+    MessageBuilder::new(fid).cast(text).sign(signing_key)
+}
+
+/// First Hubble release that accepts long casts
+const HUBBLE_LONG_CAST_VERSION: (u64, u64, u64) = (1, 13, 0);
+
+/// Longest cast type a hub accepts, judged from its `/v1/info` response
+///
+/// Snapchain nodes, which report `shardInfos`, accept 10k casts. Hubble
+/// accepts long casts from [`HUBBLE_LONG_CAST_VERSION`] on; anything else is
+/// assumed to take regular casts only.
+pub fn supported_cast_type(info: &Value) -> CastType {
+    if info.get("shardInfos").is_some() {
+        return CastType::TEN_K_CAST;
+    }
+    let version = info
+        .get("version")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let mut parts = version
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse::<u64>().unwrap_or(0));
+    let version = (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    );
+    if version >= HUBBLE_LONG_CAST_VERSION {
+        CastType::LONG_CAST
+    } else {
+        CastType::CAST
+    }
+}
+
+/// Something embedded in a cast
+#[derive(Debug, Clone, PartialEq)]
+pub enum CastEmbed {
+    /// A link, such as a web page or an image
+    Url(String),
+    /// Another cast, making this one a quote cast
+    Cast(CastId),
+}
+
+impl std::fmt::Display for CastEmbed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CastEmbed::Url(url) => write!(f, "{url}"),
+            CastEmbed::Cast(cast_id) => write!(
+                f,
+                "cast {}:0x{}",
+                cast_id.get_fid(),
+                hex::encode(cast_id.get_hash())
+            ),
+        }
+    }
+}
+
+/// A cast to publish, optionally as a thread when its text is too long
+#[derive(Debug, Clone, Default)]
+pub struct CastDraft {
+    /// Text as written, with `@username` tokens
+    pub text: String,
+    /// FIDs of the `@username` tokens to encode as mentions, keyed by
+    /// lowercased name; other tokens stay plain text
+    pub mentions: HashMap<String, u64>,
+    /// URLs and quoted casts embedded in the cast (the first part of a
+    /// thread), in order
+    pub embeds: Vec<CastEmbed>,
+    /// Cast the draft replies to
+    pub parent: Option<CastId>,
+    /// Parent URL such as a channel, when not replying to a cast
+    pub parent_url: Option<String>,
+    /// Regular, long or 10k cast; only regular casts are split into threads
+    pub cast_type: CastType,
+}
+
+impl CastDraft {
+    /// Texts of the casts to publish
+    ///
+    /// Without `thread` this is the text itself, even when over the limit, so
+    /// the builder reports it. With `thread` long text is split into numbered
+    /// parts of at most `MAX_CAST_TEXT_BYTES`.
+    pub fn parts(&self, thread: bool) -> Vec<String> {
+        if thread {
+            split_thread(&self.text, MAX_CAST_TEXT_BYTES)
+        } else {
+            vec![self.text.clone()]
+        }
+    }
+
+    /// Smallest cast type that fits the whole text in a single cast
+    pub fn single_cast_type(&self) -> CastType {
+        let length = encode_mentions(&self.text, &self.mentions).text.len();
+        if length <= MAX_CAST_TEXT_BYTES {
+            CastType::CAST
+        } else if length <= MAX_LONG_CAST_TEXT_BYTES {
+            CastType::LONG_CAST
+        } else {
+            CastType::TEN_K_CAST
+        }
+    }
+
+    /// Builder of one part; parts after the first reply to `previous`
+    fn part_builder(&self, fid: u64, text: &str, previous: Option<CastId>) -> CastBuilder {
+        let encoded = encode_mentions(text, &self.mentions);
+        let mut cast = MessageBuilder::new(fid)
+            .cast(encoded.text)
+            .cast_type(self.cast_type);
+        for (mention, position) in encoded.mentions.into_iter().zip(encoded.positions) {
+            cast = cast.mention(mention, position);
+        }
+        match previous {
+            Some(previous) => cast = cast.reply_to(previous),
+            None => {
+                for embed in &self.embeds {
+                    cast = match embed {
+                        CastEmbed::Url(url) => cast.embed_url(url.clone()),
+                        CastEmbed::Cast(cast_id) => cast.embed_cast(cast_id.clone()),
+                    };
+                }
+                if let Some(parent) = &self.parent {
+                    cast = cast.reply_to(parent.clone());
+                } else if let Some(url) = &self.parent_url {
+                    cast = cast.parent_url(url.clone());
+                }
+            }
+        }
+        cast
+    }
+
+    /// Validate every cast of the draft without signing
+    ///
+    /// # Arguments
+    /// * `fid` - The author's FID
+    /// * `thread` - Split long text into a thread
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - The texts that would be published
+    pub fn validate(&self, fid: u64, thread: bool) -> Result<Vec<String>> {
+        let parts = self.parts(thread);
+        let placeholder = CastId {
+            fid,
+            hash: vec![0; 20],
+            ..Default::default()
+        };
+        for (i, text) in parts.iter().enumerate() {
+            let previous = (i > 0).then(|| placeholder.clone());
+            self.part_builder(fid, text, previous).build()?;
+        }
+        Ok(parts)
+    }
+
+    /// Build the signed casts, each thread part replying to the one before
+    ///
+    /// # Arguments
+    /// * `fid` - The author's FID
+    /// * `thread` - Split long text into a thread
+    /// * `signing_key` - An active signer of `fid`
+    ///
+    /// # Returns
+    /// * `Result<Vec<Message>>` - The signed messages in posting order
+    pub fn sign(&self, fid: u64, thread: bool, signing_key: &SigningKey) -> Result<Vec<Message>> {
+        let mut messages: Vec<Message> = Vec::new();
+        for text in self.validate(fid, thread)? {
+            let previous = messages.last().map(|message| CastId {
+                fid,
+                hash: message.get_hash().to_vec(),
+                ..Default::default()
+            });
+            messages.push(self.part_builder(fid, &text, previous).sign(signing_key)?);
+        }
+        Ok(messages)
+    }
+}
+
+impl FarcasterClient<Signing> {
+    /// Publish a plain text cast
+    ///
+    /// # Arguments
+    /// * `fid` - The author's FID
+    /// * `text` - The cast text
+    /// * `signing_key` - An active signer of `fid`
+    ///
+    /// # Returns
+    /// * `Result<HubResponse>` - The hub response or an error
+    pub async fn submit_cast(
+        &self,
+        fid: u64,
+        text: &str,
+        signing_key: &SigningKey,
+    ) -> Result<HubResponse> {
+        let message = build_cast_message(fid, text, signing_key)?;
+        self.submit_message(&message).await
+    }
+
+    /// Publish a cast draft, as a reply chain when `thread` splits it
+    ///
+    /// Parts are submitted in order and submission stops at the first failure,
+    /// since later parts reply to earlier ones.
+    ///
+    /// # Arguments
+    /// * `fid` - The author's FID
+    /// * `draft` - Text, embeds and parent of the cast
+    /// * `thread` - Split long text into a thread
+    /// * `signing_key` - An active signer of `fid`
+    ///
+    /// # Returns
+    /// * `Result<Vec<(Message, HubResponse)>>` - Each published message with its hub response
+    pub async fn submit_cast_draft(
+        &self,
+        fid: u64,
+        draft: &CastDraft,
+        thread: bool,
+        signing_key: &SigningKey,
+    ) -> Result<Vec<(Message, HubResponse)>> {
+        let mut published = Vec::new();
+        for (i, message) in draft
+            .sign(fid, thread, signing_key)?
+            .into_iter()
+            .enumerate()
+        {
+            let response = self
+                .submit_message(&message)
+                .await
+                .map_err(|e| e.context(format!("Failed to publish part {} of the cast", i + 1)))?;
+            published.push((message, response));
+        }
+        Ok(published)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::Signature;
+    use ed25519_dalek::Verifier;
+    use protobuf::Message as ProtobufMessage;
+
+    use super::*;
+    use crate::core::protocol::message::MessageData;
+
+    #[test]
+    fn test_build_cast_message() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let message = build_cast_message(42, "gm", &signing_key).unwrap();
+
+        let data = MessageData::parse_from_bytes(message.get_data_bytes()).unwrap();
+        assert_eq!(data.get_fid(), 42);
+        assert_eq!(data.get_cast_add_body().get_text(), "gm");
+
+        let signature = Signature::from_slice(message.get_signature()).unwrap();
+        assert!(signing_key
+            .verifying_key()
+            .verify(message.get_hash(), &signature)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_thread_parts_reply_to_each_other() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let draft = CastDraft {
+            text: "word ".repeat(150),
+            embeds: vec![CastEmbed::Url("https://example.com".to_string())],
+            ..Default::default()
+        };
+        assert!(draft.validate(42, false).is_err());
+
+        let messages = draft.sign(42, true, &signing_key).unwrap();
+        assert_eq!(messages.len(), 3);
+        let first = MessageData::parse_from_bytes(messages[0].get_data_bytes()).unwrap();
+        assert_eq!(first.get_cast_add_body().get_embeds().len(), 1);
+        assert!(!first.get_cast_add_body().has_parent_cast_id());
+        let second = MessageData::parse_from_bytes(messages[1].get_data_bytes()).unwrap();
+        let body = second.get_cast_add_body();
+        assert!(body.get_embeds().is_empty());
+        assert_eq!(body.get_parent_cast_id().get_hash(), messages[0].get_hash());
+        assert!(body.get_text().ends_with("(2/3)"));
+    }
+
+    #[test]
+    fn test_draft_encodes_embeds() {
+        let quoted = CastId {
+            fid: 5,
+            hash: vec![1; 20],
+            ..Default::default()
+        };
+        let mut draft = CastDraft {
+            text: "look".to_string(),
+            embeds: vec![
+                CastEmbed::Url("https://example.com/cat.png".to_string()),
+                CastEmbed::Cast(quoted.clone()),
+            ],
+            ..Default::default()
+        };
+        let message = draft
+            .sign(42, false, &SigningKey::from_bytes(&[7u8; 32]))
+            .unwrap();
+        let data = MessageData::parse_from_bytes(message[0].get_data_bytes()).unwrap();
+        let embeds = data.get_cast_add_body().get_embeds();
+        assert_eq!(embeds[0].get_url(), "https://example.com/cat.png");
+        assert_eq!(embeds[1].get_cast_id(), &quoted);
+
+        draft
+            .embeds
+            .push(CastEmbed::Url("https://example.com".to_string()));
+        assert!(draft.validate(42, false).is_err());
+    }
+
+    #[test]
+    fn test_long_cast() {
+        let mut draft = CastDraft {
+            text: "word ".repeat(150),
+            ..Default::default()
+        };
+        assert_eq!(draft.single_cast_type(), CastType::LONG_CAST);
+        draft.cast_type = draft.single_cast_type();
+        let message = draft
+            .sign(42, false, &SigningKey::from_bytes(&[7u8; 32]))
+            .unwrap();
+        let data = MessageData::parse_from_bytes(message[0].get_data_bytes()).unwrap();
+        assert_eq!(
+            data.get_cast_add_body().get_field_type(),
+            CastType::LONG_CAST
+        );
+
+        let info = |info: Value| supported_cast_type(&info);
+        assert_eq!(
+            info(serde_json::json!({"version": "0.2.0", "shardInfos": []})),
+            CastType::TEN_K_CAST
+        );
+        assert_eq!(
+            info(serde_json::json!({"version": "1.13.4"})),
+            CastType::LONG_CAST
+        );
+        assert_eq!(
+            info(serde_json::json!({"version": "1.9.0"})),
+            CastType::CAST
+        );
+    }
+
+    #[test]
+    fn test_draft_encodes_mentions() {
+        let draft = CastDraft {
+            text: "thanks @dwr and @unknown".to_string(),
+            mentions: HashMap::from([("dwr".to_string(), 3)]),
+            ..Default::default()
+        };
+        let message = draft
+            .sign(42, false, &SigningKey::from_bytes(&[7u8; 32]))
+            .unwrap();
+        let body = MessageData::parse_from_bytes(message[0].get_data_bytes()).unwrap();
+        let body = body.get_cast_add_body();
+        assert_eq!(body.get_text(), "thanks  and @unknown");
+        assert_eq!(body.get_mentions(), &[3]);
+        assert_eq!(body.get_mentions_positions(), &[7]);
+    }
+}
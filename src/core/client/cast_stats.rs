@@ -0,0 +1,323 @@
+//! Engagement summaries of casts
+//!
+//! Likes and recasts are read from the reactions targeting a cast and replies
+//! from `castsByParent`. Hubs keep no index of embeds, so quotes are counted
+//! among the casts that are read anyway: the replies, and the author's own
+//! casts posted after the cast.
+//!
+//! Summaries are cached in the data directory for [`CACHE_TTL`], so ranking
+//! the same FID's casts again is fast.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use futures::stream::StreamExt;
+use futures::stream::{
+    self,
+};
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::client::hub_client::farcaster_now;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::replay::message_hash;
+use crate::core::client::time_window::TimeWindow;
+use crate::core::utils::ttl_cache::TtlCache;
+
+/// How long a cached summary is reused
+pub const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Casts whose engagement is fetched at the same time
+const STATS_CONCURRENCY: usize = 4;
+
+/// Most of the author's later casts searched for quotes of a single cast
+const MAX_QUOTE_SCAN: u32 = 500;
+
+/// Engagement counts of one cast
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CastStats {
+    pub fid: u64,
+    pub hash: String,
+    pub text: String,
+    /// Farcaster timestamp (seconds since the Farcaster epoch)
+    pub timestamp: u64,
+    pub likes: u64,
+    pub recasts: u64,
+    pub replies: u64,
+    pub quotes: u64,
+    /// Sum of the four counts, used for ranking
+    pub engagement: u64,
+}
+
+/// File cache of cast summaries, keyed by cast hash
+#[derive(Debug)]
+pub struct CastStatsCache {
+    entries: TtlCache<String, CastStats>,
+    /// Cached summaries are not reused, only replaced
+    refresh: bool,
+}
+
+impl CastStatsCache {
+    /// Create a cache that lives in memory only
+    pub fn in_memory() -> Self {
+        Self {
+            entries: TtlCache::in_memory(CACHE_TTL),
+            refresh: false,
+        }
+    }
+
+    /// Create a cache loaded from and saved to a JSON file
+    pub fn with_cache_file(path: PathBuf) -> Self {
+        Self {
+            entries: TtlCache::with_file(CACHE_TTL, path),
+            refresh: false,
+        }
+    }
+
+    /// Ignore cached summaries, replacing them with freshly fetched ones
+    pub fn refreshing(mut self) -> Self {
+        self.refresh = true;
+        self
+    }
+
+    /// Default cache file in the data directory
+    pub fn default_cache_path() -> PathBuf {
+        crate::core::utils::data_dir::data_dir()
+            .join("cache")
+            .join("cast_stats.json")
+    }
+
+    /// A summary fetched less than [`CACHE_TTL`] ago
+    pub fn get(&self, hash: &str) -> Option<CastStats> {
+        if self.refresh {
+            return None;
+        }
+        self.entries.get(&hash.to_lowercase())
+    }
+
+    /// Remember a freshly fetched summary
+    pub fn insert(&self, stats: &CastStats) {
+        self.entries.insert(stats.hash.clone(), stats.clone());
+    }
+
+    /// Write the cache back to its file, dropping expired entries
+    pub fn save(&self) -> Result<()> {
+        self.entries.save()
+    }
+}
+
+impl<M> FarcasterClient<M> {
+    /// Engagement summary of a single cast
+    ///
+    /// # Arguments
+    /// * `fid` - The FID of the cast author
+    /// * `hash` - The cast hash (0x-prefixed hex)
+    /// * `cache` - Summaries to reuse and to add to
+    ///
+    /// # Returns
+    /// * `Result<CastStats>` - The counts or an error
+    pub async fn get_cast_stats(
+        &self,
+        fid: u64,
+        hash: &str,
+        cache: &CastStatsCache,
+    ) -> Result<CastStats> {
+        if let Some(stats) = cache.get(hash) {
+            return Ok(stats);
+        }
+        let cast = self.get_cast_by_id(fid, hash).await?;
+        let timestamp = cast_timestamp(&cast).unwrap_or_default();
+        let later = self
+            .get_casts_in_window(fid, TimeWindow::since(timestamp), MAX_QUOTE_SCAN)
+            .await?;
+        let stats = self.count_engagement(&cast, &later).await?;
+        cache.insert(&stats);
+        Ok(stats)
+    }
+
+    /// The casts of a FID from the last `days` days, most engaging first
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `days` - Size of the window, counted back from now
+    /// * `cache` - Summaries to reuse and to add to
+    ///
+    /// # Returns
+    /// * `Result<Vec<CastStats>>` - One summary per cast, ranked by engagement
+    pub async fn get_top_casts(
+        &self,
+        fid: u64,
+        days: u32,
+        cache: &CastStatsCache,
+    ) -> Result<Vec<CastStats>> {
+        let since = u64::from(farcaster_now()).saturating_sub(u64::from(days) * 24 * 60 * 60);
+        let casts = self
+            .get_casts_in_window(fid, TimeWindow::since(since), 0)
+            .await?;
+        let results: Vec<Result<CastStats>> = stream::iter(&casts)
+            .map(|cast| async {
+                let hash = cast_hash(cast).unwrap_or_default();
+                if let Some(stats) = cache.get(&hash) {
+                    return Ok(stats);
+                }
+                let stats = self.count_engagement(cast, &casts).await?;
+                cache.insert(&stats);
+                Ok(stats)
+            })
+            .buffer_unordered(STATS_CONCURRENCY)
+            .collect()
+            .await;
+        let mut ranked = results.into_iter().collect::<Result<Vec<_>>>()?;
+        rank(&mut ranked);
+        Ok(ranked)
+    }
+
+    /// Count reactions, replies and quotes of a cast
+    ///
+    /// # Arguments
+    /// * `cast` - The cast message
+    /// * `others` - Further casts to search for quotes, besides the replies
+    async fn count_engagement(&self, cast: &Value, others: &[Value]) -> Result<CastStats> {
+        let fid = cast
+            .pointer("/data/fid")
+            .and_then(Value::as_u64)
+            .context("Cast has no FID")?;
+        let hash = cast_hash(cast).context("Cast has no hash")?;
+        let (likes, recasts, replies) = tokio::try_join!(
+            self.get_reactions_by_cast(fid, &hash, "REACTION_TYPE_LIKE", 0),
+            self.get_reactions_by_cast(fid, &hash, "REACTION_TYPE_RECAST", 0),
+            self.get_casts_by_parent(fid, &hash, 0),
+        )?;
+        let quotes: HashSet<String> = replies
+            .iter()
+            .chain(others)
+            .filter(|other| quotes_cast(other, fid, &hash))
+            .filter_map(cast_hash)
+            .collect();
+        Ok(CastStats::new(
+            cast,
+            likes.len() as u64,
+            recasts.len() as u64,
+            replies.len() as u64,
+            quotes.len() as u64,
+        ))
+    }
+}
+
+impl CastStats {
+    fn new(cast: &Value, likes: u64, recasts: u64, replies: u64, quotes: u64) -> Self {
+        Self {
+            fid: cast
+                .pointer("/data/fid")
+                .and_then(Value::as_u64)
+                .unwrap_or_default(),
+            hash: cast_hash(cast).unwrap_or_default(),
+            text: cast
+                .pointer("/data/castAddBody/text")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            timestamp: cast_timestamp(cast).unwrap_or_default(),
+            likes,
+            recasts,
+            replies,
+            quotes,
+            engagement: likes + recasts + replies + quotes,
+        }
+    }
+}
+
+/// Order summaries by engagement, newest first among equals
+pub fn rank(stats: &mut [CastStats]) {
+    stats.sort_by(|a, b| {
+        b.engagement
+            .cmp(&a.engagement)
+            .then(b.timestamp.cmp(&a.timestamp))
+    });
+}
+
+/// Whether a cast embeds the cast `fid`/`hash`
+fn quotes_cast(cast: &Value, fid: u64, hash: &str) -> bool {
+    cast.pointer("/data/castAddBody/embeds")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|embed| embed.get("castId"))
+        .any(|cast_id| {
+            cast_id.get("fid").and_then(Value::as_u64) == Some(fid)
+                && cast_id
+                    .get("hash")
+                    .and_then(Value::as_str)
+                    .is_some_and(|h| h.eq_ignore_ascii_case(hash))
+        })
+}
+
+/// The lowercase `0x` hex hash of a cast, as the hub API takes it
+fn cast_hash(cast: &Value) -> Option<String> {
+    message_hash(cast).map(|hash| format!("0x{hash}"))
+}
+
+fn cast_timestamp(message: &Value) -> Option<u64> {
+    message.pointer("/data/timestamp").and_then(Value::as_u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn cast(hash: &str, timestamp: u64, quoted: Option<(u64, &str)>) -> Value {
+        let embeds: Vec<Value> = quoted
+            .map(|(fid, hash)| json!({"castId": {"fid": fid, "hash": hash}}))
+            .into_iter()
+            .collect();
+        json!({
+            "hash": hash,
+            "data": {
+                "fid": 3,
+                "timestamp": timestamp,
+                "castAddBody": {"text": "gm", "embeds": embeds}
+            }
+        })
+    }
+
+    #[test]
+    fn test_quotes_cast() {
+        assert!(quotes_cast(&cast("0xb", 2, Some((3, "0xAA"))), 3, "0xaa"));
+        assert!(!quotes_cast(&cast("0xb", 2, Some((4, "0xaa"))), 3, "0xaa"));
+        assert!(!quotes_cast(&cast("0xb", 2, None), 3, "0xaa"));
+    }
+
+    #[test]
+    fn test_rank_and_cache() {
+        let mut stats = vec![
+            CastStats::new(&cast("0x01", 10, None), 1, 0, 0, 0),
+            CastStats::new(&cast("0x02", 20, None), 2, 1, 1, 1),
+            CastStats::new(&cast("0x03", 30, None), 1, 0, 0, 0),
+        ];
+        assert_eq!(stats[1].engagement, 5);
+        rank(&mut stats);
+        let order: Vec<&str> = stats.iter().map(|s| s.hash.as_str()).collect();
+        assert_eq!(order, ["0x02", "0x03", "0x01"]);
+
+        let cache = CastStatsCache::in_memory();
+        assert!(cache.get("0x02").is_none());
+        cache.insert(&stats[0]);
+        assert_eq!(cache.get("0X02"), Some(stats[0].clone()));
+        assert!(cache.refreshing().get("0x02").is_none());
+    }
+
+    #[test]
+    fn test_cast_hash_is_normalized() {
+        assert_eq!(
+            cast_hash(&cast("0xABcd", 1, None)).as_deref(),
+            Some("0xabcd")
+        );
+        assert_eq!(cast_hash(&json!({"data": {}})), None);
+    }
+}
@@ -0,0 +1,343 @@
+//! Channel membership and moderation from the public Warpcast API
+//!
+//! Channels are a client-level feature: hubs only see casts whose parent URL
+//! is the channel URL, while membership, moderators, bans and restrictions
+//! are kept by Warpcast and served without authentication at
+//! `api.warpcast.com` (or `CASTORIX_WARPCAST_API_URL`). Lists are paged with
+//! the API's `next.cursor` until the requested limit is reached.
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::consts::env_vars;
+
+/// Public Warpcast API used unless `CASTORIX_WARPCAST_API_URL` is set
+pub const DEFAULT_WARPCAST_API_URL: &str = "https://api.warpcast.com";
+
+/// A channel's settings and staff
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Channel {
+    pub id: String,
+    /// Parent URL of the channel's casts
+    pub url: String,
+    pub name: String,
+    pub description: String,
+    pub image_url: Option<String>,
+    /// Owner of the channel
+    pub lead_fid: Option<u64>,
+    pub moderator_fids: Vec<u64>,
+    pub member_count: Option<u64>,
+    pub follower_count: Option<u64>,
+    /// Unix time (seconds) the channel was created
+    pub created_at: Option<u64>,
+}
+
+/// Role of a channel's staff member
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelRole {
+    Lead,
+    Moderator,
+}
+
+/// A channel's lead or moderator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChannelModerator {
+    pub fid: u64,
+    pub role: ChannelRole,
+}
+
+impl Channel {
+    /// The lead followed by the moderators, without duplicates
+    pub fn moderators(&self) -> Vec<ChannelModerator> {
+        let lead = self.lead_fid.map(|fid| ChannelModerator {
+            fid,
+            role: ChannelRole::Lead,
+        });
+        let moderators = self
+            .moderator_fids
+            .iter()
+            .filter(|fid| Some(**fid) != self.lead_fid)
+            .map(|&fid| ChannelModerator {
+                fid,
+                role: ChannelRole::Moderator,
+            });
+        lead.into_iter().chain(moderators).collect()
+    }
+}
+
+/// Membership of one FID in one channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelMember {
+    pub channel_id: String,
+    pub fid: u64,
+    /// Unix time (seconds) the FID became a member
+    #[serde(default)]
+    pub member_at: Option<u64>,
+}
+
+/// How a channel moderates a FID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// Casts from the FID are hidden from the channel
+    Ban,
+    /// The FID's casts need approval before they show in the channel
+    Restrict,
+}
+
+impl ModerationAction {
+    fn endpoint(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            ModerationAction::Ban => ("/fc/channel-bans", "bannedUsers", "bannedAt"),
+            ModerationAction::Restrict => (
+                "/fc/channel-restricted-users",
+                "restrictedUsers",
+                "restrictedAt",
+            ),
+        }
+    }
+}
+
+/// A FID banned or restricted in a channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChannelModeration {
+    pub channel_id: String,
+    pub fid: u64,
+    pub action: ModerationAction,
+    /// Unix time (seconds) of the ban or restriction
+    pub since: Option<u64>,
+}
+
+/// Client of the Warpcast channel API
+#[derive(Debug, Clone)]
+pub struct ChannelClient {
+    api_url: String,
+}
+
+impl Default for ChannelClient {
+    fn default() -> Self {
+        let api_url = std::env::var(env_vars::WARPCAST_API_URL)
+            .ok()
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_WARPCAST_API_URL.to_string());
+        Self::new(api_url)
+    }
+}
+
+impl ChannelClient {
+    pub fn new(api_url: impl Into<String>) -> Self {
+        Self {
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn get(&self, path: &str, query: &[(&str, String)]) -> Result<serde_json::Value> {
+        let url = format!("{}{path}", self.api_url);
+        let response = crate::core::utils::http::client()
+            .get(&url)
+            .query(query)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach the Warpcast API at {url}"))?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            anyhow::bail!("Warpcast API returned {status} for {path}: {body}");
+        }
+        serde_json::from_str(&body).with_context(|| format!("Invalid JSON from {path}"))
+    }
+
+    /// Fetch `result.<field>` page by page, up to `limit` items
+    async fn get_list<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+        field: &str,
+        limit: usize,
+    ) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        while items.len() < limit {
+            let mut query = query.to_vec();
+            if let Some(cursor) = cursor.take() {
+                query.push(("cursor", cursor));
+            }
+            let page = self.get(path, &query).await?;
+            let page_items = page
+                .pointer(&format!("/result/{field}"))
+                .and_then(|items| items.as_array())
+                .cloned()
+                .unwrap_or_default();
+            let empty = page_items.is_empty();
+            items.extend(page_items);
+            cursor = page
+                .pointer("/next/cursor")
+                .and_then(|cursor| cursor.as_str())
+                .map(String::from);
+            if empty || cursor.is_none() {
+                break;
+            }
+        }
+        items.truncate(limit);
+        items
+            .into_iter()
+            .map(|item| {
+                serde_json::from_value(item.clone())
+                    .with_context(|| format!("Unexpected item in {path}: {item}"))
+            })
+            .collect()
+    }
+
+    /// Get a channel by id
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel id, such as `farcaster`
+    ///
+    /// # Returns
+    /// * `Result<Channel>` - The channel, or an error if it does not exist
+    pub async fn get_channel(&self, channel_id: &str) -> Result<Channel> {
+        let response = self
+            .get("/v1/channel", &[("channelId", channel_id.to_string())])
+            .await?;
+        let channel = response
+            .pointer("/result/channel")
+            .ok_or_else(|| anyhow::anyhow!("Channel '{channel_id}' not found"))?;
+        Ok(serde_json::from_value(channel.clone())?)
+    }
+
+    /// Get the members of a channel
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel id
+    /// * `limit` - Most members to return
+    pub async fn get_members(&self, channel_id: &str, limit: usize) -> Result<Vec<ChannelMember>> {
+        self.get_list(
+            "/fc/channel-members",
+            &[("channelId", channel_id.to_string())],
+            "members",
+            limit,
+        )
+        .await
+    }
+
+    /// Get the channels a FID is a member of
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `limit` - Most memberships to return
+    pub async fn get_memberships(&self, fid: u64, limit: usize) -> Result<Vec<ChannelMember>> {
+        self.get_list(
+            "/fc/channel-members",
+            &[("fid", fid.to_string())],
+            "members",
+            limit,
+        )
+        .await
+    }
+
+    /// Get the FIDs a channel bans or restricts
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel id
+    /// * `action` - Bans or restrictions
+    /// * `limit` - Most entries to return
+    pub async fn get_moderation(
+        &self,
+        channel_id: &str,
+        action: ModerationAction,
+        limit: usize,
+    ) -> Result<Vec<ChannelModeration>> {
+        let (path, field, since_field) = action.endpoint();
+        let items = self
+            .get_list::<serde_json::Value>(
+                path,
+                &[("channelId", channel_id.to_string())],
+                field,
+                limit,
+            )
+            .await?;
+        Ok(items
+            .iter()
+            .filter_map(|item| moderation(item, channel_id, action, since_field))
+            .collect())
+    }
+}
+
+fn moderation(
+    item: &serde_json::Value,
+    channel_id: &str,
+    action: ModerationAction,
+    since_field: &str,
+) -> Option<ChannelModeration> {
+    Some(ChannelModeration {
+        channel_id: item
+            .get("channelId")
+            .and_then(|id| id.as_str())
+            .unwrap_or(channel_id)
+            .to_string(),
+        fid: item.get("fid")?.as_u64()?,
+        action,
+        since: item.get(since_field).and_then(|since| since.as_u64()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_channel() {
+        let channel: Channel = serde_json::from_value(serde_json::json!({
+            "id": "rust",
+            "url": "https://warpcast.com/~/channel/rust",
+            "name": "Rust",
+            "leadFid": 3,
+            "moderatorFids": [3, 42],
+            "followerCount": 1200,
+            "createdAt": 1700000000
+        }))
+        .unwrap();
+        assert_eq!(channel.follower_count, Some(1200));
+        assert_eq!(
+            channel.moderators(),
+            [
+                ChannelModerator {
+                    fid: 3,
+                    role: ChannelRole::Lead
+                },
+                ChannelModerator {
+                    fid: 42,
+                    role: ChannelRole::Moderator
+                }
+            ]
+        );
+
+        let member: ChannelMember = serde_json::from_value(
+            serde_json::json!({"fid": 7, "channelId": "rust", "memberAt": 1710000000}),
+        )
+        .unwrap();
+        assert_eq!(member.member_at, Some(1710000000));
+    }
+
+    #[test]
+    fn test_moderation() {
+        let item = serde_json::json!({"fid": 9, "channelId": "rust", "bannedAt": 1720000000});
+        let (_, _, since_field) = ModerationAction::Ban.endpoint();
+        let ban = moderation(&item, "rust", ModerationAction::Ban, since_field).unwrap();
+        assert_eq!(ban.fid, 9);
+        assert_eq!(ban.since, Some(1720000000));
+        assert!(moderation(
+            &serde_json::json!({}),
+            "rust",
+            ModerationAction::Ban,
+            "bannedAt"
+        )
+        .is_none());
+    }
+}
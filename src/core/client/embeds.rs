@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::stream::StreamExt;
+use futures::stream::{
+    self,
+};
+use reqwest::Client;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::utils::ttl_cache::TtlCache;
+
+/// Embed pages fetched at the same time by default
+pub const DEFAULT_EMBED_CONCURRENCY: usize = 4;
+
+/// Per-page request timeout
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Only the head of a page is needed for its meta tags
+const MAX_HTML_BYTES: usize = 256 * 1024;
+
+/// How long a cached preview (or a failed fetch) is reused
+const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Link preview built from a page's OpenGraph metadata
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbedPreview {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+}
+
+impl EmbedPreview {
+    /// Whether the page had no usable metadata
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.description.is_none() && self.image.is_none()
+    }
+}
+
+/// Fetches link previews for cast embeds with bounded concurrency and a cache
+///
+/// Failed fetches are cached too (as `None`), so a dead link costs one
+/// timeout per [`CACHE_TTL`] rather than one per run.
+pub struct EmbedFetcher {
+    client: Client,
+    concurrency: usize,
+    cache: TtlCache<String, Option<EmbedPreview>>,
+}
+
+impl EmbedFetcher {
+    /// Create a fetcher with an in-memory cache only
+    pub fn new(concurrency: usize) -> Self {
+        Self::with_cache(concurrency, TtlCache::in_memory(CACHE_TTL))
+    }
+
+    /// Create a fetcher whose cache is loaded from and saved to a JSON file
+    pub fn with_cache_file(concurrency: usize, path: PathBuf) -> Self {
+        Self::with_cache(concurrency, TtlCache::with_file(CACHE_TTL, path))
+    }
+
+    fn with_cache(concurrency: usize, cache: TtlCache<String, Option<EmbedPreview>>) -> Self {
+        let client = crate::core::utils::http::builder()
+            .and_then(|builder| Ok(builder.timeout(FETCH_TIMEOUT).build()?))
+            .unwrap_or_else(|_| crate::core::utils::http::client());
+        Self {
+            client,
+            concurrency: concurrency.max(1),
+            cache,
+        }
+    }
+
+    /// Default cache file in the data directory
+    pub fn default_cache_path() -> PathBuf {
+        crate::core::utils::data_dir::data_dir().join("embed_cache.json")
+    }
+
+    /// Fetch previews for a set of URLs
+    ///
+    /// Non-HTTP URLs and pages without metadata are left out of the result.
+    ///
+    /// # Arguments
+    /// * `urls` - Embed URLs; duplicates are fetched once
+    ///
+    /// # Returns
+    /// * `HashMap<String, EmbedPreview>` - Previews keyed by URL
+    pub async fn fetch_all(&self, urls: &[String]) -> HashMap<String, EmbedPreview> {
+        let mut previews = HashMap::new();
+        let mut missing = Vec::new();
+        for url in urls {
+            if !(url.starts_with("https://") || url.starts_with("http://"))
+                || previews.contains_key(url)
+                || missing.contains(url)
+            {
+                continue;
+            }
+            match self.cache.get(url) {
+                Some(Some(preview)) => {
+                    previews.insert(url.clone(), preview);
+                }
+                Some(None) => {}
+                None => missing.push(url.clone()),
+            }
+        }
+
+        let fetched: Vec<(String, Option<EmbedPreview>)> = stream::iter(missing)
+            .map(|url| {
+                let client = self.client.clone();
+                async move {
+                    let preview = fetch_preview(&client, &url).await.ok().flatten();
+                    (url, preview)
+                }
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+
+        for (url, preview) in fetched {
+            if let Some(preview) = &preview {
+                previews.insert(url.clone(), preview.clone());
+            }
+            self.cache.insert(url, preview);
+        }
+        previews
+    }
+
+    /// Write the cache back to its file, dropping expired entries
+    pub fn save(&self) -> Result<()> {
+        self.cache.save()
+    }
+}
+
+/// Download the head of an HTML page and read its metadata
+async fn fetch_preview(client: &Client, url: &str) -> Result<Option<EmbedPreview>> {
+    let mut response = client.get(url).send().await?.error_for_status()?;
+    let is_html = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("html"));
+    if !is_html {
+        return Ok(None);
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        if body.len() >= MAX_HTML_BYTES {
+            break;
+        }
+    }
+    let preview = parse_open_graph(url, &String::from_utf8_lossy(&body));
+    Ok((!preview.is_empty()).then_some(preview))
+}
+
+/// Build a preview from the OpenGraph (or plain HTML) metadata of a page
+pub fn parse_open_graph(url: &str, html: &str) -> EmbedPreview {
+    let mut meta: HashMap<String, String> = HashMap::new();
+    let lower = html.to_ascii_lowercase();
+    let mut offset = 0;
+    while let Some(start) = lower[offset..].find("<meta") {
+        let start = offset + start;
+        let Some(end) = lower[start..].find('>') else {
+            break;
+        };
+        let tag = &html[start..start + end];
+        let attributes = parse_attributes(tag);
+        let key = attributes
+            .get("property")
+            .or_else(|| attributes.get("name"))
+            .map(|k| k.to_ascii_lowercase());
+        if let (Some(key), Some(content)) = (key, attributes.get("content")) {
+            meta.entry(key).or_insert_with(|| decode_entities(content));
+        }
+        offset = start + end;
+    }
+
+    let title_tag = lower.find("<title").and_then(|start| {
+        let open_end = start + lower[start..].find('>')? + 1;
+        let close = open_end + lower[open_end..].find("</title")?;
+        Some(decode_entities(html[open_end..close].trim()))
+    });
+    let pick = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| meta.get(*key))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    EmbedPreview {
+        url: url.to_string(),
+        title: pick(&["og:title", "twitter:title"]).or(title_tag.filter(|t| !t.is_empty())),
+        description: pick(&["og:description", "twitter:description", "description"]),
+        image: pick(&["og:image", "og:image:url", "twitter:image"]),
+    }
+}
+
+/// Parse `name="value"` pairs of an HTML tag (lowercased names)
+fn parse_attributes(tag: &str) -> HashMap<String, String> {
+    let mut attributes = HashMap::new();
+    let mut rest = tag.trim_start_matches('<');
+    // Skip the tag name
+    rest = rest.trim_start_matches(|c: char| !c.is_whitespace());
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        let Some(eq) = rest.find('=') else {
+            break;
+        };
+        let name = rest[..eq].trim().to_ascii_lowercase();
+        rest = rest[eq + 1..].trim_start();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let Some(close) = rest[1..].find(quote) else {
+                    break;
+                };
+                let value = &rest[1..1 + close];
+                rest = &rest[close + 2..];
+                value
+            }
+            _ => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let value = &rest[..end];
+                rest = &rest[end..];
+                value
+            }
+        };
+        // Names can carry leftover words from valueless attributes ("async name")
+        let name = name.rsplit(char::is_whitespace).next().unwrap_or_default();
+        attributes.insert(name.to_string(), value.to_string());
+    }
+    attributes
+}
+
+/// Decode the handful of HTML entities common in meta content
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_open_graph() {
+        let html = r#"<html><head>
+            <title>Fallback title</title>
+            <meta property="og:title" content="Castorix &amp; Farcaster" />
+            <meta name='description' content='Plain description'>
+            <META PROPERTY="og:image" CONTENT="https://example.com/card.png">
+        </head></html>"#;
+        let preview = parse_open_graph("https://example.com", html);
+        assert_eq!(preview.title.as_deref(), Some("Castorix & Farcaster"));
+        assert_eq!(preview.description.as_deref(), Some("Plain description"));
+        assert_eq!(
+            preview.image.as_deref(),
+            Some("https://example.com/card.png")
+        );
+
+        let plain = parse_open_graph("https://example.com", "<title> Just a title </title>");
+        assert_eq!(plain.title.as_deref(), Some("Just a title"));
+        assert!(parse_open_graph("https://example.com", "<p>nothing</p>").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_uses_cache_and_skips_non_http() {
+        let fetcher = EmbedFetcher::new(2);
+        let cached = EmbedPreview {
+            url: "https://cached.example".to_string(),
+            title: Some("Cached".to_string()),
+            ..Default::default()
+        };
+        fetcher
+            .cache
+            .insert(cached.url.clone(), Some(cached.clone()));
+
+        let previews = fetcher
+            .fetch_all(&[
+                cached.url.clone(),
+                "chain://eip155:1/erc721:0x0".to_string(),
+            ])
+            .await;
+        assert_eq!(previews.len(), 1);
+        assert_eq!(previews.get(&cached.url), Some(&cached));
+    }
+}
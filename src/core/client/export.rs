@@ -0,0 +1,364 @@
+//! Resumable exports of hub lists to JSONL
+//!
+//! An export walks a paginated hub endpoint (followers, following or casts of
+//! a FID) and appends every message to a JSONL file, one hub JSON message per
+//! line, the format [`OfflineSnapshot`](crate::core::client::offline::OfflineSnapshot)
+//! reads. After each page a checkpoint next to the output records the page
+//! token to continue from and how many bytes of the file are complete, so an
+//! interrupted export picks up where it stopped instead of starting over.
+//!
+//! An export can be limited to a [`TimeWindow`]; messages outside it are
+//! skipped, and casts and reactions stop paging once past its end.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::client::adaptive_paging::fetch_page_adaptive;
+use crate::core::client::adaptive_paging::AdaptivePaging;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::time_window::ListOrder;
+use crate::core::client::time_window::TimeWindow;
+
+/// Which list of a FID to export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+    Followers,
+    Following,
+    Casts,
+    Reactions,
+}
+
+impl ExportKind {
+    /// Names accepted by [`ExportKind::from_str`](std::str::FromStr)
+    pub const NAMES: [&'static str; 4] = ["followers", "following", "casts", "reactions"];
+
+    /// Name used on the command line and in checkpoints
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Followers => "followers",
+            Self::Following => "following",
+            Self::Casts => "casts",
+            Self::Reactions => "reactions",
+        }
+    }
+
+    fn query(&self, fid: u64) -> String {
+        match self {
+            Self::Followers => format!("/v1/linksByTargetFid?target_fid={fid}&link_type=follow"),
+            Self::Following => format!("/v1/linksByFid?fid={fid}&link_type=follow"),
+            Self::Casts => format!("/v1/castsByFid?fid={fid}"),
+            Self::Reactions => format!("/v1/reactionsByFid?fid={fid}"),
+        }
+    }
+
+    /// How the list's endpoint orders its messages
+    fn order(&self) -> ListOrder {
+        match self {
+            Self::Followers | Self::Following => ListOrder::Unordered,
+            Self::Casts | Self::Reactions => ListOrder::OldestFirst,
+        }
+    }
+}
+
+impl std::str::FromStr for ExportKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "followers" => Ok(Self::Followers),
+            "following" => Ok(Self::Following),
+            "casts" => Ok(Self::Casts),
+            "reactions" => Ok(Self::Reactions),
+            other => anyhow::bail!(
+                "Unknown export '{}' (expected one of: {})",
+                other,
+                Self::NAMES.join(", ")
+            ),
+        }
+    }
+}
+
+/// Progress of an export, saved after every page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportCheckpoint {
+    /// What is exported, e.g. `followers`
+    pub kind: String,
+    pub fid: u64,
+    /// Time range the export is limited to
+    #[serde(default, skip_serializing_if = "TimeWindow::is_unbounded")]
+    pub window: TimeWindow,
+    /// Token of the next page to fetch; `None` before the first page
+    pub page_token: Option<String>,
+    /// Pages written so far
+    pub pages: u64,
+    /// Messages written so far
+    pub messages: u64,
+    /// Length of the output file up to the last complete page
+    pub bytes: u64,
+}
+
+impl ExportCheckpoint {
+    fn new(kind: ExportKind, fid: u64, window: TimeWindow) -> Self {
+        Self {
+            kind: kind.name().to_string(),
+            fid,
+            window,
+            page_token: None,
+            pages: 0,
+            messages: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Checkpoint file kept next to an export's output
+    pub fn path_for(output: &Path) -> PathBuf {
+        let mut name = output.as_os_str().to_owned();
+        name.push(".checkpoint.json");
+        PathBuf::from(name)
+    }
+
+    /// Load the checkpoint of an output, if there is one
+    pub fn load(output: &Path) -> Result<Option<Self>> {
+        let path = Self::path_for(output);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Ok(None);
+        };
+        serde_json::from_str(&content)
+            .map(Some)
+            .with_context(|| format!("Invalid export checkpoint {}", path.display()))
+    }
+
+    /// Write the checkpoint atomically, so a crash never leaves half of one
+    fn save(&self, output: &Path) -> Result<()> {
+        let path = Self::path_for(output);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, &path)
+            .with_context(|| format!("Failed to write checkpoint {}", path.display()))
+    }
+}
+
+/// What to do with the checkpoint of an earlier run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeMode {
+    /// Resume from a checkpoint if there is one, otherwise start over
+    Auto,
+    /// Resume from a checkpoint, failing if there is none
+    Resume,
+    /// Ignore any checkpoint and start over
+    Fresh,
+}
+
+/// Open the output for appending, positioned after the last complete page
+///
+/// Anything written after the checkpoint (a page cut short by the
+/// interruption) is truncated away, so resuming never duplicates messages.
+fn open_output(
+    output: &Path,
+    kind: ExportKind,
+    fid: u64,
+    window: TimeWindow,
+    mode: ResumeMode,
+) -> Result<(File, ExportCheckpoint)> {
+    let checkpoint = match (mode, ExportCheckpoint::load(output)?) {
+        (ResumeMode::Fresh, _) => None,
+        (ResumeMode::Resume, None) => anyhow::bail!(
+            "No checkpoint to resume for {} (looked for {})",
+            output.display(),
+            ExportCheckpoint::path_for(output).display()
+        ),
+        (_, Some(checkpoint)) if checkpoint.kind != kind.name() || checkpoint.fid != fid => {
+            anyhow::bail!(
+                "{} holds an export of {} for FID {}; use --fresh to overwrite it",
+                output.display(),
+                checkpoint.kind,
+                checkpoint.fid
+            )
+        }
+        (_, Some(checkpoint)) if checkpoint.window != window => {
+            anyhow::bail!(
+                "{} holds an export with different --since/--until; use --fresh to overwrite it",
+                output.display()
+            )
+        }
+        (_, checkpoint) => checkpoint,
+    };
+
+    match checkpoint {
+        Some(checkpoint) => {
+            let file = OpenOptions::new()
+                .write(true)
+                .open(output)
+                .with_context(|| format!("Failed to open {} to resume", output.display()))?;
+            let length = file.metadata()?.len();
+            if length < checkpoint.bytes {
+                anyhow::bail!(
+                    "{} is shorter than its checkpoint ({} < {} bytes); use --fresh to start over",
+                    output.display(),
+                    length,
+                    checkpoint.bytes
+                );
+            }
+            file.set_len(checkpoint.bytes)?;
+            drop(file);
+            let file = OpenOptions::new().append(true).open(output)?;
+            Ok((file, checkpoint))
+        }
+        None => {
+            let file = File::create(output)
+                .with_context(|| format!("Failed to create {}", output.display()))?;
+            let checkpoint = ExportCheckpoint::new(kind, fid, window);
+            checkpoint.save(output)?;
+            Ok((file, checkpoint))
+        }
+    }
+}
+
+impl<M> FarcasterClient<M> {
+    /// Export a list of a FID to a JSONL file, checkpointing after every page
+    ///
+    /// The checkpoint is removed once the last page is written.
+    ///
+    /// # Arguments
+    /// * `kind` - Which list to export
+    /// * `fid` - The Farcaster ID
+    /// * `window` - Time range to export; unbounded for everything
+    /// * `output` - JSONL file to write
+    /// * `mode` - Whether to resume an interrupted export
+    /// * `on_page` - Called with the checkpoint after each page, and once before
+    ///   the first page fetched in this run
+    ///
+    /// # Returns
+    /// * `Result<ExportCheckpoint>` - Totals of the finished export
+    pub async fn export_list(
+        &self,
+        kind: ExportKind,
+        fid: u64,
+        window: TimeWindow,
+        output: &Path,
+        mode: ResumeMode,
+        mut on_page: impl FnMut(&ExportCheckpoint),
+    ) -> Result<ExportCheckpoint> {
+        let (mut file, mut checkpoint) = open_output(output, kind, fid, window, mode)?;
+        on_page(&checkpoint);
+
+        let base_url = format!("{}{}", self.hub_url(), kind.query(fid));
+        let paging = AdaptivePaging::default();
+        loop {
+            let page = fetch_page_adaptive(
+                self.http_client().clone(),
+                base_url.clone(),
+                checkpoint.page_token.clone(),
+                paging.clone(),
+                kind.name(),
+            )
+            .await?;
+            let messages = page
+                .get("messages")
+                .and_then(|m| m.as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            let mut lines = String::new();
+            let mut written = 0;
+            let mut past_window = false;
+            for message in &messages {
+                if window.is_past(message, kind.order()) {
+                    past_window = true;
+                    break;
+                }
+                if !window.contains(message) {
+                    continue;
+                }
+                lines.push_str(&serde_json::to_string(message)?);
+                lines.push('\n');
+                written += 1;
+            }
+            file.write_all(lines.as_bytes())?;
+            file.sync_data()?;
+
+            checkpoint.pages += 1;
+            checkpoint.messages += written;
+            checkpoint.bytes += lines.len() as u64;
+            checkpoint.page_token = page
+                .get("nextPageToken")
+                .and_then(|t| t.as_str())
+                .filter(|t| !t.is_empty() && !messages.is_empty() && !past_window)
+                .map(|t| t.to_string());
+            let done = checkpoint.page_token.is_none();
+            if !done {
+                checkpoint.save(output)?;
+            }
+            on_page(&checkpoint);
+            if done {
+                break;
+            }
+        }
+
+        let _ = std::fs::remove_file(ExportCheckpoint::path_for(output));
+        Ok(checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_truncates_partial_page() {
+        let all = TimeWindow::default();
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("followers.jsonl");
+        std::fs::write(&output, "{\"a\":1}\n{\"b\":2}\n{\"partial").unwrap();
+        let checkpoint = ExportCheckpoint {
+            page_token: Some("next".to_string()),
+            pages: 1,
+            messages: 2,
+            bytes: 16,
+            ..ExportCheckpoint::new(ExportKind::Followers, 3, TimeWindow::default())
+        };
+        checkpoint.save(&output).unwrap();
+
+        let (_, resumed) =
+            open_output(&output, ExportKind::Followers, 3, all, ResumeMode::Auto).unwrap();
+        assert_eq!(resumed, checkpoint);
+        assert_eq!(
+            std::fs::read_to_string(&output).unwrap(),
+            "{\"a\":1}\n{\"b\":2}\n"
+        );
+
+        // A checkpoint for another list or window is never resumed by accident
+        assert!(open_output(
+            &output,
+            ExportKind::Followers,
+            3,
+            TimeWindow::since(5),
+            ResumeMode::Auto
+        )
+        .is_err());
+        assert!(open_output(&output, ExportKind::Casts, 3, all, ResumeMode::Auto).is_err());
+
+        let (_, fresh) =
+            open_output(&output, ExportKind::Casts, 3, all, ResumeMode::Fresh).unwrap();
+        assert_eq!(fresh.pages, 0);
+        assert!(std::fs::read_to_string(&output).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resume_requires_checkpoint() {
+        let all = TimeWindow::default();
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("casts.jsonl");
+        assert!(open_output(&output, ExportKind::Casts, 3, all, ResumeMode::Resume).is_err());
+        assert!(open_output(&output, ExportKind::Casts, 3, all, ResumeMode::Auto).is_ok());
+        assert!("likes".parse::<ExportKind>().is_err());
+    }
+}
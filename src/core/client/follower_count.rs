@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::client::hub_client::fetch_page;
+use crate::core::client::hub_client::FarcasterClient;
+
+/// Page size used while sampling follow links
+const SAMPLE_PAGE_SIZE: u32 = 1000;
+
+/// Response fields some hubs use to report the size of a link list
+const TOTAL_FIELDS: &[&str] = &["totalCount", "total", "count"];
+
+/// How a follower count was obtained
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CountSource {
+    /// Reported directly by the hub
+    HubTotal,
+    /// Every follow link was counted
+    Exact,
+    /// Extrapolated from samples at both ends of the follower FID range
+    Estimate,
+    /// Only the sampled links are known; the real count is higher
+    AtLeast,
+}
+
+/// Follower count of a FID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowerCount {
+    pub count: u64,
+    pub source: CountSource,
+    /// Number of follow links actually downloaded
+    pub sampled: u64,
+}
+
+impl<M> FarcasterClient<M> {
+    /// Count followers of a FID without downloading every follow link
+    ///
+    /// The hub is first asked for a single link to see whether it reports a
+    /// total. Otherwise up to `max_pages` pages are counted; if the list is
+    /// longer, one page from the far end is fetched and, since links by target
+    /// are ordered by follower FID, the count is extrapolated from the density
+    /// of followers in the sampled FID ranges.
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `max_pages` - Maximum number of pages counted before estimating
+    ///
+    /// # Returns
+    /// * `Result<FollowerCount>` - The count and how it was obtained, or an error
+    pub async fn get_follower_count(&self, fid: u64, max_pages: u32) -> Result<FollowerCount> {
+        let base_url = format!(
+            "{}/v1/linksByTargetFid?target_fid={}&link_type=follow",
+            self.hub_url(),
+            fid
+        );
+
+        let probe = fetch_page(
+            self.http_client().clone(),
+            format!("{base_url}&pageSize=1"),
+            "followers",
+        )
+        .await?;
+        if let Some(total) = reported_total(&probe) {
+            return Ok(FollowerCount {
+                count: total,
+                source: CountSource::HubTotal,
+                sampled: 0,
+            });
+        }
+
+        // Count from the low end of the follower FID range
+        let mut followers: HashSet<u64> = HashSet::new();
+        let mut page_token: Option<String> = None;
+        for _ in 0..max_pages.max(1) {
+            let mut url = format!("{base_url}&pageSize={SAMPLE_PAGE_SIZE}");
+            if let Some(token) = &page_token {
+                url.push_str(&format!("&pageToken={token}"));
+            }
+            let page = fetch_page(self.http_client().clone(), url, "followers").await?;
+            let fids = follower_fids(&page);
+            page_token = next_page_token(&page).filter(|_| !fids.is_empty());
+            followers.extend(fids);
+            if page_token.is_none() {
+                return Ok(FollowerCount {
+                    count: followers.len() as u64,
+                    source: CountSource::Exact,
+                    sampled: followers.len() as u64,
+                });
+            }
+        }
+        let low_max = followers.iter().copied().max().unwrap_or(0);
+        let low_count = followers.len() as u64;
+
+        // Sample the high end of the range
+        let tail = fetch_page(
+            self.http_client().clone(),
+            format!("{base_url}&pageSize={SAMPLE_PAGE_SIZE}&reverse=true"),
+            "followers",
+        )
+        .await?;
+        let tail_fids = follower_fids(&tail);
+        let high_min = tail_fids.iter().copied().min().unwrap_or(u64::MAX);
+        let high_count = tail_fids.iter().filter(|f| !followers.contains(f)).count() as u64;
+        let sampled = low_count + high_count;
+
+        // The two samples meet, so every follower has been seen
+        if high_min <= low_max {
+            return Ok(FollowerCount {
+                count: sampled,
+                source: CountSource::Exact,
+                sampled,
+            });
+        }
+
+        let total_fids = self
+            .get_hub_info()
+            .await
+            .ok()
+            .and_then(|info| {
+                info.get("dbStats")
+                    .and_then(|s| s.get("numFidRegistrations"))
+                    .and_then(|n| n.as_u64())
+            })
+            .unwrap_or(0);
+
+        Ok(
+            match estimate_count(low_count, low_max, high_count, high_min, total_fids) {
+                Some(count) => FollowerCount {
+                    count,
+                    source: CountSource::Estimate,
+                    sampled,
+                },
+                None => FollowerCount {
+                    count: sampled,
+                    source: CountSource::AtLeast,
+                    sampled,
+                },
+            },
+        )
+    }
+}
+
+/// Total link count if the hub included one in the response
+fn reported_total(page: &serde_json::Value) -> Option<u64> {
+    TOTAL_FIELDS
+        .iter()
+        .find_map(|field| page.get(*field))
+        .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()))
+}
+
+fn follower_fids(page: &serde_json::Value) -> Vec<u64> {
+    page.get("messages")
+        .and_then(|m| m.as_array())
+        .map(|messages| {
+            messages
+                .iter()
+                .filter_map(|m| m.get("data")?.get("fid")?.as_u64())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn next_page_token(page: &serde_json::Value) -> Option<String> {
+    page.get("nextPageToken")
+        .and_then(|t| t.as_str())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_string())
+}
+
+/// Extrapolate a follower count from samples at both ends of the FID range
+///
+/// `low_count` followers were found in `1..=low_max` and `high_count` in
+/// `high_min..=total_fids`; the unsampled gap is assumed to have the average
+/// density of the two sampled ranges.
+fn estimate_count(
+    low_count: u64,
+    low_max: u64,
+    high_count: u64,
+    high_min: u64,
+    total_fids: u64,
+) -> Option<u64> {
+    if total_fids == 0 || high_min > total_fids || high_min <= low_max {
+        return None;
+    }
+    let sampled_range = low_max + (total_fids - high_min + 1);
+    let gap = high_min - low_max - 1;
+    let density = (low_count + high_count) as f64 / sampled_range as f64;
+    Some(low_count + high_count + (density * gap as f64).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reported_total() {
+        assert_eq!(
+            reported_total(&serde_json::json!({ "messages": [], "totalCount": "42" })),
+            Some(42)
+        );
+        assert_eq!(reported_total(&serde_json::json!({ "messages": [] })), None);
+    }
+
+    #[test]
+    fn test_estimate_count() {
+        // 100 followers in FIDs 1..=1000 and 100 in 9001..=10000: 10% density
+        assert_eq!(estimate_count(100, 1000, 100, 9001, 10_000), Some(1000));
+        // Without the FID range only a lower bound is possible
+        assert_eq!(estimate_count(100, 1000, 100, 9001, 0), None);
+    }
+}
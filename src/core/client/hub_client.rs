@@ -1,14 +1,28 @@
+use std::time::Duration;
+
 use anyhow::Context;
 use anyhow::Result;
 use chrono::Utc;
 use ed25519_dalek::Signer as Ed25519Signer;
 use ed25519_dalek::SigningKey;
 use protobuf::Message as ProtobufMessage;
-use reqwest::Client;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::core::audit;
+use crate::core::client::adaptive_paging::fetch_page_adaptive;
+use crate::core::client::adaptive_paging::AdaptivePaging;
+use crate::core::client::adaptive_paging::MAX_PAGE_SIZE;
+use crate::core::client::hub_pool::HubEndpoint;
+use crate::core::client::hub_pool::HubPool;
+use crate::core::client::rate_limit;
+use crate::core::client::submit::is_duplicate_response;
+use crate::core::client::submit::SubmissionCache;
+use crate::core::client::submit::SUBMIT_MAX_ATTEMPTS;
+use crate::core::client::webhook::backoff_delay;
+use crate::core::client::webhook::is_retryable_status;
 use crate::core::crypto::key_manager::KeyManager;
+use crate::core::policy;
 use crate::core::protocol::message::FarcasterNetwork;
 use crate::core::protocol::message::HashScheme;
 use crate::core::protocol::message::Message;
@@ -17,12 +31,32 @@ use crate::core::protocol::message::MessageType;
 use crate::core::protocol::message::SignatureScheme;
 use crate::core::protocol::username_proof::UserNameProof;
 use crate::core::protocol::username_proof::UserNameType;
+use crate::core::utils::http::request_note;
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
+
+/// Marker for clients that can only read from the hub
+///
+/// Read-only clients have no submit methods at all, so code holding a
+/// `FarcasterClient<ReadOnly>` (such as the REST API) cannot sign messages.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadOnly;
+
+/// State for clients that can sign and submit messages
+///
+/// The Ethereum key manager is only needed by the EIP-712 and legacy proof paths;
+/// Ed25519 submissions load the FID's signer from encrypted storage.
+pub struct Signing {
+    key_manager: Option<KeyManager>,
+}
 
 /// Farcaster Hub client for submitting messages and proofs
-pub struct FarcasterClient {
-    client: Client,
+///
+/// The type parameter selects the capability set: [`ReadOnly`] (the default) or [`Signing`].
+pub struct FarcasterClient<M = ReadOnly> {
+    client: HubPool,
     hub_url: String,
-    key_manager: Option<KeyManager>,
+    mode: M,
 }
 
 /// Farcaster message structure (using protobuf Message)
@@ -68,6 +102,73 @@ pub struct CastId {
     pub hash: String,
 }
 
+/// Parse the `proofs` array of a userNameProofsByFid response
+fn parse_username_proofs(data: &serde_json::Value) -> Result<Vec<UsernameProofData>> {
+    let Some(proofs) = data.get("proofs").and_then(|p| p.as_array()) else {
+        return Ok(Vec::new());
+    };
+    proofs
+        .iter()
+        .map(|proof| {
+            serde_json::from_value(proof.clone())
+                .with_context(|| format!("Invalid username proof in hub response: {proof}"))
+        })
+        .collect()
+}
+
+/// Farcaster epoch (January 1, 2021 UTC) in unix seconds
+pub const FARCASTER_EPOCH: u64 = 1609459200;
+
+/// Current time as a Farcaster timestamp (seconds since the Farcaster epoch)
+pub fn farcaster_now() -> u32 {
+    (Utc::now().timestamp() as u64 - FARCASTER_EPOCH) as u32
+}
+
+/// Hash and sign message data with an Ed25519 signer
+///
+/// # Arguments
+/// * `data` - The message data to sign
+/// * `signing_key` - An active signer of `data.fid`
+///
+/// # Returns
+/// * `Result<Message>` - The signed message, with `data_bytes` set
+pub fn build_ed25519_message(data: MessageData, signing_key: &SigningKey) -> Result<Message> {
+    policy::current().check_fid(data.get_fid())?;
+    let data_bytes = data.write_to_bytes()?;
+    let hash = blake3::hash(&data_bytes).as_bytes()[..20].to_vec();
+
+    let mut message = Message::new();
+    let signature = metrics::time(Phase::Signing, "Ed25519 message", || {
+        signing_key.sign(&hash)
+    });
+    message.set_signature(signature.to_bytes().to_vec());
+    message.set_signer(signing_key.verifying_key().to_bytes().to_vec());
+    message.set_hash(hash);
+    message.set_hash_scheme(HashScheme::HASH_SCHEME_BLAKE3);
+    message.set_signature_scheme(SignatureScheme::SIGNATURE_SCHEME_ED25519);
+    message.set_data_bytes(data_bytes);
+    audit::record_message(&message, false);
+    Ok(message)
+}
+
+/// The data of a signed message, whether it is carried in `data` or `data_bytes`
+pub fn message_data(message: &Message) -> Result<MessageData> {
+    if message.has_data() {
+        return Ok(message.get_data().clone());
+    }
+    MessageData::parse_from_bytes(message.get_data_bytes())
+        .context("Message has no valid data_bytes")
+}
+
+/// Response reported for a message the hub already had
+fn duplicate_response(hash: &str) -> HubResponse {
+    HubResponse {
+        success: true,
+        message: Some("Message already accepted by the hub".to_string()),
+        data: Some(serde_json::json!({ "hash": hash, "duplicate": true })),
+    }
+}
+
 /// Hub response structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HubResponse {
@@ -76,23 +177,87 @@ pub struct HubResponse {
     pub data: Option<serde_json::Value>,
 }
 
-impl FarcasterClient {
-    /// Create a new Farcaster client
+impl FarcasterClient<ReadOnly> {
+    /// Create a new Farcaster client without authentication (read-only operations)
     ///
     /// # Arguments
     /// * `hub_url` - The Farcaster Hub URL
-    /// * `key_manager` - Optional key manager instance (required for write operations)
     ///
     /// # Returns
     /// * `Self` - The FarcasterClient instance
-    pub fn new(hub_url: String, key_manager: Option<KeyManager>) -> Self {
+    pub fn read_only(hub_url: String) -> Self {
         Self {
-            client: Client::new(),
+            client: HubPool::with_fallbacks(&hub_url),
             hub_url,
-            key_manager,
+            mode: ReadOnly,
         }
     }
 
+    /// Create a read-only client for an explicit, ordered list of hubs
+    ///
+    /// Reads fail over along the list; see [`HubPool`].
+    ///
+    /// # Arguments
+    /// * `hubs` - Hubs in failover order; the first one is the primary hub
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The FarcasterClient instance, or an error if `hubs` is empty
+    pub fn with_hubs(hubs: Vec<HubEndpoint>) -> Result<Self> {
+        let client = HubPool::new(hubs)?;
+        Ok(Self {
+            hub_url: client.primary_url().to_string(),
+            client,
+            mode: ReadOnly,
+        })
+    }
+
+    /// Create a read-only client spreading reads over several hubs
+    ///
+    /// Reads go round-robin to healthy hubs; see [`HubPool::read_replicas`].
+    ///
+    /// # Arguments
+    /// * `hubs` - Hubs to read from; the first one is the primary hub
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The FarcasterClient instance, or an error if `hubs` is empty
+    pub fn with_read_replicas(hubs: Vec<HubEndpoint>) -> Result<Self> {
+        let client = HubPool::read_replicas(hubs)?;
+        Ok(Self {
+            hub_url: client.primary_url().to_string(),
+            client,
+            mode: ReadOnly,
+        })
+    }
+
+    /// Upgrade this client to a signing client
+    ///
+    /// # Arguments
+    /// * `key_manager` - Optional key manager instance (required for EIP-712 signing)
+    ///
+    /// # Returns
+    /// * `FarcasterClient<Signing>` - A client that can submit messages
+    pub fn into_signing(self, key_manager: Option<KeyManager>) -> FarcasterClient<Signing> {
+        FarcasterClient {
+            client: self.client,
+            hub_url: self.hub_url,
+            mode: Signing { key_manager },
+        }
+    }
+}
+
+impl FarcasterClient<Signing> {
+    /// Create a new Farcaster client that can submit messages
+    ///
+    /// # Arguments
+    /// * `hub_url` - The Farcaster Hub URL
+    /// * `key_manager` - Optional key manager instance (required for EIP-712 signing)
+    ///
+    /// # Returns
+    /// * `Self` - The FarcasterClient instance
+    pub fn new(hub_url: String, key_manager: Option<KeyManager>) -> Self {
+        FarcasterClient::read_only(hub_url).into_signing(key_manager)
+    }
+
     /// Create a new Farcaster client with key manager
     ///
     /// # Arguments
@@ -115,15 +280,9 @@ impl FarcasterClient {
         ))
     }
 
-    /// Create a new Farcaster client without authentication (read-only operations)
-    ///
-    /// # Arguments
-    /// * `hub_url` - The Farcaster Hub URL
-    ///
-    /// # Returns
-    /// * `Self` - The FarcasterClient instance
-    pub fn read_only(hub_url: String) -> Self {
-        Self::new(hub_url, None)
+    /// Get the key manager instance
+    pub fn key_manager(&self) -> Option<&KeyManager> {
+        self.mode.key_manager.as_ref()
     }
 
     /// Submit a username proof to Farcaster Hub using EIP-712 signature
@@ -137,7 +296,7 @@ impl FarcasterClient {
         &self,
         proof: &UserNameProof,
     ) -> Result<HubResponse> {
-        if self.key_manager.is_none() {
+        if self.mode.key_manager.is_none() {
             anyhow::bail!("Key manager required for EIP-712 signing");
         }
 
@@ -157,10 +316,7 @@ impl FarcasterClient {
 
         // Create MessageData with username proof
         let mut message_data = MessageData::new();
-        // Use Farcaster time (seconds since January 1, 2021)
-        const FARCASTER_EPOCH: u64 = 1609459200; // January 1, 2021 UTC in seconds
-        let current_timestamp = Utc::now().timestamp() as u64;
-        let farcaster_timestamp = (current_timestamp - FARCASTER_EPOCH) as u32;
+        let farcaster_timestamp = farcaster_now();
 
         message_data.set_field_type(MessageType::MESSAGE_TYPE_USERNAME_PROOF);
         message_data.set_fid(proof.get_fid());
@@ -187,14 +343,16 @@ impl FarcasterClient {
         message.set_signature_scheme(SignatureScheme::SIGNATURE_SCHEME_ED25519);
 
         // Sign using Ed25519 with the Ethereum private key converted to Ed25519
-        let key_manager = self.key_manager.as_ref().unwrap();
+        let key_manager = self.mode.key_manager.as_ref().unwrap();
         let wallet = key_manager.wallet();
 
         // Convert Ethereum private key to Ed25519 for Farcaster
         let private_key_bytes = wallet.signer().to_bytes();
         let ed25519_signing_key =
             SigningKey::from_bytes(&private_key_bytes[..32].try_into().unwrap());
-        let signature = ed25519_signing_key.sign(&hash_20);
+        let signature = metrics::time(Phase::Signing, "Ed25519 message", || {
+            ed25519_signing_key.sign(&hash_20)
+        });
         message.set_signature(signature.to_bytes().to_vec());
 
         // For Ed25519 signature scheme, the signer should be the Ed25519 public key
@@ -204,6 +362,7 @@ impl FarcasterClient {
         // According to Snapchain docs, when dataBytes is set, data should be undefined
         message.set_data_bytes(message_data_bytes);
         message.clear_data(); // Clear the data field as per Snapchain requirements
+        audit::record_message(&message, false);
 
         self.submit_message(&message).await
     }
@@ -245,10 +404,7 @@ impl FarcasterClient {
 
         // Create MessageData with username proof
         let mut message_data = MessageData::new();
-        // Use Farcaster time (seconds since January 1, 2021)
-        const FARCASTER_EPOCH: u64 = 1609459200; // January 1, 2021 UTC in seconds
-        let current_timestamp = Utc::now().timestamp() as u64;
-        let farcaster_timestamp = (current_timestamp - FARCASTER_EPOCH) as u32;
+        let farcaster_timestamp = farcaster_now();
 
         message_data.set_field_type(MessageType::MESSAGE_TYPE_USERNAME_PROOF);
         message_data.set_fid(proof.get_fid());
@@ -275,7 +431,9 @@ impl FarcasterClient {
         message.set_signature_scheme(SignatureScheme::SIGNATURE_SCHEME_ED25519);
 
         // Sign the hash using the Ed25519 signing key
-        let signature = signing_key.sign(&hash_20);
+        let signature = metrics::time(Phase::Signing, "Ed25519 message", || {
+            signing_key.sign(&hash_20)
+        });
         message.set_signature(signature.to_bytes().to_vec());
         message.set_signer(signing_key.verifying_key().to_bytes().to_vec());
 
@@ -283,6 +441,7 @@ impl FarcasterClient {
         // According to Snapchain docs, when dataBytes is set, data should be undefined
         message.set_data_bytes(message_data_bytes);
         message.clear_data(); // Clear the data field as per Snapchain requirements
+        audit::record_message(&message, false);
 
         self.submit_message(&message).await
     }
@@ -295,7 +454,7 @@ impl FarcasterClient {
     /// # Returns
     /// * `Result<HubResponse>` - The hub response or an error
     pub async fn submit_username_proof(&self, proof: &UserNameProof) -> Result<HubResponse> {
-        if self.key_manager.is_none() {
+        if self.mode.key_manager.is_none() {
             return Err(anyhow::anyhow!(
                 "Key manager required for submitting proofs"
             ));
@@ -303,10 +462,7 @@ impl FarcasterClient {
 
         // Create MessageData with username proof
         let mut message_data = MessageData::new();
-        // Use Farcaster time (seconds since January 1, 2021)
-        const FARCASTER_EPOCH: u64 = 1609459200; // January 1, 2021 UTC in seconds
-        let current_timestamp = Utc::now().timestamp() as u64;
-        let farcaster_timestamp = (current_timestamp - FARCASTER_EPOCH) as u32;
+        let farcaster_timestamp = farcaster_now();
 
         message_data.set_field_type(MessageType::MESSAGE_TYPE_USERNAME_PROOF);
         message_data.set_fid(proof.get_fid());
@@ -334,6 +490,7 @@ impl FarcasterClient {
 
         // Sign the hash using the private key
         let key_manager = self
+            .mode
             .key_manager
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Key manager required for signing"))?;
@@ -344,7 +501,9 @@ impl FarcasterClient {
         let private_key_bytes = wallet.signer().to_bytes();
         let ed25519_signing_key =
             SigningKey::from_bytes(&private_key_bytes[..32].try_into().unwrap());
-        let signature = ed25519_signing_key.sign(&hash_20);
+        let signature = metrics::time(Phase::Signing, "Ed25519 message", || {
+            ed25519_signing_key.sign(&hash_20)
+        });
         message.set_signature(signature.to_bytes().to_vec());
         message.set_signer(ed25519_signing_key.verifying_key().to_bytes().to_vec());
 
@@ -352,52 +511,149 @@ impl FarcasterClient {
         // According to Snapchain docs, when dataBytes is set, data should be undefined
         message.set_data_bytes(message_data_bytes);
         message.clear_data(); // Clear the data field as per Snapchain requirements
+        audit::record_message(&message, false);
 
         self.submit_message(&message).await
     }
 
     /// Submit a message to Farcaster Hub
     ///
+    /// Submission is idempotent per message hash: network errors and 5xx/429
+    /// responses are retried with backoff, a "duplicate" response from the hub is
+    /// treated as success, and hashes this hub recently accepted are skipped via
+    /// the local [`SubmissionCache`].
+    ///
     /// # Arguments
     /// * `message` - The message to submit
     ///
     /// # Returns
     /// * `Result<HubResponse>` - The hub response or an error
-    async fn submit_message(&self, message: &FarcasterMessage) -> Result<HubResponse> {
+    pub async fn submit_message(&self, message: &FarcasterMessage) -> Result<HubResponse> {
         let url = format!("{}/v1/submitMessage", self.hub_url);
+        let hash = format!("0x{}", hex::encode(message.get_hash()));
+        policy::current().check_fid(message_data(message)?.get_fid())?;
+
+        let mut cache = SubmissionCache::open();
+        if cache.contains(&self.hub_url, &hash) {
+            return Ok(duplicate_response(&hash));
+        }
 
         // Serialize the message to protobuf format
         let message_data = message.write_to_bytes()?;
+        rate_limit::acquire().await;
+
+        let mut attempt = 0;
+        // Only a 2xx with a parseable body confirms the hub stored the message
+        let mut confirmed = false;
+        let hub_response = loop {
+            let (request, request_id) = self.client.post(&url);
+            let note = request_note(&request_id);
+            let timer = metrics::start(Phase::Hub, "/v1/submitMessage");
+            // A body that fails to arrive is a transport error like a failed send
+            let received = match request
+                .header("Content-Type", "application/octet-stream")
+                .body(message_data.clone())
+                .send()
+                .await
+            {
+                Ok(response) => {
+                    let status = response.status();
+                    response.text().await.map(|text| (status, text))
+                }
+                Err(e) => Err(e),
+            };
+            let last_error = match received {
+                Ok((status, response_text)) => {
+                    if status.is_success() {
+                        match serde_json::from_str(&response_text) {
+                            Ok(hub_response) => {
+                                confirmed = true;
+                                break hub_response;
+                            }
+                            Err(_) => {
+                                break HubResponse {
+                                    success: true,
+                                    message: Some("Message submitted successfully".to_string()),
+                                    data: Some(
+                                        serde_json::json!({ "raw_response": response_text }),
+                                    ),
+                                }
+                            }
+                        }
+                    }
+                    if is_duplicate_response(status, &response_text) {
+                        break duplicate_response(&hash);
+                    }
+                    let error = anyhow::anyhow!(
+                        "Farcaster Hub returned error {}{note}: {}",
+                        status,
+                        response_text
+                    );
+                    if !is_retryable_status(status) {
+                        return Err(error);
+                    }
+                    error
+                }
+                Err(e) => {
+                    anyhow::Error::new(e).context(format!("Request to Farcaster Hub failed{note}"))
+                }
+            };
+            drop(timer);
+
+            attempt += 1;
+            if attempt >= SUBMIT_MAX_ATTEMPTS {
+                return Err(last_error.context(format!(
+                    "submitMessage failed after {attempt} attempts for {hash}"
+                )));
+            }
+            tokio::time::sleep(backoff_delay(Duration::from_millis(500), attempt - 1)).await;
+        };
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Content-Type", "application/octet-stream")
-            .body(message_data)
-            .send()
-            .await
-            .with_context(|| "Failed to send request to Farcaster Hub")?;
+        if confirmed {
+            if let Err(e) = cache.record(&self.hub_url, &hash) {
+                eprintln!("⚠️  Could not update submission cache: {e}");
+            }
+        }
+        audit::record_message(message, true);
+        Ok(hub_response)
+    }
 
-        let status = response.status();
-        let response_text = response.text().await?;
+    /// Get Ed25519 private key for a FID from local storage
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>>` - Ed25519 private key bytes or an error
+    #[allow(dead_code)]
+    async fn get_ed25519_private_key_for_fid(&self, fid: u64) -> Result<Vec<u8>> {
+        // Load encrypted Ed25519 key manager
+        let keys_file =
+            crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager::default_keys_file(
+            )?;
+        let ed25519_manager =
+            crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager::load_from_file(
+                &keys_file,
+            )?;
 
-        if status.is_success() {
-            let hub_response: HubResponse =
-                serde_json::from_str(&response_text).unwrap_or_else(|_| HubResponse {
-                    success: true,
-                    message: Some("Message submitted successfully".to_string()),
-                    data: Some(serde_json::json!({ "raw_response": response_text })),
-                });
-            Ok(hub_response)
-        } else {
-            Err(anyhow::anyhow!(
-                "Farcaster Hub returned error {}: {}",
-                status,
-                response_text
-            ))
+        // Check if Ed25519 key exists for this FID
+        if !ed25519_manager.has_key(fid) {
+            anyhow::bail!("❌ No Ed25519 key found for FID: {}\n💡 Please generate or import an Ed25519 key for this FID first:\n   castorix hub key generate {}\n   castorix hub key import {}", fid, fid, fid);
         }
+
+        // Prompt for password
+        let password = crate::core::crypto::encrypted_storage::prompt_password(&format!(
+            "Enter password for FID {fid}: "
+        ))?;
+
+        // Get the Ed25519 signing key
+        let signing_key = ed25519_manager.get_signing_key(fid, &password)?;
+
+        Ok(signing_key.to_bytes().to_vec())
     }
+}
 
+impl<M> FarcasterClient<M> {
     /// Get user information from Farcaster Hub
     ///
     /// # Arguments
@@ -411,7 +667,6 @@ impl FarcasterClient {
         let response = self
             .client
             .get(&url)
-            .send()
             .await
             .with_context(|| "Failed to get user data from Farcaster Hub")?;
 
@@ -455,7 +710,6 @@ impl FarcasterClient {
         let response = self
             .client
             .get(&url)
-            .send()
             .await
             .with_context(|| "Failed to get user data from Farcaster Hub")?;
 
@@ -503,7 +757,6 @@ impl FarcasterClient {
         let response = self
             .client
             .get(&url)
-            .send()
             .await
             .with_context(|| "Failed to get onchain events from Farcaster Hub")?;
 
@@ -555,7 +808,6 @@ impl FarcasterClient {
         let response = self
             .client
             .get(&url)
-            .send()
             .await
             .with_context(|| "Failed to get verification data from Farcaster Hub")?;
 
@@ -610,9 +862,76 @@ impl FarcasterClient {
         }
     }
 
-    /// Get the key manager instance
-    pub fn key_manager(&self) -> Option<&KeyManager> {
-        self.key_manager.as_ref()
+    /// Get username proofs (fnames, ENS and Basenames) associated with a FID
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `Result<Vec<UsernameProofData>>` - List of username proofs or an error
+    pub async fn get_username_proofs(&self, fid: u64) -> Result<Vec<UsernameProofData>> {
+        let url = format!("{}/v1/userNameProofsByFid?fid={}", self.hub_url, fid);
+
+        let response = self
+            .client
+            .get(&url)
+            .await
+            .with_context(|| "Failed to get username proofs from Farcaster Hub")?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        if status.is_success() {
+            let data: serde_json::Value = serde_json::from_str(&response_text)
+                .with_context(|| "Failed to parse username proofs response")?;
+            parse_username_proofs(&data)
+        } else {
+            Err(anyhow::anyhow!(
+                "Farcaster Hub returned error {}: {}",
+                status,
+                response_text
+            ))
+        }
+    }
+
+    /// Get the username proof for a name (fname, ENS name or Basename)
+    ///
+    /// # Arguments
+    /// * `name` - The username, e.g. `dwr` or `vitalik.eth`
+    ///
+    /// # Returns
+    /// * `Result<Option<UsernameProofData>>` - The proof, `None` if no FID holds the name
+    pub async fn get_username_proof_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<UsernameProofData>> {
+        let url = reqwest::Url::parse_with_params(
+            &format!("{}/v1/userNameProofByName", self.hub_url),
+            &[("name", name)],
+        )?;
+
+        let response = self
+            .client
+            .get(url.as_str())
+            .await
+            .with_context(|| "Failed to get username proof from Farcaster Hub")?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        if status.is_success() {
+            let proof = serde_json::from_str(&response_text)
+                .with_context(|| "Failed to parse username proof response")?;
+            Ok(Some(proof))
+        } else if status == reqwest::StatusCode::NOT_FOUND || response_text.contains("not_found") {
+            Ok(None)
+        } else {
+            Err(anyhow::anyhow!(
+                "Farcaster Hub returned error {}: {}",
+                status,
+                response_text
+            ))
+        }
     }
 
     /// Get casts by FID
@@ -626,56 +945,43 @@ impl FarcasterClient {
     pub async fn get_casts_by_fid(&self, fid: u64, limit: u32) -> Result<Vec<serde_json::Value>> {
         let mut all_casts = Vec::new();
         let mut page_token: Option<String> = None;
-        let page_size = if limit > 0 && limit < 100 { limit } else { 100 };
+        let paging = AdaptivePaging::new(if limit > 0 && limit < MAX_PAGE_SIZE {
+            limit
+        } else {
+            MAX_PAGE_SIZE
+        });
         let mut total_retrieved = 0;
 
         loop {
-            let mut url = format!(
-                "{}/v1/castsByFid?fid={}&pageSize={}&reverse=true",
-                self.hub_url, fid, page_size
-            );
-
-            if let Some(ref token) = page_token {
-                url.push_str(&format!("&pageToken={}", token));
-            }
-
-            let response = self
-                .client
-                .get(&url)
-                .send()
-                .await
-                .with_context(|| "Failed to get casts from Farcaster Hub")?;
-
-            let status = response.status();
-            let response_text = response.text().await?;
+            let data = fetch_page_adaptive(
+                self.client.clone(),
+                format!("{}/v1/castsByFid?fid={}&reverse=true", self.hub_url, fid),
+                page_token.clone(),
+                paging.clone(),
+                "casts",
+            )
+            .await?;
 
-            if status.is_success() {
-                let data: serde_json::Value = serde_json::from_str(&response_text)
-                    .with_context(|| "Failed to parse casts response")?;
-
-                if let Some(messages) = data.get("messages").and_then(|m| m.as_array()) {
-                    let page_casts = messages.clone();
-                    let page_count = page_casts.len();
-
-                    // Check if we would exceed the limit
-                    if limit > 0 && total_retrieved + page_count as u32 > limit {
-                        let remaining = limit - total_retrieved;
-                        let mut truncated = page_casts;
-                        truncated.truncate(remaining as usize);
-                        all_casts.extend(truncated);
-                        break;
-                    }
+            if let Some(messages) = data.get("messages").and_then(|m| m.as_array()) {
+                let page_casts = messages.clone();
+                let page_count = page_casts.len();
+
+                // Check if we would exceed the limit
+                if limit > 0 && total_retrieved + page_count as u32 > limit {
+                    let remaining = limit - total_retrieved;
+                    let mut truncated = page_casts;
+                    truncated.truncate(remaining as usize);
+                    all_casts.extend(truncated);
+                    break;
+                }
 
-                    all_casts.extend(page_casts);
-                    total_retrieved = all_casts.len() as u32;
+                all_casts.extend(page_casts);
+                total_retrieved = all_casts.len() as u32;
 
-                    // Check for next page
-                    if let Some(next_token) = data.get("nextPageToken").and_then(|t| t.as_str()) {
-                        if !next_token.is_empty() && (limit == 0 || total_retrieved < limit) {
-                            page_token = Some(next_token.to_string());
-                        } else {
-                            break;
-                        }
+                // Check for next page
+                if let Some(next_token) = data.get("nextPageToken").and_then(|t| t.as_str()) {
+                    if !next_token.is_empty() && (limit == 0 || total_retrieved < limit) {
+                        page_token = Some(next_token.to_string());
                     } else {
                         break;
                     }
@@ -683,11 +989,7 @@ impl FarcasterClient {
                     break;
                 }
             } else {
-                return Err(anyhow::anyhow!(
-                    "Farcaster Hub returned error {}: {}",
-                    status,
-                    response_text
-                ));
+                break;
             }
         }
 
@@ -709,7 +1011,6 @@ impl FarcasterClient {
         let response = self
             .client
             .get(&url)
-            .send()
             .await
             .with_context(|| "Failed to get signers from Farcaster Hub")?;
 
@@ -759,43 +1060,43 @@ impl FarcasterClient {
         }
     }
 
-    /// Get the hub URL
-    pub fn hub_url(&self) -> &str {
-        &self.hub_url
-    }
-
-    /// Get Ed25519 private key for a FID from local storage
+    /// Ask the hub to validate a message without storing or broadcasting it
+    ///
+    /// The hub runs its full checks, including that the signer is an active
+    /// key of the message's FID, so this is a safe way to test a signer.
     ///
     /// # Arguments
-    /// * `fid` - The Farcaster ID
+    /// * `message` - The signed message to validate
     ///
     /// # Returns
-    /// * `Result<Vec<u8>>` - Ed25519 private key bytes or an error
-    #[allow(dead_code)]
-    async fn get_ed25519_private_key_for_fid(&self, fid: u64) -> Result<Vec<u8>> {
-        // Load encrypted Ed25519 key manager
-        let keys_file =
-            crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager::default_keys_file(
-            )?;
-        let ed25519_manager =
-            crate::core::crypto::encrypted_storage::EncryptedEd25519KeyManager::load_from_file(
-                &keys_file,
-            )?;
+    /// * `Result<()>` - Ok if the hub accepts the message, otherwise the hub's reason
+    pub async fn validate_message(&self, message: &Message) -> Result<()> {
+        let url = format!("{}/v1/validateMessage", self.hub_url);
+        let (request, request_id) = self.client.post(&url);
+        let note = request_note(&request_id);
+        let response = request
+            .header("Content-Type", "application/octet-stream")
+            .body(message.write_to_bytes()?)
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to Farcaster Hub{note}"))?;
 
-        // Check if Ed25519 key exists for this FID
-        if !ed25519_manager.has_key(fid) {
-            anyhow::bail!("❌ No Ed25519 key found for FID: {}\n💡 Please generate or import an Ed25519 key for this FID first:\n   castorix hub key generate {}\n   castorix hub key import {}", fid, fid, fid);
+        let status = response.status();
+        let response_text = response.text().await?;
+        if !status.is_success() {
+            anyhow::bail!("Farcaster Hub rejected the message ({status}){note}: {response_text}");
         }
+        parse_validation(&response_text)
+    }
 
-        // Prompt for password
-        let password = crate::core::crypto::encrypted_storage::prompt_password(&format!(
-            "Enter password for FID {fid}: "
-        ))?;
-
-        // Get the Ed25519 signing key
-        let signing_key = ed25519_manager.get_signing_key(fid, &password)?;
+    /// Get the hub URL
+    pub fn hub_url(&self) -> &str {
+        &self.hub_url
+    }
 
-        Ok(signing_key.to_bytes().to_vec())
+    /// Get the underlying HTTP client
+    pub(crate) fn http_client(&self) -> &HubPool {
+        &self.client
     }
 
     /// Get Hub information and sync status
@@ -808,7 +1109,6 @@ impl FarcasterClient {
         let response = self
             .client
             .get(&url)
-            .send()
             .await
             .with_context(|| "Failed to get hub info from Farcaster Hub")?;
 
@@ -836,94 +1136,12 @@ impl FarcasterClient {
     /// # Returns
     /// * `Result<Vec<serde_json::Value>>` - List of follower information or an error
     pub async fn get_followers(&self, fid: u64, limit: u32) -> Result<Vec<serde_json::Value>> {
-        let mut all_followers = Vec::new();
-        let mut page_token: Option<String> = None;
-        let page_size = 100; // Fixed page size for better performance
-        let mut total_retrieved = 0;
-        let mut page_count = 0;
-
-        println!("🔄 Starting to fetch followers for FID: {fid}");
-
-        loop {
-            page_count += 1;
-            let mut url = format!(
-                "{}/v1/linksByTargetFid?target_fid={}&link_type=follow&pageSize={}",
-                self.hub_url, fid, page_size
-            );
-
-            if let Some(ref token) = page_token {
-                url.push_str(&format!("&pageToken={}", token));
-            }
-
-            println!("📄 Fetching page {page_count} (page size: {page_size})...");
-            println!("🔗 URL: {}", url);
-
-            let response = self
-                .client
-                .get(&url)
-                .send()
-                .await
-                .with_context(|| "Failed to get followers from Farcaster Hub")?;
-
-            let status = response.status();
-            let response_text = response.text().await?;
-
-            if status.is_success() {
-                let data: serde_json::Value = serde_json::from_str(&response_text)
-                    .with_context(|| "Failed to parse followers response")?;
-
-                if let Some(messages) = data.get("messages").and_then(|m| m.as_array()) {
-                    let page_followers = messages.clone();
-                    let page_follower_count = page_followers.len();
-
-                    println!("✅ Page {page_count}: Retrieved {page_follower_count} followers");
-
-                    // If we have a limit, check if we would exceed it
-                    if limit > 0 && total_retrieved + page_follower_count as u32 > limit {
-                        let remaining = limit - total_retrieved;
-                        let mut truncated_followers = page_followers;
-                        truncated_followers.truncate(remaining as usize);
-                        let truncated_count = truncated_followers.len();
-                        all_followers.extend(truncated_followers);
-                        println!(
-                            "🛑 Reached limit of {limit}, stopping at {} total followers",
-                            total_retrieved + truncated_count as u32
-                        );
-                        break;
-                    }
-
-                    all_followers.extend(page_followers);
-                    total_retrieved = all_followers.len() as u32;
-                    println!("📊 Total followers so far: {total_retrieved}");
-
-                    // Check if there's a next page
-                    if let Some(next_token) = data.get("nextPageToken").and_then(|t| t.as_str()) {
-                        if !next_token.is_empty() {
-                            page_token = Some(next_token.to_string());
-                            println!("➡️  More pages available, continuing...");
-                        } else {
-                            println!("🏁 No more pages available");
-                            break;
-                        }
-                    } else {
-                        println!("🏁 No nextPageToken found, stopping");
-                        break;
-                    }
-                } else {
-                    println!("⚠️  No messages found in response, stopping");
-                    break;
-                }
-            } else {
-                return Err(anyhow::anyhow!(
-                    "Farcaster Hub returned error {}: {}",
-                    status,
-                    response_text
-                ));
-            }
-        }
-
-        println!("✅ Completed fetching followers: {total_retrieved} total followers from {page_count} pages");
-        Ok(all_followers)
+        let base_url = format!(
+            "{}/v1/linksByTargetFid?target_fid={}&link_type=follow",
+            self.hub_url, fid
+        );
+        self.get_links_pipelined(base_url, fid, limit, "followers")
+            .await
     }
 
     /// Get following for a FID
@@ -935,94 +1153,111 @@ impl FarcasterClient {
     /// # Returns
     /// * `Result<Vec<serde_json::Value>>` - List of following information or an error
     pub async fn get_following(&self, fid: u64, limit: u32) -> Result<Vec<serde_json::Value>> {
-        let mut all_following = Vec::new();
-        let mut page_token: Option<String> = None;
-        let page_size = 100; // Fixed page size for better performance
-        let mut total_retrieved = 0;
+        let base_url = format!(
+            "{}/v1/linksByFid?fid={}&link_type=follow",
+            self.hub_url, fid
+        );
+        self.get_links_pipelined(base_url, fid, limit, "following")
+            .await
+    }
+
+    /// Walk a link endpoint page by page, requesting the next page while the current one is processed
+    ///
+    /// Each page token only arrives with the previous page, so pages cannot be
+    /// fanned out: at most one request is in flight. As soon as a response is
+    /// parsed the request for the following page is spawned, so network latency
+    /// overlaps with merging and progress reporting. Results keep hub order.
+    ///
+    /// # Arguments
+    /// * `base_url` - Full endpoint URL without paging parameters
+    /// * `fid` - The Farcaster ID (for progress output)
+    /// * `limit` - Maximum number of links to retrieve (0 for all)
+    /// * `what` - Either "followers" or "following" (for progress output)
+    ///
+    /// # Returns
+    /// * `Result<Vec<serde_json::Value>>` - Link messages or an error
+    async fn get_links_pipelined(
+        &self,
+        base_url: String,
+        fid: u64,
+        limit: u32,
+        what: &'static str,
+    ) -> Result<Vec<serde_json::Value>> {
+        let mut all_links: Vec<serde_json::Value> = Vec::new();
+        // Shrinks when the hub rate limits the walk and grows back afterwards
+        let paging = AdaptivePaging::default();
         let mut page_count = 0;
 
-        println!("🔄 Starting to fetch following for FID: {fid}");
+        println!("🔄 Starting to fetch {what} for FID: {fid}");
 
-        loop {
+        let mut pending = Some(tokio::spawn(fetch_page_adaptive(
+            self.client.clone(),
+            base_url.clone(),
+            None,
+            paging.clone(),
+            what,
+        )));
+
+        while let Some(request) = pending.take() {
             page_count += 1;
-            let mut url = format!(
-                "{}/v1/linksByFid?fid={}&link_type=follow&pageSize={}",
-                self.hub_url, fid, page_size
+            println!(
+                "📄 Fetching page {page_count} (page size: {})...",
+                paging.page_size()
             );
 
-            if let Some(ref token) = page_token {
-                url.push_str(&format!("&pageToken={}", token));
+            let data = request
+                .await
+                .with_context(|| format!("Page request for {what} was aborted"))??;
+
+            let Some(messages) = data.get("messages").and_then(|m| m.as_array()) else {
+                println!("⚠️  No messages found in response, stopping");
+                break;
+            };
+            let page_link_count = messages.len();
+            let total_after_page = all_links.len() + page_link_count;
+            let limit_reached = limit > 0 && total_after_page >= limit as usize;
+
+            // Start the next request before merging this page. An empty page
+            // ends the walk even with a token, so a hub that keeps handing out
+            // tokens cannot loop forever
+            match data.get("nextPageToken").and_then(|t| t.as_str()) {
+                Some(next_token)
+                    if !next_token.is_empty() && !messages.is_empty() && !limit_reached =>
+                {
+                    pending = Some(tokio::spawn(fetch_page_adaptive(
+                        self.client.clone(),
+                        base_url.clone(),
+                        Some(next_token.to_string()),
+                        paging.clone(),
+                        what,
+                    )));
+                    println!("➡️  More pages available, continuing...");
+                }
+                Some(next_token) if !next_token.is_empty() && limit_reached => {}
+                _ => println!("🏁 No more pages available"),
             }
 
-            println!("📄 Fetching page {page_count} (page size: {page_size})...");
-            println!("🔗 URL: {}", url);
-
-            let response = self
-                .client
-                .get(&url)
-                .send()
-                .await
-                .with_context(|| "Failed to get following from Farcaster Hub")?;
-
-            let status = response.status();
-            let response_text = response.text().await?;
-
-            if status.is_success() {
-                let data: serde_json::Value = serde_json::from_str(&response_text)
-                    .with_context(|| "Failed to parse following response")?;
-
-                if let Some(messages) = data.get("messages").and_then(|m| m.as_array()) {
-                    let page_following = messages.clone();
-                    let page_following_count = page_following.len();
-
-                    println!("✅ Page {page_count}: Retrieved {page_following_count} following");
-
-                    // If we have a limit, check if we would exceed it
-                    if limit > 0 && total_retrieved + page_following_count as u32 > limit {
-                        let remaining = limit - total_retrieved;
-                        let mut truncated_following = page_following;
-                        truncated_following.truncate(remaining as usize);
-                        let truncated_count = truncated_following.len();
-                        all_following.extend(truncated_following);
-                        println!(
-                            "🛑 Reached limit of {limit}, stopping at {} total following",
-                            total_retrieved + truncated_count as u32
-                        );
-                        break;
-                    }
+            println!("✅ Page {page_count}: Retrieved {page_link_count} {what}");
+            all_links.extend(messages.iter().cloned());
 
-                    all_following.extend(page_following);
-                    total_retrieved = all_following.len() as u32;
-                    println!("📊 Total following so far: {total_retrieved}");
-
-                    // Check if there's a next page
-                    if let Some(next_token) = data.get("nextPageToken").and_then(|t| t.as_str()) {
-                        if !next_token.is_empty() {
-                            page_token = Some(next_token.to_string());
-                            println!("➡️  More pages available, continuing...");
-                        } else {
-                            println!("🏁 No more pages available");
-                            break;
-                        }
-                    } else {
-                        println!("🏁 No nextPageToken found, stopping");
-                        break;
-                    }
-                } else {
-                    println!("⚠️  No messages found in response, stopping");
-                    break;
-                }
+            if limit > 0 && all_links.len() > limit as usize {
+                all_links.truncate(limit as usize);
+            }
+            if limit_reached {
+                println!(
+                    "🛑 Reached limit of {limit}, stopping at {} total {what}",
+                    all_links.len()
+                );
             } else {
-                return Err(anyhow::anyhow!(
-                    "Farcaster Hub returned error {}: {}",
-                    status,
-                    response_text
-                ));
+                println!("📊 Total {what} so far: {}", all_links.len());
             }
         }
 
-        println!("✅ Completed fetching following: {total_retrieved} total following from {page_count} pages");
-        Ok(all_following)
+        println!(
+            "✅ Completed fetching {what}: {} total {what} from {page_count} pages",
+            all_links.len()
+        );
+        Ok(all_links)
     }
 
     /// Get storage limits for a FID
@@ -1038,7 +1273,6 @@ impl FarcasterClient {
         let response = self
             .client
             .get(&url)
-            .send()
             .await
             .with_context(|| "Failed to get storage limits from Farcaster Hub")?;
 
@@ -1071,7 +1305,6 @@ impl FarcasterClient {
         let response = self
             .client
             .get(&url)
-            .send()
             .await
             .with_context(|| "Failed to get user profile from Farcaster Hub")?;
 
@@ -1095,6 +1328,209 @@ impl FarcasterClient {
             ))
         }
     }
+
+    /// Get casts that mention a FID
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID being mentioned
+    /// * `limit` - Maximum number of casts to retrieve (0 for all)
+    ///
+    /// # Returns
+    /// * `Result<Vec<serde_json::Value>>` - List of cast messages, newest first, or an error
+    pub async fn get_casts_by_mention(
+        &self,
+        fid: u64,
+        limit: u32,
+    ) -> Result<Vec<serde_json::Value>> {
+        let query = format!("/v1/castsByMention?fid={}&reverse=true", fid);
+        self.get_messages_paginated(&query, limit, "mentions").await
+    }
+
+    /// Get reactions of a given type targeting a cast
+    ///
+    /// # Arguments
+    /// * `target_fid` - The FID of the cast author
+    /// * `target_hash` - The cast hash (0x-prefixed hex)
+    /// * `reaction_type` - Hub reaction type, e.g. `REACTION_TYPE_LIKE` or `REACTION_TYPE_RECAST`
+    /// * `limit` - Maximum number of reactions to retrieve (0 for all)
+    ///
+    /// # Returns
+    /// * `Result<Vec<serde_json::Value>>` - List of reaction messages or an error
+    pub async fn get_reactions_by_cast(
+        &self,
+        target_fid: u64,
+        target_hash: &str,
+        reaction_type: &str,
+        limit: u32,
+    ) -> Result<Vec<serde_json::Value>> {
+        let query = format!(
+            "/v1/reactionsByCast?target_fid={}&target_hash={}&reaction_type={}&reverse=true",
+            target_fid, target_hash, reaction_type
+        );
+        self.get_messages_paginated(&query, limit, "reactions")
+            .await
+    }
+
+    /// Get a single cast by its ID
+    ///
+    /// # Arguments
+    /// * `fid` - The FID of the cast author
+    /// * `hash` - The cast hash (0x-prefixed hex)
+    ///
+    /// # Returns
+    /// * `Result<serde_json::Value>` - The cast message or an error
+    pub async fn get_cast_by_id(&self, fid: u64, hash: &str) -> Result<serde_json::Value> {
+        let url = format!("{}/v1/castById?fid={}&hash={}", self.hub_url, fid, hash);
+
+        let response = self
+            .client
+            .get(&url)
+            .await
+            .with_context(|| "Failed to get cast from Farcaster Hub")?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        if status.is_success() {
+            serde_json::from_str(&response_text).with_context(|| "Failed to parse cast response")
+        } else {
+            Err(anyhow::anyhow!(
+                "Farcaster Hub returned error {}: {}",
+                status,
+                response_text
+            ))
+        }
+    }
+
+    /// Get direct replies to a cast
+    ///
+    /// # Arguments
+    /// * `fid` - The FID of the parent cast author
+    /// * `hash` - The parent cast hash (0x-prefixed hex)
+    /// * `limit` - Maximum number of replies to retrieve (0 for all)
+    ///
+    /// # Returns
+    /// * `Result<Vec<serde_json::Value>>` - List of reply casts or an error
+    pub async fn get_casts_by_parent(
+        &self,
+        fid: u64,
+        hash: &str,
+        limit: u32,
+    ) -> Result<Vec<serde_json::Value>> {
+        let query = format!("/v1/castsByParent?fid={}&hash={}", fid, hash);
+        self.get_messages_paginated(&query, limit, "replies").await
+    }
+
+    /// Get the most recent followers of a FID without walking the whole list
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `limit` - Maximum number of follow links to retrieve (0 for all)
+    ///
+    /// # Returns
+    /// * `Result<Vec<serde_json::Value>>` - Follow link messages, newest first, or an error
+    pub async fn get_recent_followers(
+        &self,
+        fid: u64,
+        limit: u32,
+    ) -> Result<Vec<serde_json::Value>> {
+        let query = format!(
+            "/v1/linksByTargetFid?target_fid={}&link_type=follow&reverse=true",
+            fid
+        );
+        self.get_messages_paginated(&query, limit, "followers")
+            .await
+    }
+
+    /// Walk a paginated `messages` endpoint quietly until `limit` is reached
+    ///
+    /// # Arguments
+    /// * `query` - Path and query string starting with `/v1/`, without paging parameters
+    /// * `limit` - Maximum number of messages to retrieve (0 for all)
+    /// * `what` - Short description used in error messages
+    ///
+    /// # Returns
+    /// * `Result<Vec<serde_json::Value>>` - Collected messages or an error
+    pub(crate) async fn get_messages_paginated(
+        &self,
+        query: &str,
+        limit: u32,
+        what: &str,
+    ) -> Result<Vec<serde_json::Value>> {
+        let mut all_messages = Vec::new();
+        let mut page_token: Option<String> = None;
+        let paging = AdaptivePaging::new(if limit > 0 && limit < MAX_PAGE_SIZE {
+            limit
+        } else {
+            MAX_PAGE_SIZE
+        });
+
+        loop {
+            let data = fetch_page_adaptive(
+                self.client.clone(),
+                format!("{}{}", self.hub_url, query),
+                page_token.clone(),
+                paging.clone(),
+                what,
+            )
+            .await?;
+
+            let Some(messages) = data.get("messages").and_then(|m| m.as_array()) else {
+                break;
+            };
+            all_messages.extend(messages.iter().cloned());
+
+            if limit > 0 && all_messages.len() >= limit as usize {
+                all_messages.truncate(limit as usize);
+                break;
+            }
+
+            match data.get("nextPageToken").and_then(|t| t.as_str()) {
+                Some(next_token) if !next_token.is_empty() && !messages.is_empty() => {
+                    page_token = Some(next_token.to_string());
+                }
+                _ => break,
+            }
+        }
+
+        Ok(all_messages)
+    }
+}
+
+/// Fetch one page from a hub list endpoint and parse it as JSON
+pub(crate) async fn fetch_page(
+    client: HubPool,
+    url: String,
+    what: &'static str,
+) -> Result<serde_json::Value> {
+    let response = client
+        .get(&url)
+        .await
+        .with_context(|| format!("Failed to get {what} from Farcaster Hub"))?;
+
+    let status = response.status();
+    let response_text = response.text().await?;
+
+    if status.is_success() {
+        serde_json::from_str(&response_text)
+            .with_context(|| format!("Failed to parse {what} response"))
+    } else {
+        Err(anyhow::anyhow!(
+            "Farcaster Hub returned error {}: {}",
+            status,
+            response_text
+        ))
+    }
+}
+
+/// Interpret a `/v1/validateMessage` response body
+fn parse_validation(response_text: &str) -> Result<()> {
+    let data: serde_json::Value = serde_json::from_str(response_text)
+        .with_context(|| "Failed to parse validateMessage response")?;
+    match data.get("valid").and_then(|valid| valid.as_bool()) {
+        Some(true) => Ok(()),
+        _ => anyhow::bail!("Farcaster Hub found the message invalid: {response_text}"),
+    }
 }
 
 /// Get Ed25519 public key for a specific FID from encrypted storage
@@ -1137,10 +1573,154 @@ mod tests {
         assert_eq!(client.hub_url(), "https://hub-api.neynar.com");
     }
 
+    #[test]
+    fn test_into_signing_keeps_hub_url() {
+        let client = FarcasterClient::read_only("https://hub.example".to_string());
+        let signing = client.into_signing(None);
+
+        assert_eq!(signing.hub_url(), "https://hub.example");
+        assert!(signing.key_manager().is_none());
+    }
+
+    #[test]
+    fn test_with_hubs_rejects_empty_list() {
+        assert!(FarcasterClient::with_hubs(Vec::new()).is_err());
+        let client =
+            FarcasterClient::with_hubs(vec![HubEndpoint::new("https://hub.example")]).unwrap();
+        assert_eq!(client.hub_url(), "https://hub.example");
+    }
+
+    #[test]
+    fn test_with_read_replicas_rejects_empty_list() {
+        assert!(FarcasterClient::with_read_replicas(Vec::new()).is_err());
+        let client = FarcasterClient::with_read_replicas(vec![
+            HubEndpoint::new("https://hub-a.example"),
+            HubEndpoint::new("https://hub-b.example"),
+        ])
+        .unwrap();
+        assert_eq!(client.hub_url(), "https://hub-a.example");
+    }
+
+    #[test]
+    fn test_parse_username_proofs() {
+        let data = serde_json::json!({
+            "proofs": [{
+                "timestamp": 1700000000,
+                "name": "alice.eth",
+                "owner": "0x8773442740c17c9d0f0b87022c722f9a136206ed",
+                "signature": "0x1234",
+                "fid": 42,
+                "type": "USERNAME_TYPE_ENS_L1"
+            }]
+        });
+        let proofs = parse_username_proofs(&data).unwrap();
+        assert_eq!(proofs.len(), 1);
+        assert_eq!(proofs[0].name, "alice.eth");
+        assert_eq!(proofs[0].proof_type, "USERNAME_TYPE_ENS_L1");
+
+        assert!(parse_username_proofs(&serde_json::json!({}))
+            .unwrap()
+            .is_empty());
+        assert!(parse_username_proofs(&serde_json::json!({ "proofs": [{ "name": 1 }] })).is_err());
+    }
+
+    #[test]
+    fn test_parse_validation() {
+        assert!(parse_validation(r#"{"valid":true,"message":{}}"#).is_ok());
+        assert!(parse_validation(r#"{"valid":false}"#).is_err());
+        assert!(parse_validation("{}").is_err());
+        assert!(parse_validation("not json").is_err());
+    }
+
     #[tokio::test]
     async fn test_farcaster_client_from_env() {
         // Test that from_env now returns an error (environment variables are no longer allowed)
         let result = FarcasterClient::from_env();
         assert!(result.is_err());
     }
+
+    /// Serve follower pages of FID 3 from a mock hub
+    ///
+    /// Each page is `(page token, status, follower FIDs, next page token)`.
+    #[cfg(feature = "api-server")]
+    async fn mock_follower_pages(pages: &[(Option<&str>, u16, &[u64], &str)]) -> FarcasterClient {
+        use crate::core::client::mock_hub::Fixture;
+        use crate::core::client::MockHub;
+
+        let fixtures = pages.iter().map(|(token, status, fids, next)| {
+            let mut request = format!(
+                "GET /v1/linksByTargetFid?link_type=follow&pageSize={}",
+                crate::core::client::adaptive_paging::MAX_PAGE_SIZE
+            );
+            if let Some(token) = token {
+                request.push_str(&format!("&pageToken={token}"));
+            }
+            request.push_str("&target_fid=3");
+            let messages: Vec<serde_json::Value> = fids
+                .iter()
+                .map(|fid| serde_json::json!({ "data": { "fid": fid } }))
+                .collect();
+            Fixture {
+                request,
+                status: *status,
+                body: serde_json::json!({ "messages": messages, "nextPageToken": next }),
+            }
+        });
+        let addr = MockHub::from_fixtures(fixtures)
+            .serve(std::net::SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+        FarcasterClient::read_only(format!("http://{addr}"))
+    }
+
+    #[cfg(feature = "api-server")]
+    fn follower_fids(links: &[serde_json::Value]) -> Vec<u64> {
+        links
+            .iter()
+            .filter_map(|link| link.pointer("/data/fid").and_then(|fid| fid.as_u64()))
+            .collect()
+    }
+
+    #[cfg(feature = "api-server")]
+    #[tokio::test]
+    async fn test_links_pipelined_keeps_hub_order() {
+        let client = mock_follower_pages(&[
+            (None, 200, &[1, 2], "p2"),
+            (Some("p2"), 200, &[3, 4], "p3"),
+            (Some("p3"), 200, &[5], ""),
+        ])
+        .await;
+        let links = client.get_followers(3, 0).await.unwrap();
+        assert_eq!(follower_fids(&links), [1, 2, 3, 4, 5]);
+    }
+
+    #[cfg(feature = "api-server")]
+    #[tokio::test]
+    async fn test_links_pipelined_stops_at_limit() {
+        // No fixture for page p3: prefetching it would fail the walk
+        let client =
+            mock_follower_pages(&[(None, 200, &[1, 2], "p2"), (Some("p2"), 200, &[3, 4], "p3")])
+                .await;
+        let links = client.get_followers(3, 3).await.unwrap();
+        assert_eq!(follower_fids(&links), [1, 2, 3]);
+    }
+
+    #[cfg(feature = "api-server")]
+    #[tokio::test]
+    async fn test_links_pipelined_stops_at_empty_page() {
+        // No fixture for page p3: following the token would fail the walk
+        let client =
+            mock_follower_pages(&[(None, 200, &[1, 2], "p2"), (Some("p2"), 200, &[], "p3")]).await;
+        let links = client.get_followers(3, 0).await.unwrap();
+        assert_eq!(follower_fids(&links), [1, 2]);
+    }
+
+    #[cfg(feature = "api-server")]
+    #[tokio::test]
+    async fn test_links_pipelined_fails_on_prefetched_page_error() {
+        let client =
+            mock_follower_pages(&[(None, 200, &[1, 2], "p2"), (Some("p2"), 500, &[], "")]).await;
+        let error = client.get_followers(3, 0).await.unwrap_err();
+        assert!(error.to_string().contains("500"), "{error}");
+    }
 }
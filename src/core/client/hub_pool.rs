@@ -0,0 +1,553 @@
+//! Ordered hub list with per-hub API keys and failover for reads
+//!
+//! A [`HubPool`] sends every request to the client's primary hub first. Reads
+//! that fail with a connection error, a timeout or a 5xx response are retried
+//! on the next configured hub; a 401/403/429 moves on to the hub's next API
+//! key first, so keys can be rotated without downtime. Submissions only go to
+//! the primary hub.
+//!
+//! When request ids are enabled (see [`crate::core::utils::http`]) each
+//! attempt carries its own id, and errors name it.
+//!
+//! Fallback hubs come from `hubs.json` in the data directory, installed once
+//! per process with [`set_fallback_hubs`]; every client built afterwards with
+//! [`FarcasterClient::read_only`](crate::core::client::hub_client::FarcasterClient::read_only)
+//! uses them.
+//!
+//! A pool built with [`HubPool::read_replicas`] instead spreads reads over
+//! all hubs round-robin. Hubs that failed a request or a [`HubPool::check_health`]
+//! probe are skipped until they pass a probe again, and are only tried when
+//! no healthy hub is left.
+
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use reqwest::Client;
+use reqwest::RequestBuilder;
+use reqwest::Response;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::utils::http;
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
+
+/// Header API keys are sent in unless a hub sets its own
+pub const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+
+/// How long a read may take before the next hub is tried
+const FAILOVER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long a health probe may take
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+static FALLBACK_HUBS: RwLock<Vec<HubEndpoint>> = RwLock::new(Vec::new());
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// A hub and the credentials to use with it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HubEndpoint {
+    pub url: String,
+    /// API keys tried in order; later keys are used when earlier ones are
+    /// rejected or rate limited
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<String>,
+    /// Header the API key is sent in (default `x-api-key`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_header: Option<String>,
+}
+
+impl HubEndpoint {
+    /// A hub without credentials
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into().trim_end_matches('/').to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn header(&self) -> &str {
+        self.api_key_header
+            .as_deref()
+            .unwrap_or(DEFAULT_API_KEY_HEADER)
+    }
+}
+
+/// Location of the hub list in the data directory
+pub fn hubs_config_path() -> PathBuf {
+    crate::core::utils::data_dir::data_dir().join("hubs.json")
+}
+
+/// Load the ordered hub list, empty when no `hubs.json` exists
+pub fn load_hubs_config() -> Result<Vec<HubEndpoint>> {
+    let path = hubs_config_path();
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Ok(Vec::new());
+    };
+    let hubs: Vec<HubEndpoint> = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid hub list {}", path.display()))?;
+    Ok(hubs
+        .into_iter()
+        .map(|hub| HubEndpoint {
+            url: hub.url.trim_end_matches('/').to_string(),
+            ..hub
+        })
+        .collect())
+}
+
+/// Set the hubs clients fall back to (and the credentials of listed hubs)
+pub fn set_fallback_hubs(hubs: Vec<HubEndpoint>) {
+    if let Ok(mut fallback) = FALLBACK_HUBS.write() {
+        *fallback = hubs;
+    }
+}
+
+/// The hub configured for `url` in `hubs.json`, or `url` without credentials
+pub fn configured_endpoint(url: &str) -> HubEndpoint {
+    let url = url.trim_end_matches('/');
+    FALLBACK_HUBS
+        .read()
+        .ok()
+        .and_then(|hubs| hubs.iter().find(|hub| hub.url == url).cloned())
+        .unwrap_or_else(|| HubEndpoint::new(url))
+}
+
+/// Report which hub served each request on stderr
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+/// Whether request routing should be reported on stderr
+pub(crate) fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+/// `primary_url` first, then the configured hubs in their order
+fn ordered_hubs(primary_url: &str, configured: Vec<HubEndpoint>) -> Vec<HubEndpoint> {
+    let primary_url = primary_url.trim_end_matches('/');
+    let primary = configured
+        .iter()
+        .find(|hub| hub.url == primary_url)
+        .cloned()
+        .unwrap_or_else(|| HubEndpoint::new(primary_url));
+    let mut hubs = vec![primary];
+    hubs.extend(configured.into_iter().filter(|hub| hub.url != primary_url));
+    hubs
+}
+
+/// Reject an empty hub list, which has no primary hub
+fn require_hubs(hubs: &[HubEndpoint]) -> Result<()> {
+    if hubs.is_empty() {
+        anyhow::bail!("At least one hub is required");
+    }
+    Ok(())
+}
+
+/// Reported health of one hub in a pool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UpstreamHealth {
+    pub url: String,
+    pub healthy: bool,
+    /// Unix time (seconds) of the last health probe
+    pub last_checked: Option<u64>,
+    /// Why the hub was last marked unhealthy
+    pub last_error: Option<String>,
+    /// Reads this hub has served
+    pub requests: u64,
+}
+
+/// Health state of one hub, shared by clones of a pool
+#[derive(Default)]
+struct HubHealth {
+    unhealthy: AtomicBool,
+    /// Unix time of the last probe, 0 if never probed
+    last_checked: AtomicU64,
+    last_error: RwLock<Option<String>>,
+    requests: AtomicU64,
+}
+
+impl HubHealth {
+    fn mark_healthy(&self) {
+        self.unhealthy.store(false, Ordering::Relaxed);
+    }
+
+    fn mark_unhealthy(&self, error: &anyhow::Error) {
+        self.unhealthy.store(true, Ordering::Relaxed);
+        if let Ok(mut last_error) = self.last_error.write() {
+            *last_error = Some(error.to_string());
+        }
+    }
+}
+
+struct PoolInner {
+    client: Client,
+    hubs: Vec<HubEndpoint>,
+    /// Index of the API key in use, per hub
+    key_index: Vec<AtomicUsize>,
+    health: Vec<HubHealth>,
+    /// Spread reads over all hubs instead of preferring the first
+    round_robin: bool,
+    next: AtomicUsize,
+}
+
+/// HTTP access to a primary hub with ordered fallbacks
+///
+/// Cheap to clone; clones share the client and the API key rotation state.
+#[derive(Clone)]
+pub struct HubPool {
+    inner: Arc<PoolInner>,
+}
+
+impl HubPool {
+    /// Pool with `hubs[0]` as the primary hub
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The pool, or an error if `hubs` is empty
+    pub fn new(hubs: Vec<HubEndpoint>) -> Result<Self> {
+        require_hubs(&hubs)?;
+        Ok(Self::build(hubs, false))
+    }
+
+    /// Pool spreading reads round-robin over healthy hubs
+    ///
+    /// `hubs[0]` is still the hub submissions would go to.
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The pool, or an error if `hubs` is empty
+    pub fn read_replicas(hubs: Vec<HubEndpoint>) -> Result<Self> {
+        require_hubs(&hubs)?;
+        Ok(Self::build(hubs, true))
+    }
+
+    /// `hubs` must not be empty: the first hub is the primary hub
+    fn build(hubs: Vec<HubEndpoint>, round_robin: bool) -> Self {
+        let key_index = hubs.iter().map(|_| AtomicUsize::new(0)).collect();
+        let health = hubs.iter().map(|_| HubHealth::default()).collect();
+        Self {
+            inner: Arc::new(PoolInner {
+                client: crate::core::utils::http::client(),
+                hubs,
+                key_index,
+                health,
+                round_robin,
+                next: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Pool for `primary_url` followed by the configured fallback hubs
+    ///
+    /// The primary hub keeps the credentials configured for its URL, if any.
+    pub fn with_fallbacks(primary_url: &str) -> Self {
+        let configured = FALLBACK_HUBS
+            .read()
+            .map(|hubs| hubs.clone())
+            .unwrap_or_default();
+        Self::build(ordered_hubs(primary_url, configured), false)
+    }
+
+    /// URL of the primary hub
+    pub fn primary_url(&self) -> &str {
+        &self.inner.hubs[0].url
+    }
+
+    /// All hubs in failover order
+    pub fn hubs(&self) -> &[HubEndpoint] {
+        &self.inner.hubs
+    }
+
+    /// Indices of the hubs to try for the next read, in order
+    fn read_order(&self) -> Vec<usize> {
+        let count = self.inner.hubs.len();
+        if !self.inner.round_robin || count == 0 {
+            return (0..count).collect();
+        }
+        let start = self.inner.next.fetch_add(1, Ordering::Relaxed) % count;
+        let (healthy, unhealthy): (Vec<usize>, Vec<usize>) = (0..count)
+            .map(|i| (start + i) % count)
+            .partition(|&hub| !self.inner.health[hub].unhealthy.load(Ordering::Relaxed));
+        healthy.into_iter().chain(unhealthy).collect()
+    }
+
+    /// Probe every hub's `/v1/info` and update its health
+    pub async fn check_health(&self) {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let probes = self.inner.hubs.iter().enumerate().map(|(hub, endpoint)| {
+            let url = format!("{}/v1/info", endpoint.url);
+            let request = self
+                .with_credentials(hub, self.inner.client.get(&url))
+                .timeout(HEALTH_CHECK_TIMEOUT);
+            async move {
+                let result = match request.send().await {
+                    Ok(response) if response.status().is_success() => Ok(()),
+                    Ok(response) => Err(anyhow::anyhow!("{url} returned {}", response.status())),
+                    Err(e) => Err(anyhow::Error::new(e).context(format!("{url} is unreachable"))),
+                };
+                (hub, result)
+            }
+        });
+        for (hub, result) in futures::future::join_all(probes).await {
+            let health = &self.inner.health[hub];
+            health.last_checked.store(now, Ordering::Relaxed);
+            match result {
+                Ok(()) => health.mark_healthy(),
+                Err(e) => health.mark_unhealthy(&e),
+            }
+        }
+    }
+
+    /// Health of every hub, in pool order
+    pub fn health(&self) -> Vec<UpstreamHealth> {
+        self.inner
+            .hubs
+            .iter()
+            .zip(&self.inner.health)
+            .map(|(endpoint, health)| UpstreamHealth {
+                url: endpoint.url.clone(),
+                healthy: !health.unhealthy.load(Ordering::Relaxed),
+                last_checked: Some(health.last_checked.load(Ordering::Relaxed))
+                    .filter(|&checked| checked > 0),
+                last_error: health
+                    .last_error
+                    .read()
+                    .ok()
+                    .and_then(|error| error.clone()),
+                requests: health.requests.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    fn with_credentials(&self, hub: usize, request: RequestBuilder) -> RequestBuilder {
+        let endpoint = &self.inner.hubs[hub];
+        let key = self.inner.key_index[hub].load(Ordering::Relaxed);
+        match endpoint.api_keys.get(key) {
+            Some(api_key) => request.header(endpoint.header(), api_key),
+            None => request,
+        }
+    }
+
+    /// Start a POST to the primary hub, with its credentials
+    ///
+    /// Submissions are not failed over: a message accepted by a lagging hub
+    /// would be reported as published before the primary hub has it.
+    ///
+    /// # Returns
+    /// * The request, and its request id if request ids are enabled
+    pub fn post(&self, url: &str) -> (RequestBuilder, Option<String>) {
+        http::tag_request(self.with_credentials(0, self.inner.client.post(url)))
+    }
+
+    /// GET a hub URL, failing over to the next hub when the current one is down
+    ///
+    /// `url` is built on the primary hub's URL; other hubs get the same path.
+    /// Responses with other error statuses (e.g. 400, 404) are returned as they
+    /// are, since another hub would answer the same.
+    ///
+    /// # Arguments
+    /// * `url` - Full request URL starting with the primary hub's URL
+    ///
+    /// # Returns
+    /// * `Result<Response>` - The first usable response, or the last error
+    pub async fn get(&self, url: &str) -> Result<Response> {
+        let _timer = metrics::start(Phase::Hub, request_path(url));
+        let Some(path) = url.strip_prefix(self.primary_url()) else {
+            let (request, request_id) = http::tag_request(self.inner.client.get(url));
+            return request
+                .send()
+                .await
+                .map(|response| log_failure(url, response, &request_id))
+                .with_context(|| format!("GET {url} failed{}", http::request_note(&request_id)));
+        };
+        let failover = self.inner.hubs.len() > 1;
+
+        let mut last_error = None;
+        for hub in self.read_order() {
+            let endpoint = &self.inner.hubs[hub];
+            let hub_url = format!("{}{path}", endpoint.url);
+            let keys = endpoint.api_keys.len().max(1);
+            for _ in 0..keys {
+                let mut request = self.with_credentials(hub, self.inner.client.get(&hub_url));
+                if failover {
+                    request = request.timeout(FAILOVER_TIMEOUT);
+                }
+                let (request, request_id) = http::tag_request(request);
+                let note = http::request_note(&request_id);
+                let attempt = match request.send().await {
+                    Ok(response) if is_key_rejected(response.status()) && keys > 1 => {
+                        self.rotate_key(hub);
+                        Attempt::NextKey(anyhow::anyhow!(
+                            "{} rejected the API key ({}){note}",
+                            endpoint.url,
+                            response.status()
+                        ))
+                    }
+                    Ok(response) if response.status().is_server_error() && failover => {
+                        Attempt::NextHub(anyhow::anyhow!(
+                            "{} returned {}{note}",
+                            endpoint.url,
+                            response.status()
+                        ))
+                    }
+                    Ok(response) => Attempt::Done(response),
+                    Err(e) if failover && (e.is_connect() || e.is_timeout()) => Attempt::NextHub(
+                        anyhow::Error::new(e)
+                            .context(format!("{} is unreachable{note}", endpoint.url)),
+                    ),
+                    Err(e) => {
+                        return Err(
+                            anyhow::Error::new(e).context(format!("GET {hub_url} failed{note}"))
+                        )
+                    }
+                };
+                match attempt {
+                    Attempt::Done(response) => {
+                        let health = &self.inner.health[hub];
+                        health.requests.fetch_add(1, Ordering::Relaxed);
+                        health.mark_healthy();
+                        if is_verbose() {
+                            crate::eprintln!("🛰️  {path} served by {}", endpoint.url);
+                        }
+                        return Ok(log_failure(&hub_url, response, &request_id));
+                    }
+                    Attempt::NextKey(error) => {
+                        if is_verbose() {
+                            crate::eprintln!("🔑 {error}, trying the next key");
+                        }
+                        last_error = Some(error);
+                    }
+                    Attempt::NextHub(error) => {
+                        self.inner.health[hub].mark_unhealthy(&error);
+                        if is_verbose() {
+                            crate::eprintln!("⚠️  {error}, trying the next hub");
+                        }
+                        last_error = Some(error);
+                        break;
+                    }
+                }
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| anyhow::anyhow!("No hub configured"))
+            .context("Every configured hub failed"))
+    }
+
+    fn rotate_key(&self, hub: usize) {
+        let keys = self.inner.hubs[hub].api_keys.len().max(1);
+        let index = &self.inner.key_index[hub];
+        let next = (index.load(Ordering::Relaxed) + 1) % keys;
+        index.store(next, Ordering::Relaxed);
+    }
+}
+
+/// Path of a request URL without its query, naming the hub endpoint
+fn request_path(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(url) => url.path().to_string(),
+        Err(_) => url.to_string(),
+    }
+}
+
+/// Log the request id of an error response; callers report the error itself
+fn log_failure(url: &str, response: Response, request_id: &Option<String>) -> Response {
+    let status = response.status();
+    if let Some(id) = request_id {
+        if status.is_client_error() || status.is_server_error() {
+            crate::eprintln!("🧾 {url} returned {status} (request id {id})");
+        }
+    }
+    response
+}
+
+/// What to do after one request
+enum Attempt {
+    Done(Response),
+    NextKey(anyhow::Error),
+    NextHub(anyhow::Error),
+}
+
+/// Statuses meaning the API key is invalid, revoked or out of quota
+fn is_key_rejected(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primary_keeps_configured_credentials() {
+        let keyed = HubEndpoint {
+            url: "https://hub-b.example".to_string(),
+            api_keys: vec!["k1".to_string()],
+            api_key_header: None,
+        };
+        let hubs = ordered_hubs(
+            "https://hub-b.example/",
+            vec![HubEndpoint::new("https://hub-a.example"), keyed.clone()],
+        );
+        assert_eq!(hubs, vec![keyed, HubEndpoint::new("https://hub-a.example")]);
+
+        let hubs = ordered_hubs("https://hub-c.example", hubs);
+        assert_eq!(hubs[0], HubEndpoint::new("https://hub-c.example"));
+        assert_eq!(hubs.len(), 3);
+    }
+
+    #[test]
+    fn test_empty_hub_list_is_rejected() {
+        assert!(HubPool::new(Vec::new()).is_err());
+        assert!(HubPool::read_replicas(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_rotate_key_wraps() {
+        let pool = HubPool::new(vec![HubEndpoint {
+            url: "https://hub.example".to_string(),
+            api_keys: vec!["k1".to_string(), "k2".to_string()],
+            api_key_header: Some("api_key".to_string()),
+        }])
+        .unwrap();
+        pool.rotate_key(0);
+        assert_eq!(pool.inner.key_index[0].load(Ordering::Relaxed), 1);
+        pool.rotate_key(0);
+        assert_eq!(pool.inner.key_index[0].load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_read_replicas_round_robin() {
+        let hubs = vec![
+            HubEndpoint::new("https://hub-a.example"),
+            HubEndpoint::new("https://hub-b.example"),
+            HubEndpoint::new("https://hub-c.example"),
+        ];
+        let failover = HubPool::new(hubs.clone()).unwrap();
+        assert_eq!(failover.read_order(), [0, 1, 2]);
+        assert_eq!(failover.read_order(), [0, 1, 2]);
+
+        let pool = HubPool::read_replicas(hubs).unwrap();
+        assert_eq!(pool.read_order(), [0, 1, 2]);
+        assert_eq!(pool.read_order(), [1, 2, 0]);
+
+        pool.inner.health[2].mark_unhealthy(&anyhow::anyhow!("down"));
+        assert_eq!(pool.read_order(), [0, 1, 2]);
+        assert_eq!(pool.read_order(), [0, 1, 2]);
+        assert_eq!(pool.read_order(), [1, 0, 2]);
+
+        let health = pool.health();
+        assert!(health[0].healthy);
+        assert!(!health[2].healthy);
+        assert_eq!(health[2].last_error.as_deref(), Some("down"));
+        assert_eq!(health[2].last_checked, None);
+    }
+}
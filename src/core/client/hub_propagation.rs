@@ -0,0 +1,204 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+
+use crate::core::client::hub_client::fetch_page;
+use crate::core::client::hub_client::FarcasterClient;
+
+/// How long to wait for the hub to index an onchain event by default
+pub const DEFAULT_HUB_WAIT_TIMEOUT: Duration = Duration::from_secs(180);
+/// Delay between onChainEventsByFid polls
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Events fetched per poll, newest first
+const EVENTS_PAGE_SIZE: u32 = 100;
+
+/// Onchain state change expected to show up in the hub
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpectedEvent {
+    /// The FID was registered in the IdRegistry
+    IdRegister,
+    /// A key was added to the KeyRegistry
+    SignerAdd(Vec<u8>),
+    /// A key was removed from the KeyRegistry
+    SignerRemove(Vec<u8>),
+}
+
+impl ExpectedEvent {
+    /// `event_type` filter for onChainEventsByFid
+    fn event_type(&self) -> &'static str {
+        match self {
+            ExpectedEvent::IdRegister => "EVENT_TYPE_ID_REGISTER",
+            ExpectedEvent::SignerAdd(_) | ExpectedEvent::SignerRemove(_) => "EVENT_TYPE_SIGNER",
+        }
+    }
+
+    /// Whether a hub onchain event is the expected one
+    ///
+    /// KeyRegistry keys can only be added and removed once, so the key and the
+    /// signer event type identify the event without a transaction hash.
+    fn matches(&self, event: &serde_json::Value) -> bool {
+        let (body, event_type, key) = match self {
+            ExpectedEvent::IdRegister => (
+                "idRegisterEventBody",
+                "ID_REGISTER_EVENT_TYPE_REGISTER",
+                None,
+            ),
+            ExpectedEvent::SignerAdd(key) => {
+                ("signerEventBody", "SIGNER_EVENT_TYPE_ADD", Some(key))
+            }
+            ExpectedEvent::SignerRemove(key) => {
+                ("signerEventBody", "SIGNER_EVENT_TYPE_REMOVE", Some(key))
+            }
+        };
+        let Some(body) = event.get(body) else {
+            return false;
+        };
+        if body.get("eventType").and_then(|t| t.as_str()) != Some(event_type) {
+            return false;
+        }
+        match key {
+            None => true,
+            Some(key) => body
+                .get("key")
+                .and_then(|k| k.as_str())
+                .map(|k| {
+                    k.trim_start_matches("0x")
+                        .eq_ignore_ascii_case(&hex::encode(key))
+                })
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// When the hub picked up an onchain event
+#[derive(Debug, Clone)]
+pub struct HubPropagation {
+    /// Time spent polling until the event appeared
+    pub waited: Duration,
+    /// Time from the event's block to it being observed, when the hub reports the block time
+    pub since_block: Option<Duration>,
+    pub block_number: Option<u64>,
+    pub polls: u32,
+}
+
+impl HubPropagation {
+    /// One-line description of the latency, e.g. `after 6.1s (14s after block 1234)`
+    pub fn summary(&self) -> String {
+        let mut summary = format!("after {:.1}s", self.waited.as_secs_f64());
+        match (self.since_block, self.block_number) {
+            (Some(lag), Some(block)) => {
+                summary.push_str(&format!(" ({}s after block {block})", lag.as_secs()))
+            }
+            (Some(lag), None) => {
+                summary.push_str(&format!(" ({}s after its block)", lag.as_secs()))
+            }
+            _ => {}
+        }
+        summary
+    }
+}
+
+impl<M> FarcasterClient<M> {
+    /// Wait until the hub has indexed an onchain event for a FID
+    ///
+    /// Polls onChainEventsByFid until the event appears. Messages signed with a
+    /// new signer are rejected until the hub has seen its key being added, so
+    /// this is the point from which they can be submitted.
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID the event belongs to
+    /// * `expected` - The event to wait for
+    /// * `timeout` - How long to keep polling
+    ///
+    /// # Returns
+    /// * `Result<HubPropagation>` - The observed latency, or an error on timeout
+    pub async fn wait_for_onchain_event(
+        &self,
+        fid: u64,
+        expected: &ExpectedEvent,
+        timeout: Duration,
+    ) -> Result<HubPropagation> {
+        let url = format!(
+            "{}/v1/onChainEventsByFid?fid={}&event_type={}&pageSize={}&reverse=true",
+            self.hub_url(),
+            fid,
+            expected.event_type(),
+            EVENTS_PAGE_SIZE
+        );
+        let started = Instant::now();
+        let mut polls = 0;
+        loop {
+            polls += 1;
+            // Transient hub errors are retried until the timeout
+            let page = fetch_page(self.http_client().clone(), url.clone(), "onchain events").await;
+            let found = page.as_ref().ok().and_then(|page| {
+                page.get("events")
+                    .and_then(|e| e.as_array())
+                    .and_then(|events| events.iter().find(|e| expected.matches(e)).cloned())
+            });
+            if let Some(event) = found {
+                let block_timestamp = event.get("blockTimestamp").and_then(|t| t.as_u64());
+                let now = chrono::Utc::now().timestamp() as u64;
+                return Ok(HubPropagation {
+                    waited: started.elapsed(),
+                    since_block: block_timestamp
+                        .map(|t| Duration::from_secs(now.saturating_sub(t))),
+                    block_number: event.get("blockNumber").and_then(|n| n.as_u64()),
+                    polls,
+                });
+            }
+            if started.elapsed() >= timeout {
+                let reason = match page {
+                    Err(e) => format!(" (last error: {e})"),
+                    Ok(_) => String::new(),
+                };
+                anyhow::bail!(
+                    "Hub did not index the {} event for FID {} within {}s{}",
+                    expected.event_type(),
+                    fid,
+                    timeout.as_secs(),
+                    reason
+                );
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_expected_event_matches() {
+        let add = json!({
+            "type": "EVENT_TYPE_SIGNER",
+            "blockNumber": 123,
+            "signerEventBody": {"key": "0xABCD", "keyType": 1, "eventType": "SIGNER_EVENT_TYPE_ADD"}
+        });
+        assert!(ExpectedEvent::SignerAdd(vec![0xab, 0xcd]).matches(&add));
+        assert!(!ExpectedEvent::SignerAdd(vec![0xab, 0xce]).matches(&add));
+        assert!(!ExpectedEvent::SignerRemove(vec![0xab, 0xcd]).matches(&add));
+        assert!(!ExpectedEvent::IdRegister.matches(&add));
+
+        let register = json!({
+            "type": "EVENT_TYPE_ID_REGISTER",
+            "idRegisterEventBody": {"to": "0x01", "eventType": "ID_REGISTER_EVENT_TYPE_REGISTER"}
+        });
+        assert!(ExpectedEvent::IdRegister.matches(&register));
+    }
+
+    #[test]
+    fn test_propagation_summary() {
+        let propagation = HubPropagation {
+            waited: Duration::from_millis(6100),
+            since_block: Some(Duration::from_secs(14)),
+            block_number: Some(1234),
+            polls: 4,
+        };
+        assert_eq!(propagation.summary(), "after 6.1s (14s after block 1234)");
+    }
+}
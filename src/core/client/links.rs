@@ -0,0 +1,167 @@
+use anyhow::Result;
+use ed25519_dalek::SigningKey;
+
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_client::HubResponse;
+use crate::core::client::hub_client::Signing;
+use crate::core::protocol::builder::MessageBuilder;
+use crate::core::protocol::message::Message;
+
+/// Link type of follows
+pub const FOLLOW_LINK_TYPE: &str = "follow";
+
+/// Why a followed account is suggested for unfollowing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnfollowReason {
+    /// Has never cast
+    NeverCast,
+    /// Last cast is older than the inactivity threshold
+    Inactive {
+        /// Farcaster timestamp of the latest cast
+        last_cast: u64,
+    },
+    /// Labelled as spam
+    Spam,
+}
+
+/// Target FIDs of follow link messages
+pub fn followed_fids(links: &[serde_json::Value]) -> Vec<u64> {
+    links
+        .iter()
+        .filter_map(|link| link.pointer("/data/linkBody/targetFid")?.as_u64())
+        .collect()
+}
+
+/// Decide whether a followed account should be unfollowed
+///
+/// Spam takes precedence over inactivity, so the reason shown is the strongest one.
+///
+/// # Arguments
+/// * `last_cast` - Farcaster timestamp of the account's latest cast, if any
+/// * `is_spam` - Whether the account is labelled as spam
+/// * `now` - Current Farcaster timestamp
+/// * `inactive_days` - Days without casts after which an account is inactive
+///
+/// # Returns
+/// * `Option<UnfollowReason>` - The reason to unfollow, or `None` to keep following
+pub fn unfollow_reason(
+    last_cast: Option<u64>,
+    is_spam: bool,
+    now: u64,
+    inactive_days: u64,
+) -> Option<UnfollowReason> {
+    if is_spam {
+        return Some(UnfollowReason::Spam);
+    }
+    match last_cast {
+        None => Some(UnfollowReason::NeverCast),
+        Some(last_cast) if now.saturating_sub(last_cast) > inactive_days * 24 * 60 * 60 => {
+            Some(UnfollowReason::Inactive { last_cast })
+        }
+        Some(_) => None,
+    }
+}
+
+/// Build a LINK_REMOVE message undoing a follow, signed with an Ed25519 signer
+///
+/// # Arguments
+/// * `fid` - The FID that follows `target_fid`
+/// * `target_fid` - The FID to unfollow
+/// * `signing_key` - An active signer of `fid`
+///
+/// # Returns
+/// * `Result<Message>` - The signed message, with `data_bytes` set
+pub fn build_unfollow_message(
+    fid: u64,
+    target_fid: u64,
+    signing_key: &SigningKey,
+) -> Result<Message> {
+    MessageBuilder::new(fid)
+        .unfollow(target_fid)
+        .sign(signing_key)
+}
+
+impl<M> FarcasterClient<M> {
+    /// Get the timestamp of a FID's latest cast
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `Result<Option<u64>>` - Farcaster timestamp of the latest cast, `None` if it never cast
+    pub async fn get_latest_cast_timestamp(&self, fid: u64) -> Result<Option<u64>> {
+        let casts = self.get_casts_by_fid(fid, 1).await?;
+        Ok(casts
+            .first()
+            .and_then(|cast| cast.pointer("/data/timestamp"))
+            .and_then(|t| t.as_u64()))
+    }
+}
+
+impl FarcasterClient<Signing> {
+    /// Unfollow a FID with a LINK_REMOVE message
+    ///
+    /// # Arguments
+    /// * `fid` - The FID that follows `target_fid`
+    /// * `target_fid` - The FID to unfollow
+    /// * `signing_key` - An active signer of `fid`
+    ///
+    /// # Returns
+    /// * `Result<HubResponse>` - The hub response or an error
+    pub async fn unfollow(
+        &self,
+        fid: u64,
+        target_fid: u64,
+        signing_key: &SigningKey,
+    ) -> Result<HubResponse> {
+        let message = build_unfollow_message(fid, target_fid, signing_key)?;
+        self.submit_message(&message).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protobuf::Message as ProtobufMessage;
+
+    use super::*;
+    use crate::core::protocol::message::MessageData;
+    use crate::core::protocol::message::MessageType;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    #[test]
+    fn test_unfollow_reason() {
+        let now = 1000 * DAY;
+        assert_eq!(
+            unfollow_reason(None, false, now, 180),
+            Some(UnfollowReason::NeverCast)
+        );
+        assert_eq!(unfollow_reason(Some(now - DAY), false, now, 180), None);
+        assert_eq!(
+            unfollow_reason(Some(now - 200 * DAY), false, now, 180),
+            Some(UnfollowReason::Inactive {
+                last_cast: now - 200 * DAY
+            })
+        );
+        assert_eq!(
+            unfollow_reason(Some(now - DAY), true, now, 180),
+            Some(UnfollowReason::Spam)
+        );
+    }
+
+    #[test]
+    fn test_unfollow_message() {
+        let links = vec![
+            serde_json::json!({ "data": { "linkBody": { "type": "follow", "targetFid": 7 } } }),
+            serde_json::json!({ "data": { "linkBody": { "type": "follow" } } }),
+        ];
+        assert_eq!(followed_fids(&links), vec![7]);
+
+        let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+        let message = build_unfollow_message(42, 7, &signing_key).unwrap();
+        let data = MessageData::parse_from_bytes(message.get_data_bytes()).unwrap();
+        assert_eq!(data.get_field_type(), MessageType::MESSAGE_TYPE_LINK_REMOVE);
+        assert_eq!(data.get_link_body().get_target_fid(), 7);
+        assert_eq!(data.get_link_body().get_field_type(), "follow");
+    }
+}
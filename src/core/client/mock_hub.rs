@@ -0,0 +1,346 @@
+//! Hub HTTP API served from recorded fixtures
+//!
+//! [`MockHub`] answers `/v1/*` requests with canned responses stored one per
+//! file in a fixture directory, so the CLI and library can be exercised
+//! without a network. In recording mode every request is forwarded to a real
+//! hub and its response is written back as a fixture; replaying the same
+//! commands against the directory then gives identical output.
+//!
+//! Requests are matched on method, path and query string, with query
+//! parameters sorted so that their order does not matter. Request bodies are
+//! not part of the match.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use anyhow::Context;
+use anyhow::Result;
+use axum::body::Bytes;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::Method;
+use axum::http::StatusCode;
+use axum::http::Uri;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::Json;
+use axum::Router;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+use serde_json::Value;
+
+/// Longest readable prefix kept in fixture file names
+const MAX_FILE_STEM: usize = 80;
+
+/// A recorded hub response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    /// Request the response belongs to, as returned by [`fixture_key`]
+    pub request: String,
+    pub status: u16,
+    pub body: Value,
+}
+
+/// Matching key of a request: `GET /v1/userDataByFid?fid=3`
+///
+/// # Arguments
+/// * `method` - HTTP method
+/// * `path` - Request path
+/// * `params` - Query parameters; their order does not matter
+pub fn fixture_key(method: &str, path: &str, params: &BTreeMap<String, String>) -> String {
+    let query = params
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join("&");
+    if query.is_empty() {
+        format!("{method} {path}")
+    } else {
+        format!("{method} {path}?{query}")
+    }
+}
+
+/// File name a fixture is stored under
+///
+/// A readable form of the key, shortened if needed, plus a hash of the full
+/// key so that distinct requests never share a file.
+pub fn fixture_file_name(key: &str) -> String {
+    let readable: String = key
+        .trim_start_matches("GET ")
+        .trim_start_matches('/')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '=' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_FILE_STEM)
+        .collect();
+    let hash = blake3::hash(key.as_bytes()).to_hex();
+    format!("{readable}.{}.json", &hash[..8])
+}
+
+/// Where unmatched requests are sent
+struct Upstream {
+    client: reqwest::Client,
+    hub_url: String,
+}
+
+/// Fixture-backed hub HTTP API
+pub struct MockHub {
+    dir: PathBuf,
+    fixtures: RwLock<HashMap<String, Fixture>>,
+    upstream: Option<Upstream>,
+}
+
+impl MockHub {
+    /// Replay the fixtures in a directory
+    ///
+    /// # Arguments
+    /// * `dir` - Fixture directory; it may be missing or empty
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The mock hub, or an error if a fixture cannot be parsed
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut fixtures = HashMap::new();
+        if dir.is_dir() {
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                let content = std::fs::read_to_string(&path)?;
+                let fixture: Fixture = serde_json::from_str(&content)
+                    .with_context(|| format!("Invalid fixture {}", path.display()))?;
+                fixtures.insert(fixture.request.clone(), fixture);
+            }
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            fixtures: RwLock::new(fixtures),
+            upstream: None,
+        })
+    }
+
+    /// Serve fixtures built in memory, e.g. by tests
+    pub fn from_fixtures(fixtures: impl IntoIterator<Item = Fixture>) -> Self {
+        Self {
+            dir: PathBuf::new(),
+            fixtures: RwLock::new(
+                fixtures
+                    .into_iter()
+                    .map(|fixture| (fixture.request.clone(), fixture))
+                    .collect(),
+            ),
+            upstream: None,
+        }
+    }
+
+    /// Forward every request to a real hub and record its responses into `dir`
+    ///
+    /// # Arguments
+    /// * `dir` - Fixture directory; existing fixtures are overwritten when re-recorded
+    /// * `hub_url` - The hub to record from
+    pub fn recording(dir: &Path, hub_url: &str) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create fixture directory {}", dir.display()))?;
+        let mut hub = Self::load(dir)?;
+        hub.upstream = Some(Upstream {
+            client: crate::core::utils::http::client(),
+            hub_url: hub_url.trim_end_matches('/').to_string(),
+        });
+        Ok(hub)
+    }
+
+    /// Number of fixtures available
+    pub fn len(&self) -> usize {
+        self.fixtures.read().map(|f| f.len()).unwrap_or(0)
+    }
+
+    /// Whether no fixtures are available
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether requests are forwarded and recorded
+    pub fn is_recording(&self) -> bool {
+        self.upstream.is_some()
+    }
+
+    /// Serve the hub API on a local address
+    ///
+    /// # Arguments
+    /// * `addr` - Address to bind; use port 0 for any free port
+    ///
+    /// # Returns
+    /// * `Result<SocketAddr>` - The bound address; the server runs until the process exits
+    pub async fn serve(self, addr: SocketAddr) -> Result<SocketAddr> {
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to bind mock hub on {addr}"))?;
+        let local_addr = listener.local_addr()?;
+
+        let app = Router::new()
+            .fallback(handle_request)
+            .with_state(Arc::new(self));
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                eprintln!("❌ Mock hub stopped: {e}");
+            }
+        });
+
+        Ok(local_addr)
+    }
+
+    /// Forward a request upstream and store the response as a fixture
+    async fn record(
+        &self,
+        upstream: &Upstream,
+        key: String,
+        method: &Method,
+        uri: &Uri,
+        body: Bytes,
+    ) -> Result<Fixture> {
+        let path_and_query = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+        let url = format!("{}{}", upstream.hub_url, path_and_query);
+        // reqwest and axum use different `http` versions, so go through the name
+        let method = reqwest::Method::from_bytes(method.as_str().as_bytes())?;
+        let mut request = upstream.client.request(method, &url);
+        if !body.is_empty() {
+            request = request
+                .header("Content-Type", "application/octet-stream")
+                .body(body);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to forward {key} to {}", upstream.hub_url))?;
+        let status = response.status().as_u16();
+        let text = response.text().await?;
+        let body = serde_json::from_str(&text).unwrap_or(Value::String(text));
+
+        let fixture = Fixture {
+            request: key.clone(),
+            status,
+            body,
+        };
+        let path = self.dir.join(fixture_file_name(&key));
+        std::fs::write(&path, serde_json::to_string_pretty(&fixture)? + "\n")
+            .with_context(|| format!("Failed to write fixture {}", path.display()))?;
+        if let Ok(mut fixtures) = self.fixtures.write() {
+            fixtures.insert(key, fixture.clone());
+        }
+        Ok(fixture)
+    }
+}
+
+async fn handle_request(
+    State(hub): State<Arc<MockHub>>,
+    method: Method,
+    uri: Uri,
+    Query(params): Query<BTreeMap<String, String>>,
+    body: Bytes,
+) -> Response {
+    let key = fixture_key(method.as_str(), uri.path(), &params);
+
+    let fixture = match &hub.upstream {
+        Some(upstream) => match hub.record(upstream, key.clone(), &method, &uri, body).await {
+            Ok(fixture) => Some(fixture),
+            Err(e) => {
+                return (
+                    StatusCode::BAD_GATEWAY,
+                    Json(json!({ "errCode": "bad_gateway", "message": format!("{e:#}") })),
+                )
+                    .into_response()
+            }
+        },
+        None => hub.fixtures.read().ok().and_then(|f| f.get(&key).cloned()),
+    };
+
+    match fixture {
+        Some(fixture) => {
+            let status = StatusCode::from_u16(fixture.status).unwrap_or(StatusCode::OK);
+            (status, Json(fixture.body)).into_response()
+        }
+        None => {
+            eprintln!("⚠️  No fixture for {key}");
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "errCode": "not_found",
+                    "message": format!("No fixture recorded for {key}"),
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_key_ignores_parameter_order() {
+        let params: BTreeMap<String, String> = [("pageSize", "10"), ("fid", "3")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let key = fixture_key("GET", "/v1/castsByFid", &params);
+        assert_eq!(key, "GET /v1/castsByFid?fid=3&pageSize=10");
+        assert_eq!(
+            fixture_key("GET", "/v1/info", &BTreeMap::new()),
+            "GET /v1/info"
+        );
+
+        let name = fixture_file_name(&key);
+        assert!(name.starts_with("v1_castsByFid_fid=3_pageSize=10."));
+        assert_ne!(
+            name,
+            fixture_file_name("POST /v1/castsByFid?fid=3&pageSize=10")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replays_fixtures() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = Fixture {
+            request: "GET /v1/info".to_string(),
+            status: 200,
+            body: json!({ "version": "mock" }),
+        };
+        std::fs::write(
+            dir.path().join(fixture_file_name(&fixture.request)),
+            serde_json::to_string(&fixture).unwrap(),
+        )
+        .unwrap();
+
+        let hub = MockHub::load(dir.path()).unwrap();
+        assert_eq!(hub.len(), 1);
+        let addr = hub
+            .serve(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+
+        let info: Value = reqwest::get(format!("http://{addr}/v1/info"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(info["version"], "mock");
+
+        let missing = reqwest::get(format!("http://{addr}/v1/castsByFid?fid=1"))
+            .await
+            .unwrap();
+        assert_eq!(missing.status().as_u16(), 404);
+    }
+}
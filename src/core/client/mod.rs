@@ -2,6 +2,71 @@
 //!
 //! Provides high-level interface for interacting with Farcaster Hub
 
+pub mod adaptive_paging;
+pub mod auth_address;
+pub mod autocast;
+pub mod cast;
+pub mod cast_stats;
+pub mod channels;
+pub mod embeds;
+pub mod export;
+pub mod follower_count;
 pub mod hub_client;
+pub mod hub_pool;
+pub mod hub_propagation;
+pub mod links;
+#[cfg(feature = "api-server")]
+pub mod mock_hub;
+pub mod monitor;
+pub mod multi_hub;
+pub mod name_resolver;
+pub mod notifications;
+pub mod offline;
+pub mod profile;
+pub mod rate_limit;
+pub mod replay;
+pub mod stream;
+pub mod submit;
+pub mod sync_status;
+pub mod thread;
+pub mod tiers;
+pub mod time_window;
+pub mod verifications;
+pub mod webhook;
 
+pub use adaptive_paging::AdaptivePaging;
+pub use cast_stats::CastStats;
+pub use cast_stats::CastStatsCache;
+pub use channels::Channel;
+pub use channels::ChannelClient;
+pub use embeds::EmbedFetcher;
+pub use embeds::EmbedPreview;
+pub use export::ExportCheckpoint;
+pub use export::ExportKind;
+pub use follower_count::FollowerCount;
 pub use hub_client::FarcasterClient;
+pub use hub_client::ReadOnly;
+pub use hub_client::Signing;
+pub use hub_pool::HubEndpoint;
+pub use hub_pool::HubPool;
+pub use hub_propagation::ExpectedEvent;
+pub use hub_propagation::HubPropagation;
+#[cfg(feature = "api-server")]
+pub use mock_hub::MockHub;
+pub use monitor::FidMonitor;
+pub use multi_hub::MultiHubClient;
+pub use name_resolver::NameResolver;
+pub use notifications::Notification;
+pub use notifications::NotificationKind;
+pub use offline::OfflineSnapshot;
+pub use profile::ProfileChange;
+pub use sync_status::SyncStatus;
+pub use thread::CastThread;
+pub use thread::ThreadLimits;
+pub use thread::ThreadNode;
+pub use tiers::TierSubscription;
+pub use time_window::TimeWindow;
+pub use verifications::FidVerifications;
+pub use verifications::VerificationMapper;
+pub use verifications::VerificationsMap;
+pub use webhook::WebhookNotifier;
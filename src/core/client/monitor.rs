@@ -0,0 +1,315 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use anyhow::Result;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_client::ReadOnly;
+use crate::core::client::replay::message_hash;
+
+/// Number of recent follow links checked on every poll
+const FOLLOWER_WINDOW: u32 = 100;
+/// Number of recent mentions checked on every poll
+const MENTION_WINDOW: u32 = 50;
+/// Number of the FID's own recent casts whose recasts are tracked
+const RECAST_CAST_WINDOW: u32 = 10;
+
+/// Kind of activity detected for a monitored FID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonitorEventKind {
+    NewFollower,
+    Mention,
+    Recast,
+    SignerAdded,
+    SignerRemoved,
+}
+
+/// A single notification produced by [`FidMonitor`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorEvent {
+    /// The monitored FID
+    pub fid: u64,
+    /// What happened
+    pub kind: MonitorEventKind,
+    /// Unix timestamp (seconds) at which the change was detected
+    pub detected_at: i64,
+    /// Event specific payload (raw hub message or signer key)
+    pub data: serde_json::Value,
+}
+
+/// Polls the hub for changes affecting a FID and reports them as [`MonitorEvent`]s
+///
+/// The first call to [`FidMonitor::poll`] only records a baseline; later calls
+/// return whatever appeared (or, for signers, disappeared) since the previous poll.
+pub struct FidMonitor<'a, M = ReadOnly> {
+    client: &'a FarcasterClient<M>,
+    fid: u64,
+    initialized: bool,
+    followers: HashSet<u64>,
+    mentions: HashSet<String>,
+    recasts: HashSet<String>,
+    signers: HashSet<String>,
+}
+
+impl<'a, M> FidMonitor<'a, M> {
+    /// Create a new monitor for a FID
+    ///
+    /// # Arguments
+    /// * `client` - Hub client used for polling
+    /// * `fid` - The Farcaster ID to watch
+    pub fn new(client: &'a FarcasterClient<M>, fid: u64) -> Self {
+        Self {
+            client,
+            fid,
+            initialized: false,
+            followers: HashSet::new(),
+            mentions: HashSet::new(),
+            recasts: HashSet::new(),
+            signers: HashSet::new(),
+        }
+    }
+
+    /// Get the monitored FID
+    pub fn fid(&self) -> u64 {
+        self.fid
+    }
+
+    /// Poll the hub once and return the events detected since the last poll
+    ///
+    /// Everything is fetched before the monitor's state changes, so a poll
+    /// that fails part way leaves it untouched and the next poll reports the
+    /// same events.
+    ///
+    /// # Returns
+    /// * `Result<Vec<MonitorEvent>>` - New events (empty on the baseline poll)
+    pub async fn poll(&mut self) -> Result<Vec<MonitorEvent>> {
+        let followers = self
+            .client
+            .get_recent_followers(self.fid, FOLLOWER_WINDOW)
+            .await?;
+        let mentions = self
+            .client
+            .get_casts_by_mention(self.fid, MENTION_WINDOW)
+            .await?;
+        // Recasts of the FID's recent casts
+        let casts = self
+            .client
+            .get_casts_by_fid(self.fid, RECAST_CAST_WINDOW)
+            .await?;
+        let mut recasts = Vec::new();
+        for cast in casts {
+            let Some(hash) = message_hash(&cast) else {
+                continue;
+            };
+            recasts.extend(
+                self.client
+                    .get_reactions_by_cast(
+                        self.fid,
+                        &format!("0x{hash}"),
+                        "REACTION_TYPE_RECAST",
+                        0,
+                    )
+                    .await?,
+            );
+        }
+        let current_signers: HashSet<String> = self
+            .client
+            .get_signers(self.fid)
+            .await?
+            .into_iter()
+            .map(|s| s.key)
+            .collect();
+
+        let mut events = Vec::new();
+        let emit = self.initialized;
+
+        // New followers
+        let follower_fids: Vec<(u64, serde_json::Value)> = followers
+            .into_iter()
+            .filter_map(|m| message_fid(&m).map(|fid| (fid, m)))
+            .collect();
+        let new_followers = unseen(&self.followers, follower_fids.iter().map(|(fid, _)| *fid));
+        for (fid, message) in follower_fids {
+            if new_followers.contains(&fid) {
+                events.push(self.event(MonitorEventKind::NewFollower, message));
+            }
+        }
+
+        let new_mentions = self.collect_by_hash(
+            &self.mentions,
+            mentions,
+            MonitorEventKind::Mention,
+            &mut events,
+        );
+        let new_recasts = self.collect_by_hash(
+            &self.recasts,
+            recasts,
+            MonitorEventKind::Recast,
+            &mut events,
+        );
+
+        // Signer changes
+        for key in current_signers.difference(&self.signers) {
+            events.push(self.event(
+                MonitorEventKind::SignerAdded,
+                serde_json::json!({ "key": key }),
+            ));
+        }
+        for key in self.signers.difference(&current_signers) {
+            events.push(self.event(
+                MonitorEventKind::SignerRemoved,
+                serde_json::json!({ "key": key }),
+            ));
+        }
+        self.followers.extend(new_followers);
+        self.mentions.extend(new_mentions);
+        self.recasts.extend(new_recasts);
+        self.signers = current_signers;
+        self.initialized = true;
+        if emit {
+            Ok(events)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Add an event for each message whose hash is not in `seen`, returning
+    /// those hashes
+    fn collect_by_hash(
+        &self,
+        seen: &HashSet<String>,
+        messages: Vec<serde_json::Value>,
+        kind: MonitorEventKind,
+        events: &mut Vec<MonitorEvent>,
+    ) -> HashSet<String> {
+        let hashed: Vec<(String, serde_json::Value)> = messages
+            .into_iter()
+            .filter_map(|m| message_hash(&m).map(|h| (h, m)))
+            .collect();
+        let new_hashes = unseen(seen, hashed.iter().map(|(h, _)| h.clone()));
+        for (hash, message) in hashed {
+            if new_hashes.contains(&hash) {
+                events.push(self.event(kind, message));
+            }
+        }
+        new_hashes
+    }
+
+    fn event(&self, kind: MonitorEventKind, data: serde_json::Value) -> MonitorEvent {
+        MonitorEvent {
+            fid: self.fid,
+            kind,
+            detected_at: Utc::now().timestamp(),
+            data,
+        }
+    }
+}
+
+/// The items that are not in `seen`, which is left unchanged
+fn unseen<T, I>(seen: &HashSet<T>, items: I) -> HashSet<T>
+where
+    T: Eq + Hash,
+    I: IntoIterator<Item = T>,
+{
+    items
+        .into_iter()
+        .filter(|item| !seen.contains(item))
+        .collect()
+}
+
+fn message_fid(message: &serde_json::Value) -> Option<u64> {
+    message
+        .get("data")
+        .and_then(|d| d.get("fid"))
+        .and_then(|f| f.as_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+    use crate::core::client::mock_hub::Fixture;
+    use crate::core::client::MockHub;
+
+    fn hub(fixtures: &[(&str, u16, serde_json::Value)]) -> MockHub {
+        MockHub::from_fixtures(fixtures.iter().map(|(request, status, body)| Fixture {
+            request: request.to_string(),
+            status: *status,
+            body: body.clone(),
+        }))
+    }
+
+    async fn client(hub: MockHub) -> FarcasterClient {
+        let addr = hub
+            .serve(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+        FarcasterClient::read_only(format!("http://{addr}"))
+    }
+
+    #[test]
+    fn test_unseen_leaves_seen_unchanged() {
+        let seen: HashSet<u64> = [1, 2].into_iter().collect();
+        let new = unseen(&seen, vec![2, 3, 4, 3]);
+        assert_eq!(new, [3, 4].into_iter().collect());
+        assert_eq!(seen.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_poll_reports_events_on_the_next_poll() {
+        let followers =
+            "GET /v1/linksByTargetFid?link_type=follow&pageSize=100&reverse=true&target_fid=3";
+        let mentions = "GET /v1/castsByMention?fid=3&pageSize=50&reverse=true";
+        let casts = "GET /v1/castsByFid?fid=3&pageSize=10&reverse=true";
+        let signers = "GET /v1/onChainSignersByFid?fid=3";
+        let none = serde_json::json!({"messages": []});
+        let follow = serde_json::json!({"messages": [{"data": {"fid": 7}}]});
+        let quiet = client(hub(&[
+            (followers, 200, none.clone()),
+            (mentions, 200, none.clone()),
+            (casts, 200, none.clone()),
+            (signers, 200, serde_json::json!({"events": []})),
+        ]))
+        .await;
+        let failing = client(hub(&[
+            (followers, 200, follow.clone()),
+            (mentions, 400, serde_json::json!({"errCode": "bad_request"})),
+        ]))
+        .await;
+        let followed = client(hub(&[
+            (followers, 200, follow),
+            (mentions, 200, none.clone()),
+            (casts, 200, none),
+            (signers, 200, serde_json::json!({"events": []})),
+        ]))
+        .await;
+
+        let mut monitor = FidMonitor::new(&quiet, 3);
+        assert!(monitor.poll().await.unwrap().is_empty());
+        monitor.client = &failing;
+        assert!(monitor.poll().await.is_err());
+        monitor.client = &followed;
+        let events = monitor.poll().await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, MonitorEventKind::NewFollower);
+        assert!(monitor.poll().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_monitor_event_serialization() {
+        let event = MonitorEvent {
+            fid: 42,
+            kind: MonitorEventKind::NewFollower,
+            detected_at: 0,
+            data: serde_json::json!({}),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["kind"], "new_follower");
+        assert_eq!(json["fid"], 42);
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::task::JoinSet;
+
+use crate::core::client::hub_client::FarcasterClient;
+
+/// Message stores whose latest message hash is compared across hubs
+///
+/// Each entry maps a storage-limit store name to the endpoint returning its messages.
+const LATEST_HASH_QUERIES: &[(&str, &str)] = &[
+    ("CASTS", "/v1/castsByFid?fid={fid}&reverse=true"),
+    ("LINKS", "/v1/linksByFid?fid={fid}&reverse=true"),
+    (
+        "VERIFICATIONS",
+        "/v1/verificationsByFid?fid={fid}&reverse=true",
+    ),
+    ("USER_DATA", "/v1/userDataByFid?fid={fid}&reverse=true"),
+];
+
+/// State of a single message store on one hub
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreState {
+    /// Number of messages stored, as reported by storageLimitsByFid
+    pub used: Option<u64>,
+    /// Hash of the most recent message in the store
+    pub latest_hash: Option<String>,
+}
+
+/// Per-store view of a FID on one hub
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HubStoreSnapshot {
+    pub hub_url: String,
+    /// Store name (e.g. `CASTS`) to its state
+    pub stores: BTreeMap<String, StoreState>,
+    /// Set when the hub could not be queried at all
+    pub error: Option<String>,
+}
+
+/// A store whose state differs between hubs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreDivergence {
+    pub store: String,
+    /// Hub URL to that hub's state for the store
+    pub states: Vec<(String, StoreState)>,
+}
+
+impl<M> FarcasterClient<M> {
+    /// Get message counts and latest message hashes per store for a FID
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `Result<HubStoreSnapshot>` - Store states as seen by this hub or an error
+    pub async fn get_store_snapshot(&self, fid: u64) -> Result<HubStoreSnapshot> {
+        let mut stores: BTreeMap<String, StoreState> = BTreeMap::new();
+
+        let limits = self.get_storage_limits(fid).await?;
+        if let Some(limits) = limits.get("limits").and_then(|l| l.as_array()) {
+            for limit in limits {
+                if let Some(name) = limit.get("name").and_then(|n| n.as_str()) {
+                    stores.entry(name.to_string()).or_default().used =
+                        limit.get("used").and_then(|u| u.as_u64());
+                }
+            }
+        }
+
+        for (store, query) in LATEST_HASH_QUERIES {
+            let query = query.replace("{fid}", &fid.to_string());
+            let latest = self.get_messages_paginated(&query, 1, store).await?;
+            stores.entry(store.to_string()).or_default().latest_hash = latest
+                .first()
+                .and_then(|m| m.get("hash"))
+                .and_then(|h| h.as_str())
+                .map(|h| h.to_string());
+        }
+
+        Ok(HubStoreSnapshot {
+            hub_url: self.hub_url().to_string(),
+            stores,
+            error: None,
+        })
+    }
+}
+
+/// Queries several hubs concurrently
+pub struct MultiHubClient {
+    clients: Vec<Arc<FarcasterClient>>,
+}
+
+impl MultiHubClient {
+    /// Create a read-only client for each hub URL
+    ///
+    /// # Arguments
+    /// * `hub_urls` - Hub URLs to query
+    pub fn new(hub_urls: Vec<String>) -> Self {
+        Self {
+            clients: hub_urls
+                .into_iter()
+                .map(|url| Arc::new(FarcasterClient::read_only(url)))
+                .collect(),
+        }
+    }
+
+    /// Get the hub URLs in query order
+    pub fn hub_urls(&self) -> Vec<&str> {
+        self.clients.iter().map(|c| c.hub_url()).collect()
+    }
+
+    /// Fetch store snapshots for a FID from every hub at once
+    ///
+    /// Hubs that fail are reported with `error` set rather than failing the whole call.
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `Vec<HubStoreSnapshot>` - One snapshot per hub, in the order hubs were given
+    pub async fn store_snapshots(&self, fid: u64) -> Vec<HubStoreSnapshot> {
+        let mut tasks = JoinSet::new();
+        for (index, client) in self.clients.iter().enumerate() {
+            let client = Arc::clone(client);
+            tasks.spawn(async move {
+                let snapshot =
+                    client
+                        .get_store_snapshot(fid)
+                        .await
+                        .unwrap_or_else(|e| HubStoreSnapshot {
+                            hub_url: client.hub_url().to_string(),
+                            stores: BTreeMap::new(),
+                            error: Some(e.to_string()),
+                        });
+                (index, snapshot)
+            });
+        }
+
+        let mut results: Vec<(usize, HubStoreSnapshot)> = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok(result) = joined {
+                results.push(result);
+            }
+        }
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, snapshot)| snapshot).collect()
+    }
+}
+
+/// Find stores whose count or latest hash differs between reachable hubs
+pub fn find_divergences(snapshots: &[HubStoreSnapshot]) -> Vec<StoreDivergence> {
+    let reachable: Vec<&HubStoreSnapshot> =
+        snapshots.iter().filter(|s| s.error.is_none()).collect();
+    let store_names: BTreeSet<&String> = reachable.iter().flat_map(|s| s.stores.keys()).collect();
+
+    store_names
+        .into_iter()
+        .filter_map(|store| {
+            let states: Vec<(String, StoreState)> = reachable
+                .iter()
+                .map(|s| {
+                    (
+                        s.hub_url.clone(),
+                        s.stores.get(store).cloned().unwrap_or_default(),
+                    )
+                })
+                .collect();
+            let diverged = states.windows(2).any(|w| w[0].1 != w[1].1);
+            diverged.then(|| StoreDivergence {
+                store: store.clone(),
+                states,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(hub: &str, casts: u64, hash: &str) -> HubStoreSnapshot {
+        let mut stores = BTreeMap::new();
+        stores.insert(
+            "CASTS".to_string(),
+            StoreState {
+                used: Some(casts),
+                latest_hash: Some(hash.to_string()),
+            },
+        );
+        HubStoreSnapshot {
+            hub_url: hub.to_string(),
+            stores,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_find_divergences() {
+        let in_sync = vec![snapshot("a", 10, "0x1"), snapshot("b", 10, "0x1")];
+        assert!(find_divergences(&in_sync).is_empty());
+
+        let diverged = vec![snapshot("a", 10, "0x1"), snapshot("b", 9, "0x0")];
+        let divergences = find_divergences(&diverged);
+        assert_eq!(divergences.len(), 1);
+        assert_eq!(divergences[0].store, "CASTS");
+    }
+
+    #[test]
+    fn test_unreachable_hubs_are_ignored() {
+        let mut down = snapshot("b", 0, "");
+        down.error = Some("timeout".to_string());
+        assert!(find_divergences(&[snapshot("a", 10, "0x1"), down]).is_empty());
+    }
+}
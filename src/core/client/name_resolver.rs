@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use ethers::providers::Middleware;
+use ethers::types::Address;
+
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::protocol::cast_text::find_mentions;
+use crate::core::utils::ttl_cache::TtlCache;
+
+/// How long a resolved name is reused before asking the hub again
+const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Resolves fnames and ENS names to FIDs, with a file cache
+///
+/// Names are looked up with the hub's `userNameProofByName` endpoint. ENS
+/// names without a username proof fall back to resolving the name on L1 and
+/// looking up the FID whose custody address it points to.
+pub struct NameResolver {
+    hub_client: FarcasterClient,
+    eth_rpc_url: String,
+    cache: TtlCache<String, u64>,
+}
+
+/// Whether an argument looks like a username rather than a FID
+///
+/// fnames are lowercase letters, digits and hyphens; ENS names add dots.
+pub fn looks_like_name(value: &str) -> bool {
+    let value = value.trim_start_matches('@');
+    !value.is_empty()
+        && value.parse::<u64>().is_err()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '_')
+        && value.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+impl NameResolver {
+    /// Create a resolver with an in-memory cache only
+    ///
+    /// # Arguments
+    /// * `hub_url` - The Farcaster Hub URL
+    /// * `eth_rpc_url` - Ethereum mainnet RPC URL for ENS resolution
+    pub fn new(hub_url: String, eth_rpc_url: String) -> Self {
+        Self {
+            hub_client: FarcasterClient::read_only(hub_url),
+            eth_rpc_url,
+            cache: TtlCache::in_memory(CACHE_TTL),
+        }
+    }
+
+    /// Create a resolver whose cache is loaded from and saved to a JSON file
+    pub fn with_cache_file(hub_url: String, eth_rpc_url: String, path: PathBuf) -> Self {
+        Self {
+            hub_client: FarcasterClient::read_only(hub_url),
+            eth_rpc_url,
+            cache: TtlCache::with_file(CACHE_TTL, path),
+        }
+    }
+
+    /// Default cache file in the data directory
+    pub fn default_cache_path() -> PathBuf {
+        crate::core::utils::data_dir::data_dir().join("name_cache.json")
+    }
+
+    /// Resolve a username to a FID
+    ///
+    /// # Arguments
+    /// * `name` - An fname (`dwr`) or ENS name (`vitalik.eth`), with or without `@`
+    ///
+    /// # Returns
+    /// * `Result<u64>` - The FID holding the name, or an error if none does
+    pub async fn resolve(&mut self, name: &str) -> Result<u64> {
+        let name = name.trim_start_matches('@').to_lowercase();
+        if let Some(fid) = self.cache.get(&name) {
+            return Ok(fid);
+        }
+
+        let fid = match self.hub_client.get_username_proof_by_name(&name).await? {
+            Some(proof) => proof.fid,
+            None if name.contains('.') => self
+                .resolve_ens_custody(&name)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("No FID found for ENS name '{name}'"))?,
+            None => anyhow::bail!("No FID holds the username '{name}'"),
+        };
+        self.cache.insert(name, fid);
+        Ok(fid)
+    }
+
+    /// Resolve the `@username` tokens of cast text
+    ///
+    /// # Arguments
+    /// * `text` - Cast text with `@username` tokens
+    ///
+    /// # Returns
+    /// * `(HashMap<String, u64>, Vec<(String, String)>)` - FIDs of the resolved
+    ///   names, and the names that could not be resolved with the reason
+    pub async fn resolve_mentions(
+        &mut self,
+        text: &str,
+    ) -> (HashMap<String, u64>, Vec<(String, String)>) {
+        let mut fids = HashMap::new();
+        let mut unresolved = Vec::new();
+        for token in find_mentions(text) {
+            if fids.contains_key(&token.name)
+                || unresolved.iter().any(|(name, _)| *name == token.name)
+            {
+                continue;
+            }
+            match self.resolve(&token.name).await {
+                Ok(fid) => {
+                    fids.insert(token.name, fid);
+                }
+                Err(e) => unresolved.push((token.name, e.to_string())),
+            }
+        }
+        (fids, unresolved)
+    }
+
+    /// FID whose custody address an ENS name resolves to
+    async fn resolve_ens_custody(&self, name: &str) -> Result<Option<u64>> {
+        let provider = crate::core::utils::http::provider(self.eth_rpc_url.as_str())
+            .context("Invalid Ethereum RPC URL")?;
+        let address = match provider.resolve_name(name).await {
+            Ok(address) => address,
+            Err(_) => return Ok(None),
+        };
+        custody_fid(address).await
+    }
+
+    /// Write the cache back to its file, dropping expired entries
+    pub fn save(&self) -> Result<()> {
+        self.cache.save()
+    }
+}
+
+/// FID whose custody address is `address`
+#[cfg(feature = "contracts")]
+async fn custody_fid(address: Address) -> Result<Option<u64>> {
+    let contract_client = crate::farcaster::contracts::FarcasterContractClient::new(
+        crate::consts::get_config().eth_op_rpc_url().to_string(),
+        crate::farcaster::contracts::types::ContractAddresses::default(),
+    )?;
+    contract_client.address_has_fid(address).await
+}
+
+/// Custody lookups need the IdRegistry client, so only username proofs are used
+#[cfg(not(feature = "contracts"))]
+async fn custody_fid(_address: Address) -> Result<Option<u64>> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_name() {
+        assert!(looks_like_name("dwr"));
+        assert!(looks_like_name("vitalik.eth"));
+        assert!(looks_like_name("@alice-b"));
+        assert!(!looks_like_name("12345"));
+        assert!(!looks_like_name("hello world"));
+        assert!(!looks_like_name(""));
+    }
+
+    #[tokio::test]
+    async fn test_cached_names_skip_the_hub() {
+        let mut resolver = NameResolver::new(
+            "http://127.0.0.1:9".to_string(),
+            "http://127.0.0.1:9".to_string(),
+        );
+        resolver.cache.insert("dwr".to_string(), 3);
+        assert_eq!(resolver.resolve("@DWR").await.unwrap(), 3);
+    }
+}
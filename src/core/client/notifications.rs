@@ -0,0 +1,211 @@
+use std::cmp::Reverse;
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::client::hub_client::FarcasterClient;
+
+/// Kind of notification entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// Someone mentioned the FID in a cast
+    Mention,
+    /// Someone liked one of the FID's casts
+    Like,
+    /// Someone recasted one of the FID's casts
+    Recast,
+}
+
+/// A single "who talked to me" entry built from hub messages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    /// FID of the user who mentioned or reacted
+    pub actor_fid: u64,
+    /// Farcaster timestamp (seconds since the Farcaster epoch)
+    pub timestamp: u64,
+    /// Hash of the mention cast or reaction message
+    pub hash: String,
+    /// Hash of the FID's cast that was reacted to (reactions only)
+    pub target_hash: Option<String>,
+    /// Cast text (mentions only)
+    pub text: Option<String>,
+}
+
+/// One page of notifications, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationPage {
+    pub items: Vec<Notification>,
+    /// 1-based page number
+    pub page: usize,
+    pub page_size: usize,
+    /// Whether more entries are available on the next page
+    pub has_more: bool,
+}
+
+impl Notification {
+    /// Build a mention notification from a castsByMention message
+    pub fn from_mention(message: &serde_json::Value) -> Option<Self> {
+        let data = message.get("data")?;
+        Some(Self {
+            kind: NotificationKind::Mention,
+            actor_fid: data.get("fid")?.as_u64()?,
+            timestamp: data.get("timestamp").and_then(|t| t.as_u64()).unwrap_or(0),
+            hash: message.get("hash")?.as_str()?.to_string(),
+            target_hash: None,
+            text: data
+                .get("castAddBody")
+                .and_then(|b| b.get("text"))
+                .and_then(|t| t.as_str())
+                .map(|t| t.to_string()),
+        })
+    }
+
+    /// Build a like/recast notification from a reactionsByCast message
+    pub fn from_reaction(message: &serde_json::Value) -> Option<Self> {
+        let data = message.get("data")?;
+        let body = data.get("reactionBody")?;
+        let kind = match body.get("type")?.as_str()? {
+            "REACTION_TYPE_LIKE" => NotificationKind::Like,
+            "REACTION_TYPE_RECAST" => NotificationKind::Recast,
+            _ => return None,
+        };
+        Some(Self {
+            kind,
+            actor_fid: data.get("fid")?.as_u64()?,
+            timestamp: data.get("timestamp").and_then(|t| t.as_u64()).unwrap_or(0),
+            hash: message.get("hash")?.as_str()?.to_string(),
+            target_hash: body
+                .get("targetCastId")
+                .and_then(|c| c.get("hash"))
+                .and_then(|h| h.as_str())
+                .map(|h| h.to_string()),
+            text: None,
+        })
+    }
+}
+
+impl<M> FarcasterClient<M> {
+    /// Get mentions of a FID and reactions to its recent casts as a notification feed
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `page` - 1-based page number
+    /// * `page_size` - Number of entries per page
+    /// * `casts_window` - How many of the FID's most recent casts to collect reactions for
+    ///
+    /// # Returns
+    /// * `Result<NotificationPage>` - The requested page, newest first, or an error
+    pub async fn get_notifications(
+        &self,
+        fid: u64,
+        page: usize,
+        page_size: usize,
+        casts_window: u32,
+    ) -> Result<NotificationPage> {
+        let page = page.max(1);
+        let page_size = page_size.max(1);
+        // Fetch one entry past the requested page so we know whether another page exists
+        let wanted = page
+            .checked_mul(page_size)
+            .and_then(|n| n.checked_add(1))
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Page {page} of {page_size} notifications is out of range")
+            })?;
+
+        let mut items: Vec<Notification> = self
+            .get_casts_by_mention(fid, wanted)
+            .await?
+            .iter()
+            .filter_map(Notification::from_mention)
+            .collect();
+
+        for cast in self.get_casts_by_fid(fid, casts_window).await? {
+            let Some(hash) = cast.get("hash").and_then(|h| h.as_str()) else {
+                continue;
+            };
+            for reaction_type in ["REACTION_TYPE_LIKE", "REACTION_TYPE_RECAST"] {
+                let reactions = self
+                    .get_reactions_by_cast(fid, hash, reaction_type, wanted)
+                    .await?;
+                items.extend(reactions.iter().filter_map(Notification::from_reaction));
+            }
+        }
+
+        Ok(paginate(items, page, page_size))
+    }
+}
+
+/// Sort notifications newest first and cut out the requested page
+fn paginate(mut items: Vec<Notification>, page: usize, page_size: usize) -> NotificationPage {
+    items.sort_by_key(|n| Reverse(n.timestamp));
+    let start = (page - 1) * page_size;
+    let has_more = items.len() > start + page_size;
+    let items = items.into_iter().skip(start).take(page_size).collect();
+
+    NotificationPage {
+        items,
+        page,
+        page_size,
+        has_more,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reaction_notification() {
+        let message = serde_json::json!({
+            "hash": "0xabc",
+            "data": {
+                "fid": 7,
+                "timestamp": 100,
+                "reactionBody": {
+                    "type": "REACTION_TYPE_RECAST",
+                    "targetCastId": { "fid": 1, "hash": "0xdef" }
+                }
+            }
+        });
+        let notification = Notification::from_reaction(&message).unwrap();
+        assert_eq!(notification.kind, NotificationKind::Recast);
+        assert_eq!(notification.actor_fid, 7);
+        assert_eq!(notification.target_hash.as_deref(), Some("0xdef"));
+    }
+
+    #[test]
+    fn test_paginate_orders_newest_first() {
+        let items = (0..5)
+            .map(|i| Notification {
+                kind: NotificationKind::Mention,
+                actor_fid: i,
+                timestamp: i,
+                hash: format!("0x{i}"),
+                target_hash: None,
+                text: None,
+            })
+            .collect();
+        let page = paginate(items, 2, 2);
+        let fids: Vec<u64> = page.items.iter().map(|n| n.actor_fid).collect();
+        assert_eq!(fids, vec![2, 1]);
+        assert!(page.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_out_of_range_page_is_an_error() {
+        // Rejected before any request is made, so no hub is needed
+        let client = FarcasterClient::read_only("http://127.0.0.1:9".to_string());
+        assert!(client
+            .get_notifications(3, usize::MAX, 20, 10)
+            .await
+            .is_err());
+        assert!(client
+            .get_notifications(3, u32::MAX as usize, 2, 10)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,734 @@
+//! Offline hub backed by a local snapshot export
+//!
+//! [`OfflineSnapshot`] loads exported hub messages from disk and answers the
+//! subset of the hub HTTP API used by the read-only `hub` commands. Serving it
+//! on a loopback port lets every existing code path run unchanged against a
+//! downloaded snapshot instead of a live hub.
+//!
+//! Supported export formats:
+//! * `.json` - a hub HTTP response (`{"messages": [...]}`) or a plain array of messages
+//! * `.jsonl` / `.ndjson` - one hub JSON message per line
+//! * `.pb` / `.bin` - a stream of length-delimited protobuf `Message`s
+//!
+//! Raw RocksDB directories are not read directly; export them to one of the
+//! formats above first.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use base64::engine::general_purpose;
+use base64::Engine as _;
+use serde_json::json;
+use serde_json::Value;
+
+use crate::core::protocol::message::CastAddBody_oneof_parent;
+use crate::core::protocol::message::CastId;
+use crate::core::protocol::message::Embed_oneof_embed;
+use crate::core::protocol::message::LinkBody_oneof_target;
+use crate::core::protocol::message::Message;
+use crate::core::protocol::message::MessageData;
+use crate::core::protocol::message::MessageData_oneof_body;
+use crate::core::protocol::message::ReactionBody_oneof_target;
+
+/// Page size used when a request does not specify one
+const DEFAULT_PAGE_SIZE: usize = 1000;
+
+/// Message indexes kept by the snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Index {
+    Casts,
+    Mentions,
+    Reactions,
+    Links,
+    LinksByTarget,
+    UserData,
+    Verifications,
+}
+
+/// In-memory view of a hub snapshot export
+#[derive(Debug, Default)]
+pub struct OfflineSnapshot {
+    messages: Vec<Value>,
+    by_fid: HashMap<(Index, u64), Vec<usize>>,
+    reactions_by_cast: HashMap<String, Vec<usize>>,
+    casts_by_hash: HashMap<String, usize>,
+    casts_by_parent: HashMap<String, Vec<usize>>,
+}
+
+impl OfflineSnapshot {
+    /// Load a snapshot export file or a directory of export files
+    ///
+    /// # Arguments
+    /// * `path` - Export file, or directory scanned (non-recursively) for export files
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The indexed snapshot or an error
+    pub fn load(path: &Path) -> Result<Self> {
+        let files: Vec<PathBuf> = if path.is_dir() {
+            if path.join("CURRENT").exists() {
+                anyhow::bail!(
+                    "{} looks like a raw RocksDB directory; export it to protobuf or JSON messages first",
+                    path.display()
+                );
+            }
+            let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+                .with_context(|| format!("Failed to read snapshot directory {}", path.display()))?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.is_file() && export_format(p).is_some())
+                .collect();
+            files.sort();
+            files
+        } else {
+            if export_format(path).is_none() {
+                anyhow::bail!(
+                    "Unsupported snapshot file {} (expected .json, .jsonl, .ndjson, .pb or .bin)",
+                    path.display()
+                );
+            }
+            vec![path.to_path_buf()]
+        };
+
+        let mut messages = Vec::new();
+        for file in &files {
+            messages.extend(read_export_file(file)?);
+        }
+        if messages.is_empty() {
+            anyhow::bail!("No messages found in snapshot {}", path.display());
+        }
+
+        Ok(Self::from_messages(messages))
+    }
+
+    /// Build a snapshot from hub JSON messages, applying removes
+    pub fn from_messages(messages: Vec<Value>) -> Self {
+        let mut seen = HashSet::new();
+        let messages: Vec<Value> = messages
+            .into_iter()
+            .filter(|m| match m.get("hash").and_then(|h| h.as_str()) {
+                Some(hash) => seen.insert(hash.to_lowercase()),
+                None => true,
+            })
+            .collect();
+
+        let mut snapshot = Self {
+            messages: resolve_conflicts(messages),
+            ..Default::default()
+        };
+        snapshot.messages.sort_by_key(timestamp);
+
+        for (position, message) in snapshot.messages.iter().enumerate() {
+            let Some(data) = message.get("data") else {
+                continue;
+            };
+            let fid = data.get("fid").and_then(|f| f.as_u64()).unwrap_or(0);
+            let mut add = |index: Index, key: u64| {
+                snapshot
+                    .by_fid
+                    .entry((index, key))
+                    .or_default()
+                    .push(position)
+            };
+
+            match data.get("type").and_then(|t| t.as_str()).unwrap_or("") {
+                "MESSAGE_TYPE_CAST_ADD" => {
+                    add(Index::Casts, fid);
+                    let mentions = data
+                        .pointer("/castAddBody/mentions")
+                        .and_then(|m| m.as_array())
+                        .map(|m| m.iter().filter_map(|f| f.as_u64()).collect::<HashSet<_>>())
+                        .unwrap_or_default();
+                    for mentioned in mentions {
+                        add(Index::Mentions, mentioned);
+                    }
+                    if let Some(hash) = message.get("hash").and_then(|h| h.as_str()) {
+                        snapshot.casts_by_hash.insert(hash.to_lowercase(), position);
+                    }
+                    if let Some(parent) = data
+                        .pointer("/castAddBody/parentCastId/hash")
+                        .and_then(|h| h.as_str())
+                    {
+                        snapshot
+                            .casts_by_parent
+                            .entry(parent.to_lowercase())
+                            .or_default()
+                            .push(position);
+                    }
+                }
+                "MESSAGE_TYPE_REACTION_ADD" => {
+                    add(Index::Reactions, fid);
+                    if let Some(hash) = data
+                        .pointer("/reactionBody/targetCastId/hash")
+                        .and_then(|h| h.as_str())
+                    {
+                        snapshot
+                            .reactions_by_cast
+                            .entry(hash.to_lowercase())
+                            .or_default()
+                            .push(position);
+                    }
+                }
+                "MESSAGE_TYPE_LINK_ADD" => {
+                    add(Index::Links, fid);
+                    if let Some(target) =
+                        data.pointer("/linkBody/targetFid").and_then(|t| t.as_u64())
+                    {
+                        add(Index::LinksByTarget, target);
+                    }
+                }
+                "MESSAGE_TYPE_USER_DATA_ADD" => add(Index::UserData, fid),
+                "MESSAGE_TYPE_VERIFICATION_ADD_ETH_ADDRESS" => add(Index::Verifications, fid),
+                _ => {}
+            }
+        }
+
+        snapshot
+    }
+
+    /// Number of live messages in the snapshot
+    pub fn message_count(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Number of distinct FIDs that authored messages
+    pub fn fid_count(&self) -> usize {
+        self.messages
+            .iter()
+            .filter_map(|m| m.pointer("/data/fid").and_then(|f| f.as_u64()))
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Answer a hub HTTP API request
+    ///
+    /// # Arguments
+    /// * `path` - Request path, e.g. `/v1/castsByFid`
+    /// * `params` - Query string parameters
+    ///
+    /// # Returns
+    /// * `Option<Value>` - The hub-shaped JSON response, or None if the endpoint or item is unknown
+    pub fn query(&self, path: &str, params: &HashMap<String, String>) -> Option<Value> {
+        let number = |name: &str| params.get(name).and_then(|v| v.parse::<u64>().ok());
+        let param = |name: &str| params.get(name).map(|v| v.as_str());
+
+        let positions: Vec<usize> = match path {
+            "/v1/info" => {
+                return Some(json!({
+                    "version": "offline-snapshot",
+                    "isSyncing": false,
+                    "dbStats": {
+                        "numMessages": self.message_count(),
+                        "numFidRegistrations": self.fid_count(),
+                    },
+                    "shardInfos": [],
+                }))
+            }
+            "/v1/castById" => {
+                let hash = param("hash")?.to_lowercase();
+                return self
+                    .casts_by_hash
+                    .get(&hash)
+                    .map(|&p| self.messages[p].clone());
+            }
+            "/v1/onChainSignersByFid" | "/v1/onChainEventsByFid" => {
+                // On-chain events are not part of message exports
+                return Some(json!({ "events": [], "nextPageToken": "" }));
+            }
+            "/v1/castsByFid" => self.indexed(Index::Casts, number("fid")?),
+            "/v1/castsByMention" => self.indexed(Index::Mentions, number("fid")?),
+            "/v1/castsByParent" => {
+                let hash = param("hash")?.to_lowercase();
+                self.casts_by_parent.get(&hash).cloned().unwrap_or_default()
+            }
+            "/v1/reactionsByFid" => self.filtered(
+                self.indexed(Index::Reactions, number("fid")?),
+                "/data/reactionBody/type",
+                param("reaction_type"),
+            ),
+            "/v1/reactionsByCast" => {
+                let hash = param("target_hash")?.to_lowercase();
+                let reactions = self
+                    .reactions_by_cast
+                    .get(&hash)
+                    .cloned()
+                    .unwrap_or_default();
+                self.filtered(reactions, "/data/reactionBody/type", param("reaction_type"))
+            }
+            "/v1/linksByFid" => self.filtered(
+                self.indexed(Index::Links, number("fid")?),
+                "/data/linkBody/type",
+                param("link_type"),
+            ),
+            "/v1/linksByTargetFid" => self.filtered(
+                self.indexed(Index::LinksByTarget, number("target_fid")?),
+                "/data/linkBody/type",
+                param("link_type"),
+            ),
+            "/v1/userDataByFid" => {
+                let user_data = self.indexed(Index::UserData, number("fid")?);
+                if let Some(data_type) = param("user_data_type") {
+                    let filtered =
+                        self.filtered(user_data, "/data/userDataBody/type", Some(data_type));
+                    return filtered.last().map(|&p| self.messages[p].clone());
+                }
+                user_data
+            }
+            "/v1/verificationsByFid" => self.indexed(Index::Verifications, number("fid")?),
+            _ => return None,
+        };
+
+        Some(self.page(positions, params))
+    }
+
+    fn indexed(&self, index: Index, fid: u64) -> Vec<usize> {
+        self.by_fid.get(&(index, fid)).cloned().unwrap_or_default()
+    }
+
+    fn filtered(&self, positions: Vec<usize>, pointer: &str, wanted: Option<&str>) -> Vec<usize> {
+        let Some(wanted) = wanted else {
+            return positions;
+        };
+        positions
+            .into_iter()
+            .filter(|&p| {
+                self.messages[p]
+                    .pointer(pointer)
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|v| v.eq_ignore_ascii_case(wanted))
+            })
+            .collect()
+    }
+
+    /// Apply `reverse`, `pageSize` and `pageToken` (an offset) to oldest-first positions
+    fn page(&self, mut positions: Vec<usize>, params: &HashMap<String, String>) -> Value {
+        if params
+            .get("reverse")
+            .is_some_and(|r| r == "true" || r == "1")
+        {
+            positions.reverse();
+        }
+        let page_size = params
+            .get("pageSize")
+            .and_then(|s| s.parse::<usize>().ok())
+            .filter(|&s| s > 0)
+            .unwrap_or(DEFAULT_PAGE_SIZE);
+        let offset = params
+            .get("pageToken")
+            .and_then(|t| t.parse::<usize>().ok())
+            .unwrap_or(0);
+
+        let end = (offset + page_size).min(positions.len());
+        let messages: Vec<Value> = positions
+            .get(offset..end)
+            .unwrap_or_default()
+            .iter()
+            .map(|&p| self.messages[p].clone())
+            .collect();
+        let next_page_token = if end < positions.len() {
+            end.to_string()
+        } else {
+            String::new()
+        };
+
+        json!({ "messages": messages, "nextPageToken": next_page_token })
+    }
+}
+
+/// Serving a snapshot over HTTP, which needs the axum server stack
+#[cfg(feature = "api-server")]
+mod server {
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+
+    use anyhow::Context;
+    use anyhow::Result;
+    use axum::extract::Query;
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::http::Uri;
+    use axum::response::IntoResponse;
+    use axum::response::Response;
+    use axum::Json;
+    use axum::Router;
+    use serde_json::json;
+
+    use super::OfflineSnapshot;
+
+    impl OfflineSnapshot {
+        /// Serve the snapshot as a hub HTTP API on a local address
+        ///
+        /// # Arguments
+        /// * `addr` - Address to bind; use port 0 for any free port
+        ///
+        /// # Returns
+        /// * `Result<SocketAddr>` - The bound address; the server runs until the process exits
+        pub async fn serve(self, addr: SocketAddr) -> Result<SocketAddr> {
+            let listener = tokio::net::TcpListener::bind(addr)
+                .await
+                .with_context(|| format!("Failed to bind offline hub on {addr}"))?;
+            let local_addr = listener.local_addr()?;
+
+            let app = Router::new()
+                .fallback(handle_request)
+                .with_state(Arc::new(self));
+            tokio::spawn(async move {
+                if let Err(e) = axum::serve(listener, app).await {
+                    eprintln!("❌ Offline hub stopped: {e}");
+                }
+            });
+
+            Ok(local_addr)
+        }
+    }
+
+    async fn handle_request(
+        State(snapshot): State<Arc<OfflineSnapshot>>,
+        uri: Uri,
+        Query(params): Query<HashMap<String, String>>,
+    ) -> Response {
+        match snapshot.query(uri.path(), &params) {
+            Some(body) => Json(body).into_response(),
+            None => (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "errCode": "not_found",
+                    "message": format!("{} is not available in the offline snapshot", uri.path()),
+                })),
+            )
+                .into_response(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Json,
+    JsonLines,
+    Protobuf,
+}
+
+fn export_format(path: &Path) -> Option<ExportFormat> {
+    match path.extension()?.to_str()?.to_lowercase().as_str() {
+        "json" => Some(ExportFormat::Json),
+        "jsonl" | "ndjson" => Some(ExportFormat::JsonLines),
+        "pb" | "bin" => Some(ExportFormat::Protobuf),
+        _ => None,
+    }
+}
+
+fn read_export_file(path: &Path) -> Result<Vec<Value>> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let context = || format!("Failed to parse snapshot file {}", path.display());
+
+    match export_format(path) {
+        Some(ExportFormat::Json) => {
+            let value: Value = serde_json::from_slice(&bytes).with_context(context)?;
+            Ok(match value {
+                Value::Array(messages) => messages,
+                Value::Object(mut object) => match object.remove("messages") {
+                    Some(Value::Array(messages)) => messages,
+                    _ => vec![Value::Object(object)],
+                },
+                _ => Vec::new(),
+            })
+        }
+        Some(ExportFormat::JsonLines) => std::str::from_utf8(&bytes)
+            .with_context(context)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).with_context(context))
+            .collect(),
+        Some(ExportFormat::Protobuf) => {
+            let mut input = protobuf::CodedInputStream::from_bytes(&bytes);
+            let mut messages = Vec::new();
+            while !input.eof().with_context(context)? {
+                let message: Message = input.read_message().with_context(context)?;
+                messages.push(message_to_json(&message)?);
+            }
+            Ok(messages)
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Drop removed casts and keep only the latest add per reaction, link, verification and user data slot
+fn resolve_conflicts(messages: Vec<Value>) -> Vec<Value> {
+    let removed_casts: HashSet<String> = messages
+        .iter()
+        .filter(|m| message_type(m) == "MESSAGE_TYPE_CAST_REMOVE")
+        .filter_map(|m| m.pointer("/data/castRemoveBody/targetHash")?.as_str())
+        .map(|h| h.to_lowercase())
+        .collect();
+
+    let mut latest: HashMap<String, Value> = HashMap::new();
+    let mut others = Vec::new();
+    for message in messages {
+        match conflict_key(&message) {
+            Some(key) => {
+                let newer = latest
+                    .get(&key)
+                    .is_none_or(|current| timestamp(&message) >= timestamp(current));
+                if newer {
+                    latest.insert(key, message);
+                }
+            }
+            None => others.push(message),
+        }
+    }
+
+    others
+        .into_iter()
+        .filter(|m| match message_type(m) {
+            "MESSAGE_TYPE_CAST_REMOVE" => false,
+            "MESSAGE_TYPE_CAST_ADD" => m
+                .get("hash")
+                .and_then(|h| h.as_str())
+                .is_none_or(|h| !removed_casts.contains(&h.to_lowercase())),
+            _ => true,
+        })
+        .chain(latest.into_values().filter(|m| {
+            !matches!(
+                message_type(m),
+                "MESSAGE_TYPE_REACTION_REMOVE"
+                    | "MESSAGE_TYPE_LINK_REMOVE"
+                    | "MESSAGE_TYPE_VERIFICATION_REMOVE"
+            )
+        }))
+        .collect()
+}
+
+/// Slot an add/remove message competes for, e.g. one follow per (fid, target)
+fn conflict_key(message: &Value) -> Option<String> {
+    let data = message.get("data")?;
+    let fid = data.get("fid")?.as_u64()?;
+    match message_type(message) {
+        "MESSAGE_TYPE_REACTION_ADD" | "MESSAGE_TYPE_REACTION_REMOVE" => {
+            let body = data.get("reactionBody")?;
+            Some(format!(
+                "reaction:{fid}:{}:{}:{}",
+                body.get("type")?,
+                body.get("targetCastId").unwrap_or(&Value::Null),
+                body.get("targetUrl").unwrap_or(&Value::Null)
+            ))
+        }
+        "MESSAGE_TYPE_LINK_ADD" | "MESSAGE_TYPE_LINK_REMOVE" => {
+            let body = data.get("linkBody")?;
+            Some(format!(
+                "link:{fid}:{}:{}",
+                body.get("type")?,
+                body.get("targetFid")?
+            ))
+        }
+        "MESSAGE_TYPE_VERIFICATION_ADD_ETH_ADDRESS" => {
+            let address = data
+                .pointer("/verificationAddAddressBody/address")?
+                .as_str()?;
+            Some(format!("verification:{fid}:{}", address.to_lowercase()))
+        }
+        "MESSAGE_TYPE_VERIFICATION_REMOVE" => {
+            let address = data.pointer("/verificationRemoveBody/address")?.as_str()?;
+            Some(format!("verification:{fid}:{}", address.to_lowercase()))
+        }
+        "MESSAGE_TYPE_USER_DATA_ADD" => Some(format!(
+            "user_data:{fid}:{}",
+            data.pointer("/userDataBody/type")?
+        )),
+        _ => None,
+    }
+}
+
+fn message_type(message: &Value) -> &str {
+    message
+        .pointer("/data/type")
+        .and_then(|t| t.as_str())
+        .unwrap_or("")
+}
+
+fn timestamp(message: &Value) -> u64 {
+    message
+        .pointer("/data/timestamp")
+        .and_then(|t| t.as_u64())
+        .unwrap_or(0)
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn cast_id_json(cast_id: &CastId) -> Value {
+    json!({ "fid": cast_id.fid, "hash": hex_bytes(&cast_id.hash) })
+}
+
+/// Convert a protobuf message into the JSON shape returned by the hub HTTP API
+pub fn message_to_json(message: &Message) -> Result<Value> {
+    let data = match message.data.as_ref() {
+        Some(data) => data.clone(),
+        None if !message.data_bytes.is_empty() => {
+            protobuf::Message::parse_from_bytes(&message.data_bytes)
+                .context("Failed to decode message data bytes")?
+        }
+        None => MessageData::new(),
+    };
+
+    let mut data_json = json!({
+        "type": format!("{:?}", data.field_type),
+        "fid": data.fid,
+        "timestamp": data.timestamp,
+        "network": format!("{:?}", data.network),
+    });
+
+    let body = match &data.body {
+        Some(MessageData_oneof_body::cast_add_body(body)) => {
+            let embeds: Vec<Value> = body
+                .embeds
+                .iter()
+                .filter_map(|e| match &e.embed {
+                    Some(Embed_oneof_embed::url(url)) => Some(json!({ "url": url })),
+                    Some(Embed_oneof_embed::cast_id(id)) => {
+                        Some(json!({ "castId": cast_id_json(id) }))
+                    }
+                    None => None,
+                })
+                .collect();
+            let mut body_json = json!({
+                "text": body.text,
+                "mentions": body.mentions,
+                "mentionsPositions": body.mentions_positions,
+                "embeds": embeds,
+                "type": format!("{:?}", body.field_type),
+            });
+            match &body.parent {
+                Some(CastAddBody_oneof_parent::parent_cast_id(id)) => {
+                    body_json["parentCastId"] = cast_id_json(id)
+                }
+                Some(CastAddBody_oneof_parent::parent_url(url)) => {
+                    body_json["parentUrl"] = json!(url)
+                }
+                None => {}
+            }
+            Some(("castAddBody", body_json))
+        }
+        Some(MessageData_oneof_body::cast_remove_body(body)) => Some((
+            "castRemoveBody",
+            json!({ "targetHash": hex_bytes(&body.target_hash) }),
+        )),
+        Some(MessageData_oneof_body::reaction_body(body)) => {
+            let mut body_json = json!({ "type": format!("{:?}", body.field_type) });
+            match &body.target {
+                Some(ReactionBody_oneof_target::target_cast_id(id)) => {
+                    body_json["targetCastId"] = cast_id_json(id)
+                }
+                Some(ReactionBody_oneof_target::target_url(url)) => {
+                    body_json["targetUrl"] = json!(url)
+                }
+                None => {}
+            }
+            Some(("reactionBody", body_json))
+        }
+        Some(MessageData_oneof_body::link_body(body)) => {
+            let mut body_json = json!({ "type": body.field_type });
+            if let Some(LinkBody_oneof_target::target_fid(target)) = &body.target {
+                body_json["targetFid"] = json!(target);
+            }
+            if body.displayTimestamp > 0 {
+                body_json["displayTimestamp"] = json!(body.displayTimestamp);
+            }
+            Some(("linkBody", body_json))
+        }
+        Some(MessageData_oneof_body::user_data_body(body)) => Some((
+            "userDataBody",
+            json!({ "type": format!("{:?}", body.field_type), "value": body.value }),
+        )),
+        Some(MessageData_oneof_body::verification_add_address_body(body)) => Some((
+            "verificationAddAddressBody",
+            json!({
+                "address": hex_bytes(&body.address),
+                "claimSignature": general_purpose::STANDARD.encode(&body.claim_signature),
+                "blockHash": hex_bytes(&body.block_hash),
+                "chainId": body.chain_id,
+                "protocol": format!("{:?}", body.protocol),
+            }),
+        )),
+        Some(MessageData_oneof_body::verification_remove_body(body)) => Some((
+            "verificationRemoveBody",
+            json!({
+                "address": hex_bytes(&body.address),
+                "protocol": format!("{:?}", body.protocol),
+            }),
+        )),
+        _ => None,
+    };
+    if let Some((name, body_json)) = body {
+        data_json[name] = body_json;
+    }
+
+    Ok(json!({
+        "data": data_json,
+        "hash": hex_bytes(&message.hash),
+        "hashScheme": format!("{:?}", message.hash_scheme),
+        "signature": general_purpose::STANDARD.encode(&message.signature),
+        "signatureScheme": format!("{:?}", message.signature_scheme),
+        "signer": hex_bytes(&message.signer),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn follow(fid: u64, target: u64, timestamp: u64, kind: &str) -> Value {
+        json!({
+            "hash": format!("0x{fid}{target}{timestamp}"),
+            "data": {
+                "type": kind,
+                "fid": fid,
+                "timestamp": timestamp,
+                "linkBody": { "type": "follow", "targetFid": target }
+            }
+        })
+    }
+
+    #[test]
+    fn test_link_removes_and_pagination() {
+        let snapshot = OfflineSnapshot::from_messages(vec![
+            follow(1, 9, 10, "MESSAGE_TYPE_LINK_ADD"),
+            follow(2, 9, 11, "MESSAGE_TYPE_LINK_ADD"),
+            follow(3, 9, 12, "MESSAGE_TYPE_LINK_ADD"),
+            follow(3, 9, 13, "MESSAGE_TYPE_LINK_REMOVE"),
+        ]);
+
+        let params: HashMap<String, String> = [
+            ("target_fid".to_string(), "9".to_string()),
+            ("pageSize".to_string(), "1".to_string()),
+            ("reverse".to_string(), "true".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let page = snapshot.query("/v1/linksByTargetFid", &params).unwrap();
+        assert_eq!(page["messages"][0]["data"]["fid"], 2);
+        assert_eq!(page["nextPageToken"], "1");
+    }
+
+    #[test]
+    fn test_protobuf_message_to_json() {
+        let mut data = MessageData::new();
+        data.fid = 42;
+        data.field_type = crate::core::protocol::message::MessageType::MESSAGE_TYPE_CAST_ADD;
+        let mut body = crate::core::protocol::message::CastAddBody::new();
+        body.text = "gm".to_string();
+        data.body = Some(MessageData_oneof_body::cast_add_body(body));
+        let mut message = Message::new();
+        message.data = protobuf::SingularPtrField::some(data);
+        message.hash = vec![0xab, 0xcd];
+
+        let json = message_to_json(&message).unwrap();
+        assert_eq!(json["hash"], "0xabcd");
+        assert_eq!(json["data"]["type"], "MESSAGE_TYPE_CAST_ADD");
+        assert_eq!(json["data"]["castAddBody"]["text"], "gm");
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use ed25519_dalek::SigningKey;
+
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_client::HubResponse;
+use crate::core::client::hub_client::Signing;
+use crate::core::protocol::builder::MessageBuilder;
+use crate::core::protocol::message::Message;
+use crate::core::protocol::message::UserDataType;
+
+/// A user data field that can be set on a profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileField {
+    /// Short name used on the command line
+    pub name: &'static str,
+    pub data_type: UserDataType,
+    /// Type name as the hub's HTTP API reports it
+    pub hub_name: &'static str,
+}
+
+/// Profile fields in display order
+pub const PROFILE_FIELDS: &[ProfileField] = &[
+    ProfileField {
+        name: "username",
+        data_type: UserDataType::USER_DATA_TYPE_USERNAME,
+        hub_name: "USER_DATA_TYPE_USERNAME",
+    },
+    ProfileField {
+        name: "display",
+        data_type: UserDataType::USER_DATA_TYPE_DISPLAY,
+        hub_name: "USER_DATA_TYPE_DISPLAY",
+    },
+    ProfileField {
+        name: "bio",
+        data_type: UserDataType::USER_DATA_TYPE_BIO,
+        hub_name: "USER_DATA_TYPE_BIO",
+    },
+    ProfileField {
+        name: "pfp",
+        data_type: UserDataType::USER_DATA_TYPE_PFP,
+        hub_name: "USER_DATA_TYPE_PFP",
+    },
+    ProfileField {
+        name: "banner",
+        data_type: UserDataType::USER_DATA_TYPE_BANNER,
+        hub_name: "USER_DATA_TYPE_BANNER",
+    },
+    ProfileField {
+        name: "url",
+        data_type: UserDataType::USER_DATA_TYPE_URL,
+        hub_name: "USER_DATA_TYPE_URL",
+    },
+    ProfileField {
+        name: "location",
+        data_type: UserDataType::USER_DATA_TYPE_LOCATION,
+        hub_name: "USER_DATA_TYPE_LOCATION",
+    },
+    ProfileField {
+        name: "twitter",
+        data_type: UserDataType::USER_DATA_TYPE_TWITTER,
+        hub_name: "USER_DATA_TYPE_TWITTER",
+    },
+    ProfileField {
+        name: "github",
+        data_type: UserDataType::USER_DATA_TYPE_GITHUB,
+        hub_name: "USER_DATA_TYPE_GITHUB",
+    },
+];
+
+/// Look up a profile field by its command line name
+pub fn profile_field(name: &str) -> Option<&'static ProfileField> {
+    PROFILE_FIELDS.iter().find(|field| field.name == name)
+}
+
+/// A field whose value would change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileChange {
+    pub field: &'static ProfileField,
+    /// Value on the hub, `None` if the field was never set
+    pub current: Option<String>,
+    pub new: String,
+}
+
+/// Current profile values keyed by field name, from userDataByFid messages
+pub fn current_profile(messages: &[serde_json::Value]) -> HashMap<&'static str, String> {
+    let mut profile = HashMap::new();
+    for message in messages {
+        let Some(body) = message.pointer("/data/userDataBody") else {
+            continue;
+        };
+        let hub_name = body.get("type").and_then(|t| t.as_str());
+        let value = body.get("value").and_then(|v| v.as_str());
+        let field = PROFILE_FIELDS
+            .iter()
+            .find(|field| Some(field.hub_name) == hub_name);
+        if let (Some(field), Some(value)) = (field, value) {
+            profile.insert(field.name, value.to_string());
+        }
+    }
+    profile
+}
+
+/// Changes needed to bring a profile to the requested values
+///
+/// Requested values equal to what the hub already has are left out, so each
+/// change costs one USER_DATA_ADD message and unchanged fields cost nothing.
+pub fn diff_profile(
+    current: &HashMap<&'static str, String>,
+    updates: &[(&'static ProfileField, String)],
+) -> Vec<ProfileChange> {
+    updates
+        .iter()
+        .filter(|(field, value)| {
+            current.get(field.name).map(String::as_str).unwrap_or("") != value.as_str()
+        })
+        .map(|(field, value)| ProfileChange {
+            field,
+            current: current.get(field.name).cloned(),
+            new: value.clone(),
+        })
+        .collect()
+}
+
+/// Build a USER_DATA_ADD message signed with an Ed25519 signer
+///
+/// # Arguments
+/// * `fid` - The FID whose profile is updated
+/// * `data_type` - The user data field to set
+/// * `value` - The new value (empty to clear the field)
+/// * `signing_key` - An active signer of `fid`
+///
+/// # Returns
+/// * `Result<Message>` - The signed message, with `data_bytes` set
+pub fn build_user_data_message(
+    fid: u64,
+    data_type: UserDataType,
+    value: &str,
+    signing_key: &SigningKey,
+) -> Result<Message> {
+    MessageBuilder::new(fid)
+        .user_data(data_type, value)
+        .sign(signing_key)
+}
+
+impl FarcasterClient<Signing> {
+    /// Set one profile field with a USER_DATA_ADD message
+    ///
+    /// # Arguments
+    /// * `fid` - The FID whose profile is updated
+    /// * `data_type` - The user data field to set
+    /// * `value` - The new value
+    /// * `signing_key` - An active signer of `fid`
+    ///
+    /// # Returns
+    /// * `Result<HubResponse>` - The hub response or an error
+    pub async fn submit_user_data(
+        &self,
+        fid: u64,
+        data_type: UserDataType,
+        value: &str,
+        signing_key: &SigningKey,
+    ) -> Result<HubResponse> {
+        let message = build_user_data_message(fid, data_type, value, signing_key)?;
+        self.submit_message(&message).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::Verifier;
+    use protobuf::Message as ProtobufMessage;
+
+    use super::*;
+    use crate::core::protocol::message::MessageData;
+    use crate::core::protocol::message::MessageType;
+
+    #[test]
+    fn test_diff_profile_skips_unchanged_fields() {
+        let messages = vec![
+            serde_json::json!({ "data": { "userDataBody": { "type": "USER_DATA_TYPE_BIO", "value": "gm" } } }),
+            serde_json::json!({ "data": { "userDataBody": { "type": "USER_DATA_TYPE_DISPLAY", "value": "Alice" } } }),
+        ];
+        let current = current_profile(&messages);
+        assert_eq!(current.get("bio").map(String::as_str), Some("gm"));
+
+        let bio = profile_field("bio").unwrap();
+        let display = profile_field("display").unwrap();
+        let url = profile_field("url").unwrap();
+        let changes = diff_profile(
+            &current,
+            &[
+                (bio, "gm".to_string()),
+                (display, "Alice B".to_string()),
+                (url, "https://example.com".to_string()),
+            ],
+        );
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field.name, "display");
+        assert_eq!(changes[0].current.as_deref(), Some("Alice"));
+        assert_eq!(changes[1].current, None);
+    }
+
+    #[test]
+    fn test_user_data_message_is_signed() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let message =
+            build_user_data_message(42, UserDataType::USER_DATA_TYPE_BIO, "gm", &signing_key)
+                .unwrap();
+
+        let data = MessageData::parse_from_bytes(message.get_data_bytes()).unwrap();
+        assert_eq!(
+            data.get_field_type(),
+            MessageType::MESSAGE_TYPE_USER_DATA_ADD
+        );
+        assert_eq!(data.get_user_data_body().get_value(), "gm");
+        let signature = ed25519_dalek::Signature::from_slice(message.get_signature()).unwrap();
+        assert!(signing_key
+            .verifying_key()
+            .verify(message.get_hash(), &signature)
+            .is_ok());
+    }
+}
@@ -0,0 +1,179 @@
+//! Rate limiting of outbound hub writes
+//!
+//! Hubs treat a FID that floods them with messages as spam, so every message
+//! this process submits - casts, reactions, links, proofs and replays - takes
+//! a token from one shared bucket first. The bucket holds [`DEFAULT_BURST`]
+//! tokens and refills at the configured rate, so a few interactive commands
+//! go out at once while batch tools settle to a steady pace:
+//!
+//! ```text
+//! castorix --write-rate 30 replay --from http://hub-a:3381 --to http://hub-b:3381 --fid 12345
+//! CASTORIX_WRITE_RATE=0 castorix hub cast 12345 "gm"
+//! ```
+//!
+//! The rate is in messages per minute; `0` turns the limiter off.
+
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+
+use crate::consts::env_vars;
+
+/// Messages per minute unless overridden
+pub const DEFAULT_MESSAGES_PER_MINUTE: u32 = 60;
+/// Messages that can be sent back to back before the rate applies
+pub const DEFAULT_BURST: u32 = 10;
+
+/// Waits shorter than this are not reported
+const QUIET_WAIT: Duration = Duration::from_secs(1);
+
+static LIMITER: OnceLock<Mutex<Option<TokenBucket>>> = OnceLock::new();
+
+/// How fast messages may be submitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteRate {
+    /// Messages per minute; `0` means unlimited
+    pub per_minute: u32,
+    /// Bucket size, at most `per_minute`
+    pub burst: u32,
+}
+
+impl Default for WriteRate {
+    fn default() -> Self {
+        Self::per_minute(DEFAULT_MESSAGES_PER_MINUTE)
+    }
+}
+
+impl WriteRate {
+    /// A rate of `per_minute` messages with the default burst
+    pub fn per_minute(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            burst: DEFAULT_BURST.min(per_minute).max(1),
+        }
+    }
+
+    /// Rate from the `--write-rate` flag, else `CASTORIX_WRITE_RATE`
+    pub fn load(write_rate: Option<u32>) -> Result<Self> {
+        let per_minute = match write_rate {
+            Some(rate) => rate,
+            None => match std::env::var(env_vars::WRITE_RATE) {
+                Ok(rate) => rate.trim().parse().map_err(|_| {
+                    anyhow::anyhow!(
+                        "{} must be a number of messages per minute: {rate}",
+                        env_vars::WRITE_RATE
+                    )
+                })?,
+                Err(_) => DEFAULT_MESSAGES_PER_MINUTE,
+            },
+        };
+        Ok(Self::per_minute(per_minute))
+    }
+
+    /// Whether submissions are limited at all
+    pub fn is_limited(&self) -> bool {
+        self.per_minute > 0
+    }
+}
+
+/// Token bucket handing out one token per message
+///
+/// Tokens are reserved ahead: a caller that finds the bucket empty still
+/// takes a token and is told how long to wait for it, so concurrent callers
+/// queue in order instead of racing for the next refill.
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    per_second: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket for `rate`, or `None` if it is unlimited
+    pub fn new(rate: WriteRate, now: Instant) -> Option<Self> {
+        rate.is_limited().then(|| Self {
+            capacity: f64::from(rate.burst),
+            tokens: f64::from(rate.burst),
+            per_second: f64::from(rate.per_minute) / 60.0,
+            updated: now,
+        })
+    }
+
+    /// Take a token, returning how long to wait before using it
+    pub fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.updated = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.per_second)
+        }
+    }
+}
+
+/// Make `rate` apply to every message this process submits
+///
+/// Only the first call has an effect.
+pub fn install(rate: WriteRate) {
+    let _ = LIMITER.set(Mutex::new(TokenBucket::new(rate, Instant::now())));
+}
+
+/// Wait until the next message may be submitted
+pub async fn acquire() {
+    let limiter = LIMITER.get_or_init(|| {
+        let rate = WriteRate::load(None).unwrap_or_default();
+        Mutex::new(TokenBucket::new(rate, Instant::now()))
+    });
+    let wait = match limiter.lock() {
+        Ok(mut bucket) => match bucket.as_mut() {
+            Some(bucket) => bucket.reserve(Instant::now()),
+            None => return,
+        },
+        Err(_) => return,
+    };
+    if wait >= QUIET_WAIT {
+        eprintln!(
+            "⏳ Waiting {:.1}s to stay under the hub write rate limit",
+            wait.as_secs_f64()
+        );
+    }
+    tokio::time::sleep(wait).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_bursts_then_paces() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(WriteRate::per_minute(60), start).unwrap();
+        for _ in 0..DEFAULT_BURST {
+            assert_eq!(bucket.reserve(start), Duration::ZERO);
+        }
+        // One message per second once the burst is spent, queued in order
+        assert_eq!(bucket.reserve(start), Duration::from_secs(1));
+        assert_eq!(bucket.reserve(start), Duration::from_secs(2));
+
+        // Tokens refill over time but never beyond the burst
+        let later = start + Duration::from_secs(3600);
+        for _ in 0..DEFAULT_BURST {
+            assert_eq!(bucket.reserve(later), Duration::ZERO);
+        }
+        assert_eq!(bucket.reserve(later), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_write_rate() {
+        assert!(TokenBucket::new(WriteRate::per_minute(0), Instant::now()).is_none());
+        assert_eq!(WriteRate::per_minute(3).burst, 3);
+        assert_eq!(WriteRate::per_minute(600).burst, DEFAULT_BURST);
+        assert_eq!(WriteRate::load(Some(30)).unwrap().per_minute, 30);
+    }
+}
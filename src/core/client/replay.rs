@@ -0,0 +1,683 @@
+//! Replay a FID's messages from one hub onto another
+//!
+//! Used to seed a self-hosted hub (such as a new Snapchain node) with the
+//! messages of a FID: every cast, reaction, link, verification and user data
+//! message is read from the source hub, the ones the target already has are
+//! skipped, and the rest are submitted in timestamp order.
+//!
+//! Hub HTTP APIs serve messages as JSON, while `submitMessage` takes the
+//! protobuf bytes the signature was made over. The message data is therefore
+//! re-encoded from its JSON with the protobuf schema and checked against the
+//! message hash before it is submitted. Protobuf libraries differ in the order
+//! they write fields and in whether repeated numbers are packed, so each
+//! combination is tried until one reproduces the signed bytes; messages whose
+//! bytes can't be reproduced are reported instead of being sent.
+
+use std::collections::HashSet;
+
+use anyhow::Context;
+use anyhow::Result;
+use base64::engine::general_purpose;
+use base64::Engine as _;
+use protobuf::descriptor::DescriptorProto;
+use protobuf::descriptor::EnumDescriptorProto;
+use protobuf::descriptor::FieldDescriptorProto;
+use protobuf::descriptor::FieldDescriptorProto_Label;
+use protobuf::descriptor::FieldDescriptorProto_Type;
+use protobuf::descriptor::FileDescriptorProto;
+use protobuf::CodedOutputStream;
+use serde_json::Value;
+
+use crate::core::client::adaptive_paging::fetch_page_adaptive;
+use crate::core::client::adaptive_paging::AdaptivePaging;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_client::Signing;
+use crate::core::client::rate_limit;
+use crate::core::client::submit::is_duplicate_response;
+use crate::core::client::submit::SubmissionCache;
+use crate::core::protocol::message::HashScheme;
+use crate::core::protocol::message::Message;
+use crate::core::protocol::message::SignatureScheme;
+
+/// Hub endpoints listing the messages of a FID, with a label for each
+pub const REPLAY_STORES: [(&str, &str); 5] = [
+    ("user data", "userDataByFid"),
+    ("verifications", "verificationsByFid"),
+    ("links", "linksByFid"),
+    ("casts", "castsByFid"),
+    ("reactions", "reactionsByFid"),
+];
+
+/// JSON names some hubs use that differ from the schema's
+const FIELD_ALIASES: [(&str, &str); 2] = [
+    (
+        "verificationAddEthAddressBody",
+        "verification_add_address_body",
+    ),
+    ("ethSignature", "claim_signature"),
+];
+
+/// Messages of a FID to submit, with what was left out
+#[derive(Debug, Default)]
+pub struct ReplayPlan {
+    /// Messages missing on the target, oldest first
+    pub pending: Vec<Value>,
+    /// Messages read from the source
+    pub total: usize,
+    /// Messages left out because the target already has them, or the
+    /// source listed them twice
+    pub present: usize,
+}
+
+/// Work out which source messages the target is missing
+///
+/// # Arguments
+/// * `source` - Messages read from the source hub
+/// * `target` - Messages the target hub already has
+///
+/// # Returns
+/// * `ReplayPlan` - Missing messages in timestamp order; messages with the same
+///   timestamp keep the source's order
+pub fn plan_replay(source: Vec<Value>, target: &[Value]) -> ReplayPlan {
+    let present: HashSet<String> = target.iter().filter_map(message_hash).collect();
+    let total = source.len();
+    let mut seen = HashSet::new();
+    let mut pending: Vec<Value> = source
+        .into_iter()
+        .filter(|message| match message_hash(message) {
+            Some(hash) => !present.contains(&hash) && seen.insert(hash),
+            None => true,
+        })
+        .collect();
+    pending.sort_by_key(|message| {
+        message
+            .pointer("/data/timestamp")
+            .and_then(Value::as_u64)
+            .unwrap_or_default()
+    });
+    ReplayPlan {
+        present: total - pending.len(),
+        pending,
+        total,
+    }
+}
+
+/// The lowercase hex hash of a hub JSON message
+pub fn message_hash(message: &Value) -> Option<String> {
+    let hash = message.get("hash")?.as_str()?;
+    Some(hex::encode(decode_bytes(hash).ok()?))
+}
+
+/// Rebuild the protobuf message a hub JSON message was signed as
+///
+/// # Arguments
+/// * `json` - A message as served by the hub HTTP API
+///
+/// # Returns
+/// * `Result<Message>` - The message with `data_bytes` set, or an error when
+///   the data can't be re-encoded to bytes matching the message hash
+pub fn message_from_json(json: &Value) -> Result<Message> {
+    let field = |name: &str| {
+        json.get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Message has no {name}"))
+    };
+    let hash = decode_bytes(field("hash")?)?;
+    if field("hashScheme").unwrap_or("HASH_SCHEME_BLAKE3") != "HASH_SCHEME_BLAKE3" {
+        anyhow::bail!("Only BLAKE3 message hashes can be replayed");
+    }
+    if field("signatureScheme").unwrap_or("SIGNATURE_SCHEME_ED25519") != "SIGNATURE_SCHEME_ED25519"
+    {
+        anyhow::bail!("Only Ed25519 signed messages can be replayed");
+    }
+
+    let data_bytes = match json.get("dataBytes").and_then(Value::as_str) {
+        Some(data_bytes) => decode_bytes(data_bytes)?,
+        None => {
+            let data = json
+                .get("data")
+                .ok_or_else(|| anyhow::anyhow!("Message has no data"))?;
+            let mut encoded = None;
+            'encodings: for order in FieldOrder::ALL {
+                for packed in [true, false] {
+                    let bytes = encode_message(&Schema::load(order, packed), "MessageData", data)?;
+                    if matches_hash(&bytes, &hash) {
+                        encoded = Some(bytes);
+                        break 'encodings;
+                    }
+                }
+            }
+            encoded.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Re-encoded data of 0x{} does not match its hash",
+                    hex::encode(&hash)
+                )
+            })?
+        }
+    };
+    if !matches_hash(&data_bytes, &hash) {
+        anyhow::bail!(
+            "dataBytes of 0x{} do not match its hash",
+            hex::encode(&hash)
+        );
+    }
+
+    let mut message = Message::new();
+    message.set_data_bytes(data_bytes);
+    message.set_hash(hash);
+    message.set_hash_scheme(HashScheme::HASH_SCHEME_BLAKE3);
+    message.set_signature(decode_bytes(field("signature")?)?);
+    message.set_signature_scheme(SignatureScheme::SIGNATURE_SCHEME_ED25519);
+    message.set_signer(decode_bytes(field("signer")?)?);
+    Ok(message)
+}
+
+fn matches_hash(data_bytes: &[u8], hash: &[u8]) -> bool {
+    blake3::hash(data_bytes).as_bytes()[..20] == *hash
+}
+
+/// Order in which an encoder writes the fields of a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldOrder {
+    /// As declared in the `.proto` file, like ts-proto
+    Declaration,
+    /// By field number, oneof members included, like prost
+    Number,
+    /// Regular fields by number, then oneof members, like rust-protobuf
+    OneofsLast,
+}
+
+impl FieldOrder {
+    const ALL: [FieldOrder; 3] = [Self::Declaration, Self::Number, Self::OneofsLast];
+
+    fn sort(self, fields: &[FieldDescriptorProto]) -> Vec<&FieldDescriptorProto> {
+        let mut fields: Vec<&FieldDescriptorProto> = fields.iter().collect();
+        match self {
+            Self::Declaration => {}
+            Self::Number => fields.sort_by_key(|field| field.get_number()),
+            Self::OneofsLast => fields.sort_by_key(|field| {
+                (
+                    field.has_oneof_index() && !field.get_proto3_optional(),
+                    field.get_number(),
+                )
+            }),
+        }
+        fields
+    }
+}
+
+/// Bytes in hub JSON: `0x` hex or base64
+fn decode_bytes(value: &str) -> Result<Vec<u8>> {
+    if let Some(hex) = value.strip_prefix("0x") {
+        if let Ok(bytes) = hex::decode(hex) {
+            return Ok(bytes);
+        }
+    }
+    general_purpose::STANDARD
+        .decode(value)
+        .with_context(|| format!("Invalid bytes in hub JSON: {value}"))
+}
+
+/// Message and enum descriptors of the hub protocol
+struct Schema {
+    files: [&'static FileDescriptorProto; 2],
+    order: FieldOrder,
+    /// Write repeated numbers packed, the proto3 default
+    packed: bool,
+}
+
+impl Schema {
+    fn load(order: FieldOrder, packed: bool) -> Self {
+        Self {
+            order,
+            packed,
+            files: [
+                crate::core::protocol::message::file_descriptor_proto(),
+                crate::core::protocol::username_proof::file_descriptor_proto(),
+            ],
+        }
+    }
+
+    /// Whether `type_name` (`.pkg.Name` or `Name`) refers to `name` in `file`
+    fn matches(file: &FileDescriptorProto, type_name: &str, name: &str) -> bool {
+        let type_name = type_name.trim_start_matches('.');
+        type_name == name || type_name == format!("{}.{name}", file.get_package())
+    }
+
+    fn message(&self, type_name: &str) -> Result<&'static DescriptorProto> {
+        self.files
+            .iter()
+            .flat_map(|file| {
+                file.get_message_type()
+                    .iter()
+                    .filter(|message| Self::matches(file, type_name, message.get_name()))
+            })
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Unknown message type {type_name}"))
+    }
+
+    fn enumeration(&self, type_name: &str) -> Result<&'static EnumDescriptorProto> {
+        self.files
+            .iter()
+            .flat_map(|file| {
+                file.get_enum_type()
+                    .iter()
+                    .filter(|enumeration| Self::matches(file, type_name, enumeration.get_name()))
+            })
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Unknown enum type {type_name}"))
+    }
+}
+
+/// `snake_case` to the JSON `lowerCamelCase` name
+fn json_name(field: &FieldDescriptorProto) -> String {
+    if !field.get_json_name().is_empty() {
+        return field.get_json_name().to_string();
+    }
+    let mut name = String::new();
+    let mut upper = false;
+    for c in field.get_name().chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            name.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+/// The JSON value of a field, by its JSON name, proto name or a known alias
+fn field_value<'a>(
+    object: &'a serde_json::Map<String, Value>,
+    field: &FieldDescriptorProto,
+) -> Option<&'a Value> {
+    object
+        .get(&json_name(field))
+        .or_else(|| object.get(field.get_name()))
+        .or_else(|| {
+            FIELD_ALIASES
+                .iter()
+                .filter(|(_, name)| *name == field.get_name())
+                .find_map(|(alias, _)| object.get(*alias))
+        })
+        .filter(|value| !value.is_null())
+}
+
+/// Encode a JSON object as protobuf `type_name`
+///
+/// Fields are written in the schema's [`FieldOrder`]: `.proto` declaration
+/// order, protobuf field-number order, or field-number order with oneof
+/// members last.
+fn encode_message(schema: &Schema, type_name: &str, json: &Value) -> Result<Vec<u8>> {
+    let descriptor = schema.message(type_name)?;
+    let object = json
+        .as_object()
+        .ok_or_else(|| anyhow::anyhow!("Expected an object for {type_name}, got {json}"))?;
+    let mut bytes = Vec::new();
+    let mut output = CodedOutputStream::vec(&mut bytes);
+    for field in schema.order.sort(descriptor.get_field()) {
+        let Some(value) = field_value(object, field) else {
+            continue;
+        };
+        let number = field.get_number() as u32;
+        // Oneof members and proto3 optionals are written whenever they are set
+        let explicit = field.has_oneof_index() || field.get_proto3_optional();
+        if field.get_label() != FieldDescriptorProto_Label::LABEL_REPEATED {
+            let value = scalar(schema, field, value)?;
+            if explicit || !value.is_default() {
+                value.write(&mut output, number)?;
+            }
+            continue;
+        }
+        let items = value
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Expected an array for {}", field.get_name()))?;
+        let items = items
+            .iter()
+            .map(|item| scalar(schema, field, item))
+            .collect::<Result<Vec<_>>>()?;
+        if items.is_empty() {
+            continue;
+        }
+        if schema.packed && items.iter().all(FieldValue::is_packable) {
+            let mut packed = Vec::new();
+            let mut packed_output = CodedOutputStream::vec(&mut packed);
+            for item in &items {
+                item.write_no_tag(&mut packed_output)?;
+            }
+            packed_output.flush()?;
+            drop(packed_output);
+            output.write_bytes(number, &packed)?;
+        } else {
+            for item in &items {
+                item.write(&mut output, number)?;
+            }
+        }
+    }
+    output.flush()?;
+    drop(output);
+    Ok(bytes)
+}
+
+/// A decoded field value, ready to be written
+enum FieldValue {
+    Varint(u64),
+    Signed(i64),
+    ZigZag32(i32),
+    ZigZag64(i64),
+    Fixed32(u32),
+    Fixed64(u64),
+    Float(f32),
+    Double(f64),
+    Bool(bool),
+    /// Strings, bytes and nested messages
+    LengthDelimited(Vec<u8>, bool),
+}
+
+impl FieldValue {
+    fn is_default(&self) -> bool {
+        match self {
+            Self::Varint(v) | Self::Fixed64(v) => *v == 0,
+            Self::Signed(v) | Self::ZigZag64(v) => *v == 0,
+            Self::ZigZag32(v) => *v == 0,
+            Self::Fixed32(v) => *v == 0,
+            Self::Float(v) => *v == 0.0,
+            Self::Double(v) => *v == 0.0,
+            Self::Bool(v) => !v,
+            // Nested messages are written whenever they are present
+            Self::LengthDelimited(bytes, is_message) => !is_message && bytes.is_empty(),
+        }
+    }
+
+    fn is_packable(&self) -> bool {
+        !matches!(self, Self::LengthDelimited(..))
+    }
+
+    fn write(&self, output: &mut CodedOutputStream, number: u32) -> Result<()> {
+        match self {
+            Self::Varint(v) => output.write_uint64(number, *v)?,
+            Self::Signed(v) => output.write_int64(number, *v)?,
+            Self::ZigZag32(v) => output.write_sint32(number, *v)?,
+            Self::ZigZag64(v) => output.write_sint64(number, *v)?,
+            Self::Fixed32(v) => output.write_fixed32(number, *v)?,
+            Self::Fixed64(v) => output.write_fixed64(number, *v)?,
+            Self::Float(v) => output.write_float(number, *v)?,
+            Self::Double(v) => output.write_double(number, *v)?,
+            Self::Bool(v) => output.write_bool(number, *v)?,
+            Self::LengthDelimited(bytes, _) => output.write_bytes(number, bytes)?,
+        }
+        Ok(())
+    }
+
+    fn write_no_tag(&self, output: &mut CodedOutputStream) -> Result<()> {
+        match self {
+            Self::Varint(v) => output.write_uint64_no_tag(*v)?,
+            Self::Signed(v) => output.write_int64_no_tag(*v)?,
+            Self::ZigZag32(v) => output.write_sint32_no_tag(*v)?,
+            Self::ZigZag64(v) => output.write_sint64_no_tag(*v)?,
+            Self::Fixed32(v) => output.write_fixed32_no_tag(*v)?,
+            Self::Fixed64(v) => output.write_fixed64_no_tag(*v)?,
+            Self::Float(v) => output.write_float_no_tag(*v)?,
+            Self::Double(v) => output.write_double_no_tag(*v)?,
+            Self::Bool(v) => output.write_bool_no_tag(*v)?,
+            Self::LengthDelimited(bytes, _) => output.write_bytes_no_tag(bytes)?,
+        }
+        Ok(())
+    }
+}
+
+/// Decode one JSON value of `field`
+fn scalar(schema: &Schema, field: &FieldDescriptorProto, value: &Value) -> Result<FieldValue> {
+    use FieldDescriptorProto_Type::*;
+
+    let name = field.get_name();
+    let unsigned = || -> Result<u64> {
+        match value {
+            Value::String(s) => s.parse().ok(),
+            _ => value.as_u64(),
+        }
+        .ok_or_else(|| anyhow::anyhow!("Expected an unsigned integer for {name}, got {value}"))
+    };
+    let signed = || -> Result<i64> {
+        match value {
+            Value::String(s) => s.parse().ok(),
+            _ => value.as_i64(),
+        }
+        .ok_or_else(|| anyhow::anyhow!("Expected an integer for {name}, got {value}"))
+    };
+    let float = || -> Result<f64> {
+        value
+            .as_f64()
+            .ok_or_else(|| anyhow::anyhow!("Expected a number for {name}, got {value}"))
+    };
+    let string = || -> Result<&str> {
+        value
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Expected a string for {name}, got {value}"))
+    };
+    Ok(match field.get_field_type() {
+        TYPE_UINT64 | TYPE_UINT32 => FieldValue::Varint(unsigned()?),
+        TYPE_INT64 | TYPE_INT32 => FieldValue::Signed(signed()?),
+        TYPE_SINT32 => FieldValue::ZigZag32(signed()? as i32),
+        TYPE_SINT64 => FieldValue::ZigZag64(signed()?),
+        TYPE_FIXED32 => FieldValue::Fixed32(unsigned()? as u32),
+        TYPE_SFIXED32 => FieldValue::Fixed32(signed()? as i32 as u32),
+        TYPE_FIXED64 => FieldValue::Fixed64(unsigned()?),
+        TYPE_SFIXED64 => FieldValue::Fixed64(signed()? as u64),
+        TYPE_FLOAT => FieldValue::Float(float()? as f32),
+        TYPE_DOUBLE => FieldValue::Double(float()?),
+        TYPE_BOOL => FieldValue::Bool(
+            value
+                .as_bool()
+                .ok_or_else(|| anyhow::anyhow!("Expected a bool for {name}, got {value}"))?,
+        ),
+        TYPE_ENUM => {
+            let number = match value.as_str() {
+                Some(label) => schema
+                    .enumeration(field.get_type_name())?
+                    .get_value()
+                    .iter()
+                    .find(|v| v.get_name() == label)
+                    .map(|v| v.get_number())
+                    .ok_or_else(|| anyhow::anyhow!("Unknown value {label} for {name}"))?,
+                None => signed()? as i32,
+            };
+            FieldValue::Signed(number as i64)
+        }
+        TYPE_STRING => FieldValue::LengthDelimited(string()?.as_bytes().to_vec(), false),
+        TYPE_BYTES => FieldValue::LengthDelimited(decode_bytes(string()?)?, false),
+        TYPE_MESSAGE => {
+            FieldValue::LengthDelimited(encode_message(schema, field.get_type_name(), value)?, true)
+        }
+        TYPE_GROUP => anyhow::bail!("Groups are not supported ({name})"),
+    })
+}
+
+impl<M> FarcasterClient<M> {
+    /// Read every message of a FID from the hub
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `on_store` - Called with each store's label and message count
+    ///
+    /// # Returns
+    /// * `Result<Vec<Value>>` - Hub JSON messages of all [`REPLAY_STORES`]
+    pub async fn get_all_messages(
+        &self,
+        fid: u64,
+        mut on_store: impl FnMut(&str, usize),
+    ) -> Result<Vec<Value>> {
+        let mut all = Vec::new();
+        for (label, endpoint) in REPLAY_STORES {
+            let base_url = format!("{}/v1/{endpoint}?fid={fid}", self.hub_url());
+            let paging = AdaptivePaging::default();
+            let mut page_token = None;
+            let mut count = 0;
+            loop {
+                let page = fetch_page_adaptive(
+                    self.http_client().clone(),
+                    base_url.clone(),
+                    page_token.take(),
+                    paging.clone(),
+                    label,
+                )
+                .await?;
+                let messages = page
+                    .get("messages")
+                    .and_then(|m| m.as_array())
+                    .cloned()
+                    .unwrap_or_default();
+                count += messages.len();
+                page_token = page
+                    .get("nextPageToken")
+                    .and_then(|t| t.as_str())
+                    .filter(|t| !t.is_empty() && !messages.is_empty())
+                    .map(|t| t.to_string());
+                all.extend(messages);
+                if page_token.is_none() {
+                    break;
+                }
+            }
+            on_store(label, count);
+        }
+        Ok(all)
+    }
+}
+
+impl FarcasterClient<Signing> {
+    /// Submit a message signed by someone else, as it is
+    ///
+    /// Like [`FarcasterClient::submit_message`], a message the local
+    /// submission cache records as accepted by this hub is not sent again.
+    ///
+    /// # Arguments
+    /// * `message` - A signed message, e.g. from [`message_from_json`]
+    ///
+    /// # Returns
+    /// * `Result<bool>` - `true` if the hub merged the message, `false` if it
+    ///   already had it
+    pub async fn replay_message(&self, message: &Message) -> Result<bool> {
+        use protobuf::Message as _;
+
+        let hash = format!("0x{}", hex::encode(message.get_hash()));
+        let mut cache = SubmissionCache::open();
+        if cache.contains(self.hub_url(), &hash) {
+            return Ok(false);
+        }
+
+        let url = format!("{}/v1/submitMessage", self.hub_url());
+        rate_limit::acquire().await;
+        let (request, _) = self.http_client().post(&url);
+        let response = request
+            .header("Content-Type", "application/octet-stream")
+            .body(message.write_to_bytes()?)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", self.hub_url()))?;
+        let status = response.status();
+        let body = response.text().await?;
+        if status.is_success() {
+            if serde_json::from_str::<Value>(&body).is_ok() {
+                if let Err(e) = cache.record(self.hub_url(), &hash) {
+                    eprintln!("⚠️  Could not update submission cache: {e}");
+                }
+            }
+            return Ok(true);
+        }
+        if is_duplicate_response(status, &body) {
+            return Ok(false);
+        }
+        anyhow::bail!("Hub returned {status}: {body}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::SigningKey;
+    use protobuf::Message as _;
+
+    use super::*;
+    use crate::core::client::hub_client::build_ed25519_message;
+    use crate::core::protocol::message::CastAddBody;
+    use crate::core::protocol::message::FarcasterNetwork;
+    use crate::core::protocol::message::MessageData;
+    use crate::core::protocol::message::MessageType;
+
+    fn hub_json(message: &Message, data: Value) -> Value {
+        serde_json::json!({
+            "data": data,
+            "hash": format!("0x{}", hex::encode(message.get_hash())),
+            "hashScheme": "HASH_SCHEME_BLAKE3",
+            "signature": general_purpose::STANDARD.encode(message.get_signature()),
+            "signatureScheme": "SIGNATURE_SCHEME_ED25519",
+            "signer": format!("0x{}", hex::encode(message.get_signer())),
+        })
+    }
+
+    #[test]
+    fn test_message_from_json_reproduces_signed_bytes() {
+        let mut body = CastAddBody::new();
+        body.set_text("gm".to_string());
+        body.set_mentions(vec![2, 3]);
+        body.set_mentions_positions(vec![0, 1]);
+        body.set_parent_url("https://warpcast.com/~/channel/rust".to_string());
+        let mut data = MessageData::new();
+        data.set_field_type(MessageType::MESSAGE_TYPE_CAST_ADD);
+        data.set_fid(42);
+        data.set_timestamp(120_000_000);
+        data.set_network(FarcasterNetwork::FARCASTER_NETWORK_MAINNET);
+        data.set_cast_add_body(body);
+        let signed = build_ed25519_message(data, &SigningKey::from_bytes(&[7; 32])).unwrap();
+
+        let json = hub_json(
+            &signed,
+            serde_json::json!({
+                "type": "MESSAGE_TYPE_CAST_ADD",
+                "fid": 42,
+                "timestamp": 120000000,
+                "network": "FARCASTER_NETWORK_MAINNET",
+                "castAddBody": {
+                    "embedsDeprecated": [],
+                    "mentions": [2, 3],
+                    "parentUrl": "https://warpcast.com/~/channel/rust",
+                    "text": "gm",
+                    "mentionsPositions": [0, 1],
+                    "embeds": []
+                }
+            }),
+        );
+        let rebuilt = message_from_json(&json).unwrap();
+        assert_eq!(rebuilt.get_data_bytes(), signed.get_data_bytes());
+        assert_eq!(
+            rebuilt.write_to_bytes().unwrap(),
+            signed.write_to_bytes().unwrap()
+        );
+
+        // Data that doesn't match the hash is refused
+        let mut tampered = json.clone();
+        tampered["data"]["castAddBody"]["text"] = "gn".into();
+        assert!(message_from_json(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_plan_replay() {
+        fn message(hash: &str, timestamp: u64) -> Value {
+            serde_json::json!({"hash": hash, "data": {"timestamp": timestamp}})
+        }
+
+        let source = vec![
+            message("0x03", 30),
+            message("0x01", 10),
+            message("0x02", 20),
+            message("0x01", 10),
+        ];
+        let plan = plan_replay(source, &[message("0x02", 20)]);
+        assert_eq!(plan.total, 4);
+        assert_eq!(plan.present, 2);
+        let hashes: Vec<String> = plan.pending.iter().filter_map(message_hash).collect();
+        assert_eq!(hashes, ["01", "03"]);
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::VecDeque;
+
+use anyhow::Result;
+use futures::stream::Stream;
+use futures::stream::{
+    self,
+};
+
+use crate::core::client::adaptive_paging::fetch_page_adaptive;
+use crate::core::client::adaptive_paging::AdaptivePaging;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_pool::HubPool;
+
+/// Pagination state carried between items of a hub list stream
+struct PageCursor {
+    client: HubPool,
+    base_url: String,
+    page_token: Option<String>,
+    buffer: VecDeque<serde_json::Value>,
+    /// At most one page is held in memory; pages shrink when the hub rate limits
+    paging: AdaptivePaging,
+    exhausted: bool,
+    what: &'static str,
+}
+
+impl PageCursor {
+    fn new(client: HubPool, base_url: String, what: &'static str) -> Self {
+        Self {
+            client,
+            base_url,
+            page_token: None,
+            buffer: VecDeque::new(),
+            paging: AdaptivePaging::default(),
+            exhausted: false,
+            what,
+        }
+    }
+
+    /// Yield the next buffered message, fetching another page when the buffer runs dry
+    async fn next_item(mut self) -> Option<(Result<serde_json::Value>, Self)> {
+        loop {
+            if let Some(message) = self.buffer.pop_front() {
+                return Some((Ok(message), self));
+            }
+            if self.exhausted {
+                return None;
+            }
+
+            let page = fetch_page_adaptive(
+                self.client.clone(),
+                self.base_url.clone(),
+                self.page_token.clone(),
+                self.paging.clone(),
+                self.what,
+            );
+            let mut data = match page.await {
+                Ok(data) => data,
+                Err(e) => {
+                    // Report the failure once and end the stream
+                    self.exhausted = true;
+                    return Some((Err(e), self));
+                }
+            };
+
+            let messages = match data.get_mut("messages").map(serde_json::Value::take) {
+                Some(serde_json::Value::Array(messages)) => messages,
+                _ => Vec::new(),
+            };
+            self.page_token = data
+                .get("nextPageToken")
+                .and_then(|t| t.as_str())
+                .filter(|t| !t.is_empty() && !messages.is_empty())
+                .map(|t| t.to_string());
+            self.exhausted = self.page_token.is_none();
+            self.buffer.extend(messages);
+        }
+    }
+}
+
+impl<M> FarcasterClient<M> {
+    /// Stream followers of a FID page by page
+    ///
+    /// Unlike [`FarcasterClient::get_followers`] the results are not collected into
+    /// a `Vec`; only the current page is kept in memory. The stream ends after the
+    /// first error. Items are the hub's link messages as raw JSON.
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `impl Stream<Item = Result<serde_json::Value>>` - Follow link messages in hub order
+    pub fn followers_stream(&self, fid: u64) -> impl Stream<Item = Result<serde_json::Value>> {
+        self.list_stream(
+            format!("/v1/linksByTargetFid?target_fid={}&link_type=follow", fid),
+            "followers",
+        )
+    }
+
+    /// Stream the accounts a FID follows page by page
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `impl Stream<Item = Result<serde_json::Value>>` - Follow link messages in hub order
+    pub fn following_stream(&self, fid: u64) -> impl Stream<Item = Result<serde_json::Value>> {
+        self.list_stream(
+            format!("/v1/linksByFid?fid={}&link_type=follow", fid),
+            "following",
+        )
+    }
+
+    /// Stream casts published by a FID page by page
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `impl Stream<Item = Result<serde_json::Value>>` - Cast messages in hub order
+    pub fn casts_stream(&self, fid: u64) -> impl Stream<Item = Result<serde_json::Value>> {
+        self.list_stream(format!("/v1/castsByFid?fid={}", fid), "casts")
+    }
+
+    /// Stream the messages of a FID from one of the hub's `...ByFid` endpoints
+    ///
+    /// # Arguments
+    /// * `endpoint` - Endpoint name, e.g. `reactionsByFid`
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `impl Stream<Item = Result<serde_json::Value>>` - Messages in hub order
+    pub fn fid_messages_stream(
+        &self,
+        endpoint: &'static str,
+        fid: u64,
+    ) -> impl Stream<Item = Result<serde_json::Value>> {
+        self.list_stream(format!("/v1/{}?fid={}", endpoint, fid), endpoint)
+    }
+
+    fn list_stream(
+        &self,
+        query: String,
+        what: &'static str,
+    ) -> impl Stream<Item = Result<serde_json::Value>> {
+        let cursor = PageCursor::new(
+            self.http_client().clone(),
+            format!("{}{}", self.hub_url(), query),
+            what,
+        );
+        stream::unfold(cursor, PageCursor::next_item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_stream_ends_after_error() {
+        let client = FarcasterClient::read_only("http://127.0.0.1:1".to_string());
+        let items: Vec<Result<serde_json::Value>> = client.followers_stream(1).collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::utils::data_dir;
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
+
+/// Number of attempts made for a single submitMessage call
+pub const SUBMIT_MAX_ATTEMPTS: u32 = 4;
+/// Number of recently accepted message hashes remembered locally, per hub
+const DEDUP_CACHE_CAPACITY: usize = 256;
+
+/// Hub error codes and messages that mean the message is already stored
+const DUPLICATE_MARKERS: &[&str] = &[
+    "bad_request.duplicate",
+    "already been merged",
+    "already exists",
+    "duplicate message",
+];
+
+/// Whether a failed submitMessage response means the hub already has the message
+///
+/// A retry after a lost response re-sends a message the hub may have merged the
+/// first time, so duplicates count as success rather than an error.
+pub fn is_duplicate_response(status: StatusCode, body: &str) -> bool {
+    if !status.is_client_error() {
+        return false;
+    }
+    let body = body.to_lowercase();
+    DUPLICATE_MARKERS.iter().any(|marker| body.contains(marker))
+}
+
+/// Small on-disk record of message hashes each hub has accepted
+///
+/// Lets a re-run of the same command skip messages that were already
+/// submitted instead of sending them again. Hashes are kept per hub, so a
+/// message accepted by one hub is still sent to another.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SubmissionCache {
+    #[serde(default)]
+    hubs: BTreeMap<String, VecDeque<String>>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl SubmissionCache {
+    /// Load the cache from the data directory, starting empty if it is missing or unreadable
+    pub fn open() -> Self {
+        let _timer = metrics::start(Phase::DiskIo, "submission cache");
+        let path = data_dir::data_dir()
+            .join("cache")
+            .join("submitted_messages.json");
+        let mut cache: Self = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        cache.path = Some(path);
+        cache
+    }
+
+    /// Whether a hub already accepted a message hash
+    pub fn contains(&self, hub_url: &str, hash: &str) -> bool {
+        self.hubs
+            .get(&normalize_hub_url(hub_url))
+            .is_some_and(|hashes| hashes.iter().any(|h| h == hash))
+    }
+
+    /// Remember a message hash accepted by a hub and persist the cache
+    ///
+    /// Only call this once the hub answered 2xx with a parseable body.
+    pub fn record(&mut self, hub_url: &str, hash: &str) -> Result<()> {
+        if self.contains(hub_url, hash) {
+            return Ok(());
+        }
+        let hashes = self.hubs.entry(normalize_hub_url(hub_url)).or_default();
+        hashes.push_back(hash.to_string());
+        while hashes.len() > DEDUP_CACHE_CAPACITY {
+            hashes.pop_front();
+        }
+
+        if let Some(path) = &self.path {
+            let _timer = metrics::start(Phase::DiskIo, "submission cache");
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, serde_json::to_string(self)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Cache key of a hub: scheme, host and port, without default ports or a trailing slash
+fn normalize_hub_url(hub_url: &str) -> String {
+    let hub_url = hub_url.trim();
+    match reqwest::Url::parse(hub_url) {
+        Ok(url) => url.as_str().trim_end_matches('/').to_string(),
+        Err(_) => hub_url.trim_end_matches('/').to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_detection() {
+        let body =
+            r#"{"errCode":"bad_request.duplicate","message":"message has already been merged"}"#;
+        assert!(is_duplicate_response(StatusCode::BAD_REQUEST, body));
+        assert!(!is_duplicate_response(
+            StatusCode::BAD_REQUEST,
+            r#"{"errCode":"bad_request.validation_failure"}"#
+        ));
+        assert!(!is_duplicate_response(StatusCode::BAD_GATEWAY, body));
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let hub = "http://hub.example:3381";
+        let mut cache = SubmissionCache::default();
+        for i in 0..DEDUP_CACHE_CAPACITY + 10 {
+            cache.record(hub, &format!("0x{i:x}")).unwrap();
+        }
+        assert_eq!(cache.hubs[hub].len(), DEDUP_CACHE_CAPACITY);
+        assert!(!cache.contains(hub, "0x0"));
+        assert!(cache.contains(hub, &format!("0x{:x}", DEDUP_CACHE_CAPACITY + 9)));
+    }
+
+    #[test]
+    fn test_cache_is_per_hub() {
+        let mut cache = SubmissionCache::default();
+        cache.record("http://hub-a.example:3381/", "0xabc").unwrap();
+
+        assert!(cache.contains("HTTP://Hub-A.example:3381", "0xabc"));
+        assert!(!cache.contains("http://hub-b.example:3381", "0xabc"));
+
+        cache.record("http://hub-b.example:3381", "0xabc").unwrap();
+        assert!(cache.contains("http://hub-b.example:3381", "0xabc"));
+        assert_eq!(cache.hubs.len(), 2);
+    }
+
+    #[test]
+    fn test_hub_url_normalization() {
+        assert_eq!(
+            normalize_hub_url(" https://Hub.Example:443/ "),
+            "https://hub.example"
+        );
+        assert_eq!(
+            normalize_hub_url("http://hub.example:3381"),
+            normalize_hub_url("http://hub.example:3381/")
+        );
+        assert_ne!(
+            normalize_hub_url("http://hub.example:3381"),
+            normalize_hub_url("http://hub.example:3382")
+        );
+    }
+
+    #[test]
+    fn test_legacy_cache_file_starts_empty() {
+        let cache: SubmissionCache = serde_json::from_str(r#"{"hashes":["0xabc"]}"#).unwrap();
+        assert!(!cache.contains("http://hub.example:3381", "0xabc"));
+    }
+}
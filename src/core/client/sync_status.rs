@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Approximate Snapchain block time used to turn block delay into wall-clock lag
+pub const DEFAULT_BLOCK_TIME: Duration = Duration::from_secs(1);
+
+/// Sync state of a single shard as reported by /v1/info
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShardSyncStatus {
+    pub shard_id: u64,
+    pub max_height: Option<u64>,
+    /// Number of blocks the hub is behind the network for this shard
+    pub block_delay: Option<u64>,
+    pub num_messages: Option<u64>,
+    pub mempool_size: Option<u64>,
+}
+
+/// Interpreted view of a hub's /v1/info response
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncStatus {
+    pub version: Option<String>,
+    /// Hubble-style explicit syncing flag, when present
+    pub is_syncing: Option<bool>,
+    pub num_messages: Option<u64>,
+    pub num_fid_registrations: Option<u64>,
+    /// Number of connected peers, when the hub reports it
+    pub peers: Option<u64>,
+    pub shards: Vec<ShardSyncStatus>,
+}
+
+impl SyncStatus {
+    /// Parse the JSON returned by [`crate::core::client::FarcasterClient::get_hub_info`]
+    pub fn from_hub_info(info: &serde_json::Value) -> Self {
+        let u64_field = |v: &serde_json::Value, key: &str| v.get(key).and_then(|x| x.as_u64());
+
+        let shards = info
+            .get("shardInfos")
+            .and_then(|s| s.as_array())
+            .map(|shards| {
+                shards
+                    .iter()
+                    .map(|shard| ShardSyncStatus {
+                        shard_id: u64_field(shard, "shardId").unwrap_or(0),
+                        max_height: u64_field(shard, "maxHeight"),
+                        block_delay: u64_field(shard, "blockDelay"),
+                        num_messages: u64_field(shard, "numMessages"),
+                        mempool_size: u64_field(shard, "mempoolSize"),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let db_stats = info.get("dbStats");
+
+        Self {
+            version: info
+                .get("version")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string()),
+            is_syncing: info.get("isSyncing").and_then(|s| s.as_bool()),
+            num_messages: db_stats.and_then(|d| u64_field(d, "numMessages")),
+            num_fid_registrations: db_stats.and_then(|d| u64_field(d, "numFidRegistrations")),
+            peers: u64_field(info, "numPeers").or_else(|| {
+                info.get("peers")
+                    .and_then(|p| p.as_array())
+                    .map(|p| p.len() as u64)
+            }),
+            shards,
+        }
+    }
+
+    /// Largest block delay across all shards
+    pub fn max_block_delay(&self) -> Option<u64> {
+        self.shards.iter().filter_map(|s| s.block_delay).max()
+    }
+
+    /// Estimated wall-clock lag for the slowest shard
+    pub fn estimated_lag(&self, block_time: Duration) -> Option<Duration> {
+        self.max_block_delay()
+            .map(|blocks| block_time.saturating_mul(blocks.min(u32::MAX as u64) as u32))
+    }
+
+    /// Reasons the hub should be considered unhealthy (empty when healthy)
+    ///
+    /// # Arguments
+    /// * `max_block_delay` - Largest acceptable per-shard block delay
+    pub fn health_issues(&self, max_block_delay: u64) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        if self.is_syncing == Some(true) {
+            issues.push("hub reports it is still syncing".to_string());
+        }
+        for shard in &self.shards {
+            if let Some(delay) = shard.block_delay {
+                if delay > max_block_delay {
+                    issues.push(format!(
+                        "shard {} is {} blocks behind (threshold {})",
+                        shard.shard_id, delay, max_block_delay
+                    ));
+                }
+            }
+        }
+        if self.peers == Some(0) {
+            issues.push("hub has no connected peers".to_string());
+        }
+
+        issues
+    }
+}
+
+/// Format a duration as a short human-readable string such as `2h 5m` or `42s`
+pub fn format_lag(lag: Duration) -> String {
+    let secs = lag.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m {}s", secs / 60, secs % 60),
+        3600..=86399 => format!("{}h {}m", secs / 3600, (secs % 3600) / 60),
+        _ => format!("{}d {}h", secs / 86400, (secs % 86400) / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_snapchain_info() {
+        let info = serde_json::json!({
+            "version": "0.2.0",
+            "dbStats": { "numMessages": 1000, "numFidRegistrations": 10 },
+            "numShards": 2,
+            "shardInfos": [
+                { "shardId": 0, "maxHeight": 100, "blockDelay": 0 },
+                { "shardId": 1, "maxHeight": 90, "blockDelay": 250, "numMessages": 500 }
+            ]
+        });
+        let status = SyncStatus::from_hub_info(&info);
+        assert_eq!(status.shards.len(), 2);
+        assert_eq!(status.max_block_delay(), Some(250));
+        assert_eq!(status.num_messages, Some(1000));
+        assert!(status.health_issues(300).is_empty());
+        assert_eq!(status.health_issues(100).len(), 1);
+    }
+
+    #[test]
+    fn test_format_lag() {
+        assert_eq!(format_lag(Duration::from_secs(42)), "42s");
+        assert_eq!(format_lag(Duration::from_secs(125)), "2m 5s");
+        assert_eq!(format_lag(Duration::from_secs(7500)), "2h 5m");
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::client::embeds::EmbedPreview;
+use crate::core::client::hub_client::FarcasterClient;
+
+/// Bounds on how much of a conversation is fetched
+#[derive(Debug, Clone, Copy)]
+pub struct ThreadLimits {
+    /// Maximum number of parent casts resolved above the requested cast
+    pub max_parents: usize,
+    /// Maximum reply depth expanded below the requested cast
+    pub max_depth: usize,
+    /// Maximum number of casts in the whole tree
+    pub max_casts: usize,
+}
+
+impl Default for ThreadLimits {
+    fn default() -> Self {
+        Self {
+            max_parents: 10,
+            max_depth: 3,
+            max_casts: 200,
+        }
+    }
+}
+
+/// A cast and the replies below it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadNode {
+    pub fid: u64,
+    pub hash: String,
+    pub text: String,
+    /// Farcaster timestamp (seconds since the Farcaster epoch)
+    pub timestamp: u64,
+    /// URLs embedded in the cast
+    pub embeds: Vec<String>,
+    /// Link previews of the embedded URLs, when enrichment was requested
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previews: Vec<EmbedPreview>,
+    /// Whether this is the cast the thread was requested for
+    pub focus: bool,
+    /// Some replies were left out to stay within the size limit
+    pub truncated: bool,
+    /// Replies, oldest first
+    pub replies: Vec<ThreadNode>,
+}
+
+/// A conversation around one cast
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CastThread {
+    /// Topmost resolved cast; its chain of replies leads down to the focal cast
+    pub root: ThreadNode,
+    /// Hash of the cast the thread was requested for
+    pub focus_hash: String,
+    /// Number of casts in the tree
+    pub cast_count: usize,
+    /// The root replies to another cast that was not fetched because of `max_parents`
+    pub has_more_parents: bool,
+    /// Channel or URL the root cast was posted under, if any
+    pub parent_url: Option<String>,
+}
+
+impl ThreadNode {
+    /// Build a childless node from a hub cast message
+    pub fn from_message(message: &serde_json::Value) -> Option<Self> {
+        let data = message.get("data")?;
+        let body = data.get("castAddBody");
+        Some(Self {
+            fid: data.get("fid")?.as_u64()?,
+            hash: message.get("hash")?.as_str()?.to_lowercase(),
+            text: body
+                .and_then(|b| b.get("text"))
+                .and_then(|t| t.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            timestamp: data.get("timestamp").and_then(|t| t.as_u64()).unwrap_or(0),
+            embeds: body
+                .and_then(|b| b.get("embeds"))
+                .and_then(|e| e.as_array())
+                .map(|embeds| {
+                    embeds
+                        .iter()
+                        .filter_map(|e| e.get("url").and_then(|u| u.as_str()))
+                        .map(|u| u.to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            previews: Vec::new(),
+            focus: false,
+            truncated: false,
+            replies: Vec::new(),
+        })
+    }
+
+    /// Number of casts in this subtree, including this one
+    pub fn count(&self) -> usize {
+        1 + self.replies.iter().map(ThreadNode::count).sum::<usize>()
+    }
+
+    /// Embedded URLs of every cast in this subtree
+    pub fn embed_urls(&self) -> Vec<String> {
+        let mut urls = self.embeds.clone();
+        for reply in &self.replies {
+            urls.extend(reply.embed_urls());
+        }
+        urls
+    }
+
+    /// Attach fetched link previews to every cast in this subtree
+    pub fn attach_previews(&mut self, previews: &HashMap<String, EmbedPreview>) {
+        self.previews = self
+            .embeds
+            .iter()
+            .filter_map(|url| previews.get(url).cloned())
+            .collect();
+        for reply in &mut self.replies {
+            reply.attach_previews(previews);
+        }
+    }
+}
+
+/// FID and hash of the cast a message replies to
+fn parent_cast_id(message: &serde_json::Value) -> Option<(u64, String)> {
+    let parent = message.pointer("/data/castAddBody/parentCastId")?;
+    Some((
+        parent.get("fid")?.as_u64()?,
+        parent.get("hash")?.as_str()?.to_lowercase(),
+    ))
+}
+
+/// Assemble the reply tree below `message` from replies keyed by parent hash
+fn build_tree(
+    message: &serde_json::Value,
+    replies: &HashMap<String, Vec<serde_json::Value>>,
+    truncated: &HashSet<String>,
+) -> Option<ThreadNode> {
+    let mut node = ThreadNode::from_message(message)?;
+    node.truncated = truncated.contains(&node.hash);
+    if let Some(children) = replies.get(&node.hash) {
+        node.replies = children
+            .iter()
+            .filter_map(|child| build_tree(child, replies, truncated))
+            .collect();
+        node.replies.sort_by_key(|reply| reply.timestamp);
+    }
+    Some(node)
+}
+
+/// Hang the focal subtree below its ancestors, nearest ancestor first
+fn attach_ancestors(focus: ThreadNode, ancestors: &[serde_json::Value]) -> ThreadNode {
+    ancestors.iter().fold(focus, |child, ancestor| {
+        match ThreadNode::from_message(ancestor) {
+            Some(mut node) => {
+                node.replies.push(child);
+                node
+            }
+            None => child,
+        }
+    })
+}
+
+impl<M> FarcasterClient<M> {
+    /// Resolve the conversation around a cast into a tree
+    ///
+    /// Parents are followed upwards until the thread root or `max_parents`;
+    /// replies are expanded breadth-first below the requested cast until
+    /// `max_depth` or `max_casts` is reached.
+    ///
+    /// # Arguments
+    /// * `fid` - The FID of the cast author
+    /// * `hash` - The cast hash (0x-prefixed hex)
+    /// * `limits` - Bounds on the size of the tree
+    ///
+    /// # Returns
+    /// * `Result<CastThread>` - The conversation tree or an error
+    pub async fn get_thread(
+        &self,
+        fid: u64,
+        hash: &str,
+        limits: ThreadLimits,
+    ) -> Result<CastThread> {
+        let focus = self.get_cast_by_id(fid, hash).await?;
+        let focus_hash = focus
+            .get("hash")
+            .and_then(|h| h.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Hub returned a cast without a hash"))?
+            .to_lowercase();
+        let mut budget = limits.max_casts.max(1) - 1;
+
+        let mut ancestors = Vec::new();
+        let mut parent = parent_cast_id(&focus);
+        while let Some((parent_fid, parent_hash)) = parent.take() {
+            if ancestors.len() >= limits.max_parents || budget == 0 {
+                parent = Some((parent_fid, parent_hash));
+                break;
+            }
+            let message = self.get_cast_by_id(parent_fid, &parent_hash).await?;
+            parent = parent_cast_id(&message);
+            ancestors.push(message);
+            budget -= 1;
+        }
+        let top = ancestors.last().unwrap_or(&focus);
+        let parent_url = top
+            .pointer("/data/castAddBody/parentUrl")
+            .and_then(|u| u.as_str())
+            .map(|u| u.to_string());
+
+        let mut replies: HashMap<String, Vec<serde_json::Value>> = HashMap::new();
+        let mut truncated = HashSet::new();
+        let mut frontier = vec![(fid, focus_hash.clone())];
+        for _ in 0..limits.max_depth {
+            let mut next = Vec::new();
+            for (parent_fid, parent_hash) in frontier {
+                if budget == 0 {
+                    break;
+                }
+                // Ask for one extra reply to learn whether any were left out
+                let mut children = self
+                    .get_casts_by_parent(parent_fid, &parent_hash, (budget + 1) as u32)
+                    .await?;
+                if children.len() > budget {
+                    children.truncate(budget);
+                    truncated.insert(parent_hash.clone());
+                }
+                budget -= children.len();
+                next.extend(children.iter().filter_map(|child| {
+                    let node = ThreadNode::from_message(child)?;
+                    Some((node.fid, node.hash))
+                }));
+                replies.insert(parent_hash, children);
+            }
+            if next.is_empty() || budget == 0 {
+                break;
+            }
+            frontier = next;
+        }
+
+        let mut focus_node = build_tree(&focus, &replies, &truncated)
+            .ok_or_else(|| anyhow::anyhow!("Cast {} is not a cast message", hash))?;
+        focus_node.focus = true;
+        let root = attach_ancestors(focus_node, &ancestors);
+
+        Ok(CastThread {
+            cast_count: root.count(),
+            root,
+            focus_hash,
+            has_more_parents: parent.is_some(),
+            parent_url,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cast(
+        fid: u64,
+        hash: &str,
+        timestamp: u64,
+        parent: Option<(u64, &str)>,
+    ) -> serde_json::Value {
+        let mut body = serde_json::json!({ "text": format!("cast {hash}"), "embeds": [] });
+        if let Some((parent_fid, parent_hash)) = parent {
+            body["parentCastId"] = serde_json::json!({ "fid": parent_fid, "hash": parent_hash });
+        }
+        serde_json::json!({
+            "hash": hash,
+            "data": { "fid": fid, "timestamp": timestamp, "castAddBody": body }
+        })
+    }
+
+    #[test]
+    fn test_build_tree_with_ancestors() {
+        let root = cast(1, "0xa", 10, None);
+        let focus = cast(2, "0xb", 20, Some((1, "0xa")));
+        let late = cast(3, "0xd", 40, Some((2, "0xb")));
+        let early = cast(4, "0xc", 30, Some((2, "0xb")));
+        let nested = cast(5, "0xe", 50, Some((4, "0xc")));
+
+        let mut replies = HashMap::new();
+        replies.insert("0xb".to_string(), vec![late, early]);
+        replies.insert("0xc".to_string(), vec![nested]);
+        let truncated = HashSet::from(["0xc".to_string()]);
+
+        assert_eq!(parent_cast_id(&focus), Some((1, "0xa".to_string())));
+        let focus_node = build_tree(&focus, &replies, &truncated).unwrap();
+        let tree = attach_ancestors(focus_node, &[root]);
+
+        assert_eq!(tree.hash, "0xa");
+        assert_eq!(tree.count(), 5);
+        let focus_node = &tree.replies[0];
+        let hashes: Vec<&str> = focus_node.replies.iter().map(|r| r.hash.as_str()).collect();
+        assert_eq!(hashes, vec!["0xc", "0xd"]);
+        assert!(focus_node.replies[0].truncated);
+        assert_eq!(focus_node.replies[0].replies[0].fid, 5);
+    }
+}
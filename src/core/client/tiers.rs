@@ -0,0 +1,161 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::core::client::hub_client::fetch_page;
+use crate::core::client::hub_client::FarcasterClient;
+
+/// A subscription tier held by a FID, as reported by the hub
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TierSubscription {
+    pub tier_type: String,
+    /// Unix time the subscription ends; `None` when it does not expire
+    pub expires_at: Option<u64>,
+}
+
+impl TierSubscription {
+    /// Whether the subscription is still running at `now` (Unix time)
+    pub fn is_active(&self, now: u64) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+/// An onchain tier purchase indexed by the hub
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TierPurchaseEvent {
+    pub tier_type: String,
+    pub for_days: u64,
+    pub payer: Option<String>,
+    pub block_number: Option<u64>,
+    pub block_timestamp: Option<u64>,
+    pub transaction_hash: Option<String>,
+}
+
+/// Read a number the hub may encode as a JSON number or a string
+fn as_u64(value: Option<&serde_json::Value>) -> Option<u64> {
+    value.and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()))
+}
+
+/// Tier subscriptions in a storageLimitsByFid response
+///
+/// Accepts both the snake_case and camelCase field names hubs have used.
+pub fn tier_subscriptions(storage_limits: &serde_json::Value) -> Vec<TierSubscription> {
+    let Some(subscriptions) = storage_limits
+        .get("tier_subscriptions")
+        .or_else(|| storage_limits.get("tierSubscriptions"))
+        .and_then(|t| t.as_array())
+    else {
+        return Vec::new();
+    };
+    subscriptions
+        .iter()
+        .filter_map(|subscription| {
+            let tier_type = subscription
+                .get("tier_type")
+                .or_else(|| subscription.get("tierType"))?
+                .as_str()?
+                .to_string();
+            let expires_at = as_u64(
+                subscription
+                    .get("expires_at")
+                    .or_else(|| subscription.get("expiresAt")),
+            )
+            .filter(|expires_at| *expires_at > 0);
+            Some(TierSubscription {
+                tier_type,
+                expires_at,
+            })
+        })
+        .collect()
+}
+
+impl<M> FarcasterClient<M> {
+    /// Get the subscription tiers of a FID
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `Result<Vec<TierSubscription>>` - Current and expired subscriptions
+    pub async fn get_tier_subscriptions(&self, fid: u64) -> Result<Vec<TierSubscription>> {
+        let storage_limits = self.get_storage_limits(fid).await?;
+        Ok(tier_subscriptions(&storage_limits))
+    }
+
+    /// Get the tier purchases made for a FID, newest first
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `Result<Vec<TierPurchaseEvent>>` - Purchases indexed by the hub
+    pub async fn get_tier_purchases(&self, fid: u64) -> Result<Vec<TierPurchaseEvent>> {
+        let url = format!(
+            "{}/v1/onChainEventsByFid?fid={}&event_type=EVENT_TYPE_TIER_PURCHASE&reverse=true",
+            self.hub_url(),
+            fid
+        );
+        let page = fetch_page(self.http_client().clone(), url, "tier purchases").await?;
+        let events = page
+            .get("events")
+            .and_then(|e| e.as_array())
+            .map(|events| {
+                events
+                    .iter()
+                    .filter_map(|event| {
+                        let body = event.get("tierPurchaseEventBody")?;
+                        Some(TierPurchaseEvent {
+                            tier_type: body
+                                .get("tierType")
+                                .and_then(|t| t.as_str())
+                                .unwrap_or("unknown")
+                                .to_string(),
+                            for_days: as_u64(body.get("forDays")).unwrap_or(0),
+                            payer: body.get("payer").and_then(|p| p.as_str()).map(String::from),
+                            block_number: as_u64(event.get("blockNumber")),
+                            block_timestamp: as_u64(event.get("blockTimestamp")),
+                            transaction_hash: event
+                                .get("transactionHash")
+                                .and_then(|h| h.as_str())
+                                .map(String::from),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_tier_subscriptions() {
+        let snake = json!({
+            "limits": [],
+            "tier_subscriptions": [
+                {"tier_type": "Pro", "expires_at": 1_900_000_000u64},
+                {"tier_type": "Legacy", "expires_at": 0}
+            ]
+        });
+        let subscriptions = tier_subscriptions(&snake);
+        assert_eq!(subscriptions.len(), 2);
+        assert_eq!(subscriptions[0].expires_at, Some(1_900_000_000));
+        assert!(subscriptions[0].is_active(1_800_000_000));
+        assert!(!subscriptions[0].is_active(1_950_000_000));
+        assert_eq!(subscriptions[1].expires_at, None);
+
+        let camel = json!({"tierSubscriptions": [{"tierType": "Pro", "expiresAt": "1900000000"}]});
+        assert_eq!(
+            tier_subscriptions(&camel),
+            vec![TierSubscription {
+                tier_type: "Pro".to_string(),
+                expires_at: Some(1_900_000_000),
+            }]
+        );
+        assert!(tier_subscriptions(&json!({})).is_empty());
+    }
+}
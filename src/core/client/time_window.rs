@@ -0,0 +1,344 @@
+//! Time windows for hub queries
+//!
+//! `--since` and `--until` take a date (`2024-06-01`), an RFC 3339 time
+//! (`2024-06-01T12:00:00Z`) or an age counted back from now (`90m`, `12h`,
+//! `7d`, `4w`). A date given to `--until` includes that whole day.
+//!
+//! Hub list endpoints take no time range, so windows are applied while
+//! paging. Lists ordered by timestamp (casts and reactions) stop at the
+//! first message past the edge of the window instead of walking the rest;
+//! follow links are ordered by FID, so every page is read and filtered.
+
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::NaiveDate;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::client::adaptive_paging::fetch_page_adaptive;
+use crate::core::client::adaptive_paging::AdaptivePaging;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::hub_client::FARCASTER_EPOCH;
+
+/// Range of Farcaster timestamps a query is limited to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    /// Oldest Farcaster timestamp included
+    pub since: Option<u64>,
+    /// First Farcaster timestamp no longer included
+    pub until: Option<u64>,
+}
+
+/// How a hub list orders its messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListOrder {
+    /// By timestamp, newest first (`reverse=true` on a timestamp index)
+    NewestFirst,
+    /// By timestamp, oldest first
+    OldestFirst,
+    /// Not by timestamp, e.g. follow links ordered by FID
+    Unordered,
+}
+
+impl TimeWindow {
+    /// Window from `--since` and `--until` values
+    pub fn parse(since: Option<&str>, until: Option<&str>) -> Result<Self> {
+        let now = Utc::now();
+        let window = Self {
+            since: since.map(|s| parse_time(s, now, false)).transpose()?,
+            until: until.map(|u| parse_time(u, now, true)).transpose()?,
+        };
+        if let (Some(since), Some(until)) = (window.since, window.until) {
+            if since >= until {
+                anyhow::bail!("--since must be earlier than --until");
+            }
+        }
+        Ok(window)
+    }
+
+    /// Window of everything at or after a Farcaster timestamp
+    pub fn since(timestamp: u64) -> Self {
+        Self {
+            since: Some(timestamp),
+            until: None,
+        }
+    }
+
+    /// Whether the window lets every message through
+    pub fn is_unbounded(&self) -> bool {
+        self.since.is_none() && self.until.is_none()
+    }
+
+    /// Whether a hub message's timestamp falls inside the window
+    pub fn contains(&self, message: &Value) -> bool {
+        let Some(timestamp) = message_timestamp(message) else {
+            return self.is_unbounded();
+        };
+        self.since.is_none_or(|since| timestamp >= since)
+            && self.until.is_none_or(|until| timestamp < until)
+    }
+
+    /// Whether a message lies beyond the window in the list's direction, so
+    /// that every later message in the list does too
+    pub fn is_past(&self, message: &Value, order: ListOrder) -> bool {
+        let Some(timestamp) = message_timestamp(message) else {
+            return false;
+        };
+        match order {
+            ListOrder::NewestFirst => self.since.is_some_and(|since| timestamp < since),
+            ListOrder::OldestFirst => self.until.is_some_and(|until| timestamp >= until),
+            ListOrder::Unordered => false,
+        }
+    }
+
+    /// Short description such as "since 2024-06-01 00:00 until 2024-07-01 00:00"
+    pub fn describe(&self) -> String {
+        let format = |timestamp: u64| {
+            DateTime::from_timestamp((timestamp + FARCASTER_EPOCH) as i64, 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| timestamp.to_string())
+        };
+        let mut parts = Vec::new();
+        if let Some(since) = self.since {
+            parts.push(format!("since {}", format(since)));
+        }
+        if let Some(until) = self.until {
+            parts.push(format!("until {}", format(until)));
+        }
+        parts.join(" ")
+    }
+}
+
+/// Parse a `--since`/`--until` value into a Farcaster timestamp
+///
+/// # Arguments
+/// * `value` - A date, an RFC 3339 time or an age such as `7d`
+/// * `now` - Time ages are counted back from
+/// * `end_of_day` - Whether a bare date means the end of that day
+pub fn parse_time(value: &str, now: DateTime<Utc>, end_of_day: bool) -> Result<u64> {
+    let value = value.trim();
+    let time = if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let date = if end_of_day {
+            date.succ_opt().unwrap_or(date)
+        } else {
+            date
+        };
+        date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+    } else if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        time.with_timezone(&Utc)
+    } else {
+        now.checked_sub_signed(parse_age(value)?)
+            .ok_or_else(|| anyhow::anyhow!("{value} is before the Farcaster epoch (2021-01-01)"))?
+    };
+    let unix = time.timestamp();
+    if unix < FARCASTER_EPOCH as i64 {
+        anyhow::bail!("{value} is before the Farcaster epoch (2021-01-01)");
+    }
+    Ok(unix as u64 - FARCASTER_EPOCH)
+}
+
+/// Parse an age such as `90m`, `12h`, `7d` or `4w`
+fn parse_age(value: &str) -> Result<Duration> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid time '{value}': expected a date (2024-06-01), an RFC 3339 time or an age such as 12h, 7d or 4w"
+        )
+    };
+    let (split, _) = value.char_indices().last().ok_or_else(invalid)?;
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    if amount <= 0 {
+        return Err(invalid());
+    }
+    let age = match unit {
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => return Err(invalid()),
+    };
+    age.ok_or_else(|| anyhow::anyhow!("Invalid time '{value}': the age is too large"))
+}
+
+fn message_timestamp(message: &Value) -> Option<u64> {
+    message.pointer("/data/timestamp").and_then(Value::as_u64)
+}
+
+impl<M> FarcasterClient<M> {
+    /// Walk a paginated `messages` endpoint, keeping messages inside a window
+    ///
+    /// # Arguments
+    /// * `query` - Path and query string starting with `/v1/`, without paging parameters
+    /// * `order` - How the endpoint orders messages; timestamp-ordered lists
+    ///   stop at the first message past the window
+    /// * `window` - Time range to keep
+    /// * `limit` - Maximum number of messages to retrieve (0 for all)
+    /// * `what` - Short description used in error messages
+    ///
+    /// # Returns
+    /// * `Result<Vec<Value>>` - Messages inside the window, in list order
+    pub async fn get_messages_in_window(
+        &self,
+        query: &str,
+        order: ListOrder,
+        window: TimeWindow,
+        limit: u32,
+        what: &str,
+    ) -> Result<Vec<Value>> {
+        let base_url = format!("{}{query}", self.hub_url());
+        let paging = AdaptivePaging::default();
+        let mut page_token = None;
+        let mut kept = Vec::new();
+        loop {
+            let page = fetch_page_adaptive(
+                self.http_client().clone(),
+                base_url.clone(),
+                page_token.take(),
+                paging.clone(),
+                what,
+            )
+            .await?;
+            let messages = page
+                .get("messages")
+                .and_then(|m| m.as_array())
+                .cloned()
+                .unwrap_or_default();
+            for message in &messages {
+                if window.is_past(message, order) {
+                    return Ok(kept);
+                }
+                if window.contains(message) {
+                    kept.push(message.clone());
+                    if limit > 0 && kept.len() >= limit as usize {
+                        return Ok(kept);
+                    }
+                }
+            }
+            page_token = page
+                .get("nextPageToken")
+                .and_then(|t| t.as_str())
+                .filter(|t| !t.is_empty() && !messages.is_empty())
+                .map(|t| t.to_string());
+            if page_token.is_none() {
+                return Ok(kept);
+            }
+        }
+    }
+
+    /// Casts of a FID inside a window, newest first
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `window` - Time range to keep
+    /// * `limit` - Maximum number of casts to retrieve (0 for all)
+    pub async fn get_casts_in_window(
+        &self,
+        fid: u64,
+        window: TimeWindow,
+        limit: u32,
+    ) -> Result<Vec<Value>> {
+        let query = format!("/v1/castsByFid?fid={fid}&reverse=true");
+        self.get_messages_in_window(&query, ListOrder::NewestFirst, window, limit, "casts")
+            .await
+    }
+
+    /// Reactions made by a FID inside a window, newest first
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `reaction_type` - Hub reaction type such as `REACTION_TYPE_LIKE`, or all types
+    /// * `window` - Time range to keep
+    /// * `limit` - Maximum number of reactions to retrieve (0 for all)
+    pub async fn get_reactions_in_window(
+        &self,
+        fid: u64,
+        reaction_type: Option<&str>,
+        window: TimeWindow,
+        limit: u32,
+    ) -> Result<Vec<Value>> {
+        let mut query = format!("/v1/reactionsByFid?fid={fid}&reverse=true");
+        if let Some(reaction_type) = reaction_type {
+            query.push_str(&format!("&reaction_type={reaction_type}"));
+        }
+        self.get_messages_in_window(&query, ListOrder::NewestFirst, window, limit, "reactions")
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn at(timestamp: u64) -> Value {
+        json!({"data": {"timestamp": timestamp}})
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let now = DateTime::parse_from_rfc3339("2024-06-10T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let june_1 = 1_717_200_000 - FARCASTER_EPOCH;
+        assert_eq!(parse_time("2024-06-01", now, false).unwrap(), june_1);
+        // A bare date given to --until includes that whole day
+        assert_eq!(
+            parse_time("2024-06-01", now, true).unwrap(),
+            june_1 + 86_400
+        );
+        assert_eq!(
+            parse_time("2024-06-01T01:00:00+01:00", now, false).unwrap(),
+            june_1
+        );
+        assert_eq!(parse_time("9d", now, false).unwrap(), june_1);
+        assert_eq!(parse_time("216h", now, true).unwrap(), june_1);
+        assert!(parse_time("2020-12-31", now, false).is_err());
+        assert!(parse_time("soon", now, false).is_err());
+        assert!(parse_time("", now, false).is_err());
+    }
+
+    #[test]
+    fn test_parse_age_rejects_bad_amounts() {
+        assert_eq!(parse_age("90m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_age("4w").unwrap(), Duration::weeks(4));
+        // Non-ASCII units and amounts are refused, not split mid-character
+        assert!(parse_age("7日").is_err());
+        assert!(parse_age("5µ").is_err());
+        assert!(parse_age("µ").is_err());
+        // Overflow is an error rather than a panic
+        assert!(parse_age("99999999999999w").is_err());
+        assert!(parse_age(&format!("{}d", i64::MAX)).is_err());
+        assert!(parse_age("-3d").is_err());
+        assert!(parse_age("0h").is_err());
+
+        let now = Utc::now();
+        assert!(parse_time("99999999999999w", now, false).is_err());
+        assert!(parse_time("-3d", now, false).is_err());
+    }
+
+    #[test]
+    fn test_window_bounds() {
+        let window = TimeWindow {
+            since: Some(100),
+            until: Some(200),
+        };
+        assert!(!window.contains(&at(99)));
+        assert!(window.contains(&at(100)));
+        assert!(!window.contains(&at(200)));
+        assert!(window.is_past(&at(99), ListOrder::NewestFirst));
+        assert!(!window.is_past(&at(99), ListOrder::OldestFirst));
+        assert!(window.is_past(&at(200), ListOrder::OldestFirst));
+        assert!(!window.is_past(&at(99), ListOrder::Unordered));
+        assert!(TimeWindow::default().contains(&at(1)));
+
+        assert!(TimeWindow::parse(Some("2024-06-02"), Some("2024-06-01")).is_err());
+        assert_eq!(
+            TimeWindow::parse(None, None).unwrap(),
+            TimeWindow::default()
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::stream::StreamExt;
+use futures::stream::{
+    self,
+};
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::client::hub_client::fetch_page;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::utils::ttl_cache::TtlCache;
+
+/// FIDs fetched at the same time by default
+pub const DEFAULT_VERIFICATION_CONCURRENCY: usize = 8;
+
+/// How long cached verifications of a FID are reused
+const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Verified addresses of a FID
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FidVerifications {
+    pub fid: u64,
+    /// 0x-prefixed lowercase Ethereum addresses
+    pub eth_addresses: Vec<String>,
+    /// Base58 Solana addresses
+    pub sol_addresses: Vec<String>,
+}
+
+impl FidVerifications {
+    /// Collect the verified addresses in a verificationsByFid page
+    ///
+    /// # Arguments
+    /// * `page` - Hub response with a `messages` array
+    pub fn extend_from_page(&mut self, page: &serde_json::Value) {
+        let Some(messages) = page.get("messages").and_then(|m| m.as_array()) else {
+            return;
+        };
+        for message in messages {
+            let Some(body) = message
+                .get("data")
+                .and_then(|d| d.get("verificationAddAddressBody"))
+            else {
+                continue;
+            };
+            let Some(address) = body.get("address").and_then(|a| a.as_str()) else {
+                continue;
+            };
+            match body.get("protocol").and_then(|p| p.as_str()) {
+                Some("PROTOCOL_SOLANA") => {
+                    // Some hubs return the raw key bytes as hex rather than base58
+                    let address = match address.strip_prefix("0x").map(hex::decode) {
+                        Some(Ok(bytes)) => bs58::encode(bytes).into_string(),
+                        _ => address.to_string(),
+                    };
+                    if !self.sol_addresses.contains(&address) {
+                        self.sol_addresses.push(address);
+                    }
+                }
+                // Messages from before the protocol field was added are Ethereum
+                _ => {
+                    let address = address.to_lowercase();
+                    if !self.eth_addresses.contains(&address) {
+                        self.eth_addresses.push(address);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether the FID has no verified addresses
+    pub fn is_empty(&self) -> bool {
+        self.eth_addresses.is_empty() && self.sol_addresses.is_empty()
+    }
+}
+
+/// Canonical form of a verified address, as stored in [`FidVerifications`]
+///
+/// Ethereum addresses are lowercased; Solana addresses must be base58 encoded
+/// 32-byte keys and are returned unchanged.
+///
+/// # Arguments
+/// * `address` - A 0x-prefixed Ethereum address or a base58 Solana address
+pub fn normalize_address(address: &str) -> Result<String> {
+    let address = address.trim();
+    if let Some(hex_part) = address.strip_prefix("0x") {
+        if hex_part.len() == 40 && hex::decode(hex_part).is_ok() {
+            return Ok(address.to_lowercase());
+        }
+    } else if bs58::decode(address)
+        .into_vec()
+        .is_ok_and(|bytes| bytes.len() == 32)
+    {
+        return Ok(address.to_string());
+    }
+    anyhow::bail!("'{address}' is neither an Ethereum nor a Solana address")
+}
+
+impl<M> FarcasterClient<M> {
+    /// Get the verified Ethereum and Solana addresses of a FID
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    ///
+    /// # Returns
+    /// * `Result<FidVerifications>` - All verified addresses, across every page
+    pub async fn get_verifications(&self, fid: u64) -> Result<FidVerifications> {
+        let mut verifications = FidVerifications {
+            fid,
+            ..Default::default()
+        };
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut url = format!("{}/v1/verificationsByFid?fid={}", self.hub_url(), fid);
+            if let Some(token) = &page_token {
+                url.push_str(&format!("&pageToken={}", token));
+            }
+            let page = fetch_page(self.http_client().clone(), url, "verifications").await?;
+            verifications.extend_from_page(&page);
+
+            match page.get("nextPageToken").and_then(|t| t.as_str()) {
+                Some(token) if !token.is_empty() => page_token = Some(token.to_string()),
+                _ => break,
+            }
+        }
+        Ok(verifications)
+    }
+}
+
+/// FID ↔ address mapping of a set of FIDs
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerificationsMap {
+    /// Verifications in the order the FIDs were given
+    pub fids: Vec<FidVerifications>,
+    /// FIDs whose verifications could not be fetched, with the error
+    pub failed: Vec<(u64, String)>,
+}
+
+impl VerificationsMap {
+    /// Address → FIDs that verified it, for both protocols
+    pub fn reverse_index(&self) -> BTreeMap<String, Vec<u64>> {
+        let mut index: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+        for entry in &self.fids {
+            for address in entry.eth_addresses.iter().chain(&entry.sol_addresses) {
+                let fids = index.entry(address.clone()).or_default();
+                if !fids.contains(&entry.fid) {
+                    fids.push(entry.fid);
+                }
+            }
+        }
+        index
+    }
+}
+
+/// Fetches verifications of many FIDs with bounded concurrency and a cache
+///
+/// Only successful lookups are cached, so FIDs that failed are retried on
+/// the next run.
+pub struct VerificationMapper {
+    concurrency: usize,
+    cache: TtlCache<u64, FidVerifications>,
+}
+
+impl VerificationMapper {
+    /// Create a mapper with an in-memory cache only
+    pub fn new(concurrency: usize) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            cache: TtlCache::in_memory(CACHE_TTL),
+        }
+    }
+
+    /// Create a mapper whose cache is loaded from and saved to a JSON file
+    pub fn with_cache_file(concurrency: usize, path: PathBuf) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            cache: TtlCache::with_file(CACHE_TTL, path),
+        }
+    }
+
+    /// Default cache file in the data directory
+    pub fn default_cache_path() -> PathBuf {
+        crate::core::utils::data_dir::data_dir().join("verifications_cache.json")
+    }
+
+    /// FIDs with unexpired cached verifications, in ascending order
+    pub fn cached_fids(&self) -> Vec<u64> {
+        let mut fids = self.cache.fresh_keys();
+        fids.sort_unstable();
+        fids
+    }
+
+    /// Fetch the verifications of a set of FIDs
+    ///
+    /// # Arguments
+    /// * `client` - Hub client used for cache misses
+    /// * `fids` - FIDs to look up; duplicates are fetched once
+    ///
+    /// # Returns
+    /// * `VerificationsMap` - Verifications per FID, plus the FIDs that failed
+    pub async fn fetch_all<M>(
+        &self,
+        client: &FarcasterClient<M>,
+        fids: &[u64],
+    ) -> VerificationsMap {
+        let mut found: HashMap<u64, FidVerifications> = HashMap::new();
+        let mut missing = Vec::new();
+        let mut seen = HashSet::new();
+        for &fid in fids {
+            if !seen.insert(fid) {
+                continue;
+            }
+            match self.cache.get(&fid) {
+                Some(verifications) => {
+                    found.insert(fid, verifications);
+                }
+                None => missing.push(fid),
+            }
+        }
+
+        let fetched: Vec<(u64, Result<FidVerifications>)> = stream::iter(missing)
+            .map(|fid| async move { (fid, client.get_verifications(fid).await) })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut errors = HashMap::new();
+        for (fid, result) in fetched {
+            match result {
+                Ok(verifications) => {
+                    self.cache.insert(fid, verifications.clone());
+                    found.insert(fid, verifications);
+                }
+                Err(e) => {
+                    errors.insert(fid, e.to_string());
+                }
+            }
+        }
+
+        let mut map = VerificationsMap::default();
+        for &fid in fids {
+            if let Some(verifications) = found.remove(&fid) {
+                map.fids.push(verifications);
+            } else if let Some(error) = errors.remove(&fid) {
+                map.failed.push((fid, error));
+            }
+        }
+        map
+    }
+
+    /// Write the cache back to its file, dropping expired entries
+    pub fn save(&self) -> Result<()> {
+        self.cache.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_extend_from_page() {
+        let page = json!({
+            "messages": [
+                {"data": {"type": "MESSAGE_TYPE_VERIFICATION_ADD_ETH_ADDRESS", "verificationAddAddressBody": {
+                    "address": "0xABCDEF0000000000000000000000000000000001", "protocol": "PROTOCOL_ETHEREUM"}}},
+                {"data": {"type": "MESSAGE_TYPE_VERIFICATION_ADD_ETH_ADDRESS", "verificationAddAddressBody": {
+                    "address": "0x0101010101010101010101010101010101010101010101010101010101010101",
+                    "protocol": "PROTOCOL_SOLANA"}}},
+                {"data": {"type": "MESSAGE_TYPE_VERIFICATION_ADD_ETH_ADDRESS", "verificationAddAddressBody": {
+                    "address": "0xabcdef0000000000000000000000000000000001"}}}
+            ]
+        });
+        let mut verifications = FidVerifications {
+            fid: 3,
+            ..Default::default()
+        };
+        verifications.extend_from_page(&page);
+
+        assert_eq!(
+            verifications.eth_addresses,
+            vec!["0xabcdef0000000000000000000000000000000001"]
+        );
+        assert_eq!(
+            verifications.sol_addresses,
+            vec![bs58::encode([1u8; 32]).into_string()]
+        );
+    }
+
+    #[test]
+    fn test_normalize_address() {
+        assert_eq!(
+            normalize_address("0xABCDEF0000000000000000000000000000000001").unwrap(),
+            "0xabcdef0000000000000000000000000000000001"
+        );
+        let sol = bs58::encode([1u8; 32]).into_string();
+        assert_eq!(normalize_address(&sol).unwrap(), sol);
+        assert!(normalize_address("0x1234").is_err());
+        assert!(normalize_address("alice.eth").is_err());
+    }
+
+    #[test]
+    fn test_reverse_index() {
+        let map = VerificationsMap {
+            fids: vec![
+                FidVerifications {
+                    fid: 1,
+                    eth_addresses: vec!["0xaa".to_string()],
+                    sol_addresses: vec!["Sol1".to_string()],
+                },
+                FidVerifications {
+                    fid: 2,
+                    eth_addresses: vec!["0xaa".to_string(), "0xbb".to_string()],
+                    sol_addresses: vec![],
+                },
+            ],
+            failed: vec![],
+        };
+        let index = map.reverse_index();
+        assert_eq!(index["0xaa"], vec![1, 2]);
+        assert_eq!(index["0xbb"], vec![2]);
+        assert_eq!(index["Sol1"], vec![1]);
+    }
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use reqwest::Client;
+use reqwest::StatusCode;
+use serde::Serialize;
+
+/// Delivers JSON payloads to a webhook endpoint with retry and exponential backoff
+pub struct WebhookNotifier {
+    client: Client,
+    url: String,
+    max_retries: u32,
+    base_delay: Duration,
+}
+
+impl WebhookNotifier {
+    /// Create a new webhook notifier
+    ///
+    /// # Arguments
+    /// * `url` - The webhook endpoint URL (http or https)
+    /// * `max_retries` - Number of retries after the first failed attempt
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The notifier or an error if the URL is invalid
+    pub fn new(url: String, max_retries: u32) -> Result<Self> {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            anyhow::bail!("Webhook URL must start with http:// or https://: {}", url);
+        }
+
+        Ok(Self {
+            client: crate::core::utils::http::builder()?
+                .timeout(Duration::from_secs(10))
+                .build()
+                .with_context(|| "Failed to build webhook HTTP client")?,
+            url,
+            max_retries,
+            base_delay: Duration::from_secs(1),
+        })
+    }
+
+    /// Override the initial backoff delay (doubled after each failed attempt)
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Get the webhook URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// POST a JSON payload to the webhook, retrying on network errors, 429 and 5xx
+    ///
+    /// Other 4xx responses are treated as permanent failures and are not retried.
+    ///
+    /// # Arguments
+    /// * `payload` - Any serializable payload
+    ///
+    /// # Returns
+    /// * `Result<()>` - Ok once the endpoint accepted the payload
+    pub async fn deliver<T: Serialize>(&self, payload: &T) -> Result<()> {
+        let mut attempt = 0;
+
+        loop {
+            let last_error = match self.client.post(&self.url).json(payload).send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    let body = response.text().await.unwrap_or_default();
+                    if !is_retryable_status(status) {
+                        anyhow::bail!("Webhook rejected payload with {}: {}", status, body);
+                    }
+                    format!("{status}: {body}")
+                }
+                Err(e) => e.to_string(),
+            };
+
+            if attempt >= self.max_retries {
+                anyhow::bail!(
+                    "Webhook delivery failed after {} attempts: {}",
+                    attempt + 1,
+                    last_error
+                );
+            }
+
+            tokio::time::sleep(backoff_delay(self.base_delay, attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Whether a webhook response status is worth retrying
+pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+/// Exponential backoff capped at 60 seconds
+pub(crate) fn backoff_delay(base: Duration, attempt: u32) -> Duration {
+    base.saturating_mul(2u32.saturating_pow(attempt))
+        .min(Duration::from_secs(60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_url_validation() {
+        assert!(WebhookNotifier::new("https://example.com/hook".to_string(), 3).is_ok());
+        assert!(WebhookNotifier::new("ftp://example.com/hook".to_string(), 3).is_err());
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_backoff_delay_is_capped() {
+        let base = Duration::from_secs(1);
+        assert_eq!(backoff_delay(base, 0), Duration::from_secs(1));
+        assert_eq!(backoff_delay(base, 3), Duration::from_secs(8));
+        assert_eq!(backoff_delay(base, 20), Duration::from_secs(60));
+    }
+}
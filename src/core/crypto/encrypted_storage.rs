@@ -5,6 +5,8 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
 
 use aes_gcm::aead::Aead;
 use aes_gcm::aead::AeadCore;
@@ -16,8 +18,6 @@ use anyhow::Context;
 use anyhow::Result as AnyhowResult;
 use argon2::password_hash::rand_core::OsRng;
 use argon2::password_hash::SaltString;
-use argon2::Argon2;
-use argon2::PasswordHasher;
 use base64::engine::general_purpose;
 use base64::Engine as _;
 use bs58;
@@ -31,6 +31,15 @@ use hex;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::core::crypto::kdf;
+use crate::core::crypto::kdf::KdfParams;
+use crate::core::utils::data_dir;
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
+use crate::core::utils::private_file;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
+
 // Define CryptoError if it doesn't exist
 #[derive(Debug)]
 pub enum CryptoError {
@@ -88,6 +97,15 @@ struct EncryptedEthKeyData {
     nonce: String,
     /// Creation timestamp
     created_at: u64,
+    /// Key file format version
+    #[serde(default = "kdf::legacy_key_format_version")]
+    version: u32,
+    /// Argon2 parameters used to derive the encryption key
+    #[serde(default = "KdfParams::legacy")]
+    kdf: KdfParams,
+    /// BIP32 path the key was derived from, for keys generated from a mnemonic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    derivation_path: Option<String>,
 }
 
 /// Ethereum key information
@@ -99,6 +117,13 @@ pub struct EthKeyInfo {
     pub address: String,
     /// Creation timestamp
     pub created_at: u64,
+    /// BIP32 derivation path (None for imported or randomly generated keys)
+    pub derivation_path: Option<String>,
+}
+
+/// BIP44 derivation path for the Ethereum account at `index`: `m/44'/60'/0'/0/{index}`
+pub fn custody_derivation_path(index: u32) -> String {
+    format!("m/44'/60'/0'/0/{index}")
 }
 
 /// Encrypted Ed25519 key data structure
@@ -116,6 +141,15 @@ struct EncryptedEd25519KeyData {
     nonce: String,
     /// Creation timestamp
     created_at: u64,
+    /// Key file format version
+    #[serde(default = "kdf::legacy_key_format_version")]
+    version: u32,
+    /// Argon2 parameters used to derive the encryption key
+    #[serde(default = "KdfParams::legacy")]
+    kdf: KdfParams,
+    /// Label of a key kept next to the FID's key, see [`ImportConflict::KeepBoth`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
 }
 
 /// Ed25519 key information
@@ -127,16 +161,71 @@ pub struct Ed25519KeyInfo {
     pub public_key: String,
     /// Creation timestamp
     pub created_at: u64,
+    /// Label of a kept key; `None` for the key the FID signs with
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// What to do when importing a key for a FID that already has one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum ImportConflict {
+    /// Leave the store unchanged and fail
+    Abort,
+    /// Back up the keys file, then replace the FID's key
+    Overwrite,
+    /// Keep the FID's current key under a label and use the new one
+    KeepBoth,
+}
+
+/// How an import conflict was resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// The keys file was copied here before the key is replaced
+    BackedUp(std::path::PathBuf),
+    /// The previous key was kept under this label
+    Labeled(String),
+}
+
+/// Map key of a FID's key in the Ed25519 keys file
+///
+/// Kept keys are stored as `<fid>:<label>`, so files without labels keep
+/// their plain numeric keys.
+fn slot(fid: u64) -> String {
+    fid.to_string()
+}
+
+fn labeled_slot(fid: u64, label: &str) -> String {
+    format!("{fid}:{label}")
+}
+
+/// Copy a keys file to `<file>.<unix time>.bak`
+///
+/// # Returns
+/// * `Result<PathBuf>` - Path of the backup
+pub fn backup_keys_file(file_path: &str) -> Result<std::path::PathBuf, CryptoError> {
+    let backup = std::path::PathBuf::from(format!(
+        "{file_path}.{}.bak",
+        chrono::Utc::now().timestamp()
+    ));
+    fs::copy(file_path, &backup)?;
+    Ok(backup)
 }
 
 /// Internal implementation of EncryptedEd25519KeyManager
 struct EncryptedEd25519KeyManagerImpl {
-    encrypted_keys: HashMap<u64, EncryptedEd25519KeyData>,
+    /// Behind a lock so unlocking through `&self` can swap in an upgraded entry
+    encrypted_keys: Mutex<HashMap<String, EncryptedEd25519KeyData>>,
+    /// File the keys were loaded from, used to persist format upgrades on unlock
+    source_file: Option<String>,
 }
 
 /// Internal implementation of EncryptedEthKeyManager
 struct EncryptedEthKeyManagerImpl {
-    encrypted_keys: HashMap<u64, EncryptedEthKeyData>,
+    /// Behind a lock so unlocking through `&self` can swap in an upgraded entry
+    encrypted_keys: Mutex<HashMap<u64, EncryptedEthKeyData>>,
+    /// File the keys were loaded from, used to persist format upgrades on unlock
+    source_file: Option<String>,
 }
 
 /// Encrypted key manager trait
@@ -244,6 +333,21 @@ impl EncryptedEd25519KeyManager {
             .map_err(|e| CryptoError::Other(e.to_string()))
     }
 
+    /// Encrypt and store a signing key obtained elsewhere
+    ///
+    /// Used by the importers in [`crate::core::crypto::key_import`] for keys
+    /// from recovery phrases and other clients' export formats.
+    pub fn import_signing_key(
+        &mut self,
+        fid: u64,
+        signing_key: &SigningKey,
+        password: &str,
+    ) -> Result<(), CryptoError> {
+        self.inner
+            .store_signing_key(fid, signing_key, password)
+            .map_err(|e| CryptoError::Other(e.to_string()))
+    }
+
     /// Remove key for FID
     pub fn remove_key(&mut self, fid: u64) -> Result<(), CryptoError> {
         self.inner
@@ -251,6 +355,66 @@ impl EncryptedEd25519KeyManager {
             .map_err(|e| CryptoError::Other(e.to_string()))
     }
 
+    /// Remove a key kept under a label
+    pub fn remove_labeled_key(&mut self, fid: u64, label: &str) -> Result<(), CryptoError> {
+        self.inner
+            .keys()
+            .remove(&labeled_slot(fid, label))
+            .map(|_| ())
+            .ok_or_else(|| CryptoError::KeyNotFound(format!("FID {fid} labeled '{label}'")))
+    }
+
+    /// Free the FID's slot before importing a new key for it
+    ///
+    /// Does nothing when the FID has no key yet.
+    ///
+    /// # Arguments
+    /// * `fid` - The FID a key is being imported for
+    /// * `conflict` - What to do with the FID's current key
+    /// * `label` - Label for the current key with [`ImportConflict::KeepBoth`];
+    ///   defaults to `previous-<first 8 hex digits of its public key>`
+    /// * `file_path` - Keys file to back up with [`ImportConflict::Overwrite`]
+    ///
+    /// # Returns
+    /// * `Result<Option<ConflictResolution>>` - What was done, if there was a conflict
+    pub fn resolve_import_conflict(
+        &mut self,
+        fid: u64,
+        conflict: ImportConflict,
+        label: Option<&str>,
+        file_path: &str,
+    ) -> Result<Option<ConflictResolution>, CryptoError> {
+        if !self.has_key(fid) {
+            return Ok(None);
+        }
+        match conflict {
+            ImportConflict::Abort => Err(CryptoError::Other(format!(
+                "Ed25519 key for FID {fid} already exists; use --on-conflict overwrite or keep-both"
+            ))),
+            ImportConflict::Overwrite => {
+                let backup = if Path::new(file_path).exists() {
+                    Some(backup_keys_file(file_path)?)
+                } else {
+                    None
+                };
+                self.remove_key(fid)?;
+                Ok(backup.map(ConflictResolution::BackedUp))
+            }
+            ImportConflict::KeepBoth => {
+                let label = match label {
+                    Some(label) => label.to_string(),
+                    None => {
+                        let public_key = self.get_public_key(fid)?;
+                        let prefix = public_key.trim_start_matches("0x");
+                        format!("previous-{}", &prefix[..prefix.len().min(8)])
+                    }
+                };
+                self.inner.relabel_key(fid, &label)?;
+                Ok(Some(ConflictResolution::Labeled(label)))
+            }
+        }
+    }
+
     /// Save to file
     pub fn save_to_file(&self, file_path: &str) -> Result<(), CryptoError> {
         self.inner
@@ -258,6 +422,18 @@ impl EncryptedEd25519KeyManager {
             .map_err(|e| CryptoError::Other(e.to_string()))
     }
 
+    /// Get the signing key kept under a label
+    pub fn get_labeled_signing_key(
+        &self,
+        fid: u64,
+        label: &str,
+        password: &str,
+    ) -> Result<SigningKey, CryptoError> {
+        self.inner
+            .get_signing_key_in(&labeled_slot(fid, label), password)
+            .map_err(|e| CryptoError::Other(e.to_string()))
+    }
+
     /// Get signing key for FID
     pub fn get_signing_key(&self, fid: u64, password: &str) -> Result<SigningKey, CryptoError> {
         self.inner
@@ -361,19 +537,44 @@ impl EncryptedEthKeyManager {
             .map_err(|e| CryptoError::Other(e.to_string()))
     }
 
-    /// Generate from recovery phrase
+    /// Generate from recovery phrase using the first account (`m/44'/60'/0'/0/0`)
     pub async fn generate_from_recovery_phrase(
         &mut self,
         fid: u64,
         recovery_phrase: &str,
         password: &str,
+    ) -> Result<(), CryptoError> {
+        self.generate_from_recovery_phrase_at_path(
+            fid,
+            recovery_phrase,
+            password,
+            &custody_derivation_path(0),
+        )
+        .await
+    }
+
+    /// Generate from recovery phrase at an arbitrary BIP32 derivation path
+    pub async fn generate_from_recovery_phrase_at_path(
+        &mut self,
+        fid: u64,
+        recovery_phrase: &str,
+        password: &str,
+        derivation_path: &str,
     ) -> Result<(), CryptoError> {
         self.inner
-            .generate_from_recovery_phrase(fid, recovery_phrase, password)
+            .generate_from_recovery_phrase(fid, recovery_phrase, password, derivation_path)
             .await
             .map_err(|e| CryptoError::Other(e.to_string()))
     }
 
+    /// Get the derivation path a key was generated from, if it came from a mnemonic
+    pub fn get_derivation_path(&self, fid: u64) -> Option<String> {
+        self.inner
+            .keys()
+            .get(&fid)
+            .and_then(|k| k.derivation_path.clone())
+    }
+
     /// Import and encrypt an existing private key
     pub async fn import_and_encrypt(
         &mut self,
@@ -430,6 +631,7 @@ impl EncryptedEthKeyManager {
 /// Prompt for password
 pub fn prompt_password(prompt: &str) -> Result<String, anyhow::Error> {
     use rpassword::prompt_password;
+    prompt::ensure_interactive(InputKind::Password, prompt)?;
     Ok(prompt_password(prompt)?)
 }
 
@@ -440,15 +642,21 @@ impl EncryptedEd25519KeyManagerImpl {
     /// Create a new encrypted Ed25519 key manager
     fn new() -> Self {
         Self {
-            encrypted_keys: HashMap::new(),
+            encrypted_keys: Mutex::new(HashMap::new()),
+            source_file: None,
         }
     }
 
+    /// The keys, locked
+    fn keys(&self) -> MutexGuard<'_, HashMap<String, EncryptedEd25519KeyData>> {
+        self.encrypted_keys
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     /// Get the default keys file path
     fn default_keys_file() -> AnyhowResult<String> {
-        let home_dir =
-            dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
-        let keys_dir = home_dir.join(".castorix").join("keys");
+        let keys_dir = data_dir::keys_dir();
         std::fs::create_dir_all(&keys_dir)?;
         Ok(keys_dir
             .join("ed25519_keys.json")
@@ -458,6 +666,7 @@ impl EncryptedEd25519KeyManagerImpl {
 
     /// Load keys from file
     fn load_from_file(file_path: &str) -> AnyhowResult<Self> {
+        let _timer = metrics::start(Phase::DiskIo, "key file");
         if !Path::new(file_path).exists() {
             return Ok(Self::new());
         }
@@ -465,18 +674,22 @@ impl EncryptedEd25519KeyManagerImpl {
         let content = fs::read_to_string(file_path)
             .with_context(|| format!("Failed to read keys file: {file_path}"))?;
 
-        let encrypted_keys: HashMap<u64, EncryptedEd25519KeyData> =
+        let encrypted_keys: HashMap<String, EncryptedEd25519KeyData> =
             serde_json::from_str(&content).with_context(|| "Failed to parse keys file")?;
 
-        Ok(Self { encrypted_keys })
+        Ok(Self {
+            encrypted_keys: Mutex::new(encrypted_keys),
+            source_file: Some(file_path.to_string()),
+        })
     }
 
     /// Save keys to file
     fn save_to_file(&self, file_path: &str) -> AnyhowResult<()> {
-        let content = serde_json::to_string_pretty(&self.encrypted_keys)
+        let _timer = metrics::start(Phase::DiskIo, "key file");
+        let content = serde_json::to_string_pretty(&*self.keys())
             .with_context(|| "Failed to serialize keys")?;
 
-        fs::write(file_path, content)
+        private_file::write(Path::new(file_path), content.as_bytes())
             .with_context(|| format!("Failed to write keys file: {file_path}"))?;
 
         Ok(())
@@ -485,7 +698,7 @@ impl EncryptedEd25519KeyManagerImpl {
     /// Generate a new Ed25519 key pair and encrypt it
     async fn generate_and_encrypt(&mut self, fid: u64, password: &str) -> AnyhowResult<()> {
         // Check if key already exists for this FID
-        if self.encrypted_keys.contains_key(&fid) {
+        if self.keys().contains_key(&slot(fid)) {
             anyhow::bail!("Ed25519 key for FID {} already exists", fid);
         }
 
@@ -494,7 +707,7 @@ impl EncryptedEd25519KeyManagerImpl {
         let verifying_key = signing_key.verifying_key();
 
         // Encrypt only the signing key (private key)
-        let (encrypted_signing_key, salt, nonce) =
+        let (encrypted_signing_key, salt, nonce, kdf_params) =
             self.encrypt_key(&signing_key.to_bytes(), password)?;
 
         // Store public key unencrypted (it's not secret)
@@ -507,9 +720,12 @@ impl EncryptedEd25519KeyManagerImpl {
             salt,
             nonce,
             created_at: chrono::Utc::now().timestamp() as u64,
+            version: kdf::KEY_FORMAT_VERSION,
+            kdf: kdf_params,
+            label: None,
         };
 
-        self.encrypted_keys.insert(fid, key_data);
+        self.keys().insert(slot(fid), key_data);
         Ok(())
     }
 
@@ -521,7 +737,7 @@ impl EncryptedEd25519KeyManagerImpl {
         password: &str,
     ) -> AnyhowResult<()> {
         // Check if key already exists for this FID
-        if self.encrypted_keys.contains_key(&fid) {
+        if self.keys().contains_key(&slot(fid)) {
             anyhow::bail!("Ed25519 key for FID {} already exists", fid);
         }
 
@@ -561,10 +777,23 @@ impl EncryptedEd25519KeyManagerImpl {
             }
         };
 
+        self.store_signing_key(fid, &signing_key, password)
+    }
+
+    /// Encrypt a signing key and store it for a FID
+    fn store_signing_key(
+        &mut self,
+        fid: u64,
+        signing_key: &SigningKey,
+        password: &str,
+    ) -> AnyhowResult<()> {
+        if self.keys().contains_key(&slot(fid)) {
+            anyhow::bail!("Ed25519 key for FID {} already exists", fid);
+        }
         let verifying_key = signing_key.verifying_key();
 
         // Encrypt only the signing key (private key)
-        let (encrypted_signing_key, salt, nonce) =
+        let (encrypted_signing_key, salt, nonce, kdf_params) =
             self.encrypt_key(&signing_key.to_bytes(), password)?;
 
         // Store public key unencrypted (it's not secret)
@@ -577,36 +806,53 @@ impl EncryptedEd25519KeyManagerImpl {
             salt,
             nonce,
             created_at: chrono::Utc::now().timestamp() as u64,
+            version: kdf::KEY_FORMAT_VERSION,
+            kdf: kdf_params,
+            label: None,
         };
 
-        self.encrypted_keys.insert(fid, key_data);
+        self.keys().insert(slot(fid), key_data);
         Ok(())
     }
 
     /// Get public key for a FID
     fn get_public_key(&self, fid: u64) -> AnyhowResult<String> {
-        let key_data = self
-            .encrypted_keys
-            .get(&fid)
-            .ok_or_else(|| anyhow::anyhow!("No Ed25519 key found for FID: {}", fid))?;
-
-        Ok(key_data.public_key.clone())
+        self.keys()
+            .get(&slot(fid))
+            .map(|key_data| key_data.public_key.clone())
+            .ok_or_else(|| anyhow::anyhow!("No Ed25519 key found for FID: {}", fid))
     }
 
     /// Get decrypted signing key for a FID
     fn get_signing_key(&self, fid: u64, password: &str) -> AnyhowResult<SigningKey> {
+        if !self.has_key(fid) {
+            anyhow::bail!("No Ed25519 key found for FID: {}", fid);
+        }
+        self.get_signing_key_in(&slot(fid), password)
+    }
+
+    /// Get the decrypted signing key stored under a map key
+    fn get_signing_key_in(&self, slot: &str, password: &str) -> AnyhowResult<SigningKey> {
         let key_data = self
-            .encrypted_keys
-            .get(&fid)
-            .ok_or_else(|| anyhow::anyhow!("No Ed25519 key found for FID: {}", fid))?;
+            .keys()
+            .get(slot)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No Ed25519 key stored as {slot}"))?;
 
         let signing_key_bytes = self.decrypt_key(
             &key_data.encrypted_signing_key,
             &key_data.salt,
             &key_data.nonce,
+            &key_data.kdf,
             password,
         )?;
 
+        if kdf::needs_upgrade(key_data.version, &key_data.kdf) {
+            if let Err(e) = self.persist_upgrade(slot, &signing_key_bytes, password) {
+                eprintln!("⚠️  Could not upgrade encryption of Ed25519 key {slot}: {e}");
+            }
+        }
+
         Ok(SigningKey::from_bytes(
             &signing_key_bytes[..32]
                 .try_into()
@@ -622,26 +868,77 @@ impl EncryptedEd25519KeyManagerImpl {
 
     /// Check if key exists for FID
     fn has_key(&self, fid: u64) -> bool {
-        self.encrypted_keys.contains_key(&fid)
+        self.keys().contains_key(&slot(fid))
     }
 
-    /// List all keys
+    /// List all keys, each FID's key before its labeled ones
     fn list_keys(&self) -> Vec<Ed25519KeyInfo> {
-        self.encrypted_keys
-            .iter()
-            .map(|(fid, key_data)| Ed25519KeyInfo {
-                fid: *fid,
+        let mut keys: Vec<Ed25519KeyInfo> = self
+            .keys()
+            .values()
+            .map(|key_data| Ed25519KeyInfo {
+                fid: key_data.fid,
                 public_key: key_data.public_key.clone(),
                 created_at: key_data.created_at,
+                label: key_data.label.clone(),
             })
-            .collect()
+            .collect();
+        keys.sort_by(|a, b| (a.fid, &a.label).cmp(&(b.fid, &b.label)));
+        keys
     }
 
     /// Remove a key
     fn remove_key(&mut self, fid: u64) -> AnyhowResult<()> {
-        self.encrypted_keys
-            .remove(&fid)
+        self.keys()
+            .remove(&slot(fid))
+            .ok_or_else(|| anyhow::anyhow!("No key found for FID: {}", fid))?;
+        Ok(())
+    }
+
+    /// Move the FID's key to a labeled slot, freeing the FID for a new key
+    fn relabel_key(&mut self, fid: u64, label: &str) -> AnyhowResult<()> {
+        if label.is_empty() || label.contains(':') {
+            anyhow::bail!("Invalid key label '{label}': it must be non-empty and without ':'");
+        }
+        let labeled = labeled_slot(fid, label);
+        if self.keys().contains_key(&labeled) {
+            anyhow::bail!("FID {fid} already has a key labeled '{label}'");
+        }
+        let mut key_data = self
+            .keys()
+            .remove(&slot(fid))
             .ok_or_else(|| anyhow::anyhow!("No key found for FID: {}", fid))?;
+        key_data.label = Some(label.to_string());
+        self.keys().insert(labeled, key_data);
+        Ok(())
+    }
+
+    /// Re-encrypt a key with the current format and write it back to its source file
+    ///
+    /// The in-memory entry is replaced too, so later unlocks don't upgrade again.
+    fn persist_upgrade(&self, slot: &str, key_bytes: &[u8], password: &str) -> AnyhowResult<()> {
+        let Some(file_path) = self.source_file.clone() else {
+            return Ok(());
+        };
+        let (encrypted_signing_key, salt, nonce, kdf_params) =
+            self.encrypt_key(key_bytes, password)?;
+
+        let on_disk = Self::load_from_file(&file_path)?;
+        let upgraded = {
+            let mut keys = on_disk.keys();
+            let Some(entry) = keys.get_mut(slot) else {
+                return Ok(());
+            };
+            entry.encrypted_signing_key = encrypted_signing_key;
+            entry.salt = salt;
+            entry.nonce = nonce;
+            entry.version = kdf::KEY_FORMAT_VERSION;
+            entry.kdf = kdf_params;
+            entry.clone()
+        };
+        on_disk.save_to_file(&file_path)?;
+
+        self.keys().insert(slot.to_string(), upgraded);
         Ok(())
     }
 
@@ -650,18 +947,14 @@ impl EncryptedEd25519KeyManagerImpl {
         &self,
         key_bytes: &[u8],
         password: &str,
-    ) -> AnyhowResult<(String, String, String)> {
+    ) -> AnyhowResult<(String, String, String, KdfParams)> {
         // Generate salt
         let salt = SaltString::generate(&mut OsRng);
 
         // Derive key from password
-        let argon2 = Argon2::default();
-        let password_hash = argon2
-            .hash_password(password.as_bytes(), &salt)
-            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
-
-        let hash_bytes = password_hash.hash.unwrap();
-        let key = Key::<Aes256Gcm>::from_slice(&hash_bytes.as_bytes()[..32]);
+        let kdf_params = KdfParams::configured();
+        let derived = kdf_params.derive_key(password, &salt)?;
+        let key = Key::<Aes256Gcm>::from_slice(&derived);
         let cipher = Aes256Gcm::new(key);
 
         // Generate nonce
@@ -676,6 +969,7 @@ impl EncryptedEd25519KeyManagerImpl {
             general_purpose::STANDARD.encode(&ciphertext),
             general_purpose::STANDARD.encode(salt.as_str().as_bytes()),
             general_purpose::STANDARD.encode(nonce),
+            kdf_params,
         ))
     }
 
@@ -685,6 +979,7 @@ impl EncryptedEd25519KeyManagerImpl {
         encrypted_key: &str,
         salt: &str,
         nonce: &str,
+        kdf_params: &KdfParams,
         password: &str,
     ) -> AnyhowResult<Vec<u8>> {
         // Decode base64
@@ -707,14 +1002,9 @@ impl EncryptedEd25519KeyManagerImpl {
         let salt = SaltString::from_b64(&salt_str)
             .map_err(|e| anyhow::anyhow!("Failed to recreate salt: {}", e))?;
 
-        // Derive key from password
-        let argon2 = Argon2::default();
-        let password_hash = argon2
-            .hash_password(password.as_bytes(), &salt)
-            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
-
-        let hash_bytes = password_hash.hash.unwrap();
-        let key = Key::<Aes256Gcm>::from_slice(&hash_bytes.as_bytes()[..32]);
+        // Derive key from password using the recorded parameters
+        let derived = kdf_params.derive_key(password, &salt)?;
+        let key = Key::<Aes256Gcm>::from_slice(&derived);
         let cipher = Aes256Gcm::new(key);
 
         // Decrypt
@@ -731,15 +1021,21 @@ impl EncryptedEthKeyManagerImpl {
     /// Create a new encrypted Ethereum key manager
     fn new() -> Self {
         Self {
-            encrypted_keys: HashMap::new(),
+            encrypted_keys: Mutex::new(HashMap::new()),
+            source_file: None,
         }
     }
 
+    /// The keys, locked
+    fn keys(&self) -> MutexGuard<'_, HashMap<u64, EncryptedEthKeyData>> {
+        self.encrypted_keys
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     /// Get the default keys file path
     fn default_keys_file() -> AnyhowResult<String> {
-        let home_dir =
-            dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
-        let keys_dir = home_dir.join(".castorix").join("custody");
+        let keys_dir = data_dir::custody_dir();
         std::fs::create_dir_all(&keys_dir)?;
         Ok(keys_dir
             .join("custody_keys.json")
@@ -749,9 +1045,7 @@ impl EncryptedEthKeyManagerImpl {
 
     /// Get the custody key file path for a specific FID
     fn custody_key_file(fid: u64) -> AnyhowResult<String> {
-        let home_dir =
-            dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
-        let keys_dir = home_dir.join(".castorix").join("custody");
+        let keys_dir = data_dir::custody_dir();
         std::fs::create_dir_all(&keys_dir)?;
         Ok(keys_dir
             .join(format!("fid-{}-custody.json", fid))
@@ -761,6 +1055,7 @@ impl EncryptedEthKeyManagerImpl {
 
     /// Load keys from file
     fn load_from_file(file_path: &str) -> AnyhowResult<Self> {
+        let _timer = metrics::start(Phase::DiskIo, "key file");
         if !Path::new(file_path).exists() {
             return Ok(Self::new());
         }
@@ -771,29 +1066,34 @@ impl EncryptedEthKeyManagerImpl {
         let encrypted_keys: HashMap<u64, EncryptedEthKeyData> =
             serde_json::from_str(&content).with_context(|| "Failed to parse keys file")?;
 
-        Ok(Self { encrypted_keys })
+        Ok(Self {
+            encrypted_keys: Mutex::new(encrypted_keys),
+            source_file: Some(file_path.to_string()),
+        })
     }
 
     /// Save keys to file
     fn save_to_file(&self, file_path: &str) -> AnyhowResult<()> {
-        let content = serde_json::to_string_pretty(&self.encrypted_keys)
+        let _timer = metrics::start(Phase::DiskIo, "key file");
+        let content = serde_json::to_string_pretty(&*self.keys())
             .with_context(|| "Failed to serialize keys")?;
 
-        fs::write(file_path, content)
+        private_file::write(Path::new(file_path), content.as_bytes())
             .with_context(|| format!("Failed to write keys file: {file_path}"))?;
 
         Ok(())
     }
 
-    /// Generate Ethereum key from recovery phrase and encrypt it
+    /// Generate Ethereum key from recovery phrase at a BIP32 path and encrypt it
     async fn generate_from_recovery_phrase(
         &mut self,
         fid: u64,
         recovery_phrase: &str,
         password: &str,
+        derivation_path: &str,
     ) -> AnyhowResult<()> {
         // Check if key already exists for this FID
-        if self.encrypted_keys.contains_key(&fid) {
+        if self.keys().contains_key(&fid) {
             anyhow::bail!("Ethereum key for FID {} already exists", fid);
         }
 
@@ -803,9 +1103,6 @@ impl EncryptedEthKeyManagerImpl {
             .collect::<Vec<&str>>()
             .join(" ");
 
-        // Use BIP44 standard path for Ethereum: m/44'/60'/0'/0/0
-        let derivation_path = "m/44'/60'/0'/0/0";
-
         // Use ethers' built-in BIP44 derivation
         let wallet =
             ethers::signers::MnemonicBuilder::<ethers::signers::coins_bip39::English>::default()
@@ -825,7 +1122,7 @@ impl EncryptedEthKeyManagerImpl {
         let private_key_bytes = wallet.signer().to_bytes();
 
         // Encrypt the private key
-        let (encrypted_private_key, salt, nonce) =
+        let (encrypted_private_key, salt, nonce, kdf_params) =
             self.encrypt_key(&private_key_bytes, password)?;
 
         let key_data = EncryptedEthKeyData {
@@ -835,16 +1132,19 @@ impl EncryptedEthKeyManagerImpl {
             salt,
             nonce,
             created_at: chrono::Utc::now().timestamp() as u64,
+            version: kdf::KEY_FORMAT_VERSION,
+            kdf: kdf_params,
+            derivation_path: Some(derivation_path.to_string()),
         };
 
-        self.encrypted_keys.insert(fid, key_data);
+        self.keys().insert(fid, key_data);
         Ok(())
     }
 
     /// Generate a new Ethereum key pair and encrypt it
     async fn generate_and_encrypt(&mut self, fid: u64, password: &str) -> AnyhowResult<()> {
         // Check if key already exists for this FID
-        if self.encrypted_keys.contains_key(&fid) {
+        if self.keys().contains_key(&fid) {
             anyhow::bail!("Ethereum key for FID {} already exists", fid);
         }
 
@@ -854,7 +1154,7 @@ impl EncryptedEthKeyManagerImpl {
         let private_key_bytes = wallet.signer().to_bytes();
 
         // Encrypt the private key
-        let (encrypted_private_key, salt, nonce) =
+        let (encrypted_private_key, salt, nonce, kdf_params) =
             self.encrypt_key(&private_key_bytes, password)?;
 
         let key_data = EncryptedEthKeyData {
@@ -864,9 +1164,12 @@ impl EncryptedEthKeyManagerImpl {
             salt,
             nonce,
             created_at: chrono::Utc::now().timestamp() as u64,
+            version: kdf::KEY_FORMAT_VERSION,
+            kdf: kdf_params,
+            derivation_path: None,
         };
 
-        self.encrypted_keys.insert(fid, key_data);
+        self.keys().insert(fid, key_data);
         Ok(())
     }
 
@@ -878,7 +1181,7 @@ impl EncryptedEthKeyManagerImpl {
         password: &str,
     ) -> AnyhowResult<()> {
         // Check if key already exists for this FID
-        if self.encrypted_keys.contains_key(&fid) {
+        if self.keys().contains_key(&fid) {
             anyhow::bail!("Ethereum key for FID {} already exists", fid);
         }
 
@@ -908,7 +1211,7 @@ impl EncryptedEthKeyManagerImpl {
         let address = format!("{:?}", wallet.address());
 
         // Encrypt the private key
-        let (encrypted_private_key, salt, nonce) =
+        let (encrypted_private_key, salt, nonce, kdf_params) =
             self.encrypt_key(&private_key_bytes, password)?;
 
         let key_data = EncryptedEthKeyData {
@@ -918,35 +1221,46 @@ impl EncryptedEthKeyManagerImpl {
             salt,
             nonce,
             created_at: chrono::Utc::now().timestamp() as u64,
+            version: kdf::KEY_FORMAT_VERSION,
+            kdf: kdf_params,
+            derivation_path: None,
         };
 
-        self.encrypted_keys.insert(fid, key_data);
+        self.keys().insert(fid, key_data);
         Ok(())
     }
 
     /// Get Ethereum address for a FID
     fn get_address(&self, fid: u64) -> AnyhowResult<String> {
-        let key_data = self
-            .encrypted_keys
+        self.keys()
             .get(&fid)
-            .ok_or_else(|| anyhow::anyhow!("No Ethereum key found for FID: {}", fid))?;
-
-        Ok(key_data.address.clone())
+            .map(|key_data| key_data.address.clone())
+            .ok_or_else(|| anyhow::anyhow!("No Ethereum key found for FID: {}", fid))
     }
 
     /// Get decrypted private key for a FID
     fn get_private_key(&self, fid: u64, password: &str) -> AnyhowResult<Vec<u8>> {
         let key_data = self
-            .encrypted_keys
+            .keys()
             .get(&fid)
+            .cloned()
             .ok_or_else(|| anyhow::anyhow!("No Ethereum key found for FID: {}", fid))?;
 
-        self.decrypt_key(
+        let private_key = self.decrypt_key(
             &key_data.encrypted_private_key,
             &key_data.salt,
             &key_data.nonce,
+            &key_data.kdf,
             password,
-        )
+        )?;
+
+        if kdf::needs_upgrade(key_data.version, &key_data.kdf) {
+            if let Err(e) = self.persist_upgrade(fid, &private_key, password) {
+                eprintln!("⚠️  Could not upgrade encryption of custody key for FID {fid}: {e}");
+            }
+        }
+
+        Ok(private_key)
     }
 
     /// Get wallet for a FID
@@ -958,24 +1272,25 @@ impl EncryptedEthKeyManagerImpl {
 
     /// Check if key exists for FID
     fn has_key(&self, fid: u64) -> bool {
-        self.encrypted_keys.contains_key(&fid)
+        self.keys().contains_key(&fid)
     }
 
     /// List all keys
     fn list_keys(&self) -> Vec<EthKeyInfo> {
-        self.encrypted_keys
+        self.keys()
             .iter()
             .map(|(fid, key_data)| EthKeyInfo {
                 fid: *fid,
                 address: key_data.address.clone(),
                 created_at: key_data.created_at,
+                derivation_path: key_data.derivation_path.clone(),
             })
             .collect()
     }
 
     /// Remove a key
     fn remove_key(&mut self, fid: u64) -> AnyhowResult<()> {
-        self.encrypted_keys
+        self.keys()
             .remove(&fid)
             .ok_or_else(|| anyhow::anyhow!("No key found for FID: {}", fid))?;
         Ok(())
@@ -986,23 +1301,48 @@ impl EncryptedEthKeyManagerImpl {
         self.get_wallet(fid, password)
     }
 
+    /// Re-encrypt a key with the current format and write it back to its source file
+    ///
+    /// The in-memory entry is replaced too, so later unlocks don't upgrade again.
+    fn persist_upgrade(&self, fid: u64, key_bytes: &[u8], password: &str) -> AnyhowResult<()> {
+        let Some(file_path) = self.source_file.clone() else {
+            return Ok(());
+        };
+        let (encrypted_private_key, salt, nonce, kdf_params) =
+            self.encrypt_key(key_bytes, password)?;
+
+        let on_disk = Self::load_from_file(&file_path)?;
+        let upgraded = {
+            let mut keys = on_disk.keys();
+            let Some(entry) = keys.get_mut(&fid) else {
+                return Ok(());
+            };
+            entry.encrypted_private_key = encrypted_private_key;
+            entry.salt = salt;
+            entry.nonce = nonce;
+            entry.version = kdf::KEY_FORMAT_VERSION;
+            entry.kdf = kdf_params;
+            entry.clone()
+        };
+        on_disk.save_to_file(&file_path)?;
+
+        self.keys().insert(fid, upgraded);
+        Ok(())
+    }
+
     /// Encrypt a key with password
     fn encrypt_key(
         &self,
         key_bytes: &[u8],
         password: &str,
-    ) -> AnyhowResult<(String, String, String)> {
+    ) -> AnyhowResult<(String, String, String, KdfParams)> {
         // Generate salt
         let salt = SaltString::generate(&mut OsRng);
 
         // Derive key from password
-        let argon2 = Argon2::default();
-        let password_hash = argon2
-            .hash_password(password.as_bytes(), &salt)
-            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
-
-        let hash_bytes = password_hash.hash.unwrap();
-        let key = Key::<Aes256Gcm>::from_slice(&hash_bytes.as_bytes()[..32]);
+        let kdf_params = KdfParams::configured();
+        let derived = kdf_params.derive_key(password, &salt)?;
+        let key = Key::<Aes256Gcm>::from_slice(&derived);
         let cipher = Aes256Gcm::new(key);
 
         // Generate nonce
@@ -1017,6 +1357,7 @@ impl EncryptedEthKeyManagerImpl {
             general_purpose::STANDARD.encode(&ciphertext),
             general_purpose::STANDARD.encode(salt.as_str().as_bytes()),
             general_purpose::STANDARD.encode(nonce),
+            kdf_params,
         ))
     }
 
@@ -1026,6 +1367,7 @@ impl EncryptedEthKeyManagerImpl {
         encrypted_key: &str,
         salt: &str,
         nonce: &str,
+        kdf_params: &KdfParams,
         password: &str,
     ) -> AnyhowResult<Vec<u8>> {
         // Decode base64
@@ -1048,14 +1390,9 @@ impl EncryptedEthKeyManagerImpl {
         let salt = SaltString::from_b64(&salt_str)
             .map_err(|e| anyhow::anyhow!("Failed to recreate salt: {}", e))?;
 
-        // Derive key from password
-        let argon2 = Argon2::default();
-        let password_hash = argon2
-            .hash_password(password.as_bytes(), &salt)
-            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
-
-        let hash_bytes = password_hash.hash.unwrap();
-        let key = Key::<Aes256Gcm>::from_slice(&hash_bytes.as_bytes()[..32]);
+        // Derive key from password using the recorded parameters
+        let derived = kdf_params.derive_key(password, &salt)?;
+        let key = Key::<Aes256Gcm>::from_slice(&derived);
         let cipher = Aes256Gcm::new(key);
 
         // Decrypt
@@ -1067,3 +1404,125 @@ impl EncryptedEthKeyManagerImpl {
         Ok(plaintext)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+    #[tokio::test]
+    async fn test_mnemonic_accounts_by_index() {
+        let mut manager = EncryptedEthKeyManager::new();
+        manager
+            .generate_from_recovery_phrase(1, TEST_MNEMONIC, "pw")
+            .await
+            .unwrap();
+        manager
+            .generate_from_recovery_phrase_at_path(
+                2,
+                TEST_MNEMONIC,
+                "pw",
+                &custody_derivation_path(1),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            manager.get_address(1).unwrap(),
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+        );
+        assert_eq!(
+            manager.get_address(2).unwrap(),
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"
+        );
+        assert_eq!(
+            manager.get_derivation_path(2).as_deref(),
+            Some("m/44'/60'/0'/0/1")
+        );
+    }
+
+    #[test]
+    fn test_resolve_import_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("ed25519_keys.json");
+        let file = file.to_str().unwrap();
+        let first = SigningKey::from_bytes(&[1; 32]);
+        let second = SigningKey::from_bytes(&[2; 32]);
+
+        let mut manager = EncryptedEd25519KeyManager::new();
+        assert_eq!(
+            manager
+                .resolve_import_conflict(7, ImportConflict::Abort, None, file)
+                .unwrap(),
+            None
+        );
+        manager.import_signing_key(7, &first, "pw").unwrap();
+        manager.save_to_file(file).unwrap();
+        assert!(manager
+            .resolve_import_conflict(7, ImportConflict::Abort, None, file)
+            .is_err());
+
+        let resolution = manager
+            .resolve_import_conflict(7, ImportConflict::KeepBoth, Some("old"), file)
+            .unwrap();
+        assert_eq!(resolution, Some(ConflictResolution::Labeled("old".into())));
+        manager.import_signing_key(7, &second, "pw").unwrap();
+        manager.save_to_file(file).unwrap();
+
+        let mut manager = EncryptedEd25519KeyManager::load_from_file(file).unwrap();
+        let keys = manager.list_keys();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].label, None);
+        assert_eq!(keys[1].label.as_deref(), Some("old"));
+        assert_eq!(
+            manager.get_signing_key(7, "pw").unwrap().to_bytes(),
+            second.to_bytes()
+        );
+        assert_eq!(
+            manager
+                .get_labeled_signing_key(7, "old", "pw")
+                .unwrap()
+                .to_bytes(),
+            first.to_bytes()
+        );
+
+        let Some(ConflictResolution::BackedUp(backup)) = manager
+            .resolve_import_conflict(7, ImportConflict::Overwrite, None, file)
+            .unwrap()
+        else {
+            panic!("expected a backup");
+        };
+        assert!(!manager.has_key(7));
+        let backed_up = EncryptedEd25519KeyManager::load_from_file(backup.to_str().unwrap());
+        assert!(backed_up.unwrap().has_key(7));
+    }
+
+    #[test]
+    fn test_upgrade_on_unlock_replaces_in_memory_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("ed25519_keys.json");
+        let file = file.to_str().unwrap();
+        let key = SigningKey::from_bytes(&[3; 32]);
+
+        let mut manager = EncryptedEd25519KeyManager::new();
+        manager.import_signing_key(7, &key, "pw").unwrap();
+        if let Some(entry) = manager.inner.keys().get_mut(&slot(7)) {
+            entry.version = kdf::LEGACY_KEY_FORMAT_VERSION;
+        }
+        manager.save_to_file(file).unwrap();
+
+        let manager = EncryptedEd25519KeyManager::load_from_file(file).unwrap();
+        assert_eq!(
+            manager.get_signing_key(7, "pw").unwrap().to_bytes(),
+            key.to_bytes()
+        );
+        let in_memory = manager.inner.keys()[&slot(7)].clone();
+        assert_eq!(in_memory.version, kdf::KEY_FORMAT_VERSION);
+
+        let on_disk = EncryptedEd25519KeyManager::load_from_file(file).unwrap();
+        let on_disk = on_disk.inner.keys()[&slot(7)].clone();
+        assert_eq!(on_disk.version, kdf::KEY_FORMAT_VERSION);
+        assert_eq!(on_disk.nonce, in_memory.nonce);
+    }
+}
@@ -0,0 +1,152 @@
+//! Password-based key derivation for encrypted key files
+//!
+//! Key files record the Argon2id parameters they were encrypted with, so the
+//! defaults can be raised later without breaking decryption of older files.
+//! Files written before parameters were recorded (format version 1) used the
+//! argon2 crate defaults and are read with [`KdfParams::legacy`].
+
+use anyhow::Result;
+use argon2::password_hash::SaltString;
+use argon2::Algorithm;
+use argon2::Argon2;
+use argon2::Params;
+use argon2::PasswordHasher;
+use argon2::Version;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
+
+/// Format version of key files that do not record KDF parameters
+pub const LEGACY_KEY_FORMAT_VERSION: u32 = 1;
+
+/// Current key file format version
+pub const KEY_FORMAT_VERSION: u32 = 2;
+
+/// Serde default for key files without a `version` field
+pub fn legacy_key_format_version() -> u32 {
+    LEGACY_KEY_FORMAT_VERSION
+}
+
+/// Argon2id cost parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    /// Number of passes
+    pub iterations: u32,
+    /// Degree of parallelism (lanes)
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self::recommended()
+    }
+}
+
+impl KdfParams {
+    /// Parameters implied by `Argon2::default()`, used by format version 1 files
+    pub fn legacy() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+
+    /// Parameters used for newly encrypted keys unless configured otherwise
+    pub fn recommended() -> Self {
+        Self {
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 4,
+        }
+    }
+
+    /// Parameters configured for this installation (see `CASTORIX_KDF_*`)
+    pub fn configured() -> Self {
+        crate::consts::get_config().kdf_params()
+    }
+
+    /// Check that Argon2 accepts these parameters
+    pub fn validate(&self) -> Result<()> {
+        self.argon2().map(|_| ())
+    }
+
+    /// Whether keys protected with these parameters should be re-encrypted with `target`
+    pub fn is_weaker_than(&self, target: &KdfParams) -> bool {
+        self.memory_kib < target.memory_kib || self.iterations < target.iterations
+    }
+
+    /// Derive a 32-byte encryption key from a password and salt
+    ///
+    /// # Arguments
+    /// * `password` - The user's password
+    /// * `salt` - Per-key random salt
+    ///
+    /// # Returns
+    /// * `Result<[u8; 32]>` - The derived AES-256 key
+    pub fn derive_key(&self, password: &str, salt: &SaltString) -> Result<[u8; 32]> {
+        let _timer = metrics::start(Phase::Signing, "Argon2 key derivation");
+        let password_hash = self
+            .argon2()?
+            .hash_password(password.as_bytes(), salt)
+            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
+        let hash = password_hash
+            .hash
+            .ok_or_else(|| anyhow::anyhow!("Argon2 produced no hash output"))?;
+
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&hash.as_bytes()[..32]);
+        Ok(key)
+    }
+
+    fn argon2(&self) -> Result<Argon2<'static>> {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(|e| anyhow::anyhow!("Invalid Argon2 parameters: {}", e))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
+
+/// Whether a key file entry should be re-encrypted on unlock
+///
+/// # Arguments
+/// * `version` - Format version recorded in the entry
+/// * `kdf` - KDF parameters recorded in the entry
+pub fn needs_upgrade(version: u32, kdf: &KdfParams) -> bool {
+    version < KEY_FORMAT_VERSION || kdf.is_weaker_than(&KdfParams::configured())
+}
+
+#[cfg(test)]
+mod tests {
+    use argon2::password_hash::rand_core::OsRng;
+
+    use super::*;
+
+    #[test]
+    fn test_legacy_params_match_argon2_default() {
+        let salt = SaltString::generate(&mut OsRng);
+        let expected = Argon2::default()
+            .hash_password(b"correct horse", &salt)
+            .unwrap()
+            .hash
+            .unwrap();
+
+        let derived = KdfParams::legacy()
+            .derive_key("correct horse", &salt)
+            .unwrap();
+        assert_eq!(&derived[..], &expected.as_bytes()[..32]);
+    }
+
+    #[test]
+    fn test_upgrade_detection() {
+        assert!(KdfParams::legacy().is_weaker_than(&KdfParams::recommended()));
+        assert!(!KdfParams::recommended().is_weaker_than(&KdfParams::legacy()));
+        assert!(needs_upgrade(
+            LEGACY_KEY_FORMAT_VERSION,
+            &KdfParams::recommended()
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+//! Security audit of locally stored keys
+//!
+//! Provides the report types used by `castorix key audit` together with the
+//! offline checks (file permissions, password strength). Checks that need the
+//! hub or the chain are performed by the CLI handler and added to the report.
+
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Minimum password score (0-4) considered acceptable for key encryption
+pub const MIN_PASSWORD_SCORE: u8 = 3;
+
+/// Common passwords and keyboard runs, also when capitalised, written in
+/// leetspeak or padded with digits and symbols
+const COMMON_PASSWORDS: &[&str] = &[
+    "password",
+    "passwd",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "qwerty",
+    "qwertyuiop",
+    "asdfgh",
+    "asdfghjkl",
+    "zxcvbnm",
+    "iloveyou",
+    "letmein",
+    "welcome",
+    "monkey",
+    "dragon",
+    "sunshine",
+    "princess",
+    "football",
+    "baseball",
+    "master",
+    "admin",
+    "hunter",
+    "trustno",
+    "summer",
+    "winter",
+    "secret",
+    "farcaster",
+    "castorix",
+    "changeme",
+];
+
+/// Decorations around a common password that still leave it guessable
+const MAX_DECORATION: usize = 4;
+
+/// How serious an audit finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSeverity {
+    High,
+    Medium,
+    Low,
+}
+
+impl std::fmt::Display for AuditSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditSeverity::High => write!(f, "HIGH"),
+            AuditSeverity::Medium => write!(f, "MEDIUM"),
+            AuditSeverity::Low => write!(f, "LOW"),
+        }
+    }
+}
+
+/// A single weakness found for a stored key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditFinding {
+    pub severity: AuditSeverity,
+    /// Human-readable key identifier (e.g. `wallet my-key` or `custody FID 123`)
+    pub key: String,
+    pub issue: String,
+    pub recommendation: String,
+}
+
+/// Result of auditing all stored keys
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyAuditReport {
+    /// Number of key files or entries inspected
+    pub keys_checked: usize,
+    pub findings: Vec<AuditFinding>,
+}
+
+impl KeyAuditReport {
+    /// Record a finding
+    pub fn add(
+        &mut self,
+        severity: AuditSeverity,
+        key: impl Into<String>,
+        issue: impl Into<String>,
+        recommendation: impl Into<String>,
+    ) {
+        self.findings.push(AuditFinding {
+            severity,
+            key: key.into(),
+            issue: issue.into(),
+            recommendation: recommendation.into(),
+        });
+    }
+
+    /// Number of findings with the given severity
+    pub fn count(&self, severity: AuditSeverity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+
+    /// Sort findings so the most severe come first
+    pub fn sort(&mut self) {
+        self.findings.sort_by_key(|f| f.severity);
+    }
+}
+
+/// Estimate password strength on a 0-4 scale (0 = trivially guessable, 4 = strong)
+///
+/// Character classes earn nothing on their own. The usual decorations -
+/// capitals, leetspeak, digits and symbols tacked on either end - are
+/// stripped first, and a common password or keyboard run underneath scores
+/// 0 or 1 however it is dressed up, so `Password1!` rates like `password`.
+/// Anything else is scored by a brute-force estimate of its length and
+/// alphabet, with repeated characters counted once.
+pub fn password_strength(password: &str) -> u8 {
+    let lowered = password.to_lowercase();
+    let core = lowered.trim_matches(|c: char| !c.is_alphabetic());
+    let core = if core.is_empty() {
+        lowered.as_str()
+    } else {
+        core
+    };
+    let plain: String = core.chars().map(unleet).collect();
+    if [lowered.as_str(), core, plain.as_str()]
+        .iter()
+        .any(|candidate| COMMON_PASSWORDS.contains(candidate) || is_trivial_pattern(candidate))
+    {
+        let decoration = lowered.chars().count() - core.chars().count();
+        return u8::from(decoration > MAX_DECORATION);
+    }
+
+    let alphabet: u32 = [
+        (password.chars().any(|c| c.is_ascii_lowercase()), 26),
+        (password.chars().any(|c| c.is_ascii_uppercase()), 26),
+        (password.chars().any(|c| c.is_ascii_digit()), 10),
+        (password.chars().any(|c| !c.is_ascii_alphanumeric()), 33),
+    ]
+    .iter()
+    .filter(|(present, _)| *present)
+    .map(|(_, size)| size)
+    .sum();
+    let chars: Vec<char> = password.chars().collect();
+    let distinct_runs = 1 + chars.windows(2).filter(|w| w[0] != w[1]).count();
+    let bits = distinct_runs as f64 * f64::from(alphabet).log2();
+    match bits {
+        b if b < 30.0 => 0,
+        b if b < 45.0 => 1,
+        b if b < 60.0 => 2,
+        b if b < 75.0 => 3,
+        _ => 4,
+    }
+}
+
+/// Undo common leetspeak substitutions
+fn unleet(c: char) -> char {
+    match c {
+        '0' => 'o',
+        '1' | '!' => 'i',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' => 't',
+        other => other,
+    }
+}
+
+/// A single repeated character or a run of consecutive characters (e.g. `abcdef`, `987654`)
+fn is_trivial_pattern(password: &str) -> bool {
+    let chars: Vec<char> = password.chars().collect();
+    if chars.len() < 2 {
+        return true;
+    }
+    let same = chars.windows(2).all(|w| w[0] == w[1]);
+    let ascending = chars.windows(2).all(|w| w[1] as i64 - w[0] as i64 == 1);
+    let descending = chars.windows(2).all(|w| w[0] as i64 - w[1] as i64 == 1);
+    same || ascending || descending
+}
+
+/// Describe a permission problem with a key file or directory, if any
+///
+/// Key material should only be accessible by its owner; any group or other
+/// permission bits are reported. Always `None` on non-Unix platforms.
+pub fn permission_issue(path: &Path) -> Option<String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = std::fs::metadata(path).ok()?.permissions().mode() & 0o777;
+        if mode & 0o077 != 0 {
+            return Some(format!(
+                "{} has mode {:o}; group/other users can access it",
+                path.display(),
+                mode
+            ));
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_strength() {
+        assert_eq!(password_strength("password"), 0);
+        assert_eq!(password_strength("abcdefgh"), 0);
+        assert_eq!(password_strength("aaaaaaaaaaaa"), 0);
+        assert!(password_strength("hunter22") < MIN_PASSWORD_SCORE);
+        assert!(password_strength("Correct-Horse-Battery-9") >= MIN_PASSWORD_SCORE);
+    }
+
+    #[test]
+    fn test_decorated_common_passwords_are_weak() {
+        for weak in [
+            "Password1!",
+            "P@ssw0rd",
+            "password123",
+            "Qwerty123!",
+            "iloveyou2024",
+            "Summer2024!",
+            "Farcaster!1",
+            "hunter2",
+            "Tru5tNo1",
+            "abc123",
+            "11111111!",
+        ] {
+            assert!(
+                password_strength(weak) <= 1,
+                "{weak} scored {}",
+                password_strength(weak)
+            );
+        }
+        // Mixing classes is not enough without length
+        assert!(password_strength("aB3$") < MIN_PASSWORD_SCORE);
+        assert!(password_strength("kT9#qz2Lm!xW") >= MIN_PASSWORD_SCORE);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permission_issue() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::set_permissions(file.path(), std::fs::Permissions::from_mode(0o600)).unwrap();
+        assert!(permission_issue(file.path()).is_none());
+
+        std::fs::set_permissions(file.path(), std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(permission_issue(file.path()).is_some());
+    }
+}
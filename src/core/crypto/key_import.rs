@@ -0,0 +1,264 @@
+//! Signer keys from other Farcaster clients' export formats
+//!
+//! - **Recovery phrase**: an Ed25519 key derived from a BIP39 mnemonic with
+//!   SLIP-0010 along a hardened path, [`DEFAULT_SIGNER_DERIVATION_PATH`] unless
+//!   the path the previous client used is given.
+//! - **Protobuf export**: a length-delimited message with the private key in
+//!   field 1 and, optionally, the public key in field 2, as raw bytes, hex or base64.
+//! - **JSON export**: an object with `privateKey` (or `private_key`) in hex or
+//!   base64, optionally with `publicKey` to check against.
+//!
+//! Whatever the source, the public key is checked against any key stored
+//! alongside the private key, so a corrupt export is rejected rather than
+//! imported as a key that signs for nothing.
+
+use anyhow::Context;
+use anyhow::Result;
+use base64::engine::general_purpose;
+use base64::Engine;
+use ed25519_dalek::SigningKey;
+use hmac::Hmac;
+use hmac::Mac;
+use sha2::Sha512;
+
+/// Default SLIP-0010 path for signers derived from a recovery phrase
+pub const DEFAULT_SIGNER_DERIVATION_PATH: &str = "m/44'/60'/0'/0'/0'";
+
+/// Derive an Ed25519 signer from a BIP39 recovery phrase
+///
+/// # Arguments
+/// * `phrase` - The recovery phrase
+/// * `passphrase` - Optional BIP39 passphrase ("" for none)
+/// * `path` - SLIP-0010 derivation path; every component must be hardened
+///
+/// # Returns
+/// * `Result<SigningKey>` - The derived signing key
+pub fn signing_key_from_mnemonic(phrase: &str, passphrase: &str, path: &str) -> Result<SigningKey> {
+    let mnemonic = bip39::Mnemonic::parse_normalized(phrase.trim())
+        .map_err(|e| anyhow::anyhow!("Invalid recovery phrase: {e}"))?;
+    let seed = mnemonic.to_seed_normalized(passphrase);
+    let key = slip10_derive(&seed, &parse_hardened_path(path)?);
+    Ok(SigningKey::from_bytes(&key))
+}
+
+/// Parse a derivation path such as `m/44'/60'/0'/0'/0'` into hardened indexes
+fn parse_hardened_path(path: &str) -> Result<Vec<u32>> {
+    let mut components = path.trim().split('/');
+    if components.next() != Some("m") {
+        anyhow::bail!("Derivation path must start with 'm/': {path}");
+    }
+    components
+        .map(|component| {
+            let index = component
+                .strip_suffix('\'')
+                .or_else(|| component.strip_suffix('h'))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Ed25519 derivation only supports hardened components, got '{component}'"
+                    )
+                })?;
+            let index: u32 = index
+                .parse()
+                .with_context(|| format!("Invalid path component '{component}'"))?;
+            if index >= 0x8000_0000 {
+                anyhow::bail!("Path component '{component}' is out of range");
+            }
+            Ok(index | 0x8000_0000)
+        })
+        .collect()
+}
+
+/// SLIP-0010 private key derivation for Ed25519
+fn slip10_derive(seed: &[u8], path: &[u32]) -> [u8; 32] {
+    let hmac = |key: &[u8], data: &[&[u8]]| {
+        let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts any key length");
+        for part in data {
+            mac.update(part);
+        }
+        let output = mac.finalize().into_bytes();
+        let mut key = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        key.copy_from_slice(&output[..32]);
+        chain_code.copy_from_slice(&output[32..]);
+        (key, chain_code)
+    };
+
+    let (mut key, mut chain_code) = hmac(b"ed25519 seed", &[seed]);
+    for index in path {
+        (key, chain_code) = hmac(&chain_code, &[&[0u8], &key, &index.to_be_bytes()]);
+    }
+    key
+}
+
+/// Read a signer from a protobuf key export
+///
+/// # Arguments
+/// * `data` - The export as raw bytes, or as hex/base64 text
+///
+/// # Returns
+/// * `Result<SigningKey>` - The signing key
+pub fn signing_key_from_protobuf(data: &[u8]) -> Result<SigningKey> {
+    let bytes = decode_text_or_raw(data)?;
+    let mut private_key = None;
+    let mut public_key = None;
+    let mut rest = bytes.as_slice();
+    while !rest.is_empty() {
+        let (tag, after_tag) = read_varint(rest)?;
+        let (field, wire_type) = (tag >> 3, tag & 7);
+        rest = after_tag;
+        match wire_type {
+            // varint
+            0 => rest = read_varint(rest)?.1,
+            // length-delimited
+            2 => {
+                let (len, after_len) = read_varint(rest)?;
+                let len = len as usize;
+                if after_len.len() < len {
+                    anyhow::bail!("Truncated protobuf key export");
+                }
+                let value = &after_len[..len];
+                match field {
+                    1 => private_key = Some(value.to_vec()),
+                    2 => public_key = Some(value.to_vec()),
+                    _ => {}
+                }
+                rest = &after_len[len..];
+            }
+            _ => anyhow::bail!("Unsupported protobuf wire type {wire_type} in key export"),
+        }
+    }
+    let private_key =
+        private_key.ok_or_else(|| anyhow::anyhow!("Key export has no private key (field 1)"))?;
+    signing_key_from_parts(&private_key, public_key.as_deref())
+}
+
+/// Read a signer from a JSON key export
+///
+/// # Arguments
+/// * `json` - The export, an object with `privateKey` and optionally `publicKey`
+///
+/// # Returns
+/// * `Result<SigningKey>` - The signing key
+pub fn signing_key_from_json(json: &str) -> Result<SigningKey> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).context("Key export is not valid JSON")?;
+    let field = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| value.get(*name).and_then(|v| v.as_str()))
+    };
+    let private_key = field(&["privateKey", "private_key", "signerPrivateKey"])
+        .ok_or_else(|| anyhow::anyhow!("Key export has no privateKey field"))?;
+    let public_key = field(&["publicKey", "public_key", "signerPublicKey"])
+        .map(decode_key_text)
+        .transpose()?;
+    signing_key_from_parts(&decode_key_text(private_key)?, public_key.as_deref())
+}
+
+/// Read a signer from a hex or base64 private key (32-byte seed or 64-byte keypair)
+pub fn signing_key_from_text(text: &str) -> Result<SigningKey> {
+    signing_key_from_parts(&decode_key_text(text)?, None)
+}
+
+/// Build a signing key from a 32-byte seed or 64-byte keypair, checking the public key
+fn signing_key_from_parts(private_key: &[u8], public_key: Option<&[u8]>) -> Result<SigningKey> {
+    let seed: [u8; 32] = match private_key.len() {
+        32 | 64 => private_key[..32].try_into()?,
+        len => anyhow::bail!("Invalid private key length: {len} bytes, expected 32 or 64"),
+    };
+    let signing_key = SigningKey::from_bytes(&seed);
+    let derived = signing_key.verifying_key().to_bytes();
+    let expected = public_key.or((private_key.len() == 64).then(|| &private_key[32..]));
+    if let Some(expected) = expected {
+        if expected != derived {
+            anyhow::bail!(
+                "Public key in the export (0x{}) doesn't match the private key (0x{})",
+                hex::encode(expected),
+                hex::encode(derived)
+            );
+        }
+    }
+    Ok(signing_key)
+}
+
+/// Decode a key written as 0x-hex, hex or base64
+fn decode_key_text(text: &str) -> Result<Vec<u8>> {
+    let text = text.trim();
+    let hex_text = text.strip_prefix("0x").unwrap_or(text);
+    if !hex_text.is_empty() && hex_text.chars().all(|c| c.is_ascii_hexdigit()) {
+        return hex::decode(hex_text).context("Invalid hex key");
+    }
+    general_purpose::STANDARD
+        .decode(text)
+        .context("Key is neither hex nor base64")
+}
+
+/// Accept an export as raw bytes or as hex/base64 text of those bytes
+fn decode_text_or_raw(data: &[u8]) -> Result<Vec<u8>> {
+    match std::str::from_utf8(data) {
+        Ok(text) if !text.trim().is_empty() => decode_key_text(text).or_else(|_| Ok(data.to_vec())),
+        _ => Ok(data.to_vec()),
+    }
+}
+
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[i + 1..]));
+        }
+    }
+    anyhow::bail!("Malformed varint in protobuf key export")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slip10_test_vector() {
+        // SLIP-0010 test vector 1 for ed25519, chain m/0'
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let key = slip10_derive(&seed, &parse_hardened_path("m/0'").unwrap());
+        assert_eq!(
+            hex::encode(key),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+        assert!(parse_hardened_path("m/44'/60'/0'/0/0").is_err());
+    }
+
+    #[test]
+    fn test_protobuf_and_json_exports() {
+        let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+        let public_key = signing_key.verifying_key().to_bytes();
+
+        let mut export = vec![0x0a, 32];
+        export.extend_from_slice(&signing_key.to_bytes());
+        export.extend_from_slice(&[0x12, 32]);
+        export.extend_from_slice(&public_key);
+        let from_raw = signing_key_from_protobuf(&export).unwrap();
+        let from_base64 =
+            signing_key_from_protobuf(general_purpose::STANDARD.encode(&export).as_bytes())
+                .unwrap();
+        assert_eq!(from_raw.to_bytes(), signing_key.to_bytes());
+        assert_eq!(from_base64.to_bytes(), signing_key.to_bytes());
+
+        let json = format!(
+            r#"{{ "privateKey": "0x{}", "publicKey": "0x{}" }}"#,
+            hex::encode(signing_key.to_bytes()),
+            hex::encode(public_key)
+        );
+        assert_eq!(
+            signing_key_from_json(&json).unwrap().to_bytes(),
+            signing_key.to_bytes()
+        );
+
+        let mismatched = format!(
+            r#"{{ "privateKey": "{}", "publicKey": "{}" }}"#,
+            hex::encode(signing_key.to_bytes()),
+            hex::encode([1u8; 32])
+        );
+        assert!(signing_key_from_json(&mismatched).is_err());
+    }
+}
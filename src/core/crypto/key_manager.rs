@@ -4,6 +4,11 @@ use ethers::core::k256::ecdsa::SigningKey;
 use ethers::prelude::*;
 use ethers::signers::LocalWallet;
 use ethers::signers::Signer;
+use ethers::utils::hash_message;
+
+use crate::core::audit;
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
 
 /// Private key management system that loads keys from environment variables
 #[derive(Clone)]
@@ -79,10 +84,15 @@ impl KeyManager {
     /// # Returns
     /// * `Result<Signature>` - The signature or an error
     pub async fn sign_message(&self, message: &str) -> Result<Signature> {
-        self.wallet
+        let timer = metrics::start(Phase::Signing, "personal_sign");
+        let signature = self
+            .wallet
             .sign_message(message)
             .await
-            .with_context(|| "Failed to sign message")
+            .with_context(|| "Failed to sign message")?;
+        drop(timer);
+        audit::record_eth_signature(self.address(), "personal_sign", hash_message(message));
+        Ok(signature)
     }
 
     /// Sign arbitrary data with the private key
@@ -93,10 +103,15 @@ impl KeyManager {
     /// # Returns
     /// * `Result<Signature>` - The signature or an error
     pub async fn sign_data(&self, data: &[u8]) -> Result<Signature> {
-        self.wallet
+        let timer = metrics::start(Phase::Signing, "personal_sign");
+        let signature = self
+            .wallet
             .sign_message(data)
             .await
-            .with_context(|| "Failed to sign data")
+            .with_context(|| "Failed to sign data")?;
+        drop(timer);
+        audit::record_eth_signature(self.address(), "personal_sign", hash_message(data));
+        Ok(signature)
     }
 
     /// Get the public key
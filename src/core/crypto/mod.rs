@@ -2,12 +2,19 @@
 //!
 //! Provides secure key storage, signing, and encryption
 
+pub mod auth_address;
 pub mod encrypted_storage;
+pub mod kdf;
+pub mod key_audit;
+pub mod key_import;
 pub mod key_manager;
+pub mod vanity;
 
 pub use encrypted_storage::CryptoError;
 pub use encrypted_storage::Ed25519KeyInfo;
 pub use encrypted_storage::EncryptedEd25519KeyManager;
 pub use encrypted_storage::EncryptedEthKeyManager;
 pub use encrypted_storage::EthKeyInfo;
+pub use kdf::KdfParams;
+pub use key_audit::KeyAuditReport;
 pub use key_manager::KeyManager;
@@ -0,0 +1,230 @@
+//! Vanity Ethereum addresses: grinding random keys until the address matches
+//!
+//! Each extra hex character in the pattern makes a match 16 times rarer, so a
+//! six-character prefix takes about 16.8 million attempts on average. With
+//! `checksum` matching, letters must also match their EIP-55 case, which
+//! doubles the work for every letter in the pattern.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+use ethers::core::k256::ecdsa::SigningKey;
+use ethers::types::Address;
+use ethers::utils::secret_key_to_address;
+use ethers::utils::to_checksum;
+
+/// Attempts a worker makes between updates of the shared counter
+const BATCH: u64 = 256;
+
+/// Interval between progress reports
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What a vanity address must look like
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VanityPattern {
+    prefix: String,
+    suffix: String,
+    checksum: bool,
+}
+
+impl VanityPattern {
+    /// Pattern from a prefix and/or suffix of hex characters
+    ///
+    /// # Arguments
+    /// * `prefix` - Start of the address, with or without `0x`
+    /// * `suffix` - End of the address
+    /// * `checksum` - Match letter case against the EIP-55 checksum address
+    pub fn new(prefix: Option<&str>, suffix: Option<&str>, checksum: bool) -> Result<Self> {
+        let clean = |part: Option<&str>| -> Result<String> {
+            let part = part.unwrap_or("").trim();
+            let part = part
+                .strip_prefix("0x")
+                .or_else(|| part.strip_prefix("0X"))
+                .unwrap_or(part);
+            if let Some(c) = part.chars().find(|c| !c.is_ascii_hexdigit()) {
+                anyhow::bail!("'{c}' is not a hex character; addresses only contain 0-9 and a-f");
+            }
+            Ok(if checksum {
+                part.to_string()
+            } else {
+                part.to_lowercase()
+            })
+        };
+        let prefix = clean(prefix)?;
+        let suffix = clean(suffix)?;
+        if prefix.is_empty() && suffix.is_empty() {
+            anyhow::bail!("Give a --prefix and/or --suffix to match");
+        }
+        if prefix.len() + suffix.len() > 40 {
+            anyhow::bail!("An address has only 40 hex characters");
+        }
+        Ok(Self {
+            prefix,
+            suffix,
+            checksum,
+        })
+    }
+
+    /// Whether `address` matches the pattern
+    pub fn matches(&self, address: &Address) -> bool {
+        let hex = if self.checksum {
+            to_checksum(address, None)[2..].to_string()
+        } else {
+            hex::encode(address.as_bytes())
+        };
+        hex.starts_with(&self.prefix) && hex.ends_with(&self.suffix)
+    }
+
+    /// Average number of keys to try before one matches
+    pub fn difficulty(&self) -> f64 {
+        let pattern = self.prefix.chars().chain(self.suffix.chars());
+        pattern.fold(1.0, |odds, c| {
+            let cased = if self.checksum && c.is_ascii_alphabetic() {
+                2.0
+            } else {
+                1.0
+            };
+            odds * 16.0 * cased
+        })
+    }
+
+    /// Time within which a match is found with 50% probability at `rate`
+    /// attempts per second
+    pub fn half_life(&self, rate: f64) -> Option<Duration> {
+        (rate > 0.0).then(|| Duration::from_secs_f64(self.difficulty() * 2f64.ln() / rate))
+    }
+}
+
+impl std::fmt::Display for VanityPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gap = 40 - self.prefix.len() - self.suffix.len();
+        write!(
+            f,
+            "0x{}{}{}",
+            self.prefix,
+            "…".repeat(gap.min(1)),
+            self.suffix
+        )
+    }
+}
+
+/// A key whose address matches a [`VanityPattern`]
+pub struct VanityKey {
+    pub key: SigningKey,
+    pub address: Address,
+    /// Keys tried across all workers, counted in whole batches
+    pub attempts: u64,
+    pub elapsed: Duration,
+}
+
+impl VanityKey {
+    /// Private key as hex, without `0x`
+    pub fn private_key_hex(&self) -> String {
+        hex::encode(self.key.to_bytes())
+    }
+}
+
+/// Try random keys on `threads` worker threads until one matches
+///
+/// # Arguments
+/// * `pattern` - The address pattern
+/// * `threads` - Worker threads (at least one)
+/// * `max_attempts` - Give up after roughly this many keys
+/// * `progress` - Called about once a second with the attempts so far and the
+///   time spent
+///
+/// # Returns
+/// * `Option<VanityKey>` - The matching key, or `None` if `max_attempts` ran out
+pub fn grind(
+    pattern: &VanityPattern,
+    threads: usize,
+    max_attempts: Option<u64>,
+    mut progress: impl FnMut(u64, Duration),
+) -> Option<VanityKey> {
+    let started = Instant::now();
+    let done = AtomicBool::new(false);
+    let attempts = AtomicU64::new(0);
+    let found: Mutex<Option<(SigningKey, Address)>> = Mutex::new(None);
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| {
+                let mut rng = rand::thread_rng();
+                while !done.load(Ordering::Relaxed) {
+                    for _ in 0..BATCH {
+                        let key = SigningKey::random(&mut rng);
+                        let address = secret_key_to_address(&key);
+                        if pattern.matches(&address) {
+                            if !done.swap(true, Ordering::Relaxed) {
+                                if let Ok(mut found) = found.lock() {
+                                    *found = Some((key, address));
+                                }
+                            }
+                            break;
+                        }
+                    }
+                    let total = attempts.fetch_add(BATCH, Ordering::Relaxed) + BATCH;
+                    if max_attempts.is_some_and(|max| total >= max) {
+                        done.store(true, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+
+        let mut last_report = Instant::now();
+        while !done.load(Ordering::Relaxed) {
+            std::thread::sleep(Duration::from_millis(50));
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                last_report = Instant::now();
+                progress(attempts.load(Ordering::Relaxed), started.elapsed());
+            }
+        }
+    });
+
+    let (key, address) = found.into_inner().ok()??;
+    Some(VanityKey {
+        key,
+        address,
+        attempts: attempts.load(Ordering::Relaxed),
+        elapsed: started.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vanity_pattern() {
+        let pattern = VanityPattern::new(Some("0xCAFE"), None, false).unwrap();
+        assert_eq!(pattern.difficulty(), 65536.0);
+        let address: Address = "0xcafe000000000000000000000000000000000001"
+            .parse()
+            .unwrap();
+        assert!(pattern.matches(&address));
+        assert!(!pattern.matches(&Address::zero()));
+
+        let pattern = VanityPattern::new(Some("ab"), Some("1"), true).unwrap();
+        assert_eq!(pattern.difficulty(), 16.0 * 32.0 * 32.0);
+        assert!(VanityPattern::new(Some("0xg"), None, false).is_err());
+        assert!(VanityPattern::new(None, Some(""), false).is_err());
+        assert!(VanityPattern::new(Some(&"a".repeat(41)), None, false).is_err());
+    }
+
+    #[test]
+    fn test_grind() {
+        let pattern = VanityPattern::new(None, Some("a"), false).unwrap();
+        let key = grind(&pattern, 2, None, |_, _| {}).unwrap();
+        assert!(key.attempts >= 1);
+        assert_eq!(secret_key_to_address(&key.key), key.address);
+        assert!(pattern.matches(&key.address));
+
+        let impossible = VanityPattern::new(Some(&"0".repeat(40)), None, false).unwrap();
+        assert!(grind(&impossible, 1, Some(BATCH), |_, _| {}).is_none());
+    }
+}
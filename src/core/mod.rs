@@ -1,17 +1,26 @@
 //! Core functionality for Castorix library
 //!
 //! This module contains the essential components for interacting with Farcaster protocol:
+//! - Audit: Append-only log of signatures
 //! - Client: Farcaster Hub API client
 //! - Crypto: Key management and cryptographic utilities  
+//! - Policy: Rules consulted before write operations
 //! - Protocol: Message types and protocol implementation
+//! - Services: Queries shared by the CLI, REST API and MCP server
 //! - Types: Common data structures
 //! - Utils: Utility functions
-//! - Contracts: Smart contract interactions
+//! - Contracts: Smart contract interactions (`contracts` feature)
+//! - Release: Signed release manifests for self verification and update
 
+pub mod audit;
 pub mod client;
+#[cfg(feature = "contracts")]
 pub mod contracts;
 pub mod crypto;
+pub mod policy;
 pub mod protocol;
+pub mod release;
+pub mod services;
 pub mod types;
 pub mod utils;
 
@@ -0,0 +1,226 @@
+//! Policy rules consulted before write operations
+//!
+//! Lets whoever runs castorix (for example behind the MCP server, where an AI
+//! agent initiates actions) limit what it may do, independently of the
+//! command or tool that asks. Rules live under `"policy"` in
+//! `<data dir>/config.json`:
+//!
+//! ```json
+//! {
+//!   "policy": {
+//!     "require_yes": true,
+//!     "allowed_fids": [12345],
+//!     "max_eth_per_tx": "0.01"
+//!   }
+//! }
+//! ```
+//!
+//! Hub messages are checked when signed and when submitted; transactions
+//! before they are sent. The CLI installs the policy at start-up with
+//! [`install`]; until then every operation is allowed.
+
+use std::collections::BTreeSet;
+use std::sync::RwLock;
+
+use anyhow::Context;
+use anyhow::Result;
+use ethers::types::U256;
+use ethers::utils::format_ether;
+use ethers::utils::parse_ether;
+use serde::Deserialize;
+use serde::Serialize;
+
+static POLICY: RwLock<Option<Policy>> = RwLock::new(None);
+
+#[cfg(test)]
+thread_local! {
+    static TEST_POLICY: std::cell::RefCell<Option<Policy>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Rules for write operations; the default allows everything
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Policy {
+    /// Refuse interactive confirmation prompts: operations that ask for
+    /// confirmation only proceed when confirmed up front with `--yes`
+    #[serde(default)]
+    pub require_yes: bool,
+    /// Only sign messages and send transactions for these FIDs (empty: any FID)
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub allowed_fids: BTreeSet<u64>,
+    /// Largest ETH value a single transaction may send, e.g. `"0.01"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_eth_per_tx: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    policy: Option<Policy>,
+}
+
+impl Policy {
+    /// Load the policy from `config.json` in the data directory
+    ///
+    /// A missing file or `policy` section gives the default, permissive policy.
+    pub fn load() -> Result<Self> {
+        let path = crate::core::utils::data_dir::data_dir().join("config.json");
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Ok(Self::default());
+        };
+        let config: ConfigFile = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+        let policy = config.policy.unwrap_or_default();
+        policy
+            .max_value()
+            .with_context(|| format!("Invalid policy in {}", path.display()))?;
+        Ok(policy)
+    }
+
+    /// Whether any rule is set
+    pub fn is_restricted(&self) -> bool {
+        *self != Self::default()
+    }
+
+    fn max_value(&self) -> Result<Option<U256>> {
+        self.max_eth_per_tx
+            .as_deref()
+            .map(|max| parse_ether(max).with_context(|| format!("Invalid max_eth_per_tx '{max}'")))
+            .transpose()
+    }
+
+    /// Check that an operation may ask for confirmation interactively
+    ///
+    /// # Arguments
+    /// * `yes` - Whether the operation was confirmed up front (`--yes`)
+    pub fn check_confirmation(&self, yes: bool) -> Result<()> {
+        if self.require_yes && !yes {
+            anyhow::bail!(
+                "Blocked by policy: confirmation prompts are disabled (require_yes); re-run with --yes"
+            );
+        }
+        Ok(())
+    }
+
+    /// Check that messages or transactions for a FID are allowed
+    pub fn check_fid(&self, fid: u64) -> Result<()> {
+        if !self.allowed_fids.is_empty() && !self.allowed_fids.contains(&fid) {
+            anyhow::bail!("Blocked by policy: FID {fid} is not in allowed_fids");
+        }
+        Ok(())
+    }
+
+    /// Check the ETH value of a transaction
+    ///
+    /// # Arguments
+    /// * `value` - Value in wei
+    pub fn check_value(&self, value: U256) -> Result<()> {
+        if let Some(max) = self.max_value()? {
+            if value > max {
+                anyhow::bail!(
+                    "Blocked by policy: transaction sends {} ETH, above max_eth_per_tx of {} ETH",
+                    format_ether(value),
+                    format_ether(max)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a transaction: its value and, when it acts for one, its FID
+    pub fn check_transaction(&self, fid: Option<u64>, value: U256) -> Result<()> {
+        if let Some(fid) = fid {
+            self.check_fid(fid)?;
+        }
+        self.check_value(value)
+    }
+}
+
+/// Make `policy` the one consulted by write operations in this process
+pub fn install(policy: Policy) {
+    if let Ok(mut current) = POLICY.write() {
+        *current = Some(policy);
+    }
+}
+
+/// The policy in force, permissive until [`install`] is called
+pub fn current() -> Policy {
+    #[cfg(test)]
+    if let Some(policy) = TEST_POLICY.with(|policy| policy.borrow().clone()) {
+        return policy;
+    }
+    POLICY
+        .read()
+        .ok()
+        .and_then(|policy| policy.clone())
+        .unwrap_or_default()
+}
+
+/// Restores the policy of the test thread when dropped
+#[cfg(test)]
+pub(crate) struct TestPolicyGuard(Option<Policy>);
+
+#[cfg(test)]
+impl Drop for TestPolicyGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        TEST_POLICY.with(|policy| *policy.borrow_mut() = previous);
+    }
+}
+
+/// Make `policy` the one in force on the current test thread
+///
+/// Unlike [`install`] this does not leak into tests running in parallel.
+#[cfg(test)]
+pub(crate) fn install_for_test(policy: Policy) -> TestPolicyGuard {
+    TestPolicyGuard(TEST_POLICY.with(|current| current.replace(Some(policy))))
+}
+
+/// A policy that only allows `fid`
+#[cfg(test)]
+pub(crate) fn only_fid(fid: u64) -> Policy {
+    Policy {
+        allowed_fids: BTreeSet::from([fid]),
+        ..Policy::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_allows_everything() {
+        let policy = Policy::default();
+        assert!(!policy.is_restricted());
+        assert!(policy.check_confirmation(false).is_ok());
+        assert!(policy.check_fid(1).is_ok());
+        assert!(policy.check_value(parse_ether("100").unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_rules() {
+        let policy: Policy = serde_json::from_str(
+            r#"{"require_yes": true, "allowed_fids": [3, 5], "max_eth_per_tx": "0.01"}"#,
+        )
+        .unwrap();
+        assert!(policy.check_confirmation(false).is_err());
+        assert!(policy.check_confirmation(true).is_ok());
+        assert!(policy.check_fid(3).is_ok());
+        assert!(policy.check_fid(4).is_err());
+        assert!(policy
+            .check_transaction(Some(5), parse_ether("0.01").unwrap())
+            .is_ok());
+        assert!(policy
+            .check_transaction(None, parse_ether("0.011").unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_install_for_test_is_scoped() {
+        {
+            let _guard = install_for_test(only_fid(3));
+            assert!(current().check_fid(4).is_err());
+        }
+        assert!(current().check_fid(4).is_ok());
+    }
+}
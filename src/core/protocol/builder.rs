@@ -0,0 +1,643 @@
+//! Fluent construction of Farcaster messages
+//!
+//! [`MessageBuilder`] assembles the `MessageData` of any supported message
+//! type and checks it against the hub's validation rules (text length, embed
+//! and mention limits, hash sizes) before anything is signed, so a malformed
+//! message fails locally instead of being rejected by the hub:
+//!
+//! ```ignore
+//! let message = MessageBuilder::new(fid)
+//!     .cast("gm @dwr")
+//!     .mention(3, 3)
+//!     .embed_url("https://farcaster.xyz")
+//!     .sign(&signing_key)?;
+//! client.submit_message(&message).await?;
+//! ```
+
+use anyhow::Result;
+use ed25519_dalek::SigningKey;
+
+use crate::core::client::hub_client::build_ed25519_message;
+use crate::core::client::hub_client::farcaster_now;
+use crate::core::client::links::FOLLOW_LINK_TYPE;
+use crate::core::protocol::message::CastAddBody;
+use crate::core::protocol::message::CastAddBody_oneof_parent;
+use crate::core::protocol::message::CastId;
+use crate::core::protocol::message::CastRemoveBody;
+use crate::core::protocol::message::CastType;
+use crate::core::protocol::message::Embed;
+use crate::core::protocol::message::Embed_oneof_embed;
+use crate::core::protocol::message::FarcasterNetwork;
+use crate::core::protocol::message::LinkBody;
+use crate::core::protocol::message::Message;
+use crate::core::protocol::message::MessageData;
+use crate::core::protocol::message::MessageData_oneof_body;
+use crate::core::protocol::message::MessageType;
+use crate::core::protocol::message::ReactionBody;
+use crate::core::protocol::message::ReactionType;
+use crate::core::protocol::message::UserDataBody;
+use crate::core::protocol::message::UserDataType;
+use crate::core::protocol::username_proof::UserNameProof;
+
+/// Maximum text of a regular cast, in bytes
+pub const MAX_CAST_TEXT_BYTES: usize = 320;
+/// Maximum text of a long cast, in bytes
+pub const MAX_LONG_CAST_TEXT_BYTES: usize = 1024;
+/// Maximum text of a 10k cast, in bytes
+pub const MAX_TEN_K_CAST_TEXT_BYTES: usize = 10_000;
+/// Maximum embeds per cast
+pub const MAX_EMBEDS: usize = 2;
+/// Maximum mentions per cast
+pub const MAX_MENTIONS: usize = 10;
+/// Maximum length of embed, parent and reaction URLs, in bytes
+pub const MAX_URL_BYTES: usize = 256;
+/// Maximum length of a link type such as `follow`, in bytes
+pub const MAX_LINK_TYPE_BYTES: usize = 8;
+/// Length of a message hash
+pub const MESSAGE_HASH_BYTES: usize = 20;
+
+/// Maximum text of a cast type, in bytes
+pub fn max_cast_text_bytes(cast_type: CastType) -> usize {
+    match cast_type {
+        CastType::CAST => MAX_CAST_TEXT_BYTES,
+        CastType::LONG_CAST => MAX_LONG_CAST_TEXT_BYTES,
+        CastType::TEN_K_CAST => MAX_TEN_K_CAST_TEXT_BYTES,
+    }
+}
+
+/// Maximum value of a user data field, in bytes
+pub fn max_user_data_bytes(data_type: UserDataType) -> usize {
+    match data_type {
+        UserDataType::USER_DATA_TYPE_DISPLAY => 32,
+        UserDataType::USER_DATA_TYPE_USERNAME => 20,
+        UserDataType::USER_DATA_TYPE_LOCATION => 50,
+        UserDataType::USER_DATA_TYPE_TWITTER => 15,
+        UserDataType::USER_DATA_TYPE_GITHUB => 39,
+        _ => 256,
+    }
+}
+
+/// Build a CastId from a FID and a hex message hash (with or without `0x`)
+pub fn cast_id(fid: u64, hash: &str) -> Result<CastId> {
+    let hash = hex::decode(hash.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid cast hash '{hash}': {e}"))?;
+    let mut cast_id = CastId::new();
+    cast_id.set_fid(fid);
+    cast_id.set_hash(hash);
+    validate_cast_id(&cast_id, "cast")?;
+    Ok(cast_id)
+}
+
+/// Parse a cast reference written as `FID:HASH`
+pub fn parse_cast_ref(value: &str) -> Result<CastId> {
+    let (fid, hash) = value
+        .split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("Cast reference '{value}' must be FID:HASH"))?;
+    let fid = fid
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid FID in cast reference '{value}'"))?;
+    cast_id(fid, hash)
+}
+
+fn validate_cast_id(cast_id: &CastId, what: &str) -> Result<()> {
+    if cast_id.get_fid() == 0 {
+        anyhow::bail!("{what} FID must not be 0");
+    }
+    if cast_id.get_hash().len() != MESSAGE_HASH_BYTES {
+        anyhow::bail!(
+            "{what} hash must be {MESSAGE_HASH_BYTES} bytes, got {}",
+            cast_id.get_hash().len()
+        );
+    }
+    Ok(())
+}
+
+fn validate_url(url: &str, what: &str) -> Result<()> {
+    if url.is_empty() {
+        anyhow::bail!("{what} URL must not be empty");
+    }
+    if url.len() > MAX_URL_BYTES {
+        anyhow::bail!(
+            "{what} URL is {} bytes, the limit is {MAX_URL_BYTES}",
+            url.len()
+        );
+    }
+    Ok(())
+}
+
+/// Check a CastAdd body against the hub's rules
+pub fn validate_cast_body(body: &CastAddBody) -> Result<()> {
+    let text = body.get_text();
+    let max_text = max_cast_text_bytes(body.get_field_type());
+    if text.len() > max_text {
+        anyhow::bail!(
+            "Cast text is {} bytes, the limit for {:?} is {max_text}",
+            text.len(),
+            body.get_field_type()
+        );
+    }
+    if body.get_field_type() != CastType::CAST && text.len() <= MAX_CAST_TEXT_BYTES {
+        anyhow::bail!("Long casts must be longer than {MAX_CAST_TEXT_BYTES} bytes");
+    }
+    if text.is_empty() && body.get_embeds().is_empty() && body.get_mentions().is_empty() {
+        anyhow::bail!("Cast must have text, embeds or mentions");
+    }
+
+    if body.get_embeds().len() > MAX_EMBEDS {
+        anyhow::bail!(
+            "Cast has {} embeds, the limit is {MAX_EMBEDS}",
+            body.get_embeds().len()
+        );
+    }
+    for embed in body.get_embeds() {
+        match &embed.embed {
+            Some(Embed_oneof_embed::url(url)) => validate_url(url, "Embed")?,
+            Some(Embed_oneof_embed::cast_id(cast_id)) => {
+                validate_cast_id(cast_id, "Embedded cast")?
+            }
+            None => anyhow::bail!("Embed must have a URL or a cast"),
+        }
+    }
+
+    let mentions = body.get_mentions();
+    let positions = body.get_mentions_positions();
+    if mentions.len() > MAX_MENTIONS {
+        anyhow::bail!(
+            "Cast has {} mentions, the limit is {MAX_MENTIONS}",
+            mentions.len()
+        );
+    }
+    if mentions.len() != positions.len() {
+        anyhow::bail!(
+            "Cast has {} mentions but {} mention positions",
+            mentions.len(),
+            positions.len()
+        );
+    }
+    if mentions.contains(&0) {
+        anyhow::bail!("Mentioned FID must not be 0");
+    }
+    let mut previous = 0;
+    for &position in positions {
+        let position = position as usize;
+        if position < previous {
+            anyhow::bail!("Mention positions must be in ascending order");
+        }
+        if position > text.len() || !text.is_char_boundary(position) {
+            anyhow::bail!("Mention position {position} is not a character boundary of the text");
+        }
+        previous = position;
+    }
+
+    match &body.parent {
+        Some(CastAddBody_oneof_parent::parent_cast_id(cast_id)) => {
+            validate_cast_id(cast_id, "Parent cast")?
+        }
+        Some(CastAddBody_oneof_parent::parent_url(url)) => validate_url(url, "Parent")?,
+        None => {}
+    }
+    Ok(())
+}
+
+fn validate_body(fid: u64, body: &MessageData_oneof_body) -> Result<()> {
+    match body {
+        MessageData_oneof_body::cast_add_body(body) => validate_cast_body(body),
+        MessageData_oneof_body::cast_remove_body(body) => {
+            if body.get_target_hash().len() != MESSAGE_HASH_BYTES {
+                anyhow::bail!("Cast hash must be {MESSAGE_HASH_BYTES} bytes");
+            }
+            Ok(())
+        }
+        MessageData_oneof_body::reaction_body(body) => {
+            if body.get_field_type() == ReactionType::REACTION_TYPE_NONE {
+                anyhow::bail!("Reaction type must be set");
+            }
+            if body.has_target_cast_id() {
+                validate_cast_id(body.get_target_cast_id(), "Reaction target")
+            } else if body.has_target_url() {
+                validate_url(body.get_target_url(), "Reaction target")
+            } else {
+                anyhow::bail!("Reaction must target a cast or a URL")
+            }
+        }
+        MessageData_oneof_body::link_body(body) => {
+            let link_type = body.get_field_type();
+            if link_type.is_empty() || link_type.len() > MAX_LINK_TYPE_BYTES {
+                anyhow::bail!("Link type must be 1 to {MAX_LINK_TYPE_BYTES} bytes");
+            }
+            if body.get_target_fid() == 0 {
+                anyhow::bail!("Link target FID must not be 0");
+            }
+            if body.get_target_fid() == fid {
+                anyhow::bail!("FID {fid} cannot link to itself");
+            }
+            Ok(())
+        }
+        MessageData_oneof_body::user_data_body(body) => {
+            let data_type = body.get_field_type();
+            if data_type == UserDataType::USER_DATA_TYPE_NONE {
+                anyhow::bail!("User data type must be set");
+            }
+            let max = max_user_data_bytes(data_type);
+            if body.get_value().len() > max {
+                anyhow::bail!(
+                    "{data_type:?} is {} bytes, the limit is {max}",
+                    body.get_value().len()
+                );
+            }
+            Ok(())
+        }
+        MessageData_oneof_body::username_proof_body(proof) => {
+            if proof.get_fid() != fid {
+                anyhow::bail!(
+                    "Username proof is for FID {}, not FID {fid}",
+                    proof.get_fid()
+                );
+            }
+            if proof.get_name().is_empty() {
+                anyhow::bail!("Username proof must have a name");
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check decoded `MessageData` against the rules messages built here follow
+pub fn validate_message_data(data: &MessageData) -> Result<()> {
+    if data.get_fid() == 0 {
+        anyhow::bail!("FID must not be 0");
+    }
+    match &data.body {
+        Some(body) => validate_body(data.get_fid(), body),
+        None => anyhow::bail!("Message has no body"),
+    }
+}
+
+/// Entry point for building messages signed by a FID
+///
+/// Pick the message kind with one of the body methods, then call `build` for
+/// the validated `MessageData` or `sign` for a message ready to submit.
+#[derive(Debug, Clone)]
+pub struct MessageBuilder {
+    fid: u64,
+    network: FarcasterNetwork,
+    timestamp: Option<u32>,
+}
+
+impl MessageBuilder {
+    /// Start a message from `fid` on mainnet, timestamped when built
+    pub fn new(fid: u64) -> Self {
+        Self {
+            fid,
+            network: FarcasterNetwork::FARCASTER_NETWORK_MAINNET,
+            timestamp: None,
+        }
+    }
+
+    /// Set the network the message is for
+    pub fn network(mut self, network: FarcasterNetwork) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Set the Farcaster timestamp instead of using the current time
+    pub fn timestamp(mut self, timestamp: u32) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    fn draft(self, message_type: MessageType, body: MessageData_oneof_body) -> MessageDraft {
+        MessageDraft {
+            header: self,
+            message_type,
+            body,
+        }
+    }
+
+    /// Compose a cast
+    pub fn cast(self, text: impl Into<String>) -> CastBuilder {
+        let mut body = CastAddBody::new();
+        body.set_text(text.into());
+        CastBuilder { header: self, body }
+    }
+
+    /// Delete one of the FID's casts by hash
+    pub fn remove_cast(self, hash: &[u8]) -> MessageDraft {
+        let mut body = CastRemoveBody::new();
+        body.set_target_hash(hash.to_vec());
+        self.draft(
+            MessageType::MESSAGE_TYPE_CAST_REMOVE,
+            MessageData_oneof_body::cast_remove_body(body),
+        )
+    }
+
+    fn reaction_body(reaction_type: ReactionType, target: ReactionTarget) -> ReactionBody {
+        let mut body = ReactionBody::new();
+        body.set_field_type(reaction_type);
+        match target {
+            ReactionTarget::Cast(cast_id) => body.set_target_cast_id(cast_id),
+            ReactionTarget::Url(url) => body.set_target_url(url),
+        }
+        body
+    }
+
+    /// Like or recast a cast or URL
+    pub fn reaction(self, reaction_type: ReactionType, target: ReactionTarget) -> MessageDraft {
+        let body = Self::reaction_body(reaction_type, target);
+        self.draft(
+            MessageType::MESSAGE_TYPE_REACTION_ADD,
+            MessageData_oneof_body::reaction_body(body),
+        )
+    }
+
+    /// Undo a like or recast
+    pub fn remove_reaction(
+        self,
+        reaction_type: ReactionType,
+        target: ReactionTarget,
+    ) -> MessageDraft {
+        let body = Self::reaction_body(reaction_type, target);
+        self.draft(
+            MessageType::MESSAGE_TYPE_REACTION_REMOVE,
+            MessageData_oneof_body::reaction_body(body),
+        )
+    }
+
+    fn link_body(link_type: &str, target_fid: u64) -> MessageData_oneof_body {
+        let mut body = LinkBody::new();
+        body.set_field_type(link_type.to_string());
+        body.set_target_fid(target_fid);
+        MessageData_oneof_body::link_body(body)
+    }
+
+    /// Add a link of `link_type` (e.g. `follow`) to another FID
+    pub fn link(self, link_type: &str, target_fid: u64) -> MessageDraft {
+        let body = Self::link_body(link_type, target_fid);
+        self.draft(MessageType::MESSAGE_TYPE_LINK_ADD, body)
+    }
+
+    /// Remove a link of `link_type` to another FID
+    pub fn remove_link(self, link_type: &str, target_fid: u64) -> MessageDraft {
+        let body = Self::link_body(link_type, target_fid);
+        self.draft(MessageType::MESSAGE_TYPE_LINK_REMOVE, body)
+    }
+
+    /// Follow another FID
+    pub fn follow(self, target_fid: u64) -> MessageDraft {
+        self.link(FOLLOW_LINK_TYPE, target_fid)
+    }
+
+    /// Unfollow another FID
+    pub fn unfollow(self, target_fid: u64) -> MessageDraft {
+        self.remove_link(FOLLOW_LINK_TYPE, target_fid)
+    }
+
+    /// Set a profile field; an empty value clears it
+    pub fn user_data(self, data_type: UserDataType, value: impl Into<String>) -> MessageDraft {
+        let mut body = UserDataBody::new();
+        body.set_field_type(data_type);
+        body.set_value(value.into());
+        self.draft(
+            MessageType::MESSAGE_TYPE_USER_DATA_ADD,
+            MessageData_oneof_body::user_data_body(body),
+        )
+    }
+
+    /// Claim a username with an ENS or Basename proof
+    pub fn username_proof(self, proof: UserNameProof) -> MessageDraft {
+        self.draft(
+            MessageType::MESSAGE_TYPE_USERNAME_PROOF,
+            MessageData_oneof_body::username_proof_body(proof),
+        )
+    }
+}
+
+/// What a reaction applies to
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReactionTarget {
+    Cast(CastId),
+    Url(String),
+}
+
+/// A message with its body chosen, ready to validate and sign
+#[derive(Debug, Clone)]
+pub struct MessageDraft {
+    header: MessageBuilder,
+    message_type: MessageType,
+    body: MessageData_oneof_body,
+}
+
+impl MessageDraft {
+    /// Validate the message and return its `MessageData`
+    pub fn build(self) -> Result<MessageData> {
+        let fid = self.header.fid;
+        if fid == 0 {
+            anyhow::bail!("FID must not be 0");
+        }
+        validate_body(fid, &self.body)?;
+
+        let mut data = MessageData::new();
+        data.set_field_type(self.message_type);
+        data.set_fid(fid);
+        data.set_timestamp(self.header.timestamp.unwrap_or_else(farcaster_now));
+        data.set_network(self.header.network);
+        data.body = Some(self.body);
+        Ok(data)
+    }
+
+    /// Validate the message and sign it with an Ed25519 signer of the FID
+    ///
+    /// # Arguments
+    /// * `signing_key` - An active signer of the FID
+    ///
+    /// # Returns
+    /// * `Result<Message>` - The signed message, with `data_bytes` set
+    pub fn sign(self, signing_key: &SigningKey) -> Result<Message> {
+        build_ed25519_message(self.build()?, signing_key)
+    }
+}
+
+/// A cast being composed
+#[derive(Debug, Clone)]
+pub struct CastBuilder {
+    header: MessageBuilder,
+    body: CastAddBody,
+}
+
+impl CastBuilder {
+    /// Mark the cast as a long cast (up to 1024 bytes) or 10k cast
+    pub fn cast_type(mut self, cast_type: CastType) -> Self {
+        self.body.set_field_type(cast_type);
+        self
+    }
+
+    /// Mention a FID at a byte offset of the text
+    ///
+    /// Mentions are not written in the text itself: clients render the
+    /// mentioned username at `position`.
+    pub fn mention(mut self, fid: u64, position: u32) -> Self {
+        self.body.mut_mentions().push(fid);
+        self.body.mut_mentions_positions().push(position);
+        self
+    }
+
+    /// Embed a URL
+    pub fn embed_url(mut self, url: impl Into<String>) -> Self {
+        let mut embed = Embed::new();
+        embed.set_url(url.into());
+        self.body.mut_embeds().push(embed);
+        self
+    }
+
+    /// Embed (quote) another cast
+    pub fn embed_cast(mut self, cast_id: CastId) -> Self {
+        let mut embed = Embed::new();
+        embed.set_cast_id(cast_id);
+        self.body.mut_embeds().push(embed);
+        self
+    }
+
+    /// Reply to a cast
+    pub fn reply_to(mut self, parent: CastId) -> Self {
+        self.body.set_parent_cast_id(parent);
+        self
+    }
+
+    /// Post under a parent URL, such as a channel URL
+    pub fn parent_url(mut self, url: impl Into<String>) -> Self {
+        self.body.set_parent_url(url.into());
+        self
+    }
+
+    /// Finish the cast body
+    pub fn into_draft(self) -> MessageDraft {
+        self.header.draft(
+            MessageType::MESSAGE_TYPE_CAST_ADD,
+            MessageData_oneof_body::cast_add_body(self.body),
+        )
+    }
+
+    /// Validate the cast and return its `MessageData`
+    pub fn build(self) -> Result<MessageData> {
+        self.into_draft().build()
+    }
+
+    /// Validate the cast and sign it with an Ed25519 signer of the FID
+    pub fn sign(self, signing_key: &SigningKey) -> Result<Message> {
+        self.into_draft().sign(signing_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protobuf::Message as ProtobufMessage;
+
+    use super::*;
+
+    const HASH: &str = "0x0102030405060708090a0b0c0d0e0f1011121314";
+
+    #[test]
+    fn test_build_cast() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let message = MessageBuilder::new(42)
+            .timestamp(1_000)
+            .cast("gm  and ")
+            .mention(3, 3)
+            .mention(4, 8)
+            .embed_url("https://example.com")
+            .reply_to(cast_id(5, HASH).unwrap())
+            .sign(&signing_key)
+            .unwrap();
+
+        let data = MessageData::parse_from_bytes(message.get_data_bytes()).unwrap();
+        assert_eq!(data.get_field_type(), MessageType::MESSAGE_TYPE_CAST_ADD);
+        assert_eq!(data.get_timestamp(), 1_000);
+        let body = data.get_cast_add_body();
+        assert_eq!(body.get_mentions(), &[3, 4]);
+        assert_eq!(body.get_mentions_positions(), &[3, 8]);
+        assert_eq!(body.get_embeds().len(), 1);
+        assert_eq!(body.get_parent_cast_id().get_fid(), 5);
+    }
+
+    #[test]
+    fn test_parse_cast_ref() {
+        let cast = parse_cast_ref(&format!("5:{HASH}")).unwrap();
+        assert_eq!(cast.get_fid(), 5);
+        assert_eq!(cast.get_hash().len(), MESSAGE_HASH_BYTES);
+        assert!(parse_cast_ref(HASH).is_err());
+        assert!(parse_cast_ref("x:0x01").is_err());
+        assert!(parse_cast_ref("5:0x0102").is_err());
+    }
+
+    #[test]
+    fn test_cast_validation() {
+        let builder = MessageBuilder::new(42);
+        assert!(builder.clone().cast("x".repeat(321)).build().is_err());
+        assert!(builder
+            .clone()
+            .cast("x".repeat(321))
+            .cast_type(CastType::LONG_CAST)
+            .build()
+            .is_ok());
+        assert!(builder
+            .clone()
+            .cast("short")
+            .cast_type(CastType::LONG_CAST)
+            .build()
+            .is_err());
+        assert!(builder.clone().cast("").build().is_err());
+        assert!(builder
+            .clone()
+            .cast("three")
+            .embed_url("https://a.example")
+            .embed_url("https://b.example")
+            .embed_url("https://c.example")
+            .build()
+            .is_err());
+        assert!(builder
+            .clone()
+            .cast("out of order")
+            .mention(1, 5)
+            .mention(2, 1)
+            .build()
+            .is_err());
+        assert!(builder.clone().cast("é").mention(1, 1).build().is_err());
+        assert!(builder.cast("past the end").mention(1, 99).build().is_err());
+    }
+
+    #[test]
+    fn test_other_message_types() {
+        let builder = MessageBuilder::new(42);
+        let follow = builder.clone().follow(7).build().unwrap();
+        assert_eq!(follow.get_field_type(), MessageType::MESSAGE_TYPE_LINK_ADD);
+        assert_eq!(follow.get_link_body().get_field_type(), "follow");
+        assert!(builder.clone().follow(42).build().is_err());
+
+        let like = builder
+            .clone()
+            .reaction(
+                ReactionType::REACTION_TYPE_LIKE,
+                ReactionTarget::Cast(cast_id(5, HASH).unwrap()),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            like.get_field_type(),
+            MessageType::MESSAGE_TYPE_REACTION_ADD
+        );
+
+        assert!(builder
+            .clone()
+            .user_data(UserDataType::USER_DATA_TYPE_DISPLAY, "x".repeat(33))
+            .build()
+            .is_err());
+        assert!(builder
+            .clone()
+            .user_data(UserDataType::USER_DATA_TYPE_BIO, "")
+            .build()
+            .is_ok());
+        assert!(builder.remove_cast(&[1, 2, 3]).build().is_err());
+        assert!(cast_id(5, "0x1234").is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Cast text helpers: `@username` mentions and splitting long text into threads
+
+use std::collections::HashMap;
+
+/// Length of the ` (i/n)` counter appended to thread parts when `n` has `digits` digits
+fn counter_len(digits: usize) -> usize {
+    " (/)".len() + 2 * digits
+}
+
+fn digits(n: usize) -> usize {
+    n.to_string().len()
+}
+
+/// Largest char boundary of `text` at or below `index`
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Where to cut `text` so the first piece fits in `budget` bytes
+///
+/// Prefers a paragraph break, then the end of a sentence, then any whitespace,
+/// and only cuts inside a word when there is no whitespace at all.
+fn break_point(text: &str, budget: usize) -> usize {
+    if text.len() <= budget {
+        return text.len();
+    }
+    let window = &text[..floor_char_boundary(text, budget)];
+    let min = window.len() / 2;
+    let after = |pattern: &str| {
+        window
+            .rfind(pattern)
+            .map(|i| i + pattern.len())
+            .filter(|&i| i > min)
+    };
+    after("\n\n")
+        .or_else(|| {
+            [". ", "! ", "? ", ".\n", "!\n", "?\n", "\n"]
+                .iter()
+                .filter_map(|pattern| after(pattern))
+                .max()
+        })
+        .or_else(|| {
+            window
+                .rfind(char::is_whitespace)
+                .filter(|&i| i > 0)
+                .map(|i| i + 1)
+        })
+        .unwrap_or(window.len())
+}
+
+fn split_with_budget(text: &str, budget: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let mut cut = break_point(rest, budget);
+        if cut == 0 {
+            cut = rest.chars().next().map_or(rest.len(), char::len_utf8);
+        }
+        let (part, tail) = rest.split_at(cut);
+        let part = part.trim();
+        if !part.is_empty() {
+            parts.push(part.to_string());
+        }
+        rest = tail.trim_start();
+    }
+    parts
+}
+
+/// Split text into thread parts of at most `max_bytes` each
+///
+/// Text that already fits is returned as a single part unchanged. Otherwise
+/// the parts break at paragraphs, sentences or words where possible and each
+/// ends with a ` (i/n)` counter, which is included in `max_bytes`.
+///
+/// # Arguments
+/// * `text` - The full text
+/// * `max_bytes` - Byte limit of one cast, e.g. `MAX_CAST_TEXT_BYTES`
+///
+/// # Returns
+/// * `Vec<String>` - The parts in posting order
+pub fn split_thread(text: &str, max_bytes: usize) -> Vec<String> {
+    let text = text.trim();
+    if text.len() <= max_bytes {
+        return vec![text.to_string()];
+    }
+
+    let mut counter_digits = 1;
+    loop {
+        let budget = max_bytes.saturating_sub(counter_len(counter_digits)).max(1);
+        let parts = split_with_budget(text, budget);
+        if digits(parts.len()) > counter_digits {
+            counter_digits = digits(parts.len());
+            continue;
+        }
+        let total = parts.len();
+        return parts
+            .into_iter()
+            .enumerate()
+            .map(|(i, part)| format!("{part} ({}/{total})", i + 1))
+            .collect();
+    }
+}
+
+/// An `@username` token in cast text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MentionToken {
+    /// Lowercased username without the `@`
+    pub name: String,
+    /// Byte offset of the `@`
+    pub start: usize,
+    /// Byte offset just past the name
+    pub end: usize,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'
+}
+
+/// Find `@username` tokens in text
+///
+/// An `@` only starts a mention at the beginning of the text or after a
+/// character that can't be part of a name, so email addresses are left alone.
+/// Trailing dots are punctuation, not part of the name (`@alice.eth.`).
+pub fn find_mentions(text: &str) -> Vec<MentionToken> {
+    let mut tokens = Vec::new();
+    for (start, _) in text.match_indices('@') {
+        let preceded_by_name = text[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| is_name_char(c) || c == '@');
+        if preceded_by_name {
+            continue;
+        }
+        let rest = &text[start + 1..];
+        let len = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+        let name = rest[..len].trim_end_matches('.');
+        if name.is_empty() || !name.chars().any(|c| c.is_ascii_alphabetic()) {
+            continue;
+        }
+        tokens.push(MentionToken {
+            name: name.to_lowercase(),
+            start,
+            end: start + 1 + name.len(),
+        });
+    }
+    tokens
+}
+
+/// Cast text with mentions in the protocol's encoding
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EncodedMentions {
+    /// Text with every resolved `@username` removed
+    pub text: String,
+    pub mentions: Vec<u64>,
+    /// Byte offset in `text` where each mentioned name is rendered
+    pub positions: Vec<u32>,
+}
+
+/// Encode resolved `@username` tokens as mentions
+///
+/// Each resolved token is cut from the text and recorded as the FID plus the
+/// byte offset it was cut from, which is how clients expect mentions. Names
+/// missing from `fids` stay in the text as written.
+///
+/// # Arguments
+/// * `text` - Text with `@username` tokens
+/// * `fids` - FIDs of resolved names, keyed by lowercased name
+pub fn encode_mentions(text: &str, fids: &HashMap<String, u64>) -> EncodedMentions {
+    let mut encoded = EncodedMentions::default();
+    let mut copied = 0;
+    for token in find_mentions(text) {
+        let Some(&fid) = fids.get(&token.name) else {
+            continue;
+        };
+        encoded.text.push_str(&text[copied..token.start]);
+        encoded.mentions.push(fid);
+        encoded.positions.push(encoded.text.len() as u32);
+        copied = token.end;
+    }
+    encoded.text.push_str(&text[copied..]);
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_mentions() {
+        let tokens = find_mentions("@dwr and @Vitalik.eth. mail me@example.com @ @123 @v");
+        let names: Vec<_> = tokens.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["dwr", "vitalik.eth", "v"]);
+        assert_eq!((tokens[1].start, tokens[1].end), (9, 21));
+    }
+
+    #[test]
+    fn test_encode_mentions() {
+        let fids = HashMap::from([("dwr".to_string(), 3), ("v".to_string(), 2)]);
+        let encoded = encode_mentions("gm @dwr and @v, not @nobody", &fids);
+        assert_eq!(encoded.text, "gm  and , not @nobody");
+        assert_eq!(encoded.mentions, vec![3, 2]);
+        assert_eq!(encoded.positions, vec![3, 8]);
+    }
+
+    #[test]
+    fn test_short_text_is_one_part() {
+        assert_eq!(split_thread("  gm  ", 320), vec!["gm".to_string()]);
+    }
+
+    #[test]
+    fn test_split_at_sentences_and_words() {
+        let text = "First sentence here. Second sentence is a bit longer. Third one.";
+        let parts = split_thread(text, 40);
+        assert!(parts.iter().all(|part| part.len() <= 40));
+        assert_eq!(parts[0], "First sentence here. (1/3)");
+        assert!(parts.last().unwrap().ends_with("(3/3)"));
+
+        let words = "word ".repeat(200);
+        let parts = split_thread(&words, 320);
+        assert!(parts.iter().all(|part| part.len() <= 320));
+        assert!(parts.iter().all(|part| !part.contains("wor ")));
+    }
+
+    #[test]
+    fn test_split_respects_char_boundaries() {
+        let text = "é".repeat(400);
+        let parts = split_thread(&text, 320);
+        assert!(parts.iter().all(|part| part.len() <= 320));
+        let joined: String = parts
+            .iter()
+            .map(|part| part.rsplit_once(" (").unwrap().0)
+            .collect();
+        assert_eq!(joined, text);
+    }
+}
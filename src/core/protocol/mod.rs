@@ -1,11 +1,15 @@
 //! Farcaster protocol implementation
 //!
-//! Message types, username proofs, and protocol utilities
+//! Message types, message building, username proofs, and protocol utilities
 
+pub mod builder;
+pub mod cast_text;
 pub mod message;
 pub mod spam_checker;
 pub mod username_proof;
+pub mod verify;
 
+pub use builder::MessageBuilder;
 pub use message::Message;
 pub use message::MessageData;
 pub use message::MessageType;
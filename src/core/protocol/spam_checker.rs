@@ -7,6 +7,9 @@ use anyhow::Result;
 use serde::Deserialize;
 use serde::Serialize;
 
+/// Spam labels dataset shipped in the `labels` submodule
+pub const DEFAULT_SPAM_LABELS_PATH: &str = "labels/labels/spam.jsonl";
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SpamLabel {
     pub provider: u64,
@@ -106,10 +109,115 @@ impl SpamChecker {
     pub fn get_newest_timestamp(&self) -> Option<u64> {
         self.labels.values().map(|label| label.timestamp).max()
     }
+
+    /// Convert the loaded labels into a compact index sorted by FID
+    pub fn into_index(self) -> SpamIndex {
+        let mut entries: Vec<SpamIndexEntry> = self
+            .labels
+            .into_values()
+            .map(|label| SpamIndexEntry {
+                fid: label.target_type.fid,
+                label_value: label.label_value,
+                timestamp: label.timestamp,
+            })
+            .collect();
+        entries.sort_unstable_by_key(|e| e.fid);
+        SpamIndex { entries }
+    }
+}
+
+/// Spam label reduced to the fields needed for lookups
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SpamIndexEntry {
+    pub fid: u64,
+    pub label_value: u8,
+    pub timestamp: u64,
+}
+
+/// Result of checking one FID against the spam labels
+#[derive(Debug, Clone, Serialize)]
+pub struct SpamCheckResult {
+    pub fid: u64,
+    /// `spam`, `not_spam` or `unknown`
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_value: Option<u8>,
+    /// Unix timestamp at which the label was assigned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labeled_at: Option<u64>,
+}
+
+/// Read-only spam labels sorted by FID for O(log n) lookups
+///
+/// Built once and shared, so large batch checks do not reload or rescan the dataset.
+pub struct SpamIndex {
+    entries: Vec<SpamIndexEntry>,
+}
+
+impl SpamIndex {
+    /// Load spam labels from a JSONL file and index them
+    pub fn load_from_file(file_path: &str) -> Result<Self> {
+        Ok(SpamChecker::load_from_file(file_path)?.into_index())
+    }
+
+    /// Number of labelled FIDs
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the index holds no labels
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All labels, sorted by FID
+    pub fn entries(&self) -> &[SpamIndexEntry] {
+        &self.entries
+    }
+
+    /// Look up the label of a FID
+    pub fn lookup(&self, fid: u64) -> Option<&SpamIndexEntry> {
+        self.entries
+            .binary_search_by_key(&fid, |e| e.fid)
+            .ok()
+            .map(|i| &self.entries[i])
+    }
+
+    /// Check a FID, annotating the result with its label value and timestamp
+    pub fn check(&self, fid: u64) -> SpamCheckResult {
+        match self.lookup(fid) {
+            Some(entry) => SpamCheckResult {
+                fid,
+                status: match entry.label_value {
+                    0 => "spam",
+                    2 => "not_spam",
+                    _ => "unknown",
+                },
+                label_value: Some(entry.label_value),
+                labeled_at: Some(entry.timestamp),
+            },
+            None => SpamCheckResult {
+                fid,
+                status: "unknown",
+                label_value: None,
+                labeled_at: None,
+            },
+        }
+    }
+}
+
+impl FromIterator<SpamIndexEntry> for SpamIndex {
+    fn from_iter<I: IntoIterator<Item = SpamIndexEntry>>(iter: I) -> Self {
+        let mut entries: Vec<SpamIndexEntry> = iter.into_iter().collect();
+        entries.sort_unstable_by_key(|e| e.fid);
+        entries.dedup_by_key(|e| e.fid);
+        SpamIndex { entries }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
 
     #[test]
     fn test_spam_checker() {
@@ -117,4 +225,27 @@ mod tests {
         // let checker = SpamChecker::load_from_file("test_spam.jsonl").unwrap();
         // assert_eq!(checker.is_spam(12345), Some(true));
     }
+
+    #[test]
+    fn test_spam_index_lookup() {
+        let index = SpamIndex {
+            entries: vec![
+                SpamIndexEntry {
+                    fid: 3,
+                    label_value: 2,
+                    timestamp: 100,
+                },
+                SpamIndexEntry {
+                    fid: 9,
+                    label_value: 0,
+                    timestamp: 200,
+                },
+            ],
+        };
+        assert_eq!(index.check(9).status, "spam");
+        assert_eq!(index.check(9).labeled_at, Some(200));
+        assert_eq!(index.check(3).status, "not_spam");
+        assert_eq!(index.check(4).status, "unknown");
+        assert!(index.check(4).labeled_at.is_none());
+    }
 }
@@ -0,0 +1,211 @@
+//! Out-of-band checks of signed Farcaster messages
+//!
+//! Recomputes a message's hash and verifies its signature the way hubs do, so
+//! a message from a log, an export or a failed submission can be checked
+//! without a hub. The signature checks also work on arbitrary payloads: raw
+//! bytes for Ed25519, typed data or a digest for EIP-712.
+
+use anyhow::Context;
+use anyhow::Result;
+use ed25519_dalek::Signature;
+use ed25519_dalek::Verifier;
+use ed25519_dalek::VerifyingKey;
+use ethers::types::transaction::eip712::Eip712;
+use ethers::types::transaction::eip712::TypedData;
+use ethers::types::Address;
+use ethers::types::H256;
+use protobuf::Message as _;
+use serde::Serialize;
+
+use crate::core::client::hub_client::message_data;
+use crate::core::crypto::auth_address::message_data_digest;
+use crate::core::protocol::builder::validate_message_data;
+use crate::core::protocol::builder::MESSAGE_HASH_BYTES;
+use crate::core::protocol::message::HashScheme;
+use crate::core::protocol::message::Message;
+use crate::core::protocol::message::SignatureScheme;
+
+/// BLAKE3 hash of message data, truncated to 20 bytes as hubs do
+pub fn message_hash(data_bytes: &[u8]) -> Vec<u8> {
+    blake3::hash(data_bytes).as_bytes()[..MESSAGE_HASH_BYTES].to_vec()
+}
+
+/// Verify an Ed25519 signature over `payload`
+///
+/// # Arguments
+/// * `public_key` - 32-byte Ed25519 public key
+/// * `payload` - The signed bytes; for hub messages, the message hash
+/// * `signature` - 64-byte signature
+pub fn verify_ed25519(public_key: &[u8], payload: &[u8], signature: &[u8]) -> Result<()> {
+    let public_key: [u8; 32] = public_key.try_into().map_err(|_| {
+        anyhow::anyhow!(
+            "Ed25519 public key must be 32 bytes, got {}",
+            public_key.len()
+        )
+    })?;
+    let key = VerifyingKey::from_bytes(&public_key).context("Invalid Ed25519 public key")?;
+    let signature = Signature::from_slice(signature).map_err(|_| {
+        anyhow::anyhow!(
+            "Ed25519 signature must be 64 bytes, got {}",
+            signature.len()
+        )
+    })?;
+    key.verify(payload, &signature)
+        .map_err(|_| anyhow::anyhow!("Ed25519 signature does not match"))
+}
+
+/// Verify an EIP-712 signature over a digest
+///
+/// # Arguments
+/// * `address` - Ethereum address expected to have signed
+/// * `digest` - The EIP-712 digest (`keccak256(0x1901 ‖ domain ‖ struct)`)
+/// * `signature` - 65-byte `r ‖ s ‖ v` signature
+pub fn verify_eip712_digest(address: Address, digest: H256, signature: &[u8]) -> Result<()> {
+    let signature = ethers::types::Signature::try_from(signature)
+        .map_err(|e| anyhow::anyhow!("Invalid EIP-712 signature: {e}"))?;
+    let recovered = signature
+        .recover(digest)
+        .context("Failed to recover the EIP-712 signer")?;
+    if recovered != address {
+        anyhow::bail!("EIP-712 signature was made by {recovered:?}, not {address:?}");
+    }
+    Ok(())
+}
+
+/// Verify an EIP-712 signature over typed data, such as a signed key request
+/// or an ID registry transfer
+pub fn verify_typed_data(address: Address, typed_data: &TypedData, signature: &[u8]) -> Result<()> {
+    let digest = typed_data
+        .encode_eip712()
+        .map_err(|e| anyhow::anyhow!("Failed to encode typed data: {e}"))?;
+    verify_eip712_digest(address, H256::from(digest), signature)
+}
+
+/// Verify an auth address's EIP-712 signature over a message hash, as used by
+/// `SIGNATURE_SCHEME_EIP712` messages
+pub fn verify_message_hash_eip712(
+    address: Address,
+    message_hash: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    verify_eip712_digest(address, message_data_digest(message_hash), signature)
+}
+
+/// Outcome of checking one message
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageVerification {
+    /// Hash of the message data as a hub computes it, `0x` hex
+    pub computed_hash: String,
+    /// Whether the message's own hash and hash scheme match
+    pub hash_ok: bool,
+    /// Why the data breaks the protocol's rules, if it does
+    pub validation_error: Option<String>,
+    /// Whether the signature is valid, `None` when it was not checked
+    pub signature_ok: Option<bool>,
+    /// Why the signature check failed
+    pub signature_error: Option<String>,
+}
+
+impl MessageVerification {
+    /// Whether every check made passed
+    pub fn is_valid(&self) -> bool {
+        self.hash_ok && self.validation_error.is_none() && self.signature_ok != Some(false)
+    }
+}
+
+/// Check a message's hash and data, and optionally its signature
+///
+/// The hash is computed over `data_bytes` when present, since that is what
+/// was signed; otherwise over the re-encoded `data`.
+pub fn verify_message(message: &Message, check_signature: bool) -> Result<MessageVerification> {
+    let data = message_data(message)?;
+    let data_bytes = if message.get_data_bytes().is_empty() {
+        data.write_to_bytes()?
+    } else {
+        message.get_data_bytes().to_vec()
+    };
+    let computed_hash = message_hash(&data_bytes);
+    let hash_ok = message.get_hash_scheme() == HashScheme::HASH_SCHEME_BLAKE3
+        && message.get_hash() == computed_hash.as_slice();
+
+    let mut verification = MessageVerification {
+        computed_hash: format!("0x{}", hex::encode(&computed_hash)),
+        hash_ok,
+        validation_error: validate_message_data(&data).err().map(|e| format!("{e:#}")),
+        signature_ok: None,
+        signature_error: None,
+    };
+    if check_signature {
+        let result = match message.get_signature_scheme() {
+            SignatureScheme::SIGNATURE_SCHEME_ED25519 => verify_ed25519(
+                message.get_signer(),
+                message.get_hash(),
+                message.get_signature(),
+            ),
+            SignatureScheme::SIGNATURE_SCHEME_EIP712 if message.get_signer().len() == 20 => {
+                verify_message_hash_eip712(
+                    Address::from_slice(message.get_signer()),
+                    message.get_hash(),
+                    message.get_signature(),
+                )
+            }
+            SignatureScheme::SIGNATURE_SCHEME_EIP712 => Err(anyhow::anyhow!(
+                "EIP-712 signer must be a 20-byte address, got {} bytes",
+                message.get_signer().len()
+            )),
+            scheme => Err(anyhow::anyhow!("{scheme:?} signatures are not supported")),
+        };
+        verification.signature_ok = Some(result.is_ok());
+        verification.signature_error = result.err().map(|e| format!("{e:#}"));
+    }
+    Ok(verification)
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::SigningKey;
+    use ethers::signers::LocalWallet;
+    use ethers::signers::Signer;
+
+    use super::*;
+    use crate::core::protocol::builder::MessageBuilder;
+
+    #[test]
+    fn test_verify_message() {
+        let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+        let message = MessageBuilder::new(42)
+            .cast("gm")
+            .sign(&signing_key)
+            .unwrap();
+        let verification = verify_message(&message, true).unwrap();
+        assert!(verification.is_valid());
+        assert_eq!(verification.signature_ok, Some(true));
+
+        let mut tampered = message.clone();
+        let mut signature = tampered.get_signature().to_vec();
+        signature[0] ^= 1;
+        tampered.set_signature(signature);
+        let verification = verify_message(&tampered, true).unwrap();
+        assert!(verification.hash_ok);
+        assert_eq!(verification.signature_ok, Some(false));
+
+        let mut rehashed = message;
+        rehashed.set_hash(vec![0; 20]);
+        assert!(!verify_message(&rehashed, false).unwrap().hash_ok);
+    }
+
+    #[test]
+    fn test_verify_eip712() {
+        let wallet: LocalWallet =
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let hash = [3u8; 20];
+        let signature = wallet.sign_hash(message_data_digest(&hash)).unwrap();
+        verify_message_hash_eip712(wallet.address(), &hash, &signature.to_vec()).unwrap();
+        assert!(verify_message_hash_eip712(Address::zero(), &hash, &signature.to_vec()).is_err());
+        assert!(
+            verify_message_hash_eip712(wallet.address(), &[4u8; 20], &signature.to_vec()).is_err()
+        );
+    }
+}
@@ -0,0 +1,304 @@
+//! Signed release manifests
+//!
+//! A release publishes a JSON manifest listing one artifact per target together
+//! with its SHA-256 digest, and a minisign signature of that manifest at
+//! `<manifest url>.minisig`. The manifest is only trusted once the signature
+//! checks out against the release public key; artifacts are then trusted by hash.
+//!
+//! The public key is compiled into the binary, so a manifest can only be
+//! swapped by someone holding the release signing key.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use minisign_verify::PublicKey;
+use minisign_verify::Signature;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+/// Base64 minisign public key of releases, embedded at build time
+///
+/// Release builds set `CASTORIX_RELEASE_PUBKEY` when compiling; the variable
+/// is not read at run time.
+pub const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("CASTORIX_RELEASE_PUBKEY");
+/// Environment variable overriding the default manifest URL
+pub const RELEASE_MANIFEST_ENV: &str = "CASTORIX_RELEASE_MANIFEST";
+/// Manifest of the latest GitHub release
+pub const DEFAULT_MANIFEST_URL: &str =
+    "https://github.com/RyanKung/castorix/releases/latest/download/manifest.json";
+/// Download prefix of tagged GitHub releases
+const RELEASE_DOWNLOAD_URL: &str = "https://github.com/RyanKung/castorix/releases/download";
+
+/// Version of the running binary
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A single downloadable build in a release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseArtifact {
+    /// Target triple-like key, e.g. `x86_64-linux`
+    pub target: String,
+    /// Download URL of the binary
+    pub url: String,
+    /// Hex encoded SHA-256 digest of the binary
+    pub sha256: String,
+}
+
+/// Signed description of a release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseManifest {
+    pub version: String,
+    pub artifacts: Vec<ReleaseArtifact>,
+}
+
+impl ReleaseManifest {
+    /// Parse a manifest after checking its minisign signature
+    ///
+    /// # Arguments
+    /// * `manifest` - Raw manifest bytes exactly as downloaded
+    /// * `signature` - Contents of the `.minisig` file
+    /// * `public_key` - Base64 minisign public key
+    ///
+    /// # Returns
+    /// * `Result<ReleaseManifest>` - The manifest, or an error if the signature does not verify
+    pub fn verify(manifest: &[u8], signature: &str, public_key: &str) -> Result<Self> {
+        let public_key = PublicKey::from_base64(public_key.trim())
+            .map_err(|e| anyhow::anyhow!("Invalid release public key: {e}"))?;
+        let signature = Signature::decode(signature)
+            .map_err(|e| anyhow::anyhow!("Invalid manifest signature: {e}"))?;
+        public_key
+            .verify(manifest, &signature, false)
+            .map_err(|e| anyhow::anyhow!("Manifest signature verification failed: {e}"))?;
+        serde_json::from_slice(manifest).context("Signed manifest is not valid JSON")
+    }
+
+    /// Get the artifact built for a target
+    pub fn artifact_for(&self, target: &str) -> Option<&ReleaseArtifact> {
+        self.artifacts.iter().find(|a| a.target == target)
+    }
+
+    /// Whether this release is newer than the running binary
+    pub fn is_newer_than_current(&self) -> bool {
+        version_key(&self.version) > version_key(CURRENT_VERSION)
+    }
+
+    /// Fail unless this manifest describes the given version
+    ///
+    /// A signed manifest for another release is still authentic, but its
+    /// digests say nothing about a binary built from a different version.
+    pub fn ensure_version(&self, version: &str) -> Result<()> {
+        if version_key(&self.version) != version_key(version) {
+            anyhow::bail!(
+                "Manifest is for release {}, not for castorix {version}",
+                self.version
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Target key of the running binary, e.g. `x86_64-linux`
+pub fn current_target() -> String {
+    format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS)
+}
+
+/// Manifest URL to use: explicit argument, then environment, then the latest release
+pub fn manifest_url(explicit: Option<String>) -> String {
+    explicit
+        .or_else(|| std::env::var(RELEASE_MANIFEST_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_MANIFEST_URL.to_string())
+}
+
+/// Manifest URL of a tagged release, e.g. `v0.1.0`
+pub fn version_manifest_url(version: &str) -> String {
+    format!(
+        "{RELEASE_DOWNLOAD_URL}/v{}/manifest.json",
+        version.trim_start_matches('v')
+    )
+}
+
+/// Release public key to use: explicit argument, then the embedded key
+pub fn release_public_key(explicit: Option<String>) -> Result<String> {
+    explicit
+        .or_else(|| RELEASE_PUBLIC_KEY.map(str::to_string))
+        .filter(|k| !k.trim().is_empty())
+        .context("This build has no embedded release public key; pass --public-key")
+}
+
+/// Download a manifest and its signature and verify them
+///
+/// # Arguments
+/// * `url` - Manifest URL; the signature is fetched from `<url>.minisig`
+/// * `public_key` - Base64 minisign public key
+///
+/// # Returns
+/// * `Result<ReleaseManifest>` - The verified manifest or an error
+pub async fn fetch_manifest(url: &str, public_key: &str) -> Result<ReleaseManifest> {
+    let client = crate::core::utils::http::client();
+    let manifest = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()
+        .with_context(|| format!("Failed to download manifest {url}"))?
+        .bytes()
+        .await?;
+    let signature = client
+        .get(format!("{url}.minisig"))
+        .send()
+        .await?
+        .error_for_status()
+        .with_context(|| format!("Failed to download signature {url}.minisig"))?
+        .text()
+        .await?;
+    ReleaseManifest::verify(&manifest, &signature, public_key)
+}
+
+/// Hex encoded SHA-256 digest of some bytes
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Path and SHA-256 digest of the running binary
+pub fn current_exe_digest() -> Result<(PathBuf, String)> {
+    let exe = std::env::current_exe().context("Cannot locate the running binary")?;
+    let bytes = std::fs::read(&exe).with_context(|| format!("Cannot read {}", exe.display()))?;
+    Ok((exe, sha256_hex(&bytes)))
+}
+
+/// Download an artifact and check it against the digest in the manifest
+pub async fn download_artifact(artifact: &ReleaseArtifact) -> Result<Vec<u8>> {
+    let bytes = crate::core::utils::http::client()
+        .get(&artifact.url)
+        .send()
+        .await?
+        .error_for_status()
+        .with_context(|| format!("Failed to download {}", artifact.url))?
+        .bytes()
+        .await?
+        .to_vec();
+    let digest = sha256_hex(&bytes);
+    if !digest.eq_ignore_ascii_case(&artifact.sha256) {
+        anyhow::bail!(
+            "Downloaded artifact hash {digest} does not match manifest {}",
+            artifact.sha256
+        );
+    }
+    Ok(bytes)
+}
+
+/// Replace a binary with new contents
+///
+/// The new binary is written next to the old one and renamed over it, so a
+/// failure part way leaves the original in place.
+pub fn replace_binary(exe: &Path, bytes: &[u8]) -> Result<()> {
+    let staged = exe.with_extension("update");
+    std::fs::write(&staged, bytes).with_context(|| format!("Cannot write {}", staged.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(exe)
+            .map(|m| m.permissions().mode())
+            .unwrap_or(0o755);
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(mode))?;
+    }
+
+    std::fs::rename(&staged, exe).with_context(|| {
+        let _ = std::fs::remove_file(&staged);
+        format!("Cannot replace {}", exe.display())
+    })
+}
+
+/// Numeric `major.minor.patch` key used to order versions
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Key pair and signature of the file "test" from the minisign-verify documentation
+    const PUBLIC_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=
+trusted comment: timestamp:1633700835\tfile:test\tprehashed
+wLMDjy9FLAuxZ3q4NlEvkgtyhrr0gtTu6KC4KBJdITbbOeAi1zBIYo0v4iTgt8jJpIidRJnp94ABQkJAgAooBQ==";
+
+    #[test]
+    fn test_verify_checks_signature_before_parsing() {
+        let tampered = ReleaseManifest::verify(b"tampered", SIGNATURE, PUBLIC_KEY);
+        assert!(tampered
+            .unwrap_err()
+            .to_string()
+            .contains("verification failed"));
+
+        // A valid signature gets past verification and fails only on the JSON body
+        let signed = ReleaseManifest::verify(b"test", SIGNATURE, PUBLIC_KEY);
+        assert!(signed.unwrap_err().to_string().contains("not valid JSON"));
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        assert!(version_key("0.2.0") > version_key("0.1.9"));
+        assert!(version_key("v1.10.0") > version_key("1.9.3"));
+        assert_eq!(version_key("0.1.0-rc.1"), version_key("0.1.0"));
+    }
+
+    #[test]
+    fn test_artifact_lookup() {
+        let manifest = ReleaseManifest {
+            version: "9.9.9".to_string(),
+            artifacts: vec![ReleaseArtifact {
+                target: current_target(),
+                url: "https://example.com/castorix".to_string(),
+                sha256: sha256_hex(b""),
+            }],
+        };
+        assert!(manifest.is_newer_than_current());
+        assert!(manifest.artifact_for(&current_target()).is_some());
+        assert!(manifest.artifact_for("sparc-plan9").is_none());
+    }
+
+    #[test]
+    fn test_ensure_version_rejects_other_releases() {
+        let manifest = ReleaseManifest {
+            version: "v0.2.0".to_string(),
+            artifacts: Vec::new(),
+        };
+        assert!(manifest.ensure_version("0.2.0").is_ok());
+        assert!(manifest.ensure_version("0.1.9").is_err());
+    }
+
+    #[test]
+    fn test_version_manifest_url() {
+        assert_eq!(
+            version_manifest_url("0.1.0"),
+            "https://github.com/RyanKung/castorix/releases/download/v0.1.0/manifest.json"
+        );
+        assert_eq!(
+            version_manifest_url("v0.1.0"),
+            version_manifest_url("0.1.0")
+        );
+    }
+
+    #[test]
+    fn test_explicit_public_key_wins() {
+        let key = release_public_key(Some(PUBLIC_KEY.to_string())).unwrap();
+        assert_eq!(key, PUBLIC_KEY);
+        if RELEASE_PUBLIC_KEY.is_none() {
+            assert!(release_public_key(None).is_err());
+        }
+    }
+}
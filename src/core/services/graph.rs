@@ -0,0 +1,143 @@
+//! Social graph queries: followers and following of a FID
+
+use anyhow::Result;
+use futures::Stream;
+use futures::StreamExt;
+use serde_json::json;
+use serde_json::Value;
+
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::time_window::ListOrder;
+use crate::core::client::time_window::TimeWindow;
+
+/// Which side of the follow graph to list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkDirection {
+    /// Accounts following the FID
+    Followers,
+    /// Accounts the FID follows
+    Following,
+}
+
+impl LinkDirection {
+    /// Name used in messages and as the list's key in JSON results
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Followers => "followers",
+            Self::Following => "following",
+        }
+    }
+
+    /// FID on the other end of a follow link: the follower, or the followed account
+    pub fn linked_fid(&self, link: &Value) -> Option<u64> {
+        let pointer = match self {
+            Self::Followers => "/data/fid",
+            Self::Following => "/data/linkBody/targetFid",
+        };
+        link.pointer(pointer).and_then(|fid| fid.as_u64())
+    }
+
+    /// JSON result for a list of links: `{"fid", "<followers|following>", "count"}`
+    pub fn to_json(&self, fid: u64, links: &[Value]) -> Value {
+        json!({
+            "fid": fid,
+            self.name(): links,
+            "count": links.len(),
+        })
+    }
+}
+
+/// Follow graph queries shared by the CLI, REST API and MCP server
+pub struct GraphService<'a> {
+    client: &'a FarcasterClient,
+}
+
+impl<'a> GraphService<'a> {
+    pub fn new(client: &'a FarcasterClient) -> Self {
+        Self { client }
+    }
+
+    /// Fetch up to `limit` links (0 for all), pipelining page requests
+    ///
+    /// # Arguments
+    /// * `direction` - Followers or following
+    /// * `fid` - The Farcaster ID
+    /// * `limit` - Maximum number of links, 0 for all
+    pub async fn links(
+        &self,
+        direction: LinkDirection,
+        fid: u64,
+        limit: u32,
+    ) -> Result<Vec<Value>> {
+        match direction {
+            LinkDirection::Followers => self.client.get_followers(fid, limit).await,
+            LinkDirection::Following => self.client.get_following(fid, limit).await,
+        }
+    }
+
+    /// Fetch up to `limit` links (0 for all) made inside a time window
+    ///
+    /// Follow links are listed by FID rather than by time, so every page is
+    /// read and filtered.
+    pub async fn links_in_window(
+        &self,
+        direction: LinkDirection,
+        fid: u64,
+        window: TimeWindow,
+        limit: u32,
+    ) -> Result<Vec<Value>> {
+        let query = match direction {
+            LinkDirection::Followers => {
+                format!("/v1/linksByTargetFid?target_fid={fid}&link_type=follow")
+            }
+            LinkDirection::Following => format!("/v1/linksByFid?fid={fid}&link_type=follow"),
+        };
+        self.client
+            .get_messages_in_window(
+                &query,
+                ListOrder::Unordered,
+                window,
+                limit,
+                direction.name(),
+            )
+            .await
+    }
+
+    /// Stream up to `limit` links (0 for all) one page at a time
+    ///
+    /// For callers that report progress or stop early; holds at most one
+    /// page in memory.
+    pub fn links_stream(
+        &self,
+        direction: LinkDirection,
+        fid: u64,
+        limit: u32,
+    ) -> impl Stream<Item = Result<Value>> {
+        let stream = match direction {
+            LinkDirection::Followers => self.client.followers_stream(fid).left_stream(),
+            LinkDirection::Following => self.client.following_stream(fid).right_stream(),
+        };
+        let limit = if limit == 0 {
+            usize::MAX
+        } else {
+            limit as usize
+        };
+        stream.take(limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linked_fid() {
+        let link = json!({"data": {"fid": 5, "linkBody": {"type": "follow", "targetFid": 3}}});
+        assert_eq!(LinkDirection::Followers.linked_fid(&link), Some(5));
+        assert_eq!(LinkDirection::Following.linked_fid(&link), Some(3));
+
+        let result = LinkDirection::Following.to_json(5, &[link]);
+        assert_eq!(result["count"], 1);
+        assert_eq!(result["following"][0]["data"]["fid"], 5);
+    }
+}
@@ -0,0 +1,149 @@
+//! Message counts: how many messages a FID has in each hub store
+//!
+//! Hubs do not return totals for their list endpoints, so each store is
+//! counted by walking its `...ByFid` endpoint. The walk can stop early at a
+//! cap, which is enough to tell a quiet account from a busy one cheaply.
+
+use anyhow::Result;
+use futures::StreamExt;
+use serde::Serialize;
+
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::services::profile::StorageUsage;
+
+/// Stores counted, with the endpoint listing them and their storage limit name
+pub const MESSAGE_STORES: [(&str, &str, &str); 5] = [
+    ("casts", "castsByFid", "CASTS"),
+    ("reactions", "reactionsByFid", "REACTIONS"),
+    ("links", "linksByFid", "LINKS"),
+    ("verifications", "verificationsByFid", "VERIFICATIONS"),
+    ("user_data", "userDataByFid", "USER_DATA"),
+];
+
+/// Messages of one store
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StoreCount {
+    /// Store name, e.g. `casts`
+    pub store: &'static str,
+    pub count: u64,
+    /// Whether counting stopped at the cap, making `count` a lower bound
+    pub capped: bool,
+    /// The store's `used` figure from the FID's storage limits, if reported
+    pub storage_used: Option<u64>,
+}
+
+/// Message counts of a FID, one entry per store in [`MESSAGE_STORES`] order
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageCounts {
+    pub fid: u64,
+    pub stores: Vec<StoreCount>,
+}
+
+impl MessageCounts {
+    /// Messages across all stores
+    pub fn total(&self) -> u64 {
+        self.stores.iter().map(|store| store.count).sum()
+    }
+
+    /// Whether any store stopped at the cap
+    pub fn capped(&self) -> bool {
+        self.stores.iter().any(|store| store.capped)
+    }
+}
+
+/// Message queries against the hub
+pub struct MessageService<'a> {
+    client: &'a FarcasterClient,
+}
+
+impl<'a> MessageService<'a> {
+    pub fn new(client: &'a FarcasterClient) -> Self {
+        Self { client }
+    }
+
+    /// Count a FID's messages in every store
+    ///
+    /// Stores are walked at the same time, one page at a time each.
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `cap` - Stop counting a store after this many messages
+    pub async fn counts(&self, fid: u64, cap: Option<u64>) -> Result<MessageCounts> {
+        let storage = match self.client.get_storage_limits(fid).await {
+            Ok(limits) => StorageUsage::from_limits(&limits),
+            Err(_) => Vec::new(),
+        };
+        let counts = futures::future::join_all(
+            MESSAGE_STORES
+                .iter()
+                .map(|&(_, endpoint, _)| self.count_store(endpoint, fid, cap)),
+        )
+        .await;
+
+        let mut stores = Vec::with_capacity(MESSAGE_STORES.len());
+        for (&(store, _, limit_name), count) in MESSAGE_STORES.iter().zip(counts) {
+            let (count, capped) = count?;
+            stores.push(StoreCount {
+                store,
+                count,
+                capped,
+                storage_used: storage
+                    .iter()
+                    .find(|usage| usage.name == limit_name)
+                    .map(|usage| usage.used),
+            });
+        }
+        Ok(MessageCounts { fid, stores })
+    }
+
+    async fn count_store(
+        &self,
+        endpoint: &'static str,
+        fid: u64,
+        cap: Option<u64>,
+    ) -> Result<(u64, bool)> {
+        let mut messages = std::pin::pin!(self.client.fid_messages_stream(endpoint, fid));
+        let mut count = 0;
+        while let Some(message) = messages.next().await {
+            message?;
+            if cap.is_some_and(|cap| count >= cap) {
+                return Ok((count, true));
+            }
+            count += 1;
+        }
+        Ok((count, false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_counts() {
+        let store = |store, count, capped| StoreCount {
+            store,
+            count,
+            capped,
+            storage_used: None,
+        };
+        let counts = MessageCounts {
+            fid: 1,
+            stores: vec![store("casts", 10, false), store("links", 5, false)],
+        };
+        assert_eq!(counts.total(), 15);
+        assert!(!counts.capped());
+
+        let counts = MessageCounts {
+            fid: 1,
+            stores: vec![store("casts", 100, true)],
+        };
+        assert!(counts.capped());
+    }
+
+    #[tokio::test]
+    async fn test_counts_fail_without_hub() {
+        let client = FarcasterClient::read_only("http://127.0.0.1:1".to_string());
+        assert!(MessageService::new(&client).counts(1, None).await.is_err());
+    }
+}
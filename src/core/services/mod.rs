@@ -0,0 +1,24 @@
+//! Query services shared by the CLI, REST API and MCP server
+//!
+//! Each frontend parses its own input and renders its own output, but the
+//! queries in between go through these services, so a profile, a follower
+//! list or a spam status is computed the same way whichever frontend asks,
+//! and a feature added here becomes available to all three.
+
+pub mod graph;
+pub mod messages;
+pub mod profile;
+pub mod signers;
+pub mod spam;
+
+pub use graph::GraphService;
+pub use graph::LinkDirection;
+pub use messages::MessageCounts;
+pub use messages::MessageService;
+pub use profile::Profile;
+pub use profile::ProfileService;
+pub use profile::UserStats;
+pub use signers::SignerService;
+pub use signers::SignerUsageReport;
+pub use spam::SpamService;
+pub use spam::SpamSummary;
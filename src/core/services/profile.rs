@@ -0,0 +1,226 @@
+//! Profile queries: user data, stats, addresses and usernames of a FID
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::client::follower_count::FollowerCount;
+use crate::core::client::hub_client::FarcasterClient;
+use crate::core::client::profile::current_profile;
+
+/// Pages of follow links sampled when counting followers for stats
+pub const DEFAULT_SAMPLE_PAGES: u32 = 5;
+
+/// Profile of a FID, parsed from its user data messages
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub fid: u64,
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub pfp_url: Option<String>,
+    pub banner_url: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    pub twitter: Option<String>,
+    pub github: Option<String>,
+    pub primary_eth_address: Option<String>,
+    pub primary_sol_address: Option<String>,
+    /// Number of user data messages the profile was built from
+    pub fields: usize,
+}
+
+impl Profile {
+    /// Build a profile from userDataByFid messages; empty values count as unset
+    pub fn from_messages(fid: u64, messages: &[Value]) -> Self {
+        let mut values = current_profile(messages);
+        values.retain(|_, value| !value.is_empty());
+        let primary_address = |hub_name: &str| {
+            messages
+                .iter()
+                .filter_map(|m| m.pointer("/data/userDataBody"))
+                .filter(|body| body.get("type").and_then(|t| t.as_str()) == Some(hub_name))
+                .filter_map(|body| body.get("value").and_then(|v| v.as_str()))
+                .find(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        Self {
+            fid,
+            username: values.remove("username"),
+            display_name: values.remove("display"),
+            bio: values.remove("bio"),
+            pfp_url: values.remove("pfp"),
+            banner_url: values.remove("banner"),
+            location: values.remove("location"),
+            url: values.remove("url"),
+            twitter: values.remove("twitter"),
+            github: values.remove("github"),
+            primary_eth_address: primary_address("USER_DATA_PRIMARY_ADDRESS_ETHEREUM"),
+            primary_sol_address: primary_address("USER_DATA_PRIMARY_ADDRESS_SOLANA"),
+            fields: messages.len(),
+        }
+    }
+
+    /// Build a profile from a whole userDataByFid response
+    pub fn from_user_data(fid: u64, user_data: &Value) -> Self {
+        let messages = user_data
+            .get("messages")
+            .and_then(|m| m.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        Self::from_messages(fid, messages)
+    }
+
+    /// Whether the FID has no user data at all
+    pub fn is_empty(&self) -> bool {
+        self.fields == 0
+    }
+}
+
+/// Usage of one storage store of a FID
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageUsage {
+    /// Store name, e.g. `LINKS` or `CASTS`
+    pub name: String,
+    pub store_type: String,
+    pub used: u64,
+    pub limit: u64,
+}
+
+impl StorageUsage {
+    /// Parse the `limits` of a storageLimitsByFid response
+    pub fn from_limits(storage_limits: &Value) -> Vec<Self> {
+        storage_limits
+            .get("limits")
+            .and_then(|l| l.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|limit| {
+                Some(Self {
+                    name: limit.get("name")?.as_str()?.to_string(),
+                    store_type: limit.get("storeType")?.as_str()?.to_string(),
+                    used: limit.get("used")?.as_u64()?,
+                    limit: limit.get("limit")?.as_u64()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Share of the limit in use, in whole percent
+    pub fn percent(&self) -> u64 {
+        (self.used * 100).checked_div(self.limit).unwrap_or(0)
+    }
+}
+
+/// Follower, following and storage figures of a FID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserStats {
+    pub fid: u64,
+    pub followers: FollowerCount,
+    /// Follow links the FID holds, from its LINKS storage usage
+    pub following: Option<u64>,
+    pub storage: Vec<StorageUsage>,
+}
+
+/// Profile queries shared by the CLI, REST API and MCP server
+pub struct ProfileService<'a> {
+    client: &'a FarcasterClient,
+}
+
+impl<'a> ProfileService<'a> {
+    pub fn new(client: &'a FarcasterClient) -> Self {
+        Self { client }
+    }
+
+    /// Raw userDataByFid response of a FID
+    pub async fn user(&self, fid: u64) -> Result<Value> {
+        self.client.get_user(fid).await
+    }
+
+    /// Parsed profile of a FID
+    pub async fn profile(&self, fid: u64) -> Result<Profile> {
+        let messages = self.client.get_user_profile(fid).await?;
+        Ok(Profile::from_messages(fid, &messages))
+    }
+
+    /// Follower count and storage usage of a FID
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `sample_pages` - Pages of follow links to count before estimating
+    pub async fn stats(&self, fid: u64, sample_pages: u32) -> Result<UserStats> {
+        let (followers, storage_limits) = tokio::try_join!(
+            self.client.get_follower_count(fid, sample_pages),
+            self.client.get_storage_limits(fid),
+        )?;
+        let storage = StorageUsage::from_limits(&storage_limits);
+        let following = storage
+            .iter()
+            .find(|usage| usage.name == "LINKS")
+            .map(|usage| usage.used);
+        Ok(UserStats {
+            fid,
+            followers,
+            following,
+            storage,
+        })
+    }
+
+    /// Verified Ethereum addresses of a FID
+    pub async fn eth_addresses(&self, fid: u64) -> Result<Vec<String>> {
+        self.client.get_eth_addresses(fid).await
+    }
+
+    /// Custody address of a FID
+    pub async fn custody_address(&self, fid: u64) -> Result<String> {
+        self.client.get_custody_address(fid).await
+    }
+
+    /// ENS names and Basenames a FID has username proofs for
+    pub async fn ens_domains(&self, fid: u64) -> Result<Vec<String>> {
+        let proofs = self.client.get_username_proofs(fid).await?;
+        Ok(proofs.into_iter().map(|proof| proof.name).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn user_data(data_type: &str, value: &str) -> Value {
+        json!({"data": {"userDataBody": {"type": data_type, "value": value}}})
+    }
+
+    #[test]
+    fn test_profile_from_messages() {
+        let messages = [
+            user_data("USER_DATA_TYPE_USERNAME", "alice"),
+            user_data("USER_DATA_TYPE_BIO", ""),
+            user_data("USER_DATA_TYPE_PFP", "https://example.com/a.png"),
+            user_data("USER_DATA_PRIMARY_ADDRESS_ETHEREUM", "0xabc"),
+        ];
+        let profile = Profile::from_messages(3, &messages);
+        assert_eq!(profile.username.as_deref(), Some("alice"));
+        assert_eq!(profile.bio, None);
+        assert_eq!(
+            profile.pfp_url.as_deref(),
+            Some("https://example.com/a.png")
+        );
+        assert_eq!(profile.primary_eth_address.as_deref(), Some("0xabc"));
+        assert_eq!(profile.fields, 4);
+        assert!(Profile::from_user_data(3, &json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_storage_usage() {
+        let usage = StorageUsage::from_limits(&json!({"limits": [
+            {"storeType": "STORE_TYPE_LINKS", "name": "LINKS", "limit": 2500, "used": 250},
+            {"storeType": "STORE_TYPE_CASTS", "name": "CASTS"}
+        ]}));
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].percent(), 10);
+    }
+}
@@ -0,0 +1,202 @@
+//! Signer usage: how many hub messages each Ed25519 signer of a FID signed
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use futures::StreamExt;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::client::hub_client::FarcasterClient;
+
+/// Hub endpoints listing the messages a FID authored
+pub const MESSAGE_ENDPOINTS: [&str; 5] = [
+    "castsByFid",
+    "reactionsByFid",
+    "linksByFid",
+    "userDataByFid",
+    "verificationsByFid",
+];
+
+/// Messages signed by one signer key
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SignerUsage {
+    /// Ed25519 public key, lowercase hex with `0x`
+    pub key: String,
+    /// Whether the key is currently registered in KeyRegistry
+    pub active: bool,
+    pub messages: u64,
+    /// Message counts by type, e.g. `cast_add`
+    pub by_type: BTreeMap<String, u64>,
+    /// Farcaster timestamp of the oldest message
+    pub first_timestamp: Option<u64>,
+    /// Farcaster timestamp of the newest message
+    pub last_timestamp: Option<u64>,
+}
+
+impl SignerUsage {
+    fn new(key: String, active: bool) -> Self {
+        Self {
+            key,
+            active,
+            ..Self::default()
+        }
+    }
+}
+
+/// Signer usage of a FID, built up one message at a time
+#[derive(Debug, Clone, Serialize)]
+pub struct SignerUsageReport {
+    pub fid: u64,
+    /// Messages scanned, including any without a signer
+    pub scanned: u64,
+    /// Active signers first, then keys that signed messages but are no longer
+    /// registered; most used first within each group
+    pub signers: Vec<SignerUsage>,
+}
+
+impl SignerUsageReport {
+    /// Start a report listing every active key with no messages
+    pub fn new(fid: u64, active_keys: &[String]) -> Self {
+        Self {
+            fid,
+            scanned: 0,
+            signers: active_keys
+                .iter()
+                .map(|key| SignerUsage::new(key.to_lowercase(), true))
+                .collect(),
+        }
+    }
+
+    /// Count one hub message against its signer
+    pub fn record(&mut self, message: &Value) {
+        self.scanned += 1;
+        let Some(signer) = message.get("signer").and_then(|s| s.as_str()) else {
+            return;
+        };
+        let key = signer.to_lowercase();
+        let index = match self.signers.iter().position(|usage| usage.key == key) {
+            Some(index) => index,
+            None => {
+                self.signers.push(SignerUsage::new(key, false));
+                self.signers.len() - 1
+            }
+        };
+        let usage = &mut self.signers[index];
+        usage.messages += 1;
+
+        let message_type = message
+            .pointer("/data/type")
+            .and_then(|t| t.as_str())
+            .unwrap_or("MESSAGE_TYPE_UNKNOWN");
+        let message_type = message_type
+            .strip_prefix("MESSAGE_TYPE_")
+            .unwrap_or(message_type)
+            .to_lowercase();
+        *usage.by_type.entry(message_type).or_default() += 1;
+
+        if let Some(timestamp) = message.pointer("/data/timestamp").and_then(|t| t.as_u64()) {
+            usage.first_timestamp = Some(
+                usage
+                    .first_timestamp
+                    .map_or(timestamp, |t| t.min(timestamp)),
+            );
+            usage.last_timestamp =
+                Some(usage.last_timestamp.map_or(timestamp, |t| t.max(timestamp)));
+        }
+    }
+
+    /// Sort signers for display: active first, most used first
+    pub fn finish(mut self) -> Self {
+        self.signers
+            .sort_by(|a, b| b.active.cmp(&a.active).then(b.messages.cmp(&a.messages)));
+        self
+    }
+
+    /// Active signers that have never signed a message
+    pub fn unused(&self) -> impl Iterator<Item = &SignerUsage> {
+        self.signers
+            .iter()
+            .filter(|usage| usage.active && usage.messages == 0)
+    }
+
+    /// Active signers whose newest message is older than `before` (a Farcaster
+    /// timestamp), including unused ones
+    pub fn stale(&self, before: u64) -> impl Iterator<Item = &SignerUsage> {
+        self.signers.iter().filter(move |usage| {
+            usage.active && usage.last_timestamp.is_none_or(|last| last < before)
+        })
+    }
+}
+
+/// Signer queries against the hub
+pub struct SignerService<'a> {
+    client: &'a FarcasterClient,
+}
+
+impl<'a> SignerService<'a> {
+    pub fn new(client: &'a FarcasterClient) -> Self {
+        Self { client }
+    }
+
+    /// Scan every message of a FID and count them by signer
+    ///
+    /// Walks each of [`MESSAGE_ENDPOINTS`] one page at a time, so memory stays
+    /// flat however many messages the FID has.
+    pub async fn usage(&self, fid: u64) -> Result<SignerUsageReport> {
+        let active_keys: Vec<String> = self
+            .client
+            .get_signers(fid)
+            .await?
+            .into_iter()
+            .map(|signer| signer.key)
+            .collect();
+        let mut report = SignerUsageReport::new(fid, &active_keys);
+        for endpoint in MESSAGE_ENDPOINTS {
+            let mut messages = std::pin::pin!(self.client.fid_messages_stream(endpoint, fid));
+            while let Some(message) = messages.next().await {
+                report.record(&message?);
+            }
+        }
+        Ok(report.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn message(signer: &str, message_type: &str, timestamp: u64) -> Value {
+        json!({"data": {"type": message_type, "timestamp": timestamp}, "signer": signer})
+    }
+
+    #[test]
+    fn test_signer_usage_report() {
+        let mut report = SignerUsageReport::new(7, &["0xAA".to_string(), "0xbb".to_string()]);
+        report.record(&message("0xaa", "MESSAGE_TYPE_CAST_ADD", 100));
+        report.record(&message("0xaa", "MESSAGE_TYPE_REACTION_ADD", 300));
+        report.record(&message("0xcc", "MESSAGE_TYPE_CAST_ADD", 50));
+        report.record(&json!({"data": {}}));
+        let report = report.finish();
+
+        assert_eq!(report.scanned, 4);
+        let keys: Vec<&str> = report.signers.iter().map(|u| u.key.as_str()).collect();
+        assert_eq!(keys, ["0xaa", "0xbb", "0xcc"]);
+
+        let used = &report.signers[0];
+        assert_eq!(used.messages, 2);
+        assert_eq!(used.by_type["cast_add"], 1);
+        assert_eq!(
+            (used.first_timestamp, used.last_timestamp),
+            (Some(100), Some(300))
+        );
+        assert!(!report.signers[2].active);
+
+        let unused: Vec<&str> = report.unused().map(|u| u.key.as_str()).collect();
+        assert_eq!(unused, ["0xbb"]);
+        assert_eq!(report.stale(200).count(), 1);
+        assert_eq!(report.stale(400).count(), 2);
+    }
+}
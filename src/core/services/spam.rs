@@ -0,0 +1,134 @@
+//! Spam label lookups against the Warpcast spam dataset
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::core::protocol::spam_checker::SpamCheckResult;
+use crate::core::protocol::spam_checker::SpamIndex;
+use crate::core::protocol::spam_checker::DEFAULT_SPAM_LABELS_PATH;
+
+/// Label value the dataset uses for spam
+const SPAM_LABEL: u8 = 0;
+
+/// Label value the dataset uses for accounts found not to be spam
+const NOT_SPAM_LABEL: u8 = 2;
+
+/// Counts of labels in the dataset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SpamSummary {
+    /// Labelled FIDs
+    pub total: usize,
+    pub spam: usize,
+    pub not_spam: usize,
+    /// FIDs with any other label value
+    pub unknown: usize,
+    /// Unix timestamp of the oldest label
+    pub oldest_label: Option<u64>,
+    /// Unix timestamp of the newest label
+    pub newest_label: Option<u64>,
+}
+
+impl SpamSummary {
+    /// Share of labelled FIDs marked as spam, in percent
+    pub fn spam_percentage(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.spam as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+/// Spam checks shared by the CLI, REST API and MCP server
+///
+/// Cheap to clone; clones share one index, loaded once.
+#[derive(Clone)]
+pub struct SpamService {
+    index: Arc<SpamIndex>,
+}
+
+impl SpamService {
+    /// Load the labels shipped in the `labels` submodule
+    pub fn load() -> Result<Self> {
+        Self::load_from_file(DEFAULT_SPAM_LABELS_PATH)
+    }
+
+    /// Load labels from a JSONL file
+    pub fn load_from_file(path: &str) -> Result<Self> {
+        Ok(Self::new(SpamIndex::load_from_file(path)?))
+    }
+
+    pub fn new(index: SpamIndex) -> Self {
+        Self {
+            index: Arc::new(index),
+        }
+    }
+
+    /// Status of a FID with its label value and timestamp
+    pub fn check(&self, fid: u64) -> SpamCheckResult {
+        self.index.check(fid)
+    }
+
+    /// Status of several FIDs, in the order given
+    pub fn check_many(&self, fids: &[u64]) -> Vec<SpamCheckResult> {
+        fids.iter().map(|&fid| self.check(fid)).collect()
+    }
+
+    /// Whether a FID is labelled as spam; unlabelled FIDs are not
+    pub fn is_spam(&self, fid: u64) -> bool {
+        self.index
+            .lookup(fid)
+            .is_some_and(|entry| entry.label_value == SPAM_LABEL)
+    }
+
+    /// Counts of labels by value
+    pub fn summary(&self) -> SpamSummary {
+        let entries = self.index.entries();
+        let count = |value| entries.iter().filter(|e| e.label_value == value).count();
+        let spam = count(SPAM_LABEL);
+        let not_spam = count(NOT_SPAM_LABEL);
+        SpamSummary {
+            total: entries.len(),
+            spam,
+            not_spam,
+            unknown: entries.len() - spam - not_spam,
+            oldest_label: entries.iter().map(|e| e.timestamp).min(),
+            newest_label: entries.iter().map(|e| e.timestamp).max(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::protocol::spam_checker::SpamIndexEntry;
+
+    #[test]
+    fn test_check_and_summary() {
+        let service = SpamService::new(
+            [(9, SPAM_LABEL), (3, NOT_SPAM_LABEL), (4, 1)]
+                .into_iter()
+                .map(|(fid, label_value)| SpamIndexEntry {
+                    fid,
+                    label_value,
+                    timestamp: 100,
+                })
+                .collect(),
+        );
+        assert!(service.is_spam(9));
+        assert!(!service.is_spam(3));
+        assert!(!service.is_spam(7));
+
+        let statuses: Vec<&str> = service
+            .check_many(&[9, 3, 7])
+            .iter()
+            .map(|result| result.status)
+            .collect();
+        assert_eq!(statuses, ["spam", "not_spam", "unknown"]);
+
+        let summary = service.summary();
+        assert_eq!((summary.total, summary.spam, summary.unknown), (3, 1, 1));
+    }
+}
@@ -0,0 +1,236 @@
+//! Base data directory and profiles
+//!
+//! Every on-disk store (wallet keys, custody keys, Ed25519 signer keys) lives
+//! under one base data directory. By default that is `~/.castorix`; `--path`
+//! or `CASTORIX_DATA_DIR` replaces it and `--profile <name>` namespaces it to
+//! `<base>/profiles/<name>` so separate identities never share key files.
+//!
+//! There is no fallback to the working directory: without a home directory
+//! one of `--path` or `CASTORIX_DATA_DIR` is required.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use anyhow::Result;
+
+use crate::consts::env_vars;
+
+/// Profile used when neither `--profile` nor `CASTORIX_PROFILE` is set
+pub const DEFAULT_PROFILE: &str = "default";
+/// Current on-disk layout version, recorded in `<data dir>/layout_version`
+pub const LAYOUT_VERSION: u32 = 2;
+
+const LAYOUT_VERSION_FILE: &str = "layout_version";
+
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Resolve the data directory for this process and upgrade its layout
+///
+/// Must be called before any store is opened; later calls return the directory
+/// chosen by the first one.
+///
+/// # Arguments
+/// * `base` - Custom base directory (`--path`), or `None` for `CASTORIX_DATA_DIR` / `~/.castorix`
+/// * `profile` - Profile name (`--profile`), or `None` for `CASTORIX_PROFILE` / default
+///
+/// # Returns
+/// * `Result<PathBuf>` - The data directory in use
+pub fn init(base: Option<&str>, profile: Option<&str>) -> Result<PathBuf> {
+    let dir = resolve(base, profile)?;
+    let dir = DATA_DIR.get_or_init(|| dir).clone();
+    upgrade_layout(&dir)?;
+    Ok(dir)
+}
+
+/// Get the data directory, resolving the default one if [`init`] was not called
+///
+/// # Panics
+/// If [`init`] was not called and the default directory cannot be resolved,
+/// i.e. there is no home directory and `CASTORIX_DATA_DIR` is unset. Keys are
+/// never written relative to the working directory instead.
+pub fn data_dir() -> PathBuf {
+    DATA_DIR
+        .get_or_init(|| resolve(None, None).unwrap_or_else(|e| panic!("{e:#}")))
+        .clone()
+}
+
+/// Directory holding encrypted wallet keys and the Ed25519 signer key file
+pub fn keys_dir() -> PathBuf {
+    data_dir().join("keys")
+}
+
+/// Directory holding encrypted custody keys
+pub fn custody_dir() -> PathBuf {
+    data_dir().join("custody")
+}
+
+/// Work out the data directory for a base path and profile
+fn resolve(base: Option<&str>, profile: Option<&str>) -> Result<PathBuf> {
+    let base = match base {
+        Some(path) => PathBuf::from(shellexpand::tilde(path).as_ref()),
+        None => default_base(std::env::var(env_vars::DATA_DIR).ok(), dirs::home_dir())?,
+    };
+
+    match profile_name(profile) {
+        name if name != DEFAULT_PROFILE => {
+            validate_profile_name(&name)?;
+            Ok(base.join("profiles").join(name))
+        }
+        _ => Ok(base),
+    }
+}
+
+/// Base directory when `--path` is not given: `CASTORIX_DATA_DIR`, else `~/.castorix`
+fn default_base(env: Option<String>, home: Option<PathBuf>) -> Result<PathBuf> {
+    if let Some(dir) = env.filter(|d| !d.trim().is_empty()) {
+        return Ok(PathBuf::from(shellexpand::tilde(&dir).as_ref()));
+    }
+    home.map(|home| home.join(".castorix")).with_context(|| {
+        format!(
+            "Could not find home directory; pass --path or set {}",
+            env_vars::DATA_DIR
+        )
+    })
+}
+
+/// Name of the profile in use: `profile` (`--profile`), else
+/// `CASTORIX_PROFILE`, else [`DEFAULT_PROFILE`]
+pub fn profile_name(profile: Option<&str>) -> String {
+    profile
+        .map(|p| p.to_string())
+        .or_else(|| std::env::var(env_vars::PROFILE).ok())
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Profile names become directory names, so keep them to a safe character set
+fn validate_profile_name(name: &str) -> Result<()> {
+    if name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        anyhow::bail!("Invalid profile name '{name}': use letters, digits, '-' or '_'")
+    }
+}
+
+/// Bring a data directory up to [`LAYOUT_VERSION`]
+///
+/// Version 1 directories may hold wallet key files directly in the base
+/// directory (older `key list --path` read them from there). They are moved
+/// into `keys/`; files that would overwrite an existing key are left in place.
+fn upgrade_layout(dir: &Path) -> Result<()> {
+    let marker = dir.join(LAYOUT_VERSION_FILE);
+    let version: u32 = std::fs::read_to_string(&marker)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(1);
+    if version >= LAYOUT_VERSION {
+        return Ok(());
+    }
+
+    let keys_dir = dir.join("keys");
+    std::fs::create_dir_all(&keys_dir)
+        .with_context(|| format!("Failed to create {}", keys_dir.display()))?;
+
+    for path in loose_wallet_files(dir)? {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let target = keys_dir.join(name);
+        if target.exists() {
+            eprintln!(
+                "⚠️  Not migrating {}: {} already exists",
+                path.display(),
+                target.display()
+            );
+            continue;
+        }
+        std::fs::rename(&path, &target)
+            .with_context(|| format!("Failed to move {} into keys/", path.display()))?;
+        eprintln!("📦 Migrated {} -> {}", path.display(), target.display());
+    }
+
+    std::fs::write(&marker, LAYOUT_VERSION.to_string())
+        .with_context(|| format!("Failed to write {}", marker.display()))?;
+    Ok(())
+}
+
+/// Wallet key files stored directly in a directory rather than under `keys/`
+fn loose_wallet_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let is_wallet = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .is_some_and(|json| {
+                json.get("encrypted_key").is_some() && json.get("address").is_some()
+            });
+        if is_wallet {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_are_namespaced() {
+        let base = resolve(Some("/tmp/castorix-test"), Some("work")).unwrap();
+        assert_eq!(base, PathBuf::from("/tmp/castorix-test/profiles/work"));
+
+        let default = resolve(Some("/tmp/castorix-test"), Some(DEFAULT_PROFILE)).unwrap();
+        assert_eq!(default, PathBuf::from("/tmp/castorix-test"));
+
+        assert!(resolve(None, Some("../escape")).is_err());
+    }
+
+    #[test]
+    fn test_default_base_never_falls_back_to_working_directory() {
+        let home = Some(PathBuf::from("/home/alice"));
+        assert_eq!(
+            default_base(None, home.clone()).unwrap(),
+            PathBuf::from("/home/alice/.castorix")
+        );
+        assert_eq!(
+            default_base(Some("/srv/castorix".to_string()), home).unwrap(),
+            PathBuf::from("/srv/castorix")
+        );
+        assert_eq!(
+            default_base(Some("/srv/castorix".to_string()), None).unwrap(),
+            PathBuf::from("/srv/castorix")
+        );
+
+        let err = default_base(None, None).unwrap_err().to_string();
+        assert!(err.contains("CASTORIX_DATA_DIR"));
+        assert!(default_base(Some(" ".to_string()), None).is_err());
+    }
+
+    #[test]
+    fn test_upgrade_moves_loose_wallet_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let wallet = r#"{"encrypted_key":"x","address":"0x1"}"#;
+        std::fs::write(dir.path().join("main.json"), wallet).unwrap();
+        std::fs::write(dir.path().join("notes.json"), "{}").unwrap();
+
+        upgrade_layout(dir.path()).unwrap();
+
+        assert!(dir.path().join("keys").join("main.json").exists());
+        assert!(!dir.path().join("main.json").exists());
+        assert!(dir.path().join("notes.json").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(LAYOUT_VERSION_FILE)).unwrap(),
+            LAYOUT_VERSION.to_string()
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Waiting for L2 finality before an on-chain operation is reported done
+//!
+//! On OP Mainnet and Base a receipt only means the sequencer included the
+//! transaction; the block can still be reorganised away until it is derived
+//! from L1 (`safe`) and that L1 data is final (`finalized`). By default a
+//! receipt is enough. With `--wait-for` (or `CASTORIX_WAIT_FOR`) transactions
+//! are watched until they have a number of confirmations or their block is
+//! covered by the `safe` or `finalized` tag:
+//!
+//! ```text
+//! castorix --wait-for 5 fid register --wallet main
+//! castorix --wait-for finalized storage rent 12345 --units 1
+//! ```
+//!
+//! While waiting the receipt is re-fetched: a transaction that moved to
+//! another block is reported, and one that left the chain is an error.
+//! `CASTORIX_WAIT_TIMEOUT` bounds the wait in seconds (30 minutes by default).
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+use ethers::providers::Http;
+use ethers::providers::Middleware;
+use ethers::providers::Provider;
+use ethers::types::BlockNumber;
+use ethers::types::TransactionReceipt;
+
+use crate::consts::env_vars;
+
+/// Longest wait for finality unless overridden
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Interval between checks; OP Stack chains make a block every two seconds
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+static SETTINGS: OnceLock<FinalitySettings> = OnceLock::new();
+
+/// When a mined transaction counts as done
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Finality {
+    /// As soon as there is a receipt
+    #[default]
+    Included,
+    /// Once the block has this many confirmations, itself included
+    Confirmations(u64),
+    /// Once the `safe` block tag reaches the block
+    Safe,
+    /// Once the `finalized` block tag reaches the block
+    Finalized,
+}
+
+impl FromStr for Finality {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "included" => Ok(Self::Included),
+            "safe" => Ok(Self::Safe),
+            "finalized" => Ok(Self::Finalized),
+            number => match number.parse::<u64>() {
+                Ok(0 | 1) => Ok(Self::Included),
+                Ok(confirmations) => Ok(Self::Confirmations(confirmations)),
+                Err(_) => anyhow::bail!(
+                    "Invalid finality '{value}': expected a number of confirmations, 'safe' or 'finalized'"
+                ),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Finality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Included => write!(f, "inclusion"),
+            Self::Confirmations(n) => write!(f, "{n} confirmations"),
+            Self::Safe => write!(f, "the safe block"),
+            Self::Finalized => write!(f, "the finalized block"),
+        }
+    }
+}
+
+/// What to wait for after a transaction is mined, and for how long
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FinalitySettings {
+    pub wait: Finality,
+    pub timeout: Duration,
+}
+
+impl Default for FinalitySettings {
+    fn default() -> Self {
+        Self {
+            wait: Finality::Included,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl FinalitySettings {
+    /// Settings from the `--wait-for` flag, else the environment
+    pub fn load(wait_for: Option<&str>) -> Result<Self> {
+        let wait = match wait_for
+            .map(str::to_string)
+            .or_else(|| std::env::var(env_vars::WAIT_FOR).ok())
+        {
+            Some(value) => value.parse()?,
+            None => Finality::Included,
+        };
+        let timeout = match std::env::var(env_vars::WAIT_TIMEOUT) {
+            Ok(secs) => Duration::from_secs(secs.trim().parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "{} must be a number of seconds: {secs}",
+                    env_vars::WAIT_TIMEOUT
+                )
+            })?),
+            Err(_) => DEFAULT_TIMEOUT,
+        };
+        Ok(Self { wait, timeout })
+    }
+}
+
+/// Make `settings` apply to every transaction this process sends
+///
+/// Only the first call has an effect.
+pub fn install(settings: FinalitySettings) {
+    let _ = SETTINGS.set(settings);
+}
+
+/// The settings in force: the installed ones, or the environment alone
+pub fn settings() -> FinalitySettings {
+    *SETTINGS.get_or_init(|| FinalitySettings::load(None).unwrap_or_default())
+}
+
+/// Wait until a mined transaction is final per the settings in force
+///
+/// # Returns
+/// * `Result<TransactionReceipt>` - The receipt as of the final block, which
+///   differs from `receipt` if a reorg moved the transaction; an error if it
+///   left the chain, reverted after a reorg or the wait timed out
+pub async fn confirm(
+    provider: &Provider<Http>,
+    receipt: TransactionReceipt,
+) -> Result<TransactionReceipt> {
+    confirm_with(provider, receipt, settings()).await
+}
+
+/// [`confirm`] with explicit settings
+pub async fn confirm_with(
+    provider: &Provider<Http>,
+    mut receipt: TransactionReceipt,
+    settings: FinalitySettings,
+) -> Result<TransactionReceipt> {
+    if settings.wait == Finality::Included {
+        return Ok(receipt);
+    }
+    let hash = receipt.transaction_hash;
+    crate::println!("   ⏳ Waiting for {} of {hash:?}...", settings.wait);
+    let started = Instant::now();
+
+    loop {
+        let current = provider
+            .get_transaction_receipt(hash)
+            .await?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Transaction {hash:?} was removed from block {} by a reorg; \
+                     it may be included again, so check it before retrying",
+                    block_label(&receipt)
+                )
+            })?;
+        if current.block_hash != receipt.block_hash {
+            crate::println!(
+                "   ⚠️  Reorg: transaction {hash:?} moved from block {} to block {}",
+                block_label(&receipt),
+                block_label(&current)
+            );
+            if current.status == Some(0.into()) {
+                anyhow::bail!("Transaction {hash:?} reverted after a reorg");
+            }
+        }
+        receipt = current;
+
+        let Some(block) = receipt.block_number.map(|n| n.as_u64()) else {
+            anyhow::bail!("Receipt of {hash:?} has no block number");
+        };
+        let reached = match settings.wait {
+            Finality::Included => true,
+            Finality::Confirmations(n) => {
+                let head = provider.get_block_number().await?.as_u64();
+                head.saturating_sub(block) + 1 >= n
+            }
+            Finality::Safe => tag_reaches(provider, BlockNumber::Safe, block).await?,
+            Finality::Finalized => tag_reaches(provider, BlockNumber::Finalized, block).await?,
+        };
+        if reached {
+            crate::println!("   ✅ Reached {} (block {block})", settings.wait);
+            return Ok(receipt);
+        }
+
+        if started.elapsed() >= settings.timeout {
+            anyhow::bail!(
+                "Timed out after {}s waiting for {}; {hash:?} is in block {block} but not final yet",
+                settings.timeout.as_secs(),
+                settings.wait
+            );
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+async fn tag_reaches(provider: &Provider<Http>, tag: BlockNumber, block: u64) -> Result<bool> {
+    let tagged = provider
+        .get_block(tag)
+        .await?
+        .and_then(|b| b.number)
+        .map(|n| n.as_u64());
+    Ok(tagged.is_some_and(|tagged| tagged >= block))
+}
+
+fn block_label(receipt: &TransactionReceipt) -> String {
+    match (receipt.block_number, receipt.block_hash) {
+        (Some(number), Some(hash)) => format!("{number} ({hash:?})"),
+        (Some(number), None) => number.to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_finality() {
+        assert_eq!("".parse::<Finality>().unwrap(), Finality::Included);
+        assert_eq!("1".parse::<Finality>().unwrap(), Finality::Included);
+        assert_eq!(
+            "12".parse::<Finality>().unwrap(),
+            Finality::Confirmations(12)
+        );
+        assert_eq!("Safe".parse::<Finality>().unwrap(), Finality::Safe);
+        assert_eq!(
+            "finalized".parse::<Finality>().unwrap(),
+            Finality::Finalized
+        );
+        assert!("soon".parse::<Finality>().is_err());
+        assert_eq!(Finality::Confirmations(3).to_string(), "3 confirmations");
+    }
+}
@@ -0,0 +1,351 @@
+//! Shared HTTP client for all outbound traffic
+//!
+//! Hub requests, RPC calls, embed and image fetches and release downloads all
+//! use clients built here, so proxy, TLS and user-agent settings apply the
+//! same way everywhere. Settings live under `"http"` in
+//! `<data dir>/config.json`:
+//!
+//! ```json
+//! {
+//!   "http": {
+//!     "proxy": "socks5h://127.0.0.1:9050",
+//!     "no_proxy": "localhost,127.0.0.1",
+//!     "ca_cert": "/etc/ssl/corp-proxy.pem"
+//!   }
+//! }
+//! ```
+//!
+//! `CASTORIX_PROXY`, `CASTORIX_HTTP_PROXY`, `CASTORIX_HTTPS_PROXY`,
+//! `CASTORIX_NO_PROXY`, `CASTORIX_CA_CERT`, `CASTORIX_USER_AGENT`,
+//! `CASTORIX_REQUEST_ID` and `CASTORIX_REQUEST_ID_HEADER` override the file.
+//! Settings left unset by both fall back to the conventional `ALL_PROXY`,
+//! `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` variables.
+//! SOCKS proxies need the `socks` feature, on by default.
+//!
+//! With `"request_id": true` every hub request carries a fresh id in an
+//! `x-request-id` header, and failed requests are logged with it, so errors can
+//! be matched up with a hosted hub provider's own logs.
+//!
+//! The CLI installs the settings at start-up with [`install`]; until then the
+//! environment alone is used.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use anyhow::Result;
+use ethers::providers::Http;
+use ethers::providers::Provider;
+use reqwest::Certificate;
+use reqwest::Client;
+use reqwest::ClientBuilder;
+use reqwest::NoProxy;
+use reqwest::Proxy;
+use reqwest::RequestBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::consts::env_vars;
+
+/// User-Agent sent unless overridden
+pub const USER_AGENT: &str = concat!("castorix/", env!("CARGO_PKG_VERSION"));
+
+/// Header request ids are sent in unless overridden
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+static CLIENT: OnceLock<Client> = OnceLock::new();
+static SETTINGS: OnceLock<HttpSettings> = OnceLock::new();
+
+/// Proxy, TLS and user-agent settings; the default connects directly
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HttpSettings {
+    /// Proxy for all traffic, e.g. `http://proxy:3128` or `socks5h://127.0.0.1:9050`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Proxy for plain HTTP requests, taking precedence over `proxy`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_proxy: Option<String>,
+    /// Proxy for HTTPS requests, taking precedence over `proxy`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_proxy: Option<String>,
+    /// Comma separated hosts, domains and CIDR ranges reached directly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+    /// PEM file of an extra root certificate to trust, e.g. of an intercepting proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<PathBuf>,
+    /// User-Agent header, [`USER_AGENT`] if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Send a fresh request id with every hub request
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub request_id: bool,
+    /// Header carrying the request id, [`REQUEST_ID_HEADER`] if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id_header: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    http: Option<HttpSettings>,
+}
+
+impl HttpSettings {
+    /// Load settings from `config.json` in the data directory, then the environment
+    ///
+    /// Values are checked when a client is built, see [`install`].
+    pub fn load() -> Result<Self> {
+        let path = crate::core::utils::data_dir::data_dir().join("config.json");
+        let from_file = match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                let config: ConfigFile = serde_json::from_str(&content)
+                    .with_context(|| format!("Invalid config file {}", path.display()))?;
+                config.http.unwrap_or_default()
+            }
+            Err(_) => Self::default(),
+        };
+        Ok(from_file.with_env(|name| std::env::var(name).ok()))
+    }
+
+    /// Apply environment overrides and conventional proxy variables
+    ///
+    /// # Arguments
+    /// * `var` - Looks up an environment variable
+    pub fn with_env(self, var: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| var(name))
+                .filter(|value| !value.is_empty())
+        };
+        let pick = |own: &str, file: Option<String>, conventional: &[&str]| {
+            var(&[own]).or(file).or_else(|| var(conventional))
+        };
+        Self {
+            proxy: pick(env_vars::PROXY, self.proxy, &["ALL_PROXY", "all_proxy"]),
+            http_proxy: pick(
+                env_vars::HTTP_PROXY,
+                self.http_proxy,
+                &["HTTP_PROXY", "http_proxy"],
+            ),
+            https_proxy: pick(
+                env_vars::HTTPS_PROXY,
+                self.https_proxy,
+                &["HTTPS_PROXY", "https_proxy"],
+            ),
+            no_proxy: pick(env_vars::NO_PROXY, self.no_proxy, &["NO_PROXY", "no_proxy"]),
+            ca_cert: var(&[env_vars::CA_CERT])
+                .map(PathBuf::from)
+                .or(self.ca_cert),
+            user_agent: var(&[env_vars::USER_AGENT]).or(self.user_agent),
+            request_id: var(&[env_vars::REQUEST_ID])
+                .map_or(self.request_id, |value| is_enabled(&value)),
+            request_id_header: var(&[env_vars::REQUEST_ID_HEADER]).or(self.request_id_header),
+        }
+    }
+
+    /// Client builder with these settings applied
+    pub fn builder(&self) -> Result<ClientBuilder> {
+        let mut builder = Client::builder()
+            .user_agent(self.user_agent.as_deref().unwrap_or(USER_AGENT))
+            // Proxies come only from these settings, never picked up behind their back
+            .no_proxy();
+
+        let no_proxy = self.no_proxy.as_deref().and_then(NoProxy::from_string);
+        // Scheme-specific proxies first: reqwest uses the first one that matches
+        let proxies = [
+            (&self.http_proxy, "http"),
+            (&self.https_proxy, "https"),
+            (&self.proxy, "all"),
+        ];
+        for (url, scheme) in proxies {
+            let Some(url) = url else {
+                continue;
+            };
+            check_proxy_scheme(url)?;
+            let proxy = match scheme {
+                "http" => Proxy::http(url),
+                "https" => Proxy::https(url),
+                _ => Proxy::all(url),
+            }
+            .with_context(|| format!("Invalid proxy URL {url}"))?;
+            builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+        }
+
+        if let Some(path) = &self.ca_cert {
+            let pem = std::fs::read(path)
+                .with_context(|| format!("Failed to read CA certificate {}", path.display()))?;
+            let certificate = Certificate::from_pem(&pem)
+                .with_context(|| format!("Invalid CA certificate {}", path.display()))?;
+            builder = builder.add_root_certificate(certificate);
+        }
+        Ok(builder)
+    }
+
+    /// Whether any request goes through a proxy
+    pub fn uses_proxy(&self) -> bool {
+        self.proxy.is_some() || self.http_proxy.is_some() || self.https_proxy.is_some()
+    }
+
+    /// Attach a fresh request id to `request` if request ids are enabled
+    ///
+    /// # Returns
+    /// * The request, and the id it carries if any
+    pub fn tag(&self, request: RequestBuilder) -> (RequestBuilder, Option<String>) {
+        if !self.request_id {
+            return (request, None);
+        }
+        let id = new_request_id();
+        let header = self
+            .request_id_header
+            .as_deref()
+            .unwrap_or(REQUEST_ID_HEADER);
+        (request.header(header, &id), Some(id))
+    }
+}
+
+fn is_enabled(value: &str) -> bool {
+    matches!(
+        value.to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
+/// A random request id: 16 bytes as lowercase hex
+pub fn new_request_id() -> String {
+    hex::encode(rand::random::<[u8; 16]>())
+}
+
+#[cfg(not(feature = "socks"))]
+fn check_proxy_scheme(url: &str) -> Result<()> {
+    if url.to_ascii_lowercase().starts_with("socks") {
+        anyhow::bail!("SOCKS proxy {url} needs castorix built with the `socks` feature");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "socks")]
+fn check_proxy_scheme(_url: &str) -> Result<()> {
+    Ok(())
+}
+
+/// Make `settings` the ones every client of this process is built with
+///
+/// Only the first call has an effect: clients already handed out keep their
+/// settings, so call this before making any request.
+pub fn install(settings: HttpSettings) -> Result<()> {
+    let client = settings.builder()?.build()?;
+    let _ = SETTINGS.set(settings);
+    let _ = CLIENT.set(client);
+    Ok(())
+}
+
+/// The settings in force: the installed ones, or the environment alone
+pub fn settings() -> &'static HttpSettings {
+    SETTINGS.get_or_init(|| HttpSettings::default().with_env(|name| std::env::var(name).ok()))
+}
+
+/// Attach a request id to `request` per the settings in force, see [`HttpSettings::tag`]
+pub fn tag_request(request: RequestBuilder) -> (RequestBuilder, Option<String>) {
+    settings().tag(request)
+}
+
+/// ` (request id …)` for error messages, empty without a request id
+pub fn request_note(request_id: &Option<String>) -> String {
+    request_id
+        .as_ref()
+        .map(|id| format!(" (request id {id})"))
+        .unwrap_or_default()
+}
+
+/// Builder for callers that need their own timeouts or pool limits
+pub fn builder() -> Result<ClientBuilder> {
+    settings().builder()
+}
+
+/// The shared client; clones share one connection pool
+pub fn client() -> Client {
+    CLIENT
+        .get_or_init(|| match builder().and_then(|b| Ok(b.build()?)) {
+            Ok(client) => client,
+            Err(e) => {
+                crate::eprintln!("⚠️  Ignoring HTTP settings: {e:#}");
+                Client::new()
+            }
+        })
+        .clone()
+}
+
+/// JSON-RPC provider over the shared client
+pub fn provider(rpc_url: &str) -> Result<Provider<Http>> {
+    let url = reqwest::Url::parse(rpc_url).with_context(|| format!("Invalid RPC URL {rpc_url}"))?;
+    Ok(Provider::new(Http::new_with_client(url, client())))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_env_precedence() {
+        let env: HashMap<&str, &str> = [
+            ("CASTORIX_PROXY", "http://own:1"),
+            ("ALL_PROXY", "http://conventional:2"),
+            ("HTTPS_PROXY", "http://conventional:3"),
+            ("NO_PROXY", ""),
+        ]
+        .into();
+        let file = HttpSettings {
+            proxy: Some("http://file:4".to_string()),
+            https_proxy: Some("http://file:5".to_string()),
+            ..HttpSettings::default()
+        };
+        let settings = file.with_env(|name| env.get(name).map(|v| v.to_string()));
+        assert_eq!(settings.proxy.as_deref(), Some("http://own:1"));
+        assert_eq!(settings.https_proxy.as_deref(), Some("http://file:5"));
+        assert_eq!(settings.http_proxy, None);
+        assert_eq!(settings.no_proxy, None);
+        assert!(settings.uses_proxy());
+    }
+
+    #[test]
+    fn test_builder_validates_settings() {
+        assert!(HttpSettings::default().builder().is_ok());
+        let bad_proxy = HttpSettings {
+            proxy: Some("not a url".to_string()),
+            ..HttpSettings::default()
+        };
+        assert!(bad_proxy.builder().is_err());
+        let missing_cert = HttpSettings {
+            ca_cert: Some(PathBuf::from("/nonexistent/ca.pem")),
+            ..HttpSettings::default()
+        };
+        assert!(missing_cert.builder().is_err());
+    }
+
+    #[test]
+    fn test_request_id() {
+        let env: HashMap<&str, &str> = [
+            ("CASTORIX_REQUEST_ID", "true"),
+            ("CASTORIX_REQUEST_ID_HEADER", "x-trace-id"),
+        ]
+        .into();
+        let settings =
+            HttpSettings::default().with_env(|name| env.get(name).map(|v| v.to_string()));
+        assert!(settings.request_id);
+
+        let (request, id) = settings.tag(Client::new().get("http://hub.invalid"));
+        let id = id.unwrap();
+        assert_eq!(id.len(), 32);
+        let request = request.build().unwrap();
+        assert_eq!(request.headers()["x-trace-id"], id.as_str());
+        assert_ne!(new_request_id(), id);
+
+        let (_, id) = HttpSettings::default().tag(Client::new().get("http://hub.invalid"));
+        assert_eq!(id, None);
+    }
+}
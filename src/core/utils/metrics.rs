@@ -0,0 +1,377 @@
+//! In-process timing of the slow parts of a command
+//!
+//! With `--timing` every RPC call, hub request, signature (including the
+//! password-based key derivation that unlocks keys) and key or cache file
+//! access is timed, and a table of where the time went is printed on stderr
+//! when the command finishes:
+//!
+//! ```text
+//! castorix --timing fid register --wallet main
+//! ```
+//!
+//! RPC calls are timed through the `rpc` tracing span ethers opens for each
+//! JSON-RPC request, so every provider is covered without wrapping it; the
+//! other phases time themselves with [`start`]. When timing is off nothing is
+//! recorded.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::level_filters::LevelFilter;
+use tracing::span;
+use tracing::subscriber::Interest;
+use tracing::Event;
+use tracing::Metadata;
+use tracing::Subscriber;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STARTED: OnceLock<Instant> = OnceLock::new();
+static RECORDS: Mutex<Vec<Record>> = Mutex::new(Vec::new());
+
+/// Kind of work a timing belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    /// JSON-RPC calls to an Ethereum node
+    Rpc,
+    /// Requests to a Farcaster hub
+    Hub,
+    /// Signing, and deriving keys from passwords
+    Signing,
+    /// Reading and writing key, cache and log files
+    DiskIo,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Rpc => "RPC",
+            Self::Hub => "Hub",
+            Self::Signing => "Signing",
+            Self::DiskIo => "Disk IO",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Record {
+    phase: Phase,
+    operation: String,
+    elapsed: Duration,
+}
+
+/// Totals for one operation, or for a whole phase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stat {
+    pub phase: Phase,
+    /// The operation, such as `eth_call` or `/v1/castsByFid`; empty for a
+    /// phase total
+    pub operation: String,
+    pub calls: usize,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl Stat {
+    fn add(&mut self, elapsed: Duration) {
+        self.calls += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+
+    /// Mean duration of a call
+    pub fn average(&self) -> Duration {
+        self.total / self.calls.max(1) as u32
+    }
+}
+
+/// Start recording timings for the rest of the process
+///
+/// # Returns
+/// * `TimingReport` - Prints the summary table when dropped, so keep it alive
+///   until the command is done
+pub fn enable() -> TimingReport {
+    STARTED.get_or_init(Instant::now);
+    ENABLED.store(true, Ordering::Relaxed);
+    // Fails only when a subscriber is already installed; RPC calls then go
+    // untimed rather than replacing it
+    let _ = tracing::subscriber::set_global_default(RpcTimer::default());
+    TimingReport
+}
+
+/// Whether timings are being recorded
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Record one timed operation
+pub fn record(phase: Phase, operation: impl Into<String>, elapsed: Duration) {
+    if !is_enabled() {
+        return;
+    }
+    if let Ok(mut records) = RECORDS.lock() {
+        records.push(Record {
+            phase,
+            operation: operation.into(),
+            elapsed,
+        });
+    }
+}
+
+/// Time an operation until the returned timer is dropped
+///
+/// ```ignore
+/// let _timer = metrics::start(Phase::DiskIo, "key file");
+/// ```
+pub fn start(phase: Phase, operation: impl Into<String>) -> Timer {
+    Timer(is_enabled().then(|| (phase, operation.into(), Instant::now())))
+}
+
+/// Time a synchronous operation
+pub fn time<T>(phase: Phase, operation: &str, f: impl FnOnce() -> T) -> T {
+    let _timer = start(phase, operation);
+    f()
+}
+
+/// Records its operation when dropped
+#[must_use = "the operation is timed until the timer is dropped"]
+pub struct Timer(Option<(Phase, String, Instant)>);
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if let Some((phase, operation, started)) = self.0.take() {
+            record(phase, operation, started.elapsed());
+        }
+    }
+}
+
+/// Totals per operation, in phase order and slowest first within a phase,
+/// followed by the totals per phase
+pub fn summary() -> (Vec<Stat>, Vec<Stat>) {
+    let records = RECORDS.lock().map(|r| r.clone()).unwrap_or_default();
+    let mut operations: HashMap<(Phase, String), Stat> = HashMap::new();
+    let mut phases: HashMap<Phase, Stat> = HashMap::new();
+    for record in records {
+        let empty = |phase, operation| Stat {
+            phase,
+            operation,
+            calls: 0,
+            total: Duration::ZERO,
+            max: Duration::ZERO,
+        };
+        operations
+            .entry((record.phase, record.operation.clone()))
+            .or_insert_with(|| empty(record.phase, record.operation))
+            .add(record.elapsed);
+        phases
+            .entry(record.phase)
+            .or_insert_with(|| empty(record.phase, String::new()))
+            .add(record.elapsed);
+    }
+
+    let mut operations: Vec<Stat> = operations.into_values().collect();
+    operations
+        .sort_by(|a, b| (a.phase, b.total, &a.operation).cmp(&(b.phase, a.total, &b.operation)));
+    let mut phases: Vec<Stat> = phases.into_values().collect();
+    phases.sort_by_key(|stat| stat.phase);
+    (operations, phases)
+}
+
+/// The summary as a table
+pub fn render() -> String {
+    let (operations, phases) = summary();
+    let mut table = String::from("⏱️  Timing summary\n");
+    if operations.is_empty() {
+        table.push_str("   No RPC, hub, signing or disk operations were timed\n");
+    } else {
+        let width = operations
+            .iter()
+            .map(|stat| stat.operation.len())
+            .max()
+            .unwrap_or(0)
+            .max("OPERATION".len());
+        table.push_str(&format!(
+            "   {:<8} {:<width$} {:>6} {:>10} {:>10} {:>10}\n",
+            "PHASE", "OPERATION", "CALLS", "TOTAL", "AVG", "MAX"
+        ));
+        for stat in operations.iter().chain(&phases) {
+            let operation = if stat.operation.is_empty() {
+                "(total)"
+            } else {
+                &stat.operation
+            };
+            table.push_str(&format!(
+                "   {:<8} {:<width$} {:>6} {:>10} {:>10} {:>10}\n",
+                stat.phase.to_string(),
+                operation,
+                stat.calls,
+                format!("{:.1?}", stat.total),
+                format!("{:.1?}", stat.average()),
+                format!("{:.1?}", stat.max),
+            ));
+        }
+    }
+    if let Some(started) = STARTED.get() {
+        table.push_str(&format!("   Wall time: {:.1?}\n", started.elapsed()));
+    }
+    table
+}
+
+/// Prints the timing summary on stderr when dropped
+pub struct TimingReport;
+
+impl Drop for TimingReport {
+    fn drop(&mut self) {
+        crate::eprint!("\n{}", render());
+    }
+}
+
+/// Tracing subscriber that only listens to ethers' per-request `rpc` spans
+///
+/// A span lives from the moment a request is built until its response is
+/// decoded, so its lifetime is the call's duration.
+#[derive(Default)]
+struct RpcTimer {
+    next_id: AtomicU64,
+    spans: Mutex<HashMap<u64, OpenSpan>>,
+}
+
+struct OpenSpan {
+    method: String,
+    started: Instant,
+    refs: usize,
+}
+
+fn is_rpc_span(metadata: &Metadata<'_>) -> bool {
+    metadata.is_span()
+        && metadata.name() == "rpc"
+        && metadata.target().starts_with("ethers_providers")
+}
+
+#[derive(Default)]
+struct MethodVisitor(String);
+
+impl Visit for MethodVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "method" {
+            self.0 = value.to_string();
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "method" {
+            self.0 = format!("{value:?}");
+        }
+    }
+}
+
+impl Subscriber for RpcTimer {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if is_rpc_span(metadata) {
+            Interest::always()
+        } else {
+            Interest::never()
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        is_rpc_span(metadata)
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::TRACE)
+    }
+
+    fn new_span(&self, attributes: &span::Attributes<'_>) -> span::Id {
+        let mut method = MethodVisitor::default();
+        attributes.record(&mut method);
+        // Span ids must not be zero
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Ok(mut spans) = self.spans.lock() {
+            spans.insert(
+                id,
+                OpenSpan {
+                    method: method.0,
+                    started: Instant::now(),
+                    refs: 1,
+                },
+            );
+        }
+        span::Id::from_u64(id)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+
+    fn clone_span(&self, id: &span::Id) -> span::Id {
+        if let Ok(mut spans) = self.spans.lock() {
+            if let Some(span) = spans.get_mut(&id.into_u64()) {
+                span.refs += 1;
+            }
+        }
+        id.clone()
+    }
+
+    fn try_close(&self, id: span::Id) -> bool {
+        let Ok(mut spans) = self.spans.lock() else {
+            return false;
+        };
+        let Some(span) = spans.get_mut(&id.into_u64()) else {
+            return false;
+        };
+        span.refs -= 1;
+        if span.refs > 0 {
+            return false;
+        }
+        if let Some(span) = spans.remove(&id.into_u64()) {
+            drop(spans);
+            record(Phase::Rpc, span.method, span.started.elapsed());
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        ENABLED.store(true, Ordering::Relaxed);
+        record(Phase::Hub, "/v1/test_summary", Duration::from_millis(30));
+        record(Phase::Hub, "/v1/test_summary", Duration::from_millis(10));
+        {
+            let _timer = start(Phase::DiskIo, "test_summary");
+        }
+
+        let (operations, phases) = summary();
+        let hub = operations
+            .iter()
+            .find(|stat| stat.operation == "/v1/test_summary")
+            .unwrap();
+        assert_eq!(hub.calls, 2);
+        assert_eq!(hub.total, Duration::from_millis(40));
+        assert_eq!(hub.average(), Duration::from_millis(20));
+        assert_eq!(hub.max, Duration::from_millis(30));
+        assert!(operations
+            .iter()
+            .any(|stat| stat.phase == Phase::DiskIo && stat.operation == "test_summary"));
+        assert!(phases.windows(2).all(|pair| pair[0].phase < pair[1].phase));
+        assert!(render().contains("/v1/test_summary"));
+    }
+}
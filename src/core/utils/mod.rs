@@ -3,3 +3,12 @@
 //! General purpose helper functions
 
 // Utils will be moved here as needed
+
+pub mod data_dir;
+pub mod finality;
+pub mod http;
+pub mod metrics;
+pub mod private_file;
+pub mod prompt;
+pub mod ttl_cache;
+pub mod warpcast;
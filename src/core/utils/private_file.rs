@@ -0,0 +1,70 @@
+//! Crash-safe writes of files only their owner may read
+//!
+//! Key files hold every FID's encrypted key, so a write that dies halfway
+//! (a crash, a full disk) must not leave a truncated file behind. The new
+//! content goes to a temporary file in the same directory, created with mode
+//! 0600, which is synced and then renamed over the original: readers see
+//! either the old file or the new one, never a mix.
+
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Context;
+use anyhow::Result;
+
+/// Replace `path` with `contents`, atomically and readable by the owner only
+pub fn write(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut staged = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("Cannot create a temporary file in {}", dir.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        staged
+            .as_file()
+            .set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+
+    staged
+        .write_all(contents)
+        .and_then(|()| staged.as_file().sync_all())
+        .with_context(|| format!("Cannot write {}", path.display()))?;
+    staged
+        .persist(path)
+        .map_err(|e| e.error)
+        .with_context(|| format!("Cannot replace {}", path.display()))?;
+
+    // Make the rename itself durable
+    #[cfg(unix)]
+    if let Ok(dir) = std::fs::File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_replaces_file_privately() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.json");
+        std::fs::write(&path, "old").unwrap();
+
+        write(&path, b"new").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
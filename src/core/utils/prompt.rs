@@ -0,0 +1,129 @@
+//! Interactive input, and refusing it in non-interactive mode
+//!
+//! With `--non-interactive` (or `CASTORIX_NON_INTERACTIVE=1`) every place
+//! that would wait for a password, a confirmation or typed text fails at
+//! once with an [`InputRequired`] error instead, so castorix never hangs in
+//! an automation pipeline. The CLI prints that error as JSON on stderr and
+//! exits with [`EXIT_INPUT_REQUIRED`].
+
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use serde::Serialize;
+
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Exit code of a command stopped by [`InputRequired`]
+pub const EXIT_INPUT_REQUIRED: i32 = 3;
+
+/// Turn non-interactive mode on or off for this process
+pub fn set_non_interactive(enabled: bool) {
+    NON_INTERACTIVE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether prompting is refused
+pub fn is_non_interactive() -> bool {
+    NON_INTERACTIVE.load(Ordering::Relaxed)
+}
+
+/// What a command wanted to ask for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputKind {
+    Password,
+    Confirmation,
+    Text,
+}
+
+impl fmt::Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            InputKind::Password => "password",
+            InputKind::Confirmation => "confirmation",
+            InputKind::Text => "text",
+        })
+    }
+}
+
+/// Input a command needed but was not allowed to prompt for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InputRequired {
+    pub input: InputKind,
+    /// The prompt that would have been shown
+    pub prompt: String,
+    /// How to provide the input without a prompt, where possible
+    pub hint: Option<String>,
+}
+
+impl fmt::Display for InputRequired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} required but prompting is disabled (--non-interactive): {}",
+            self.input,
+            self.prompt.trim().trim_end_matches(':')
+        )?;
+        if let Some(hint) = &self.hint {
+            write!(f, "; {hint}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InputRequired {}
+
+/// Fail with [`InputRequired`] if prompting is disabled
+///
+/// Call right before reading from the terminal.
+///
+/// # Arguments
+/// * `input` - What is about to be asked for
+/// * `prompt` - The prompt shown to the user
+pub fn ensure_interactive(input: InputKind, prompt: &str) -> anyhow::Result<()> {
+    if !is_non_interactive() {
+        return Ok(());
+    }
+    let hint = match input {
+        InputKind::Confirmation => Some("re-run with --yes where the command supports it"),
+        InputKind::Password | InputKind::Text => None,
+    };
+    Err(InputRequired {
+        input,
+        prompt: prompt.to_string(),
+        hint: hint.map(str::to_string),
+    }
+    .into())
+}
+
+/// Show a prompt on stdout, about to read the answer from stdin
+///
+/// Fails with [`InputRequired`] instead if prompting is disabled.
+pub fn ask(input: InputKind, prompt: &str) -> anyhow::Result<()> {
+    ensure_interactive(input, prompt)?;
+    print!("{prompt}");
+    std::io::Write::flush(&mut std::io::stdout())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ensure_interactive() {
+        assert!(ensure_interactive(InputKind::Password, "Password: ").is_ok());
+
+        set_non_interactive(true);
+        let error = ensure_interactive(InputKind::Confirmation, "Proceed?").unwrap_err();
+        set_non_interactive(false);
+        let required = error.downcast_ref::<InputRequired>().unwrap();
+        assert_eq!(required.input, InputKind::Confirmation);
+        assert!(required.hint.is_some());
+        assert!(error.to_string().starts_with("confirmation required"));
+        assert_eq!(
+            serde_json::to_value(required).unwrap()["input"],
+            "confirmation"
+        );
+    }
+}
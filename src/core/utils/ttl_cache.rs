@@ -0,0 +1,162 @@
+//! Expiring key-value cache, optionally persisted to a JSON file
+//!
+//! Used for lookups that are slow but change rarely (link previews, name
+//! resolution, verifications, cast engagement): each entry remembers when it
+//! was fetched and is ignored once older than the cache's time to live. An
+//! unreadable or outdated cache file simply starts an empty cache.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry<V> {
+    /// Unix time the value was fetched
+    fetched_at: i64,
+    value: V,
+}
+
+/// Cache whose entries expire a fixed time after they were inserted
+#[derive(Debug)]
+pub struct TtlCache<K, V> {
+    ttl_secs: i64,
+    entries: Mutex<HashMap<K, Entry<V>>>,
+    path: Option<PathBuf>,
+}
+
+impl<K, V> TtlCache<K, V>
+where
+    K: Eq + Hash + Clone + Serialize + DeserializeOwned,
+    V: Clone + Serialize + DeserializeOwned,
+{
+    /// Create a cache that lives in memory only
+    pub fn in_memory(ttl: Duration) -> Self {
+        Self {
+            ttl_secs: ttl.as_secs() as i64,
+            entries: Mutex::new(HashMap::new()),
+            path: None,
+        }
+    }
+
+    /// Create a cache loaded from and saved to a JSON file
+    pub fn with_file(ttl: Duration, path: PathBuf) -> Self {
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            ttl_secs: ttl.as_secs() as i64,
+            entries: Mutex::new(entries),
+            path: Some(path),
+        }
+    }
+
+    /// The value of a key, if it was inserted less than the time to live ago
+    pub fn get(&self, key: &K) -> Option<V> {
+        let now = chrono::Utc::now().timestamp();
+        self.entries
+            .lock()
+            .unwrap()
+            .get(key)
+            .filter(|entry| self.is_fresh(entry, now))
+            .map(|entry| entry.value.clone())
+    }
+
+    /// Store a freshly fetched value
+    pub fn insert(&self, key: K, value: V) {
+        self.insert_at(key, value, chrono::Utc::now().timestamp());
+    }
+
+    /// Store a value fetched at a given Unix time
+    pub fn insert_at(&self, key: K, value: V, fetched_at: i64) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key, Entry { fetched_at, value });
+    }
+
+    /// Keys whose values have not expired, in no particular order
+    pub fn fresh_keys(&self) -> Vec<K> {
+        let now = chrono::Utc::now().timestamp();
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, entry)| self.is_fresh(entry, now))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Write the cache back to its file, dropping expired entries
+    ///
+    /// Does nothing for an in-memory cache.
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let now = chrono::Utc::now().timestamp();
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, entry| self.is_fresh(entry, now));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(&*entries)?)
+            .with_context(|| format!("Failed to write cache {}", path.display()))
+    }
+
+    fn is_fresh(&self, entry: &Entry<V>, now: i64) -> bool {
+        now - entry.fetched_at < self.ttl_secs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_entries_expire() {
+        let cache: TtlCache<String, u64> = TtlCache::in_memory(TTL);
+        let now = chrono::Utc::now().timestamp();
+        cache.insert("fresh".to_string(), 1);
+        cache.insert_at("stale".to_string(), 2, now - 61);
+
+        assert_eq!(cache.get(&"fresh".to_string()), Some(1));
+        assert_eq!(cache.get(&"stale".to_string()), None);
+        assert_eq!(cache.fresh_keys(), vec!["fresh".to_string()]);
+    }
+
+    #[test]
+    fn test_save_round_trips_and_drops_expired() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("cache.json");
+        let now = chrono::Utc::now().timestamp();
+
+        let cache: TtlCache<u64, Option<String>> = TtlCache::with_file(TTL, path.clone());
+        cache.insert(1, Some("one".to_string()));
+        cache.insert(2, None);
+        cache.insert_at(3, Some("three".to_string()), now - 120);
+        cache.save().unwrap();
+
+        let reloaded: TtlCache<u64, Option<String>> = TtlCache::with_file(TTL, path.clone());
+        assert_eq!(reloaded.get(&1), Some(Some("one".to_string())));
+        assert_eq!(reloaded.get(&2), Some(None));
+        assert_eq!(reloaded.get(&3), None);
+        let mut keys = reloaded.fresh_keys();
+        keys.sort_unstable();
+        assert_eq!(keys, vec![1, 2]);
+
+        std::fs::write(&path, "not json").unwrap();
+        let corrupt: TtlCache<u64, Option<String>> = TtlCache::with_file(TTL, path);
+        assert!(corrupt.fresh_keys().is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+//! Warpcast deep links and terminal QR codes
+
+use anyhow::Result;
+use qrcode::render::unicode::Dense1x2;
+use qrcode::QrCode;
+
+/// Base URL of the Warpcast web client
+pub const WARPCAST_BASE_URL: &str = "https://warpcast.com";
+
+/// Link to a profile, by username when it is known
+pub fn profile_url(fid: u64, username: Option<&str>) -> String {
+    match username.filter(|u| !u.is_empty()) {
+        Some(username) => format!("{WARPCAST_BASE_URL}/{username}"),
+        None => format!("{WARPCAST_BASE_URL}/~/profiles/{fid}"),
+    }
+}
+
+/// Link to a cast
+///
+/// With the author's username Warpcast's short form (`/<user>/0x<8 hex>`) is
+/// used; otherwise the conversation link, which takes the full hash.
+pub fn cast_url(hash: &str, author_username: Option<&str>) -> String {
+    let hash = format!("0x{}", hash.trim_start_matches("0x").to_lowercase());
+    match author_username.filter(|u| !u.is_empty()) {
+        Some(username) => format!(
+            "{WARPCAST_BASE_URL}/{username}/{}",
+            &hash[..hash.len().min(10)]
+        ),
+        None => format!("{WARPCAST_BASE_URL}/~/conversations/{hash}"),
+    }
+}
+
+/// Username from a hub `userDataByFid` response
+pub fn username_from_user_data(user_data: &serde_json::Value) -> Option<String> {
+    user_data
+        .get("messages")?
+        .as_array()?
+        .iter()
+        .filter_map(|m| m.get("data")?.get("userDataBody"))
+        .find(|body| body.get("type").and_then(|t| t.as_str()) == Some("USER_DATA_TYPE_USERNAME"))
+        .and_then(|body| body.get("value")?.as_str())
+        .map(|u| u.to_string())
+}
+
+/// Render a QR code with half-height block characters for a terminal
+pub fn render_qr(data: &str) -> Result<String> {
+    let code = QrCode::new(data.as_bytes())?;
+    Ok(code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .quiet_zone(true)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links() {
+        assert_eq!(profile_url(3, Some("dwr")), "https://warpcast.com/dwr");
+        assert_eq!(profile_url(3, None), "https://warpcast.com/~/profiles/3");
+        let hash = "0xA1B2C3D4E5F60718293a4b5c6d7e8f9012345678";
+        assert_eq!(
+            cast_url(hash, Some("dwr")),
+            "https://warpcast.com/dwr/0xa1b2c3d4"
+        );
+        assert_eq!(
+            cast_url(hash, None),
+            "https://warpcast.com/~/conversations/0xa1b2c3d4e5f60718293a4b5c6d7e8f9012345678"
+        );
+    }
+
+    #[test]
+    fn test_render_qr() {
+        let qr = render_qr("https://warpcast.com/~/profiles/3").unwrap();
+        assert!(qr.lines().count() > 10);
+    }
+}
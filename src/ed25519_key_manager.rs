@@ -9,6 +9,8 @@ use ed25519_dalek::VerifyingKey;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::core::utils::private_file;
+
 /// Ed25519 key manager for Farcaster message signing
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ed25519KeyManager {
@@ -137,7 +139,7 @@ impl Ed25519KeyManager {
     /// Save keys to file
     pub fn save_to_file(&self, file_path: &Path) -> Result<()> {
         let json = serde_json::to_string_pretty(self).context("Failed to serialize keys")?;
-        fs::write(file_path, json).context("Failed to write keys file")?;
+        private_file::write(file_path, json.as_bytes()).context("Failed to write keys file")?;
         Ok(())
     }
 
@@ -154,9 +156,7 @@ impl Ed25519KeyManager {
 
     /// Get the default keys file path
     pub fn default_keys_file() -> Result<std::path::PathBuf> {
-        let home_dir =
-            dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
-        Ok(home_dir.join(".castorix").join("ed25519_keys.json"))
+        Ok(crate::core::utils::data_dir::data_dir().join("ed25519_keys.json"))
     }
 }
 
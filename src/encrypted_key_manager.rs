@@ -11,8 +11,6 @@ use anyhow::Context;
 use anyhow::Result;
 use argon2::password_hash::rand_core::OsRng;
 use argon2::password_hash::SaltString;
-use argon2::Argon2;
-use argon2::PasswordHasher;
 use base64::engine::general_purpose;
 use base64::Engine as _;
 use ethers::core::k256::ecdsa::SigningKey;
@@ -22,7 +20,14 @@ use ethers::signers::Signer;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::core::crypto::kdf;
+use crate::core::crypto::kdf::KdfParams;
 use crate::core::crypto::key_manager::KeyManager;
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
+use crate::core::utils::private_file;
+use crate::core::utils::prompt;
+use crate::core::utils::prompt::InputKind;
 
 /// Encrypted key storage structure
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +44,12 @@ struct EncryptedKeyData {
     alias: String,
     /// Creation timestamp
     created_at: u64,
+    /// Key file format version
+    #[serde(default = "kdf::legacy_key_format_version")]
+    version: u32,
+    /// Argon2 parameters used to derive the encryption key
+    #[serde(default = "KdfParams::legacy")]
+    kdf: KdfParams,
 }
 
 /// Key information structure
@@ -80,10 +91,7 @@ impl EncryptedKeyManager {
     /// # Returns
     /// * `Self` - The EncryptedKeyManager instance
     pub fn default_config() -> Self {
-        let home_dir = dirs::home_dir().unwrap_or_else(|| std::env::current_dir().unwrap());
-        let storage_path = home_dir
-            .join(".castorix")
-            .join("keys")
+        let storage_path = crate::core::utils::data_dir::keys_dir()
             .to_string_lossy()
             .to_string();
         Self::new(&storage_path)
@@ -217,6 +225,11 @@ impl EncryptedKeyManager {
             return Err(anyhow::anyhow!("Address mismatch - key may be corrupted"));
         }
 
+        // Re-encrypt keys written with an older format or weaker KDF settings
+        if kdf::needs_upgrade(encrypted_data.version, &encrypted_data.kdf) {
+            self.upgrade_encrypted_key(key_name, &encrypted_data, &private_key_bytes, password)?;
+        }
+
         // Create KeyManager
         self.key_manager = Some(KeyManager::from_private_key(&hex::encode(
             private_key_bytes,
@@ -317,15 +330,10 @@ impl EncryptedKeyManager {
         let salt = SaltString::generate(&mut OsRng);
 
         // Derive key from password using Argon2
-        let argon2 = Argon2::default();
-        let password_hash = argon2
-            .hash_password(password.as_bytes(), &salt)
-            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
-
-        let hash = password_hash.hash.unwrap();
-        let key_bytes = hash.as_bytes();
+        let kdf_params = KdfParams::configured();
+        let key_bytes = kdf_params.derive_key(password, &salt)?;
         #[allow(deprecated)]
-        let key = Key::<Aes256Gcm>::from_slice(&key_bytes[..32]);
+        let key = Key::<Aes256Gcm>::from_slice(&key_bytes);
 
         // Generate nonce
         let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
@@ -346,6 +354,8 @@ impl EncryptedKeyManager {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs(),
+            version: kdf::KEY_FORMAT_VERSION,
+            kdf: kdf_params,
         })
     }
 
@@ -359,16 +369,10 @@ impl EncryptedKeyManager {
         let salt = SaltString::from_b64(&encrypted_data.salt)
             .map_err(|e| anyhow::anyhow!("Invalid salt format: {}", e))?;
 
-        // Derive key from password using Argon2
-        let argon2 = Argon2::default();
-        let password_hash = argon2
-            .hash_password(password.as_bytes(), &salt)
-            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
-
-        let hash = password_hash.hash.unwrap();
-        let key_bytes = hash.as_bytes();
+        // Derive key from password using the recorded Argon2 parameters
+        let key_bytes = encrypted_data.kdf.derive_key(password, &salt)?;
         #[allow(deprecated)]
-        let key = Key::<Aes256Gcm>::from_slice(&key_bytes[..32]);
+        let key = Key::<Aes256Gcm>::from_slice(&key_bytes);
 
         // Decode nonce and encrypted data
         let nonce_bytes = general_purpose::STANDARD
@@ -390,8 +394,24 @@ impl EncryptedKeyManager {
         Ok(decrypted_key)
     }
 
+    /// Re-encrypt a key with the current format and KDF parameters, keeping its metadata
+    fn upgrade_encrypted_key(
+        &self,
+        key_name: &str,
+        old_data: &EncryptedKeyData,
+        private_key: &[u8],
+        password: &str,
+    ) -> Result<()> {
+        let mut upgraded = self.encrypt_private_key(private_key, password, &old_data.alias)?;
+        upgraded.address = old_data.address.clone();
+        upgraded.created_at = old_data.created_at;
+        self.save_encrypted_key(key_name, &upgraded)
+            .with_context(|| format!("Failed to upgrade key file for '{key_name}'"))
+    }
+
     /// Save encrypted key to file
     fn save_encrypted_key(&self, key_name: &str, encrypted_data: &EncryptedKeyData) -> Result<()> {
+        let _timer = metrics::start(Phase::DiskIo, "key file");
         // Ensure storage directory exists
         let storage_dir = Path::new(&self.storage_path);
         fs::create_dir_all(storage_dir).with_context(|| "Failed to create storage directory")?;
@@ -400,13 +420,15 @@ impl EncryptedKeyManager {
         let key_path = self.get_key_path(key_name);
         let json = serde_json::to_string_pretty(encrypted_data)
             .with_context(|| "Failed to serialize encrypted data")?;
-        fs::write(&key_path, json).with_context(|| "Failed to write encrypted key file")?;
+        private_file::write(Path::new(&key_path), json.as_bytes())
+            .with_context(|| "Failed to write encrypted key file")?;
 
         Ok(())
     }
 
     /// Load encrypted key from file
     fn load_encrypted_key(&self, key_name: &str) -> Result<EncryptedKeyData> {
+        let _timer = metrics::start(Phase::DiskIo, "key file");
         let key_path = self.get_key_path(key_name);
         let json =
             fs::read_to_string(&key_path).with_context(|| "Failed to read encrypted key file")?;
@@ -521,7 +543,7 @@ impl EncryptedKeyManager {
     }
 
     /// Get the file path for a key
-    fn get_key_path(&self, key_name: &str) -> String {
+    pub fn get_key_path(&self, key_name: &str) -> String {
         format!("{}/{}.json", self.storage_path, key_name)
     }
 }
@@ -529,6 +551,7 @@ impl EncryptedKeyManager {
 /// Prompt user for password securely
 pub fn prompt_password(prompt: &str) -> Result<String> {
     use rpassword::read_password;
+    prompt::ensure_interactive(InputKind::Password, prompt)?;
     print!("{prompt}");
     std::io::Write::flush(&mut std::io::stdout()).with_context(|| "Failed to flush stdout")?;
 
@@ -608,4 +631,44 @@ mod tests {
         let result = manager2.load_and_decrypt(wrong_password, key_name).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_legacy_key_file_is_upgraded_on_unlock() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage_path = temp_dir.path().to_string_lossy().to_string();
+        let password = "test_password_123";
+        let key_name = "legacy_key";
+
+        // Write a version 1 key file: Argon2 defaults and no recorded parameters
+        let private_key = [7u8; 32];
+        let address = LocalWallet::from(SigningKey::from_slice(&private_key).unwrap()).address();
+        let salt = SaltString::generate(&mut OsRng);
+        let key_bytes = KdfParams::legacy().derive_key(password, &salt).unwrap();
+        #[allow(deprecated)]
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key_bytes));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let legacy = serde_json::json!({
+            "encrypted_key": general_purpose::STANDARD.encode(cipher.encrypt(&nonce, private_key.as_ref()).unwrap()),
+            "salt": salt.to_string(),
+            "nonce": general_purpose::STANDARD.encode(nonce),
+            "address": format!("{address:?}"),
+            "alias": "old",
+            "created_at": 1,
+        });
+        let manager = EncryptedKeyManager::new(&storage_path);
+        fs::write(manager.get_key_path(key_name), legacy.to_string()).unwrap();
+
+        let mut manager = EncryptedKeyManager::new(&storage_path);
+        manager.load_and_decrypt(password, key_name).await.unwrap();
+        assert_eq!(manager.address().unwrap(), address);
+
+        let upgraded = manager.load_encrypted_key(key_name).unwrap();
+        assert_eq!(upgraded.version, kdf::KEY_FORMAT_VERSION);
+        assert_eq!(upgraded.kdf, KdfParams::configured());
+        assert_eq!(upgraded.created_at, 1);
+
+        // The upgraded file still unlocks with the same password
+        let mut manager = EncryptedKeyManager::new(&storage_path);
+        manager.load_and_decrypt(password, key_name).await.unwrap();
+    }
 }
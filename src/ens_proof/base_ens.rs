@@ -1,15 +1,15 @@
 use std::str::FromStr;
 
 use anyhow::Result;
-use ethers::providers::Http;
 use ethers::providers::Middleware;
-use ethers::providers::Provider;
 use ethers::types::transaction::eip2718::TypedTransaction;
 use ethers::types::Address;
 use ethers::types::TransactionRequest;
 use ethers::types::H160;
 
 use super::core::EnsProof;
+use super::names::namehash;
+use super::normalize::normalize;
 
 impl EnsProof {
     /// Check if a specific Base subdomain exists and get its owner
@@ -22,9 +22,9 @@ impl EnsProof {
     /// # Returns
     /// * `Result<Option<String>>` - Owner address if domain exists, None otherwise
     pub async fn check_base_subdomain(&self, domain: &str) -> Result<Option<String>> {
-        use reqwest::Client;
-
-        let client = Client::new();
+        let domain = normalize(domain)?;
+        let domain = domain.as_str();
+        let client = crate::core::utils::http::client();
 
         // Try multiple methods to find the Base subdomain
 
@@ -152,6 +152,8 @@ impl EnsProof {
     /// # Returns
     /// * `Result<Option<String>>` - Owner address if domain exists, None otherwise
     pub async fn query_base_ens_contract(&self, domain: &str) -> Result<Option<String>> {
+        let domain = normalize(domain)?;
+        let domain = domain.as_str();
         // Load environment variables from .env file
         dotenv::dotenv().ok();
 
@@ -162,12 +164,11 @@ impl EnsProof {
             let base_rpc = config.eth_base_rpc_url().to_string();
             (base_rpc, "Base")
         } else {
-            // For regular ENS domains, use ETH_RPC_URL
-            let eth_rpc = config.eth_rpc_url().to_string();
-            (eth_rpc, "Ethereum")
+            // For regular ENS domains, use the proof's Ethereum RPC URL
+            (self.rpc_url.clone(), "Ethereum")
         };
 
-        let provider = Provider::<Http>::try_from(&rpc_url)
+        let provider = crate::core::utils::http::provider(&rpc_url)
             .map_err(|e| anyhow::anyhow!("Failed to create {} provider: {}", chain_name, e))?;
 
         // ENS contracts - try different contracts for Base subdomains
@@ -191,12 +192,12 @@ impl EnsProof {
         // For Base subdomains, also try to check if the parent domain exists
         if domain.ends_with(".base.eth") {
             println!("   Checking parent domain 'base.eth' first...");
-            let parent_namehash = self.calculate_namehash("base.eth")?;
+            let parent_namehash = namehash("base.eth").0;
             println!("   Parent namehash: 0x{}", hex::encode(parent_namehash));
         }
 
         // Calculate namehash for the domain
-        let namehash = self.calculate_namehash(domain)?;
+        let namehash = namehash(domain).0;
         println!("   Namehash: 0x{}", hex::encode(namehash));
 
         // Step 1: Call resolver(bytes32) to get resolver address
@@ -294,43 +295,6 @@ impl EnsProof {
         }
     }
 
-    /// Calculate namehash for a domain name
-    ///
-    /// This implements the ENS namehash algorithm as specified in EIP-137.
-    ///
-    /// # Arguments
-    /// * `domain` - The domain name (e.g., "ryankung.base.eth")
-    ///
-    /// # Returns
-    /// * `Result<[u8; 32]>` - The namehash as a 32-byte array
-    fn calculate_namehash(&self, domain: &str) -> Result<[u8; 32]> {
-        use tiny_keccak::Hasher;
-        use tiny_keccak::Keccak;
-
-        // Split domain into labels
-        let labels: Vec<&str> = domain.split('.').collect();
-
-        // Start with the zero hash
-        let mut node = [0u8; 32];
-
-        // Process labels in reverse order
-        for label in labels.iter().rev() {
-            // First hash the label with keccak256
-            let mut label_hasher = Keccak::v256();
-            label_hasher.update(label.as_bytes());
-            let mut label_hash = [0u8; 32];
-            label_hasher.finalize(&mut label_hash);
-
-            // Then hash node + label_hash with keccak256
-            let mut hasher = Keccak::v256();
-            hasher.update(&node);
-            hasher.update(&label_hash);
-            hasher.finalize(&mut node);
-        }
-
-        Ok(node)
-    }
-
     /// Get all ENS domains for a given address
     ///
     /// This method queries for regular ENS domains owned by the address.
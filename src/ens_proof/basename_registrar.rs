@@ -0,0 +1,280 @@
+//! Basename registration and renewal through the Base registrar controller
+//!
+//! Basenames (`*.base.eth`) are sold by the RegistrarController on Base. Unlike
+//! the L1 ENS controller there is no commit-reveal step: a registration is a
+//! single `register` call paying `registerPrice`, which includes the temporary
+//! premium on recently expired names. Renewals pay the base rent only.
+
+use std::str::FromStr;
+
+use anyhow::Context;
+use anyhow::Result;
+use ethers::abi::ParamType;
+use ethers::abi::Token;
+use ethers::middleware::SignerMiddleware;
+use ethers::providers::Http;
+use ethers::providers::Middleware;
+use ethers::providers::Provider;
+use ethers::signers::LocalWallet;
+use ethers::signers::Signer;
+use ethers::types::Address;
+use ethers::types::TransactionRequest;
+use ethers::types::H256;
+use ethers::types::U256;
+
+use super::names::namehash;
+use super::normalize::normalize;
+use super::wildcard::encode_call;
+use crate::core::audit;
+use crate::core::policy;
+use crate::core::utils::finality;
+
+/// Basenames RegistrarController on Base mainnet
+pub const BASENAME_REGISTRAR_CONTROLLER: &str = "0x4cCb0BB02FCABA27e82a56646E81d8c5bC4119a5";
+/// Basenames L2Resolver on Base mainnet
+pub const BASENAME_L2_RESOLVER: &str = "0xC6d566A56A1aFf6508b41f6c90ff131615583BCD";
+/// Registration and renewal periods are counted in 365-day years
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+/// Extra value sent over the quoted price, in percent; the controller refunds the excess
+const PRICE_BUFFER_PERCENT: u64 = 5;
+/// Shortest label the controller accepts
+const MIN_LABEL_LENGTH: usize = 3;
+
+/// Label of a basename, accepting `alice` or `alice.base.eth`
+///
+/// # Arguments
+/// * `name` - The basename, with or without the `.base.eth` suffix
+///
+/// # Returns
+/// * `Result<String>` - The normalized label, or an error if the controller would reject it
+pub fn basename_label(name: &str) -> Result<String> {
+    let name = normalize(name)?;
+    let label = name.strip_suffix(".base.eth").unwrap_or(&name);
+    if label.contains('.') {
+        anyhow::bail!("'{name}' is not a basename; expected 'name' or 'name.base.eth'");
+    }
+    if label.chars().count() < MIN_LABEL_LENGTH {
+        anyhow::bail!("Basenames must be at least {MIN_LABEL_LENGTH} characters long");
+    }
+    if label.starts_with('-') || label.ends_with('-') {
+        anyhow::bail!("Basenames cannot start or end with a hyphen");
+    }
+    Ok(label.to_string())
+}
+
+/// Value to send for a quoted price, with [`PRICE_BUFFER_PERCENT`] on top
+pub fn price_with_buffer(price: U256) -> U256 {
+    price + price * PRICE_BUFFER_PERCENT / 100
+}
+
+/// Client for the Basenames RegistrarController
+pub struct BasenameRegistrar {
+    provider: Provider<Http>,
+    controller: Address,
+    resolver: Address,
+}
+
+impl BasenameRegistrar {
+    /// Create a registrar client for Base mainnet
+    ///
+    /// # Arguments
+    /// * `base_rpc_url` - Base RPC URL
+    pub fn new(base_rpc_url: &str) -> Result<Self> {
+        let provider = crate::core::utils::http::provider(base_rpc_url)
+            .map_err(|e| anyhow::anyhow!("Failed to create Base provider: {}", e))?;
+        Ok(Self {
+            provider,
+            controller: Address::from_str(BASENAME_REGISTRAR_CONTROLLER)?,
+            resolver: Address::from_str(BASENAME_L2_RESOLVER)?,
+        })
+    }
+
+    /// Whether a label can be registered
+    pub async fn available(&self, label: &str) -> Result<bool> {
+        let data = self
+            .call("available(string)", &[Token::String(label.to_string())])
+            .await?;
+        let tokens = ethers::abi::decode(&[ParamType::Bool], &data)
+            .context("Failed to decode available() response")?;
+        Ok(tokens[0].clone().into_bool().unwrap_or(false))
+    }
+
+    /// Price of registering a label, premium included
+    ///
+    /// # Arguments
+    /// * `label` - The basename label
+    /// * `duration` - Registration period in seconds
+    pub async fn register_price(&self, label: &str, duration: u64) -> Result<U256> {
+        let data = self
+            .call(
+                "registerPrice(string,uint256)",
+                &[
+                    Token::String(label.to_string()),
+                    Token::Uint(duration.into()),
+                ],
+            )
+            .await?;
+        decode_uint(&data, "registerPrice")
+    }
+
+    /// Price of renewing a label (the base rent, without premium)
+    ///
+    /// # Arguments
+    /// * `label` - The basename label
+    /// * `duration` - Renewal period in seconds
+    pub async fn renew_price(&self, label: &str, duration: u64) -> Result<U256> {
+        let data = self
+            .call(
+                "rentPrice(string,uint256)",
+                &[
+                    Token::String(label.to_string()),
+                    Token::Uint(duration.into()),
+                ],
+            )
+            .await?;
+        // rentPrice returns Price { base, premium }
+        decode_uint(&data, "rentPrice")
+    }
+
+    /// Register a label to `owner`, pointing its address record at the owner
+    ///
+    /// # Arguments
+    /// * `wallet` - Wallet paying for and sending the registration
+    /// * `label` - The basename label
+    /// * `owner` - Address that will own the name
+    /// * `duration` - Registration period in seconds
+    /// * `primary` - Also set the name as the owner's primary (reverse) name
+    /// * `value` - ETH sent with the call; the excess over the price is refunded
+    ///
+    /// # Returns
+    /// * `Result<H256>` - The transaction hash, once mined
+    pub async fn register(
+        &self,
+        wallet: &LocalWallet,
+        label: &str,
+        owner: Address,
+        duration: u64,
+        primary: bool,
+        value: U256,
+    ) -> Result<H256> {
+        let node = namehash(&format!("{label}.base.eth"));
+        let set_addr = encode_call(
+            "setAddr(bytes32,address)",
+            &[Token::FixedBytes(node.0.to_vec()), Token::Address(owner)],
+        );
+        let request = Token::Tuple(vec![
+            Token::String(label.to_string()),
+            Token::Address(owner),
+            Token::Uint(duration.into()),
+            Token::Address(self.resolver),
+            Token::Array(vec![Token::Bytes(set_addr)]),
+            Token::Bool(primary && owner == wallet.address()),
+        ]);
+        let data = encode_call(
+            "register((string,address,uint256,address,bytes[],bool))",
+            &[request],
+        );
+        self.send(wallet, data, value).await
+    }
+
+    /// Renew a label
+    ///
+    /// # Arguments
+    /// * `wallet` - Wallet paying for the renewal (need not own the name)
+    /// * `label` - The basename label
+    /// * `duration` - Renewal period in seconds
+    /// * `value` - ETH sent with the call; the excess over the price is refunded
+    ///
+    /// # Returns
+    /// * `Result<H256>` - The transaction hash, once mined
+    pub async fn renew(
+        &self,
+        wallet: &LocalWallet,
+        label: &str,
+        duration: u64,
+        value: U256,
+    ) -> Result<H256> {
+        let data = encode_call(
+            "renew(string,uint256)",
+            &[
+                Token::String(label.to_string()),
+                Token::Uint(duration.into()),
+            ],
+        );
+        self.send(wallet, data, value).await
+    }
+
+    /// ETH balance of an address on Base
+    pub async fn balance(&self, address: Address) -> Result<U256> {
+        Ok(self.provider.get_balance(address, None).await?)
+    }
+
+    async fn call(&self, signature: &str, args: &[Token]) -> Result<Vec<u8>> {
+        let tx = TransactionRequest::new()
+            .to(self.controller)
+            .data(encode_call(signature, args));
+        let data = self
+            .provider
+            .call(&tx.into(), None)
+            .await
+            .map_err(|e| anyhow::anyhow!("RegistrarController {} failed: {}", signature, e))?;
+        Ok(data.to_vec())
+    }
+
+    /// Simulate, then send a payable call to the controller and wait for it to be mined
+    async fn send(&self, wallet: &LocalWallet, data: Vec<u8>, value: U256) -> Result<H256> {
+        policy::current().check_value(value)?;
+        let chain_id = self.provider.get_chainid().await?;
+        let wallet = wallet.clone().with_chain_id(chain_id.as_u64());
+        let tx = TransactionRequest::new()
+            .from(wallet.address())
+            .to(self.controller)
+            .data(data)
+            .value(value);
+        if let Err(e) = self.provider.call(&tx.clone().into(), None).await {
+            anyhow::bail!("Transaction simulation failed: {}", e);
+        }
+
+        let client = SignerMiddleware::new(self.provider.clone(), wallet);
+        let sender = client.address();
+        let pending_tx = client.send_transaction(tx, None).await?;
+        let tx_hash = pending_tx.tx_hash();
+        audit::record_transaction(Some(sender), "basename", tx_hash);
+        match pending_tx.await? {
+            Some(receipt) if receipt.status == Some(1.into()) => {
+                let receipt = finality::confirm(&self.provider, receipt).await?;
+                Ok(receipt.transaction_hash)
+            }
+            Some(_) => anyhow::bail!("Transaction {tx_hash:?} reverted"),
+            None => anyhow::bail!("Transaction {tx_hash:?} was dropped"),
+        }
+    }
+}
+
+fn decode_uint(data: &[u8], method: &str) -> Result<U256> {
+    let tokens = ethers::abi::decode(&[ParamType::Uint(256)], data)
+        .with_context(|| format!("Failed to decode {method}() response"))?;
+    tokens[0]
+        .clone()
+        .into_uint()
+        .ok_or_else(|| anyhow::anyhow!("Malformed {method}() response"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basename_label() {
+        assert_eq!(basename_label("Alice.base.eth").unwrap(), "alice");
+        assert_eq!(basename_label("alice").unwrap(), "alice");
+        assert!(basename_label("ab").is_err());
+        assert!(basename_label("alice.eth").is_err());
+        assert!(basename_label("-alice").is_err());
+    }
+
+    #[test]
+    fn test_price_with_buffer() {
+        assert_eq!(price_with_buffer(U256::from(1000)), U256::from(1050));
+    }
+}
@@ -0,0 +1,113 @@
+//! Username proofs for names owned by smart-contract wallets (ERC-1271)
+//!
+//! A contract wallet (Safe, Coinbase Smart Wallet, ...) has no private key, so
+//! its proof names the contract as owner and carries the signature of an EOA
+//! the contract accepts. Only the contract can say which signers it accepts,
+//! so proofs are checked by calling ERC-1271 `isValidSignature(bytes32,bytes)`
+//! with the EIP-191 hash of the proof message.
+
+use anyhow::Result;
+use ethers::abi::Token;
+use ethers::providers::Http;
+use ethers::providers::Middleware;
+use ethers::providers::Provider;
+use ethers::providers::RpcError;
+use ethers::types::Address;
+use ethers::utils::hash_message;
+
+use super::core::EnsProof;
+use super::wildcard::encode_call;
+use super::wildcard::eth_call;
+
+/// Value `isValidSignature` returns for an accepted signature
+pub const ERC1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Whether an `isValidSignature` return value accepts the signature
+///
+/// The result is ABI-encoded `bytes4`, so the magic value is left-aligned in
+/// a 32-byte word.
+pub fn is_erc1271_magic_value(data: &[u8]) -> bool {
+    data.len() >= 4 && data[..4] == ERC1271_MAGIC_VALUE
+}
+
+impl EnsProof {
+    /// Provider for the chain a name resolves on: Base for Basenames,
+    /// Ethereum for everything else
+    fn owner_chain_provider(&self, domain: &str) -> Result<Provider<Http>> {
+        let rpc_url = if domain.ends_with(".base.eth") {
+            crate::consts::get_config().eth_base_rpc_url().to_string()
+        } else {
+            self.rpc_url.clone()
+        };
+        crate::core::utils::http::provider(rpc_url.as_str())
+            .map_err(|e| anyhow::anyhow!("Failed to create provider: {}", e))
+    }
+
+    /// Check whether the owner of a name is a contract rather than an EOA
+    ///
+    /// # Arguments
+    /// * `domain` - The name, which decides the chain to look on
+    /// * `owner` - The address the name resolves to
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the owner has contract code
+    pub async fn is_contract_owner(&self, domain: &str, owner: Address) -> Result<bool> {
+        let provider = self.owner_chain_provider(domain)?;
+        let code = provider
+            .get_code(owner, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get code of {:?}: {}", owner, e))?;
+        Ok(!code.is_empty())
+    }
+
+    /// Ask a contract wallet whether it accepts a signature over a proof message
+    ///
+    /// # Arguments
+    /// * `domain` - The name, which decides the chain to call on
+    /// * `wallet` - The contract wallet owning the name
+    /// * `message` - The proof message, as produced by [`Self::create_proof_message`]
+    /// * `signature` - The signature of an EOA signer of the wallet
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if `isValidSignature` returns the ERC-1271 magic value
+    pub async fn is_valid_contract_signature(
+        &self,
+        domain: &str,
+        wallet: Address,
+        message: &str,
+        signature: &[u8],
+    ) -> Result<bool> {
+        let provider = self.owner_chain_provider(domain)?;
+        let data = encode_call(
+            "isValidSignature(bytes32,bytes)",
+            &[
+                Token::FixedBytes(hash_message(message).as_bytes().to_vec()),
+                Token::Bytes(signature.to_vec()),
+            ],
+        );
+        // Wallets reject by reverting as often as by returning another value
+        match eth_call(&provider, wallet, data).await {
+            Ok(result) => Ok(is_erc1271_magic_value(&result)),
+            Err(e) if e.as_error_response().is_some() => Ok(false),
+            Err(e) => Err(anyhow::anyhow!(
+                "isValidSignature call to {:?} failed: {}",
+                wallet,
+                e
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_magic_value() {
+        let mut word = [0u8; 32];
+        assert!(!is_erc1271_magic_value(&word));
+        word[..4].copy_from_slice(&ERC1271_MAGIC_VALUE);
+        assert!(is_erc1271_magic_value(&word));
+        assert!(!is_erc1271_magic_value(&[]));
+    }
+}
@@ -8,6 +8,7 @@ use crate::core::crypto::key_manager::KeyManager;
 use crate::core::protocol::username_proof::UserNameProof;
 use crate::core::protocol::username_proof::UserNameType;
 use crate::encrypted_key_manager::EncryptedKeyManager;
+use crate::ens_proof::normalize::normalize;
 
 /// ENS domain proof implementation
 pub struct EnsProof {
@@ -49,6 +50,8 @@ impl EnsProof {
     /// # Returns
     /// * `Result<Address>` - The resolved address or an error
     pub async fn resolve_ens(&self, domain: &str) -> Result<Address> {
+        let domain = normalize(domain)?;
+        let domain = domain.as_str();
         // Use the Base ENS implementation for resolution
         match self.query_base_ens_contract(domain).await? {
             Some(address_str) => {
@@ -85,6 +88,8 @@ impl EnsProof {
         fid: u64,
         wallet_name: Option<&str>,
     ) -> Result<UserNameProof> {
+        let domain = normalize(domain)?;
+        let domain = domain.as_str();
         // Get the appropriate key manager
         let key_manager = if let Some(wallet_name) = wallet_name {
             // Load encrypted key manager and decrypt the key
@@ -113,27 +118,59 @@ impl EnsProof {
         };
 
         // Verify domain ownership with the selected key manager
-        let resolved_address = self.query_base_ens_contract(domain).await?;
-        let owner_address = key_manager.address();
-
-        let is_owned = match resolved_address {
-            Some(addr) => {
-                let resolved_addr =
-                    Address::from_str(&addr).with_context(|| "Failed to parse resolved address")?;
-                resolved_addr == owner_address
+        self.check_subdomain_control(domain).await?;
+        let resolved_address = self.resolve_proof_address(domain).await?;
+
+        // Contract wallets can't sign: the proof names the contract as owner
+        // and carries the selected wallet's signature, if the contract accepts it
+        let signer_address = key_manager.address();
+        let (owner_address, contract_owner) = match resolved_address {
+            Some(address) if address == signer_address => (address, false),
+            Some(address) if self.is_contract_owner(domain, address).await? => (address, true),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Domain {} is not owned by the selected wallet address: {:?}",
+                    domain,
+                    signer_address
+                ))
             }
-            None => false,
         };
 
-        if !is_owned {
+        let mut proof = Self::build_unsigned_proof(domain, fid, owner_address);
+
+        // Create the message to sign
+        let message = self.create_proof_message(&proof)?;
+
+        // Sign the message with the selected key manager
+        let signature = key_manager.sign_message(&message).await?;
+        if contract_owner
+            && !self
+                .is_valid_contract_signature(domain, owner_address, &message, &signature.to_vec())
+                .await?
+        {
             return Err(anyhow::anyhow!(
-                "Domain {} is not owned by the selected wallet address: {:?}",
+                "Domain {} is owned by contract wallet {:?}, which does not accept signatures from {:?} (ERC-1271 isValidSignature)",
                 domain,
-                owner_address
+                owner_address,
+                signer_address
             ));
         }
+        proof.set_signature(signature.to_vec());
 
-        // Create the proof data
+        Ok(proof)
+    }
+
+    /// Build a username proof without its signature, timestamped now
+    ///
+    /// # Arguments
+    /// * `domain` - The ENS domain name
+    /// * `fid` - The Farcaster ID
+    /// * `owner` - The address the domain resolves to: the signer, or a contract
+    ///   wallet that accepts the signer's signature
+    ///
+    /// # Returns
+    /// * `UserNameProof` - The proof, ready for [`Self::create_proof_message`]
+    pub fn build_unsigned_proof(domain: &str, fid: u64, owner: Address) -> UserNameProof {
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -142,7 +179,7 @@ impl EnsProof {
         let mut proof = UserNameProof::new();
         proof.set_timestamp(timestamp);
         proof.set_name(domain.as_bytes().to_vec());
-        proof.set_owner(owner_address.as_bytes().to_vec());
+        proof.set_owner(owner.as_bytes().to_vec());
         proof.set_fid(fid);
 
         // Set the correct field type based on domain
@@ -152,15 +189,7 @@ impl EnsProof {
             UserNameType::USERNAME_TYPE_ENS_L1
         };
         proof.set_field_type(field_type);
-
-        // Create the message to sign
-        let message = self.create_proof_message(&proof)?;
-
-        // Sign the message with the selected key manager
-        let signature = key_manager.sign_message(&message).await?;
-        proof.set_signature(signature.to_vec());
-
-        Ok(proof)
+        proof
     }
 
     /// Create the message that needs to be signed for the proof
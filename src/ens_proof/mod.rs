@@ -1,10 +1,25 @@
 pub mod base_ens;
+pub mod basename_registrar;
+pub mod contract_wallet;
 pub mod core;
+pub mod names;
+pub mod normalize;
+pub mod precheck;
 pub mod query;
+pub mod store;
 pub mod verification;
+pub mod wildcard;
 
 pub use core::EnsProof;
 
+pub use basename_registrar::BasenameRegistrar;
+pub use names::dns_decode;
+pub use names::dns_encode;
+pub use names::labelhash;
+pub use names::namehash;
+pub use store::ProofStore;
+pub use wildcard::SubdomainOwnership;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,6 +63,27 @@ mod tests {
         assert!(json.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_unsigned_proof_signed_externally() {
+        let test_key = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+        let key_manager =
+            crate::core::crypto::key_manager::KeyManager::from_private_key(test_key).unwrap();
+        let owner = key_manager.address();
+        let ens_proof = EnsProof::new(
+            key_manager.clone(),
+            "https://eth-mainnet.g.alchemy.com/v2/test".to_string(),
+        );
+
+        let mut proof = EnsProof::build_unsigned_proof("test.eth", 123, owner);
+        assert!(!ens_proof.verify_proof(&proof).await.unwrap());
+
+        // The wallet signs the message the API hands out
+        let message = ens_proof.create_proof_message(&proof).unwrap();
+        let signature = key_manager.sign_message(&message).await.unwrap();
+        proof.set_signature(signature.to_vec());
+        assert!(ens_proof.verify_proof(&proof).await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_ens_proof_from_env() {
         // Test that from_env now returns an error (environment variables are no longer allowed)
@@ -0,0 +1,128 @@
+//! ENS name hashing and encoding primitives
+//!
+//! The building blocks of resolver and registry calls: the EIP-137
+//! `namehash` of a name, the `labelhash` of one label and the DNS wire format
+//! ENSIP-10 `resolve(bytes,bytes)` takes. They work on the name as given;
+//! normalize user input first with [`super::normalize::normalize`].
+
+use anyhow::Result;
+use ethers::types::H256;
+use ethers::utils::keccak256;
+
+/// Keccak-256 hash of a single label, the registrar's token id for it
+///
+/// # Arguments
+/// * `label` - One label, e.g. `vitalik`
+pub fn labelhash(label: &str) -> H256 {
+    H256(keccak256(label.as_bytes()))
+}
+
+/// EIP-137 namehash of a name, the node registries and resolvers key on
+///
+/// # Arguments
+/// * `name` - The name, e.g. `vitalik.eth`; the empty name is the root node
+pub fn namehash(name: &str) -> H256 {
+    if name.is_empty() {
+        return H256::zero();
+    }
+    name.rsplit('.').fold(H256::zero(), |node, label| {
+        let mut preimage = [0u8; 64];
+        preimage[..32].copy_from_slice(node.as_bytes());
+        preimage[32..].copy_from_slice(labelhash(label).as_bytes());
+        H256(keccak256(preimage))
+    })
+}
+
+/// DNS wire-format encoding of a name, as expected by `resolve(bytes,bytes)`
+///
+/// # Arguments
+/// * `name` - The ENS name (e.g., "alice.cb.id")
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - Length-prefixed labels terminated by a zero byte
+pub fn dns_encode(name: &str) -> Result<Vec<u8>> {
+    let mut encoded = Vec::with_capacity(name.len() + 2);
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 255 {
+            return Err(anyhow::anyhow!("Invalid label in ENS name: {}", name));
+        }
+        encoded.push(label.len() as u8);
+        encoded.extend_from_slice(label.as_bytes());
+    }
+    encoded.push(0);
+    Ok(encoded)
+}
+
+/// Decode a DNS wire-format name back to its dotted form
+///
+/// # Arguments
+/// * `encoded` - Length-prefixed labels terminated by a zero byte
+///
+/// # Returns
+/// * `Result<String>` - The name, or an error if the encoding is truncated,
+///   has trailing bytes or a label is not UTF-8
+pub fn dns_decode(encoded: &[u8]) -> Result<String> {
+    let mut labels = Vec::new();
+    let mut rest = encoded;
+    loop {
+        let Some((&len, tail)) = rest.split_first() else {
+            anyhow::bail!("DNS-encoded name is missing its terminating zero byte");
+        };
+        if len == 0 {
+            if !tail.is_empty() {
+                anyhow::bail!("DNS-encoded name has {} trailing bytes", tail.len());
+            }
+            break;
+        }
+        let len = len as usize;
+        if tail.len() < len {
+            anyhow::bail!("DNS-encoded name is truncated");
+        }
+        let label = std::str::from_utf8(&tail[..len])
+            .map_err(|_| anyhow::anyhow!("DNS-encoded label is not UTF-8"))?;
+        labels.push(label);
+        rest = &tail[len..];
+    }
+    Ok(labels.join("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namehash() {
+        assert_eq!(namehash(""), H256::zero());
+        assert_eq!(
+            format!("{:?}", namehash("eth")),
+            "0x93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+        );
+        assert_eq!(
+            format!("{:?}", namehash("foo.eth")),
+            "0xde9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+        );
+        assert_eq!(
+            namehash("vitalik.eth"),
+            ethers::providers::namehash("vitalik.eth")
+        );
+        assert_eq!(
+            format!("{:?}", labelhash("vitalik")),
+            "0xaf2caa1c2ca1d027f1ac823b529d0a67cd144264b2789fa2ea4d63a67c7103cc"
+        );
+    }
+
+    #[test]
+    fn test_dns_encode() {
+        assert_eq!(
+            dns_encode("alice.cb.id").unwrap(),
+            b"\x05alice\x02cb\x02id\x00".to_vec()
+        );
+        assert!(dns_encode("bad..name").is_err());
+        assert_eq!(
+            dns_decode(b"\x05alice\x02cb\x02id\x00").unwrap(),
+            "alice.cb.id"
+        );
+        assert!(dns_decode(b"\x05alice\x02cb").is_err());
+        assert!(dns_decode(b"\x05ali").is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! ENS name normalization
+//!
+//! Names are normalized the way ENS clients do (ENSIP-15) before they are
+//! hashed, resolved or put in a proof, so `Vitalik.ETH` and `vitalik.eth` are
+//! the same name and names no ENS app could register are rejected up front.
+//! The spec's mapping, emoji, confusable and script tables come from the
+//! `ens-normalize-rs` crate, so besides case folding and width mapping this
+//! covers:
+//! - disallowed and invisible characters, empty labels, `--` as the third
+//!   and fourth characters (which also rules out punycode `xn--` labels) and
+//!   underscores anywhere but the start of a label;
+//! - emoji, kept whole with their ZWJ sequences and without presentation
+//!   selectors (U+FE0F);
+//! - look-alike spoofs, whether a label mixes scripts (`vіtalik` with a
+//!   Cyrillic `і`) or is written entirely in a script that imitates another
+//!   (an all-Cyrillic `аррӏе`).
+
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use ens_normalize_rs::EnsNameNormalizer;
+
+/// The normalizer, built once: loading the spec tables is not cheap
+static NORMALIZER: OnceLock<EnsNameNormalizer> = OnceLock::new();
+
+/// Normalize an ENS name
+///
+/// # Arguments
+/// * `name` - The name as typed, e.g. `Vitalik.ETH`
+///
+/// # Returns
+/// * `Result<String>` - The normalized name, or an error naming the rule the
+///   name breaks
+pub fn normalize(name: &str) -> Result<String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        anyhow::bail!("ENS name is empty");
+    }
+    NORMALIZER
+        .get_or_init(EnsNameNormalizer::default)
+        .normalize(trimmed)
+        .map_err(|e| anyhow::anyhow!("Invalid ENS name '{name}': {e}"))
+}
+
+/// Whether a name is already in normalized form
+pub fn is_normalized(name: &str) -> bool {
+    normalize(name).is_ok_and(|normalized| normalized == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(" Vitalik.ETH ").unwrap(), "vitalik.eth");
+        assert_eq!(normalize("ａｂｃ.eth").unwrap(), "abc.eth");
+        assert_eq!(normalize("cafe\u{301}.eth").unwrap(), "café.eth");
+        assert_eq!(normalize("❤\u{FE0F}.eth").unwrap(), "❤.eth");
+        assert_eq!(normalize("_ab.eth").unwrap(), "_ab.eth");
+        let family = "👨\u{200D}👩\u{200D}👧.eth";
+        assert_eq!(normalize(family).unwrap(), family);
+        assert!(is_normalized("vitalik.eth"));
+        assert!(!is_normalized("Vitalik.eth"));
+
+        for invalid in [
+            "",
+            "a..eth",
+            "vitalik.eth.",
+            "a b.eth",
+            "a_b.eth",
+            "xn--ls8h.eth",
+            "ab--c.eth",
+            "v\u{0456}talik.eth",
+            "\u{0430}\u{0440}\u{0440}\u{04CF}\u{0435}.eth",
+            "\u{301}ab.eth",
+            "a\u{200D}b.eth",
+        ] {
+            assert!(
+                normalize(invalid).is_err(),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+}
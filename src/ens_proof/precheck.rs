@@ -0,0 +1,182 @@
+//! Readiness of ENS names for a username proof, checked before signing
+//!
+//! A hub accepts an ENS username proof when the name resolves to the FID's
+//! custody address or one of its verified addresses, and a wrapped subname
+//! has not expired. [`EnsProof::precheck`] checks all of this for one name
+//! without touching a key, so many names can be vetted before any proof is
+//! generated.
+
+use anyhow::Result;
+use ethers::types::Address;
+use serde::Serialize;
+
+use super::core::EnsProof;
+use super::normalize::normalize;
+use crate::core::client::hub_client::FarcasterClient;
+
+/// The addresses a FID can prove names for
+#[derive(Debug, Clone)]
+pub struct FidAddresses {
+    pub fid: u64,
+    pub custody: Address,
+    pub verified: Vec<Address>,
+}
+
+impl FidAddresses {
+    /// Look up a FID's custody and verified addresses on a hub
+    pub async fn fetch(hub_client: &FarcasterClient, fid: u64) -> Result<Self> {
+        let custody = hub_client.get_custody_address(fid).await?;
+        let custody = custody
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Hub returned an invalid custody address: {custody}"))?;
+        let verified = hub_client
+            .get_eth_addresses(fid)
+            .await?
+            .iter()
+            .filter_map(|address| address.parse().ok())
+            .collect();
+        Ok(Self {
+            fid,
+            custody,
+            verified,
+        })
+    }
+
+    /// How `owner` is linked to the FID: `custody` or `verified_address`
+    pub fn relation(&self, owner: Address) -> Option<&'static str> {
+        if self.custody == owner {
+            Some("custody")
+        } else if self.verified.contains(&owner) {
+            Some("verified_address")
+        } else {
+            None
+        }
+    }
+}
+
+/// Readiness report for one name
+#[derive(Debug, Clone, Serialize)]
+pub struct DomainReadiness {
+    /// The name as given
+    pub input: String,
+    /// The normalized name, if it is valid
+    pub domain: Option<String>,
+    /// The address the name resolves to
+    pub owner: Option<String>,
+    /// How the owner is linked to the FID
+    pub owner_relation: Option<&'static str>,
+    /// Whether the owner is a contract wallet, which must accept the signing
+    /// wallet's signature (ERC-1271)
+    pub contract_wallet: bool,
+    /// Issues that do not block a proof
+    pub warnings: Vec<String>,
+    /// Reasons a proof would be rejected
+    pub problems: Vec<String>,
+}
+
+impl DomainReadiness {
+    /// Whether a proof for the name can be generated and accepted
+    pub fn is_ready(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl EnsProof {
+    /// Check whether a proof of `domain` for a FID would be accepted
+    ///
+    /// Failures are recorded in the report rather than returned, so one bad
+    /// name does not stop a batch.
+    ///
+    /// # Arguments
+    /// * `domain` - The name as typed
+    /// * `fid` - Custody and verified addresses of the FID
+    pub async fn precheck(&self, domain: &str, fid: &FidAddresses) -> DomainReadiness {
+        let mut report = DomainReadiness {
+            input: domain.to_string(),
+            domain: None,
+            owner: None,
+            owner_relation: None,
+            contract_wallet: false,
+            warnings: Vec::new(),
+            problems: Vec::new(),
+        };
+        let domain = match normalize(domain) {
+            Ok(domain) => domain,
+            Err(e) => {
+                report.problems.push(e.to_string());
+                return report;
+            }
+        };
+        report.domain = Some(domain.clone());
+        if !domain.contains('.') {
+            report
+                .problems
+                .push("Not an ENS name; fnames are issued by the fname registry".to_string());
+            return report;
+        }
+
+        match self.subdomain_control_warning(&domain).await {
+            Ok(Some(warning)) => report.warnings.push(warning),
+            Ok(None) => {}
+            Err(e) => report.problems.push(e.to_string()),
+        }
+
+        let owner = match self.resolve_proof_address(&domain).await {
+            Ok(Some(owner)) => owner,
+            Ok(None) => {
+                report
+                    .problems
+                    .push(format!("{domain} does not resolve to an address"));
+                return report;
+            }
+            Err(e) => {
+                report.problems.push(format!("Resolution failed: {e}"));
+                return report;
+            }
+        };
+        report.owner = Some(format!("{owner:?}"));
+
+        report.owner_relation = fid.relation(owner);
+        if report.owner_relation.is_none() {
+            report.problems.push(format!(
+                "{owner:?} is neither the custody address nor a verified address of FID {}",
+                fid.fid
+            ));
+        }
+
+        match self.is_contract_owner(&domain, owner).await {
+            Ok(true) => {
+                report.contract_wallet = true;
+                report.warnings.push(
+                    "Owned by a contract wallet: the signing wallet must be accepted by its \
+                     ERC-1271 isValidSignature"
+                        .to_string(),
+                );
+            }
+            Ok(false) => {}
+            Err(e) => report
+                .warnings
+                .push(format!("Could not check for a contract wallet: {e}")),
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relation() {
+        let custody = Address::repeat_byte(1);
+        let verified = Address::repeat_byte(2);
+        let fid = FidAddresses {
+            fid: 42,
+            custody,
+            verified: vec![verified],
+        };
+        assert_eq!(fid.relation(custody), Some("custody"));
+        assert_eq!(fid.relation(verified), Some("verified_address"));
+        assert_eq!(fid.relation(Address::repeat_byte(3)), None);
+    }
+}
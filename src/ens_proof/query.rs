@@ -15,9 +15,6 @@ impl EnsProof {
     /// # Returns
     /// * `Result<Vec<String>>` - List of ENS domains with proofs
     pub async fn get_ens_domains_with_proofs(&self, hub_url: &str) -> Result<Vec<String>> {
-        use reqwest::Client;
-
-        let _client = Client::new();
         let current_address = self.key_manager.address();
 
         // Query the Farcaster Hub for username proofs
@@ -66,9 +63,7 @@ impl EnsProof {
     /// # Returns
     /// * `Result<Vec<String>>` - List of ENS domains with proofs
     pub async fn get_ens_domains_by_fid(&self, hub_url: &str, fid: u64) -> Result<Vec<String>> {
-        use reqwest::Client;
-
-        let client = Client::new();
+        let client = crate::core::utils::http::client();
 
         // Query username proofs to find ENS domains
         let username_proofs_url = format!("{hub_url}/v1/userNameProofsByFid?fid={fid}");
@@ -119,9 +114,7 @@ impl EnsProof {
     /// # Returns
     /// * `Result<Vec<String>>` - List of ENS domains owned by the address
     pub async fn get_ens_domains_by_address(&self, address: &str) -> Result<Vec<String>> {
-        use reqwest::Client;
-
-        let client = Client::new();
+        let client = crate::core::utils::http::client();
 
         // Query The Graph API for ENS domains owned by the address
         // Use lowercase address as The Graph stores addresses in lowercase
@@ -188,9 +181,8 @@ impl EnsProof {
 
         use ethers::types::Address;
 
-        let _provider =
-            ethers::providers::Provider::<ethers::providers::Http>::try_from(&self.rpc_url)
-                .with_context(|| "Failed to create provider")?;
+        let _provider = crate::core::utils::http::provider(&self.rpc_url)
+            .with_context(|| "Failed to create provider")?;
 
         let addr = Address::from_str(address).with_context(|| "Failed to parse address")?;
 
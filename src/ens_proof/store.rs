@@ -0,0 +1,246 @@
+//! Managed storage of generated username proofs
+//!
+//! Every proof created by `ens proof` is kept under `<data dir>/proofs` as
+//! `<id>.json` (the same JSON `hub submit-proof` reads) next to `<id>.meta.json`
+//! with its lifecycle: when it was created and every attempt to submit it.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::utils::data_dir;
+
+/// One attempt to submit a stored proof
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofSubmission {
+    pub submitted_at: DateTime<Utc>,
+    pub hub_url: String,
+    pub success: bool,
+    /// Hub response body, or the error message of a failed attempt
+    pub response: serde_json::Value,
+    /// Transaction hash when the proof was submitted on chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+}
+
+/// Metadata kept alongside a stored proof
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofMetadata {
+    pub id: String,
+    pub domain: String,
+    pub fid: u64,
+    pub created_at: DateTime<Utc>,
+    /// Hex encoded proof signature, used to link submissions to the proof
+    pub signature: String,
+    #[serde(default)]
+    pub submissions: Vec<ProofSubmission>,
+}
+
+impl ProofMetadata {
+    /// Latest successful submission, if any
+    pub fn submitted(&self) -> Option<&ProofSubmission> {
+        self.submissions.iter().rev().find(|s| s.success)
+    }
+}
+
+/// Directory of stored proofs
+pub struct ProofStore {
+    dir: PathBuf,
+}
+
+impl ProofStore {
+    /// Open the store in `<data dir>/proofs`
+    pub fn open_default() -> Result<Self> {
+        Self::open(data_dir::data_dir().join("proofs"))
+    }
+
+    /// Open (or create) a store in a directory
+    pub fn open(dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    /// Path of a stored proof's JSON
+    pub fn proof_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    fn metadata_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.meta.json"))
+    }
+
+    /// Store a newly generated proof
+    ///
+    /// # Arguments
+    /// * `domain` - The ENS name the proof is for
+    /// * `fid` - The FID the proof links to
+    /// * `proof_json` - Serialized proof as written by `EnsProof::serialize_proof`
+    ///
+    /// # Returns
+    /// * `Result<ProofMetadata>` - Metadata of the stored proof
+    pub fn save(&self, domain: &str, fid: u64, proof_json: &str) -> Result<ProofMetadata> {
+        let proof: serde_json::Value =
+            serde_json::from_str(proof_json).context("Proof is not valid JSON")?;
+        let signature = proof
+            .get("signature")
+            .and_then(|s| s.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let created_at = Utc::now();
+        let base_id = format!(
+            "{}_{}_{}",
+            domain.replace('.', "_"),
+            fid,
+            created_at.format("%Y%m%dT%H%M%S")
+        );
+        let mut id = base_id.clone();
+        let mut suffix = 1;
+        while self.metadata_path(&id).exists() {
+            suffix += 1;
+            id = format!("{base_id}_{suffix}");
+        }
+
+        let metadata = ProofMetadata {
+            id: id.clone(),
+            domain: domain.to_string(),
+            fid,
+            created_at,
+            signature,
+            submissions: Vec::new(),
+        };
+        std::fs::write(self.proof_path(&id), proof_json)?;
+        self.write_metadata(&metadata)?;
+        Ok(metadata)
+    }
+
+    /// All stored proofs, newest first
+    pub fn list(&self) -> Result<Vec<ProofMetadata>> {
+        let mut proofs = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let is_metadata = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.ends_with(".meta.json"));
+            if !is_metadata {
+                continue;
+            }
+            if let Ok(metadata) = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| Ok(serde_json::from_str::<ProofMetadata>(&content)?))
+            {
+                proofs.push(metadata);
+            }
+        }
+        proofs.sort_by_key(|p| std::cmp::Reverse(p.created_at));
+        Ok(proofs)
+    }
+
+    /// Metadata and proof JSON of a stored proof
+    pub fn get(&self, id: &str) -> Result<(ProofMetadata, String)> {
+        let metadata = std::fs::read_to_string(self.metadata_path(id))
+            .with_context(|| format!("No stored proof with id '{id}'"))?;
+        let metadata: ProofMetadata = serde_json::from_str(&metadata)?;
+        let proof = std::fs::read_to_string(self.proof_path(id))
+            .with_context(|| format!("Proof file for '{id}' is missing"))?;
+        Ok((metadata, proof))
+    }
+
+    /// Delete a stored proof and its metadata
+    pub fn delete(&self, id: &str) -> Result<()> {
+        let metadata_path = self.metadata_path(id);
+        if !metadata_path.exists() {
+            anyhow::bail!("No stored proof with id '{id}'");
+        }
+        std::fs::remove_file(metadata_path)?;
+        let proof_path = self.proof_path(id);
+        if proof_path.exists() {
+            std::fs::remove_file(proof_path)?;
+        }
+        Ok(())
+    }
+
+    /// Record a submission against the stored proof with the same signature
+    ///
+    /// Proofs submitted from files outside the store are matched by their
+    /// signature, so a copy of a stored proof still links back to it.
+    ///
+    /// # Arguments
+    /// * `signature` - Hex encoded signature of the submitted proof
+    /// * `submission` - The submission attempt
+    ///
+    /// # Returns
+    /// * `Result<Option<String>>` - Id of the linked proof, or `None` if it is not stored
+    pub fn record_submission(
+        &self,
+        signature: &str,
+        submission: ProofSubmission,
+    ) -> Result<Option<String>> {
+        let signature = signature.trim_start_matches("0x");
+        if signature.is_empty() {
+            return Ok(None);
+        }
+        let Some(mut metadata) = self.list()?.into_iter().find(|p| {
+            p.signature
+                .trim_start_matches("0x")
+                .eq_ignore_ascii_case(signature)
+        }) else {
+            return Ok(None);
+        };
+        metadata.submissions.push(submission);
+        self.write_metadata(&metadata)?;
+        Ok(Some(metadata.id))
+    }
+
+    fn write_metadata(&self, metadata: &ProofMetadata) -> Result<()> {
+        std::fs::write(
+            self.metadata_path(&metadata.id),
+            serde_json::to_string_pretty(metadata)?,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proof_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ProofStore::open(dir.path().to_path_buf()).unwrap();
+        let proof = r#"{"name":"alice.eth","fid":42,"signature":"abcd"}"#;
+
+        let saved = store.save("alice.eth", 42, proof).unwrap();
+        assert!(store.proof_path(&saved.id).exists());
+        assert_eq!(store.list().unwrap().len(), 1);
+
+        let linked = store
+            .record_submission(
+                "0xABCD",
+                ProofSubmission {
+                    submitted_at: Utc::now(),
+                    hub_url: "http://hub".to_string(),
+                    success: true,
+                    response: serde_json::json!({ "hash": "0x01" }),
+                    tx_hash: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(linked.as_deref(), Some(saved.id.as_str()));
+
+        let (metadata, json) = store.get(&saved.id).unwrap();
+        assert_eq!(json, proof);
+        assert!(metadata.submitted().is_some());
+
+        store.delete(&saved.id).unwrap();
+        assert!(store.list().unwrap().is_empty());
+        assert!(store.get(&saved.id).is_err());
+    }
+}
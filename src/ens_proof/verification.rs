@@ -1,37 +1,33 @@
-use std::str::FromStr;
-
 use anyhow::Context;
 use anyhow::Result;
 use ethers::prelude::*;
-use ethers::types::Address;
 
 use super::core::EnsProof;
+use super::normalize::normalize;
 use crate::core::protocol::username_proof::UserNameProof;
 
 impl EnsProof {
     /// Verify ENS domain ownership
     ///
+    /// Subnames served by a parent's wildcard or offchain (CCIP-Read) resolver are supported.
+    ///
     /// # Arguments
     /// * `domain` - The ENS domain to verify
     ///
     /// # Returns
     /// * `Result<bool>` - True if the domain is owned by the key manager's address
     pub async fn verify_ens_ownership(&self, domain: &str) -> Result<bool> {
-        let resolved_address = self.query_base_ens_contract(domain).await?;
-        let owner_address = self.key_manager.address();
-
-        match resolved_address {
-            Some(addr) => {
-                let resolved_addr =
-                    Address::from_str(&addr).with_context(|| "Failed to parse resolved address")?;
-                Ok(resolved_addr == owner_address)
-            }
-            None => Ok(false),
-        }
+        let domain = normalize(domain)?;
+        let domain = domain.as_str();
+        let resolved_address = self.resolve_proof_address(domain).await?;
+        Ok(resolved_address == Some(self.key_manager.address()))
     }
 
     /// Verify a username proof
     ///
+    /// The signature must come from the proof's owner or, when the owner is a
+    /// contract wallet, be accepted by its ERC-1271 `isValidSignature`.
+    ///
     /// # Arguments
     /// * `proof` - The username proof to verify
     ///
@@ -43,17 +39,24 @@ impl EnsProof {
 
         // Get the signature
         let signature_bytes = proof.get_signature();
-        if signature_bytes.is_empty() {
+        if signature_bytes.is_empty() || proof.get_owner().len() != 20 {
             return Ok(false);
         }
+        let owner = Address::from_slice(proof.get_owner());
 
         // Convert signature bytes to Signature type
         let signature =
             Signature::try_from(signature_bytes).with_context(|| "Failed to parse signature")?;
 
         // Verify the signature
-        self.key_manager
-            .verify_signature(&message, &signature)
+        if signature.recover(message.as_str())? == owner {
+            return Ok(true);
+        }
+        let domain = String::from_utf8_lossy(proof.get_name()).to_string();
+        if !self.is_contract_owner(&domain, owner).await? {
+            return Ok(false);
+        }
+        self.is_valid_contract_signature(&domain, owner, &message, signature_bytes)
             .await
     }
 }
@@ -0,0 +1,537 @@
+//! Wildcard (ENSIP-10) and offchain (CCIP-Read, EIP-3668) ENS resolution
+//!
+//! Names such as `alice.cb.id` have no resolver of their own: the parent's
+//! resolver answers for every subname, usually by reverting with
+//! `OffchainLookup` and asking the client to fetch the answer from a gateway.
+//! Wrapped subnames additionally carry NameWrapper fuses that decide whether
+//! the parent can still take the name back.
+
+use std::str::FromStr;
+
+use anyhow::Context;
+use anyhow::Result;
+use ethers::abi::ParamType;
+use ethers::abi::Token;
+use ethers::providers::Http;
+use ethers::providers::Middleware;
+use ethers::providers::Provider;
+use ethers::providers::RpcError;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::Address;
+use ethers::types::Bytes;
+use ethers::types::TransactionRequest;
+use ethers::types::U256;
+use ethers::utils::id;
+use serde::Serialize;
+
+use super::core::EnsProof;
+use super::names::dns_encode;
+use super::names::namehash;
+use super::normalize::normalize;
+
+/// ENS registry on Ethereum mainnet
+const ENS_REGISTRY: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+/// ENS NameWrapper on Ethereum mainnet
+const NAME_WRAPPER: &str = "0xD4416b13d2b3a9aBae7AcD5D6C2BbDBE25686401";
+/// ERC-165 interface id of IExtendedResolver (`resolve(bytes,bytes)`)
+const EXTENDED_RESOLVER_INTERFACE: [u8; 4] = [0x90, 0x61, 0xb9, 0x23];
+/// Maximum number of chained OffchainLookup reverts followed for one call
+const MAX_CCIP_LOOKUPS: usize = 4;
+
+/// NameWrapper fuse: the wrapped name cannot be unwrapped
+pub const CANNOT_UNWRAP: u32 = 1;
+/// NameWrapper fuse: the parent can no longer replace or burn fuses on the subname
+pub const PARENT_CANNOT_CONTROL: u32 = 1 << 16;
+
+/// Ownership of a (sub)name as seen by the registry and the NameWrapper
+#[derive(Debug, Clone, Serialize)]
+pub struct SubdomainOwnership {
+    pub name: String,
+    /// Owner recorded in the ENS registry (the NameWrapper for wrapped names)
+    pub registry_owner: Address,
+    /// Whether the name is held by the NameWrapper
+    pub wrapped: bool,
+    /// Effective owner (the NameWrapper token holder for wrapped names)
+    pub owner: Address,
+    /// NameWrapper fuses (0 for unwrapped names)
+    pub fuses: u32,
+    /// NameWrapper expiry as a unix timestamp (0 for unwrapped names)
+    pub expiry: u64,
+}
+
+impl SubdomainOwnership {
+    /// Whether the parent name's owner can still reclaim or reassign this name
+    pub fn parent_can_control(&self) -> bool {
+        !self.wrapped || self.fuses & PARENT_CANNOT_CONTROL == 0
+    }
+
+    /// Whether a wrapped name has expired at the given unix time
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.wrapped && self.expiry != 0 && self.expiry <= now
+    }
+}
+
+/// Decoded `OffchainLookup(address,string[],bytes,bytes4,bytes)` revert
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffchainLookup {
+    pub sender: Address,
+    pub urls: Vec<String>,
+    pub call_data: Vec<u8>,
+    pub callback_function: [u8; 4],
+    pub extra_data: Vec<u8>,
+}
+
+impl OffchainLookup {
+    /// Decode revert data, returning `None` when it is not an OffchainLookup error
+    pub fn decode(revert_data: &[u8]) -> Option<Self> {
+        let selector = id("OffchainLookup(address,string[],bytes,bytes4,bytes)");
+        if revert_data.len() < 4 || revert_data[..4] != selector {
+            return None;
+        }
+
+        let mut tokens = ethers::abi::decode(
+            &[
+                ParamType::Address,
+                ParamType::Array(Box::new(ParamType::String)),
+                ParamType::Bytes,
+                ParamType::FixedBytes(4),
+                ParamType::Bytes,
+            ],
+            &revert_data[4..],
+        )
+        .ok()?
+        .into_iter();
+
+        let sender = tokens.next()?.into_address()?;
+        let urls = tokens
+            .next()?
+            .into_array()?
+            .into_iter()
+            .map(|t| t.into_string())
+            .collect::<Option<Vec<_>>>()?;
+        let call_data = tokens.next()?.into_bytes()?;
+        let callback_function = tokens.next()?.into_fixed_bytes()?.try_into().ok()?;
+        let extra_data = tokens.next()?.into_bytes()?;
+
+        Some(Self {
+            sender,
+            urls,
+            call_data,
+            callback_function,
+            extra_data,
+        })
+    }
+}
+
+impl EnsProof {
+    /// Resolve a name to an address following ENSIP-10 wildcards and CCIP-Read
+    ///
+    /// The resolver is looked up on the name itself and then on each parent. If
+    /// the resolver implements `IExtendedResolver` the lookup goes through
+    /// `resolve(bytes,bytes)`, following any `OffchainLookup` reverts.
+    ///
+    /// # Arguments
+    /// * `domain` - The ENS name to resolve (e.g., "alice.cb.id")
+    ///
+    /// # Returns
+    /// * `Result<Option<Address>>` - The resolved address, or None if unresolved
+    pub async fn resolve_ens_wildcard(&self, domain: &str) -> Result<Option<Address>> {
+        let domain = normalize(domain)?;
+        let domain = domain.as_str();
+        let provider = self.mainnet_provider()?;
+        let node = namehash(domain).0;
+
+        let Some((resolver, resolver_name)) = self.find_resolver(&provider, domain).await? else {
+            return Ok(None);
+        };
+
+        let mut addr_call = id("addr(bytes32)").to_vec();
+        addr_call.extend_from_slice(&node);
+
+        let result = if self.supports_extended_resolver(&provider, resolver).await {
+            let call = encode_call(
+                "resolve(bytes,bytes)",
+                &[Token::Bytes(dns_encode(domain)?), Token::Bytes(addr_call)],
+            );
+            let response = self.ccip_call(&provider, resolver, call).await?;
+            ethers::abi::decode(&[ParamType::Bytes], &response)
+                .ok()
+                .and_then(|t| t.into_iter().next())
+                .and_then(|t| t.into_bytes())
+                .ok_or_else(|| anyhow::anyhow!("Malformed resolve() response for {}", domain))?
+        } else if resolver_name == domain {
+            self.ccip_call(&provider, resolver, addr_call)
+                .await?
+                .to_vec()
+        } else {
+            // A parent's resolver without ENSIP-10 support does not answer for subnames
+            return Ok(None);
+        };
+
+        if result.len() < 32 {
+            return Ok(None);
+        }
+        let address = Address::from_slice(&result[12..32]);
+        Ok((address != Address::zero()).then_some(address))
+    }
+
+    /// Resolve the address a username proof for `domain` must be signed by
+    ///
+    /// Basenames keep using the Base registry lookup; every other name goes
+    /// through wildcard and CCIP-Read aware resolution on mainnet.
+    ///
+    /// # Arguments
+    /// * `domain` - The ENS name
+    ///
+    /// # Returns
+    /// * `Result<Option<Address>>` - The resolved address, or None if unresolved
+    pub async fn resolve_proof_address(&self, domain: &str) -> Result<Option<Address>> {
+        let domain = normalize(domain)?;
+        let domain = domain.as_str();
+        if domain.ends_with(".base.eth") {
+            return match self.query_base_ens_contract(domain).await? {
+                Some(addr) => Ok(Some(
+                    Address::from_str(&addr).with_context(|| "Failed to parse resolved address")?,
+                )),
+                None => Ok(None),
+            };
+        }
+        self.resolve_ens_wildcard(domain).await
+    }
+
+    /// Reject expired wrapped subnames and warn when the parent can still reclaim one
+    ///
+    /// Names that are not in the registry at all (offchain subnames) are left to
+    /// resolution alone.
+    ///
+    /// # Arguments
+    /// * `domain` - The ENS name
+    pub async fn check_subdomain_control(&self, domain: &str) -> Result<()> {
+        if let Some(warning) = self.subdomain_control_warning(domain).await? {
+            println!("⚠️  {warning}");
+        }
+        Ok(())
+    }
+
+    /// [`Self::check_subdomain_control`] without printing
+    ///
+    /// # Returns
+    /// * `Result<Option<String>>` - A warning when the parent can still reclaim
+    ///   the name; an error for an expired wrapped subname
+    pub async fn subdomain_control_warning(&self, domain: &str) -> Result<Option<String>> {
+        let domain = normalize(domain)?;
+        let domain = domain.as_str();
+        if domain.ends_with(".base.eth") || domain.split('.').count() <= 2 {
+            return Ok(None);
+        }
+
+        let ownership = self.subdomain_ownership(domain).await?;
+        if ownership.registry_owner == Address::zero() {
+            return Ok(None);
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        if ownership.is_expired(now) {
+            return Err(anyhow::anyhow!(
+                "Wrapped subdomain {} expired at {}",
+                domain,
+                ownership.expiry
+            ));
+        }
+        Ok(ownership.parent_can_control().then(|| {
+            format!(
+                "The owner of the parent name can still reclaim {domain} (PARENT_CANNOT_CONTROL not burned)"
+            )
+        }))
+    }
+
+    /// Look up registry and NameWrapper ownership of a (sub)name
+    ///
+    /// # Arguments
+    /// * `domain` - The ENS name to inspect
+    ///
+    /// # Returns
+    /// * `Result<SubdomainOwnership>` - Owner, wrapping state and fuses
+    pub async fn subdomain_ownership(&self, domain: &str) -> Result<SubdomainOwnership> {
+        let domain = normalize(domain)?;
+        let domain = domain.as_str();
+        let provider = self.mainnet_provider()?;
+        let node = namehash(domain).0;
+        let registry = Address::from_str(ENS_REGISTRY)?;
+        let name_wrapper = Address::from_str(NAME_WRAPPER)?;
+
+        let owner_data = eth_call(
+            &provider,
+            registry,
+            encode_call("owner(bytes32)", &[Token::FixedBytes(node.to_vec())]),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Registry owner() call failed: {}", e))?;
+        let registry_owner = word_to_address(&owner_data);
+
+        if registry_owner != name_wrapper {
+            return Ok(SubdomainOwnership {
+                name: domain.to_string(),
+                registry_owner,
+                wrapped: false,
+                owner: registry_owner,
+                fuses: 0,
+                expiry: 0,
+            });
+        }
+
+        let data = eth_call(
+            &provider,
+            name_wrapper,
+            encode_call(
+                "getData(uint256)",
+                &[Token::Uint(U256::from_big_endian(&node))],
+            ),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("NameWrapper getData() call failed: {}", e))?;
+        let tokens = ethers::abi::decode(
+            &[ParamType::Address, ParamType::Uint(32), ParamType::Uint(64)],
+            &data,
+        )
+        .with_context(|| "Failed to decode NameWrapper getData() response")?;
+
+        Ok(SubdomainOwnership {
+            name: domain.to_string(),
+            registry_owner,
+            wrapped: true,
+            owner: tokens[0].clone().into_address().unwrap_or_default(),
+            fuses: tokens[1].clone().into_uint().unwrap_or_default().low_u32(),
+            expiry: tokens[2].clone().into_uint().unwrap_or_default().low_u64(),
+        })
+    }
+
+    /// Find the resolver responsible for a name, walking up to its parents
+    async fn find_resolver(
+        &self,
+        provider: &Provider<Http>,
+        domain: &str,
+    ) -> Result<Option<(Address, String)>> {
+        let registry = Address::from_str(ENS_REGISTRY)?;
+        let mut name = domain;
+
+        loop {
+            let data = eth_call(
+                provider,
+                registry,
+                encode_call(
+                    "resolver(bytes32)",
+                    &[Token::FixedBytes(namehash(name).0.to_vec())],
+                ),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Registry resolver() call failed: {}", e))?;
+
+            let resolver = word_to_address(&data);
+            if resolver != Address::zero() {
+                return Ok(Some((resolver, name.to_string())));
+            }
+
+            match name.split_once('.') {
+                Some((_, parent)) if !parent.is_empty() => name = parent,
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    async fn supports_extended_resolver(
+        &self,
+        provider: &Provider<Http>,
+        resolver: Address,
+    ) -> bool {
+        let call = encode_call(
+            "supportsInterface(bytes4)",
+            &[Token::FixedBytes(EXTENDED_RESOLVER_INTERFACE.to_vec())],
+        );
+        match eth_call(provider, resolver, call).await {
+            Ok(data) => data.len() >= 32 && data[31] == 1,
+            Err(_) => false,
+        }
+    }
+
+    /// eth_call that follows EIP-3668 OffchainLookup reverts through their gateways
+    async fn ccip_call(
+        &self,
+        provider: &Provider<Http>,
+        to: Address,
+        data: Vec<u8>,
+    ) -> Result<Bytes> {
+        let mut data = data;
+
+        for _ in 0..MAX_CCIP_LOOKUPS {
+            let error = match eth_call(provider, to, data.clone()).await {
+                Ok(result) => return Ok(result),
+                Err(e) => e,
+            };
+
+            let lookup = error
+                .as_error_response()
+                .and_then(|r| r.as_revert_data())
+                .and_then(|revert| OffchainLookup::decode(&revert))
+                .ok_or_else(|| anyhow::anyhow!("Resolver call failed: {}", error))?;
+
+            if lookup.sender != to {
+                return Err(anyhow::anyhow!(
+                    "OffchainLookup sender {:?} does not match resolver {:?}",
+                    lookup.sender,
+                    to
+                ));
+            }
+
+            println!(
+                "   Following CCIP-Read lookup via {} gateway(s)",
+                lookup.urls.len()
+            );
+            let response = fetch_gateway(&lookup).await?;
+
+            data = lookup.callback_function.to_vec();
+            data.extend(ethers::abi::encode(&[
+                Token::Bytes(response),
+                Token::Bytes(lookup.extra_data),
+            ]));
+        }
+
+        Err(anyhow::anyhow!(
+            "Too many chained OffchainLookup reverts (limit {})",
+            MAX_CCIP_LOOKUPS
+        ))
+    }
+
+    fn mainnet_provider(&self) -> Result<Provider<Http>> {
+        crate::core::utils::http::provider(self.rpc_url.as_str())
+            .map_err(|e| anyhow::anyhow!("Failed to create Ethereum provider: {}", e))
+    }
+}
+
+/// Query the gateways of an OffchainLookup in order until one answers
+async fn fetch_gateway(lookup: &OffchainLookup) -> Result<Vec<u8>> {
+    let client = crate::core::utils::http::client();
+    let sender = format!("{:?}", lookup.sender);
+    let data = format!("0x{}", hex::encode(&lookup.call_data));
+    let mut last_error = anyhow::anyhow!("OffchainLookup did not list any gateway URLs");
+
+    for url in &lookup.urls {
+        let url = url.replace("{sender}", &sender);
+        let request = if url.contains("{data}") {
+            client.get(url.replace("{data}", &data))
+        } else {
+            client
+                .post(&url)
+                .json(&serde_json::json!({ "sender": sender, "data": data }))
+        };
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                last_error = anyhow::anyhow!("Gateway {} unreachable: {}", url, e);
+                continue;
+            }
+        };
+
+        let status = response.status();
+        if status.is_client_error() {
+            // 4xx is authoritative per EIP-3668; do not try other gateways
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!(
+                "Gateway {} returned {}: {}",
+                url,
+                status,
+                body
+            ));
+        }
+        if !status.is_success() {
+            last_error = anyhow::anyhow!("Gateway {} returned {}", url, status);
+            continue;
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse gateway response from {url}"))?;
+        let hex_data = body
+            .get("data")
+            .and_then(|d| d.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Gateway {} response has no data field", url))?;
+        return hex::decode(hex_data.trim_start_matches("0x"))
+            .with_context(|| format!("Gateway {url} returned invalid hex"));
+    }
+
+    Err(last_error)
+}
+
+pub(super) fn encode_call(signature: &str, args: &[Token]) -> Vec<u8> {
+    let mut data = id(signature).to_vec();
+    data.extend(ethers::abi::encode(args));
+    data
+}
+
+pub(super) async fn eth_call(
+    provider: &Provider<Http>,
+    to: Address,
+    data: Vec<u8>,
+) -> std::result::Result<Bytes, ethers::providers::ProviderError> {
+    let tx = TypedTransaction::Legacy(TransactionRequest::new().to(to).data(data));
+    provider.call(&tx, None).await
+}
+
+fn word_to_address(data: &[u8]) -> Address {
+    if data.len() >= 32 {
+        Address::from_slice(&data[12..32])
+    } else {
+        Address::zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_offchain_lookup() {
+        let sender = Address::repeat_byte(0x11);
+        let mut revert = id("OffchainLookup(address,string[],bytes,bytes4,bytes)").to_vec();
+        revert.extend(ethers::abi::encode(&[
+            Token::Address(sender),
+            Token::Array(vec![Token::String(
+                "https://gateway.example/{sender}/{data}.json".to_string(),
+            )]),
+            Token::Bytes(vec![1, 2, 3]),
+            Token::FixedBytes(vec![0xde, 0xad, 0xbe, 0xef]),
+            Token::Bytes(vec![4, 5]),
+        ]));
+
+        let lookup = OffchainLookup::decode(&revert).unwrap();
+        assert_eq!(lookup.sender, sender);
+        assert_eq!(lookup.urls.len(), 1);
+        assert_eq!(lookup.call_data, vec![1, 2, 3]);
+        assert_eq!(lookup.callback_function, [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(lookup.extra_data, vec![4, 5]);
+
+        assert!(OffchainLookup::decode(&[0u8; 4]).is_none());
+    }
+
+    #[test]
+    fn test_parent_control_fuses() {
+        let mut ownership = SubdomainOwnership {
+            name: "alice.example.eth".to_string(),
+            registry_owner: Address::zero(),
+            wrapped: true,
+            owner: Address::zero(),
+            fuses: CANNOT_UNWRAP,
+            expiry: 100,
+        };
+        assert!(ownership.parent_can_control());
+        assert!(ownership.is_expired(100));
+
+        ownership.fuses |= PARENT_CANNOT_CONTROL;
+        assert!(!ownership.parent_can_control());
+    }
+}
@@ -0,0 +1,520 @@
+//! Versioned adapters over the Farcaster registries and gateways
+//!
+//! Every Farcaster contract reports its release with `VERSION()`. An adapter
+//! encodes the state-changing calls and names the EIP-712 domain of one
+//! release, so that when a gateway or registry is upgraded with changed
+//! function signatures only a new adapter is needed; the flows in
+//! [`FarcasterContractClient`] stay the same.
+//!
+//! [`Adapters::detect`] reads the chain ID and the versions once per client
+//! and picks an adapter per contract. A version that cannot be read (e.g. on
+//! a bare Anvil node) gets the newest adapter; so does an unknown version,
+//! with a warning naming the chain, and the simulation run before every
+//! transaction reports a mismatch before anything is broadcast.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use ethers::abi::Abi;
+use ethers::abi::Function;
+use ethers::abi::HumanReadableParser;
+use ethers::abi::Token;
+use ethers::contract::Contract;
+use ethers::contract::ContractCall;
+use ethers::middleware::Middleware;
+use ethers::types::Address;
+use ethers::types::Bytes;
+use ethers::types::U256;
+
+use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+use crate::farcaster::contracts::types::ContractResult;
+
+/// A contract call encoded for one contract version
+#[derive(Debug, Clone)]
+pub struct AdapterCall {
+    /// The function as deployed
+    pub function: Function,
+    /// Arguments, in the order of the function's inputs
+    pub args: Vec<Token>,
+}
+
+impl AdapterCall {
+    fn new(signature: &str, args: Vec<Token>) -> Self {
+        let function = HumanReadableParser::parse_function(signature)
+            .unwrap_or_else(|e| panic!("invalid adapter signature {signature}: {e}"));
+        Self { function, args }
+    }
+
+    /// ABI-encoded calldata, selector included
+    pub fn calldata(&self) -> Result<Bytes> {
+        Ok(self.function.encode_input(&self.args)?.into())
+    }
+
+    /// Bind the call to a contract and client, ready to simulate or send
+    ///
+    /// # Arguments
+    /// * `address` - Address of the contract the adapter was selected for
+    /// * `client` - Middleware that signs and sends, or a plain provider
+    pub fn bind<M: Middleware, D: ethers::abi::Detokenize>(
+        &self,
+        address: Address,
+        client: Arc<M>,
+    ) -> Result<ContractCall<M, D>> {
+        let mut abi = Abi::default();
+        abi.functions
+            .insert(self.function.name.clone(), vec![self.function.clone()]);
+        let contract = Contract::new(address, abi, client);
+        Ok(contract.method_hash(self.function.short_signature(), self.args.as_slice())?)
+    }
+}
+
+/// Name and version of a contract's EIP-712 domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip712Domain {
+    pub name: &'static str,
+    pub version: &'static str,
+}
+
+/// IdRegistry calls that depend on the deployed version
+pub trait IdRegistryAdapter: Send + Sync {
+    /// `VERSION()` of the release this adapter speaks
+    fn version(&self) -> &'static str;
+    /// EIP-712 domain of transfer and recovery signatures
+    fn eip712_domain(&self) -> Eip712Domain;
+    /// Transfer the caller's FID to an address that signed to accept it
+    fn transfer(&self, to: Address, deadline: U256, signature: Bytes) -> AdapterCall;
+    /// Change the caller's recovery address
+    fn change_recovery_address(&self, recovery: Address) -> AdapterCall;
+}
+
+/// IdGateway calls that depend on the deployed version
+pub trait IdGatewayAdapter: Send + Sync {
+    /// `VERSION()` of the release this adapter speaks
+    fn version(&self) -> &'static str;
+    /// EIP-712 domain of `registerFor` signatures
+    fn eip712_domain(&self) -> Eip712Domain;
+    /// Register a FID for the caller, optionally with extra storage units
+    fn register(&self, recovery: Address, extra_storage: Option<u64>) -> AdapterCall;
+    /// Register a FID to an address that signed a `Register` request
+    fn register_for(
+        &self,
+        to: Address,
+        recovery: Address,
+        deadline: U256,
+        signature: Bytes,
+        extra_storage: Option<u64>,
+    ) -> AdapterCall;
+}
+
+/// KeyRegistry calls that depend on the deployed version
+pub trait KeyRegistryAdapter: Send + Sync {
+    /// `VERSION()` of the release this adapter speaks
+    fn version(&self) -> &'static str;
+    /// EIP-712 domain of `removeFor` signatures
+    fn eip712_domain(&self) -> Eip712Domain;
+    /// Remove one of the caller's keys
+    fn remove(&self, key: Bytes) -> AdapterCall;
+}
+
+/// KeyGateway calls that depend on the deployed version
+pub trait KeyGatewayAdapter: Send + Sync {
+    /// `VERSION()` of the release this adapter speaks
+    fn version(&self) -> &'static str;
+    /// EIP-712 domain of `Add` signatures
+    fn eip712_domain(&self) -> Eip712Domain;
+    /// Add a key to the caller's FID
+    fn add(&self, key_type: u32, key: Bytes, metadata_type: u8, metadata: Bytes) -> AdapterCall;
+    /// Add a key on behalf of a FID owner who signed an `Add` request
+    #[allow(clippy::too_many_arguments)]
+    fn add_for(
+        &self,
+        owner: Address,
+        key_type: u32,
+        key: Bytes,
+        metadata_type: u8,
+        metadata: Bytes,
+        deadline: U256,
+        signature: Bytes,
+    ) -> AdapterCall;
+}
+
+/// StorageRegistry calls that depend on the deployed version
+pub trait StorageRegistryAdapter: Send + Sync {
+    /// `VERSION()` of the release this adapter speaks
+    fn version(&self) -> &'static str;
+    /// Rent storage units for a FID
+    fn rent(&self, fid: u64, units: u64) -> AdapterCall;
+}
+
+/// IdRegistry as released on 2023-11-15
+pub struct IdRegistryV1;
+
+impl IdRegistryAdapter for IdRegistryV1 {
+    fn version(&self) -> &'static str {
+        "2023.11.15"
+    }
+
+    fn eip712_domain(&self) -> Eip712Domain {
+        Eip712Domain {
+            name: "Farcaster IdRegistry",
+            version: "1",
+        }
+    }
+
+    fn transfer(&self, to: Address, deadline: U256, signature: Bytes) -> AdapterCall {
+        AdapterCall::new(
+            "function transfer(address to, uint256 deadline, bytes sig)",
+            vec![
+                Token::Address(to),
+                Token::Uint(deadline),
+                Token::Bytes(signature.to_vec()),
+            ],
+        )
+    }
+
+    fn change_recovery_address(&self, recovery: Address) -> AdapterCall {
+        AdapterCall::new(
+            "function changeRecoveryAddress(address recovery)",
+            vec![Token::Address(recovery)],
+        )
+    }
+}
+
+/// IdGateway as released on 2023-11-15
+pub struct IdGatewayV1;
+
+impl IdGatewayAdapter for IdGatewayV1 {
+    fn version(&self) -> &'static str {
+        "2023.11.15"
+    }
+
+    fn eip712_domain(&self) -> Eip712Domain {
+        Eip712Domain {
+            name: "Farcaster IdGateway",
+            version: "1",
+        }
+    }
+
+    fn register(&self, recovery: Address, extra_storage: Option<u64>) -> AdapterCall {
+        match extra_storage {
+            None => AdapterCall::new(
+                "function register(address recovery) payable returns (uint256, uint256)",
+                vec![Token::Address(recovery)],
+            ),
+            Some(extra_storage) => AdapterCall::new(
+                "function register(address recovery, uint256 extraStorage) payable returns (uint256, uint256)",
+                vec![Token::Address(recovery), Token::Uint(extra_storage.into())],
+            ),
+        }
+    }
+
+    fn register_for(
+        &self,
+        to: Address,
+        recovery: Address,
+        deadline: U256,
+        signature: Bytes,
+        extra_storage: Option<u64>,
+    ) -> AdapterCall {
+        let mut args = vec![
+            Token::Address(to),
+            Token::Address(recovery),
+            Token::Uint(deadline),
+            Token::Bytes(signature.to_vec()),
+        ];
+        match extra_storage {
+            None => AdapterCall::new(
+                "function registerFor(address to, address recovery, uint256 deadline, bytes sig) payable returns (uint256, uint256)",
+                args,
+            ),
+            Some(extra_storage) => {
+                args.push(Token::Uint(extra_storage.into()));
+                AdapterCall::new(
+                    "function registerFor(address to, address recovery, uint256 deadline, bytes sig, uint256 extraStorage) payable returns (uint256, uint256)",
+                    args,
+                )
+            }
+        }
+    }
+}
+
+/// KeyRegistry as released on 2023-11-15
+pub struct KeyRegistryV1;
+
+impl KeyRegistryAdapter for KeyRegistryV1 {
+    fn version(&self) -> &'static str {
+        "2023.11.15"
+    }
+
+    fn eip712_domain(&self) -> Eip712Domain {
+        Eip712Domain {
+            name: "Farcaster KeyRegistry",
+            version: "1",
+        }
+    }
+
+    fn remove(&self, key: Bytes) -> AdapterCall {
+        AdapterCall::new(
+            "function remove(bytes key)",
+            vec![Token::Bytes(key.to_vec())],
+        )
+    }
+}
+
+/// KeyGateway as released on 2023-11-15
+pub struct KeyGatewayV1;
+
+impl KeyGatewayAdapter for KeyGatewayV1 {
+    fn version(&self) -> &'static str {
+        "2023.11.15"
+    }
+
+    fn eip712_domain(&self) -> Eip712Domain {
+        Eip712Domain {
+            name: "Farcaster KeyGateway",
+            version: "1",
+        }
+    }
+
+    fn add(&self, key_type: u32, key: Bytes, metadata_type: u8, metadata: Bytes) -> AdapterCall {
+        AdapterCall::new(
+            "function add(uint32 keyType, bytes key, uint8 metadataType, bytes metadata)",
+            vec![
+                Token::Uint(key_type.into()),
+                Token::Bytes(key.to_vec()),
+                Token::Uint(metadata_type.into()),
+                Token::Bytes(metadata.to_vec()),
+            ],
+        )
+    }
+
+    fn add_for(
+        &self,
+        owner: Address,
+        key_type: u32,
+        key: Bytes,
+        metadata_type: u8,
+        metadata: Bytes,
+        deadline: U256,
+        signature: Bytes,
+    ) -> AdapterCall {
+        AdapterCall::new(
+            "function addFor(address fidOwner, uint32 keyType, bytes key, uint8 metadataType, bytes metadata, uint256 deadline, bytes sig)",
+            vec![
+                Token::Address(owner),
+                Token::Uint(key_type.into()),
+                Token::Bytes(key.to_vec()),
+                Token::Uint(metadata_type.into()),
+                Token::Bytes(metadata.to_vec()),
+                Token::Uint(deadline),
+                Token::Bytes(signature.to_vec()),
+            ],
+        )
+    }
+}
+
+/// StorageRegistry as released on 2023-08-23
+pub struct StorageRegistryV1;
+
+impl StorageRegistryAdapter for StorageRegistryV1 {
+    fn version(&self) -> &'static str {
+        "2023.08.23"
+    }
+
+    fn rent(&self, fid: u64, units: u64) -> AdapterCall {
+        AdapterCall::new(
+            "function rent(uint256 fid, uint256 units) payable returns (uint256)",
+            vec![Token::Uint(fid.into()), Token::Uint(units.into())],
+        )
+    }
+}
+
+/// `VERSION()` of each contract, `None` where it could not be read
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContractVersions {
+    pub id_registry: Option<String>,
+    pub id_gateway: Option<String>,
+    pub key_registry: Option<String>,
+    pub key_gateway: Option<String>,
+    pub storage_registry: Option<String>,
+}
+
+/// The adapter chosen for each contract
+#[derive(Clone)]
+pub struct Adapters {
+    pub chain_id: u64,
+    pub id_registry: Arc<dyn IdRegistryAdapter>,
+    pub id_gateway: Arc<dyn IdGatewayAdapter>,
+    pub key_registry: Arc<dyn KeyRegistryAdapter>,
+    pub key_gateway: Arc<dyn KeyGatewayAdapter>,
+    pub storage_registry: Arc<dyn StorageRegistryAdapter>,
+}
+
+impl std::fmt::Debug for Adapters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Adapters")
+            .field("chain_id", &self.chain_id)
+            .field("id_registry", &self.id_registry.version())
+            .field("id_gateway", &self.id_gateway.version())
+            .field("key_registry", &self.key_registry.version())
+            .field("key_gateway", &self.key_gateway.version())
+            .field("storage_registry", &self.storage_registry.version())
+            .finish()
+    }
+}
+
+impl Adapters {
+    /// Pick an adapter per contract from the chain and the versions read
+    /// from it
+    pub fn select(chain_id: u64, versions: &ContractVersions) -> Self {
+        Self {
+            chain_id,
+            id_registry: pick(
+                "IdRegistry",
+                chain_id,
+                versions.id_registry.as_deref(),
+                vec![Arc::new(IdRegistryV1) as Arc<dyn IdRegistryAdapter>],
+                |adapter| adapter.version(),
+            ),
+            id_gateway: pick(
+                "IdGateway",
+                chain_id,
+                versions.id_gateway.as_deref(),
+                vec![Arc::new(IdGatewayV1) as Arc<dyn IdGatewayAdapter>],
+                |adapter| adapter.version(),
+            ),
+            key_registry: pick(
+                "KeyRegistry",
+                chain_id,
+                versions.key_registry.as_deref(),
+                vec![Arc::new(KeyRegistryV1) as Arc<dyn KeyRegistryAdapter>],
+                |adapter| adapter.version(),
+            ),
+            key_gateway: pick(
+                "KeyGateway",
+                chain_id,
+                versions.key_gateway.as_deref(),
+                vec![Arc::new(KeyGatewayV1) as Arc<dyn KeyGatewayAdapter>],
+                |adapter| adapter.version(),
+            ),
+            storage_registry: pick(
+                "StorageRegistry",
+                chain_id,
+                versions.storage_registry.as_deref(),
+                vec![Arc::new(StorageRegistryV1) as Arc<dyn StorageRegistryAdapter>],
+                |adapter| adapter.version(),
+            ),
+        }
+    }
+
+    /// Read the chain ID and each contract's version and pick the adapters
+    pub async fn detect(client: &FarcasterContractClient) -> Result<Self> {
+        let chain_id = client.provider.get_chainid().await?.as_u64();
+        let (id_registry, id_gateway, key_registry, key_gateway, storage_registry) = tokio::join!(
+            client.id_registry.version(),
+            client.id_gateway.version(),
+            client.key_registry.version(),
+            client.key_gateway.version(),
+            client.storage_registry.version(),
+        );
+        let version = |result: Result<ContractResult<String>>| match result {
+            Ok(ContractResult::Success(version)) => Some(version),
+            _ => None,
+        };
+        let versions = ContractVersions {
+            id_registry: version(id_registry),
+            id_gateway: version(id_gateway),
+            key_registry: version(key_registry),
+            key_gateway: version(key_gateway),
+            storage_registry: version(storage_registry),
+        };
+        tracing::debug!("Contract versions on chain {chain_id}: {versions:?}");
+        Ok(Self::select(chain_id, &versions))
+    }
+}
+
+/// Choose among a contract's adapters, oldest first
+fn pick<A: ?Sized>(
+    contract: &str,
+    chain_id: u64,
+    version: Option<&str>,
+    mut adapters: Vec<Arc<A>>,
+    version_of: impl Fn(&A) -> &'static str,
+) -> Arc<A> {
+    let newest = adapters.len() - 1;
+    let index = match version {
+        None => newest,
+        Some(version) => match adapters.iter().position(|a| version_of(a) == version) {
+            Some(index) => index,
+            None => {
+                tracing::warn!(
+                    "{contract} reports unknown version {version} on chain {chain_id}; \
+                     using the adapter for {}",
+                    version_of(&adapters[newest])
+                );
+                newest
+            }
+        },
+    };
+    adapters.swap_remove(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_v1_calldata_matches_abi() {
+        let recovery = Address::repeat_byte(0x11);
+        let calldata = IdGatewayV1.register(recovery, Some(2)).calldata().unwrap();
+        let expected = ethers::abi::encode(&[Token::Address(recovery), Token::Uint(2.into())]);
+        assert_eq!(
+            &calldata[..4],
+            &ethers::utils::id("register(address,uint256)")[..]
+        );
+        assert_eq!(&calldata[4..], &expected[..]);
+
+        let calldata = StorageRegistryV1.rent(42, 1).calldata().unwrap();
+        assert_eq!(
+            &calldata[..4],
+            &ethers::utils::id("rent(uint256,uint256)")[..]
+        );
+
+        let call = KeyGatewayV1.add_for(
+            recovery,
+            1,
+            vec![0; 32].into(),
+            1,
+            Bytes::new(),
+            U256::one(),
+            Bytes::new(),
+        );
+        assert_eq!(
+            call.function.short_signature(),
+            ethers::utils::id("addFor(address,uint32,bytes,uint8,bytes,uint256,bytes)")
+        );
+        let call = KeyGatewayV1.add(1, vec![0; 32].into(), 1, Bytes::new());
+        assert_eq!(
+            call.function.short_signature(),
+            ethers::utils::id("add(uint32,bytes,uint8,bytes)")
+        );
+    }
+
+    #[test]
+    fn test_select() {
+        let known = ContractVersions {
+            id_gateway: Some("2023.11.15".to_string()),
+            ..Default::default()
+        };
+        let adapters = Adapters::select(10, &known);
+        assert_eq!(adapters.id_gateway.version(), "2023.11.15");
+        assert_eq!(adapters.storage_registry.version(), "2023.08.23");
+
+        let upgraded = ContractVersions {
+            key_gateway: Some("2099.01.01".to_string()),
+            ..Default::default()
+        };
+        let adapters = Adapters::select(31337, &upgraded);
+        assert_eq!(adapters.key_gateway.version(), "2023.11.15");
+    }
+}
@@ -0,0 +1,248 @@
+//! Auth address keys in the KeyRegistry
+//!
+//! Besides Ed25519 signers (key type 1), the KeyRegistry accepts Ethereum
+//! addresses as keys of type 2. Messages from such an "auth address" are signed
+//! with EIP-712 instead of Ed25519 (see `core::crypto::auth_address`).
+
+use anyhow::Result;
+use ethers::abi::encode;
+use ethers::abi::Token;
+use ethers::signers::Signer;
+use ethers::types::Address;
+use ethers::types::TransactionReceipt;
+use ethers::types::H256;
+use ethers::types::U256;
+use ethers::utils::keccak256;
+
+use crate::core::audit;
+use crate::core::policy;
+use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+use crate::farcaster::contracts::types::ContractResult;
+use crate::farcaster::contracts::types::Fid;
+
+/// KeyRegistry key type of Ed25519 signers
+pub const ED25519_KEY_TYPE: u32 = 1;
+/// KeyRegistry key type of Ethereum auth addresses
+pub const AUTH_ADDRESS_KEY_TYPE: u32 = 2;
+/// Metadata type of a SignedKeyRequest
+pub const SIGNED_KEY_REQUEST_METADATA_TYPE: u8 = 1;
+/// KeyRegistry `KeyState.ADDED`
+const KEY_STATE_ADDED: u8 = 1;
+
+impl FarcasterContractClient {
+    /// Check that the KeyRegistry has a validator for auth address keys
+    ///
+    /// Keys are only accepted for (key type, metadata type) pairs with a
+    /// registered validator, so this tells up front whether the deployment
+    /// supports auth addresses at all.
+    pub async fn auth_address_supported(&self) -> Result<bool> {
+        let validator = self
+            .key_registry
+            .contract()
+            .validators(AUTH_ADDRESS_KEY_TYPE, SIGNED_KEY_REQUEST_METADATA_TYPE)
+            .call()
+            .await?;
+        Ok(validator != Address::zero())
+    }
+
+    /// Whether an address is an active auth address of a FID
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `auth_address` - The address to look up
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if the key is added with the auth address key type
+    pub async fn is_auth_address(&self, fid: Fid, auth_address: Address) -> Result<bool> {
+        match self
+            .key_registry
+            .key_data_of(fid, auth_address.as_bytes().to_vec())
+            .await?
+        {
+            ContractResult::Success((state, key_type)) => {
+                Ok(state == KEY_STATE_ADDED && key_type == AUTH_ADDRESS_KEY_TYPE)
+            }
+            ContractResult::Error(e) => Err(anyhow::anyhow!("Failed to read key data: {e}")),
+        }
+    }
+
+    /// Add an auth address to the wallet's FID through the KeyGateway
+    ///
+    /// The wallet must be the FID's custody address; it signs both the
+    /// SignedKeyRequest metadata and the `addFor` authorization.
+    ///
+    /// # Arguments
+    /// * `fid` - The wallet's FID
+    /// * `auth_address` - The Ethereum address to authorize
+    ///
+    /// # Returns
+    /// * `Result<ContractResult<()>>` - Success or a contract error
+    pub async fn add_auth_address(
+        &self,
+        fid: Fid,
+        auth_address: Address,
+    ) -> Result<ContractResult<()>> {
+        policy::current().check_transaction(Some(fid), U256::zero())?;
+        if !self.auth_address_supported().await? {
+            return Ok(ContractResult::Error(
+                "KeyRegistry has no validator for auth address keys (key type 2)".to_string(),
+            ));
+        }
+        self.register_signer_key(
+            fid,
+            AUTH_ADDRESS_KEY_TYPE,
+            auth_address.as_bytes().to_vec(),
+            SIGNED_KEY_REQUEST_METADATA_TYPE,
+            Vec::new(),
+        )
+        .await
+    }
+
+    /// Remove an auth address from a FID with a `removeFor` authorization
+    ///
+    /// The wallet of this client signs the EIP-712 `Remove` message and must be
+    /// the FID's custody address; `payer` (or this client) sends the transaction.
+    ///
+    /// # Arguments
+    /// * `fid` - The FID to remove the auth address from
+    /// * `fid_owner` - Custody address of the FID
+    /// * `auth_address` - The auth address to remove
+    /// * `payer` - Optional client whose wallet pays gas
+    ///
+    /// # Returns
+    /// * `Result<ContractResult<TransactionReceipt>>` - The receipt or a contract error
+    pub async fn remove_auth_address(
+        &self,
+        fid: Fid,
+        fid_owner: Address,
+        auth_address: Address,
+        payer: Option<&FarcasterContractClient>,
+    ) -> Result<ContractResult<TransactionReceipt>> {
+        policy::current().check_transaction(Some(fid), U256::zero())?;
+        let key = auth_address.as_bytes().to_vec();
+        let deadline = std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)?
+            .as_secs()
+            + 3600;
+        let signature = self
+            .create_remove_for_signature(fid_owner, &key, deadline)
+            .await?;
+
+        payer
+            .unwrap_or(self)
+            .key_registry
+            .remove_for(fid, fid_owner, key, deadline, signature)
+            .await
+    }
+
+    /// Create the EIP-712 signature for `KeyRegistry.removeFor`
+    ///
+    /// The type hash, nonce and domain are read from the KeyRegistry itself,
+    /// so the digest matches whatever the deployed contract verifies.
+    ///
+    /// # Arguments
+    /// * `fid_owner` - Custody address of the FID
+    /// * `key` - Key bytes being removed
+    /// * `deadline` - Signature expiry (unix seconds)
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>>` - The 65-byte signature
+    pub async fn create_remove_for_signature(
+        &self,
+        fid_owner: Address,
+        key: &[u8],
+        deadline: u64,
+    ) -> Result<Vec<u8>> {
+        let wallet = self
+            .wallet
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No wallet available"))?;
+
+        let registry = self.key_registry.contract();
+        let type_hash = registry.remove_typehash().call().await?;
+        let nonce = registry.nonces(fid_owner).call().await?;
+        let struct_hash = remove_struct_hash(type_hash, fid_owner, key, nonce, deadline);
+        let digest = registry.hash_typed_data_v4(struct_hash).call().await?;
+
+        let signature = wallet.sign_hash(H256::from(digest))?;
+        audit::record_eth_signature(
+            Signer::address(wallet.as_ref()),
+            "eip712:Remove",
+            H256::from(digest),
+        );
+        Ok(signature.to_vec())
+    }
+}
+
+/// `hashStruct` of a KeyRegistry `Remove(address owner,bytes key,uint256 nonce,uint256 deadline)`
+fn remove_struct_hash(
+    type_hash: [u8; 32],
+    owner: Address,
+    key: &[u8],
+    nonce: U256,
+    deadline: u64,
+) -> [u8; 32] {
+    keccak256(encode(&[
+        Token::FixedBytes(type_hash.to_vec()),
+        Token::Address(owner),
+        Token::FixedBytes(keccak256(key).to_vec()),
+        Token::Uint(nonce),
+        Token::Uint(U256::from(deadline)),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::signers::LocalWallet;
+
+    use super::*;
+    use crate::core::policy::install_for_test;
+    use crate::core::policy::only_fid;
+    use crate::farcaster::contracts::types::ContractAddresses;
+
+    /// A client with a wallet whose RPC endpoint refuses connections
+    fn offline_client() -> FarcasterContractClient {
+        FarcasterContractClient::new_with_wallet(
+            "http://127.0.0.1:1".to_string(),
+            ContractAddresses::default(),
+            LocalWallet::new(&mut rand::thread_rng()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_remove_struct_hash_commits_to_key() {
+        let type_hash = keccak256("Remove(address owner,bytes key,uint256 nonce,uint256 deadline)");
+        let owner = Address::repeat_byte(0x11);
+        let a = remove_struct_hash(type_hash, owner, &[0xaa; 20], U256::zero(), 100);
+        let b = remove_struct_hash(type_hash, owner, &[0xbb; 20], U256::zero(), 100);
+        let c = remove_struct_hash(type_hash, owner, &[0xaa; 20], U256::one(), 100);
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[tokio::test]
+    async fn test_add_auth_address_checks_policy_first() {
+        let _policy = install_for_test(only_fid(3));
+        let error = offline_client()
+            .add_auth_address(4, Address::repeat_byte(0x22))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Blocked by policy"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_remove_auth_address_checks_policy_first() {
+        let _policy = install_for_test(only_fid(3));
+        let error = offline_client()
+            .remove_auth_address(
+                4,
+                Address::repeat_byte(0x11),
+                Address::repeat_byte(0x22),
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Blocked by policy"), "{error}");
+    }
+}
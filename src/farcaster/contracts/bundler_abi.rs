@@ -11,6 +11,7 @@ use ethers::providers::Provider;
 use ethers::types::Address;
 use ethers::types::U256;
 
+use crate::farcaster::contracts::errors::describe_contract_error;
 use crate::farcaster::contracts::generated::bundler_bindings::Bundler as BundlerContract;
 use crate::farcaster::contracts::types::ContractResult;
 
@@ -36,7 +37,10 @@ impl BundlerAbi {
     pub async fn price(&self, extra_storage: u64) -> Result<ContractResult<U256>> {
         match self.contract.price(extra_storage.into()).call().await {
             Ok(price) => Ok(ContractResult::Success(price)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -44,7 +48,10 @@ impl BundlerAbi {
     pub async fn id_gateway(&self) -> Result<ContractResult<Address>> {
         match self.contract.id_gateway().call().await {
             Ok(address) => Ok(ContractResult::Success(address)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -52,7 +59,10 @@ impl BundlerAbi {
     pub async fn key_gateway(&self) -> Result<ContractResult<Address>> {
         match self.contract.key_gateway().call().await {
             Ok(address) => Ok(ContractResult::Success(address)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 }
@@ -1,4 +1,3 @@
-use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
@@ -9,13 +8,23 @@ use ethers::providers::Http;
 use ethers::providers::Provider;
 use ethers::signers::LocalWallet;
 use ethers::signers::Signer;
+use ethers::types::transaction::eip2718::TypedTransaction;
 use ethers::types::Address;
 use ethers::types::TransactionRequest;
 use ethers::types::H256;
 use ethers::types::U256;
 use hex;
 
+use crate::core::audit;
+use crate::core::policy;
+use crate::core::utils::finality;
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
+use crate::farcaster::contracts::adapters::Adapters;
+use crate::farcaster::contracts::adapters::Eip712Domain;
 use crate::farcaster::contracts::bundler_abi::BundlerAbi;
+use crate::farcaster::contracts::errors::describe_contract_error;
+use crate::farcaster::contracts::errors::simulate_call;
 use crate::farcaster::contracts::id_gateway_abi::IdGatewayAbi;
 use crate::farcaster::contracts::id_registry_abi::IdRegistryAbi;
 use crate::farcaster::contracts::key_gateway_abi::KeyGatewayAbi;
@@ -28,10 +37,29 @@ use crate::farcaster::contracts::types::ContractResult;
 use crate::farcaster::contracts::types::Fid;
 use crate::farcaster::contracts::types::FidInfo;
 use crate::farcaster::contracts::types::NetworkStatus;
+use crate::farcaster::contracts::types::PriceChange;
+use crate::farcaster::contracts::types::RentRecord;
+use crate::farcaster::contracts::types::StorageInfo;
+use crate::farcaster::contracts::types::TxCost;
+
+/// Gas limit of a plain ETH transfer
+const TRANSFER_GAS: u64 = 21_000;
 
 // Global nonce registry shared across all FarcasterContractClient instances
 static GLOBAL_NONCE_REGISTRY: OnceLock<Arc<tokio::sync::Mutex<NonceRegistry>>> = OnceLock::new();
 
+/// Application identity that requests signer keys on behalf of users
+///
+/// Its FID becomes the `requestFid` of the SignedKeyRequestMetadata and its
+/// custody wallet signs the key request, so clients attribute the signer to
+/// the app rather than to the user's own FID. The user's custody wallet still
+/// authorizes the `addFor` call itself.
+#[derive(Clone)]
+pub struct AppIdentity {
+    pub fid: Fid,
+    pub wallet: Arc<LocalWallet>,
+}
+
 /// Main client for interacting with Farcaster contracts on Optimism
 #[derive(Clone)]
 pub struct FarcasterContractClient {
@@ -46,12 +74,14 @@ pub struct FarcasterContractClient {
     pub signed_key_request_validator: SignedKeyRequestValidatorAbi<Provider<Http>>,
     pub wallet: Option<Arc<LocalWallet>>,
     pub nonce_registry: Arc<tokio::sync::Mutex<NonceRegistry>>,
+    app_identity: Option<AppIdentity>,
+    adapters: Arc<tokio::sync::OnceCell<Adapters>>,
 }
 
 impl FarcasterContractClient {
     /// Create a new FarcasterContractClient with custom addresses
     pub fn new(rpc_url: String, addresses: ContractAddresses) -> Result<Self> {
-        let provider = Arc::new(Provider::<Http>::try_from(rpc_url)?);
+        let provider = Arc::new(crate::core::utils::http::provider(&rpc_url)?);
 
         // Initialize global nonce registry if not already initialized
         let nonce_registry = GLOBAL_NONCE_REGISTRY.get_or_init(|| {
@@ -84,6 +114,8 @@ impl FarcasterContractClient {
             signed_key_request_validator,
             wallet: None,
             nonce_registry: nonce_registry.clone(),
+            app_identity: None,
+            adapters: Arc::new(tokio::sync::OnceCell::new()),
         })
     }
 
@@ -120,15 +152,84 @@ impl FarcasterContractClient {
         self.wallet.as_ref().map(|w| w.address())
     }
 
+    /// Request signer keys as an app FID instead of the user's FID
+    pub fn with_app_identity(mut self, app_identity: AppIdentity) -> Self {
+        self.app_identity = Some(app_identity);
+        self
+    }
+
+    /// Get the app identity signer key requests are made as (if any)
+    pub fn app_identity(&self) -> Option<&AppIdentity> {
+        self.app_identity.as_ref()
+    }
+
+    /// Check that the app identity's wallet is the custody address of its FID
+    ///
+    /// The SignedKeyRequestValidator rejects requests signed by any other
+    /// address, so this is checked before anything is signed.
+    pub async fn verify_app_identity(&self) -> Result<()> {
+        let Some(app) = &self.app_identity else {
+            return Ok(());
+        };
+        match self.get_fid_custody(app.fid).await? {
+            Some(custody) if custody == app.wallet.address() => Ok(()),
+            Some(custody) => Err(anyhow::anyhow!(
+                "App wallet {:?} is not the custody address {:?} of app FID {}",
+                app.wallet.address(),
+                custody,
+                app.fid
+            )),
+            None => Err(anyhow::anyhow!("App FID {} not found", app.fid)),
+        }
+    }
+
+    /// The FID, address and wallet that request a signer key for `fid`
+    ///
+    /// This is the app identity when one is set, otherwise the user's FID and
+    /// its owner signing with the client's wallet.
+    fn key_requester(&self, fid: u64, fid_owner: Address) -> Result<(u64, Address, &LocalWallet)> {
+        if let Some(app) = &self.app_identity {
+            return Ok((app.fid, app.wallet.address(), app.wallet.as_ref()));
+        }
+        let wallet = self
+            .wallet
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No wallet available"))?;
+        Ok((fid, fid_owner, wallet.as_ref()))
+    }
+
     /// Get contract addresses
     pub fn addresses(&self) -> &ContractAddresses {
         &self.addresses
     }
 
+    /// Adapters for the deployed contract versions, detected on first use
+    pub async fn adapters(&self) -> Result<&Adapters> {
+        self.adapters
+            .get_or_try_init(|| Adapters::detect(self))
+            .await
+    }
+
     // ===== HIGH-LEVEL FARCACTER FUNCTIONS =====
 
     /// Get comprehensive FID information
+    ///
+    /// All five reads go out in one Multicall3 request; chains without
+    /// Multicall3 (e.g. a fresh, non-forked Anvil node) fall back to
+    /// sequential calls.
     pub async fn get_fid_info(&self, fid: Fid) -> Result<FidInfo> {
+        match self.get_fid_info_batch(&[fid]).await {
+            Ok(mut infos) if infos.len() == 1 => Ok(infos.remove(0)),
+            Ok(_) => self.get_fid_info_sequential(fid).await,
+            Err(e) => {
+                tracing::debug!("Multicall3 FID lookup failed, reading sequentially: {e}");
+                self.get_fid_info_sequential(fid).await
+            }
+        }
+    }
+
+    /// Get FID information with one `eth_call` per field
+    async fn get_fid_info_sequential(&self, fid: Fid) -> Result<FidInfo> {
         // Get custody and recovery addresses
         let custody_result = self.id_registry.custody_of(fid).await?;
         let recovery_result = self.id_registry.recovery_of(fid).await?;
@@ -197,6 +298,67 @@ impl FarcasterContractClient {
         }
     }
 
+    /// Get global storage economics from the StorageRegistry
+    pub async fn get_storage_info(&self) -> Result<StorageInfo> {
+        let registry = &self.storage_registry;
+        let unit_price = match registry.unit_price().await? {
+            ContractResult::Success(price) => price,
+            ContractResult::Error(e) => {
+                return Err(anyhow::anyhow!("Failed to get storage unit price: {}", e))
+            }
+        };
+
+        Ok(StorageInfo {
+            unit_price,
+            usd_unit_price: registry.usd_unit_price().await?.unwrap_or(U256::zero()),
+            eth_usd_price: registry.eth_usd_price().await?.unwrap_or(U256::zero()),
+            max_units: registry.max_units().await?.unwrap_or(0),
+            rented_units: registry.rented_units().await?.unwrap_or(0),
+            deprecation_timestamp: registry.deprecation_timestamp().await?.unwrap_or(0),
+            paused: registry.paused().await?.unwrap_or(false),
+        })
+    }
+
+    /// Get the storage rentals of a FID from StorageRegistry `Rent` events
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `from_block` - First block to search
+    ///
+    /// # Returns
+    /// * `Result<Vec<RentRecord>>` - Rentals in block order, or an error
+    pub async fn get_rent_history(&self, fid: Fid, from_block: u64) -> Result<Vec<RentRecord>> {
+        match self
+            .storage_registry
+            .rent_events(fid, from_block, None)
+            .await?
+        {
+            ContractResult::Success(records) => Ok(records),
+            ContractResult::Error(e) => Err(anyhow::anyhow!(
+                "Failed to get storage rentals for FID {}: {}",
+                fid,
+                e
+            )),
+        }
+    }
+
+    /// Get the storage unit price changes from StorageRegistry `SetPrice` events
+    ///
+    /// # Arguments
+    /// * `from_block` - First block to search
+    ///
+    /// # Returns
+    /// * `Result<Vec<PriceChange>>` - Price changes in block order, or an error
+    pub async fn get_price_history(&self, from_block: u64) -> Result<Vec<PriceChange>> {
+        match self.storage_registry.price_events(from_block, None).await? {
+            ContractResult::Success(changes) => Ok(changes),
+            ContractResult::Error(e) => Err(anyhow::anyhow!(
+                "Failed to get storage price history: {}",
+                e
+            )),
+        }
+    }
+
     /// Get network status information
     pub async fn get_network_status(&self) -> Result<NetworkStatus> {
         let chain_id = self.provider.get_chainid().await?;
@@ -230,24 +392,33 @@ impl FarcasterContractClient {
         let chain_id = self.provider.get_chainid().await?;
         let wallet_with_chain_id = wallet.as_ref().clone().with_chain_id(chain_id.as_u64());
 
+        let signer_middleware = SignerMiddleware::new(self.provider.clone(), wallet_with_chain_id);
+
+        // Encode register for the deployed IdGateway version
+        let call = self
+            .adapters()
+            .await?
+            .id_gateway
+            .register(recovery, None)
+            .bind::<_, (U256, U256)>(self.addresses.id_gateway, Arc::new(signer_middleware))?
+            .value(price);
+        policy::current().check_transaction(None, price)?;
+        if let Err(e) = simulate_call(&call).await {
+            return Ok(ContractResult::Error(e));
+        }
+
         // Get current nonce to avoid nonce conflicts
         let mut registry = self.nonce_registry.lock().await;
         let nonce = registry.get_next_nonce(wallet.address()).await?;
         println!("   📝 Using nonce: {}", nonce);
 
-        let signer_middleware = SignerMiddleware::new(self.provider.clone(), wallet_with_chain_id);
-
-        // Create the contract instance with signer middleware
-        let contract = self.id_gateway.contract().clone();
-        let wallet_contract = contract.connect(Arc::new(signer_middleware));
-
-        // Call register function using ethers call method with explicit nonce
-        let call = wallet_contract.method::<_, (U256, U256)>("register", recovery)?;
-        match call.value(price).nonce(nonce).send().await {
+        match call.nonce(nonce).send().await {
             Ok(tx) => {
+                audit::record_transaction(Some(wallet.address()), "register", tx.tx_hash());
                 let receipt = tx.await?;
                 match receipt {
                     Some(receipt) => {
+                        let receipt = finality::confirm(&self.provider, receipt).await?;
                         // Try to extract FID from transaction receipt
                         match self.extract_fid_from_receipt(&receipt) {
                             Ok(fid) => {
@@ -278,7 +449,10 @@ impl FarcasterContractClient {
                     )),
                 }
             }
-            Err(e) => Ok(ContractResult::Error(format!("Registration failed: {}", e))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Registration failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -309,25 +483,33 @@ impl FarcasterContractClient {
         let chain_id = self.provider.get_chainid().await?;
         let wallet_with_chain_id = wallet.as_ref().clone().with_chain_id(chain_id.as_u64());
 
+        let signer_middleware = SignerMiddleware::new(self.provider.clone(), wallet_with_chain_id);
+
+        // Encode register with extra storage for the deployed IdGateway version
+        let call = self
+            .adapters()
+            .await?
+            .id_gateway
+            .register(recovery, Some(extra_storage))
+            .bind::<_, (U256, U256)>(self.addresses.id_gateway, Arc::new(signer_middleware))?
+            .value(price);
+        policy::current().check_transaction(None, price)?;
+        if let Err(e) = simulate_call(&call).await {
+            return Ok(ContractResult::Error(e));
+        }
+
         // Get current nonce to avoid nonce conflicts
         let mut registry = self.nonce_registry.lock().await;
         let nonce = registry.get_next_nonce(wallet.address()).await?;
         println!("   📝 Using nonce: {}", nonce);
 
-        let signer_middleware = SignerMiddleware::new(self.provider.clone(), wallet_with_chain_id);
-
-        // Create the contract instance with signer middleware
-        let contract = self.id_gateway.contract().clone();
-        let wallet_contract = contract.connect(Arc::new(signer_middleware));
-
-        // Call register function with extra storage using ethers call method with explicit nonce
-        let call = wallet_contract
-            .method::<_, (U256, U256)>("register", (recovery, U256::from(extra_storage)))?;
-        match call.value(price).nonce(nonce).send().await {
+        match call.nonce(nonce).send().await {
             Ok(tx) => {
+                audit::record_transaction(Some(wallet.address()), "register", tx.tx_hash());
                 let receipt = tx.await?;
                 match receipt {
                     Some(receipt) => {
+                        let receipt = finality::confirm(&self.provider, receipt).await?;
                         // Parse the return values from the transaction receipt
                         let fid = self.extract_fid_from_receipt(&receipt)?;
                         let overpayment = U256::zero(); // For now, return 0 as overpayment
@@ -338,7 +520,10 @@ impl FarcasterContractClient {
                     )),
                 }
             }
-            Err(e) => Ok(ContractResult::Error(format!("Registration failed: {}", e))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Registration failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -356,24 +541,33 @@ impl FarcasterContractClient {
         let chain_id = self.provider.get_chainid().await?;
         let wallet_with_chain_id = wallet.as_ref().clone().with_chain_id(chain_id.as_u64());
 
+        let signer_middleware = SignerMiddleware::new(self.provider.clone(), wallet_with_chain_id);
+
+        // Encode rent for the deployed StorageRegistry version
+        let call = self
+            .adapters()
+            .await?
+            .storage_registry
+            .rent(fid, units)
+            .bind::<_, U256>(self.addresses.storage_registry, Arc::new(signer_middleware))?
+            .value(price);
+        policy::current().check_transaction(Some(fid), price)?;
+        if let Err(e) = simulate_call(&call).await {
+            return Ok(ContractResult::Error(e));
+        }
+
         // Get current nonce to avoid nonce conflicts
         let mut registry = self.nonce_registry.lock().await;
         let nonce = registry.get_next_nonce(wallet.address()).await?;
         println!("   📝 Using nonce: {}", nonce);
 
-        let signer_middleware = SignerMiddleware::new(self.provider.clone(), wallet_with_chain_id);
-
-        // Create the contract instance with signer middleware
-        let contract = self.storage_registry.contract().clone();
-        let wallet_contract = contract.connect(Arc::new(signer_middleware));
-
-        // Call rent function using ethers call method with explicit nonce
-        let call = wallet_contract.method::<_, U256>("rent", (fid, units as u32))?;
-        match call.value(price).nonce(nonce).send().await {
+        match call.nonce(nonce).send().await {
             Ok(tx) => {
+                audit::record_transaction(Some(wallet.address()), "rent", tx.tx_hash());
                 let receipt = tx.await?;
                 match receipt {
                     Some(receipt) => {
+                        let receipt = finality::confirm(&self.provider, receipt).await?;
                         // Parse the return values from the transaction receipt
                         let overpayment = self.extract_overpayment_from_receipt(&receipt)?;
                         Ok(ContractResult::Success(overpayment))
@@ -385,7 +579,7 @@ impl FarcasterContractClient {
             }
             Err(e) => Ok(ContractResult::Error(format!(
                 "Storage rental failed: {}",
-                e
+                describe_contract_error(&e)
             ))),
         }
     }
@@ -408,6 +602,22 @@ impl FarcasterContractClient {
             .clone()
             .with_chain_id(chain_id.as_u64());
 
+        let signer_middleware =
+            SignerMiddleware::new(self.provider.clone(), payment_wallet_with_chain_id);
+
+        // Encode rent for the deployed StorageRegistry version
+        let call = self
+            .adapters()
+            .await?
+            .storage_registry
+            .rent(fid, units)
+            .bind::<_, U256>(self.addresses.storage_registry, Arc::new(signer_middleware))?
+            .value(price);
+        policy::current().check_transaction(Some(fid), price)?;
+        if let Err(e) = simulate_call(&call).await {
+            return Ok(ContractResult::Error(e));
+        }
+
         // Get current nonce to avoid nonce conflicts
         let mut registry = self.nonce_registry.lock().await;
         let nonce = registry.get_next_nonce(payment_wallet.address()).await?;
@@ -417,20 +627,13 @@ impl FarcasterContractClient {
             payment_wallet.address()
         );
 
-        let signer_middleware =
-            SignerMiddleware::new(self.provider.clone(), payment_wallet_with_chain_id);
-
-        // Create the contract instance with payment wallet signer middleware
-        let contract = self.storage_registry.contract().clone();
-        let wallet_contract = contract.connect(Arc::new(signer_middleware));
-
-        // Call rent function using ethers call method with explicit nonce
-        let call = wallet_contract.method::<_, U256>("rent", (fid, units as u32))?;
-        match call.value(price).nonce(nonce).send().await {
+        match call.nonce(nonce).send().await {
             Ok(tx) => {
+                audit::record_transaction(Some(payment_wallet.address()), "rent", tx.tx_hash());
                 let receipt = tx.await?;
                 match receipt {
                     Some(receipt) => {
+                        let receipt = finality::confirm(&self.provider, receipt).await?;
                         // Parse the return values from the transaction receipt
                         let overpayment = self.extract_overpayment_from_receipt(&receipt)?;
                         Ok(ContractResult::Success(overpayment))
@@ -442,7 +645,7 @@ impl FarcasterContractClient {
             }
             Err(e) => Ok(ContractResult::Error(format!(
                 "Storage rental failed: {}",
-                e
+                describe_contract_error(&e)
             ))),
         }
     }
@@ -569,63 +772,118 @@ impl FarcasterContractClient {
         }
     }
 
-    /// Fund a wallet using NonceManager for nonce management
-    pub async fn fund_wallet(&self, target_address: Address, amount: U256) -> Result<H256> {
-        let funder_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"; // Account 0
-        let funder_wallet = LocalWallet::from_str(funder_key)?;
-        let funder_address = funder_wallet.address();
+    /// Get the ETH balance of an address
+    pub async fn get_eth_balance(&self, address: Address) -> Result<U256> {
+        Ok(self.provider.get_balance(address, None).await?)
+    }
+
+    /// Estimate the network fee of [`Self::transfer_eth`] at the current gas price
+    pub async fn estimate_transfer_fee(&self) -> Result<U256> {
+        Ok(self.provider.get_gas_price().await? * TRANSFER_GAS)
+    }
+
+    /// Get what a mined transaction cost its sender
+    ///
+    /// # Arguments
+    /// * `tx_hash` - The transaction hash
+    ///
+    /// # Returns
+    /// * `Result<Option<TxCost>>` - The cost, `None` if the transaction is unknown, or an error
+    pub async fn get_transaction_cost(&self, tx_hash: H256) -> Result<Option<TxCost>> {
+        let Some(tx) = self.provider.get_transaction(tx_hash).await? else {
+            return Ok(None);
+        };
+        let Some(receipt) = self.provider.get_transaction_receipt(tx_hash).await? else {
+            return Ok(None);
+        };
+        let gas_price = receipt
+            .effective_gas_price
+            .or(tx.gas_price)
+            .unwrap_or_default();
+        let l2_fee = receipt.gas_used.unwrap_or_default() * gas_price;
+        // Optimism receipts carry the L1 data fee as an extra field
+        let l1_fee = receipt
+            .other
+            .get_deserialized::<U256>("l1Fee")
+            .and_then(|fee| fee.ok())
+            .unwrap_or_default();
+        Ok(Some(TxCost {
+            from: tx.from,
+            value: tx.value,
+            fee: l2_fee + l1_fee,
+        }))
+    }
+
+    /// Transfer ETH from the given wallet using NonceManager for nonce management
+    pub async fn transfer_eth(
+        &self,
+        from_wallet: &LocalWallet,
+        target_address: Address,
+        amount: U256,
+    ) -> Result<H256> {
+        let from_address = from_wallet.address();
         let chain_id = self.provider.get_chainid().await?;
-        let funder_with_chain_id = funder_wallet.with_chain_id(chain_id.as_u64());
+        let from_with_chain_id = from_wallet.clone().with_chain_id(chain_id.as_u64());
 
-        // Get next nonce for funder using NonceManager
+        policy::current().check_value(amount)?;
+
+        // Simulate first so an underfunded sender fails before a nonce is reserved
+        let simulation_tx: TypedTransaction = TransactionRequest::new()
+            .from(from_address)
+            .to(target_address)
+            .value(amount)
+            .into();
+        if let Err(e) = self.provider.call(&simulation_tx, None).await {
+            return Err(anyhow::anyhow!("Transfer simulation failed: {}", e));
+        }
+
+        // Get next nonce for the sender using NonceManager
         let mut registry = self.nonce_registry.lock().await;
-        let nonce = registry.get_next_nonce(funder_address).await?;
-        println!("   🔧 Using nonce {} for funder {}", nonce, funder_address);
+        let nonce = registry.get_next_nonce(from_address).await?;
+        println!("   🔧 Using nonce {} for sender {}", nonce, from_address);
 
-        let fund_tx = TransactionRequest::new()
+        let transfer_tx = TransactionRequest::new()
             .to(target_address)
             .value(amount)
-            .gas(21000)
+            .gas(TRANSFER_GAS)
             .nonce(nonce);
 
-        let signer_middleware = SignerMiddleware::new(self.provider.clone(), funder_with_chain_id);
-        let pending_tx = signer_middleware.send_transaction(fund_tx, None).await?;
+        let signer_middleware = SignerMiddleware::new(self.provider.clone(), from_with_chain_id);
+        let pending_tx = signer_middleware
+            .send_transaction(transfer_tx, None)
+            .await?;
+        audit::record_transaction(Some(from_address), "transfer", pending_tx.tx_hash());
         let receipt = pending_tx.await?;
 
         match receipt {
             Some(receipt) => {
-                println!("   ✅ Wallet funded successfully!");
+                let receipt = finality::confirm(&self.provider, receipt).await?;
+                println!("   ✅ Transfer confirmed!");
                 println!("   Transaction Hash: {:?}", receipt.transaction_hash);
                 Ok(receipt.transaction_hash)
             }
-            None => Err(anyhow::anyhow!("Funding transaction failed")),
+            None => Err(anyhow::anyhow!("Transfer transaction failed")),
         }
     }
 
-    /// Wait for transaction confirmation to ensure nonce is updated
     /// Register a signer key for the current wallet's FID (requires wallet)
+    ///
+    /// `fid` must be the FID whose custody address is the wallet; the policy
+    /// is checked for it before anything is signed or sent.
     pub async fn register_signer_key(
         &self,
+        fid: u64,
         key_type: u32,
         key: Vec<u8>,
         metadata_type: u8,
         _metadata: Vec<u8>,
     ) -> Result<ContractResult<()>> {
+        policy::current().check_transaction(Some(fid), U256::zero())?;
         let wallet = self
             .wallet
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Wallet required for signer registration"))?;
 
-        // Get the FID for the current wallet address
-        let fid = match self.address_has_fid(wallet.address()).await? {
-            Some(fid) => fid,
-            None => {
-                return Ok(ContractResult::Error(
-                    "Wallet address does not have a FID".to_string(),
-                ))
-            }
-        };
-
         // Create deadline (1 hour from now)
         let deadline = std::time::SystemTime::now()
             .duration_since(std::time::SystemTime::UNIX_EPOCH)?
@@ -641,18 +899,14 @@ impl FarcasterContractClient {
             None => return Ok(ContractResult::Error("FID not found".to_string())),
         };
 
-        // Assert that the current wallet is the FID owner
-        let wallet = self
-            .wallet
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("No wallet available"))?;
-        assert_eq!(
-            wallet.address(),
-            fid_owner,
-            "Current wallet address {} must be the FID owner {} to sign the request",
-            wallet.address(),
-            fid_owner
-        );
+        // The current wallet must be the FID owner to sign the request
+        if wallet.address() != fid_owner {
+            anyhow::bail!(
+                "Current wallet address {} must be the FID owner {} to sign the request",
+                wallet.address(),
+                fid_owner
+            );
+        }
         println!("   ✅ Wallet address matches FID owner - can proceed with signing");
 
         // Create EIP-712 signature for SignedKeyRequest using SignedKeyRequestValidator
@@ -692,6 +946,7 @@ impl FarcasterContractClient {
         let result = self
             .key_gateway
             .add_for(
+                fid,
                 fid_owner,
                 key_type,
                 key,
@@ -718,6 +973,8 @@ impl FarcasterContractClient {
     }
 
     /// Submit signer registration with pre-generated signatures (for third-party gas payment)
+    ///
+    /// The policy is checked for `fid` before anything is sent.
     #[allow(clippy::too_many_arguments)]
     pub async fn submit_signer_registration_with_signatures(
         &self,
@@ -730,6 +987,7 @@ impl FarcasterContractClient {
         deadline: u64,
         add_for_signature: Vec<u8>,
     ) -> Result<ContractResult<()>> {
+        policy::current().check_transaction(Some(fid), U256::zero())?;
         println!("🔑 Submitting signer registration with pre-generated signatures for FID owner {} (FID: {})", fid_owner_address, fid);
 
         let wallet = self
@@ -770,6 +1028,7 @@ impl FarcasterContractClient {
         let result = self
             .key_gateway
             .add_for(
+                fid,
                 fid_owner_address,
                 key_type,
                 key,
@@ -830,7 +1089,9 @@ impl FarcasterContractClient {
         };
 
         // Create the EIP-712 typed data structure for Add
+        let domain = self.adapters().await?.key_gateway.eip712_domain();
         let typed_data = self.create_add_typed_data(
+            domain,
             fid_owner,
             key_type,
             key,
@@ -843,31 +1104,35 @@ impl FarcasterContractClient {
         )?;
 
         // Sign the typed data using EIP-712
-        let signature = wallet.sign_typed_data(&typed_data).await?;
+        let signature = {
+            let _timer = metrics::start(Phase::Signing, "EIP-712 typed data");
+            wallet.sign_typed_data(&typed_data).await?
+        };
+        audit::record_typed_data(wallet.address(), &typed_data);
 
         // Return the signature as bytes
         Ok(signature.to_vec())
     }
 
     /// Create EIP-712 signature for SignedKeyRequest using SignedKeyRequestValidator
+    ///
+    /// With an app identity the request is made for the app FID and signed
+    /// by the app's wallet.
     pub async fn create_signed_key_request_signature(
         &self,
         fid: u64,
-        _fid_owner: ethers::types::Address,
+        fid_owner: ethers::types::Address,
         public_key: &[u8],
         deadline: u64,
     ) -> Result<Vec<u8>> {
-        let wallet = self
-            .wallet
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("No wallet available"))?;
+        let (fid, _, wallet) = self.key_requester(fid, fid_owner)?;
 
         // Get chain ID and contract address
         let chain_id = self.provider.get_chainid().await?.as_u64();
         let validator_address = self.addresses.signed_key_request_validator;
 
         // Create the EIP-712 typed data structure for SignedKeyRequest
-        let typed_data = self.create_signed_key_request_typed_data(
+        let typed_data = Self::create_signed_key_request_typed_data(
             fid,
             public_key,
             deadline,
@@ -876,13 +1141,20 @@ impl FarcasterContractClient {
         )?;
 
         // Sign the typed data using EIP-712
-        let signature = wallet.sign_typed_data(&typed_data).await?;
+        let signature = {
+            let _timer = metrics::start(Phase::Signing, "EIP-712 typed data");
+            wallet.sign_typed_data(&typed_data).await?
+        };
+        audit::record_typed_data(wallet.address(), &typed_data);
 
         // Return the signature as bytes
         Ok(signature.to_vec())
     }
 
     /// Create SignedKeyRequestMetadata using the signature
+    ///
+    /// With an app identity the app FID and its custody address are the
+    /// requester.
     pub async fn create_signed_key_request_metadata(
         &self,
         fid: u64,
@@ -896,10 +1168,12 @@ impl FarcasterContractClient {
 
         use crate::farcaster::contracts::generated::signedkeyrequestvalidator_bindings::SignedKeyRequestMetadata;
 
+        let (request_fid, request_signer, _) = self.key_requester(fid, fid_owner)?;
+
         // Create the metadata struct with the provided signature
         let metadata_struct = SignedKeyRequestMetadata {
-            request_fid: U256::from(fid),
-            request_signer: fid_owner,
+            request_fid: U256::from(request_fid),
+            request_signer,
             signature: Bytes::from(signature),
             deadline: U256::from(deadline),
         };
@@ -915,8 +1189,7 @@ impl FarcasterContractClient {
     }
 
     /// Create EIP-712 typed data for SignedKeyRequest
-    fn create_signed_key_request_typed_data(
-        &self,
+    pub(crate) fn create_signed_key_request_typed_data(
         fid: u64,
         public_key: &[u8],
         deadline: u64,
@@ -1010,6 +1283,7 @@ impl FarcasterContractClient {
     #[allow(clippy::too_many_arguments)]
     fn create_add_typed_data(
         &self,
+        key_gateway_domain: Eip712Domain,
         fid_owner: ethers::types::Address,
         key_type: u32,
         key: &[u8],
@@ -1026,10 +1300,10 @@ impl FarcasterContractClient {
         use ethers::types::transaction::eip712::Eip712DomainType;
         use ethers::types::transaction::eip712::TypedData;
 
-        // Domain separator for Farcaster KeyGateway
+        // Domain separator of the deployed KeyGateway version
         let domain = EIP712Domain {
-            name: Some("Farcaster KeyGateway".to_string()),
-            version: Some("1".to_string()),
+            name: Some(key_gateway_domain.name.to_string()),
+            version: Some(key_gateway_domain.version.to_string()),
             chain_id: Some(ethers::types::U256::from(chain_id)),
             verifying_contract: Some(key_gateway_address),
             salt: None,
@@ -1131,3 +1405,79 @@ impl FarcasterContractClient {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::policy::install_for_test;
+    use crate::core::policy::only_fid;
+
+    fn wallet(key: &str) -> LocalWallet {
+        key.parse().unwrap()
+    }
+
+    /// A client with a wallet whose RPC endpoint refuses connections
+    fn offline_client() -> FarcasterContractClient {
+        FarcasterContractClient::new_with_wallet(
+            "http://127.0.0.1:1".to_string(),
+            ContractAddresses::default(),
+            LocalWallet::new(&mut rand::thread_rng()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_key_requester_uses_app_identity() {
+        let user = wallet("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80");
+        let app = wallet("59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d");
+        let client = FarcasterContractClient::new_with_wallet(
+            "http://127.0.0.1:8545".to_string(),
+            ContractAddresses::default(),
+            user.clone(),
+        )
+        .unwrap();
+
+        let (fid, owner, signer) = client.key_requester(12345, user.address()).unwrap();
+        assert_eq!((fid, owner), (12345, user.address()));
+        assert_eq!(signer.address(), user.address());
+
+        let client = client.with_app_identity(AppIdentity {
+            fid: 9152,
+            wallet: Arc::new(app.clone()),
+        });
+        let (fid, owner, signer) = client.key_requester(12345, user.address()).unwrap();
+        assert_eq!((fid, owner), (9152, app.address()));
+        assert_eq!(signer.address(), app.address());
+        // The user's wallet still authorizes the addFor call
+        assert_eq!(client.wallet_address(), Some(user.address()));
+    }
+
+    #[tokio::test]
+    async fn test_register_signer_key_checks_policy_first() {
+        let _policy = install_for_test(only_fid(3));
+        let error = offline_client()
+            .register_signer_key(4, 1, vec![9; 32], 1, vec![])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Blocked by policy"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_submit_signer_registration_checks_policy_first() {
+        let _policy = install_for_test(only_fid(3));
+        let error = offline_client()
+            .submit_signer_registration_with_signatures(
+                Address::repeat_byte(0x22),
+                4,
+                1,
+                vec![9; 32],
+                1,
+                vec![],
+                1,
+                vec![0; 65],
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Blocked by policy"), "{error}");
+    }
+}
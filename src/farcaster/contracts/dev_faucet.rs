@@ -0,0 +1,108 @@
+//! Development faucet for local networks
+//!
+//! Funding test wallets from a well-known key is only meaningful on local
+//! development chains such as Anvil. The faucet refuses to send anything unless
+//! the connected chain id is one of [`LOCAL_CHAIN_IDS`], so production code paths
+//! cannot accidentally sign with a publicly known key.
+
+use std::str::FromStr;
+
+use anyhow::Result;
+use ethers::middleware::Middleware;
+use ethers::signers::LocalWallet;
+use ethers::signers::Signer;
+use ethers::types::Address;
+use ethers::types::H256;
+use ethers::types::U256;
+
+use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+
+/// Chain ids considered local development networks (Anvil/Hardhat, Ganache)
+pub const LOCAL_CHAIN_IDS: &[u64] = &[31337, 1337];
+
+/// Anvil's default account 0, funded with 10,000 ETH on every fresh node
+const ANVIL_ACCOUNT_0_KEY: &str =
+    "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Faucet that funds wallets on local development chains only
+#[derive(Clone)]
+pub struct DevFaucet {
+    wallet: LocalWallet,
+}
+
+impl DevFaucet {
+    /// Faucet backed by Anvil's default pre-funded account
+    pub fn anvil() -> Self {
+        Self {
+            wallet: LocalWallet::from_str(ANVIL_ACCOUNT_0_KEY)
+                .expect("Anvil default key is a valid private key"),
+        }
+    }
+
+    /// Faucet backed by a custom funder key (e.g. a different Anvil account)
+    pub fn from_private_key(private_key: &str) -> Result<Self> {
+        Ok(Self {
+            wallet: LocalWallet::from_str(private_key)?,
+        })
+    }
+
+    /// Address of the funding account
+    pub fn address(&self) -> Address {
+        self.wallet.address()
+    }
+
+    /// Whether a chain id belongs to a local development network
+    pub fn is_local_chain(chain_id: u64) -> bool {
+        LOCAL_CHAIN_IDS.contains(&chain_id)
+    }
+
+    /// Fund a wallet after verifying the client is connected to a local chain
+    ///
+    /// # Arguments
+    /// * `client` - Contract client connected to the local node
+    /// * `target_address` - Wallet to fund
+    /// * `amount` - Amount in wei
+    ///
+    /// # Returns
+    /// * `Result<H256>` - Transaction hash, or an error on non-local chains
+    pub async fn fund(
+        &self,
+        client: &FarcasterContractClient,
+        target_address: Address,
+        amount: U256,
+    ) -> Result<H256> {
+        let chain_id = client.provider.get_chainid().await?.as_u64();
+        if !Self::is_local_chain(chain_id) {
+            anyhow::bail!(
+                "Dev faucet refused to run on chain {} (allowed: {:?})",
+                chain_id,
+                LOCAL_CHAIN_IDS
+            );
+        }
+
+        client
+            .transfer_eth(&self.wallet, target_address, amount)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_chain_detection() {
+        assert!(DevFaucet::is_local_chain(31337));
+        assert!(!DevFaucet::is_local_chain(10));
+        assert!(!DevFaucet::is_local_chain(8453));
+    }
+
+    #[test]
+    fn test_anvil_faucet_address() {
+        let faucet = DevFaucet::anvil();
+        assert_eq!(
+            format!("{:?}", faucet.address()),
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Decoding of Farcaster contract custom errors
+//!
+//! Reverts from the Farcaster contracts carry ABI-encoded custom errors such as
+//! `ExceedsMaximum()` or `InvalidSignature()`, which ethers only shows as hex.
+//! The error definitions are taken from the generated contract ABIs and mapped
+//! to a readable message plus a remediation hint.
+
+use std::fmt;
+
+use ethers::abi::Abi;
+use ethers::abi::Detokenize;
+use ethers::contract::ContractCall;
+use ethers::contract::ContractError;
+use ethers::providers::Middleware;
+use ethers::utils::id;
+
+use crate::farcaster::contracts::generated::bundler_bindings::BUNDLER_ABI;
+use crate::farcaster::contracts::generated::idgateway_bindings::IDGATEWAY_ABI;
+use crate::farcaster::contracts::generated::idregistry_bindings::IDREGISTRY_ABI;
+use crate::farcaster::contracts::generated::keygateway_bindings::KEYGATEWAY_ABI;
+use crate::farcaster::contracts::generated::keyregistry_bindings::KEYREGISTRY_ABI;
+use crate::farcaster::contracts::generated::signedkeyrequestvalidator_bindings::SIGNEDKEYREQUESTVALIDATOR_ABI;
+use crate::farcaster::contracts::generated::storageregistry_bindings::STORAGEREGISTRY_ABI;
+
+/// A revert decoded into a named contract error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedRevert {
+    /// Error name (e.g. `ExceedsMaximum`), or `Error` for plain revert strings
+    pub name: String,
+    /// Decoded error arguments, formatted for display
+    pub args: Vec<String>,
+    /// Human-readable explanation
+    pub message: String,
+    /// Suggested fix, when one is known
+    pub hint: Option<&'static str>,
+}
+
+impl fmt::Display for DecodedRevert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.args.is_empty() {
+            write!(f, "{} ({})", self.message, self.name)?;
+        } else {
+            write!(
+                f,
+                "{} ({}({}))",
+                self.message,
+                self.name,
+                self.args.join(", ")
+            )?;
+        }
+        if let Some(hint) = self.hint {
+            write!(f, ". Hint: {hint}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Readable message and remediation hint for a Farcaster contract error
+fn describe(name: &str) -> Option<(&'static str, Option<&'static str>)> {
+    let description = match name {
+        "ExceedsMaximum" => (
+            "The FID already has the maximum number of keys",
+            Some("remove an unused signer before adding a new one"),
+        ),
+        "InvalidSignature" => (
+            "Signature verification failed",
+            Some("check the signing address, nonce and deadline used to build the signature"),
+        ),
+        "SignatureExpired" => (
+            "The signature deadline has passed",
+            Some("sign again with a later deadline"),
+        ),
+        "InvalidAccountNonce" => (
+            "The signature was made for a different account nonce",
+            Some("re-read the nonce from the contract and sign again"),
+        ),
+        "Unauthorized" => (
+            "The caller is not authorized for this operation",
+            Some("send the transaction from the FID's custody address"),
+        ),
+        "HasId" => (
+            "The address already owns a FID",
+            Some("use a different custody address or transfer the existing FID first"),
+        ),
+        "HasNoId" => (
+            "The address does not own a FID",
+            Some("register a FID for this address first"),
+        ),
+        "InvalidState" => (
+            "The key is not in the state this operation requires",
+            Some("the key may already be added or removed; check the FID's signers"),
+        ),
+        "InvalidKeyType" | "ValidatorNotFound" => (
+            "The key type is not supported by the KeyRegistry",
+            Some("use key type 1 (Ed25519)"),
+        ),
+        "InvalidMetadataType" | "InvalidMetadata" => (
+            "The key metadata was rejected by the validator",
+            Some("rebuild the SignedKeyRequest metadata for the requesting FID"),
+        ),
+        "GatewayFrozen" => ("The gateway address can no longer be changed", None),
+        "InvalidPayment" => (
+            "The ETH sent does not cover the required price",
+            Some("query the current price and send at least that amount"),
+        ),
+        "ExceedsCapacity" => ("The storage registry has no units left to rent", None),
+        "ContractDeprecated" => ("The contract has been deprecated", None),
+        "CallFailed" => (
+            "An ETH refund or external call failed",
+            Some("make sure the caller can receive ETH refunds"),
+        ),
+        "InvalidBatchInput" => ("The batch arguments have mismatched lengths", None),
+        "OnlyGuardian" | "NotOwner" | "NotOperator" | "NotTreasurer" => {
+            ("The caller lacks the required admin role", None)
+        }
+        _ => return None,
+    };
+    Some(description)
+}
+
+/// Generated ABIs of the contracts castorix talks to
+fn farcaster_abis() -> [&'static Abi; 7] {
+    [
+        &IDREGISTRY_ABI,
+        &IDGATEWAY_ABI,
+        &KEYREGISTRY_ABI,
+        &KEYGATEWAY_ABI,
+        &STORAGEREGISTRY_ABI,
+        &BUNDLER_ABI,
+        &SIGNEDKEYREQUESTVALIDATOR_ABI,
+    ]
+}
+
+/// Decode raw revert data returned by a Farcaster contract
+///
+/// # Arguments
+/// * `data` - Revert data (4-byte selector followed by ABI-encoded arguments)
+///
+/// # Returns
+/// * `Option<DecodedRevert>` - The decoded error, or None for unknown selectors
+pub fn decode_revert(data: &[u8]) -> Option<DecodedRevert> {
+    if data.len() < 4 {
+        return None;
+    }
+    let (selector, payload) = data.split_at(4);
+
+    // Plain `require(cond, "reason")` reverts
+    if selector == id("Error(string)") {
+        let reason = ethers::abi::decode(&[ethers::abi::ParamType::String], payload)
+            .ok()?
+            .pop()?
+            .into_string()?;
+        return Some(DecodedRevert {
+            name: "Error".to_string(),
+            args: Vec::new(),
+            message: reason,
+            hint: None,
+        });
+    }
+
+    let error = farcaster_abis()
+        .into_iter()
+        .flat_map(|abi| abi.errors())
+        .find(|error| error.signature()[..4] == *selector)?;
+    let args = error
+        .decode(payload)
+        .map(|tokens| tokens.iter().map(|t| t.to_string()).collect())
+        .unwrap_or_default();
+    let (message, hint) = describe(&error.name)
+        .map(|(message, hint)| (message.to_string(), hint))
+        .unwrap_or_else(|| (format!("Contract reverted with {}", error.name), None));
+
+    Some(DecodedRevert {
+        name: error.name.clone(),
+        args,
+        message,
+        hint,
+    })
+}
+
+/// Format a contract call error, decoding custom errors where possible
+///
+/// # Arguments
+/// * `error` - The error returned by a generated contract binding
+///
+/// # Returns
+/// * `String` - A readable description, falling back to the raw error text
+pub fn describe_contract_error<M: Middleware>(error: &ContractError<M>) -> String {
+    error
+        .as_revert()
+        .and_then(|data| decode_revert(data))
+        .map(|decoded| decoded.to_string())
+        .unwrap_or_else(|| error.to_string())
+}
+
+/// Run a state-changing call through `eth_call` before it is broadcast
+///
+/// The simulation uses the same calldata, value and sender as the real
+/// transaction, so predictable reverts (already registered keys, expired
+/// signatures, ...) are reported without spending gas or reserving a nonce.
+///
+/// # Arguments
+/// * `call` - The prepared contract call
+///
+/// # Returns
+/// * `Result<(), String>` - The decoded revert reason if the simulation fails
+pub async fn simulate_call<M, D>(call: &ContractCall<M, D>) -> Result<(), String>
+where
+    M: Middleware,
+    D: Detokenize,
+{
+    call.call()
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Simulation reverted: {}", describe_contract_error(&e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ethers::abi::Token;
+    use ethers::providers::JsonRpcError;
+    use ethers::providers::MockProvider;
+    use ethers::providers::MockResponse;
+    use ethers::providers::Provider;
+    use ethers::types::Address;
+    use ethers::types::U256;
+
+    use super::*;
+    use crate::farcaster::contracts::generated::keygateway_bindings::KeyGateway;
+
+    /// A KeyGateway whose `eth_call` is answered with `response`
+    fn mocked_gateway(response: MockResponse) -> KeyGateway<Provider<MockProvider>> {
+        let (provider, mock) = Provider::mocked();
+        mock.push_response(response);
+        KeyGateway::new(Address::repeat_byte(0x11), Arc::new(provider))
+    }
+
+    #[test]
+    fn test_decode_custom_error() {
+        let decoded = decode_revert(&id("ExceedsMaximum()")).unwrap();
+        assert_eq!(decoded.name, "ExceedsMaximum");
+        assert!(decoded.hint.is_some());
+        assert!(decoded.to_string().contains("maximum number of keys"));
+    }
+
+    #[test]
+    fn test_decode_error_with_arguments() {
+        let mut data = id("InvalidAccountNonce(address,uint256)").to_vec();
+        data.extend(ethers::abi::encode(&[
+            Token::Address(Address::zero()),
+            Token::Uint(U256::from(7)),
+        ]));
+        let decoded = decode_revert(&data).unwrap();
+        assert_eq!(decoded.name, "InvalidAccountNonce");
+        assert_eq!(decoded.args.len(), 2);
+    }
+
+    #[test]
+    fn test_decode_revert_string() {
+        let mut data = id("Error(string)").to_vec();
+        data.extend(ethers::abi::encode(&[Token::String(
+            "Pausable: paused".to_string(),
+        )]));
+        assert_eq!(decode_revert(&data).unwrap().message, "Pausable: paused");
+        assert!(decode_revert(&[0xde, 0xad, 0xbe, 0xef]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_call_decodes_revert() {
+        let revert = format!("0x{}", hex::encode(id("ExceedsMaximum()")));
+        let gateway = mocked_gateway(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: Some(serde_json::Value::String(revert)),
+        }));
+        let call = gateway.add(1, vec![9; 32].into(), 1, Vec::new().into());
+        let error = simulate_call(&call).await.unwrap_err();
+        assert!(error.starts_with("Simulation reverted: "), "{error}");
+        assert!(error.contains("maximum number of keys"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_simulate_call_passes_success_through() {
+        let gateway = mocked_gateway(MockResponse::Value(serde_json::json!("0x")));
+        let call = gateway.add(1, vec![9; 32].into(), 1, Vec::new().into());
+        assert_eq!(simulate_call(&call).await, Ok(()));
+    }
+}
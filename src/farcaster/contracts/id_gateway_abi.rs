@@ -11,6 +11,7 @@ use ethers::providers::Provider;
 use ethers::types::Address;
 use ethers::types::U256;
 
+use crate::farcaster::contracts::errors::describe_contract_error;
 use crate::farcaster::contracts::generated::idgateway_bindings::IdGateway as IdGatewayContract;
 use crate::farcaster::contracts::types::ContractResult;
 
@@ -41,7 +42,10 @@ impl IdGatewayAbi {
     pub async fn id_registry(&self) -> Result<ContractResult<Address>> {
         match self.contract.id_registry().call().await {
             Ok(address) => Ok(ContractResult::Success(address)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -49,7 +53,10 @@ impl IdGatewayAbi {
     pub async fn paused(&self) -> Result<ContractResult<bool>> {
         match self.contract.paused().call().await {
             Ok(is_paused) => Ok(ContractResult::Success(is_paused)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -57,7 +64,10 @@ impl IdGatewayAbi {
     pub async fn price(&self) -> Result<ContractResult<U256>> {
         match self.contract.price().call().await {
             Ok(price) => Ok(ContractResult::Success(price)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -65,7 +75,10 @@ impl IdGatewayAbi {
     pub async fn storage_registry(&self) -> Result<ContractResult<Address>> {
         match self.contract.storage_registry().call().await {
             Ok(address) => Ok(ContractResult::Success(address)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -73,7 +86,10 @@ impl IdGatewayAbi {
     pub async fn version(&self) -> Result<ContractResult<String>> {
         match self.contract.version().call().await {
             Ok(version) => Ok(ContractResult::Success(version)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
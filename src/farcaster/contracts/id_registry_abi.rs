@@ -10,6 +10,7 @@ use ethers::providers::Http;
 use ethers::providers::Provider;
 use ethers::types::Address;
 
+use crate::farcaster::contracts::errors::describe_contract_error;
 use crate::farcaster::contracts::generated::idregistry_bindings::IdRegistry as IdRegistryContract;
 use crate::farcaster::contracts::types::ContractResult;
 use crate::farcaster::contracts::types::Fid;
@@ -42,7 +43,10 @@ impl IdRegistryAbi {
     pub async fn custody_of(&self, fid: Fid) -> Result<ContractResult<Address>> {
         match self.contract.custody_of(fid.into()).call().await {
             Ok(owner) => Ok(ContractResult::Success(owner)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -58,7 +62,10 @@ impl IdRegistryAbi {
                 let fid_u64 = fid.try_into().unwrap_or(0);
                 Ok(ContractResult::Success(fid_u64))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -66,7 +73,10 @@ impl IdRegistryAbi {
     pub async fn recovery_of(&self, fid: Fid) -> Result<ContractResult<RecoveryAddress>> {
         match self.contract.recovery_of(fid.into()).call().await {
             Ok(recovery) => Ok(ContractResult::Success(recovery)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -77,7 +87,10 @@ impl IdRegistryAbi {
                 let counter_u64 = counter.try_into().unwrap_or(0);
                 Ok(ContractResult::Success(counter_u64))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -85,7 +98,10 @@ impl IdRegistryAbi {
     pub async fn paused(&self) -> Result<ContractResult<bool>> {
         match self.contract.paused().call().await {
             Ok(is_paused) => Ok(ContractResult::Success(is_paused)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -96,7 +112,10 @@ impl IdRegistryAbi {
                 let period_u64: u64 = period.into();
                 Ok(ContractResult::Success(period_u64))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -104,7 +123,10 @@ impl IdRegistryAbi {
     pub async fn gateway_frozen(&self) -> Result<ContractResult<bool>> {
         match self.contract.gateway_frozen().call().await {
             Ok(is_frozen) => Ok(ContractResult::Success(is_frozen)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -112,7 +134,10 @@ impl IdRegistryAbi {
     pub async fn id_gateway(&self) -> Result<ContractResult<Address>> {
         match self.contract.id_gateway().call().await {
             Ok(gateway) => Ok(ContractResult::Success(gateway)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -120,7 +145,10 @@ impl IdRegistryAbi {
     pub async fn migrator(&self) -> Result<ContractResult<Address>> {
         match self.contract.migrator().call().await {
             Ok(migrator) => Ok(ContractResult::Success(migrator)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -128,7 +156,10 @@ impl IdRegistryAbi {
     pub async fn is_migrated(&self) -> Result<ContractResult<bool>> {
         match self.contract.is_migrated().call().await {
             Ok(is_migrated) => Ok(ContractResult::Success(is_migrated)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -139,7 +170,10 @@ impl IdRegistryAbi {
                 let timestamp_u64 = timestamp;
                 Ok(ContractResult::Success(timestamp_u64))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -147,7 +181,10 @@ impl IdRegistryAbi {
     pub async fn version(&self) -> Result<ContractResult<String>> {
         match self.contract.version().call().await {
             Ok(version) => Ok(ContractResult::Success(version)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -155,7 +192,10 @@ impl IdRegistryAbi {
     pub async fn name(&self) -> Result<ContractResult<String>> {
         match self.contract.name().call().await {
             Ok(name) => Ok(ContractResult::Success(name)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -180,7 +220,10 @@ impl IdRegistryAbi {
             .await
         {
             Ok(is_valid) => Ok(ContractResult::Success(is_valid)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 }
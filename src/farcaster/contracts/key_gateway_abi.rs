@@ -14,8 +14,14 @@ use ethers::types::Address;
 use ethers::types::Bytes;
 use ethers::types::U256;
 
+use crate::core::audit;
+use crate::core::policy;
+use crate::core::utils::finality;
+use crate::farcaster::contracts::errors::describe_contract_error;
+use crate::farcaster::contracts::errors::simulate_call;
 use crate::farcaster::contracts::generated::keygateway_bindings::KeyGateway as KeyGatewayContract;
 use crate::farcaster::contracts::types::ContractResult;
+use crate::farcaster::contracts::types::Fid;
 
 /// ABI-based KeyGateway contract wrapper
 #[derive(Clone)]
@@ -44,7 +50,10 @@ impl KeyGatewayAbi {
     pub async fn key_registry(&self) -> Result<ContractResult<Address>> {
         match self.contract.key_registry().call().await {
             Ok(address) => Ok(ContractResult::Success(address)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -52,7 +61,10 @@ impl KeyGatewayAbi {
     pub async fn paused(&self) -> Result<ContractResult<bool>> {
         match self.contract.paused().call().await {
             Ok(is_paused) => Ok(ContractResult::Success(is_paused)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -60,7 +72,10 @@ impl KeyGatewayAbi {
     pub async fn version(&self) -> Result<ContractResult<String>> {
         match self.contract.version().call().await {
             Ok(version) => Ok(ContractResult::Success(version)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -79,14 +94,21 @@ impl KeyGatewayAbi {
             .await
         {
             Ok(_) => Ok(ContractResult::Success(())),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
     /// Add a key for another address (authorized method)
+    ///
+    /// `fid` is the FID owned by `fid_owner`; the policy is checked for it
+    /// before anything is sent.
     #[allow(clippy::too_many_arguments)]
     pub async fn add_for(
         &self,
+        fid: Fid,
         fid_owner: Address,
         key_type: u32,
         key: Vec<u8>,
@@ -95,37 +117,53 @@ impl KeyGatewayAbi {
         deadline: U256,
         sig: Vec<u8>,
     ) -> Result<ContractResult<ethers::types::TransactionReceipt>> {
-        match self
-            .contract
-            .add_for(
-                fid_owner,
-                key_type,
-                key.into(),
-                metadata_type,
-                metadata.into(),
-                deadline,
-                sig.into(),
-            )
-            .send()
-            .await
-        {
+        policy::current().check_transaction(Some(fid), U256::zero())?;
+        let call = self.contract.add_for(
+            fid_owner,
+            key_type,
+            key.into(),
+            metadata_type,
+            metadata.into(),
+            deadline,
+            sig.into(),
+        );
+        if let Err(e) = simulate_call(&call).await {
+            return Ok(ContractResult::Error(e));
+        }
+
+        let sent = call.send().await;
+        if let Ok(pending_tx) = &sent {
+            audit::record_transaction(call.tx.from().copied(), "add_for", pending_tx.tx_hash());
+        }
+        match sent {
             Ok(pending_tx) => match pending_tx.await {
-                Ok(Some(receipt)) => Ok(ContractResult::Success(receipt)),
+                Ok(Some(receipt)) => {
+                    match finality::confirm(self.contract.client_ref(), receipt).await {
+                        Ok(receipt) => Ok(ContractResult::Success(receipt)),
+                        Err(e) => Ok(ContractResult::Error(format!("{e:#}"))),
+                    }
+                }
                 Ok(None) => Ok(ContractResult::Error(
                     "Transaction failed - no receipt received".to_string(),
                 )),
                 Err(e) => Ok(ContractResult::Error(format!("Transaction failed: {e}"))),
             },
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
     /// Add a key for another address using raw transaction (for third-party payment)
+    ///
+    /// Like [`Self::add_for`], the policy is checked for `fid` first.
     #[allow(clippy::too_many_arguments)]
     pub async fn add_for_raw(
         &self,
         provider: &ethers::providers::Provider<ethers::providers::Http>,
         wallet: &ethers::signers::LocalWallet,
+        fid: Fid,
         fid_owner: Address,
         key_type: u32,
         key: Vec<u8>,
@@ -136,6 +174,8 @@ impl KeyGatewayAbi {
     ) -> Result<ContractResult<ethers::types::TransactionReceipt>> {
         use ethers::signers::Signer;
 
+        policy::current().check_transaction(Some(fid), U256::zero())?;
+
         // Clone parameters to avoid move issues
         let key_clone = key.clone();
         let metadata_clone = metadata.clone();
@@ -156,6 +196,23 @@ impl KeyGatewayAbi {
             .calldata()
             .ok_or_else(|| anyhow::anyhow!("Failed to get calldata"))?;
 
+        // Simulate as the paying wallet before anything is signed
+        let simulation = self
+            .contract
+            .add_for(
+                fid_owner,
+                key_type,
+                key_clone.clone().into(),
+                metadata_type,
+                metadata_clone.clone().into(),
+                deadline,
+                sig_clone.clone().into(),
+            )
+            .from(wallet.address());
+        if let Err(e) = simulate_call(&simulation).await {
+            return Ok(ContractResult::Error(e));
+        }
+
         // Get gas price and estimate gas
         let gas_price = provider.get_gas_price().await?;
         let gas_limit = self
@@ -197,10 +254,14 @@ impl KeyGatewayAbi {
 
         // Send raw transaction
         let tx_hash = provider.send_raw_transaction(signed_tx_bytes).await?;
+        audit::record_transaction(Some(wallet.address()), "add_for", tx_hash.tx_hash());
 
         // Wait for receipt
         match tx_hash.await {
-            Ok(Some(receipt)) => Ok(ContractResult::Success(receipt)),
+            Ok(Some(receipt)) => match finality::confirm(provider, receipt).await {
+                Ok(receipt) => Ok(ContractResult::Success(receipt)),
+                Err(e) => Ok(ContractResult::Error(format!("{e:#}"))),
+            },
             Ok(None) => Ok(ContractResult::Error(
                 "Transaction failed - no receipt received".to_string(),
             )),
@@ -212,7 +273,10 @@ impl KeyGatewayAbi {
     pub async fn nonces(&self, owner: Address) -> Result<ContractResult<U256>> {
         match self.contract.nonces(owner).call().await {
             Ok(nonce) => Ok(ContractResult::Success(nonce)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -220,7 +284,10 @@ impl KeyGatewayAbi {
     pub async fn use_nonce(&self) -> Result<ContractResult<U256>> {
         match self.contract.use_nonce().call().await {
             Ok(nonce) => Ok(ContractResult::Success(nonce)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -228,7 +295,66 @@ impl KeyGatewayAbi {
     pub async fn add_typehash(&self) -> Result<ContractResult<Bytes>> {
         match self.contract.add_typehash().call().await {
             Ok(typehash) => Ok(ContractResult::Success(typehash.into())),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::policy::install_for_test;
+    use crate::core::policy::only_fid;
+
+    /// A gateway whose RPC endpoint refuses connections
+    fn offline_gateway() -> KeyGatewayAbi {
+        let provider = Provider::<Http>::try_from("http://127.0.0.1:1").unwrap();
+        KeyGatewayAbi::new(provider, Address::repeat_byte(0x11)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_add_for_checks_policy_first() {
+        let _policy = install_for_test(only_fid(3));
+        let error = offline_gateway()
+            .add_for(
+                4,
+                Address::repeat_byte(0x22),
+                1,
+                vec![9; 32],
+                1,
+                vec![],
+                U256::from(1),
+                vec![0; 65],
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Blocked by policy"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_add_for_raw_checks_policy_first() {
+        let _policy = install_for_test(only_fid(3));
+        let gateway = offline_gateway();
+        let provider = Provider::<Http>::try_from("http://127.0.0.1:1").unwrap();
+        let wallet = ethers::signers::LocalWallet::new(&mut rand::thread_rng());
+        let error = gateway
+            .add_for_raw(
+                &provider,
+                &wallet,
+                4,
+                Address::repeat_byte(0x22),
+                1,
+                vec![9; 32],
+                1,
+                vec![],
+                U256::from(1),
+                vec![0; 65],
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Blocked by policy"), "{error}");
+    }
+}
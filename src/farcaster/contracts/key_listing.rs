@@ -0,0 +1,193 @@
+//! KeyRegistry key enumeration and signer metadata decoding
+//!
+//! `keysOf` is read in pages straight from the KeyRegistry, and the metadata a
+//! key was added with is recovered from its `Add` event. For keys added via the
+//! SignedKeyRequestValidator that metadata names the FID (the app) which
+//! requested the signer, the address that signed the request and its deadline.
+
+#![cfg(not(doctest))]
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use ethers::abi::decode;
+use ethers::abi::ParamType;
+use ethers::abi::Token;
+use ethers::types::Address;
+use ethers::types::H256;
+
+use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+use crate::farcaster::contracts::types::ContractResult;
+use crate::farcaster::contracts::types::Fid;
+
+/// KeyRegistry key state of keys that are currently added
+pub const KEY_STATE_ADDED: u8 = 1;
+/// KeyRegistry key state of keys that were removed
+pub const KEY_STATE_REMOVED: u8 = 2;
+/// Metadata type of a SignedKeyRequestValidator signed key request
+pub const SIGNED_KEY_REQUEST_METADATA_TYPE: u8 = 1;
+/// Keys requested per `keysOf` page
+pub const DEFAULT_KEYS_PAGE_SIZE: u64 = 100;
+
+/// Decoded SignedKeyRequestMetadata
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedKeyRequest {
+    /// FID of the app that requested the signer
+    pub request_fid: Fid,
+    /// Address that signed the request (the app FID's custody address at the time)
+    pub request_signer: Address,
+    /// Unix time after which the request could no longer be used
+    pub deadline: u64,
+}
+
+/// Metadata a key was added with, from its KeyRegistry `Add` event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyAddMetadata {
+    pub block_number: u64,
+    pub key_type: u32,
+    pub metadata_type: u8,
+    pub metadata: Vec<u8>,
+}
+
+impl KeyAddMetadata {
+    /// Decode the signed key request, if the key was added with one
+    pub fn signed_key_request(&self) -> Option<SignedKeyRequest> {
+        if self.metadata_type != SIGNED_KEY_REQUEST_METADATA_TYPE {
+            return None;
+        }
+        decode_signed_key_request(&self.metadata).ok()
+    }
+}
+
+/// Decode ABI-encoded `SignedKeyRequestMetadata(uint256 requestFid, address requestSigner, bytes signature, uint256 deadline)`
+pub fn decode_signed_key_request(metadata: &[u8]) -> Result<SignedKeyRequest> {
+    let tokens = decode(
+        &[ParamType::Tuple(vec![
+            ParamType::Uint(256),
+            ParamType::Address,
+            ParamType::Bytes,
+            ParamType::Uint(256),
+        ])],
+        metadata,
+    )
+    .map_err(|e| anyhow::anyhow!("Invalid SignedKeyRequestMetadata: {}", e))?;
+
+    let Some(Token::Tuple(fields)) = tokens.into_iter().next() else {
+        anyhow::bail!("Invalid SignedKeyRequestMetadata: expected a tuple");
+    };
+    match fields.as_slice() {
+        [Token::Uint(fid), Token::Address(signer), Token::Bytes(_), Token::Uint(deadline)] => {
+            Ok(SignedKeyRequest {
+                request_fid: fid.low_u64(),
+                request_signer: *signer,
+                deadline: (*deadline).try_into().unwrap_or(u64::MAX),
+            })
+        }
+        _ => anyhow::bail!("Invalid SignedKeyRequestMetadata fields"),
+    }
+}
+
+impl FarcasterContractClient {
+    /// List a FID's keys in a state by paging through `keysOf`
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `state` - [`KEY_STATE_ADDED`] or [`KEY_STATE_REMOVED`]
+    /// * `page_size` - Keys requested per call
+    ///
+    /// # Returns
+    /// * `Result<Vec<Vec<u8>>>` - The raw keys or an error
+    pub async fn list_keys(&self, fid: Fid, state: u8, page_size: u64) -> Result<Vec<Vec<u8>>> {
+        let mut keys = Vec::new();
+        let mut start = 0;
+        loop {
+            let (page, next) = match self
+                .key_registry
+                .keys_of_page(fid, state, start, page_size.max(1))
+                .await?
+            {
+                ContractResult::Success(page) => page,
+                ContractResult::Error(e) => {
+                    return Err(anyhow::anyhow!("Failed to list keys of FID {}: {}", fid, e))
+                }
+            };
+            keys.extend(page);
+            // keysOf returns a next index of 0 once the last page was read
+            if next == 0 || next <= start {
+                return Ok(keys);
+            }
+            start = next;
+        }
+    }
+
+    /// Get the `Add` event metadata of every key a FID has added since a block
+    ///
+    /// # Arguments
+    /// * `fid` - The Farcaster ID
+    /// * `from_block` - First block to search
+    ///
+    /// # Returns
+    /// * `Result<HashMap<Vec<u8>, KeyAddMetadata>>` - Metadata by raw key, latest add wins
+    pub async fn key_add_metadata(
+        &self,
+        fid: Fid,
+        from_block: u64,
+    ) -> Result<HashMap<Vec<u8>, KeyAddMetadata>> {
+        let events = self
+            .key_registry
+            .contract()
+            .add_1_filter()
+            .topic1(H256::from_low_u64_be(fid))
+            .from_block(from_block)
+            .query_with_meta()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to query KeyRegistry Add events: {}", e))?;
+
+        let mut metadata = HashMap::new();
+        for (event, meta) in events {
+            metadata.insert(
+                event.key_bytes.to_vec(),
+                KeyAddMetadata {
+                    block_number: meta.block_number.as_u64(),
+                    key_type: event.key_type,
+                    metadata_type: event.metadata_type,
+                    metadata: event.metadata.to_vec(),
+                },
+            );
+        }
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::abi::encode;
+    use ethers::types::U256;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_signed_key_request() {
+        let signer = Address::repeat_byte(0x22);
+        let metadata = encode(&[Token::Tuple(vec![
+            Token::Uint(U256::from(9152)),
+            Token::Address(signer),
+            Token::Bytes(vec![0xab; 65]),
+            Token::Uint(U256::from(1_700_000_000u64)),
+        ])]);
+
+        let request = decode_signed_key_request(&metadata).unwrap();
+        assert_eq!(request.request_fid, 9152);
+        assert_eq!(request.request_signer, signer);
+        assert_eq!(request.deadline, 1_700_000_000);
+
+        let add = KeyAddMetadata {
+            block_number: 1,
+            key_type: 1,
+            metadata_type: SIGNED_KEY_REQUEST_METADATA_TYPE,
+            metadata,
+        };
+        assert_eq!(add.signed_key_request(), Some(request));
+        assert!(decode_signed_key_request(&[0u8; 8]).is_err());
+    }
+}
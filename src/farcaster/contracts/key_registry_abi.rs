@@ -9,7 +9,13 @@ use anyhow::Result;
 use ethers::providers::Http;
 use ethers::providers::Provider;
 use ethers::types::Address;
+use ethers::types::U256;
 
+use crate::core::audit;
+use crate::core::policy;
+use crate::core::utils::finality;
+use crate::farcaster::contracts::errors::describe_contract_error;
+use crate::farcaster::contracts::errors::simulate_call;
 use crate::farcaster::contracts::generated::keyregistry_bindings::KeyRegistry as KeyRegistryContract;
 use crate::farcaster::contracts::types::ContractResult;
 use crate::farcaster::contracts::types::Fid;
@@ -44,7 +50,10 @@ impl KeyRegistryAbi {
                 let total_u64 = total.try_into().unwrap_or(0);
                 Ok(ContractResult::Success(total_u64))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -62,7 +71,10 @@ impl KeyRegistryAbi {
                 let key_type_u32 = key_data.key_type;
                 Ok(ContractResult::Success((state_u8, key_type_u32)))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -74,7 +86,10 @@ impl KeyRegistryAbi {
                 let key_type_u32 = key_type;
                 Ok(ContractResult::Success((state_u8, key_type_u32)))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -87,7 +102,10 @@ impl KeyRegistryAbi {
             .await
         {
             Ok(key_bytes) => Ok(ContractResult::Success(key_bytes.to_vec())),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -98,7 +116,39 @@ impl KeyRegistryAbi {
                 let keys_vec: Vec<Vec<u8>> = keys.into_iter().map(|k| k.to_vec()).collect();
                 Ok(ContractResult::Success(keys_vec))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
+        }
+    }
+
+    /// Get one page of a FID's keys in a specific state
+    ///
+    /// # Returns
+    /// * `Result<ContractResult<(Vec<Vec<u8>>, u64)>>` - The keys and the next start
+    ///   index, which is 0 once the last page has been returned
+    pub async fn keys_of_page(
+        &self,
+        fid: Fid,
+        state: u8,
+        start_idx: u64,
+        batch_size: u64,
+    ) -> Result<ContractResult<(Vec<Vec<u8>>, u64)>> {
+        match self
+            .contract
+            .keys_of_with_fid_and_state(fid.into(), state, start_idx.into(), batch_size.into())
+            .call()
+            .await
+        {
+            Ok((keys, next_idx)) => Ok(ContractResult::Success((
+                keys.into_iter().map(|k| k.to_vec()).collect(),
+                next_idx.try_into().unwrap_or(0),
+            ))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -106,7 +156,10 @@ impl KeyRegistryAbi {
     pub async fn id_registry(&self) -> Result<ContractResult<Address>> {
         match self.contract.id_registry().call().await {
             Ok(address) => Ok(ContractResult::Success(address)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -114,7 +167,10 @@ impl KeyRegistryAbi {
     pub async fn paused(&self) -> Result<ContractResult<bool>> {
         match self.contract.paused().call().await {
             Ok(is_paused) => Ok(ContractResult::Success(is_paused)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -125,7 +181,10 @@ impl KeyRegistryAbi {
                 let period_u64: u64 = period.into();
                 Ok(ContractResult::Success(period_u64))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -133,7 +192,10 @@ impl KeyRegistryAbi {
     pub async fn gateway_frozen(&self) -> Result<ContractResult<bool>> {
         match self.contract.gateway_frozen().call().await {
             Ok(is_frozen) => Ok(ContractResult::Success(is_frozen)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -141,43 +203,88 @@ impl KeyRegistryAbi {
     pub async fn key_gateway(&self) -> Result<ContractResult<Address>> {
         match self.contract.key_gateway().call().await {
             Ok(gateway) => Ok(ContractResult::Success(gateway)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
-    /// Remove a key (requires proper authorization)
-    pub async fn remove(&self, key: Vec<u8>) -> Result<ContractResult<()>> {
-        match self.contract.remove(key.into()).send().await {
+    /// Remove a key of the caller's FID (requires proper authorization)
+    ///
+    /// The policy is checked for `fid` before anything is sent.
+    pub async fn remove(&self, fid: Fid, key: Vec<u8>) -> Result<ContractResult<()>> {
+        policy::current().check_transaction(Some(fid), U256::zero())?;
+        let call = self.contract.remove(key.into());
+        if let Err(e) = simulate_call(&call).await {
+            return Ok(ContractResult::Error(e));
+        }
+
+        let sent = call.send().await;
+        if let Ok(tx) = &sent {
+            audit::record_transaction(call.tx.from().copied(), "remove", tx.tx_hash());
+        }
+        match sent {
             Ok(tx) => match tx.await {
-                Ok(_receipt) => Ok(ContractResult::Success(())),
+                Ok(Some(receipt)) => {
+                    match finality::confirm(self.contract.client_ref(), receipt).await {
+                        Ok(_) => Ok(ContractResult::Success(())),
+                        Err(e) => Ok(ContractResult::Error(format!("{e:#}"))),
+                    }
+                }
+                Ok(None) => Ok(ContractResult::Error(
+                    "Transaction failed - no receipt received".to_string(),
+                )),
                 Err(e) => Ok(ContractResult::Error(format!("Transaction failed: {e}"))),
             },
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
     /// Remove a key for another FID owner (requires authorization signature)
+    ///
+    /// `fid` is the FID owned by `fid_owner`; the policy is checked for it
+    /// before anything is sent.
     pub async fn remove_for(
         &self,
+        fid: Fid,
         fid_owner: Address,
         key: Vec<u8>,
         deadline: u64,
         signature: Vec<u8>,
     ) -> Result<ContractResult<ethers::types::TransactionReceipt>> {
-        match self
-            .contract
-            .remove_for(fid_owner, key.into(), deadline.into(), signature.into())
-            .send()
-            .await
-        {
+        policy::current().check_transaction(Some(fid), U256::zero())?;
+        let call =
+            self.contract
+                .remove_for(fid_owner, key.into(), deadline.into(), signature.into());
+        if let Err(e) = simulate_call(&call).await {
+            return Ok(ContractResult::Error(e));
+        }
+
+        let sent = call.send().await;
+        if let Ok(pending_tx) = &sent {
+            audit::record_transaction(call.tx.from().copied(), "remove_for", pending_tx.tx_hash());
+        }
+        match sent {
             Ok(pending_tx) => match pending_tx.await {
-                Ok(Some(receipt)) => Ok(ContractResult::Success(receipt)),
+                Ok(Some(receipt)) => {
+                    match finality::confirm(self.contract.client_ref(), receipt).await {
+                        Ok(receipt) => Ok(ContractResult::Success(receipt)),
+                        Err(e) => Ok(ContractResult::Error(format!("{e:#}"))),
+                    }
+                }
                 Ok(None) => Ok(ContractResult::Error(
                     "Transaction failed - no receipt received".to_string(),
                 )),
                 Err(e) => Ok(ContractResult::Error(format!("Transaction failed: {e}"))),
             },
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -185,7 +292,10 @@ impl KeyRegistryAbi {
     pub async fn migrator(&self) -> Result<ContractResult<Address>> {
         match self.contract.migrator().call().await {
             Ok(migrator) => Ok(ContractResult::Success(migrator)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -193,7 +303,10 @@ impl KeyRegistryAbi {
     pub async fn is_migrated(&self) -> Result<ContractResult<bool>> {
         match self.contract.is_migrated().call().await {
             Ok(is_migrated) => Ok(ContractResult::Success(is_migrated)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -204,7 +317,10 @@ impl KeyRegistryAbi {
                 let timestamp_u64 = timestamp;
                 Ok(ContractResult::Success(timestamp_u64))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -212,7 +328,40 @@ impl KeyRegistryAbi {
     pub async fn version(&self) -> Result<ContractResult<String>> {
         match self.contract.version().call().await {
             Ok(version) => Ok(ContractResult::Success(version)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::policy::install_for_test;
+    use crate::core::policy::only_fid;
+
+    /// A registry whose RPC endpoint refuses connections
+    fn offline_registry() -> KeyRegistryAbi {
+        let provider = Provider::<Http>::try_from("http://127.0.0.1:1").unwrap();
+        KeyRegistryAbi::new(provider, Address::repeat_byte(0x11)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_remove_checks_policy_first() {
+        let _policy = install_for_test(only_fid(3));
+        let error = offline_registry().remove(4, vec![9; 32]).await.unwrap_err();
+        assert!(error.to_string().contains("Blocked by policy"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_remove_for_checks_policy_first() {
+        let _policy = install_for_test(only_fid(3));
+        let error = offline_registry()
+            .remove_for(4, Address::repeat_byte(0x22), vec![9; 32], 1, vec![0; 65])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Blocked by policy"), "{error}");
+    }
+}
@@ -0,0 +1,136 @@
+//! On-chain checks before deleting local key material
+//!
+//! Deleting the only copy of a custody key orphans its FID, and deleting a
+//! registered signer leaves a key on-chain nobody can use or rotate away from
+//! with the local tooling. These checks tell whether a key is still in use so
+//! deletion can be refused unless forced.
+
+#![cfg(not(doctest))]
+
+use std::fmt;
+
+use anyhow::Result;
+use ethers::types::Address;
+
+use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+use crate::farcaster::contracts::key_listing::KEY_STATE_ADDED;
+use crate::farcaster::contracts::types::ContractResult;
+use crate::farcaster::contracts::types::Fid;
+
+/// An on-chain role a key still holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyInUse {
+    /// The key's address is the custody address of a FID
+    Custody { fid: Fid },
+    /// The key is an added signer of a FID in the KeyRegistry
+    Signer { fid: Fid },
+}
+
+impl fmt::Display for KeyInUse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custody { fid } => write!(
+                f,
+                "custody address of FID {fid}; without it the FID can only be recovered"
+            ),
+            Self::Signer { fid } => write!(
+                f,
+                "registered signer of FID {fid}; it stays valid on-chain after deletion"
+            ),
+        }
+    }
+}
+
+/// Outcome of checking a key before deleting it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeletionCheck {
+    /// Roles the key still holds
+    pub in_use: Vec<KeyInUse>,
+    /// Checks that could not be made, e.g. because the RPC was unreachable
+    pub unverified: Vec<String>,
+}
+
+impl DeletionCheck {
+    /// Whether the key is known to hold no on-chain role
+    pub fn is_safe(&self) -> bool {
+        self.in_use.is_empty() && self.unverified.is_empty()
+    }
+
+    /// A check that could not be made at all
+    pub fn unverified(reason: impl Into<String>) -> Self {
+        Self {
+            in_use: Vec::new(),
+            unverified: vec![reason.into()],
+        }
+    }
+}
+
+impl FarcasterContractClient {
+    /// FID whose custody address is `address`, if any
+    ///
+    /// Unlike [`FarcasterContractClient::address_has_fid`] a failed call is an
+    /// error rather than "no FID".
+    pub async fn custody_fid_of(&self, address: Address) -> Result<Option<Fid>> {
+        match self.id_registry.id_of(address).await? {
+            ContractResult::Success(0) => Ok(None),
+            ContractResult::Success(fid) => Ok(Some(fid)),
+            ContractResult::Error(e) => Err(anyhow::anyhow!("Failed to read IdRegistry: {e}")),
+        }
+    }
+
+    /// Whether `public_key` is an added key of `fid` in the KeyRegistry
+    pub async fn is_active_key(&self, fid: Fid, public_key: &[u8]) -> Result<bool> {
+        match self
+            .key_registry
+            .key_data_of(fid, public_key.to_vec())
+            .await?
+        {
+            ContractResult::Success((state, _)) => Ok(state == KEY_STATE_ADDED),
+            ContractResult::Error(e) => Err(anyhow::anyhow!("Failed to read KeyRegistry: {e}")),
+        }
+    }
+
+    /// Check whether an Ethereum key is still the custody key of a FID
+    pub async fn check_custody_deletion(&self, address: Address) -> DeletionCheck {
+        let mut check = DeletionCheck::default();
+        match self.custody_fid_of(address).await {
+            Ok(Some(fid)) => check.in_use.push(KeyInUse::Custody { fid }),
+            Ok(None) => {}
+            Err(e) => check
+                .unverified
+                .push(format!("custody of {address:?}: {e}")),
+        }
+        check
+    }
+
+    /// Check whether an Ed25519 signer is still registered for a FID
+    pub async fn check_signer_deletion(&self, fid: Fid, public_key: &[u8]) -> DeletionCheck {
+        let mut check = DeletionCheck::default();
+        match self.is_active_key(fid, public_key).await {
+            Ok(true) => check.in_use.push(KeyInUse::Signer { fid }),
+            Ok(false) => {}
+            Err(e) => check.unverified.push(format!("signer of FID {fid}: {e}")),
+        }
+        check
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deletion_check() {
+        assert!(DeletionCheck::default().is_safe());
+        assert!(!DeletionCheck::unverified("RPC unreachable").is_safe());
+
+        let check = DeletionCheck {
+            in_use: vec![KeyInUse::Custody { fid: 3 }],
+            unverified: Vec::new(),
+        };
+        assert!(!check.is_safe());
+        assert!(check.in_use[0]
+            .to_string()
+            .starts_with("custody address of FID 3"));
+    }
+}
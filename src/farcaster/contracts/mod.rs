@@ -1,8 +1,30 @@
 // Core client modules
+#[cfg(not(doctest))]
+pub mod adapters;
+#[cfg(not(doctest))]
+pub mod auth_address;
 pub mod contract_client;
+#[cfg(any(test, feature = "dev"))]
+pub mod dev_faucet;
+#[cfg(not(doctest))]
+pub mod errors;
+#[cfg(not(doctest))]
+pub mod key_listing;
+#[cfg(not(doctest))]
+pub mod key_safety;
 pub mod key_utils;
+#[cfg(not(doctest))]
+pub mod multicall;
 pub mod nonce_manager;
+#[cfg(not(doctest))]
+pub mod offline_tx;
 pub mod security;
+#[cfg(not(doctest))]
+pub mod sponsored;
+#[cfg(not(doctest))]
+pub mod status_monitor;
+#[cfg(not(doctest))]
+pub mod tier_registry;
 pub mod types;
 
 // ABI modules
@@ -26,6 +48,8 @@ pub mod generated;
 
 // Re-export main types and clients
 pub use contract_client::FarcasterContractClient;
+#[cfg(any(test, feature = "dev"))]
+pub use dev_faucet::DevFaucet;
 pub use types::ContractAddresses;
 pub use types::ContractResult;
 pub use types::FidInfo;
@@ -0,0 +1,300 @@
+//! Batched contract reads through Multicall3
+//!
+//! Reading a FID's custody, recovery and key counts takes five `eth_call`s.
+//! Multicall3 (deployed at the same address on Optimism and most other chains)
+//! aggregates them, so a single FID costs one round trip and bulk lookups cost
+//! one round trip per [`MAX_FIDS_PER_MULTICALL`] FIDs.
+
+#![cfg(not(doctest))]
+
+use anyhow::Result;
+use ethers::abi::Token;
+use ethers::contract::Multicall;
+use ethers::contract::MULTICALL_ADDRESS;
+use ethers::types::Address;
+use ethers::types::Bytes;
+use ethers::types::U256;
+
+use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+use crate::farcaster::contracts::types::Fid;
+use crate::farcaster::contracts::types::FidInfo;
+use crate::farcaster::contracts::types::FidOwnership;
+
+/// Address of the Multicall3 contract
+pub const MULTICALL3_ADDRESS: Address = MULTICALL_ADDRESS;
+
+/// FIDs read per Multicall3 request, keeping the aggregated call well below RPC gas caps
+pub const MAX_FIDS_PER_MULTICALL: usize = 100;
+
+/// FIDs read per Multicall3 request by [`FarcasterContractClient::scan_fid_ownership`]
+///
+/// A scan only makes two calls per FID, so it fits more FIDs per request.
+pub const MAX_FIDS_PER_SCAN_MULTICALL: usize = 250;
+
+/// Calls made per FID: custody, recovery and the three key state counts
+const CALLS_PER_FID: usize = 5;
+
+/// Calls made per FID in a scan: custody and recovery
+const SCAN_CALLS_PER_FID: usize = 2;
+
+/// KeyRegistry key states counted in [`FidInfo`]: active, inactive, pending
+const KEY_STATES: [u8; 3] = [0, 1, 2];
+
+type CallResult = std::result::Result<Token, Bytes>;
+
+impl FarcasterContractClient {
+    /// Get FID information for many FIDs with batched Multicall3 reads
+    ///
+    /// # Arguments
+    /// * `fids` - The Farcaster IDs to look up
+    ///
+    /// # Returns
+    /// * `Result<Vec<FidInfo>>` - One entry per FID, in the order given, or an error
+    pub async fn get_fid_info_batch(&self, fids: &[Fid]) -> Result<Vec<FidInfo>> {
+        let mut infos = Vec::with_capacity(fids.len());
+        for chunk in fids.chunks(MAX_FIDS_PER_MULTICALL) {
+            infos.extend(self.multicall_fid_info(chunk).await?);
+        }
+        Ok(infos)
+    }
+
+    /// Read FID information for one chunk of FIDs in a single `eth_call`
+    async fn multicall_fid_info(&self, fids: &[Fid]) -> Result<Vec<FidInfo>> {
+        let mut multicall = Multicall::new(self.provider.clone(), Some(MULTICALL3_ADDRESS))
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create multicall: {}", e))?;
+
+        let id_registry = self.id_registry.contract();
+        let key_registry = self.key_registry.contract();
+        for &fid in fids {
+            multicall
+                .add_call(id_registry.custody_of(U256::from(fid)), true)
+                .add_call(id_registry.recovery_of(U256::from(fid)), true);
+            for state in KEY_STATES {
+                multicall.add_call(key_registry.total_keys(U256::from(fid), state), true);
+            }
+        }
+
+        let results = multicall
+            .call_raw()
+            .await
+            .map_err(|e| anyhow::anyhow!("Multicall3 request failed: {}", e))?;
+        if results.len() != fids.len() * CALLS_PER_FID {
+            anyhow::bail!(
+                "Multicall3 returned {} results for {} calls",
+                results.len(),
+                fids.len() * CALLS_PER_FID
+            );
+        }
+
+        fids.iter()
+            .zip(results.chunks(CALLS_PER_FID))
+            .map(|(&fid, results)| fid_info_from_results(fid, results))
+            .collect()
+    }
+}
+
+impl FarcasterContractClient {
+    /// Read the custody and recovery addresses of up to
+    /// [`MAX_FIDS_PER_SCAN_MULTICALL`] FIDs in a single `eth_call`
+    ///
+    /// FIDs that were never registered come back with zero addresses.
+    ///
+    /// # Arguments
+    /// * `fids` - The Farcaster IDs to read
+    ///
+    /// # Returns
+    /// * `Result<Vec<FidOwnership>>` - One entry per FID, in the order given, or an error
+    pub async fn scan_fid_ownership(&self, fids: &[Fid]) -> Result<Vec<FidOwnership>> {
+        let mut multicall = Multicall::new(self.provider.clone(), Some(MULTICALL3_ADDRESS))
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create multicall: {}", e))?;
+
+        let id_registry = self.id_registry.contract();
+        for &fid in fids {
+            multicall
+                .add_call(id_registry.custody_of(U256::from(fid)), true)
+                .add_call(id_registry.recovery_of(U256::from(fid)), true);
+        }
+
+        let results = multicall
+            .call_raw()
+            .await
+            .map_err(|e| anyhow::anyhow!("Multicall3 request failed: {}", e))?;
+        if results.len() != fids.len() * SCAN_CALLS_PER_FID {
+            anyhow::bail!(
+                "Multicall3 returned {} results for {} calls",
+                results.len(),
+                fids.len() * SCAN_CALLS_PER_FID
+            );
+        }
+
+        fids.iter()
+            .zip(results.chunks(SCAN_CALLS_PER_FID))
+            .map(|(&fid, results)| ownership_from_results(fid, results))
+            .collect()
+    }
+}
+
+/// Which FIDs a range scan reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanFilter {
+    /// Every registered FID
+    All,
+    /// Registered FIDs without a recovery address
+    NoRecovery,
+    /// Registered FIDs whose recovery address is their custody address
+    RecoveryIsCustody,
+    /// Registered FIDs that verified their own custody address on the hub
+    CustodyVerified,
+    /// FIDs in the range that have not been registered
+    Unregistered,
+}
+
+impl ScanFilter {
+    /// Names accepted by [`ScanFilter::from_str`](std::str::FromStr)
+    pub const NAMES: [&'static str; 5] = [
+        "all",
+        "no-recovery",
+        "recovery-is-custody",
+        "custody-verified",
+        "unregistered",
+    ];
+
+    /// Whether matching also needs the FID's verified addresses from a hub
+    pub fn needs_verifications(&self) -> bool {
+        matches!(self, Self::CustodyVerified)
+    }
+
+    /// Check the on-chain part of the filter
+    ///
+    /// For [`ScanFilter::CustodyVerified`] this only checks that the FID is
+    /// registered; the verified addresses are checked separately.
+    pub fn matches(&self, ownership: &FidOwnership) -> bool {
+        let registered = ownership.is_registered();
+        match self {
+            Self::All | Self::CustodyVerified => registered,
+            Self::NoRecovery => registered && ownership.recovery.is_zero(),
+            Self::RecoveryIsCustody => registered && ownership.recovery == ownership.custody,
+            Self::Unregistered => !registered,
+        }
+    }
+}
+
+impl std::str::FromStr for ScanFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "all" => Ok(Self::All),
+            "no-recovery" => Ok(Self::NoRecovery),
+            "recovery-is-custody" => Ok(Self::RecoveryIsCustody),
+            "custody-verified" => Ok(Self::CustodyVerified),
+            "unregistered" => Ok(Self::Unregistered),
+            other => anyhow::bail!(
+                "Unknown scan filter '{}' (expected one of: {})",
+                other,
+                Self::NAMES.join(", ")
+            ),
+        }
+    }
+}
+
+/// Read custody and recovery from the first two call results of one FID
+fn ownership_from_results(fid: Fid, results: &[CallResult]) -> Result<FidOwnership> {
+    let address = |index: usize, what: &str| -> Result<Address> {
+        match &results[index] {
+            Ok(Token::Address(address)) => Ok(*address),
+            other => Err(anyhow::anyhow!(
+                "Failed to get {} address for FID {}: {:?}",
+                what,
+                fid,
+                other
+            )),
+        }
+    };
+    Ok(FidOwnership {
+        fid,
+        custody: address(0, "custody")?,
+        recovery: address(1, "recovery")?,
+    })
+}
+
+/// Assemble a [`FidInfo`] from the five call results of one FID
+///
+/// Custody and recovery must succeed; a failed key count reads as zero, like
+/// the sequential lookup does.
+fn fid_info_from_results(fid: Fid, results: &[CallResult]) -> Result<FidInfo> {
+    let ownership = ownership_from_results(fid, results)?;
+    let count = |index: usize| -> u64 {
+        match &results[index] {
+            Ok(Token::Uint(count)) => (*count).try_into().unwrap_or(0),
+            _ => 0,
+        }
+    };
+
+    Ok(FidInfo {
+        fid,
+        custody: ownership.custody,
+        recovery: ownership.recovery,
+        active_keys: count(2),
+        inactive_keys: count(3),
+        pending_keys: count(4),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fid_info_from_results() {
+        let custody = Address::repeat_byte(0x11);
+        let results = vec![
+            Ok(Token::Address(custody)),
+            Ok(Token::Address(Address::zero())),
+            Ok(Token::Uint(U256::from(3))),
+            Err(Bytes::default()),
+            Ok(Token::Uint(U256::from(1))),
+        ];
+        let info = fid_info_from_results(42, &results).unwrap();
+        assert_eq!(info.custody, custody);
+        assert_eq!(info.active_keys, 3);
+        assert_eq!(info.inactive_keys, 0);
+        assert_eq!(info.pending_keys, 1);
+
+        let ownership = ownership_from_results(42, &results[..2]).unwrap();
+        assert_eq!(ownership.recovery, Address::zero());
+
+        let mut failed = results;
+        failed[0] = Err(Bytes::default());
+        assert!(fid_info_from_results(42, &failed).is_err());
+    }
+
+    #[test]
+    fn test_scan_filter() {
+        let custody = Address::repeat_byte(0x11);
+        let no_recovery = FidOwnership {
+            fid: 1,
+            custody,
+            recovery: Address::zero(),
+        };
+        let self_recovery = FidOwnership {
+            recovery: custody,
+            ..no_recovery
+        };
+        let unregistered = FidOwnership {
+            custody: Address::zero(),
+            ..no_recovery
+        };
+
+        let filter: ScanFilter = "no-recovery".parse().unwrap();
+        assert!(filter.matches(&no_recovery));
+        assert!(!filter.matches(&self_recovery));
+        assert!(!filter.matches(&unregistered));
+        assert!(ScanFilter::RecoveryIsCustody.matches(&self_recovery));
+        assert!(ScanFilter::Unregistered.matches(&unregistered));
+        assert!(!ScanFilter::All.matches(&unregistered));
+        assert!("bogus".parse::<ScanFilter>().is_err());
+    }
+}
@@ -0,0 +1,759 @@
+//! Air-gapped transactions: build online, sign offline, broadcast online
+//!
+//! [`FarcasterContractClient::build_offline_tx`] reads everything a
+//! transaction needs from the chain (price, nonce, fees and gas) and returns
+//! an [`UnsignedTx`], written to a JSON file. [`UnsignedTx::sign`] needs only
+//! that file and the key, so it can run on a machine that never touches a
+//! network; the [`SignedTx`] it produces carries the raw transaction, which
+//! [`FarcasterContractClient::broadcast_signed_tx`] sends from any online
+//! machine.
+//!
+//! Adding a signer also takes a `SignedKeyRequest` signature from the FID
+//! owner, so its calldata is only encoded when the transaction is signed.
+//!
+//! The signing machine trusts nothing in the file but the operation: before
+//! signing, the recipient, value and calldata are checked against what the
+//! operation encodes, so a file altered on the online machine is refused.
+
+#![cfg(not(doctest))]
+
+use std::fmt;
+
+use anyhow::Result;
+use ethers::abi::Token;
+use ethers::middleware::Middleware;
+use ethers::signers::LocalWallet;
+use ethers::signers::Signer;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::Address;
+use ethers::types::BlockNumber;
+use ethers::types::Bytes;
+use ethers::types::Eip1559TransactionRequest;
+use ethers::types::TransactionReceipt;
+use ethers::types::H256;
+use ethers::types::U256;
+use ethers::utils::rlp::Rlp;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::audit;
+use crate::core::policy;
+use crate::core::utils::finality;
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
+use crate::farcaster::contracts::adapters::AdapterCall;
+use crate::farcaster::contracts::adapters::Adapters;
+use crate::farcaster::contracts::adapters::ContractVersions;
+use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+use crate::farcaster::contracts::errors::simulate_call;
+use crate::farcaster::contracts::types::ContractAddresses;
+use crate::farcaster::contracts::types::ContractResult;
+use crate::farcaster::contracts::types::OPTIMISM_CHAIN_ID;
+
+/// Format version of the transaction files
+pub const TX_FILE_VERSION: u32 = 1;
+
+/// Gas limit for adding a signer, whose call cannot be estimated before the
+/// key request is signed
+pub const SIGNER_ADD_GAS: u64 = 300_000;
+
+/// Ed25519 key type and SignedKeyRequest metadata type in the KeyRegistry
+const ED25519_KEY_TYPE: u32 = 1;
+const SIGNED_KEY_REQUEST_METADATA: u8 = 1;
+
+/// What a transaction does
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum TxOperation {
+    /// Register a FID to the sender
+    Register {
+        recovery: Address,
+        extra_storage: u64,
+    },
+    /// Rent storage units for a FID
+    Rent { fid: u64, units: u64 },
+    /// Add an Ed25519 signer to the sender's FID
+    SignerAdd {
+        fid: u64,
+        key: Bytes,
+        /// Unix time the key request signature expires
+        deadline: u64,
+        /// SignedKeyRequestValidator the request is signed for
+        validator: Address,
+        /// `VERSION()` of the KeyGateway the call is encoded for
+        key_gateway_version: String,
+    },
+    /// Remove a key from the sender's FID
+    SignerRemove { fid: u64, key: Bytes },
+}
+
+impl TxOperation {
+    /// Short name, as recorded in the audit log
+    pub fn name(&self) -> &'static str {
+        match self {
+            TxOperation::Register { .. } => "register",
+            TxOperation::Rent { .. } => "rent",
+            TxOperation::SignerAdd { .. } => "signer_add",
+            TxOperation::SignerRemove { .. } => "signer_remove",
+        }
+    }
+
+    /// The FID the operation acts on, if it exists yet
+    pub fn fid(&self) -> Option<u64> {
+        match self {
+            TxOperation::Register { .. } => None,
+            TxOperation::Rent { fid, .. }
+            | TxOperation::SignerAdd { fid, .. }
+            | TxOperation::SignerRemove { fid, .. } => Some(*fid),
+        }
+    }
+
+    /// The contract the operation calls
+    pub fn contract(&self, addresses: &ContractAddresses) -> Address {
+        match self {
+            TxOperation::Register { .. } => addresses.id_gateway,
+            TxOperation::Rent { .. } => addresses.storage_registry,
+            TxOperation::SignerAdd { .. } => addresses.key_gateway,
+            TxOperation::SignerRemove { .. } => addresses.key_registry,
+        }
+    }
+
+    /// Whether the operation pays the storage price
+    pub fn is_paid(&self) -> bool {
+        matches!(
+            self,
+            TxOperation::Register { .. } | TxOperation::Rent { .. }
+        )
+    }
+
+    /// The call the operation makes, encoded for `adapters`
+    ///
+    /// For `signer_add` the metadata is left empty: it carries the key
+    /// request signature, which only exists once the transaction is signed.
+    pub fn call(&self, adapters: &Adapters) -> AdapterCall {
+        match self {
+            TxOperation::Register {
+                recovery,
+                extra_storage,
+            } => adapters
+                .id_gateway
+                .register(*recovery, (*extra_storage > 0).then_some(*extra_storage)),
+            TxOperation::Rent { fid, units } => adapters.storage_registry.rent(*fid, *units),
+            TxOperation::SignerAdd { key, .. } => adapters.key_gateway.add(
+                ED25519_KEY_TYPE,
+                key.clone(),
+                SIGNED_KEY_REQUEST_METADATA,
+                Bytes::new(),
+            ),
+            TxOperation::SignerRemove { key, .. } => adapters.key_registry.remove(key.clone()),
+        }
+    }
+}
+
+impl fmt::Display for TxOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxOperation::Register {
+                recovery,
+                extra_storage,
+            } => write!(
+                f,
+                "Register a FID with recovery address {recovery:?} and {extra_storage} extra storage units"
+            ),
+            TxOperation::Rent { fid, units } => {
+                write!(f, "Rent {units} storage units for FID {fid}")
+            }
+            TxOperation::SignerAdd { fid, key, .. } => {
+                write!(f, "Add signer 0x{} to FID {fid}", hex::encode(key))
+            }
+            TxOperation::SignerRemove { fid, key } => {
+                write!(f, "Remove key 0x{} from FID {fid}", hex::encode(key))
+            }
+        }
+    }
+}
+
+/// A transaction built online, waiting for an offline signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsignedTx {
+    pub version: u32,
+    #[serde(flatten)]
+    pub operation: TxOperation,
+    pub chain_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+    /// Calldata; empty for `signer_add`, which is encoded when signing
+    pub data: Bytes,
+    pub nonce: U256,
+    pub gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+impl UnsignedTx {
+    /// Parse a transaction file written by `tx build`
+    pub fn from_json(json: &str) -> Result<Self> {
+        let tx: Self = serde_json::from_str(json)
+            .map_err(|e| anyhow::anyhow!("Not an unsigned transaction file: {e}"))?;
+        check_version(tx.version)?;
+        Ok(tx)
+    }
+
+    /// The most the transaction can cost: value plus gas at the fee cap
+    pub fn max_cost(&self) -> Result<U256> {
+        self.gas
+            .checked_mul(self.max_fee_per_gas)
+            .and_then(|fees| fees.checked_add(self.value))
+            .ok_or_else(|| anyhow::anyhow!("The transaction's maximum cost overflows"))
+    }
+
+    /// The contract call in `data`, decoded against the operation's function
+    ///
+    /// For `signer_add`, whose calldata is encoded when signing, this is the
+    /// call that will be signed, with its metadata still empty.
+    pub fn decode_call(&self) -> Result<AdapterCall> {
+        let expected = self.operation.call(&self.adapters());
+        if matches!(self.operation, TxOperation::SignerAdd { .. }) {
+            return Ok(expected);
+        }
+        let function = expected.function;
+        let selector = function.short_signature();
+        if self.data.len() < 4 || self.data[..4] != selector {
+            anyhow::bail!(
+                "The calldata does not call {} as {} does",
+                function.signature(),
+                self.operation.name()
+            );
+        }
+        let args = function
+            .decode_input(&self.data[4..])
+            .map_err(|e| anyhow::anyhow!("The calldata does not decode: {e}"))?;
+        Ok(AdapterCall { function, args })
+    }
+
+    /// Check the chain, recipient, value, fees and calldata against the
+    /// operation
+    ///
+    /// Only the operation is shown to whoever signs, so everything else in
+    /// the file must follow from it: the chain the contracts are deployed
+    /// on, the contract the operation calls, no value unless it pays for
+    /// storage, a maximum cost within the policy's limit, a priority fee no
+    /// higher than the fee cap, and calldata that is exactly the operation's
+    /// call.
+    pub fn check(&self) -> Result<()> {
+        if self.chain_id != OPTIMISM_CHAIN_ID {
+            anyhow::bail!(
+                "The transaction is for chain {}, not Optimism ({OPTIMISM_CHAIN_ID})",
+                self.chain_id
+            );
+        }
+        let expected_to = self.operation.contract(&ContractAddresses::default());
+        if self.to != expected_to {
+            anyhow::bail!(
+                "The transaction is sent to {:?}, but {} calls {expected_to:?}",
+                self.to,
+                self.operation.name()
+            );
+        }
+
+        if self.operation.is_paid() {
+            if self.value.is_zero() {
+                anyhow::bail!("{} must pay the storage price", self.operation.name());
+            }
+        } else if !self.value.is_zero() {
+            anyhow::bail!(
+                "The transaction sends {} wei, but {} is not paid",
+                self.value,
+                self.operation.name()
+            );
+        }
+        if self.max_priority_fee_per_gas > self.max_fee_per_gas {
+            anyhow::bail!(
+                "The priority fee of {} wei is above the fee cap of {} wei",
+                self.max_priority_fee_per_gas,
+                self.max_fee_per_gas
+            );
+        }
+        policy::current().check_transaction(self.operation.fid(), self.max_cost()?)?;
+
+        if matches!(self.operation, TxOperation::SignerAdd { .. }) {
+            if !self.data.is_empty() {
+                anyhow::bail!(
+                    "signer_add calldata is encoded when signing; the file must not carry any"
+                );
+            }
+            return Ok(());
+        }
+        let expected = self.operation.call(&self.adapters()).calldata()?;
+        if self.data != expected {
+            anyhow::bail!(
+                "The calldata does not match the {} operation: {}",
+                self.operation.name(),
+                self.operation
+            );
+        }
+        Ok(())
+    }
+
+    fn adapters(&self) -> Adapters {
+        let versions = match &self.operation {
+            TxOperation::SignerAdd {
+                key_gateway_version,
+                ..
+            } => ContractVersions {
+                key_gateway: Some(key_gateway_version.clone()),
+                ..Default::default()
+            },
+            _ => ContractVersions::default(),
+        };
+        Adapters::select(self.chain_id, &versions)
+    }
+
+    /// Sign the transaction with the sender's key
+    ///
+    /// No network access is needed. The file is [checked](Self::check)
+    /// first, then for `signer_add` the key request is signed and the `add`
+    /// call encoded with it.
+    ///
+    /// # Arguments
+    /// * `wallet` - The key of `from`
+    pub async fn sign(&self, wallet: &LocalWallet) -> Result<SignedTx> {
+        self.check()?;
+        if wallet.address() != self.from {
+            anyhow::bail!(
+                "The transaction is from {:?}, but the key is for {:?}",
+                self.from,
+                wallet.address()
+            );
+        }
+        let wallet = wallet.clone().with_chain_id(self.chain_id);
+
+        let data = match &self.operation {
+            TxOperation::SignerAdd {
+                fid,
+                key,
+                deadline,
+                validator,
+                ..
+            } => {
+                let typed_data = FarcasterContractClient::create_signed_key_request_typed_data(
+                    *fid,
+                    key,
+                    *deadline,
+                    *validator,
+                    self.chain_id,
+                )?;
+                let signature = {
+                    let _timer = metrics::start(Phase::Signing, "EIP-712 typed data");
+                    wallet.sign_typed_data(&typed_data).await?
+                };
+                audit::record_typed_data(wallet.address(), &typed_data);
+
+                let metadata =
+                    encode_key_request_metadata(*fid, self.from, &signature.to_vec(), *deadline);
+                self.adapters()
+                    .key_gateway
+                    .add(
+                        ED25519_KEY_TYPE,
+                        key.clone(),
+                        SIGNED_KEY_REQUEST_METADATA,
+                        metadata,
+                    )
+                    .calldata()?
+            }
+            _ => self.data.clone(),
+        };
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(self.from)
+            .to(self.to)
+            .value(self.value)
+            .data(data)
+            .nonce(self.nonce)
+            .gas(self.gas)
+            .max_fee_per_gas(self.max_fee_per_gas)
+            .max_priority_fee_per_gas(self.max_priority_fee_per_gas)
+            .chain_id(self.chain_id)
+            .into();
+        let signature = {
+            let _timer = metrics::start(Phase::Signing, "transaction");
+            wallet.sign_transaction_sync(&tx)?
+        };
+        let hash = tx.hash(&signature);
+        audit::record_eth_signature(
+            wallet.address(),
+            &format!("tx:{}", self.operation.name()),
+            hash,
+        );
+
+        Ok(SignedTx {
+            version: TX_FILE_VERSION,
+            operation: self.operation.clone(),
+            chain_id: self.chain_id,
+            from: self.from,
+            hash,
+            raw: tx.rlp_signed(&signature),
+        })
+    }
+}
+
+/// A signed transaction, ready to broadcast
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTx {
+    pub version: u32,
+    #[serde(flatten)]
+    pub operation: TxOperation,
+    pub chain_id: u64,
+    pub from: Address,
+    pub hash: H256,
+    /// The signed, RLP-encoded transaction
+    pub raw: Bytes,
+}
+
+impl SignedTx {
+    /// Parse a transaction file written by `tx sign`
+    pub fn from_json(json: &str) -> Result<Self> {
+        let tx: Self = serde_json::from_str(json)
+            .map_err(|e| anyhow::anyhow!("Not a signed transaction file: {e}"))?;
+        check_version(tx.version)?;
+        Ok(tx)
+    }
+
+    /// Decode the raw transaction and check it against the file's fields
+    ///
+    /// # Returns
+    /// * `Result<TypedTransaction>` - The transaction, if it is signed by
+    ///   `from` for `chain_id` and hashes to `hash`
+    pub fn verify(&self) -> Result<TypedTransaction> {
+        let (tx, signature) = TypedTransaction::decode_signed(&Rlp::new(&self.raw))
+            .map_err(|e| anyhow::anyhow!("Raw transaction does not decode: {e}"))?;
+        let signer = signature.recover(tx.sighash())?;
+        if signer != self.from {
+            anyhow::bail!("Transaction is signed by {signer:?}, not {:?}", self.from);
+        }
+        if tx.chain_id().map(|id| id.as_u64()) != Some(self.chain_id) {
+            anyhow::bail!("Transaction is not signed for chain {}", self.chain_id);
+        }
+        if tx.hash(&signature) != self.hash {
+            anyhow::bail!("Transaction hash does not match {:?}", self.hash);
+        }
+        Ok(tx)
+    }
+}
+
+fn check_version(version: u32) -> Result<()> {
+    if version != TX_FILE_VERSION {
+        anyhow::bail!(
+            "Transaction file version {version} is not supported (expected {TX_FILE_VERSION})"
+        );
+    }
+    Ok(())
+}
+
+/// ABI-encode `SignedKeyRequestMetadata`, as the validator's
+/// `encodeMetadata` would
+fn encode_key_request_metadata(fid: u64, owner: Address, signature: &[u8], deadline: u64) -> Bytes {
+    ethers::abi::encode(&[Token::Tuple(vec![
+        Token::Uint(fid.into()),
+        Token::Address(owner),
+        Token::Bytes(signature.to_vec()),
+        Token::Uint(deadline.into()),
+    ])])
+    .into()
+}
+
+impl FarcasterContractClient {
+    /// A signer-add operation for this chain's validator and KeyGateway
+    ///
+    /// # Arguments
+    /// * `fid` - The FID to add the signer to
+    /// * `key` - The Ed25519 public key
+    /// * `deadline` - Unix time the key request signature expires
+    pub async fn signer_add_operation(
+        &self,
+        fid: u64,
+        key: Bytes,
+        deadline: u64,
+    ) -> Result<TxOperation> {
+        Ok(TxOperation::SignerAdd {
+            fid,
+            key,
+            deadline,
+            validator: self.addresses.signed_key_request_validator,
+            key_gateway_version: self.adapters().await?.key_gateway.version().to_string(),
+        })
+    }
+
+    /// Build an unsigned transaction for `from` to sign offline
+    ///
+    /// The call is simulated and its gas estimated with 20% headroom, except
+    /// for `signer_add`, which gets [`SIGNER_ADD_GAS`].
+    ///
+    /// # Arguments
+    /// * `from` - The address that will sign and pay
+    /// * `operation` - What the transaction does
+    /// * `gas_limit` - Gas limit to use instead of the estimate
+    pub async fn build_offline_tx(
+        &self,
+        from: Address,
+        operation: TxOperation,
+        gas_limit: Option<u64>,
+    ) -> Result<UnsignedTx> {
+        let adapters = self.adapters().await?;
+        if let Some(fid) = operation.fid() {
+            if !matches!(operation, TxOperation::Rent { .. }) {
+                self.check_offline_custody(fid, from).await?;
+            }
+        }
+        let value = match &operation {
+            TxOperation::Register { extra_storage, .. } => {
+                if let Some(fid) = self.address_has_fid(from).await? {
+                    anyhow::bail!("{from:?} already owns FID {fid}");
+                }
+                match *extra_storage {
+                    0 => self.get_registration_price().await?,
+                    extra => self.get_storage_price(1 + extra).await?,
+                }
+            }
+            TxOperation::Rent { units, .. } => self.get_storage_price(*units).await?,
+            TxOperation::SignerAdd { .. } | TxOperation::SignerRemove { .. } => U256::zero(),
+        };
+        let to = operation.contract(&self.addresses);
+        let call =
+            (!matches!(operation, TxOperation::SignerAdd { .. })).then(|| operation.call(adapters));
+        policy::current().check_transaction(operation.fid(), value)?;
+
+        let (data, estimate) = match call {
+            Some(call) => {
+                let bound = call
+                    .bind::<_, Token>(to, self.provider.clone())?
+                    .from(from)
+                    .value(value);
+                simulate_call(&bound)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("The transaction would fail: {e}"))?;
+                let gas = bound.estimate_gas().await?;
+                (call.calldata()?, gas * 12 / 10)
+            }
+            None => (Bytes::new(), U256::from(SIGNER_ADD_GAS)),
+        };
+        let nonce = self
+            .provider
+            .get_transaction_count(from, Some(BlockNumber::Pending.into()))
+            .await?;
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            self.provider.estimate_eip1559_fees(None).await?;
+
+        Ok(UnsignedTx {
+            version: TX_FILE_VERSION,
+            operation,
+            chain_id: adapters.chain_id,
+            from,
+            to,
+            value,
+            data,
+            nonce,
+            gas: gas_limit.map(U256::from).unwrap_or(estimate),
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        })
+    }
+
+    /// Broadcast a transaction signed offline and wait for it to be final
+    ///
+    /// The raw transaction is checked against the file and the connected
+    /// chain before it is sent.
+    pub async fn broadcast_signed_tx(&self, signed: &SignedTx) -> Result<TransactionReceipt> {
+        signed.verify()?;
+        let chain_id = self.provider.get_chainid().await?.as_u64();
+        if chain_id != signed.chain_id {
+            anyhow::bail!(
+                "Transaction is for chain {}, but the RPC endpoint is on chain {chain_id}",
+                signed.chain_id
+            );
+        }
+
+        let pending = self
+            .provider
+            .send_raw_transaction(signed.raw.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!("Broadcast failed: {e}"))?;
+        audit::record_transaction(Some(signed.from), signed.operation.name(), signed.hash);
+        let receipt = pending
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Transaction {:?} was dropped", signed.hash))?;
+        let receipt = finality::confirm(&self.provider, receipt).await?;
+        if receipt.status != Some(1.into()) {
+            anyhow::bail!("Transaction {:?} reverted", signed.hash);
+        }
+        Ok(receipt)
+    }
+
+    async fn check_offline_custody(&self, fid: u64, from: Address) -> Result<()> {
+        match self.id_registry.custody_of(fid).await? {
+            ContractResult::Success(custody) if custody == from => Ok(()),
+            ContractResult::Success(custody) => anyhow::bail!(
+                "FID {fid} is owned by {custody:?}; only its custody address can change its keys"
+            ),
+            ContractResult::Error(e) => {
+                anyhow::bail!("Could not read the custody address of FID {fid}: {e}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unsigned(operation: TxOperation, from: Address) -> UnsignedTx {
+        let to = operation.contract(&ContractAddresses::default());
+        let value = if operation.is_paid() { 1000 } else { 0 };
+        let data = match operation {
+            TxOperation::SignerAdd { .. } => Bytes::new(),
+            _ => operation
+                .call(&Adapters::select(10, &ContractVersions::default()))
+                .calldata()
+                .unwrap(),
+        };
+        UnsignedTx {
+            version: TX_FILE_VERSION,
+            operation,
+            chain_id: 10,
+            from,
+            to,
+            value: U256::from(value),
+            data,
+            nonce: U256::from(7),
+            gas: U256::from(100_000),
+            max_fee_per_gas: U256::from(2_000_000),
+            max_priority_fee_per_gas: U256::from(1000),
+        }
+    }
+
+    #[test]
+    fn test_tx_file_round_trip() {
+        let tx = unsigned(TxOperation::Rent { fid: 3, units: 2 }, Address::zero());
+        let json = serde_json::to_string_pretty(&tx).unwrap();
+        assert!(json.contains("\"operation\": \"rent\""));
+        assert_eq!(UnsignedTx::from_json(&json).unwrap(), tx);
+        assert_eq!(
+            tx.max_cost().unwrap(),
+            U256::from(1000 + 100_000 * 2_000_000u64)
+        );
+
+        let old = json.replace("\"version\": 1", "\"version\": 99");
+        assert!(UnsignedTx::from_json(&old).is_err());
+        assert!(SignedTx::from_json(&json).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_verify() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let tx = unsigned(TxOperation::Rent { fid: 3, units: 2 }, wallet.address());
+        let signed = tx.sign(&wallet).await.unwrap();
+        let decoded = signed.verify().unwrap();
+        assert_eq!(decoded.nonce(), Some(&U256::from(7)));
+        assert_eq!(decoded.data(), Some(&tx.data));
+
+        let mut wrong_chain = signed.clone();
+        wrong_chain.chain_id = 1;
+        assert!(wrong_chain.verify().is_err());
+        let other = LocalWallet::new(&mut rand::thread_rng());
+        assert!(tx.sign(&other).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_signer_add() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let operation = TxOperation::SignerAdd {
+            fid: 3,
+            key: Bytes::from(vec![9; 32]),
+            deadline: 1_900_000_000,
+            validator: Address::repeat_byte(0x33),
+            key_gateway_version: "2023.11.15".to_string(),
+        };
+        let tx = unsigned(operation, wallet.address());
+        let decoded = tx.sign(&wallet).await.unwrap().verify().unwrap();
+        assert_eq!(
+            &decoded.data().unwrap()[..4],
+            &ethers::utils::id("add(uint32,bytes,uint8,bytes)")[..]
+        );
+
+        let mut with_data = tx.clone();
+        with_data.data = Bytes::from(vec![1, 2, 3]);
+        assert!(with_data.sign(&wallet).await.is_err());
+    }
+
+    #[test]
+    fn test_decode_call() {
+        let tx = unsigned(TxOperation::Rent { fid: 3, units: 2 }, Address::zero());
+        let call = tx.decode_call().unwrap();
+        assert_eq!(call.function.name, "rent");
+        assert_eq!(
+            call.args,
+            vec![Token::Uint(3.into()), Token::Uint(2.into())]
+        );
+
+        let mut garbage = tx.clone();
+        garbage.data = Bytes::from(vec![1, 2, 3]);
+        assert!(garbage.decode_call().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_rejects_tampered_files() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let rent = unsigned(TxOperation::Rent { fid: 3, units: 2 }, wallet.address());
+        rent.check().unwrap();
+
+        let mut to = rent.clone();
+        to.to = Address::repeat_byte(0x22);
+        assert!(to.sign(&wallet).await.is_err());
+
+        let mut unpaid = rent.clone();
+        unpaid.value = U256::zero();
+        assert!(unpaid.sign(&wallet).await.is_err());
+
+        let mut data = rent.clone();
+        data.data = TxOperation::Rent { fid: 4, units: 2 }
+            .call(&Adapters::select(10, &ContractVersions::default()))
+            .calldata()
+            .unwrap();
+        assert!(data.decode_call().is_ok());
+        assert!(data.sign(&wallet).await.is_err());
+
+        let remove = TxOperation::SignerRemove {
+            fid: 3,
+            key: Bytes::from(vec![9; 32]),
+        };
+        let mut value = unsigned(remove, wallet.address());
+        value.check().unwrap();
+        value.value = U256::from(1);
+        assert!(value.sign(&wallet).await.is_err());
+
+        let mut chain = rent.clone();
+        chain.chain_id = 1;
+        assert!(chain.sign(&wallet).await.is_err());
+
+        let mut tip = rent.clone();
+        tip.max_priority_fee_per_gas = tip.max_fee_per_gas + 1;
+        assert!(tip.sign(&wallet).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_overflowing_fees_are_refused() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let mut tx = unsigned(TxOperation::Rent { fid: 3, units: 2 }, wallet.address());
+        tx.gas = U256::MAX;
+        tx.max_fee_per_gas = U256::MAX;
+        tx.max_priority_fee_per_gas = U256::MAX;
+        assert!(tx.max_cost().is_err());
+        assert!(tx.sign(&wallet).await.is_err());
+
+        tx.gas = U256::from(1);
+        tx.max_fee_per_gas = U256::from(1);
+        tx.value = U256::MAX;
+        assert!(tx.max_cost().is_err());
+    }
+}
@@ -50,6 +50,7 @@ impl FarcasterContractClient {
         let add_result = self
             .key_gateway
             .add_for(
+                target_fid,
                 target_fid_info.custody,
                 1,
                 test_key.to_vec(),
@@ -78,7 +79,13 @@ impl FarcasterContractClient {
                 let target_key = keys[0].clone();
                 let remove_result = self
                     .key_registry
-                    .remove_for(target_fid_info.custody, target_key, deadline, simulated_sig)
+                    .remove_for(
+                        target_fid,
+                        target_fid_info.custody,
+                        target_key,
+                        deadline,
+                        simulated_sig,
+                    )
                     .await?;
 
                 match remove_result {
@@ -95,7 +102,10 @@ impl FarcasterContractClient {
         }
 
         // Test 3: Try direct key removal
-        let remove_direct_result = self.key_registry.remove(test_key.to_vec()).await?;
+        let remove_direct_result = self
+            .key_registry
+            .remove(target_fid, test_key.to_vec())
+            .await?;
         match remove_direct_result {
             ContractResult::Success(_) => {
                 result
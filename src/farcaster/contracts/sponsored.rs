@@ -0,0 +1,658 @@
+//! Gas-sponsored transactions through ERC-4337
+//!
+//! Calls are wrapped in a UserOperation sent from a SimpleAccount smart account
+//! owned by the custody wallet, submitted to a bundler, and paid for by a
+//! paymaster speaking ERC-7677 (`pm_getPaymasterStubData` /
+//! `pm_getPaymasterData`). The custody wallet only signs; it never needs ETH.
+//!
+//! The paymaster covers gas, not call value: registration and storage fees are
+//! still sent from the smart account, so an onboarding service funds
+//! [`SponsoredSender::account_address`] with the fee beforehand. FIDs are
+//! registered with `registerFor`, so the custody address stays the wallet and
+//! not the smart account.
+
+#![cfg(not(doctest))]
+
+use std::str::FromStr;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use anyhow::Result;
+use ethers::abi::ParamType;
+use ethers::abi::Token;
+use ethers::providers::Http;
+use ethers::providers::Middleware;
+use ethers::providers::Provider;
+use ethers::signers::LocalWallet;
+use ethers::signers::Signer;
+use ethers::types::Address;
+use ethers::types::Bytes;
+use ethers::types::TransactionRequest;
+use ethers::types::H256;
+use ethers::types::U256;
+use ethers::utils::hash_message;
+use ethers::utils::id;
+use ethers::utils::keccak256;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::audit;
+use crate::core::policy;
+use crate::core::utils::finality;
+use crate::core::utils::finality::Finality;
+use crate::core::utils::metrics;
+use crate::core::utils::metrics::Phase;
+use crate::farcaster::contracts::adapters::Eip712Domain;
+use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+use crate::farcaster::contracts::types::ContractResult;
+use crate::farcaster::contracts::types::Fid;
+
+/// ERC-4337 EntryPoint v0.6, deployed at the same address on Optimism and Base
+pub const ENTRY_POINT_V06: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+/// SimpleAccountFactory for EntryPoint v0.6
+pub const SIMPLE_ACCOUNT_FACTORY: &str = "0x9406Cc6185a346906296840746125a0E44976454";
+/// Placeholder signature of the right shape, used while estimating gas
+const DUMMY_SIGNATURE: &str = "fffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c";
+/// How often the bundler is asked whether a UserOperation was included
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How long to wait for a UserOperation to be included
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(180);
+/// Lifetime of the `registerFor` signature
+const REGISTER_DEADLINE_SECS: u64 = 3600;
+
+/// Bundler and paymaster endpoints for sponsored transactions
+#[derive(Debug, Clone)]
+pub struct SponsorConfig {
+    pub bundler_url: String,
+    pub paymaster_url: String,
+}
+
+impl SponsorConfig {
+    /// Take endpoints from the command line, falling back to
+    /// `CASTORIX_BUNDLER_URL` and `CASTORIX_PAYMASTER_URL`
+    pub fn resolve(bundler_url: Option<String>, paymaster_url: Option<String>) -> Result<Self> {
+        use crate::consts::env_vars;
+
+        let read = |value: Option<String>, var: &str, flag: &str| {
+            value
+                .or_else(|| std::env::var(var).ok())
+                .filter(|url| !url.trim().is_empty())
+                .ok_or_else(|| anyhow::anyhow!("Sponsored mode needs {flag} or {var}"))
+        };
+        Ok(Self {
+            bundler_url: read(bundler_url, env_vars::BUNDLER_URL, "--bundler-url")?,
+            paymaster_url: read(paymaster_url, env_vars::PAYMASTER_URL, "--paymaster-url")?,
+        })
+    }
+
+    /// Sender for calls on the chain at `rpc_url`, from `owner`'s smart account
+    pub fn sender(&self, rpc_url: &str, owner: LocalWallet) -> Result<SponsoredSender> {
+        SponsoredSender::new(rpc_url, &self.bundler_url, &self.paymaster_url, owner)
+    }
+}
+
+/// An EntryPoint v0.6 UserOperation, serialized as bundlers expect it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    pub init_code: Bytes,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub paymaster_and_data: Bytes,
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// Hash the smart account's owner signs
+    ///
+    /// # Arguments
+    /// * `entry_point` - The EntryPoint the operation is sent to
+    /// * `chain_id` - The chain ID
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> H256 {
+        let packed = ethers::abi::encode(&[
+            Token::Address(self.sender),
+            Token::Uint(self.nonce),
+            Token::FixedBytes(keccak256(&self.init_code).to_vec()),
+            Token::FixedBytes(keccak256(&self.call_data).to_vec()),
+            Token::Uint(self.call_gas_limit),
+            Token::Uint(self.verification_gas_limit),
+            Token::Uint(self.pre_verification_gas),
+            Token::Uint(self.max_fee_per_gas),
+            Token::Uint(self.max_priority_fee_per_gas),
+            Token::FixedBytes(keccak256(&self.paymaster_and_data).to_vec()),
+        ]);
+        H256(keccak256(ethers::abi::encode(&[
+            Token::FixedBytes(keccak256(packed).to_vec()),
+            Token::Address(entry_point),
+            Token::Uint(chain_id.into()),
+        ])))
+    }
+}
+
+/// Outcome of an included UserOperation
+#[derive(Debug, Clone)]
+pub struct SponsoredReceipt {
+    pub user_op_hash: H256,
+    pub transaction_hash: H256,
+}
+
+/// Sends calls as paymaster-sponsored UserOperations from the owner's smart account
+pub struct SponsoredSender {
+    provider: Provider<Http>,
+    bundler: Provider<Http>,
+    paymaster: Provider<Http>,
+    owner: LocalWallet,
+    entry_point: Address,
+    factory: Address,
+}
+
+impl SponsoredSender {
+    /// Create a sender
+    ///
+    /// # Arguments
+    /// * `rpc_url` - RPC URL of the chain the calls are made on
+    /// * `bundler_url` - ERC-4337 bundler RPC URL
+    /// * `paymaster_url` - ERC-7677 paymaster service URL
+    /// * `owner` - Wallet owning the smart account (the custody wallet)
+    pub fn new(
+        rpc_url: &str,
+        bundler_url: &str,
+        paymaster_url: &str,
+        owner: LocalWallet,
+    ) -> Result<Self> {
+        let connect = |url: &str, what: &str| {
+            crate::core::utils::http::provider(url)
+                .map_err(|e| anyhow::anyhow!("Invalid {} URL '{}': {}", what, url, e))
+        };
+        Ok(Self {
+            provider: connect(rpc_url, "RPC")?,
+            bundler: connect(bundler_url, "bundler")?,
+            paymaster: connect(paymaster_url, "paymaster")?,
+            owner,
+            entry_point: Address::from_str(ENTRY_POINT_V06)?,
+            factory: Address::from_str(SIMPLE_ACCOUNT_FACTORY)?,
+        })
+    }
+
+    /// Address of the owner's smart account, deployed or not
+    pub async fn account_address(&self) -> Result<Address> {
+        let data = self
+            .call(
+                self.factory,
+                "getAddress(address,uint256)",
+                &[
+                    Token::Address(self.owner.address()),
+                    Token::Uint(U256::zero()),
+                ],
+            )
+            .await?;
+        let tokens = ethers::abi::decode(&[ParamType::Address], &data)
+            .context("Failed to decode SimpleAccountFactory getAddress() response")?;
+        tokens[0]
+            .clone()
+            .into_address()
+            .ok_or_else(|| anyhow::anyhow!("Malformed getAddress() response"))
+    }
+
+    /// ETH held by the smart account, available for call values
+    pub async fn account_balance(&self) -> Result<U256> {
+        let account = self.account_address().await?;
+        Ok(self.provider.get_balance(account, None).await?)
+    }
+
+    /// Make a call from the smart account with sponsored gas and wait for it to be included
+    ///
+    /// # Arguments
+    /// * `to` - Contract to call
+    /// * `value` - ETH sent with the call, taken from the smart account's balance
+    /// * `data` - Calldata
+    ///
+    /// # Returns
+    /// * `Result<SponsoredReceipt>` - The UserOperation and transaction hashes
+    pub async fn send_call(
+        &self,
+        to: Address,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<SponsoredReceipt> {
+        policy::current().check_value(value)?;
+        let sender = self.account_address().await?;
+        if !value.is_zero() {
+            let balance = self.provider.get_balance(sender, None).await?;
+            if balance < value {
+                anyhow::bail!(
+                    "Smart account {:?} holds {} ETH but the call needs {} ETH; the paymaster only covers gas, fund the account with the fee first",
+                    sender,
+                    ethers::utils::format_ether(balance),
+                    ethers::utils::format_ether(value)
+                );
+            }
+        }
+
+        let chain_id = self.provider.get_chainid().await?.as_u64();
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            self.provider.estimate_eip1559_fees(None).await?;
+        let mut op = UserOperation {
+            sender,
+            nonce: self.account_nonce(sender).await?,
+            init_code: self.init_code(sender).await?,
+            call_data: encode_call(
+                "execute(address,uint256,bytes)",
+                &[Token::Address(to), Token::Uint(value), Token::Bytes(data)],
+            )
+            .into(),
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            signature: hex::decode(DUMMY_SIGNATURE)?.into(),
+            ..Default::default()
+        };
+
+        let chain = format!("{chain_id:#x}");
+        let context = serde_json::json!({});
+        let stub: PaymasterData = self
+            .paymaster
+            .request(
+                "pm_getPaymasterStubData",
+                (&op, self.entry_point, &chain, &context),
+            )
+            .await
+            .context("Paymaster rejected the operation (pm_getPaymasterStubData)")?;
+        op.paymaster_and_data = stub.paymaster_and_data;
+
+        let gas: serde_json::Value = self
+            .bundler
+            .request("eth_estimateUserOperationGas", (&op, self.entry_point))
+            .await
+            .context("Bundler could not estimate the operation's gas")?;
+        op.call_gas_limit = quantity(&gas, "callGasLimit")?;
+        op.verification_gas_limit = quantity(&gas, "verificationGasLimit")?;
+        op.pre_verification_gas = quantity(&gas, "preVerificationGas")?;
+
+        let sponsored: PaymasterData = self
+            .paymaster
+            .request(
+                "pm_getPaymasterData",
+                (&op, self.entry_point, &chain, &context),
+            )
+            .await
+            .context("Paymaster declined to sponsor the operation (pm_getPaymasterData)")?;
+        op.paymaster_and_data = sponsored.paymaster_and_data;
+
+        let hash = op.hash(self.entry_point, chain_id);
+        op.signature = self
+            .owner
+            .sign_message(hash.as_bytes())
+            .await?
+            .to_vec()
+            .into();
+        audit::record_eth_signature(
+            self.owner.address(),
+            "user_operation",
+            hash_message(hash.as_bytes()),
+        );
+
+        let user_op_hash: H256 = self
+            .bundler
+            .request("eth_sendUserOperation", (&op, self.entry_point))
+            .await
+            .context("Bundler rejected the operation")?;
+        audit::record_transaction(Some(self.owner.address()), "user_operation", user_op_hash);
+        println!("   📨 UserOperation submitted: {user_op_hash:?}");
+        self.wait_for_receipt(user_op_hash).await
+    }
+
+    /// Poll the bundler until the operation is included
+    async fn wait_for_receipt(&self, user_op_hash: H256) -> Result<SponsoredReceipt> {
+        let started = Instant::now();
+        loop {
+            let receipt: Option<serde_json::Value> = self
+                .bundler
+                .request("eth_getUserOperationReceipt", [user_op_hash])
+                .await?;
+            if let Some(receipt) = receipt {
+                let transaction_hash = receipt
+                    .pointer("/receipt/transactionHash")
+                    .and_then(|h| h.as_str())
+                    .and_then(|h| H256::from_str(h).ok())
+                    .unwrap_or_default();
+                if receipt.get("success").and_then(|s| s.as_bool()) != Some(true) {
+                    let reason = receipt
+                        .get("reason")
+                        .and_then(|r| r.as_str())
+                        .unwrap_or("no reason given");
+                    anyhow::bail!(
+                        "UserOperation {user_op_hash:?} reverted in transaction {transaction_hash:?}: {reason}"
+                    );
+                }
+                if finality::settings().wait != Finality::Included {
+                    if let Some(receipt) = self
+                        .provider
+                        .get_transaction_receipt(transaction_hash)
+                        .await?
+                    {
+                        finality::confirm(&self.provider, receipt).await?;
+                    }
+                }
+                return Ok(SponsoredReceipt {
+                    user_op_hash,
+                    transaction_hash,
+                });
+            }
+            if started.elapsed() > RECEIPT_TIMEOUT {
+                anyhow::bail!(
+                    "UserOperation {user_op_hash:?} was not included within {}s",
+                    RECEIPT_TIMEOUT.as_secs()
+                );
+            }
+            tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Factory call deploying the account, empty once it is deployed
+    async fn init_code(&self, sender: Address) -> Result<Bytes> {
+        if !self.provider.get_code(sender, None).await?.is_empty() {
+            return Ok(Bytes::new());
+        }
+        let mut init_code = self.factory.as_bytes().to_vec();
+        init_code.extend(encode_call(
+            "createAccount(address,uint256)",
+            &[
+                Token::Address(self.owner.address()),
+                Token::Uint(U256::zero()),
+            ],
+        ));
+        Ok(init_code.into())
+    }
+
+    async fn account_nonce(&self, sender: Address) -> Result<U256> {
+        let data = self
+            .call(
+                self.entry_point,
+                "getNonce(address,uint192)",
+                &[Token::Address(sender), Token::Uint(U256::zero())],
+            )
+            .await?;
+        decode_word(&data)
+    }
+
+    async fn call(&self, to: Address, signature: &str, args: &[Token]) -> Result<Vec<u8>> {
+        let tx = TransactionRequest::new()
+            .to(to)
+            .data(encode_call(signature, args));
+        let data = self
+            .provider
+            .call(&tx.into(), None)
+            .await
+            .map_err(|e| anyhow::anyhow!("{} call failed: {}", signature, e))?;
+        Ok(data.to_vec())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PaymasterData {
+    paymaster_and_data: Bytes,
+}
+
+/// Decode a single `uint256` return value
+fn decode_word(data: &[u8]) -> Result<U256> {
+    if data.len() < 32 {
+        anyhow::bail!("Expected a 32-byte word, got {} bytes", data.len());
+    }
+    Ok(U256::from_big_endian(&data[..32]))
+}
+
+/// Read a gas quantity that bundlers return as a hex string or a number
+fn quantity(value: &serde_json::Value, field: &str) -> Result<U256> {
+    match value.get(field) {
+        Some(serde_json::Value::String(s)) => match s.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).map_err(Into::into),
+            None => U256::from_dec_str(s).map_err(Into::into),
+        },
+        Some(serde_json::Value::Number(n)) => n
+            .as_u64()
+            .map(U256::from)
+            .ok_or_else(|| anyhow::anyhow!("Invalid {field} in gas estimate")),
+        _ => Err(anyhow::anyhow!("Gas estimate has no {field}")),
+    }
+}
+
+fn encode_call(signature: &str, args: &[Token]) -> Vec<u8> {
+    let mut data = id(signature).to_vec();
+    data.extend(ethers::abi::encode(args));
+    data
+}
+
+impl FarcasterContractClient {
+    /// Register a FID to this client's wallet with gas paid by a paymaster
+    ///
+    /// The wallet signs an IdGateway `Register` message and the smart account
+    /// submits it with `registerFor`, paying the registration fee from its balance.
+    ///
+    /// # Arguments
+    /// * `sender` - Sponsored sender whose smart account is owned by this wallet
+    /// * `recovery` - Recovery address of the new FID
+    /// * `extra_storage` - Extra storage units to rent with the FID
+    ///
+    /// # Returns
+    /// * `Result<ContractResult<(Fid, SponsoredReceipt)>>` - The new FID and receipt
+    pub async fn register_fid_sponsored(
+        &self,
+        sender: &SponsoredSender,
+        recovery: Address,
+        extra_storage: u64,
+    ) -> Result<ContractResult<(Fid, SponsoredReceipt)>> {
+        let wallet = self
+            .wallet
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Wallet required for FID registration"))?;
+        let to = wallet.address();
+        let price = match self
+            .storage_registry
+            .price(1 + extra_storage as u32)
+            .await?
+        {
+            ContractResult::Success(price) => price,
+            ContractResult::Error(e) => {
+                return Ok(ContractResult::Error(format!("Failed to get price: {}", e)))
+            }
+        };
+
+        let id_gateway = self.id_gateway.address();
+        let nonce_data = self
+            .provider
+            .call(
+                &TransactionRequest::new()
+                    .to(id_gateway)
+                    .data(encode_call("nonces(address)", &[Token::Address(to)]))
+                    .into(),
+                None,
+            )
+            .await?;
+        let nonce = decode_word(&nonce_data)?;
+        let deadline = chrono::Utc::now().timestamp() as u64 + REGISTER_DEADLINE_SECS;
+        let adapters = self.adapters().await?;
+        let typed_data = register_typed_data(
+            adapters.id_gateway.eip712_domain(),
+            to,
+            recovery,
+            nonce,
+            deadline,
+            id_gateway,
+            adapters.chain_id,
+        );
+        let signature = {
+            let _timer = metrics::start(Phase::Signing, "EIP-712 typed data");
+            wallet.sign_typed_data(&typed_data).await?
+        };
+        audit::record_typed_data(wallet.address(), &typed_data);
+
+        let call = adapters.id_gateway.register_for(
+            to,
+            recovery,
+            deadline.into(),
+            signature.to_vec().into(),
+            (extra_storage > 0).then_some(extra_storage),
+        );
+        let receipt = sender
+            .send_call(id_gateway, price, call.calldata()?.to_vec())
+            .await?;
+
+        match self.address_has_fid(to).await? {
+            Some(fid) => Ok(ContractResult::Success((fid, receipt))),
+            None => Ok(ContractResult::Error(format!(
+                "Operation included in {:?} but {:?} has no FID",
+                receipt.transaction_hash, to
+            ))),
+        }
+    }
+
+    /// Rent storage for a FID with gas paid by a paymaster
+    ///
+    /// # Arguments
+    /// * `sender` - Sponsored sender; its smart account pays the rent
+    /// * `fid` - The FID to rent storage for
+    /// * `units` - Storage units to rent
+    ///
+    /// # Returns
+    /// * `Result<ContractResult<SponsoredReceipt>>` - The receipt or an error
+    pub async fn rent_storage_sponsored(
+        &self,
+        sender: &SponsoredSender,
+        fid: Fid,
+        units: u64,
+    ) -> Result<ContractResult<SponsoredReceipt>> {
+        policy::current().check_fid(fid)?;
+        let price = self.get_storage_price(units).await?;
+        let call = self.adapters().await?.storage_registry.rent(fid, units);
+        let receipt = sender
+            .send_call(
+                self.storage_registry.address(),
+                price,
+                call.calldata()?.to_vec(),
+            )
+            .await?;
+        Ok(ContractResult::Success(receipt))
+    }
+}
+
+/// EIP-712 typed data for IdGateway `registerFor`
+fn register_typed_data(
+    domain: Eip712Domain,
+    to: Address,
+    recovery: Address,
+    nonce: U256,
+    deadline: u64,
+    id_gateway: Address,
+    chain_id: u64,
+) -> ethers::types::transaction::eip712::TypedData {
+    use std::collections::BTreeMap;
+
+    use ethers::types::transaction::eip712::EIP712Domain;
+    use ethers::types::transaction::eip712::Eip712DomainType;
+    use ethers::types::transaction::eip712::TypedData;
+
+    let field = |name: &str, r#type: &str| Eip712DomainType {
+        name: name.to_string(),
+        r#type: r#type.to_string(),
+    };
+    let mut types = BTreeMap::new();
+    types.insert(
+        "EIP712Domain".to_string(),
+        vec![
+            field("name", "string"),
+            field("version", "string"),
+            field("chainId", "uint256"),
+            field("verifyingContract", "address"),
+        ],
+    );
+    types.insert(
+        "Register".to_string(),
+        vec![
+            field("to", "address"),
+            field("recovery", "address"),
+            field("nonce", "uint256"),
+            field("deadline", "uint256"),
+        ],
+    );
+
+    let mut message = BTreeMap::new();
+    message.insert("to".to_string(), serde_json::json!(format!("{to:?}")));
+    message.insert(
+        "recovery".to_string(),
+        serde_json::json!(format!("{recovery:?}")),
+    );
+    message.insert("nonce".to_string(), serde_json::json!(nonce.to_string()));
+    message.insert(
+        "deadline".to_string(),
+        serde_json::json!(deadline.to_string()),
+    );
+
+    TypedData {
+        domain: EIP712Domain {
+            name: Some(domain.name.to_string()),
+            version: Some(domain.version.to_string()),
+            chain_id: Some(U256::from(chain_id)),
+            verifying_contract: Some(id_gateway),
+            salt: None,
+        },
+        types,
+        primary_type: "Register".to_string(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::transaction::eip712::Eip712;
+
+    use super::*;
+    use crate::farcaster::contracts::adapters::IdGatewayAdapter;
+    use crate::farcaster::contracts::adapters::IdGatewayV1;
+
+    #[test]
+    fn test_user_operation_hash_and_json() {
+        let op = UserOperation {
+            sender: Address::repeat_byte(0x11),
+            nonce: U256::from(1),
+            call_data: vec![0xde, 0xad].into(),
+            ..Default::default()
+        };
+        let entry_point = Address::from_str(ENTRY_POINT_V06).unwrap();
+        assert_ne!(op.hash(entry_point, 10), op.hash(entry_point, 8453));
+
+        let json = serde_json::to_value(&op).unwrap();
+        assert_eq!(json["nonce"], "0x1");
+        assert_eq!(json["callData"], "0xdead");
+        assert_eq!(json["paymasterAndData"], "0x");
+    }
+
+    #[test]
+    fn test_register_typed_data_and_quantities() {
+        let typed_data = register_typed_data(
+            IdGatewayV1.eip712_domain(),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            U256::zero(),
+            1_700_000_000,
+            Address::repeat_byte(3),
+            10,
+        );
+        assert!(typed_data.encode_eip712().is_ok());
+
+        let gas = serde_json::json!({ "callGasLimit": "0x10", "preVerificationGas": 42 });
+        assert_eq!(quantity(&gas, "callGasLimit").unwrap(), U256::from(16));
+        assert_eq!(
+            quantity(&gas, "preVerificationGas").unwrap(),
+            U256::from(42)
+        );
+        assert!(quantity(&gas, "verificationGasLimit").is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! Protocol status monitoring: pause flags, registration price and RPC health
+//!
+//! [`FarcasterContractClient::get_network_status`] reads the pause flags
+//! once. [`ContractMonitor`] polls them together with the registration price
+//! and reports changes as [`ContractAlert`]s, for services that must know
+//! when registrations or key additions stop working. Unlike the FID monitor,
+//! the first poll already alerts on contracts that are paused.
+
+#![cfg(not(doctest))]
+
+use std::time::Instant;
+
+use anyhow::Result;
+use chrono::Utc;
+use ethers::types::U256;
+use serde::Serialize;
+
+use crate::farcaster::contracts::contract_client::FarcasterContractClient;
+
+/// Kind of change detected by [`ContractMonitor`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractAlertKind {
+    Paused,
+    Unpaused,
+    PriceChanged,
+    RpcDown,
+    RpcRecovered,
+    /// The chain head did not advance since the previous poll
+    RpcStalled,
+}
+
+/// State of the protocol contracts at one poll
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContractStatus {
+    pub chain_id: u64,
+    pub block_number: u64,
+    pub id_gateway_paused: bool,
+    pub key_gateway_paused: bool,
+    pub storage_registry_paused: bool,
+    /// FID registration price in wei
+    pub registration_price: U256,
+    /// Time the checks took
+    pub rpc_latency_ms: u64,
+}
+
+impl ContractStatus {
+    /// Pause flag of each monitored contract
+    pub fn pause_flags(&self) -> [(&'static str, bool); 3] {
+        [
+            ("IdGateway", self.id_gateway_paused),
+            ("KeyGateway", self.key_gateway_paused),
+            ("StorageRegistry", self.storage_registry_paused),
+        ]
+    }
+
+    /// Whether any monitored contract is paused
+    pub fn any_paused(&self) -> bool {
+        self.pause_flags().iter().any(|(_, paused)| *paused)
+    }
+}
+
+/// A single notification produced by [`ContractMonitor`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractAlert {
+    pub kind: ContractAlertKind,
+    /// The contract concerned, for pause changes
+    pub contract: Option<&'static str>,
+    /// Unix timestamp (seconds) at which the change was detected
+    pub detected_at: i64,
+    pub message: String,
+    /// Status at detection, absent when the RPC failed
+    pub status: Option<ContractStatus>,
+}
+
+/// Polls the protocol contracts and reports changes
+pub struct ContractMonitor<'a> {
+    client: &'a FarcasterContractClient,
+    last: Option<ContractStatus>,
+    rpc_down: bool,
+    stalled: bool,
+}
+
+impl<'a> ContractMonitor<'a> {
+    pub fn new(client: &'a FarcasterContractClient) -> Self {
+        Self {
+            client,
+            last: None,
+            rpc_down: false,
+            stalled: false,
+        }
+    }
+
+    /// Check the contracts once
+    ///
+    /// RPC failures are reported as an alert rather than an error, once per
+    /// outage.
+    ///
+    /// # Returns
+    /// * `(Option<ContractStatus>, Vec<ContractAlert>)` - The status, if it
+    ///   could be read, and the alerts since the previous poll
+    pub async fn poll(&mut self) -> (Option<ContractStatus>, Vec<ContractAlert>) {
+        let now = Utc::now().timestamp();
+        let mut alerts = Vec::new();
+        let status = match self.read_status().await {
+            Ok(status) => status,
+            Err(e) => {
+                if !self.rpc_down {
+                    self.rpc_down = true;
+                    alerts.push(alert(
+                        ContractAlertKind::RpcDown,
+                        None,
+                        now,
+                        format!("RPC checks failed: {e}"),
+                        None,
+                    ));
+                }
+                return (None, alerts);
+            }
+        };
+
+        if std::mem::take(&mut self.rpc_down) {
+            alerts.push(alert(
+                ContractAlertKind::RpcRecovered,
+                None,
+                now,
+                "RPC checks succeed again".to_string(),
+                Some(&status),
+            ));
+        }
+        let stalled = self
+            .last
+            .as_ref()
+            .is_some_and(|last| status.block_number <= last.block_number);
+        if stalled && !self.stalled {
+            alerts.push(alert(
+                ContractAlertKind::RpcStalled,
+                None,
+                now,
+                format!("Chain head stuck at block {}", status.block_number),
+                Some(&status),
+            ));
+        }
+        self.stalled = stalled;
+        alerts.extend(changes(self.last.as_ref(), &status, now));
+        self.last = Some(status.clone());
+        (Some(status), alerts)
+    }
+
+    async fn read_status(&self) -> Result<ContractStatus> {
+        let started = Instant::now();
+        let network = self.client.get_network_status().await?;
+        let registration_price = self.client.get_registration_price().await?;
+        Ok(ContractStatus {
+            chain_id: network.chain_id,
+            block_number: network.block_number,
+            id_gateway_paused: network.id_gateway_paused,
+            key_gateway_paused: network.key_gateway_paused,
+            storage_registry_paused: network.storage_registry_paused,
+            registration_price,
+            rpc_latency_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+fn alert(
+    kind: ContractAlertKind,
+    contract: Option<&'static str>,
+    detected_at: i64,
+    message: String,
+    status: Option<&ContractStatus>,
+) -> ContractAlert {
+    ContractAlert {
+        kind,
+        contract,
+        detected_at,
+        message,
+        status: status.cloned(),
+    }
+}
+
+/// Pause and price changes between two polls; with no previous poll, the
+/// contracts already paused
+fn changes(
+    previous: Option<&ContractStatus>,
+    current: &ContractStatus,
+    now: i64,
+) -> Vec<ContractAlert> {
+    let mut alerts = Vec::new();
+    let before = previous.map(|previous| previous.pause_flags());
+    for (i, (contract, paused)) in current.pause_flags().into_iter().enumerate() {
+        let was_paused = before.map(|flags| flags[i].1).unwrap_or(false);
+        if paused != was_paused {
+            let (kind, state) = if paused {
+                (ContractAlertKind::Paused, "paused")
+            } else {
+                (ContractAlertKind::Unpaused, "unpaused")
+            };
+            alerts.push(alert(
+                kind,
+                Some(contract),
+                now,
+                format!("{contract} is {state}"),
+                Some(current),
+            ));
+        }
+    }
+    if let Some(previous) = previous {
+        if previous.registration_price != current.registration_price {
+            alerts.push(alert(
+                ContractAlertKind::PriceChanged,
+                None,
+                now,
+                format!(
+                    "Registration price changed from {} to {} ETH",
+                    ethers::utils::format_ether(previous.registration_price),
+                    ethers::utils::format_ether(current.registration_price)
+                ),
+                Some(current),
+            ));
+        }
+    }
+    alerts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(block_number: u64, key_gateway_paused: bool, price: u64) -> ContractStatus {
+        ContractStatus {
+            chain_id: 10,
+            block_number,
+            id_gateway_paused: false,
+            key_gateway_paused,
+            storage_registry_paused: false,
+            registration_price: U256::from(price),
+            rpc_latency_ms: 5,
+        }
+    }
+
+    #[test]
+    fn test_changes() {
+        assert!(changes(None, &status(1, false, 10), 0).is_empty());
+
+        let paused = status(2, true, 10);
+        let alerts = changes(None, &paused, 0);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, ContractAlertKind::Paused);
+        assert_eq!(alerts[0].contract, Some("KeyGateway"));
+        assert!(changes(Some(&paused), &status(3, true, 10), 0).is_empty());
+
+        let alerts = changes(Some(&paused), &status(3, false, 20), 0);
+        let kinds: Vec<_> = alerts.iter().map(|alert| alert.kind).collect();
+        assert_eq!(
+            kinds,
+            [ContractAlertKind::Unpaused, ContractAlertKind::PriceChanged]
+        );
+        assert!(paused.any_paused());
+    }
+
+    #[tokio::test]
+    async fn test_rpc_down_alerts_once() {
+        let client = FarcasterContractClient::new(
+            "http://127.0.0.1:1".to_string(),
+            crate::farcaster::contracts::types::ContractAddresses::default(),
+        )
+        .unwrap();
+        let mut monitor = ContractMonitor::new(&client);
+        let (status, alerts) = monitor.poll().await;
+        assert!(status.is_none());
+        assert_eq!(alerts[0].kind, ContractAlertKind::RpcDown);
+        assert!(monitor.poll().await.1.is_empty());
+    }
+}
@@ -9,10 +9,15 @@ use anyhow::Result;
 use ethers::providers::Http;
 use ethers::providers::Provider;
 use ethers::types::Address;
+use ethers::types::H256;
 use ethers::types::U256;
 
+use crate::farcaster::contracts::errors::describe_contract_error;
 use crate::farcaster::contracts::generated::storageregistry_bindings::StorageRegistry as StorageRegistryContract;
 use crate::farcaster::contracts::types::ContractResult;
+use crate::farcaster::contracts::types::Fid;
+use crate::farcaster::contracts::types::PriceChange;
+use crate::farcaster::contracts::types::RentRecord;
 
 /// Storage units type
 pub type StorageUnits = u32;
@@ -44,7 +49,10 @@ impl StorageRegistryAbi {
     pub async fn price(&self, units: StorageUnits) -> Result<ContractResult<U256>> {
         match self.contract.price(units.into()).call().await {
             Ok(price) => Ok(ContractResult::Success(price)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -52,7 +60,10 @@ impl StorageRegistryAbi {
     pub async fn unit_price(&self) -> Result<ContractResult<U256>> {
         match self.contract.unit_price().call().await {
             Ok(price) => Ok(ContractResult::Success(price)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -60,7 +71,10 @@ impl StorageRegistryAbi {
     pub async fn usd_unit_price(&self) -> Result<ContractResult<U256>> {
         match self.contract.usd_unit_price().call().await {
             Ok(price) => Ok(ContractResult::Success(price)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -71,7 +85,10 @@ impl StorageRegistryAbi {
                 let units_u64 = units.try_into().unwrap_or(0);
                 Ok(ContractResult::Success(units_u64))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -82,7 +99,88 @@ impl StorageRegistryAbi {
                 let units_u64 = units.try_into().unwrap_or(0);
                 Ok(ContractResult::Success(units_u64))
             }
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
+        }
+    }
+
+    /// Get the timestamp after which rentals are no longer accepted
+    pub async fn deprecation_timestamp(&self) -> Result<ContractResult<u64>> {
+        match self.contract.deprecation_timestamp().call().await {
+            Ok(timestamp) => {
+                let timestamp_u64 = timestamp.try_into().unwrap_or(u64::MAX);
+                Ok(ContractResult::Success(timestamp_u64))
+            }
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
+        }
+    }
+
+    /// Get the `Rent` events of a FID in a block range
+    ///
+    /// The FID is an indexed topic, so the node filters the logs; providers
+    /// that cap `eth_getLogs` ranges may still reject a wide range.
+    pub async fn rent_events(
+        &self,
+        fid: Fid,
+        from_block: u64,
+        to_block: Option<u64>,
+    ) -> Result<ContractResult<Vec<RentRecord>>> {
+        let mut event = self
+            .contract
+            .rent_filter()
+            .topic2(H256::from_low_u64_be(fid))
+            .from_block(from_block);
+        if let Some(to_block) = to_block {
+            event = event.to_block(to_block);
+        }
+
+        match event.query_with_meta().await {
+            Ok(logs) => Ok(ContractResult::Success(
+                logs.into_iter()
+                    .map(|(rent, meta)| RentRecord {
+                        block_number: meta.block_number.as_u64(),
+                        payer: rent.payer,
+                        units: rent.units.try_into().unwrap_or(u64::MAX),
+                    })
+                    .collect(),
+            )),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Log query failed: {}",
+                describe_contract_error(&e)
+            ))),
+        }
+    }
+
+    /// Get the `SetPrice` events (unit price changes) in a block range
+    pub async fn price_events(
+        &self,
+        from_block: u64,
+        to_block: Option<u64>,
+    ) -> Result<ContractResult<Vec<PriceChange>>> {
+        let mut event = self.contract.set_price_filter().from_block(from_block);
+        if let Some(to_block) = to_block {
+            event = event.to_block(to_block);
+        }
+
+        match event.query_with_meta().await {
+            Ok(logs) => Ok(ContractResult::Success(
+                logs.into_iter()
+                    .map(|(change, meta)| PriceChange {
+                        block_number: meta.block_number.as_u64(),
+                        old_price: change.old_price,
+                        new_price: change.new_price,
+                    })
+                    .collect(),
+            )),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Log query failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -90,7 +188,10 @@ impl StorageRegistryAbi {
     pub async fn eth_usd_price(&self) -> Result<ContractResult<U256>> {
         match self.contract.eth_usd_price().call().await {
             Ok(price) => Ok(ContractResult::Success(price)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -98,7 +199,10 @@ impl StorageRegistryAbi {
     pub async fn vault(&self) -> Result<ContractResult<Address>> {
         match self.contract.vault().call().await {
             Ok(address) => Ok(ContractResult::Success(address)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -106,7 +210,10 @@ impl StorageRegistryAbi {
     pub async fn paused(&self) -> Result<ContractResult<bool>> {
         match self.contract.paused().call().await {
             Ok(is_paused) => Ok(ContractResult::Success(is_paused)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 
@@ -114,7 +221,10 @@ impl StorageRegistryAbi {
     pub async fn version(&self) -> Result<ContractResult<String>> {
         match self.contract.version().call().await {
             Ok(version) => Ok(ContractResult::Success(version)),
-            Err(e) => Ok(ContractResult::Error(format!("Contract call failed: {e}"))),
+            Err(e) => Ok(ContractResult::Error(format!(
+                "Contract call failed: {}",
+                describe_contract_error(&e)
+            ))),
         }
     }
 }
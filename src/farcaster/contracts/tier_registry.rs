@@ -0,0 +1,283 @@
+//! TierRegistry client for Farcaster Pro subscriptions
+//!
+//! Subscriptions are bought on Base from the TierRegistry, paying the tier's
+//! ERC-20 token (USDC for Farcaster Pro) per day of subscription. Anyone can
+//! pay for any FID: the payer only needs the token and a little ETH for gas,
+//! and the token allowance is raised before the purchase when needed.
+//!
+//! The contract is not part of the contracts submodule, so calls go through a
+//! human-readable ABI rather than generated bindings.
+
+#![cfg(not(doctest))]
+
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use ethers::abi::Abi;
+use ethers::contract::Contract;
+use ethers::middleware::SignerMiddleware;
+use ethers::providers::Http;
+use ethers::providers::Middleware;
+use ethers::providers::Provider;
+use ethers::signers::LocalWallet;
+use ethers::signers::Signer;
+use ethers::types::Address;
+use ethers::types::H256;
+use ethers::types::U256;
+
+use crate::core::audit;
+use crate::core::policy;
+use crate::core::utils::finality;
+use crate::farcaster::contracts::errors::describe_contract_error;
+
+/// TierRegistry on Base
+pub const TIER_REGISTRY_ADDRESS: &str = "0x00000000fc84484d585C3cF48d213424DFDE43FD";
+/// Tier ID of Farcaster Pro
+pub const FARCASTER_PRO_TIER: u64 = 1;
+
+const TIER_REGISTRY_ABI: &[&str] = &[
+    "function tierInfo(uint256 tier) view returns ((uint256,uint256,address,address,uint256,bool))",
+    "function price(uint256 tier, uint256 forDays) view returns (uint256)",
+    "function purchaseTier(uint256 fid, uint256 tier, uint256 forDays)",
+];
+
+const ERC20_ABI: &[&str] = &[
+    "function symbol() view returns (string)",
+    "function decimals() view returns (uint8)",
+    "function balanceOf(address owner) view returns (uint256)",
+    "function allowance(address owner, address spender) view returns (uint256)",
+    "function approve(address spender, uint256 amount) returns (bool)",
+];
+
+/// Terms of a subscription tier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TierInfo {
+    pub min_days: u64,
+    pub max_days: u64,
+    /// Where payments are sent
+    pub vault: Address,
+    pub payment_token: Address,
+    /// Price per day in the payment token's smallest unit
+    pub token_price_per_day: U256,
+    pub is_active: bool,
+}
+
+/// ERC-20 token a tier is paid in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentToken {
+    pub address: Address,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl PaymentToken {
+    /// Format an amount in the token's smallest unit, e.g. `120.000000 USDC`
+    pub fn format(&self, amount: U256) -> String {
+        let value = ethers::utils::format_units(amount, self.decimals as u32)
+            .unwrap_or_else(|_| amount.to_string());
+        format!("{value} {}", self.symbol)
+    }
+}
+
+/// Transactions sent for a purchase
+#[derive(Debug, Clone)]
+pub struct TierPurchase {
+    /// Token approval, when the allowance had to be raised
+    pub approve_tx: Option<H256>,
+    pub purchase_tx: H256,
+}
+
+/// Client for the TierRegistry and its payment tokens
+#[derive(Clone)]
+pub struct TierRegistry {
+    provider: Arc<Provider<Http>>,
+    registry: Contract<Provider<Http>>,
+}
+
+fn parse_abi(signatures: &[&str]) -> Abi {
+    ethers::abi::parse_abi(signatures).expect("Invalid built-in ABI")
+}
+
+impl TierRegistry {
+    /// Create a client for the TierRegistry on the chain at `rpc_url` (Base)
+    pub fn new(rpc_url: &str) -> Result<Self> {
+        let provider =
+            Arc::new(crate::core::utils::http::provider(rpc_url).context("Invalid Base RPC URL")?);
+        let address: Address = TIER_REGISTRY_ADDRESS.parse()?;
+        let registry = Contract::new(address, parse_abi(TIER_REGISTRY_ABI), provider.clone());
+        Ok(Self { provider, registry })
+    }
+
+    /// Get the contract address
+    pub fn address(&self) -> Address {
+        self.registry.address()
+    }
+
+    fn token(&self, address: Address) -> Contract<Provider<Http>> {
+        Contract::new(address, parse_abi(ERC20_ABI), self.provider.clone())
+    }
+
+    /// Terms of a tier
+    pub async fn tier_info(&self, tier: u64) -> Result<TierInfo> {
+        let (min_days, max_days, vault, payment_token, token_price_per_day, is_active): (
+            U256,
+            U256,
+            Address,
+            Address,
+            U256,
+            bool,
+        ) = self
+            .registry
+            .method("tierInfo", U256::from(tier))?
+            .call()
+            .await
+            .map_err(|e| anyhow::anyhow!("tierInfo failed: {}", describe_contract_error(&e)))?;
+        Ok(TierInfo {
+            min_days: min_days.try_into().unwrap_or(u64::MAX),
+            max_days: max_days.try_into().unwrap_or(u64::MAX),
+            vault,
+            payment_token,
+            token_price_per_day,
+            is_active,
+        })
+    }
+
+    /// Price of a subscription in the tier's payment token
+    ///
+    /// # Arguments
+    /// * `tier` - Tier ID
+    /// * `days` - Length of the subscription
+    pub async fn price(&self, tier: u64, days: u64) -> Result<U256> {
+        self.registry
+            .method::<_, U256>("price", (U256::from(tier), U256::from(days)))?
+            .call()
+            .await
+            .map_err(|e| anyhow::anyhow!("price failed: {}", describe_contract_error(&e)))
+    }
+
+    /// Symbol and decimals of a payment token
+    pub async fn payment_token(&self, address: Address) -> Result<PaymentToken> {
+        let token = self.token(address);
+        let symbol: String = token.method("symbol", ())?.call().await?;
+        let decimals: u8 = token.method("decimals", ())?.call().await?;
+        Ok(PaymentToken {
+            address,
+            symbol,
+            decimals,
+        })
+    }
+
+    /// Token balance of an address
+    pub async fn token_balance(&self, token: Address, owner: Address) -> Result<U256> {
+        Ok(self
+            .token(token)
+            .method::<_, U256>("balanceOf", owner)?
+            .call()
+            .await?)
+    }
+
+    /// Buy or extend a subscription for a FID
+    ///
+    /// Approves the registry to take `price` of the payment token first if the
+    /// current allowance is lower.
+    ///
+    /// # Arguments
+    /// * `wallet` - The paying wallet
+    /// * `fid` - FID the subscription is for
+    /// * `tier` - Tier ID
+    /// * `days` - Days to add
+    ///
+    /// # Returns
+    /// * `Result<TierPurchase>` - The transactions sent, once mined
+    pub async fn purchase(
+        &self,
+        wallet: &LocalWallet,
+        fid: u64,
+        tier: u64,
+        days: u64,
+    ) -> Result<TierPurchase> {
+        policy::current().check_fid(fid)?;
+        let info = self.tier_info(tier).await?;
+        if !info.is_active {
+            anyhow::bail!("Tier {tier} is not active");
+        }
+        let price = self.price(tier, days).await?;
+
+        let chain_id = self.provider.get_chainid().await?;
+        let signer = Arc::new(SignerMiddleware::new(
+            self.provider.clone(),
+            wallet.clone().with_chain_id(chain_id.as_u64()),
+        ));
+
+        let token = self.token(info.payment_token).connect(signer.clone());
+        let allowance: U256 = token
+            .method("allowance", (wallet.address(), self.address()))?
+            .call()
+            .await?;
+        let approve_tx = if allowance < price {
+            let call = token.method::<_, bool>("approve", (self.address(), price))?;
+            let pending = call
+                .send()
+                .await
+                .map_err(|e| anyhow::anyhow!("Approval failed: {}", describe_contract_error(&e)))?;
+            audit::record_transaction(Some(wallet.address()), "approve", pending.tx_hash());
+            let receipt = pending
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Approval transaction dropped"))?;
+            Some(receipt.transaction_hash)
+        } else {
+            None
+        };
+
+        let registry = self.registry.connect(signer);
+        let call = registry.method::<_, ()>(
+            "purchaseTier",
+            (U256::from(fid), U256::from(tier), U256::from(days)),
+        )?;
+        call.call()
+            .await
+            .map_err(|e| anyhow::anyhow!("Purchase would fail: {}", describe_contract_error(&e)))?;
+        let pending = call
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Purchase failed: {}", describe_contract_error(&e)))?;
+        audit::record_transaction(Some(wallet.address()), "purchaseTier", pending.tx_hash());
+        let receipt = pending
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Purchase transaction dropped"))?;
+        let receipt = finality::confirm(&self.provider, receipt).await?;
+
+        Ok(TierPurchase {
+            approve_tx,
+            purchase_tx: receipt.transaction_hash,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abis_parse() {
+        let registry = parse_abi(TIER_REGISTRY_ABI);
+        let purchase = registry.function("purchaseTier").unwrap();
+        assert_eq!(purchase.inputs.len(), 3);
+        assert_eq!(
+            purchase.short_signature(),
+            ethers::utils::id("purchaseTier(uint256,uint256,uint256)")
+        );
+        assert!(parse_abi(ERC20_ABI).function("approve").is_ok());
+    }
+
+    #[test]
+    fn test_format_token_amount() {
+        let usdc = PaymentToken {
+            address: Address::zero(),
+            symbol: "USDC".to_string(),
+            decimals: 6,
+        };
+        assert_eq!(usdc.format(U256::from(120_000_000u64)), "120.000000 USDC");
+    }
+}
@@ -3,6 +3,9 @@ use ethers::types::U256;
 use serde::Deserialize;
 use serde::Serialize;
 
+/// Chain ID of Optimism mainnet, where the Farcaster contracts are deployed
+pub const OPTIMISM_CHAIN_ID: u64 = 10;
+
 /// Farcaster contract addresses on Optimism mainnet
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractAddresses {
@@ -109,6 +112,22 @@ pub struct FidInfo {
     pub pending_keys: u64,
 }
 
+/// Custody and recovery of one FID, as read by a range scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FidOwnership {
+    pub fid: Fid,
+    /// Zero for FIDs that have not been registered
+    pub custody: Address,
+    pub recovery: Address,
+}
+
+impl FidOwnership {
+    /// Whether the FID has been registered
+    pub fn is_registered(&self) -> bool {
+        !self.custody.is_zero()
+    }
+}
+
 /// Network status information
 #[derive(Debug, Clone)]
 pub struct NetworkStatus {
@@ -119,6 +138,57 @@ pub struct NetworkStatus {
     pub storage_registry_paused: bool,
 }
 
+/// Global StorageRegistry economics
+#[derive(Debug, Clone)]
+pub struct StorageInfo {
+    /// Price of one unit in wei
+    pub unit_price: U256,
+    /// Price of one unit in USD, with 8 decimals
+    pub usd_unit_price: U256,
+    /// ETH/USD price used for conversion, with 8 decimals
+    pub eth_usd_price: U256,
+    pub max_units: u64,
+    pub rented_units: u64,
+    /// Unix time after which no more units can be rented
+    pub deprecation_timestamp: u64,
+    pub paused: bool,
+}
+
+/// A storage rental from a StorageRegistry `Rent` event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RentRecord {
+    pub block_number: u64,
+    pub payer: Address,
+    pub units: u64,
+}
+
+/// A storage unit price change from a StorageRegistry `SetPrice` event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceChange {
+    pub block_number: u64,
+    /// Previous price in USD with 8 decimals
+    pub old_price: U256,
+    /// New price in USD with 8 decimals
+    pub new_price: U256,
+}
+
+/// What a transaction cost its sender
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxCost {
+    pub from: Address,
+    /// ETH sent along with the call (registration or rent payments)
+    pub value: U256,
+    /// L2 execution fee plus the L1 data fee
+    pub fee: U256,
+}
+
+impl TxCost {
+    /// Total ETH that left the sender
+    pub fn total(&self) -> U256 {
+        self.value + self.fee
+    }
+}
+
 /// Result of signer verification
 #[derive(Debug, Clone)]
 pub struct SignerVerificationResult {
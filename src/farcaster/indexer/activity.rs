@@ -0,0 +1,218 @@
+//! Per-address activity reconstructed from indexed registry events
+
+use super::events::EventKind;
+use super::events::IndexedEvent;
+use super::store::normalize_hex;
+use super::store::EventStore;
+
+/// How an address came to own a FID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Acquisition {
+    Registered,
+    TransferredIn,
+    Recovered,
+}
+
+/// A period during which an address was the custody address of a FID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FidOwnership {
+    pub fid: u64,
+    pub acquired: Acquisition,
+    pub from_block: u64,
+    /// Block the FID was transferred or recovered away, `None` if still owned
+    pub until_block: Option<u64>,
+}
+
+/// Everything an address did on the Farcaster registries, per the local index
+#[derive(Debug, Clone, Default)]
+pub struct WalletActivity {
+    pub address: String,
+    /// Ownership periods, oldest first
+    pub fids: Vec<FidOwnership>,
+    /// KeyAdded events on FIDs while the address owned them
+    pub signers_added: Vec<IndexedEvent>,
+    /// Rent events paid by the address
+    pub rentals: Vec<IndexedEvent>,
+    /// Distinct transactions behind the events above, in chain order
+    pub tx_hashes: Vec<String>,
+}
+
+impl WalletActivity {
+    /// FIDs the address owns according to the index
+    pub fn current_fids(&self) -> Vec<u64> {
+        self.fids
+            .iter()
+            .filter(|o| o.until_block.is_none())
+            .map(|o| o.fid)
+            .collect()
+    }
+
+    /// Total storage units the address paid for
+    pub fn rented_units(&self) -> u64 {
+        self.rentals
+            .iter()
+            .map(|e| match &e.kind {
+                EventKind::Rent { units, .. } => *units,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+impl EventStore {
+    /// Reconstruct the activity of an address from the stored events
+    ///
+    /// # Arguments
+    /// * `address` - The address to report on (any hex case, with or without 0x)
+    ///
+    /// # Returns
+    /// * `WalletActivity` - Ownership periods, signers added and rentals paid
+    pub fn wallet_activity(&self, address: &str) -> WalletActivity {
+        wallet_activity(self.events(), address)
+    }
+}
+
+fn wallet_activity(events: &[IndexedEvent], address: &str) -> WalletActivity {
+    let address = normalize_hex(address);
+    let is_address = |value: &str| value.eq_ignore_ascii_case(&address);
+    let mut activity = WalletActivity {
+        address: address.clone(),
+        ..Default::default()
+    };
+    for event in events {
+        let owned = |fids: &[FidOwnership]| {
+            fids.iter()
+                .any(|o| o.fid == event.fid && o.until_block.is_none())
+        };
+        match &event.kind {
+            EventKind::Register { to, .. } if is_address(to) => {
+                activity.fids.push(FidOwnership {
+                    fid: event.fid,
+                    acquired: Acquisition::Registered,
+                    from_block: event.block_number,
+                    until_block: None,
+                });
+                push_tx(&mut activity.tx_hashes, event);
+            }
+            EventKind::Transfer { from, to } | EventKind::Recover { from, to } => {
+                let acquired = if matches!(event.kind, EventKind::Transfer { .. }) {
+                    Acquisition::TransferredIn
+                } else {
+                    Acquisition::Recovered
+                };
+                if is_address(from) {
+                    if let Some(ownership) = activity
+                        .fids
+                        .iter_mut()
+                        .rev()
+                        .find(|o| o.fid == event.fid && o.until_block.is_none())
+                    {
+                        ownership.until_block = Some(event.block_number);
+                    }
+                    push_tx(&mut activity.tx_hashes, event);
+                }
+                if is_address(to) {
+                    activity.fids.push(FidOwnership {
+                        fid: event.fid,
+                        acquired,
+                        from_block: event.block_number,
+                        until_block: None,
+                    });
+                    push_tx(&mut activity.tx_hashes, event);
+                }
+            }
+            EventKind::KeyAdded { .. } if owned(&activity.fids) => {
+                activity.signers_added.push(event.clone());
+                push_tx(&mut activity.tx_hashes, event);
+            }
+            EventKind::Rent { payer, .. } if is_address(payer) => {
+                activity.rentals.push(event.clone());
+                push_tx(&mut activity.tx_hashes, event);
+            }
+            _ => {}
+        }
+    }
+    activity
+}
+
+fn push_tx(tx_hashes: &mut Vec<String>, event: &IndexedEvent) {
+    if !tx_hashes.contains(&event.tx_hash) {
+        tx_hashes.push(event.tx_hash.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "0x00000000000000000000000000000000000000aa";
+    const BOB: &str = "0x00000000000000000000000000000000000000bb";
+
+    fn event(block_number: u64, fid: u64, kind: EventKind) -> IndexedEvent {
+        IndexedEvent {
+            block_number,
+            log_index: 0,
+            tx_hash: format!("0x{block_number:064x}"),
+            fid,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_wallet_activity() {
+        let key_added = |block| {
+            event(
+                block,
+                1,
+                EventKind::KeyAdded {
+                    key_type: 1,
+                    key: format!("0x{block:02x}"),
+                    metadata_type: 1,
+                },
+            )
+        };
+        let events = vec![
+            event(
+                10,
+                1,
+                EventKind::Register {
+                    to: ALICE.to_string(),
+                    recovery: BOB.to_string(),
+                },
+            ),
+            key_added(11),
+            event(
+                12,
+                1,
+                EventKind::Rent {
+                    payer: ALICE.to_uppercase().replace("0X", "0x"),
+                    units: 2,
+                },
+            ),
+            event(
+                13,
+                1,
+                EventKind::Transfer {
+                    from: ALICE.to_string(),
+                    to: BOB.to_string(),
+                },
+            ),
+            // Added by the new owner, not part of Alice's activity
+            key_added(14),
+        ];
+
+        let activity = wallet_activity(&events, ALICE.trim_start_matches("0x"));
+        assert_eq!(activity.fids.len(), 1);
+        assert_eq!(activity.fids[0].acquired, Acquisition::Registered);
+        assert_eq!(activity.fids[0].until_block, Some(13));
+        assert!(activity.current_fids().is_empty());
+        assert_eq!(activity.signers_added.len(), 1);
+        assert_eq!(activity.rented_units(), 2);
+        assert_eq!(activity.tx_hashes.len(), 4);
+
+        let bob = wallet_activity(&events, BOB);
+        assert_eq!(bob.current_fids(), vec![1]);
+        assert_eq!(bob.fids[0].acquired, Acquisition::TransferredIn);
+        assert_eq!(bob.signers_added.len(), 1);
+    }
+}
@@ -0,0 +1,327 @@
+//! Decoding of Farcaster registry logs
+
+use ethers::abi::decode;
+use ethers::abi::ParamType;
+use ethers::abi::Token;
+use ethers::types::Address;
+use ethers::types::Log;
+use ethers::types::H256;
+use ethers::types::U256;
+use ethers::utils::keccak256;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// IdRegistry `Register(address indexed to, uint256 indexed id, address recovery)`
+pub const REGISTER_SIGNATURE: &str = "Register(address,uint256,address)";
+/// IdRegistry `Transfer(address indexed from, address indexed to, uint256 indexed id)`
+pub const TRANSFER_SIGNATURE: &str = "Transfer(address,address,uint256)";
+/// IdRegistry `Recover(address indexed from, address indexed to, uint256 indexed id)`
+pub const RECOVER_SIGNATURE: &str = "Recover(address,address,uint256)";
+/// IdRegistry `ChangeRecoveryAddress(uint256 indexed id, address indexed recovery)`
+pub const CHANGE_RECOVERY_SIGNATURE: &str = "ChangeRecoveryAddress(uint256,address)";
+/// KeyRegistry `Add(uint256 indexed fid, uint32 indexed keyType, bytes indexed key, bytes keyBytes, uint8 metadataType, bytes metadata)`
+pub const KEY_ADD_SIGNATURE: &str = "Add(uint256,uint32,bytes,bytes,uint8,bytes)";
+/// KeyRegistry `Remove(uint256 indexed fid, bytes indexed key, bytes keyBytes)`
+pub const KEY_REMOVE_SIGNATURE: &str = "Remove(uint256,bytes,bytes)";
+/// KeyRegistry `AdminReset(uint256 indexed fid, bytes indexed key, bytes keyBytes)`
+pub const KEY_ADMIN_RESET_SIGNATURE: &str = "AdminReset(uint256,bytes,bytes)";
+/// StorageRegistry `Rent(address indexed payer, uint256 indexed fid, uint256 units)`
+pub const RENT_SIGNATURE: &str = "Rent(address,uint256,uint256)";
+/// StorageRegistry `SetPrice(uint256 oldPrice, uint256 newPrice)`
+pub const SET_PRICE_SIGNATURE: &str = "SetPrice(uint256,uint256)";
+
+/// Signatures of every event the indexer stores
+pub const INDEXED_SIGNATURES: &[&str] = &[
+    REGISTER_SIGNATURE,
+    TRANSFER_SIGNATURE,
+    RECOVER_SIGNATURE,
+    CHANGE_RECOVERY_SIGNATURE,
+    KEY_ADD_SIGNATURE,
+    KEY_REMOVE_SIGNATURE,
+    KEY_ADMIN_RESET_SIGNATURE,
+    RENT_SIGNATURE,
+    SET_PRICE_SIGNATURE,
+];
+
+/// Topic hash of an event signature
+pub fn event_topic(signature: &str) -> H256 {
+    H256::from(keccak256(signature.as_bytes()))
+}
+
+/// What happened in an indexed registry event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum EventKind {
+    Register {
+        to: String,
+        recovery: String,
+    },
+    Transfer {
+        from: String,
+        to: String,
+    },
+    Recover {
+        from: String,
+        to: String,
+    },
+    ChangeRecoveryAddress {
+        recovery: String,
+    },
+    KeyAdded {
+        key_type: u32,
+        key: String,
+        metadata_type: u8,
+    },
+    KeyRemoved {
+        key: String,
+    },
+    KeyAdminReset {
+        key: String,
+    },
+    Rent {
+        payer: String,
+        units: u64,
+    },
+    /// Storage unit price change, in USD with 8 decimals
+    SetPrice {
+        old_price: u64,
+        new_price: u64,
+    },
+}
+
+/// A decoded registry event with its position on chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedEvent {
+    pub block_number: u64,
+    pub log_index: u64,
+    pub tx_hash: String,
+    /// FID the event is about, 0 for registry-wide events such as `SetPrice`
+    pub fid: u64,
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+impl IndexedEvent {
+    /// Decode a registry log, returning `None` for events the indexer does not store
+    pub fn from_log(log: &Log) -> Option<Self> {
+        let topic0 = *log.topics.first()?;
+        let topic = |i: usize| log.topics.get(i).copied();
+        let data = log.data.as_ref();
+
+        let (fid, kind) = if topic0 == event_topic(REGISTER_SIGNATURE) {
+            let recovery = decode(&[ParamType::Address], data).ok()?;
+            (
+                topic_u64(topic(2)?),
+                EventKind::Register {
+                    to: topic_address(topic(1)?),
+                    recovery: token_address(recovery.first()?)?,
+                },
+            )
+        } else if topic0 == event_topic(TRANSFER_SIGNATURE) {
+            (
+                topic_u64(topic(3)?),
+                EventKind::Transfer {
+                    from: topic_address(topic(1)?),
+                    to: topic_address(topic(2)?),
+                },
+            )
+        } else if topic0 == event_topic(RECOVER_SIGNATURE) {
+            (
+                topic_u64(topic(3)?),
+                EventKind::Recover {
+                    from: topic_address(topic(1)?),
+                    to: topic_address(topic(2)?),
+                },
+            )
+        } else if topic0 == event_topic(CHANGE_RECOVERY_SIGNATURE) {
+            (
+                topic_u64(topic(1)?),
+                EventKind::ChangeRecoveryAddress {
+                    recovery: topic_address(topic(2)?),
+                },
+            )
+        } else if topic0 == event_topic(KEY_ADD_SIGNATURE) {
+            let tokens = decode(
+                &[ParamType::Bytes, ParamType::Uint(8), ParamType::Bytes],
+                data,
+            )
+            .ok()?;
+            (
+                topic_u64(topic(1)?),
+                EventKind::KeyAdded {
+                    key_type: topic_u64(topic(2)?) as u32,
+                    key: token_bytes(tokens.first()?)?,
+                    metadata_type: tokens.get(1)?.clone().into_uint()?.low_u32() as u8,
+                },
+            )
+        } else if topic0 == event_topic(KEY_REMOVE_SIGNATURE)
+            || topic0 == event_topic(KEY_ADMIN_RESET_SIGNATURE)
+        {
+            let tokens = decode(&[ParamType::Bytes], data).ok()?;
+            let key = token_bytes(tokens.first()?)?;
+            let kind = if topic0 == event_topic(KEY_REMOVE_SIGNATURE) {
+                EventKind::KeyRemoved { key }
+            } else {
+                EventKind::KeyAdminReset { key }
+            };
+            (topic_u64(topic(1)?), kind)
+        } else if topic0 == event_topic(RENT_SIGNATURE) {
+            let tokens = decode(&[ParamType::Uint(256)], data).ok()?;
+            (
+                topic_u64(topic(2)?),
+                EventKind::Rent {
+                    payer: topic_address(topic(1)?),
+                    units: tokens.first()?.clone().into_uint()?.low_u64(),
+                },
+            )
+        } else if topic0 == event_topic(SET_PRICE_SIGNATURE) {
+            let tokens = decode(&[ParamType::Uint(256), ParamType::Uint(256)], data).ok()?;
+            (
+                0,
+                EventKind::SetPrice {
+                    old_price: tokens.first()?.clone().into_uint()?.low_u64(),
+                    new_price: tokens.get(1)?.clone().into_uint()?.low_u64(),
+                },
+            )
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            block_number: log.block_number?.as_u64(),
+            log_index: log.log_index?.as_u64(),
+            tx_hash: format!("{:?}", log.transaction_hash?),
+            fid,
+            kind,
+        })
+    }
+
+    /// Signer key affected by this event, if it is a KeyRegistry event
+    pub fn key(&self) -> Option<&str> {
+        match &self.kind {
+            EventKind::KeyAdded { key, .. }
+            | EventKind::KeyRemoved { key }
+            | EventKind::KeyAdminReset { key } => Some(key),
+            _ => None,
+        }
+    }
+}
+
+fn topic_u64(topic: H256) -> u64 {
+    U256::from_big_endian(topic.as_bytes()).low_u64()
+}
+
+fn topic_address(topic: H256) -> String {
+    format!("{:?}", Address::from(topic))
+}
+
+fn token_address(token: &Token) -> Option<String> {
+    token.clone().into_address().map(|a| format!("{a:?}"))
+}
+
+fn token_bytes(token: &Token) -> Option<String> {
+    token
+        .clone()
+        .into_bytes()
+        .map(|b| format!("0x{}", hex::encode(b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::abi::encode;
+    use ethers::types::Bytes;
+    use ethers::types::U64;
+
+    use super::*;
+
+    fn log(topics: Vec<H256>, data: Vec<u8>) -> Log {
+        Log {
+            topics,
+            data: Bytes::from(data),
+            block_number: Some(U64::from(100)),
+            log_index: Some(U256::from(2)),
+            transaction_hash: Some(H256::repeat_byte(0xab)),
+            ..Default::default()
+        }
+    }
+
+    fn uint_topic(value: u64) -> H256 {
+        let mut bytes = [0u8; 32];
+        U256::from(value).to_big_endian(&mut bytes);
+        H256::from(bytes)
+    }
+
+    #[test]
+    fn test_decode_register() {
+        let to = Address::repeat_byte(0x11);
+        let recovery = Address::repeat_byte(0x22);
+        let event = IndexedEvent::from_log(&log(
+            vec![
+                event_topic(REGISTER_SIGNATURE),
+                H256::from(to),
+                uint_topic(42),
+            ],
+            encode(&[Token::Address(recovery)]),
+        ))
+        .unwrap();
+        assert_eq!(event.fid, 42);
+        assert_eq!(event.block_number, 100);
+        assert_eq!(
+            event.kind,
+            EventKind::Register {
+                to: format!("{to:?}"),
+                recovery: format!("{recovery:?}"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_key_added() {
+        let key = vec![0x5a; 32];
+        let event = IndexedEvent::from_log(&log(
+            vec![
+                event_topic(KEY_ADD_SIGNATURE),
+                uint_topic(7),
+                uint_topic(1),
+                H256::from(keccak256(&key)),
+            ],
+            encode(&[
+                Token::Bytes(key.clone()),
+                Token::Uint(U256::from(1)),
+                Token::Bytes(vec![1, 2, 3]),
+            ]),
+        ))
+        .unwrap();
+        assert_eq!(event.fid, 7);
+        assert_eq!(
+            event.key(),
+            Some(format!("0x{}", hex::encode(&key)).as_str())
+        );
+    }
+
+    #[test]
+    fn test_decode_set_price() {
+        let event = IndexedEvent::from_log(&log(
+            vec![event_topic(SET_PRICE_SIGNATURE)],
+            encode(&[
+                Token::Uint(U256::from(700_000_000u64)),
+                Token::Uint(U256::from(300_000_000u64)),
+            ]),
+        ))
+        .unwrap();
+        assert_eq!(event.fid, 0);
+        assert_eq!(
+            event.kind,
+            EventKind::SetPrice {
+                old_price: 700_000_000,
+                new_price: 300_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_event_is_skipped() {
+        let event = IndexedEvent::from_log(&log(vec![event_topic("Paused(address)")], vec![]));
+        assert!(event.is_none());
+    }
+}
@@ -0,0 +1,157 @@
+//! Onchain event indexer for the Farcaster registries
+//!
+//! Scans IdRegistry, KeyRegistry and StorageRegistry logs on Optimism in
+//! block batches and keeps the decoded events under `<data dir>/indexer`, so
+//! questions like "when was this signer added" can be answered from local data
+//! instead of a hub's onchain-event endpoints. Each sync resumes after the last
+//! fully stored block.
+
+pub mod activity;
+pub mod events;
+pub mod store;
+
+pub use activity::WalletActivity;
+use anyhow::Context;
+use anyhow::Result;
+use ethers::providers::Http;
+use ethers::providers::Middleware;
+use ethers::providers::Provider;
+use ethers::types::BlockNumber;
+use ethers::types::Filter;
+use ethers::types::H256;
+pub use events::EventKind;
+pub use events::IndexedEvent;
+pub use store::EventStore;
+
+use crate::farcaster::contracts::ContractAddresses;
+
+/// Block at which hubs start syncing Farcaster onchain events on Optimism
+pub const DEFAULT_START_BLOCK: u64 = 108_864_739;
+/// Default number of blocks requested per `eth_getLogs` call
+pub const DEFAULT_BATCH_SIZE: u64 = 2_000;
+
+/// Batched log scanner writing into an [`EventStore`]
+pub struct EventIndexer {
+    provider: Provider<Http>,
+    addresses: ContractAddresses,
+    batch_size: u64,
+}
+
+/// Result of a sync run
+#[derive(Debug, Clone)]
+pub struct SyncSummary {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub events: usize,
+}
+
+impl EventIndexer {
+    /// Create an indexer for the default registry deployments
+    ///
+    /// # Arguments
+    /// * `rpc_url` - Optimism RPC URL
+    /// * `batch_size` - Blocks per `eth_getLogs` request
+    ///
+    /// # Returns
+    /// * `Result<EventIndexer>` - The indexer or an error
+    pub fn new(rpc_url: &str, batch_size: u64) -> Result<Self> {
+        let provider = crate::core::utils::http::provider(rpc_url)
+            .with_context(|| format!("Invalid RPC URL {rpc_url}"))?;
+        Ok(Self {
+            provider,
+            addresses: ContractAddresses::default(),
+            batch_size: batch_size.max(1),
+        })
+    }
+
+    /// Scan a block range and store every decoded event
+    ///
+    /// Without `from_block` the scan resumes after the store's last block (or
+    /// starts at [`DEFAULT_START_BLOCK`]); without `to_block` it runs to the
+    /// current head. Batches the RPC rejects are split in half and retried.
+    ///
+    /// # Arguments
+    /// * `store` - Store to write into
+    /// * `from_block` - First block to scan
+    /// * `to_block` - Last block to scan
+    /// * `on_batch` - Called with `(batch end, head, events in batch)` after each batch
+    ///
+    /// # Returns
+    /// * `Result<SyncSummary>` - The scanned range and number of events stored
+    pub async fn sync(
+        &self,
+        store: &mut EventStore,
+        from_block: Option<u64>,
+        to_block: Option<u64>,
+        mut on_batch: impl FnMut(u64, u64, usize),
+    ) -> Result<SyncSummary> {
+        let start = from_block
+            .or_else(|| store.last_block().map(|b| b + 1))
+            .unwrap_or(DEFAULT_START_BLOCK);
+        let end = match to_block {
+            Some(block) => block,
+            None => self.provider.get_block_number().await?.as_u64(),
+        };
+
+        let mut total = 0;
+        let mut batch_size = self.batch_size;
+        let mut cursor = start;
+        while cursor <= end {
+            let batch_end = (cursor + batch_size - 1).min(end);
+            let logs = match self
+                .provider
+                .get_logs(&self.filter(cursor, batch_end))
+                .await
+            {
+                Ok(logs) => logs,
+                Err(e) if batch_size > 1 => {
+                    // Most providers cap the range or result count of a single request
+                    batch_size = (batch_size / 2).max(1);
+                    tracing::debug!("eth_getLogs {cursor}-{batch_end} failed ({e}), batch size now {batch_size}");
+                    continue;
+                }
+                Err(e) => return Err(e).context(format!("eth_getLogs failed at block {cursor}")),
+            };
+
+            let events: Vec<IndexedEvent> =
+                logs.iter().filter_map(IndexedEvent::from_log).collect();
+            let count = events.len();
+            store.commit_range(events, batch_end)?;
+            total += count;
+            on_batch(batch_end, end, count);
+            cursor = batch_end + 1;
+        }
+
+        Ok(SyncSummary {
+            from_block: start,
+            to_block: end,
+            events: total,
+        })
+    }
+
+    /// Timestamp of a block, used to report when an event happened
+    pub async fn block_timestamp(&self, block: u64) -> Result<u64> {
+        let block = self
+            .provider
+            .get_block(block)
+            .await?
+            .with_context(|| format!("Block {block} not found"))?;
+        Ok(block.timestamp.as_u64())
+    }
+
+    fn filter(&self, from: u64, to: u64) -> Filter {
+        let topics: Vec<H256> = events::INDEXED_SIGNATURES
+            .iter()
+            .map(|sig| events::event_topic(sig))
+            .collect();
+        Filter::new()
+            .address(vec![
+                self.addresses.id_registry,
+                self.addresses.key_registry,
+                self.addresses.storage_registry,
+            ])
+            .topic0(topics)
+            .from_block(BlockNumber::Number(from.into()))
+            .to_block(BlockNumber::Number(to.into()))
+    }
+}
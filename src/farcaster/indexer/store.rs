@@ -0,0 +1,230 @@
+//! Local storage of indexed registry events
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::events::EventKind;
+use super::events::IndexedEvent;
+use crate::core::utils::data_dir;
+
+const EVENTS_FILE: &str = "events.jsonl";
+const STATE_FILE: &str = "state.json";
+
+/// Sync progress persisted next to the events
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexerState {
+    /// Last block whose logs are fully stored
+    pub last_block: Option<u64>,
+}
+
+/// Append-only event log with the sync state
+///
+/// Events are appended to `events.jsonl` before `state.json` is advanced, so
+/// an interrupted sync re-scans the last batch; duplicates are dropped on load.
+pub struct EventStore {
+    dir: PathBuf,
+    state: IndexerState,
+    events: Vec<IndexedEvent>,
+}
+
+impl EventStore {
+    /// Open the store in `<data dir>/indexer`
+    pub fn open_default() -> Result<Self> {
+        Self::open(data_dir::data_dir().join("indexer"))
+    }
+
+    /// Open (or create) a store in a directory
+    ///
+    /// # Arguments
+    /// * `dir` - Directory holding `events.jsonl` and `state.json`
+    ///
+    /// # Returns
+    /// * `Result<EventStore>` - The loaded store or an error
+    pub fn open(dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let state = match std::fs::read_to_string(dir.join(STATE_FILE)) {
+            Ok(content) => serde_json::from_str(&content).context("Invalid indexer state file")?,
+            Err(_) => IndexerState::default(),
+        };
+
+        let mut events = Vec::new();
+        let mut seen = HashSet::new();
+        if let Ok(content) = std::fs::read_to_string(dir.join(EVENTS_FILE)) {
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                // A torn final line from an interrupted write is skipped
+                let Ok(event) = serde_json::from_str::<IndexedEvent>(line) else {
+                    continue;
+                };
+                if seen.insert((event.block_number, event.log_index)) {
+                    events.push(event);
+                }
+            }
+        }
+        events.sort_by_key(|e| (e.block_number, e.log_index));
+
+        Ok(Self { dir, state, events })
+    }
+
+    /// Directory the store lives in
+    pub fn dir(&self) -> &PathBuf {
+        &self.dir
+    }
+
+    /// Last fully indexed block
+    pub fn last_block(&self) -> Option<u64> {
+        self.state.last_block
+    }
+
+    /// All stored events in chain order
+    pub fn events(&self) -> &[IndexedEvent] {
+        &self.events
+    }
+
+    /// Store the events of a block range and mark it as indexed
+    ///
+    /// # Arguments
+    /// * `events` - Decoded events found in the range
+    /// * `to_block` - Last block of the range
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or an error
+    pub fn commit_range(&mut self, events: Vec<IndexedEvent>, to_block: u64) -> Result<()> {
+        let known: HashSet<(u64, u64)> = self
+            .events
+            .iter()
+            .map(|e| (e.block_number, e.log_index))
+            .collect();
+        let new_events: Vec<IndexedEvent> = events
+            .into_iter()
+            .filter(|e| !known.contains(&(e.block_number, e.log_index)))
+            .collect();
+
+        if !new_events.is_empty() {
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.dir.join(EVENTS_FILE))?;
+            for event in &new_events {
+                writeln!(file, "{}", serde_json::to_string(event)?)?;
+            }
+            file.sync_data()?;
+            self.events.extend(new_events);
+            self.events.sort_by_key(|e| (e.block_number, e.log_index));
+        }
+
+        if self.state.last_block.is_none_or(|last| to_block > last) {
+            self.state.last_block = Some(to_block);
+        }
+        std::fs::write(
+            self.dir.join(STATE_FILE),
+            serde_json::to_string_pretty(&self.state)?,
+        )?;
+        Ok(())
+    }
+
+    /// KeyRegistry events for a signer key, oldest first
+    pub fn signer_history(&self, key: &str) -> Vec<&IndexedEvent> {
+        let key = normalize_hex(key);
+        self.events
+            .iter()
+            .filter(|e| e.key().is_some_and(|k| k.eq_ignore_ascii_case(&key)))
+            .collect()
+    }
+
+    /// Register events whose owner is an address
+    pub fn registered_by(&self, address: &str) -> Vec<&IndexedEvent> {
+        let address = normalize_hex(address);
+        self.events
+            .iter()
+            .filter(|e| match &e.kind {
+                EventKind::Register { to, .. } => to.eq_ignore_ascii_case(&address),
+                _ => false,
+            })
+            .collect()
+    }
+
+    /// Every stored event touching a FID
+    pub fn fid_history(&self, fid: u64) -> Vec<&IndexedEvent> {
+        self.events.iter().filter(|e| e.fid == fid).collect()
+    }
+
+    /// StorageRegistry unit price changes, oldest first
+    pub fn price_changes(&self) -> Vec<&IndexedEvent> {
+        self.events
+            .iter()
+            .filter(|e| matches!(e.kind, EventKind::SetPrice { .. }))
+            .collect()
+    }
+}
+
+pub(super) fn normalize_hex(value: &str) -> String {
+    let value = value.trim();
+    if value.starts_with("0x") || value.starts_with("0X") {
+        format!("0x{}", &value[2..])
+    } else {
+        format!("0x{value}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(block_number: u64, fid: u64, kind: EventKind) -> IndexedEvent {
+        IndexedEvent {
+            block_number,
+            log_index: 0,
+            tx_hash: format!("0x{block_number:064x}"),
+            fid,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_commit_and_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = EventStore::open(dir.path().to_path_buf()).unwrap();
+        let register = event(
+            10,
+            1,
+            EventKind::Register {
+                to: "0x00000000000000000000000000000000000000aa".to_string(),
+                recovery: "0x0000000000000000000000000000000000000000".to_string(),
+            },
+        );
+        let added = event(
+            20,
+            1,
+            EventKind::KeyAdded {
+                key_type: 1,
+                key: "0xabcd".to_string(),
+                metadata_type: 1,
+            },
+        );
+        store
+            .commit_range(vec![register.clone(), added.clone()], 100)
+            .unwrap();
+        // Re-scanning a range after an interruption does not duplicate events
+        store.commit_range(vec![added], 100).unwrap();
+
+        let store = EventStore::open(dir.path().to_path_buf()).unwrap();
+        assert_eq!(store.last_block(), Some(100));
+        assert_eq!(store.events().len(), 2);
+        assert_eq!(store.signer_history("ABCD").len(), 1);
+        assert_eq!(
+            store
+                .registered_by("0x00000000000000000000000000000000000000AA")
+                .len(),
+            1
+        );
+        assert_eq!(store.fid_history(1).len(), 2);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod contracts;
+pub mod indexer;
 
 pub use contracts::ContractAddresses;
 pub use contracts::ContractResult;
@@ -11,7 +11,10 @@ impl ImageDisplay {
     /// Display image using viuer (terminal image display)
     pub async fn display_with_viuer(image_url: &str) -> Result<()> {
         // Download image to temporary file
-        let response = reqwest::get(image_url).await?;
+        let response = crate::core::utils::http::client()
+            .get(image_url)
+            .send()
+            .await?;
         let image_data = response.bytes().await?;
 
         // Create temporary file
@@ -33,7 +36,10 @@ impl ImageDisplay {
     /// Display image as colored block art
     pub async fn display_as_ascii(image_url: &str) -> Result<()> {
         // Download image
-        let response = reqwest::get(image_url).await?;
+        let response = crate::core::utils::http::client()
+            .get(image_url)
+            .send()
+            .await?;
         let image_data = response.bytes().await?;
 
         // Load image
@@ -14,6 +14,13 @@
 //! along with this program; if not, write to the Free Software
 //! Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
 
+#[macro_use]
+mod macros;
+pub(crate) use macros::eprint;
+pub(crate) use macros::eprintln;
+pub(crate) use macros::println;
+
+#[cfg(feature = "api-server")]
 pub mod api;
 pub mod cli;
 pub mod consts;
@@ -21,7 +28,10 @@ pub mod core;
 pub mod ed25519_key_manager;
 pub mod encrypted_key_manager;
 pub mod ens_proof;
+#[cfg(feature = "contracts")]
 pub mod farcaster;
+#[cfg(feature = "image")]
 pub mod image_display;
+#[cfg(feature = "mcp")]
 pub mod mcp;
 pub mod username_proofs;
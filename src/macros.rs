@@ -0,0 +1,57 @@
+//! Print macros shadowing the std ones
+//!
+//! Declared before every other module so that, with the `cli` feature, all
+//! `print!`, `println!`, `eprint!` and `eprintln!` calls in the crate route
+//! through [`crate::cli::output::write`], which applies `--plain` and
+//! `--lang`. Without the feature they are the std macros.
+//!
+//! They are crate-private, so `println!` is never shadowed in code that uses
+//! castorix as a library; the binaries declare their own.
+
+#[cfg(feature = "cli")]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::cli::output::write(false, false, format_args!($($arg)*))
+    };
+}
+
+#[cfg(feature = "cli")]
+macro_rules! println {
+    () => {
+        $crate::cli::output::write(false, true, format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::cli::output::write(false, true, format_args!($($arg)*))
+    };
+}
+
+#[cfg(feature = "cli")]
+macro_rules! eprint {
+    ($($arg:tt)*) => {
+        $crate::cli::output::write(true, false, format_args!($($arg)*))
+    };
+}
+
+#[cfg(feature = "cli")]
+macro_rules! eprintln {
+    () => {
+        $crate::cli::output::write(true, true, format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::cli::output::write(true, true, format_args!($($arg)*))
+    };
+}
+
+#[cfg(not(feature = "cli"))]
+pub(crate) use std::eprint;
+#[cfg(not(feature = "cli"))]
+pub(crate) use std::eprintln;
+#[cfg(not(feature = "cli"))]
+pub(crate) use std::println;
+
+#[cfg(feature = "cli")]
+pub(crate) use eprint;
+#[cfg(feature = "cli")]
+pub(crate) use eprintln;
+#[cfg(feature = "cli")]
+pub(crate) use println;
@@ -15,24 +15,113 @@
 // Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
 
 use anyhow::Result;
+use castorix::cli::aliases;
 use castorix::cli::commands::Commands;
+use castorix::cli::handlers::autocast_handlers::Schedule;
+use castorix::cli::i18n;
+use castorix::cli::output;
 use castorix::cli::types::HubCommands;
 use castorix::cli::types::KeyCommands;
-use castorix::cli::Cli;
+use castorix::cli::types::MonitorCommands;
 use castorix::cli::CliHandler;
-use castorix::consts;
+use castorix::cli::EndpointOverrides;
+use castorix::core::audit;
+use castorix::core::client::autocast::Autocast;
 use castorix::core::client::hub_client::FarcasterClient;
+use castorix::core::client::hub_pool;
+use castorix::core::client::rate_limit;
+use castorix::core::client::rate_limit::WriteRate;
 use castorix::core::crypto::key_manager::init_env;
 use castorix::core::crypto::key_manager::KeyManager;
+use castorix::core::policy;
+use castorix::core::policy::Policy;
+use castorix::core::utils::data_dir;
+use castorix::core::utils::finality;
+use castorix::core::utils::finality::FinalitySettings;
+use castorix::core::utils::http;
+use castorix::core::utils::http::HttpSettings;
+use castorix::core::utils::metrics;
+use castorix::core::utils::prompt;
+use castorix::core::utils::prompt::InputRequired;
 use castorix::ens_proof::EnsProof;
 
+// The library's print macros are crate-private; the binary's own output goes
+// through the same filter so that it honours `--plain` and `--lang` too
+macro_rules! println {
+    () => {
+        castorix::cli::output::write(false, true, format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        castorix::cli::output::write(false, true, format_args!($($arg)*))
+    };
+}
+
+macro_rules! eprintln {
+    () => {
+        castorix::cli::output::write(true, true, format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        castorix::cli::output::write(true, true, format_args!($($arg)*))
+    };
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let result = run().await;
+    if let Err(e) = &result {
+        if let Some(required) = e.downcast_ref::<InputRequired>() {
+            let error = serde_json::json!({ "error": "input_required", "required": required });
+            eprintln!("{error}");
+            std::process::exit(prompt::EXIT_INPUT_REQUIRED);
+        }
+    }
+    result
+}
+
+async fn run() -> Result<()> {
     // Initialize environment variables from .env file
     init_env()?;
 
     // Parse command line arguments
-    let cli = Cli::parse();
+    let cli = aliases::parse_with_aliases().await;
+    let _timing = cli.timing.then(metrics::enable);
+
+    // Resolve the data directory for the selected profile and upgrade its layout
+    let data_path = data_dir::init(cli.path.as_deref(), cli.profile.as_deref())?;
+    let storage_path = data_path.to_string_lossy().to_string();
+    audit::enable();
+    policy::install(Policy::load()?);
+    prompt::set_non_interactive(
+        cli.non_interactive
+            || std::env::var("CASTORIX_NON_INTERACTIVE").is_ok_and(|v| v == "1" || v == "true"),
+    );
+    http::install(HttpSettings::load()?)?;
+    finality::install(FinalitySettings::load(cli.wait_for.as_deref())?);
+    rate_limit::install(WriteRate::load(cli.write_rate)?);
+
+    // Output mode and message catalog apply to everything printed from here on
+    output::set_plain(cli.plain);
+    output::set_json_format(cli.fields.as_deref(), cli.compact)?;
+    if let Some(lang) = cli
+        .lang
+        .clone()
+        .or_else(|| std::env::var("CASTORIX_LANG").ok())
+    {
+        i18n::init(&lang)?;
+    }
+
+    // Validate per-command endpoint overrides before running anything
+    let mut endpoints = EndpointOverrides::new(cli.hub_url.clone(), cli.eth_rpc_url.clone());
+    if let Some(snapshot) = &cli.offline {
+        endpoints.use_offline_snapshot(snapshot).await?;
+    }
+    endpoints.validate().await?;
+
+    // Hub failover list and per-hub API keys, used by every hub client
+    hub_pool::set_verbose(cli.verbose);
+    if cli.offline.is_none() {
+        hub_pool::set_fallback_hubs(hub_pool::load_hubs_config()?);
+    }
 
     match cli.command {
         Commands::Key { action } => {
@@ -43,14 +132,16 @@ async fn main() -> Result<()> {
                 | KeyCommands::Import
                 | KeyCommands::Load { .. }
                 | KeyCommands::List
-                | KeyCommands::Delete { .. } => {
+                | KeyCommands::Delete { .. }
+                | KeyCommands::Audit { .. } => {
                     // These commands handle their own key management
                     CliHandler::handle_key_command(
                         action,
                         &KeyManager::from_private_key(
                             "0000000000000000000000000000000000000000000000000000000000000001",
                         )?,
-                        cli.path.as_deref(),
+                        Some(storage_path.as_str()),
+                        &endpoints,
                     )
                     .await?;
                 }
@@ -64,64 +155,210 @@ async fn main() -> Result<()> {
             }
         }
         Commands::Hub { action } => {
-            let hub_url = crate::consts::get_config().farcaster_hub_url().to_string();
+            let hub_url = endpoints.hub_url();
 
             // For read-only operations, we don't need a key manager
             match action {
                 HubCommands::User { .. }
                 | HubCommands::EthAddresses { .. }
                 | HubCommands::EnsDomains { .. }
+                | HubCommands::Proofs { .. }
                 | HubCommands::CustodyAddress { .. }
                 | HubCommands::Info
                 | HubCommands::Followers { .. }
                 | HubCommands::Following { .. }
+                | HubCommands::Export { .. }
                 | HubCommands::Profile { .. }
                 | HubCommands::Stats { .. }
+                | HubCommands::MessageCounts { .. }
                 | HubCommands::Spam { .. }
                 | HubCommands::SpamStat
-                | HubCommands::Casts { .. } => {
+                | HubCommands::Casts { .. }
+                | HubCommands::Reactions { .. }
+                | HubCommands::Thread { .. }
+                | HubCommands::CastStats { .. }
+                | HubCommands::TopCasts { .. }
+                | HubCommands::Link { .. }
+                | HubCommands::Mentions { .. }
+                | HubCommands::Compare { .. }
+                | HubCommands::SyncStatus { .. }
+                | HubCommands::VerificationsMap { .. } => {
                     let hub_client = FarcasterClient::read_only(hub_url);
                     CliHandler::handle_hub_command(action, &hub_client).await?;
                 }
-                HubCommands::SubmitProof { .. } => {
+                HubCommands::SubmitProof { .. }
+                | HubCommands::SetProfile { .. }
+                | HubCommands::Cast { .. }
+                | HubCommands::UnfollowInactive { .. } => {
                     // These commands handle their own key management
                     let hub_client = FarcasterClient::read_only(hub_url);
                     CliHandler::handle_hub_command(action, &hub_client).await?;
                 }
+                HubCommands::Key { action } => {
+                    let hub_client = FarcasterClient::read_only(hub_url);
+                    CliHandler::handle_hub_key_command(action, &hub_client, &endpoints).await?;
+                }
             }
         }
         Commands::Custody { action } => {
-            CliHandler::handle_custody_command(action).await?;
+            CliHandler::handle_custody_command(action, Some(storage_path.as_str()), &endpoints)
+                .await?;
         }
         Commands::Signers { action } => {
-            let hub_url = crate::consts::get_config().farcaster_hub_url().to_string();
-            let hub_client = FarcasterClient::read_only(hub_url);
-            CliHandler::handle_signers_command(action, &hub_client).await?;
+            let hub_client = FarcasterClient::read_only(endpoints.hub_url());
+            CliHandler::handle_signers_command(action, &hub_client, &endpoints).await?;
         }
         Commands::Ens { action } => {
-            let rpc_url = consts::get_config().eth_rpc_url().to_string();
+            let rpc_url = endpoints.eth_rpc_url();
 
             // Create a dummy key manager for ENS operations
             let dummy_key = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
             if let Ok(key_manager) = KeyManager::from_private_key(dummy_key) {
                 let ens_proof = EnsProof::new(key_manager, rpc_url);
-                CliHandler::handle_ens_command(action, &ens_proof).await?;
+                let hub_client = FarcasterClient::read_only(endpoints.hub_url());
+                CliHandler::handle_ens_command(action, &ens_proof, &hub_client).await?;
             } else {
                 println!("❌ Failed to create key manager for ENS operations");
             }
         }
         Commands::Fid { action } => {
-            CliHandler::handle_fid_command(action, cli.path.as_deref()).await?;
+            CliHandler::handle_fid_command(action, Some(storage_path.as_str()), &endpoints).await?;
         }
         Commands::Storage { action } => {
-            CliHandler::handle_storage_command(action, cli.path.as_deref()).await?;
+            CliHandler::handle_storage_command(action, Some(storage_path.as_str()), &endpoints)
+                .await?;
+        }
+        Commands::Tier { action } => {
+            CliHandler::handle_tier_command(action, Some(storage_path.as_str()), &endpoints)
+                .await?;
+        }
+        Commands::Tx { action } => {
+            CliHandler::handle_tx_command(action, Some(storage_path.as_str()), &endpoints).await?;
+        }
+        Commands::Indexer { action } => {
+            CliHandler::handle_indexer_command(action).await?;
         }
         Commands::Mcp { action } => {
-            let hub_url = consts::get_config().farcaster_hub_url().to_string();
-            CliHandler::handle_mcp_command(action, hub_url).await?;
+            CliHandler::handle_mcp_command(action, &endpoints).await?;
         }
         Commands::Api { action } => {
-            CliHandler::handle_api_command(action).await?;
+            CliHandler::handle_api_command(action, &endpoints).await?;
+        }
+        Commands::Bench { action } => {
+            CliHandler::handle_bench_command(action).await?;
+        }
+        #[cfg(feature = "dev")]
+        Commands::Dev { action } => {
+            CliHandler::handle_dev_command(action).await?;
+        }
+        Commands::Monitor {
+            target:
+                Some(MonitorCommands::Contracts {
+                    interval,
+                    webhook,
+                    max_retries,
+                }),
+            ..
+        } => {
+            CliHandler::handle_monitor_contracts_command(
+                webhook,
+                interval,
+                max_retries,
+                &endpoints,
+            )
+            .await?;
+        }
+        Commands::Monitor {
+            target: None,
+            fid,
+            webhook,
+            interval,
+            max_retries,
+        } => {
+            let fid = fid.ok_or_else(|| anyhow::anyhow!("--fid is required"))?;
+            let hub_client = FarcasterClient::read_only(endpoints.hub_url());
+            CliHandler::handle_monitor_command(fid, webhook, interval, max_retries, &hub_client)
+                .await?;
+        }
+        Commands::Autocast {
+            fid,
+            feed,
+            template,
+            interval,
+            max_per_poll,
+            embed_link,
+            post_existing,
+            once,
+            dry_run,
+        } => {
+            let hub_client = FarcasterClient::read_only(endpoints.hub_url());
+            let autocast = Autocast {
+                feed_url: feed,
+                template,
+                embed_link,
+            };
+            let schedule = Schedule {
+                interval,
+                max_per_poll,
+                post_existing,
+                once,
+                dry_run,
+            };
+            CliHandler::handle_autocast_command(fid, autocast, schedule, &hub_client).await?;
+        }
+        Commands::Share {
+            fid,
+            serve,
+            host,
+            port,
+            output,
+            casts,
+        } => {
+            let hub_client = FarcasterClient::read_only(endpoints.hub_url());
+            CliHandler::handle_share_command(fid, serve, host, port, output, casts, hub_client)
+                .await?;
+        }
+        Commands::Replay {
+            from,
+            to,
+            fid,
+            dry_run,
+        } => {
+            CliHandler::handle_replay_command(from, to, fid, dry_run).await?;
+        }
+        Commands::Alias { action } => {
+            CliHandler::handle_alias_command(action)?;
+        }
+        Commands::Context { action } => {
+            CliHandler::handle_context_command(action)?;
+        }
+        Commands::Audit { action } => {
+            CliHandler::handle_audit_command(action)?;
+        }
+        Commands::Channel { action } => {
+            CliHandler::handle_channel_command(action).await?;
+        }
+        Commands::Watchlist { action } => {
+            let hub_client = FarcasterClient::read_only(endpoints.hub_url());
+            CliHandler::handle_watchlist_command(action, &hub_client).await?;
+        }
+        Commands::SelfCmd { action } => {
+            CliHandler::handle_self_command(action).await?;
+        }
+        Commands::Util { action } => {
+            CliHandler::handle_util_command(action)?;
+        }
+        Commands::Plugin { action } => {
+            CliHandler::handle_plugin_command(action)?;
+        }
+        Commands::External(args) => {
+            let code = CliHandler::handle_external_command(
+                args,
+                &endpoints,
+                cli.profile.as_deref(),
+                cli.context.clone(),
+            )?;
+            std::process::exit(code);
         }
     }
 
@@ -0,0 +1,158 @@
+//! Per-call context for MCP tools: progress notifications and cancellation
+//!
+//! A `tools/call` request may carry `_meta.progressToken`; tools then report
+//! intermediate progress, which the server forwards to the client as
+//! `notifications/progress`. A client can give up on a call with
+//! `notifications/cancelled`, which trips the call's [`CancellationToken`].
+
+use std::sync::Arc;
+
+use serde_json::json;
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::watch;
+
+use crate::mcp::error::McpError;
+use crate::mcp::error::Result;
+use crate::mcp::types::JsonRpcNotification;
+
+/// Signal that a tool call should stop
+///
+/// Cheap to clone; clones observe the same cancellation.
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self {
+            sender: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    /// Cancel every holder of this token
+    pub fn cancel(&self) {
+        self.sender.send_replace(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    /// Wait until the token is cancelled
+    pub async fn cancelled(&self) {
+        let mut receiver = self.sender.subscribe();
+        // The sender lives as long as `self`, so this only returns once cancelled
+        let _ = receiver.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+/// Sends `notifications/progress` for one request
+#[derive(Debug, Clone)]
+pub struct ProgressReporter {
+    token: Value,
+    sink: UnboundedSender<String>,
+}
+
+impl ProgressReporter {
+    /// Reporter for the progress token a client sent, writing to `sink`
+    pub fn new(token: Value, sink: UnboundedSender<String>) -> Self {
+        Self { token, sink }
+    }
+
+    /// Send a progress notification; failures (client gone) are ignored
+    pub fn report(&self, progress: u64, total: Option<u64>, message: &str) {
+        let mut params = json!({
+            "progressToken": self.token,
+            "progress": progress,
+            "message": message,
+        });
+        if let Some(total) = total {
+            params["total"] = json!(total);
+        }
+        let notification = JsonRpcNotification::new("notifications/progress", params);
+        if let Ok(line) = serde_json::to_string(&notification) {
+            let _ = self.sink.send(line);
+        }
+    }
+}
+
+/// What a tool gets besides its arguments
+#[derive(Debug, Clone, Default)]
+pub struct CallContext {
+    pub cancellation: CancellationToken,
+    progress: Option<ProgressReporter>,
+}
+
+impl CallContext {
+    pub fn new(cancellation: CancellationToken, progress: Option<ProgressReporter>) -> Self {
+        Self {
+            cancellation,
+            progress,
+        }
+    }
+
+    /// Report progress if the client asked for it
+    ///
+    /// # Arguments
+    /// * `progress` - Work done so far, increasing with every report
+    /// * `total` - Total work, when known
+    /// * `message` - Human-readable status
+    pub fn report_progress(&self, progress: u64, total: Option<u64>, message: &str) {
+        if let Some(reporter) = &self.progress {
+            reporter.report(progress, total, message);
+        }
+    }
+
+    /// Fail with [`McpError::Cancelled`] once the call was cancelled
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.cancellation.is_cancelled() {
+            return Err(McpError::Cancelled);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cancel_wakes_waiters() {
+        let token = CancellationToken::new();
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+        assert!(!token.is_cancelled());
+        token.cancel();
+        waiter.await.unwrap();
+
+        let context = CallContext::new(token, None);
+        assert!(matches!(
+            context.check_cancelled(),
+            Err(McpError::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn test_progress_notification() {
+        let (sink, mut lines) = tokio::sync::mpsc::unbounded_channel();
+        let context = CallContext::new(
+            CancellationToken::new(),
+            Some(ProgressReporter::new(json!("t1"), sink)),
+        );
+        context.report_progress(200, Some(1000), "200 followers");
+        let notification: Value = serde_json::from_str(&lines.try_recv().unwrap()).unwrap();
+        assert_eq!(notification["method"], "notifications/progress");
+        assert_eq!(notification["params"]["progressToken"], "t1");
+        assert_eq!(notification["params"]["total"], 1000);
+    }
+}
@@ -37,6 +37,12 @@ pub enum McpError {
     #[error("RPC connection failed: {0}")]
     RpcConnectionFailed(String),
 
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error("Request cancelled")]
+    Cancelled,
+
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 
@@ -3,15 +3,20 @@
 //! This module implements the MCP server that exposes Castorix's
 //! Farcaster query capabilities to AI assistants like Claude.
 
+pub mod context;
 pub mod error;
+pub mod permissions;
 pub mod registry;
 pub mod server;
 pub mod tools;
 pub mod types;
 pub mod utils;
 
+pub use context::CallContext;
+pub use context::CancellationToken;
 pub use error::McpError;
 pub use error::Result;
+pub use permissions::ToolPermissions;
 pub use registry::ToolRegistry;
 pub use server::McpServer;
 pub use tools::create_contract_tools;
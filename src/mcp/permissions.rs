@@ -0,0 +1,270 @@
+//! Per-tool permissions for the MCP server
+//!
+//! Operators decide which tools an AI assistant may call and with what
+//! arguments. The configuration is a JSON file, by default
+//! `<data dir>/mcp.json`, loaded when the server starts:
+//!
+//! ```json
+//! {
+//!   "disabled": ["custody_list_local"],
+//!   "read_only": true,
+//!   "tools": {
+//!     "hub_check_spam": { "fids": { "max_items": 50 } },
+//!     "hub_get_followers": { "limit": { "max": 500 } }
+//!   }
+//! }
+//! ```
+//!
+//! `enabled`, when present, is an allowlist: tools not named in it are hidden.
+//! `read_only` hides every tool that declares it changes state (none of the
+//! built-in tools do yet). Disabled tools are left out of `tools/list` and
+//! calls to them are refused, as are calls whose arguments break a
+//! constraint.
+//!
+//! Constraints apply to the value a tool will actually use: an argument left
+//! out is checked at the default from the tool's input schema. A `max` also
+//! refuses 0, which the tools read as "no limit", and a numeric argument with
+//! a `max` but neither a value nor a default.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::mcp::error::McpError;
+use crate::mcp::error::Result;
+
+/// Limits on one argument of a tool
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParamConstraint {
+    /// Largest value of a numeric argument
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<u64>,
+    /// Most items of an array argument
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    /// The only values the argument may take
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<Vec<Value>>,
+}
+
+impl ParamConstraint {
+    /// Check an argument's effective value, `None` when it is neither given
+    /// nor defaulted
+    fn check(&self, param: &str, value: Option<&Value>) -> std::result::Result<(), String> {
+        let Some(value) = value else {
+            return match self.max {
+                Some(max) => Err(format!("'{param}' must be given, at most {max}")),
+                None => Ok(()),
+            };
+        };
+        if let Some(max) = self.max {
+            let n = value
+                .as_u64()
+                .ok_or_else(|| format!("'{param}' must be a whole number, not {value}"))?;
+            if n == 0 {
+                return Err(format!(
+                    "'{param}' is 0 (no limit), above the allowed maximum of {max}"
+                ));
+            }
+            if n > max {
+                return Err(format!(
+                    "'{param}' is {n}, above the allowed maximum of {max}"
+                ));
+            }
+        }
+        if let Some(max_items) = self.max_items {
+            let items = value
+                .as_array()
+                .ok_or_else(|| format!("'{param}' must be an array, not {value}"))?;
+            if items.len() > max_items {
+                return Err(format!(
+                    "'{param}' has {} items, above the allowed maximum of {max_items}",
+                    items.len()
+                ));
+            }
+        }
+        if let Some(allowed) = &self.allowed {
+            if !allowed.contains(value) {
+                return Err(format!("'{param}' may not be {value}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Which tools are exposed and how they may be called; the default allows
+/// everything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolPermissions {
+    /// Only these tools are exposed (absent: every tool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<BTreeSet<String>>,
+    /// Tools never exposed, even when listed in `enabled`
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub disabled: BTreeSet<String>,
+    /// Hide every tool that changes state
+    #[serde(default)]
+    pub read_only: bool,
+    /// Argument constraints, by tool name and then argument name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tools: BTreeMap<String, BTreeMap<String, ParamConstraint>>,
+}
+
+impl ToolPermissions {
+    /// Default location of the configuration file
+    pub fn default_path() -> std::path::PathBuf {
+        crate::core::utils::data_dir::data_dir().join("mcp.json")
+    }
+
+    /// Load permissions from a file
+    ///
+    /// # Arguments
+    /// * `path` - The configuration file
+    /// * `required` - Fail if the file is missing instead of allowing everything
+    pub fn load(path: &Path, required: bool) -> anyhow::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if !required && e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::default())
+            }
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid MCP configuration {}", path.display()))
+    }
+
+    /// Whether a tool is exposed at all
+    ///
+    /// # Arguments
+    /// * `name` - The tool name
+    /// * `modifies_state` - Whether the tool changes state
+    pub fn is_enabled(&self, name: &str, modifies_state: bool) -> bool {
+        if self.read_only && modifies_state {
+            return false;
+        }
+        if self.disabled.contains(name) {
+            return false;
+        }
+        self.enabled
+            .as_ref()
+            .is_none_or(|enabled| enabled.contains(name))
+    }
+
+    /// Check the arguments of a call against the tool's constraints
+    ///
+    /// # Arguments
+    /// * `name` - The tool name
+    /// * `arguments` - The call's arguments
+    /// * `properties` - The tool's input schema properties, whose `default`s
+    ///   stand in for arguments left out
+    pub fn check_arguments(&self, name: &str, arguments: &Value, properties: &Value) -> Result<()> {
+        let Some(constraints) = self.tools.get(name) else {
+            return Ok(());
+        };
+        for (param, constraint) in constraints {
+            let value = arguments.get(param).or_else(|| {
+                properties
+                    .get(param)
+                    .and_then(|schema| schema.get("default"))
+            });
+            constraint
+                .check(param, value)
+                .map_err(|reason| McpError::PermissionDenied(format!("{name}: {reason}")))?;
+        }
+        Ok(())
+    }
+
+    /// Tool names the configuration mentions that are not in `known`
+    ///
+    /// Usually a typo, which would otherwise silently leave a tool exposed.
+    pub fn unknown_tools<'a>(&'a self, known: &BTreeSet<String>) -> Vec<&'a str> {
+        self.enabled
+            .iter()
+            .flatten()
+            .chain(&self.disabled)
+            .chain(self.tools.keys())
+            .filter(|name| !known.contains(*name))
+            .map(String::as_str)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_default_allows_everything() {
+        let permissions = ToolPermissions::default();
+        assert!(permissions.is_enabled("hub_get_user", false));
+        assert!(permissions.is_enabled("anything", true));
+        assert!(permissions
+            .check_arguments("hub_check_spam", &json!({"fids": [1, 2, 3]}), &json!({}))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rules() {
+        let permissions: ToolPermissions = serde_json::from_value(json!({
+            "enabled": ["hub_check_spam", "hub_get_followers", "custody_list_local"],
+            "disabled": ["custody_list_local"],
+            "read_only": true,
+            "tools": {
+                "hub_check_spam": { "fids": { "max_items": 2 } },
+                "hub_get_followers": { "limit": { "max": 500 } }
+            }
+        }))
+        .unwrap();
+        assert!(permissions.is_enabled("hub_check_spam", false));
+        assert!(!permissions.is_enabled("hub_check_spam", true));
+        assert!(!permissions.is_enabled("custody_list_local", false));
+        assert!(!permissions.is_enabled("hub_get_user", false));
+
+        let none = json!({});
+        assert!(permissions
+            .check_arguments("hub_check_spam", &json!({"fids": [1, 2]}), &none)
+            .is_ok());
+        assert!(matches!(
+            permissions.check_arguments("hub_check_spam", &json!({"fids": [1, 2, 3]}), &none),
+            Err(McpError::PermissionDenied(_))
+        ));
+        assert!(permissions
+            .check_arguments("hub_check_spam", &json!({"fids": "1,2"}), &none)
+            .is_err());
+
+        let followers = json!({ "limit": { "type": "number", "default": 1000 } });
+        let check = |arguments: Value| {
+            permissions.check_arguments("hub_get_followers", &arguments, &followers)
+        };
+        assert!(check(json!({"fid": 1, "limit": 500})).is_ok());
+        assert!(check(json!({"fid": 1, "limit": 501})).is_err());
+        assert!(check(json!({"fid": 1})).is_err());
+        assert!(check(json!({"fid": 1, "limit": 0})).is_err());
+        assert!(check(json!({"fid": 1, "limit": "10"})).is_err());
+        assert!(check(json!({"fid": 1, "limit": 10.5})).is_err());
+        assert!(permissions
+            .check_arguments("hub_get_followers", &json!({"fid": 1}), &none)
+            .is_err());
+
+        let known = ["hub_check_spam", "hub_get_followers"]
+            .map(String::from)
+            .into();
+        assert_eq!(permissions.unknown_tools(&known), ["custody_list_local"]);
+    }
+
+    #[test]
+    fn test_unknown_fields_rejected() {
+        assert!(serde_json::from_value::<ToolPermissions>(json!({"disable": ["x"]})).is_err());
+    }
+}
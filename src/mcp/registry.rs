@@ -1,10 +1,17 @@
 //! MCP tool registry
 //!
-//! This module manages the registration and lookup of MCP tools.
+//! This module manages the registration and lookup of MCP tools, and
+//! enforces the operator's [`ToolPermissions`] when tools are listed and called.
 
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use serde_json::Value;
+
+use crate::mcp::error::McpError;
+use crate::mcp::error::Result;
+use crate::mcp::permissions::ToolPermissions;
 use crate::mcp::tools::McpTool;
 use crate::mcp::types::Tool;
 use crate::mcp::types::ToolsListResponse;
@@ -12,6 +19,7 @@ use crate::mcp::types::ToolsListResponse;
 /// Tool registry that manages all available MCP tools
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<Box<dyn McpTool>>>,
+    permissions: ToolPermissions,
 }
 
 impl ToolRegistry {
@@ -19,9 +27,46 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            permissions: ToolPermissions::default(),
         }
     }
 
+    /// Restrict which tools are exposed and how they may be called
+    pub fn set_permissions(&mut self, permissions: ToolPermissions) {
+        self.permissions = permissions;
+    }
+
+    /// Names of all registered tools, exposed or not
+    pub fn names(&self) -> BTreeSet<String> {
+        self.tools.keys().cloned().collect()
+    }
+
+    fn is_enabled(&self, tool: &dyn McpTool) -> bool {
+        self.permissions
+            .is_enabled(&tool.name(), tool.modifies_state())
+    }
+
+    /// Look up a tool for a call, enforcing permissions
+    ///
+    /// Disabled tools are reported as not found, like tools that do not exist.
+    ///
+    /// # Arguments
+    /// * `name` - The tool name
+    /// * `arguments` - The call's arguments, checked against the tool's constraints
+    ///   with the schema's defaults filled in
+    pub fn resolve(&self, name: &str, arguments: &Value) -> Result<Arc<Box<dyn McpTool>>> {
+        let tool = self
+            .get(name)
+            .filter(|tool| self.is_enabled(tool.as_ref().as_ref()))
+            .ok_or_else(|| McpError::ToolNotFound(name.to_string()))?;
+        self.permissions.check_arguments(
+            name,
+            arguments,
+            &tool.definition().input_schema.properties,
+        )?;
+        Ok(tool)
+    }
+
     /// Register a tool
     pub fn register(&mut self, tool: Box<dyn McpTool>) {
         let name = tool.name().to_string();
@@ -40,9 +85,14 @@ impl ToolRegistry {
         self.tools.get(name).cloned()
     }
 
-    /// Get all tool definitions
+    /// Get the definitions of all exposed tools
     pub fn list_tools(&self) -> ToolsListResponse {
-        let tools: Vec<Tool> = self.tools.values().map(|tool| tool.definition()).collect();
+        let tools: Vec<Tool> = self
+            .tools
+            .values()
+            .filter(|tool| self.is_enabled(tool.as_ref().as_ref()))
+            .map(|tool| tool.definition())
+            .collect();
 
         ToolsListResponse { tools }
     }
@@ -52,6 +102,14 @@ impl ToolRegistry {
         self.tools.len()
     }
 
+    /// Get the number of tools exposed under the current permissions
+    pub fn enabled_count(&self) -> usize {
+        self.tools
+            .values()
+            .filter(|tool| self.is_enabled(tool.as_ref().as_ref()))
+            .count()
+    }
+
     /// Check if a tool exists
     pub fn contains(&self, name: &str) -> bool {
         self.tools.contains_key(name)
@@ -71,7 +129,6 @@ mod tests {
     use serde_json::Value;
 
     use super::*;
-    use crate::mcp::error::Result;
     use crate::mcp::types::InputSchema;
 
     struct TestTool;
@@ -84,7 +141,7 @@ mod tests {
                 description: "A test tool".to_string(),
                 input_schema: InputSchema {
                     type_: "object".to_string(),
-                    properties: json!({}),
+                    properties: json!({ "limit": { "type": "number", "default": 20 } }),
                     required: vec![],
                 },
             }
@@ -93,6 +150,10 @@ mod tests {
         async fn execute(&self, _arguments: Value) -> Result<Value> {
             Ok(json!({"result": "success"}))
         }
+
+        fn modifies_state(&self) -> bool {
+            false
+        }
     }
 
     #[test]
@@ -115,4 +176,35 @@ mod tests {
         assert_eq!(list.tools.len(), 1);
         assert_eq!(list.tools[0].name, "test_tool");
     }
+
+    #[test]
+    fn test_registry_enforces_permissions() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(TestTool));
+        registry.set_permissions(
+            serde_json::from_value(json!({
+                "tools": { "test_tool": { "limit": { "max": 10 } } }
+            }))
+            .unwrap(),
+        );
+        assert!(registry.resolve("test_tool", &json!({"limit": 10})).is_ok());
+        assert!(matches!(
+            registry.resolve("test_tool", &json!({"limit": 11})),
+            Err(McpError::PermissionDenied(_))
+        ));
+        // The default applies when the limit is left out
+        assert!(matches!(
+            registry.resolve("test_tool", &json!({})),
+            Err(McpError::PermissionDenied(_))
+        ));
+
+        registry
+            .set_permissions(serde_json::from_value(json!({ "disabled": ["test_tool"] })).unwrap());
+        assert!(registry.list_tools().tools.is_empty());
+        assert_eq!(registry.enabled_count(), 0);
+        assert!(matches!(
+            registry.resolve("test_tool", &json!({})),
+            Err(McpError::ToolNotFound(_))
+        ));
+    }
 }
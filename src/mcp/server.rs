@@ -2,19 +2,34 @@
 //!
 //! This module implements the core MCP server that handles JSON-RPC requests
 //! and manages tool execution.
+//!
+//! Requests are handled concurrently, so a client can send a JSON-RPC batch
+//! (an array of requests, answered with an array of responses), receive
+//! `notifications/progress` while a tool runs, and cancel a running call with
+//! `notifications/cancelled`. Cancelled calls get no response.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::Mutex;
 
+use futures::future::join_all;
+use serde_json::Value;
 use tokio::io::AsyncBufReadExt;
 use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::RwLock;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 
+use crate::mcp::context::CallContext;
+use crate::mcp::context::CancellationToken;
+use crate::mcp::context::ProgressReporter;
 use crate::mcp::error::McpError;
 use crate::mcp::registry::ToolRegistry;
+use crate::mcp::types::CancelledParams;
 use crate::mcp::types::JsonRpcError;
 use crate::mcp::types::JsonRpcRequest;
 use crate::mcp::types::JsonRpcResponse;
@@ -22,8 +37,13 @@ use crate::mcp::types::ToolCallParams;
 use crate::mcp::types::ToolCallResponse;
 
 /// MCP server that handles JSON-RPC requests
+///
+/// Cheap to clone; clones share the registry and the calls in flight.
+#[derive(Clone)]
 pub struct McpServer {
     registry: Arc<RwLock<ToolRegistry>>,
+    /// Cancellation tokens of running tool calls, keyed by request id
+    in_flight: Arc<Mutex<HashMap<String, CancellationToken>>>,
 }
 
 impl McpServer {
@@ -31,20 +51,156 @@ impl McpServer {
     pub fn new(registry: ToolRegistry) -> Self {
         Self {
             registry: Arc::new(RwLock::new(registry)),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
     /// Handle a JSON-RPC request
     pub async fn handle_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        let id = request.id.clone();
+        self.handle_message(request, None)
+            .await
+            .unwrap_or_else(|| JsonRpcResponse::error(id, JsonRpcError::request_cancelled()))
+    }
+
+    /// Handle a JSON-RPC request or notification
+    ///
+    /// # Arguments
+    /// * `request` - The request
+    /// * `notifications` - Where to send progress notifications, if anywhere
+    ///
+    /// # Returns
+    /// * `Option<JsonRpcResponse>` - The response, or None for notifications
+    ///   and cancelled calls
+    pub async fn handle_message(
+        &self,
+        request: JsonRpcRequest,
+        notifications: Option<&UnboundedSender<String>>,
+    ) -> Option<JsonRpcResponse> {
         debug!("Handling request: method={}", request.method);
 
-        match request.method.as_str() {
-            "tools/list" => self.handle_tools_list(request.id).await,
-            "tools/call" => self.handle_tool_call(request.id, request.params).await,
-            "initialize" => self.handle_initialize(request.id).await,
-            _ => {
-                JsonRpcResponse::error(request.id, JsonRpcError::method_not_found(&request.method))
+        let response = match request.method.as_str() {
+            "tools/list" => self.handle_tools_list(request.id.clone()).await,
+            "tools/call" => {
+                let cancellation = CancellationToken::new();
+                let key = request.id.as_ref().map(Value::to_string);
+                if let Some(key) = &key {
+                    self.calls().insert(key.clone(), cancellation.clone());
+                }
+                let call = self.handle_tool_call(
+                    request.id.clone(),
+                    request.params,
+                    cancellation.clone(),
+                    notifications,
+                );
+                let response = tokio::select! {
+                    response = call => Some(response),
+                    _ = cancellation.cancelled() => None,
+                };
+                if let Some(key) = &key {
+                    self.calls().remove(key);
+                }
+                response?
+            }
+            "initialize" => self.handle_initialize(request.id.clone()).await,
+            "notifications/cancelled" => {
+                self.handle_cancelled(request.params);
+                return None;
+            }
+            method if method.starts_with("notifications/") => return None,
+            _ => JsonRpcResponse::error(
+                request.id.clone(),
+                JsonRpcError::method_not_found(&request.method),
+            ),
+        };
+        Some(response)
+    }
+
+    /// Handle one message of a stdio line: a request or a batch of them
+    ///
+    /// # Returns
+    /// * `Option<String>` - The serialized response(s), None when nothing is due
+    pub async fn handle_line(
+        &self,
+        line: &str,
+        notifications: &UnboundedSender<String>,
+    ) -> Option<String> {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(e) => {
+                error!("Failed to parse request: {}", e);
+                let response =
+                    JsonRpcResponse::error(None, JsonRpcError::parse_error(&e.to_string()));
+                return serde_json::to_string(&response).ok();
+            }
+        };
+
+        match message {
+            Value::Array(items) if items.is_empty() => {
+                let response =
+                    JsonRpcResponse::error(None, JsonRpcError::invalid_request("Empty batch"));
+                serde_json::to_string(&response).ok()
+            }
+            Value::Array(items) => {
+                let responses: Vec<JsonRpcResponse> = join_all(
+                    items
+                        .into_iter()
+                        .map(|item| self.handle_value(item, notifications)),
+                )
+                .await
+                .into_iter()
+                .flatten()
+                .collect();
+                // A batch of notifications gets no response at all
+                if responses.is_empty() {
+                    return None;
+                }
+                serde_json::to_string(&responses).ok()
+            }
+            message => {
+                let response = self.handle_value(message, notifications).await?;
+                serde_json::to_string(&response).ok()
+            }
+        }
+    }
+
+    async fn handle_value(
+        &self,
+        message: Value,
+        notifications: &UnboundedSender<String>,
+    ) -> Option<JsonRpcResponse> {
+        match serde_json::from_value::<JsonRpcRequest>(message) {
+            Ok(request) => {
+                let is_notification = request.id.is_none();
+                let response = self.handle_message(request, Some(notifications)).await;
+                // JSON-RPC notifications are never answered
+                response.filter(|_| !is_notification)
             }
+            Err(e) => Some(JsonRpcResponse::error(
+                None,
+                JsonRpcError::invalid_request(&e.to_string()),
+            )),
+        }
+    }
+
+    fn calls(&self) -> std::sync::MutexGuard<'_, HashMap<String, CancellationToken>> {
+        self.in_flight.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Handle notifications/cancelled by cancelling the call it names
+    fn handle_cancelled(&self, params: Option<Value>) {
+        let Some(params) = params.and_then(|p| serde_json::from_value::<CancelledParams>(p).ok())
+        else {
+            return;
+        };
+        // The call may already have finished; there is nothing to do then
+        if let Some(token) = self.calls().get(&params.request_id.to_string()) {
+            info!(
+                "Cancelling request {} ({})",
+                params.request_id,
+                params.reason.as_deref().unwrap_or("no reason given")
+            );
+            token.cancel();
         }
     }
 
@@ -55,7 +211,7 @@ impl McpServer {
 
         match serde_json::to_value(tools_list) {
             Ok(result) => {
-                info!("Listed {} tools", registry.count());
+                info!("Listed {} tools", registry.enabled_count());
                 JsonRpcResponse::success(id, result)
             }
             Err(e) => JsonRpcResponse::error(
@@ -70,6 +226,8 @@ impl McpServer {
         &self,
         id: Option<serde_json::Value>,
         params: Option<serde_json::Value>,
+        cancellation: CancellationToken,
+        notifications: Option<&UnboundedSender<String>>,
     ) -> JsonRpcResponse {
         let params = match params {
             Some(p) => p,
@@ -93,17 +251,34 @@ impl McpServer {
 
         debug!("Calling tool: {}", tool_params.name);
 
-        // Get the tool from registry
+        // Get the tool from registry, enforcing the operator's permissions
         let registry = self.registry.read().await;
-        let tool = match registry.get(&tool_params.name) {
-            Some(t) => t,
-            None => {
+        let tool = match registry.resolve(&tool_params.name, &tool_params.arguments) {
+            Ok(t) => t,
+            Err(McpError::PermissionDenied(reason)) => {
+                info!("Refused call to '{}': {}", tool_params.name, reason);
+                return JsonRpcResponse::error(
+                    id,
+                    JsonRpcError::unauthorized(&tool_params.name, &reason),
+                );
+            }
+            Err(_) => {
                 return JsonRpcResponse::error(id, JsonRpcError::tool_not_found(&tool_params.name))
             }
         };
 
+        let progress = tool_params
+            .meta
+            .and_then(|meta| meta.progress_token)
+            .zip(notifications)
+            .map(|(token, sink)| ProgressReporter::new(token, sink.clone()));
+        let context = CallContext::new(cancellation, progress);
+
         // Execute the tool
-        match tool.execute(tool_params.arguments).await {
+        match tool
+            .execute_with_context(tool_params.arguments, &context)
+            .await
+        {
             Ok(result) => {
                 info!("Tool '{}' executed successfully", tool_params.name);
 
@@ -156,10 +331,22 @@ impl McpServer {
         info!("Starting MCP server in stdio mode");
 
         let stdin = tokio::io::stdin();
-        let mut stdout = tokio::io::stdout();
         let mut reader = BufReader::new(stdin);
         let mut line = String::new();
 
+        // Responses and notifications from concurrent calls share one writer
+        let (sender, mut outgoing) = mpsc::unbounded_channel::<String>();
+        let writer = tokio::spawn(async move {
+            let mut stdout = tokio::io::stdout();
+            while let Some(json) = outgoing.recv().await {
+                debug!("Sending: {}", json);
+                stdout.write_all(json.as_bytes()).await?;
+                stdout.write_all(b"\n").await?;
+                stdout.flush().await?;
+            }
+            Ok::<(), std::io::Error>(())
+        });
+
         loop {
             line.clear();
             match reader.read_line(&mut line).await {
@@ -168,32 +355,20 @@ impl McpServer {
                     break;
                 }
                 Ok(_) => {
-                    let trimmed = line.trim();
+                    let trimmed = line.trim().to_string();
                     if trimmed.is_empty() {
                         continue;
                     }
 
                     debug!("Received: {}", trimmed);
 
-                    // Parse JSON-RPC request
-                    let request: JsonRpcRequest = match serde_json::from_str(trimmed) {
-                        Ok(req) => req,
-                        Err(e) => {
-                            error!("Failed to parse request: {}", e);
-                            let error_response = JsonRpcResponse::error(
-                                None,
-                                JsonRpcError::parse_error(&e.to_string()),
-                            );
-                            self.send_response(&mut stdout, &error_response).await?;
-                            continue;
+                    let server = self.clone();
+                    let sender = sender.clone();
+                    tokio::spawn(async move {
+                        if let Some(response) = server.handle_line(&trimmed, &sender).await {
+                            let _ = sender.send(response);
                         }
-                    };
-
-                    // Handle request
-                    let response = self.handle_request(request).await;
-
-                    // Send response
-                    self.send_response(&mut stdout, &response).await?;
+                    });
                 }
                 Err(e) => {
                     error!("Error reading from stdin: {}", e);
@@ -202,22 +377,11 @@ impl McpServer {
             }
         }
 
-        Ok(())
-    }
-
-    /// Send a JSON-RPC response to stdout
-    async fn send_response(
-        &self,
-        stdout: &mut tokio::io::Stdout,
-        response: &JsonRpcResponse,
-    ) -> Result<(), McpError> {
-        let json = serde_json::to_string(response)?;
-        debug!("Sending: {}", json);
-
-        stdout.write_all(json.as_bytes()).await?;
-        stdout.write_all(b"\n").await?;
-        stdout.flush().await?;
-
+        // Let calls still running finish and flush their responses
+        drop(sender);
+        writer
+            .await
+            .map_err(|e| McpError::InternalError(e.to_string()))??;
         Ok(())
     }
 }
@@ -280,4 +444,30 @@ mod tests {
         let result = response.result.unwrap();
         assert_eq!(result["serverInfo"]["name"], "castorix-mcp-server");
     }
+
+    #[tokio::test]
+    async fn test_batch_and_notifications() {
+        let server = McpServer::new(ToolRegistry::new());
+        let (sender, _notifications) = mpsc::unbounded_channel();
+
+        let batch = json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "tools/list"},
+            {"jsonrpc": "2.0", "method": "notifications/initialized"},
+            {"jsonrpc": "2.0", "id": 2, "method": "unknown/method"}
+        ]);
+        let responses = server
+            .handle_line(&batch.to_string(), &sender)
+            .await
+            .unwrap();
+        let responses: Vec<Value> = serde_json::from_str(&responses).unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], 1);
+        assert!(responses[1]["error"].is_object());
+
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/cancelled", "params": {"requestId": 9}});
+        assert!(server
+            .handle_line(&notification.to_string(), &sender)
+            .await
+            .is_none());
+    }
 }
@@ -3,6 +3,7 @@
 use async_trait::async_trait;
 use serde_json::Value;
 
+use crate::mcp::context::CallContext;
 use crate::mcp::error::Result;
 use crate::mcp::types::Tool;
 
@@ -15,11 +16,29 @@ pub trait McpTool: Send + Sync {
     /// Execute the tool with given arguments
     async fn execute(&self, arguments: Value) -> Result<Value>;
 
+    /// Execute the tool with progress reporting and cancellation
+    ///
+    /// Long-running tools override this to report progress and stop early;
+    /// the server cancels every other tool by dropping its future.
+    async fn execute_with_context(
+        &self,
+        arguments: Value,
+        _context: &CallContext,
+    ) -> Result<Value> {
+        self.execute(arguments).await
+    }
+
     /// Get the tool name
     fn name(&self) -> String {
         self.definition().name.clone()
     }
 
+    /// Whether the tool changes state (signs, submits or writes files)
+    ///
+    /// Such tools are hidden when the server runs read-only. There is no
+    /// default, so every tool has to say which it is.
+    fn modifies_state(&self) -> bool;
+
     /// Validate arguments before execution (optional)
     fn validate_arguments(&self, _arguments: &Value) -> Result<()> {
         Ok(())
@@ -80,6 +80,10 @@ impl McpTool for FidGetPriceTool {
             "currency": "ETH"
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -143,6 +147,10 @@ impl McpTool for StorageGetPriceTool {
             "currency": "ETH"
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -214,6 +222,10 @@ impl McpTool for FidCheckAddressTool {
             })),
         }
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -270,6 +282,10 @@ impl McpTool for StorageCheckUnitsTool {
             "note": "Total storage units rented across all FIDs"
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 /// Create all contract tools
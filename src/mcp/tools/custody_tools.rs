@@ -78,6 +78,10 @@ impl McpTool for CustodyListLocalTool {
             "count": keys.len()
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 /// Create all custody tools
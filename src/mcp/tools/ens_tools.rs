@@ -91,6 +91,10 @@ impl McpTool for EnsResolveDomainTool {
             "address": format!("{:?}", address)
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -157,6 +161,10 @@ impl McpTool for EnsCheckBaseSubdomainTool {
             ))),
         }
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -229,6 +237,10 @@ impl McpTool for EnsVerifyOwnershipTool {
             "owns_domain": owns_domain
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 /// Create all ENS tools
@@ -3,25 +3,30 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures::StreamExt;
 use lazy_static::lazy_static;
 use serde::Deserialize;
 use serde_json::json;
 use serde_json::Value;
 
 use crate::core::client::FarcasterClient;
+use crate::core::services::profile::DEFAULT_SAMPLE_PAGES;
+use crate::core::services::GraphService;
+use crate::core::services::LinkDirection;
+use crate::core::services::ProfileService;
+use crate::core::services::SpamService;
+use crate::mcp::context::CallContext;
 use crate::mcp::error::McpError;
 use crate::mcp::error::Result;
 use crate::mcp::tools::base::McpTool;
 use crate::mcp::types::InputSchema;
 use crate::mcp::types::Tool;
-use crate::mcp::utils::SpamChecker;
-use crate::mcp::utils::SpamStatus;
 
-// Lazy-load spam checker once
+// Lazy-load spam labels once
 lazy_static! {
-    static ref SPAM_CHECKER: Option<SpamChecker> = {
-        match SpamChecker::load() {
-            Ok(checker) => Some(checker),
+    static ref SPAM_SERVICE: Option<SpamService> = {
+        match SpamService::load() {
+            Ok(service) => Some(service),
             Err(e) => {
                 eprintln!("Warning: Failed to load spam checker: {}", e);
                 None
@@ -38,9 +43,50 @@ pub struct HubContext {
 impl HubContext {
     pub fn new(hub_url: String) -> Self {
         Self {
-            client: Arc::new(FarcasterClient::new(hub_url, None)),
+            client: Arc::new(FarcasterClient::read_only(hub_url)),
         }
     }
+
+    fn profiles(&self) -> ProfileService<'_> {
+        ProfileService::new(&self.client)
+    }
+
+    fn graph(&self) -> GraphService<'_> {
+        GraphService::new(&self.client)
+    }
+}
+
+/// Links reported between progress notifications (one hub page)
+const LINK_PROGRESS_INTERVAL: usize = 100;
+
+/// Collect up to `limit` links (0 for all) of a FID
+///
+/// Reports progress after every page and stops as soon as the call is cancelled.
+async fn collect_links(
+    hub: &HubContext,
+    direction: LinkDirection,
+    fid: u64,
+    limit: u32,
+    context: &CallContext,
+) -> Result<Value> {
+    let what = direction.name();
+    let total = (limit > 0).then_some(limit as u64);
+    let mut links = Vec::new();
+    let mut stream = std::pin::pin!(hub.graph().links_stream(direction, fid, limit));
+    while let Some(link) = stream.next().await {
+        context.check_cancelled()?;
+        let link = link
+            .map_err(|e| McpError::HubConnectionFailed(format!("Failed to get {}: {}", what, e)))?;
+        links.push(link);
+        if links.len() % LINK_PROGRESS_INTERVAL == 0 {
+            context.report_progress(
+                links.len() as u64,
+                total,
+                &format!("{} {} fetched", links.len(), what),
+            );
+        }
+    }
+    Ok(direction.to_json(fid, &links))
 }
 
 // ============================================================================
@@ -87,13 +133,17 @@ impl McpTool for HubGetUserTool {
 
         let user_data = self
             .context
-            .client
-            .get_user(args.fid)
+            .profiles()
+            .user(args.fid)
             .await
             .map_err(|e| McpError::HubConnectionFailed(format!("Failed to get user: {}", e)))?;
 
         serde_json::to_value(user_data).map_err(McpError::SerializationError)
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -145,25 +195,24 @@ impl McpTool for HubGetProfileTool {
         let args: GetProfileArgs = serde_json::from_value(arguments)
             .map_err(|e| McpError::InvalidArguments(format!("Invalid arguments: {}", e)))?;
 
-        // Get basic user data
-        let user_data =
-            self.context.client.get_user(args.fid).await.map_err(|e| {
-                McpError::HubConnectionFailed(format!("Failed to get profile: {}", e))
-            })?;
+        let profiles = self.context.profiles();
+        let profile = profiles
+            .profile(args.fid)
+            .await
+            .map_err(|e| McpError::HubConnectionFailed(format!("Failed to get profile: {}", e)))?;
 
-        let mut profile = serde_json::to_value(user_data).map_err(McpError::SerializationError)?;
+        let mut profile = serde_json::to_value(profile).map_err(McpError::SerializationError)?;
 
         if args.all {
             // Fetch additional profile data when 'all' is true
             if let Some(obj) = profile.as_object_mut() {
                 // Get verified Ethereum addresses
-                if let Ok(eth_addresses) = self.context.client.get_eth_addresses(args.fid).await {
+                if let Ok(eth_addresses) = profiles.eth_addresses(args.fid).await {
                     obj.insert("verified_addresses".to_string(), json!(eth_addresses));
                 }
 
                 // Get custody address
-                if let Ok(custody_address) = self.context.client.get_custody_address(args.fid).await
-                {
+                if let Ok(custody_address) = profiles.custody_address(args.fid).await {
                     obj.insert("custody_address".to_string(), json!(custody_address));
                 }
 
@@ -173,6 +222,10 @@ impl McpTool for HubGetProfileTool {
 
         Ok(profile)
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -217,23 +270,18 @@ impl McpTool for HubGetStatsTool {
         let args: GetStatsArgs = serde_json::from_value(arguments)
             .map_err(|e| McpError::InvalidArguments(format!("Invalid FID: {}", e)))?;
 
-        // Get user data which includes follower/following counts
-        let user_data =
-            self.context.client.get_user(args.fid).await.map_err(|e| {
-                McpError::HubConnectionFailed(format!("Failed to get stats: {}", e))
-            })?;
-
-        // Convert to Value and extract relevant stats
-        let user_value = serde_json::to_value(&user_data).map_err(McpError::SerializationError)?;
+        let stats = self
+            .context
+            .profiles()
+            .stats(args.fid, DEFAULT_SAMPLE_PAGES)
+            .await
+            .map_err(|e| McpError::HubConnectionFailed(format!("Failed to get stats: {}", e)))?;
 
-        // Build stats response
-        let stats = json!({
-            "fid": args.fid,
-            "follower_count": user_value.get("follower_count").unwrap_or(&json!(0)),
-            "following_count": user_value.get("following_count").unwrap_or(&json!(0)),
-        });
+        serde_json::to_value(stats).map_err(McpError::SerializationError)
+    }
 
-        Ok(stats)
+    fn modifies_state(&self) -> bool {
+        false
     }
 }
 
@@ -287,23 +335,26 @@ impl McpTool for HubGetFollowersTool {
     }
 
     async fn execute(&self, arguments: Value) -> Result<Value> {
+        self.execute_with_context(arguments, &CallContext::default())
+            .await
+    }
+
+    async fn execute_with_context(&self, arguments: Value, context: &CallContext) -> Result<Value> {
         let args: GetFollowersArgs = serde_json::from_value(arguments)
             .map_err(|e| McpError::InvalidArguments(format!("Invalid arguments: {}", e)))?;
 
-        let followers = self
-            .context
-            .client
-            .get_followers(args.fid, args.limit)
-            .await
-            .map_err(|e| {
-                McpError::HubConnectionFailed(format!("Failed to get followers: {}", e))
-            })?;
+        collect_links(
+            &self.context,
+            LinkDirection::Followers,
+            args.fid,
+            args.limit,
+            context,
+        )
+        .await
+    }
 
-        Ok(json!({
-            "fid": args.fid,
-            "followers": followers,
-            "count": followers.len()
-        }))
+    fn modifies_state(&self) -> bool {
+        false
     }
 }
 
@@ -353,23 +404,26 @@ impl McpTool for HubGetFollowingTool {
     }
 
     async fn execute(&self, arguments: Value) -> Result<Value> {
+        self.execute_with_context(arguments, &CallContext::default())
+            .await
+    }
+
+    async fn execute_with_context(&self, arguments: Value, context: &CallContext) -> Result<Value> {
         let args: GetFollowingArgs = serde_json::from_value(arguments)
             .map_err(|e| McpError::InvalidArguments(format!("Invalid arguments: {}", e)))?;
 
-        let following = self
-            .context
-            .client
-            .get_following(args.fid, args.limit)
-            .await
-            .map_err(|e| {
-                McpError::HubConnectionFailed(format!("Failed to get following: {}", e))
-            })?;
+        collect_links(
+            &self.context,
+            LinkDirection::Following,
+            args.fid,
+            args.limit,
+            context,
+        )
+        .await
+    }
 
-        Ok(json!({
-            "fid": args.fid,
-            "following": following,
-            "count": following.len()
-        }))
+    fn modifies_state(&self) -> bool {
+        false
     }
 }
 
@@ -417,8 +471,8 @@ impl McpTool for HubGetEthAddressesTool {
 
         let addresses = self
             .context
-            .client
-            .get_eth_addresses(args.fid)
+            .profiles()
+            .eth_addresses(args.fid)
             .await
             .map_err(|e| {
                 McpError::HubConnectionFailed(format!("Failed to get Ethereum addresses: {}", e))
@@ -430,6 +484,10 @@ impl McpTool for HubGetEthAddressesTool {
             "count": addresses.len()
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -476,8 +534,8 @@ impl McpTool for HubGetCustodyAddressTool {
 
         let custody_address = self
             .context
-            .client
-            .get_custody_address(args.fid)
+            .profiles()
+            .custody_address(args.fid)
             .await
             .map_err(|e| {
                 McpError::HubConnectionFailed(format!("Failed to get custody address: {}", e))
@@ -488,6 +546,10 @@ impl McpTool for HubGetCustodyAddressTool {
             "custody_address": custody_address
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -526,6 +588,10 @@ impl McpTool for HubGetInfoTool {
 
         Ok(hub_info)
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -570,42 +636,14 @@ impl McpTool for HubGetEnsDomainsTool {
         let args: GetEnsDomainsArgs = serde_json::from_value(arguments)
             .map_err(|e| McpError::InvalidArguments(format!("Invalid FID: {}", e)))?;
 
-        // Query username proofs from Hub API
-        let url = format!(
-            "{}/v1/userNameProofsByFid?fid={}",
-            self.context.client.hub_url(),
-            args.fid
-        );
-
-        let response = reqwest::get(&url)
+        let domains = self
+            .context
+            .profiles()
+            .ens_domains(args.fid)
             .await
-            .map_err(|e| McpError::HubConnectionFailed(format!("Failed to query Hub: {}", e)))?;
-
-        let status = response.status();
-        let response_text = response.text().await.map_err(|e| {
-            McpError::HubConnectionFailed(format!("Failed to read response: {}", e))
-        })?;
-
-        if !status.is_success() {
-            return Err(McpError::HubConnectionFailed(format!(
-                "Hub returned error {}: {}",
-                status, response_text
-            )));
-        }
-
-        let data: serde_json::Value = serde_json::from_str(&response_text).map_err(|e| {
-            McpError::HubConnectionFailed(format!("Failed to parse response: {}", e))
-        })?;
-
-        // Extract domain names from proofs
-        let mut domains = Vec::new();
-        if let Some(proofs) = data.get("proofs").and_then(|p| p.as_array()) {
-            for proof in proofs {
-                if let Some(name) = proof.get("name").and_then(|n| n.as_str()) {
-                    domains.push(name.to_string());
-                }
-            }
-        }
+            .map_err(|e| {
+                McpError::HubConnectionFailed(format!("Failed to get ENS domains: {}", e))
+            })?;
 
         Ok(json!({
             "fid": args.fid,
@@ -613,6 +651,10 @@ impl McpTool for HubGetEnsDomainsTool {
             "count": domains.len()
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -664,25 +706,15 @@ impl McpTool for HubCheckSpamTool {
         let args: CheckSpamArgs = serde_json::from_value(arguments)
             .map_err(|e| McpError::InvalidArguments(format!("Invalid arguments: {}", e)))?;
 
-        let checker = SPAM_CHECKER
+        let spam = SPAM_SERVICE
             .as_ref()
             .ok_or_else(|| McpError::InternalError("Spam checker not available".to_string()))?;
 
-        let mut results = Vec::new();
-        for fid in args.fids {
-            let status = checker.get_status(fid);
-            results.push(json!({
-                "fid": fid,
-                "is_spam": matches!(status, SpamStatus::Spam),
-                "status": match status {
-                    SpamStatus::Spam => "spam",
-                    SpamStatus::NotSpam => "not_spam",
-                    SpamStatus::Unknown => "unknown",
-                }
-            }));
-        }
+        Ok(json!({ "results": spam.check_many(&args.fids) }))
+    }
 
-        Ok(json!({ "results": results }))
+    fn modifies_state(&self) -> bool {
+        false
     }
 }
 
@@ -719,20 +751,24 @@ impl McpTool for HubGetSpamStatsTool {
     }
 
     async fn execute(&self, _arguments: Value) -> Result<Value> {
-        let checker = SPAM_CHECKER
+        let spam = SPAM_SERVICE
             .as_ref()
             .ok_or_else(|| McpError::InternalError("Spam checker not available".to_string()))?;
 
-        let stats = checker.get_stats();
+        let summary = spam.summary();
 
         Ok(json!({
-            "total_labels": stats.total_labels,
-            "unique_fids": stats.unique_fids,
-            "spam_count": stats.spam_count,
-            "non_spam_count": stats.non_spam_count,
-            "spam_percentage": format!("{:.2}%", stats.spam_percentage),
+            "total_labels": summary.total,
+            "spam_count": summary.spam,
+            "non_spam_count": summary.not_spam,
+            "unknown_count": summary.unknown,
+            "spam_percentage": format!("{:.2}%", summary.spam_percentage()),
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -801,6 +837,10 @@ impl McpTool for HubGetCastsTool {
             "count": casts.len()
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 /// Create all Hub tools
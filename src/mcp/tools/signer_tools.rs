@@ -95,6 +95,10 @@ impl McpTool for SignersListLocalTool {
             "count": keys.len()
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -152,6 +156,10 @@ impl McpTool for SignersGetInfoTool {
             "count": signers.len()
         }))
     }
+
+    fn modifies_state(&self) -> bool {
+        false
+    }
 }
 
 /// Create all signer tools
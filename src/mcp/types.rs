@@ -46,6 +46,24 @@ impl JsonRpcResponse {
     }
 }
 
+/// JSON-RPC 2.0 notification: a message that expects no response
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: Value,
+}
+
+impl JsonRpcNotification {
+    pub fn new(method: &str, params: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        }
+    }
+}
+
 /// JSON-RPC 2.0 error
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonRpcError {
@@ -78,6 +96,25 @@ pub struct InputSchema {
 pub struct ToolCallParams {
     pub name: String,
     pub arguments: Value,
+    #[serde(default, rename = "_meta")]
+    pub meta: Option<RequestMeta>,
+}
+
+/// `_meta` of a request
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RequestMeta {
+    /// Token to tag `notifications/progress` for this request with
+    #[serde(default, rename = "progressToken")]
+    pub progress_token: Option<Value>,
+}
+
+/// Parameters of `notifications/cancelled`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CancelledParams {
+    #[serde(rename = "requestId")]
+    pub request_id: Value,
+    #[serde(default)]
+    pub reason: Option<String>,
 }
 
 /// Tool call response
@@ -120,6 +157,7 @@ pub enum ErrorCode {
     MethodNotFound = -32601,
     InvalidParams = -32602,
     InternalError = -32603,
+    RequestCancelled = -32800,
 
     // MCP application errors
     ToolNotFound = -32000,
@@ -175,6 +213,14 @@ impl JsonRpcError {
         }
     }
 
+    pub fn request_cancelled() -> Self {
+        Self {
+            code: ErrorCode::RequestCancelled as i32,
+            message: "Request cancelled".to_string(),
+            data: None,
+        }
+    }
+
     pub fn tool_not_found(tool_name: &str) -> Self {
         Self {
             code: ErrorCode::ToolNotFound as i32,
@@ -205,6 +251,17 @@ impl JsonRpcError {
         }
     }
 
+    pub fn unauthorized(tool_name: &str, reason: &str) -> Self {
+        Self {
+            code: ErrorCode::Unauthorized as i32,
+            message: format!("Call to tool '{}' not permitted", tool_name),
+            data: Some(serde_json::json!({
+                "tool": tool_name,
+                "reason": reason
+            })),
+        }
+    }
+
     pub fn data_not_found(resource: &str) -> Self {
         Self {
             code: ErrorCode::DataNotFound as i32,
@@ -1,7 +1,10 @@
+#![cfg(all(feature = "contracts", feature = "dev"))]
+
 use std::str::FromStr;
 
 use anyhow::Result;
 use castorix::farcaster::contracts::contract_client::FarcasterContractClient;
+use castorix::farcaster::contracts::dev_faucet::DevFaucet;
 use castorix::farcaster::contracts::types::ContractAddresses;
 use castorix::farcaster::contracts::types::ContractResult;
 use ed25519_dalek::Signer as Ed25519Signer;
@@ -86,9 +89,13 @@ impl IntegrationTestClient {
                 wallet.address()
             );
 
-            // Use the same contract client's fund_wallet method which uses NonceManager
-            match contract_client
-                .fund_wallet(wallet.address(), ethers::utils::parse_ether("10.0")?)
+            // Use the dev faucet, which shares the contract client's NonceManager
+            match DevFaucet::anvil()
+                .fund(
+                    &contract_client,
+                    wallet.address(),
+                    ethers::utils::parse_ether("10.0")?,
+                )
                 .await
             {
                 Ok(tx_hash) => {
@@ -276,6 +283,7 @@ impl IntegrationTestClient {
         println!("🚀 Registering signer key...");
         let registration_result = test_contract_client
             .register_signer_key(
+                fid.as_u64(),
                 1, // Ed25519 key type
                 public_key_bytes.to_vec(),
                 1,                         // Ed25519 metadata type
@@ -1,3 +1,5 @@
+#![cfg(feature = "contracts")]
+
 use std::str::FromStr;
 
 use anyhow::Result;
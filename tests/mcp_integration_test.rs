@@ -1,5 +1,7 @@
 //! MCP integration tests
 
+#![cfg(feature = "mcp")]
+
 use std::sync::Arc;
 
 use castorix::mcp::create_custody_tools;
@@ -0,0 +1,52 @@
+//! Runs the CLI against recorded hub fixtures, without a network
+
+#![cfg(feature = "cli")]
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::process::Command;
+
+use castorix::core::client::MockHub;
+
+/// Start a mock hub on the fixtures in tests/fixtures/hub
+async fn start_mock_hub() -> String {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/hub");
+    let hub = MockHub::load(&fixtures).expect("fixtures should load");
+    assert!(!hub.is_empty(), "no fixtures in {}", fixtures.display());
+    let addr = hub
+        .serve(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .expect("mock hub should start");
+    format!("http://{addr}")
+}
+
+/// Run the castorix binary against a hub with a throwaway data directory
+fn run_castorix(hub_url: &str, args: &[&str]) -> String {
+    let data_dir = tempfile::tempdir().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_castorix"))
+        .arg("--path")
+        .arg(data_dir.path())
+        .args(["--hub-url", hub_url, "--plain"])
+        .args(args)
+        .output()
+        .expect("castorix should run");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cli_hub_queries_replay_fixtures() {
+    let hub_url = start_mock_hub().await;
+
+    let user = run_castorix(&hub_url, &["hub", "user", "3"]);
+    assert!(user.contains("dwr.eth"), "unexpected output:\n{user}");
+
+    let info = run_castorix(&hub_url, &["hub", "info"]);
+    assert!(
+        info.contains("\"numShards\": 2"),
+        "unexpected output:\n{info}"
+    );
+
+    // Requests without a fixture get a hub-style 404
+    let missing = run_castorix(&hub_url, &["hub", "user", "99999999"]);
+    assert!(missing.contains("404"), "unexpected output:\n{missing}");
+}
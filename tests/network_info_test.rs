@@ -1,3 +1,5 @@
+#![cfg(feature = "contracts")]
+
 use anyhow::Result;
 use castorix::farcaster::contracts::FarcasterContractClient;
 use ethers::providers::Http;
@@ -1,3 +1,5 @@
+#![cfg(feature = "contracts")]
+
 use std::sync::Arc;
 
 use anyhow::Result;